@@ -3,12 +3,12 @@
 use clap::{Parser, Subcommand};
 use iceberg_catalog::{
     api::management::v1::api_doc as v1_api_doc,
+    logging::init_logging,
     service::authz::{
         implementations::openfga::UnauthenticatedOpenFGAAuthorizer, AllowAllAuthorizer,
     },
     AuthZBackend, CONFIG,
 };
-use tracing_subscriber::{filter::LevelFilter, EnvFilter};
 
 mod healthcheck;
 mod serve;
@@ -101,18 +101,7 @@ enum Commands {
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    tracing_subscriber::fmt()
-        .json()
-        .flatten_event(true)
-        .with_current_span(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_env_filter(
-            EnvFilter::builder()
-                .with_default_directive(LevelFilter::INFO.into())
-                .from_env_lossy(),
-        )
-        .init();
+    init_logging(CONFIG.log_format)?;
 
     match cli.command {
         Some(Commands::WaitForDB {
@@ -132,7 +121,7 @@ async fn main() -> anyhow::Result<()> {
             println!("Authorizer migration complete.");
 
             println!("Migrating database...");
-            let write_pool = iceberg_catalog::implementations::postgres::get_writer_pool(
+            let write_pool = iceberg_catalog::implementations::postgres::get_migration_pool(
                 CONFIG
                     .to_pool_opts()
                     .acquire_timeout(std::time::Duration::from_secs(1)),
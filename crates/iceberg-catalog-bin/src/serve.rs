@@ -4,27 +4,42 @@ use anyhow::{anyhow, Error};
 #[cfg(feature = "ui")]
 use axum::routing::get;
 use iceberg_catalog::{
-    api::router::{new_full_router, serve as service_serve, RouterArgs},
+    api::{
+        router::{new_full_router, serve as service_serve, RouterArgs},
+        ApiContext,
+    },
+    catalog::{
+        idempotency::idempotency_key_prune_task, namespace_cleanup::namespace_cleanup_task,
+        snapshot_expiration::snapshot_expiration_task,
+    },
     implementations::{
         postgres::{
-            task_queues::{TabularExpirationQueue, TabularPurgeQueue},
+            task_queues::{
+                DeletedWarehousePurgeQueue, SnapshotCleanupQueue, StorageSecretCleanupQueue,
+                TabularExpirationQueue, TabularPurgeQueue,
+            },
             CatalogState, PostgresCatalog, ReadWrite,
         },
         Secrets,
     },
     service::{
+        audit::audit_log_task,
         authz::{
             implementations::{get_default_authorizer_from_config, Authorizers},
+            reconciler::authz_reconciler_task,
             Authorizer,
         },
+        commit_observer::{CommitAttributionObserver, CommitObserver, CommitObservers},
         contract_verification::ContractVerifiers,
+        create_request_mutator::CreateRequestMutators,
         event_publisher::{
             CloudEventBackend, CloudEventsPublisher, CloudEventsPublisherBackgroundTask, Message,
             NatsBackend, TracingPublisher,
         },
         health::ServiceHealthProvider,
+        secret_encryption::rewrap_warehouse_data_keys,
         task_queue::TaskQueues,
-        Catalog, StartupValidationData,
+        Catalog, StartupValidationData, State,
     },
     SecretBackend, CONFIG,
 };
@@ -93,6 +108,10 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
             .into()
         }
     };
+    let rewrapped = rewrap_warehouse_data_keys::<PostgresCatalog>(catalog_state.clone()).await?;
+    if rewrapped > 0 {
+        tracing::info!("Re-wrapped {rewrapped} warehouse data keys with the current master key");
+    }
     let authorizer = get_default_authorizer_from_config().await?;
 
     let health_provider = ServiceHealthProvider::new(
@@ -115,6 +134,18 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
             ReadWrite::from_pools(read_pool.clone(), write_pool.clone()),
             CONFIG.queue_config.clone(),
         )?),
+        Arc::new(SnapshotCleanupQueue::from_config(
+            ReadWrite::from_pools(read_pool.clone(), write_pool.clone()),
+            CONFIG.queue_config.clone(),
+        )?),
+        Arc::new(StorageSecretCleanupQueue::from_config(
+            ReadWrite::from_pools(read_pool.clone(), write_pool.clone()),
+            CONFIG.queue_config.clone(),
+        )?),
+        Arc::new(DeletedWarehousePurgeQueue::from_config(
+            ReadWrite::from_pools(read_pool.clone(), write_pool.clone()),
+            CONFIG.queue_config.clone(),
+        )?),
     );
 
     let listener = tokio::net::TcpListener::bind(bind_addr)
@@ -134,6 +165,8 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
             .await?
         }
         Authorizers::OpenFGA(a) => {
+            // Stores of projects are selected by the project of the authorized object
+            let a = a.with_project_resolver(Arc::new(catalog_state.clone()));
             serve_with_authn(
                 a,
                 catalog_state,
@@ -211,7 +244,11 @@ async fn serve_with_authn<A: Authorizer>(
         None
     };
 
-    if authn_k8s.is_none() && authn_oidc.is_none() {
+    if authn_k8s.is_none()
+        && authn_oidc.is_none()
+        && CONFIG.token_introspection.is_empty()
+        && CONFIG.client_certificate_authentication.is_none()
+    {
         tracing::warn!("Authentication is disabled. This is not suitable for production!");
     }
 
@@ -306,6 +343,9 @@ async fn serve_inner<A: Authorizer, N: Authenticator + 'static>(
                 ))
             })?;
 
+    let commit_attribution: Arc<dyn CommitObserver + Sync + Send> = Arc::new(
+        CommitAttributionObserver::<PostgresCatalog>::new(catalog_state.clone()),
+    );
     let router = new_full_router::<PostgresCatalog, _, Secrets, _>(RouterArgs {
         authenticator: authenticator.clone(),
         authorizer: authorizer.clone(),
@@ -314,6 +354,8 @@ async fn serve_inner<A: Authorizer, N: Authenticator + 'static>(
         queues: queues.clone(),
         publisher: CloudEventsPublisher::new(tx.clone()),
         table_change_checkers: ContractVerifiers::new(vec![]),
+        commit_observers: CommitObservers::new(vec![commit_attribution]),
+        create_request_mutators: CreateRequestMutators::new(vec![]),
         service_health_provider: health_provider,
         cors_origins: CONFIG.allow_origin.as_deref(),
         metrics_layer: Some(layer),
@@ -344,6 +386,60 @@ async fn serve_inner<A: Authorizer, N: Authenticator + 'static>(
         };
     });
 
+    if CONFIG.authz_reconciler.enabled {
+        tokio::task::spawn(authz_reconciler_task::<PostgresCatalog, _>(
+            catalog_state.clone(),
+            authorizer.clone(),
+            CONFIG.authz_reconciler.clone(),
+        ));
+    } else {
+        tracing::info!("Running without authorization reconciler.");
+    }
+
+    if CONFIG.namespace_cleanup.enabled {
+        tokio::task::spawn(namespace_cleanup_task::<PostgresCatalog, _>(
+            catalog_state.clone(),
+            authorizer.clone(),
+            CONFIG.namespace_cleanup.clone(),
+        ));
+    } else {
+        tracing::info!("Running without empty namespace cleanup.");
+    }
+
+    tokio::task::spawn(idempotency_key_prune_task::<PostgresCatalog>(
+        catalog_state.clone(),
+        CONFIG.idempotency_key_prune_interval,
+    ));
+
+    if CONFIG.audit_log.enabled {
+        tokio::task::spawn(audit_log_task::<PostgresCatalog>(
+            catalog_state.clone(),
+            CONFIG.audit_log.clone(),
+        ));
+    } else {
+        tracing::info!("Running without audit log.");
+    }
+
+    if CONFIG.snapshot_expiration.enabled {
+        tokio::task::spawn(snapshot_expiration_task::<PostgresCatalog, _, _>(
+            ApiContext {
+                v1_state: State {
+                    authz: authorizer.clone(),
+                    catalog: catalog_state.clone(),
+                    secrets: secrets_state.clone(),
+                    publisher: CloudEventsPublisher::new(tx.clone()),
+                    contract_verifiers: ContractVerifiers::new(vec![]),
+                    commit_observers: CommitObservers::new(vec![]),
+                    create_request_mutators: CreateRequestMutators::new(vec![]),
+                    queues: queues.clone(),
+                },
+            },
+            CONFIG.snapshot_expiration.clone(),
+        ));
+    } else {
+        tracing::info!("Running without snapshot expiration.");
+    }
+
     tokio::select!(
         _ = queues.spawn_queues::<PostgresCatalog, _, _>(catalog_state, secrets_state, authorizer) => tracing::error!("Tabular queue task failed"),
         err = service_serve(listener, router) => tracing::error!("Service failed: {err:?}"),
@@ -0,0 +1,3 @@
+-- Feature flags explicitly set on a warehouse. Flags that are not
+-- present fall back to the server-wide default.
+ALTER TABLE warehouse ADD COLUMN feature_flags jsonb NOT NULL DEFAULT '{}'::jsonb;
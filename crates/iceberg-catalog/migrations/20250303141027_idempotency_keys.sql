@@ -0,0 +1,14 @@
+-- Responses of create operations that were sent with an `Idempotency-Key` header.
+-- Keys are scoped to the warehouse and the principal that performed the request.
+CREATE TABLE idempotency_key (
+    warehouse_id uuid NOT NULL REFERENCES warehouse (warehouse_id) ON DELETE CASCADE,
+    principal text NOT NULL,
+    idempotency_key text NOT NULL,
+    operation text NOT NULL,
+    response jsonb NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    expires_at timestamptz NOT NULL,
+    PRIMARY KEY (warehouse_id, principal, idempotency_key)
+);
+
+CREATE INDEX idempotency_key_expires_at_idx ON idempotency_key (expires_at);
@@ -0,0 +1,9 @@
+-- Description of a table, kept in sync with the `comment` table property.
+ALTER TABLE "table" ADD COLUMN description text;
+
+UPDATE "table" t
+SET description = tp.value
+FROM table_properties tp
+WHERE tp.table_id = t.table_id AND tp.key = 'comment';
+
+CREATE INDEX table_description_gist_idx ON "table" USING gist (description gist_trgm_ops(siglen=256));
@@ -0,0 +1,3 @@
+-- Location schemes allowed for a warehouse. NULL allows all schemes
+-- supported by the warehouse's storage profile.
+ALTER TABLE warehouse ADD COLUMN allowed_schemes text[];
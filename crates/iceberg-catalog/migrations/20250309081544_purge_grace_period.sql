@@ -0,0 +1,7 @@
+-- Time to wait before the storage of dropped and purged tabulars is deleted.
+-- NULL deletes storage as soon as the purge task is picked up.
+ALTER TABLE warehouse
+    ADD COLUMN purge_grace_period_seconds bigint
+        CHECK (purge_grace_period_seconds >= 0);
+
+CREATE INDEX tabular_purges_warehouse_id_idx ON tabular_purges (warehouse_id);
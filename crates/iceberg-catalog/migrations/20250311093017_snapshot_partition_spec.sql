@@ -0,0 +1,3 @@
+-- Default partition spec of the table when the snapshot was added.
+-- NULL for snapshots added before this column existed.
+ALTER TABLE table_snapshot ADD COLUMN partition_spec_id int;
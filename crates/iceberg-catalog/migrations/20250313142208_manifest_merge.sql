@@ -0,0 +1,5 @@
+-- Maximum number of manifests of a committed snapshot before Lakekeeper
+-- merges small manifests. NULL disables merging.
+ALTER TABLE warehouse
+    ADD COLUMN manifest_merge_threshold int
+        CHECK (manifest_merge_threshold >= 1);
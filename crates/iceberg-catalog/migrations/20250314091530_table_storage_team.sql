@@ -0,0 +1 @@
+ALTER TABLE "table" ADD COLUMN storage_team text;
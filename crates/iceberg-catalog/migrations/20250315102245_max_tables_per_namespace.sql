@@ -0,0 +1,9 @@
+-- Maximum number of tables per namespace. NULL disables the limit.
+-- If max_tables_per_namespace_includes_views is set, views count towards
+-- the limit as well.
+ALTER TABLE warehouse
+    ADD COLUMN max_tables_per_namespace int
+        CHECK (max_tables_per_namespace >= 1),
+    ADD COLUMN max_tables_per_namespace_includes_views boolean NOT NULL DEFAULT false;
+
+CREATE INDEX tabular_namespace_id_typ_idx ON tabular (namespace_id, typ) WHERE deleted_at IS NULL;
@@ -0,0 +1,6 @@
+-- Storage prefixes that namespaces of a warehouse may use instead of the
+-- base location of the warehouse's storage profile.
+ALTER TABLE warehouse ADD COLUMN storage_prefix_overrides text[] NOT NULL DEFAULT '{}';
+-- Storage prefix of the namespace. Inherited by child namespaces without an
+-- own storage prefix.
+ALTER TABLE namespace ADD COLUMN storage_prefix text;
@@ -0,0 +1,4 @@
+-- Tables are listed by the time of their last change for change data capture.
+-- Tabulars that were never updated only carry created_at.
+CREATE INDEX tabular_changed_at_tabular_id_idx ON tabular ((COALESCE(updated_at, created_at)), tabular_id)
+    WHERE typ = 'table';
@@ -0,0 +1,4 @@
+-- Schema changes allowed on commit in addition to the rules of the Iceberg spec.
+-- Keys that are not set allow the respective change.
+ALTER TABLE warehouse
+    ADD COLUMN schema_evolution_policy jsonb NOT NULL DEFAULT '{}'::jsonb;
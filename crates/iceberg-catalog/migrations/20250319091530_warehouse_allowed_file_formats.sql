@@ -0,0 +1,3 @@
+-- File formats allowed for data and delete files of tables in a warehouse.
+-- NULL allows all formats.
+ALTER TABLE warehouse ADD COLUMN allowed_file_formats text[];
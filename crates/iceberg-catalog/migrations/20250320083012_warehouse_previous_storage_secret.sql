@@ -0,0 +1,6 @@
+-- Storage secret replaced by a credential rotation. It is retained until
+-- `previous_storage_secret_expires_at` so that requests that already
+-- resolved it keep working while credential vending switches to the new secret.
+ALTER TABLE warehouse
+    ADD COLUMN previous_storage_secret_id uuid,
+    ADD COLUMN previous_storage_secret_expires_at timestamptz;
@@ -0,0 +1,4 @@
+-- Constraints on namespace property values, keyed by property name.
+-- Properties without a constraint accept any value.
+ALTER TABLE warehouse
+    ADD COLUMN namespace_property_schema jsonb NOT NULL DEFAULT '{}'::jsonb;
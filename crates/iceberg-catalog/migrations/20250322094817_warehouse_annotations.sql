@@ -0,0 +1,3 @@
+-- Free-form operational metadata of a warehouse, such as the owning team.
+ALTER TABLE warehouse
+    ADD COLUMN annotations jsonb NOT NULL DEFAULT '{}'::jsonb;
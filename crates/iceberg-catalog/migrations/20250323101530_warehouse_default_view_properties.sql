@@ -0,0 +1,3 @@
+-- Properties applied to new views of a warehouse unless set on the namespace or the view.
+ALTER TABLE warehouse
+    ADD COLUMN default_view_properties jsonb NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,15 @@
+-- Authorization decisions recorded by the audit log.
+-- Records are pruned once they exceed the configured retention.
+CREATE TABLE audit_log (
+    id uuid PRIMARY KEY,
+    created_at timestamptz NOT NULL,
+    principal text,
+    action text NOT NULL,
+    object text NOT NULL,
+    allowed boolean NOT NULL,
+    request_id text NOT NULL
+);
+
+CREATE INDEX audit_log_created_at_idx ON audit_log (created_at, id);
+CREATE INDEX audit_log_principal_created_at_idx ON audit_log (principal, created_at, id);
+CREATE INDEX audit_log_object_idx ON audit_log (object text_pattern_ops);
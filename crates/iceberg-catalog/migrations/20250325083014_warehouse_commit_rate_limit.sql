@@ -0,0 +1,3 @@
+-- Maximum rate of commits to tables and views of a warehouse. NULL disables throttling.
+ALTER TABLE warehouse
+    ADD COLUMN commit_rate_limit jsonb;
@@ -0,0 +1,3 @@
+-- Groups of the principal resolved while authorizing the request.
+ALTER TABLE audit_log
+    ADD COLUMN groups jsonb NOT NULL DEFAULT '[]'::jsonb;
@@ -0,0 +1,9 @@
+-- Principal that committed a snapshot, shown in the history of the table.
+-- Only recorded for commits made by authenticated principals.
+CREATE TABLE table_commit_attribution (
+    table_id uuid NOT NULL REFERENCES "table" (table_id) ON DELETE CASCADE,
+    snapshot_id bigint NOT NULL,
+    principal text NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (table_id, snapshot_id)
+);
@@ -0,0 +1,4 @@
+-- Time before the expiry of vended credentials at which clients should refresh them.
+-- NULL uses the server default.
+ALTER TABLE warehouse
+    ADD COLUMN credential_refresh_before_seconds bigint CHECK (credential_refresh_before_seconds >= 0);
@@ -0,0 +1,3 @@
+-- Locations are compared without trailing slashes when searching for tables whose
+-- locations overlap.
+CREATE INDEX tabular_fs_location_trimmed_idx ON tabular ((TRIM(TRAILING '/' FROM fs_location)));
@@ -0,0 +1,13 @@
+-- Data keys used to encrypt the storage credentials of a warehouse before they are
+-- handed to the secret backend. Keys are stored wrapped with a server master key.
+CREATE TABLE warehouse_data_key (
+    warehouse_id uuid PRIMARY KEY REFERENCES warehouse (warehouse_id) ON DELETE CASCADE,
+    master_key_id text NOT NULL,
+    wrapped_key bytea NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX warehouse_data_key_master_key_id_idx ON warehouse_data_key (master_key_id);
+
+SELECT trigger_updated_at('warehouse_data_key');
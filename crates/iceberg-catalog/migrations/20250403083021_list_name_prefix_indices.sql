@@ -0,0 +1,8 @@
+-- Support listing tables, views and namespaces by name prefix via `LIKE 'prefix%'`.
+-- `text_pattern_ops` allows prefix matches independent of the database collation.
+CREATE INDEX tabular_namespace_id_name_pattern_idx ON tabular (namespace_id, name text_pattern_ops);
+
+CREATE INDEX namespace_warehouse_id_name_pattern_idx ON namespace (
+    warehouse_id,
+    (namespace_name[array_length(namespace_name, 1)]) text_pattern_ops
+);
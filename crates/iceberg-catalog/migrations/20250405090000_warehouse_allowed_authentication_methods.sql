@@ -0,0 +1,3 @@
+-- Authentication methods requests to a warehouse may use.
+-- NULL allows all methods.
+ALTER TABLE warehouse ADD COLUMN allowed_authentication_methods jsonb;
@@ -0,0 +1,11 @@
+-- Named templates for the creation of tables. Every change of a template is
+-- stored as a new version, tables keep the values of the version they were
+-- created from.
+CREATE TABLE table_template (
+    warehouse_id uuid NOT NULL REFERENCES warehouse (warehouse_id) ON DELETE CASCADE,
+    name text NOT NULL,
+    version integer NOT NULL,
+    template jsonb NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (warehouse_id, name, version)
+);
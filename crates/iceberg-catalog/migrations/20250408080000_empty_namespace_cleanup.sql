@@ -0,0 +1,24 @@
+-- Time after which empty namespaces of the warehouse are removed.
+-- NULL disables the cleanup.
+ALTER TABLE warehouse
+    ADD COLUMN empty_namespace_cleanup_after_seconds bigint CHECK (empty_namespace_cleanup_after_seconds >= 1);
+
+-- Time the last table or view was removed from the namespace, either by
+-- deleting it or by moving it to another namespace.
+ALTER TABLE namespace ADD COLUMN last_tabular_removed_at timestamptz;
+
+CREATE OR REPLACE FUNCTION record_namespace_tabular_removed() RETURNS trigger AS
+$$
+BEGIN
+    IF TG_OP = 'DELETE' OR OLD.namespace_id IS DISTINCT FROM NEW.namespace_id THEN
+        UPDATE namespace SET last_tabular_removed_at = now() WHERE namespace_id = OLD.namespace_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_namespace_tabular_removed
+    AFTER DELETE OR UPDATE OF namespace_id
+    ON tabular
+    FOR EACH ROW
+EXECUTE FUNCTION record_namespace_tabular_removed();
@@ -0,0 +1,3 @@
+-- Locations outside of the storage profile's base location from which tables
+-- may be registered. Tables registered from these locations are read-only.
+ALTER TABLE warehouse ADD COLUMN import_prefixes text[] NOT NULL DEFAULT '{}';
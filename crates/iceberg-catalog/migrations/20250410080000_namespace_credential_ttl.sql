@@ -0,0 +1,3 @@
+-- Default lifetime of credentials vended for tables of the namespace. Inherited
+-- by child namespaces without an own default.
+ALTER TABLE namespace ADD COLUMN credential_ttl_seconds bigint;
@@ -0,0 +1,3 @@
+-- Namespace that table operations target if they name the namespace via the
+-- configured alias. NULL disables the default namespace.
+ALTER TABLE warehouse ADD COLUMN default_namespace text[] CHECK (cardinality(default_namespace) >= 1);
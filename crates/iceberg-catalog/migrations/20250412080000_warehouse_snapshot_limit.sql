@@ -0,0 +1,3 @@
+-- Maximum number of snapshots per table, enforced on commit.
+-- NULL allows an unlimited number of snapshots.
+ALTER TABLE warehouse ADD COLUMN snapshot_limit jsonb;
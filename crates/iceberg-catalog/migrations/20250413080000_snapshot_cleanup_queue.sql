@@ -0,0 +1,14 @@
+-- Files of snapshots expired by the snapshot limit of a warehouse, deleted
+-- once the purge grace period of the warehouse has elapsed.
+create table snapshot_cleanups
+(
+    task_id      uuid primary key references task (task_id),
+    table_id     uuid  not null,
+    warehouse_id uuid  not null,
+    snapshots    jsonb not null
+);
+
+call add_time_columns('snapshot_cleanups');
+select trigger_updated_at('"snapshot_cleanups"');
+
+CREATE INDEX snapshot_cleanups_warehouse_id_idx ON snapshot_cleanups (warehouse_id);
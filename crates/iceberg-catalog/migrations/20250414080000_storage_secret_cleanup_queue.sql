@@ -0,0 +1,13 @@
+-- Storage secrets of warehouses replaced by a credential rotation, deleted
+-- once the overlap of the rotation has elapsed.
+create table storage_secret_cleanups
+(
+    task_id      uuid primary key references task (task_id),
+    warehouse_id uuid not null,
+    secret_id    uuid not null
+);
+
+call add_time_columns('storage_secret_cleanups');
+select trigger_updated_at('"storage_secret_cleanups"');
+
+CREATE INDEX storage_secret_cleanups_warehouse_id_idx ON storage_secret_cleanups (warehouse_id);
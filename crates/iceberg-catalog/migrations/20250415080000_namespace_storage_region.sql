@@ -0,0 +1,3 @@
+-- Region of the bucket of the storage prefix of the namespace, if it differs
+-- from the region of the warehouse's storage profile.
+ALTER TABLE namespace ADD COLUMN storage_region text;
@@ -0,0 +1,13 @@
+-- Tables registered from an import prefix of the warehouse reject all commits.
+-- The flag was previously kept in the property `lakekeeper.read-only`, which is
+-- missing from the metadata file the table points to.
+ALTER TABLE "table" ADD COLUMN read_only boolean NOT NULL DEFAULT false;
+
+UPDATE "table" t
+SET read_only = true
+FROM table_properties p
+WHERE p.table_id = t.table_id
+  AND p.key = 'lakekeeper.read-only'
+  AND lower(p.value) = 'true';
+
+DELETE FROM table_properties WHERE key = 'lakekeeper.read-only';
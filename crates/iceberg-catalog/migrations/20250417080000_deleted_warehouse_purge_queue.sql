@@ -0,0 +1,15 @@
+-- Data of force-deleted warehouses. The storage profile is kept with the task
+-- as the warehouse no longer exists when the data is purged.
+create table deleted_warehouse_purges
+(
+    task_id           uuid primary key references task (task_id),
+    warehouse_id      uuid   not null,
+    storage_profile   jsonb  not null,
+    storage_secret_id uuid,
+    locations         text[] not null
+);
+
+call add_time_columns('deleted_warehouse_purges');
+select trigger_updated_at('"deleted_warehouse_purges"');
+
+CREATE INDEX deleted_warehouse_purges_warehouse_id_idx ON deleted_warehouse_purges (warehouse_id);
@@ -0,0 +1,128 @@
+use axum::body::HttpBody;
+use http::header;
+use tower_http::compression::{
+    predicate::{NotForContentType, Predicate},
+    CompressionLayer,
+};
+
+use crate::config::ResponseCompressionConfig;
+
+/// Compress responses with gzip, deflate, brotli or zstd, depending on the
+/// `Accept-Encoding` header of the request.
+///
+/// Responses smaller than `min_size_bytes` are sent uncompressed, as the
+/// overhead outweighs the savings for small payloads.
+pub(crate) fn response_compression_layer(
+    config: &ResponseCompressionConfig,
+) -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().compress_when(
+        MinSize(config.min_size_bytes)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES)
+            .and(NotForContentType::SSE),
+    )
+}
+
+/// Like [`tower_http::compression::predicate::SizeAbove`], which is limited to `u16`.
+/// Responses of unknown size are compressed.
+#[derive(Debug, Clone, Copy)]
+struct MinSize(u64);
+
+impl Predicate for MinSize {
+    fn should_compress<B>(&self, response: &http::Response<B>) -> bool
+    where
+        B: HttpBody,
+    {
+        response
+            .body()
+            .size_hint()
+            .exact()
+            .or_else(|| {
+                response
+                    .headers()
+                    .get(header::CONTENT_LENGTH)
+                    .and_then(|hv| hv.to_str().ok())
+                    .and_then(|hv| hv.parse().ok())
+            })
+            .map_or(true, |size| size >= self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use axum::{body::Body, routing::get, Json, Router};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn router() -> Router {
+        let properties = |n: usize| {
+            (0..n)
+                .map(|i| (format!("property-{i}"), format!("value-{i}")))
+                .collect::<std::collections::HashMap<_, _>>()
+        };
+        Router::new()
+            .route("/large", get(move || async move { Json(properties(5000)) }))
+            .route("/small", get(move || async move { Json(properties(1)) }))
+            .layer(response_compression_layer(&ResponseCompressionConfig {
+                enabled: true,
+                // Above `u16::MAX`
+                min_size_bytes: 100_000,
+            }))
+    }
+
+    async fn get_with_encoding(uri: &str, encoding: &str) -> axum::response::Response {
+        router()
+            .oneshot(
+                http::Request::builder()
+                    .uri(uri)
+                    .header(header::ACCEPT_ENCODING, encoding)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_large_response_is_compressed() {
+        let response = get_with_encoding("/large", "gzip").await;
+        assert_eq!(
+            response.headers().get(header::CONTENT_ENCODING).unwrap(),
+            "gzip"
+        );
+        let compressed = response.collect().await.unwrap().to_bytes();
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(compressed.as_ref())
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        let properties: std::collections::HashMap<String, String> =
+            serde_json::from_str(&decompressed).unwrap();
+        assert_eq!(properties.len(), 5000);
+        assert!(compressed.len() < decompressed.len());
+    }
+
+    #[tokio::test]
+    async fn test_all_encodings_are_negotiated() {
+        for encoding in ["gzip", "deflate", "br", "zstd"] {
+            let response = get_with_encoding("/large", encoding).await;
+            assert_eq!(
+                response.headers().get(header::CONTENT_ENCODING).unwrap(),
+                encoding
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_small_response_is_not_compressed() {
+        let response = get_with_encoding("/small", "gzip").await;
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        let body = response.collect().await.unwrap().to_bytes();
+        let properties: std::collections::HashMap<String, String> =
+            serde_json::from_slice(&body).unwrap();
+        assert_eq!(properties.len(), 1);
+    }
+}
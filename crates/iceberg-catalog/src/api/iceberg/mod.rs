@@ -20,7 +20,7 @@ pub mod v1 {
 
     pub use self::{
         namespace::{ListNamespacesQuery, NamespaceParameters, PaginationQuery},
-        tables::{DataAccess, ListTablesQuery, TableParameters},
+        tables::{DataAccess, ListTablesQuery, LoadTableQuery, TableParameters},
         views::ViewParameters,
     };
     pub use crate::{
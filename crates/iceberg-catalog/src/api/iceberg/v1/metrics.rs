@@ -3,10 +3,10 @@ use axum::{
     extract::{Path, State},
     response::IntoResponse,
     routing::post,
-    Extension, Json, Router,
+    Extension, Router,
 };
 use http::StatusCode;
-use iceberg_ext::TableIdent;
+use iceberg_ext::{catalog::rest::encoding::Json, TableIdent};
 
 use super::namespace::NamespaceIdentUrl;
 use crate::{
@@ -5,13 +5,13 @@ use axum::{
     extract::{Path, Query, State},
     response::IntoResponse,
     routing::{get, post},
-    Extension, Json, Router,
+    Extension, Router,
 };
 use http::StatusCode;
 use iceberg::NamespaceIdent;
 use iceberg_ext::catalog::rest::{
-    CreateNamespaceRequest, CreateNamespaceResponse, GetNamespaceResponse, ListNamespacesResponse,
-    UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
+    encoding::Json, CreateNamespaceRequest, CreateNamespaceResponse, GetNamespaceResponse,
+    ListNamespacesResponse, UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
 };
 use serde::{Deserialize, Deserializer, Serialize};
 
@@ -32,6 +32,7 @@ where
     /// If table accounting.tax.paid.info exists, using 'SELECT NAMESPACE IN accounting'
     /// would translate into `GET /namespaces?parent=accounting` and must return a namespace,
     /// ["accounting", "tax"] only. Using 'SELECT NAMESPACE IN accounting.tax' would translate into `GET /namespaces?parent=accounting%1Ftax` and must return a namespace, ["accounting", "tax", "paid"]. If `parent` is not provided, all top-level namespaces should be listed.
+    /// Only the identifiers of namespaces are returned, their properties are neither loaded nor serialized.
     async fn list_namespaces(
         prefix: Option<Prefix>,
         query: ListNamespacesQuery,
@@ -63,9 +64,11 @@ where
         request_metadata: RequestMetadata,
     ) -> Result<()>;
 
-    /// Drop a namespace from the catalog. Namespace must be empty.
+    /// Drop a namespace from the catalog. Namespace must be empty unless
+    /// `recursive` is set.
     async fn drop_namespace(
         parameters: NamespaceParameters,
+        query: DropNamespaceQuery,
         state: ApiContext<S>,
         request_metadata: RequestMetadata,
     ) -> Result<()>;
@@ -204,9 +207,10 @@ pub fn router<I: Service<S>, S: crate::api::ThreadSafe>() -> Router<ApiContext<S
                     .map(|()| StatusCode::NO_CONTENT.into_response())
                 },
             )
-            // Drop a namespace from the catalog. Namespace must be empty.
+            // Drop a namespace from the catalog. Namespace must be empty unless recursive.
             .delete(
                 |Path((prefix, namespace)): Path<(Prefix, NamespaceIdentUrl)>,
+                 Query(query): Query<DropNamespaceQuery>,
                  State(api_context): State<ApiContext<S>>,
                  Extension(metadata): Extension<RequestMetadata>| async {
                     I::drop_namespace(
@@ -214,6 +218,7 @@ pub fn router<I: Service<S>, S: crate::api::ThreadSafe>() -> Router<ApiContext<S
                             prefix: Some(prefix),
                             namespace: namespace.into(),
                         },
+                        query,
                         api_context,
                         metadata,
                     )
@@ -267,6 +272,9 @@ pub struct ListNamespacesQuery {
     /// Default is false.
     #[serde(default)]
     pub return_uuids: bool,
+    /// Only list namespaces whose last name part starts with this prefix. Case-sensitive.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub name_prefix: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -278,6 +286,15 @@ pub struct GetNamespacePropertiesQuery {
     pub return_uuid: bool,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DropNamespaceQuery {
+    /// Drop all child namespaces, tables and views as well.
+    /// Default is false.
+    #[serde(default)]
+    pub recursive: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, utoipa::IntoParams)]
 #[serde(rename_all = "camelCase")]
 pub struct PaginationQuery {
@@ -406,6 +423,7 @@ mod tests {
             /// Drop a namespace from the catalog. Namespace must be empty.
             async fn drop_namespace(
                 _parameters: NamespaceParameters,
+                _query: DropNamespaceQuery,
                 _state: ApiContext<ThisState>,
                 _request_metadata: RequestMetadata,
             ) -> Result<()> {
@@ -510,6 +528,7 @@ mod tests {
             /// Drop a namespace from the catalog. Namespace must be empty.
             async fn drop_namespace(
                 _parameters: NamespaceParameters,
+                _query: DropNamespaceQuery,
                 _state: ApiContext<ThisState>,
                 _request_metadata: RequestMetadata,
             ) -> Result<()> {
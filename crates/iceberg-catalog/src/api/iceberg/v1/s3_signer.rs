@@ -2,9 +2,9 @@ use async_trait::async_trait;
 use axum::{
     extract::{Path, State},
     routing::post,
-    Extension, Json, Router,
+    Extension, Router,
 };
-use iceberg_ext::catalog::rest::{S3SignRequest, S3SignResponse};
+use iceberg_ext::catalog::rest::{encoding::Json, S3SignRequest, S3SignResponse};
 
 use super::{ApiContext, Prefix, Result};
 use crate::request_metadata::RequestMetadata;
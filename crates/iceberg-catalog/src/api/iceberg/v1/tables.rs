@@ -3,11 +3,11 @@ use axum::{
     extract::{Path, Query, State},
     response::IntoResponse,
     routing::{get, post},
-    Extension, Json, Router,
+    Extension, Router,
 };
 use http::{HeaderMap, StatusCode};
 use iceberg::TableIdent;
-use iceberg_ext::catalog::rest::LoadCredentialsResponse;
+use iceberg_ext::catalog::rest::{encoding::Json, LoadCredentialsResponse};
 
 use super::{PageToken, PaginationQuery};
 use crate::{
@@ -36,6 +36,9 @@ pub struct ListTablesQuery {
     /// Default is false.
     #[serde(default)]
     pub return_uuids: bool,
+    /// Only list entries whose name starts with this prefix. Case-sensitive.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub name_prefix: Option<String>,
 }
 
 impl From<ListTablesQuery> for PaginationQuery {
@@ -47,6 +50,25 @@ impl From<ListTablesQuery> for PaginationQuery {
     }
 }
 
+/// Parts of the table metadata returned by `load_table`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoadTableFields {
+    /// The complete table metadata, including all historical schemas and snapshots.
+    #[default]
+    Full,
+    /// Only the current schema, partition spec, sort order and snapshot.
+    Minimal,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadTableQuery {
+    /// Parts of the table metadata to return. Default is `full`.
+    #[serde(default)]
+    pub fields: LoadTableFields,
+}
+
 #[async_trait]
 pub trait TablesService<S: crate::api::ThreadSafe>
 where
@@ -80,6 +102,7 @@ where
     /// Load a table from the catalog
     async fn load_table(
         parameters: TableParameters,
+        query: LoadTableQuery,
         data_access: DataAccess,
         state: ApiContext<S>,
         request_metadata: RequestMetadata,
@@ -203,6 +226,7 @@ pub fn router<I: TablesService<S>, S: crate::api::ThreadSafe>() -> Router<ApiCon
             // Load a table from the catalog
             get(
                 |Path((prefix, namespace, table)): Path<(Prefix, NamespaceIdentUrl, String)>,
+                 Query(query): Query<LoadTableQuery>,
                  State(api_context): State<ApiContext<S>>,
                  headers: HeaderMap,
                  Extension(metadata): Extension<RequestMetadata>| {
@@ -214,6 +238,7 @@ pub fn router<I: TablesService<S>, S: crate::api::ThreadSafe>() -> Router<ApiCon
                                 name: table,
                             },
                         },
+                        query,
                         parse_data_access(&headers),
                         api_context,
                         metadata,
@@ -3,10 +3,11 @@ use axum::{
     extract::{Path, Query, State},
     response::IntoResponse,
     routing::{get, post},
-    Extension, Json, Router,
+    Extension, Router,
 };
 use http::{HeaderMap, StatusCode};
 use iceberg::TableIdent;
+use iceberg_ext::catalog::rest::encoding::Json;
 
 use super::ListTablesQuery;
 use crate::{
@@ -0,0 +1,669 @@
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_prometheus::metrics;
+use http::{header, HeaderValue, StatusCode};
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+
+use crate::{
+    api::{ErrorModel, IcebergErrorResponse},
+    config::{LoadSheddingConfig, RequestPriority},
+    WarehouseIdent,
+};
+
+/// Header via which clients request a priority: `low`, `normal` or `high`.
+pub const PRIORITY_HEADER: &str = "x-lakekeeper-priority";
+
+const IN_FLIGHT_REQUESTS_METRIC: &str = "lakekeeper_in_flight_requests";
+const SHED_REQUESTS_METRIC: &str = "lakekeeper_shed_requests_total";
+
+/// Most warehouses whose in-flight requests are tracked at the same time.
+const MAX_TRACKED_WAREHOUSES: u64 = 10_000;
+/// Warehouses without requests for this long are no longer tracked.
+const TRACKED_WAREHOUSE_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Limits the number of requests processed concurrently, globally and per
+/// warehouse. Requests beyond the warehouse limit are rejected right away
+/// instead of waiting for a slot, so that accepted requests keep their latency.
+/// Requests beyond the global limit wait in a bounded queue if configured.
+#[derive(Debug, Clone)]
+pub(crate) struct LoadShedder {
+    global: Option<Arc<AdmissionQueue>>,
+    per_warehouse_limit: Option<usize>,
+    // One semaphore per warehouse that recently received requests. The warehouse is taken
+    // from the path before authentication, so the number of tracked warehouses is bounded
+    // and idle warehouses are evicted. Permits of an evicted semaphore stay valid.
+    per_warehouse: moka::sync::Cache<WarehouseIdent, Arc<Semaphore>>,
+    max_requested_priority: RequestPriority,
+    retry_after: HeaderValue,
+}
+
+/// Slots of the global limit. Requests that find no free slot wait in a bounded
+/// queue and are admitted by priority, in order of arrival within a priority.
+#[derive(Debug)]
+struct AdmissionQueue {
+    state: Mutex<QueueState>,
+    max_queued: usize,
+    max_wait: Duration,
+}
+
+#[derive(Debug)]
+struct QueueState {
+    available: usize,
+    next_ticket: u64,
+    waiting: BinaryHeap<Waiter>,
+}
+
+#[derive(Debug)]
+struct Waiter {
+    priority: RequestPriority,
+    ticket: u64,
+    admit: oneshot::Sender<()>,
+}
+
+impl Ord for Waiter {
+    // The heap pops the highest priority first, and the oldest ticket within a priority.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.ticket.cmp(&self.ticket))
+    }
+}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+/// Slot of the global limit. Handed to the next waiting request when dropped.
+struct AdmissionPermit {
+    queue: Arc<AdmissionQueue>,
+}
+
+impl Drop for AdmissionPermit {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+/// A request waiting in the queue. Leaves the queue when dropped, for example
+/// because the wait timed out or the client disconnected.
+struct QueuedRequest<'a> {
+    queue: &'a AdmissionQueue,
+    ticket: u64,
+    admission: oneshot::Receiver<()>,
+    /// Set once the slot handed to the request is owned by a permit.
+    admitted: bool,
+}
+
+impl Drop for QueuedRequest<'_> {
+    fn drop(&mut self) {
+        if self.admitted {
+            return;
+        }
+        let mut state = self.queue.lock();
+        let queued = state.waiting.len();
+        state.waiting.retain(|w| w.ticket != self.ticket);
+        if state.waiting.len() == queued && self.admission.try_recv().is_ok() {
+            // Admitted after giving up waiting: Pass the slot on.
+            drop(state);
+            self.queue.release();
+        }
+    }
+}
+
+impl AdmissionQueue {
+    fn new(limit: usize, max_queued: usize, max_wait: Duration) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                available: limit,
+                next_ticket: 0,
+                waiting: BinaryHeap::new(),
+            }),
+            max_queued,
+            max_wait,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Take a free slot or wait in the queue for one. `None` if the queue is
+    /// full or no slot was handed to the request within the maximum wait.
+    async fn acquire(self: &Arc<Self>, priority: RequestPriority) -> Option<AdmissionPermit> {
+        let mut queued = {
+            let mut state = self.lock();
+            if state.available > 0 {
+                state.available -= 1;
+                return Some(AdmissionPermit {
+                    queue: self.clone(),
+                });
+            }
+            if state.waiting.len() >= self.max_queued {
+                return None;
+            }
+            let (admit, admission) = oneshot::channel();
+            let ticket = state.next_ticket;
+            state.next_ticket += 1;
+            state.waiting.push(Waiter {
+                priority,
+                ticket,
+                admit,
+            });
+            QueuedRequest {
+                queue: self,
+                ticket,
+                admission,
+                admitted: false,
+            }
+        };
+
+        match tokio::time::timeout(self.max_wait, &mut queued.admission).await {
+            Ok(Ok(())) => {
+                queued.admitted = true;
+                Some(AdmissionPermit {
+                    queue: self.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Hand a slot to the next waiting request, or free it if none waits.
+    fn release(&self) {
+        let mut state = self.lock();
+        while let Some(waiter) = state.waiting.pop() {
+            if waiter.admit.send(()).is_ok() {
+                return;
+            }
+        }
+        state.available += 1;
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum LimitScope {
+    Global,
+    Warehouse,
+}
+
+impl LimitScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            LimitScope::Global => "global",
+            LimitScope::Warehouse => "warehouse",
+        }
+    }
+}
+
+/// Slots held by a request until its response is produced.
+struct Permits {
+    _global: Option<AdmissionPermit>,
+    _warehouse: Option<OwnedSemaphorePermit>,
+}
+
+/// Tracks a request in the in-flight gauge. Also decrements the gauge if the
+/// request is cancelled because the client disconnected.
+struct InFlightGuard;
+
+impl InFlightGuard {
+    fn new() -> Self {
+        metrics::gauge!(IN_FLIGHT_REQUESTS_METRIC).increment(1.0);
+        Self
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        metrics::gauge!(IN_FLIGHT_REQUESTS_METRIC).decrement(1.0);
+    }
+}
+
+impl LoadShedder {
+    pub(crate) fn new(config: &LoadSheddingConfig) -> Self {
+        Self {
+            global: config.max_in_flight_requests.map(|limit| {
+                Arc::new(AdmissionQueue::new(
+                    limit,
+                    config.max_queued_requests,
+                    config.max_queue_wait,
+                ))
+            }),
+            per_warehouse_limit: config.max_in_flight_requests_per_warehouse,
+            per_warehouse: moka::sync::Cache::builder()
+                .max_capacity(MAX_TRACKED_WAREHOUSES)
+                .time_to_idle(TRACKED_WAREHOUSE_IDLE_TIMEOUT)
+                .build(),
+            max_requested_priority: config.max_requested_priority,
+            retry_after: HeaderValue::from(config.retry_after_seconds),
+        }
+    }
+
+    /// Priority requested via the [`PRIORITY_HEADER`], lowered to the configured
+    /// maximum. Without a valid header, reading requests are `normal` and writing
+    /// requests, such as commits, `low`.
+    fn priority(&self, request: &Request) -> RequestPriority {
+        let requested = request
+            .headers()
+            .get(PRIORITY_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<RequestPriority>().ok());
+        match requested {
+            Some(priority) => priority.min(self.max_requested_priority),
+            None if request.method().is_safe() => RequestPriority::Normal,
+            None => RequestPriority::Low,
+        }
+    }
+
+    async fn acquire(
+        &self,
+        warehouse_id: Option<WarehouseIdent>,
+        priority: RequestPriority,
+    ) -> Result<Permits, LimitScope> {
+        // Requests beyond the warehouse limit are rejected before they can occupy the queue.
+        let warehouse = match (warehouse_id, self.per_warehouse_limit) {
+            (Some(warehouse_id), Some(limit)) => {
+                let semaphore = self
+                    .per_warehouse
+                    .get_with(warehouse_id, || Arc::new(Semaphore::new(limit)));
+                Some(
+                    semaphore
+                        .try_acquire_owned()
+                        .map_err(|_| LimitScope::Warehouse)?,
+                )
+            }
+            _ => None,
+        };
+
+        let global = match &self.global {
+            Some(queue) => Some(queue.acquire(priority).await.ok_or(LimitScope::Global)?),
+            None => None,
+        };
+
+        Ok(Permits {
+            _global: global,
+            _warehouse: warehouse,
+        })
+    }
+
+    fn shed_response(&self, scope: LimitScope) -> Response {
+        let message = match scope {
+            LimitScope::Global => "Server is processing too many requests. Retry later.",
+            LimitScope::Warehouse => {
+                "Server is processing too many requests for this warehouse. Retry later."
+            }
+        };
+        let mut response = IcebergErrorResponse::from(ErrorModel::new(
+            message,
+            "TooManyRequestsInFlight",
+            StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+            None,
+        ))
+        .into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, self.retry_after.clone());
+        response
+    }
+}
+
+/// Reject requests with `503 Service Unavailable` and a `Retry-After` header
+/// while the configured number of requests is already in flight and, for the
+/// global limit, the queue is full or the request waited too long.
+pub(crate) async fn load_shedding_fn(
+    State(shedder): State<LoadShedder>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let warehouse_id = warehouse_id_from_path(request.uri().path());
+    let priority = shedder.priority(&request);
+    let _permits = match shedder.acquire(warehouse_id, priority).await {
+        Ok(permits) => permits,
+        Err(scope) => {
+            metrics::counter!(SHED_REQUESTS_METRIC, "scope" => scope.as_str()).increment(1);
+            return shedder.shed_response(scope);
+        }
+    };
+    let _in_flight = InFlightGuard::new();
+
+    next.run(request).await
+}
+
+/// Warehouse addressed by the request, taken from the prefix of catalog
+/// endpoints or the path of warehouse management and permission endpoints.
+pub(crate) fn warehouse_id_from_path(path: &str) -> Option<WarehouseIdent> {
+    let segment = path
+        .strip_prefix("/catalog/v1/")
+        .or_else(|| path.strip_prefix("/management/v1/warehouse/"))
+        .or_else(|| path.strip_prefix("/management/v1/permissions/warehouse/"))?
+        .split('/')
+        .next()?;
+    uuid::Uuid::parse_str(segment)
+        .ok()
+        .map(WarehouseIdent::from)
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{body::Body, routing::get, Router};
+    use tokio::sync::Notify;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const WAREHOUSE_1: &str = "/catalog/v1/0195a2a4-1d8f-7b42-9d5e-0c6f5c3c1a01/namespaces";
+    const WAREHOUSE_2: &str = "/catalog/v1/0195a2a4-1d8f-7b42-9d5e-0c6f5c3c1a02/namespaces";
+
+    /// Requests to `/slow` block until `release` is notified.
+    fn router(config: &LoadSheddingConfig, started: Arc<Notify>, release: Arc<Notify>) -> Router {
+        shedder_router(LoadShedder::new(config), started, release)
+    }
+
+    fn shedder_router(shedder: LoadShedder, started: Arc<Notify>, release: Arc<Notify>) -> Router {
+        let handler = move || {
+            let started = started.clone();
+            let release = release.clone();
+            async move {
+                let released = release.notified();
+                tokio::pin!(released);
+                released.as_mut().enable();
+                started.notify_one();
+                released.await;
+            }
+        };
+        let slow = get(handler.clone()).post(handler);
+        Router::new()
+            .route("/slow", slow.clone())
+            .route("/catalog/v1/{prefix}/namespaces", slow)
+            .layer(axum::middleware::from_fn_with_state(
+                shedder,
+                load_shedding_fn,
+            ))
+            .route("/health", get(|| async {}))
+    }
+
+    async fn send(router: &Router, uri: &str) -> Response {
+        send_request(
+            router,
+            http::Request::builder()
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+    }
+
+    async fn send_request(router: &Router, request: http::Request<Body>) -> Response {
+        router.clone().oneshot(request).await.unwrap()
+    }
+
+    /// Priorities of the requests waiting in the global queue, next admitted first.
+    fn queued_priorities(shedder: &LoadShedder) -> Vec<RequestPriority> {
+        let state = shedder.global.as_ref().unwrap().lock();
+        let mut waiting = state.waiting.iter().collect::<Vec<_>>();
+        waiting.sort_unstable_by(|a, b| b.cmp(a));
+        waiting.into_iter().map(|w| w.priority).collect()
+    }
+
+    async fn wait_until_queued(shedder: &LoadShedder, count: usize) {
+        while queued_priorities(shedder).len() < count {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_second_request_is_shed_while_first_proceeds() {
+        let started = Arc::new(Notify::new());
+        let release = Arc::new(Notify::new());
+        let router = router(
+            &LoadSheddingConfig {
+                max_in_flight_requests: Some(1),
+                ..Default::default()
+            },
+            started.clone(),
+            release.clone(),
+        );
+
+        let first = tokio::spawn({
+            let router = router.clone();
+            async move { send(&router, "/slow").await }
+        });
+        started.notified().await;
+
+        let second = send(&router, "/slow").await;
+        assert_eq!(second.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(second.headers().get(header::RETRY_AFTER).unwrap(), "1");
+        assert_eq!(send(&router, "/health").await.status(), StatusCode::OK);
+
+        release.notify_one();
+        assert_eq!(first.await.unwrap().status(), StatusCode::OK);
+
+        // The slot is free again once the first request completed
+        let third = tokio::spawn({
+            let router = router.clone();
+            async move { send(&router, "/slow").await }
+        });
+        started.notified().await;
+        release.notify_one();
+        assert_eq!(third.await.unwrap().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_requests_are_shed_per_warehouse() {
+        let started = Arc::new(Notify::new());
+        let release = Arc::new(Notify::new());
+        let router = router(
+            &LoadSheddingConfig {
+                max_in_flight_requests_per_warehouse: Some(1),
+                ..Default::default()
+            },
+            started.clone(),
+            release.clone(),
+        );
+
+        let first = tokio::spawn({
+            let router = router.clone();
+            async move { send(&router, WAREHOUSE_1).await }
+        });
+        started.notified().await;
+
+        assert_eq!(
+            send(&router, WAREHOUSE_1).await.status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        let other_warehouse = tokio::spawn({
+            let router = router.clone();
+            async move { send(&router, WAREHOUSE_2).await }
+        });
+        started.notified().await;
+
+        release.notify_waiters();
+        assert_eq!(first.await.unwrap().status(), StatusCode::OK);
+        assert_eq!(other_warehouse.await.unwrap().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_tracked_warehouses_are_bounded() {
+        let shedder = LoadShedder::new(&LoadSheddingConfig {
+            max_in_flight_requests_per_warehouse: Some(1),
+            ..Default::default()
+        });
+        for _ in 0..=MAX_TRACKED_WAREHOUSES {
+            let warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+            shedder
+                .acquire(Some(warehouse_id), RequestPriority::Normal)
+                .await
+                .unwrap();
+        }
+        shedder.per_warehouse.run_pending_tasks();
+        assert!(shedder.per_warehouse.entry_count() <= MAX_TRACKED_WAREHOUSES);
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_request_is_admitted_before_queued_batch_request() {
+        let started = Arc::new(Notify::new());
+        let release = Arc::new(Notify::new());
+        let shedder = LoadShedder::new(&LoadSheddingConfig {
+            max_in_flight_requests: Some(1),
+            max_queued_requests: 2,
+            max_queue_wait: Duration::from_secs(60),
+            max_requested_priority: RequestPriority::High,
+            ..Default::default()
+        });
+        let router = shedder_router(shedder.clone(), started.clone(), release.clone());
+
+        let first = tokio::spawn({
+            let router = router.clone();
+            async move { send(&router, "/slow").await }
+        });
+        started.notified().await;
+
+        // Commits and other writing requests are queued with low priority
+        let batch = tokio::spawn({
+            let router = router.clone();
+            async move {
+                let request = http::Request::post("/slow").body(Body::empty()).unwrap();
+                send_request(&router, request).await
+            }
+        });
+        wait_until_queued(&shedder, 1).await;
+        let load = tokio::spawn({
+            let router = router.clone();
+            async move {
+                let request = http::Request::get("/slow")
+                    .header(PRIORITY_HEADER, "high")
+                    .body(Body::empty())
+                    .unwrap();
+                send_request(&router, request).await
+            }
+        });
+        wait_until_queued(&shedder, 2).await;
+        assert_eq!(
+            queued_priorities(&shedder),
+            vec![RequestPriority::High, RequestPriority::Low]
+        );
+
+        // The queue is full
+        let shed = send(&router, "/slow").await;
+        assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // The slot of the first request goes to the load, although the batch request waits longer
+        release.notify_one();
+        assert_eq!(first.await.unwrap().status(), StatusCode::OK);
+        started.notified().await;
+        assert_eq!(queued_priorities(&shedder), vec![RequestPriority::Low]);
+
+        release.notify_one();
+        assert_eq!(load.await.unwrap().status(), StatusCode::OK);
+        started.notified().await;
+        release.notify_one();
+        assert_eq!(batch.await.unwrap().status(), StatusCode::OK);
+        assert!(queued_priorities(&shedder).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queued_request_is_shed_after_max_wait() {
+        let started = Arc::new(Notify::new());
+        let release = Arc::new(Notify::new());
+        let shedder = LoadShedder::new(&LoadSheddingConfig {
+            max_in_flight_requests: Some(1),
+            max_queued_requests: 1,
+            max_queue_wait: Duration::from_millis(10),
+            ..Default::default()
+        });
+        let router = shedder_router(shedder.clone(), started.clone(), release.clone());
+
+        let first = tokio::spawn({
+            let router = router.clone();
+            async move { send(&router, "/slow").await }
+        });
+        started.notified().await;
+
+        let second = send(&router, "/slow").await;
+        assert_eq!(second.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(queued_priorities(&shedder).is_empty());
+
+        release.notify_one();
+        assert_eq!(first.await.unwrap().status(), StatusCode::OK);
+        assert_eq!(shedder.global.as_ref().unwrap().lock().available, 1);
+    }
+
+    #[test]
+    fn test_requested_priority_is_capped() {
+        let shedder = LoadShedder::new(&LoadSheddingConfig::default());
+        let priority = |method: http::Method, requested: Option<&str>| {
+            let mut request = http::Request::builder().method(method).uri("/slow");
+            if let Some(requested) = requested {
+                request = request.header(PRIORITY_HEADER, requested);
+            }
+            shedder.priority(&request.body(Body::empty()).unwrap())
+        };
+
+        assert_eq!(priority(http::Method::GET, None), RequestPriority::Normal);
+        assert_eq!(priority(http::Method::POST, None), RequestPriority::Low);
+        assert_eq!(
+            priority(http::Method::POST, Some("normal")),
+            RequestPriority::Normal
+        );
+        assert_eq!(
+            priority(http::Method::GET, Some("low")),
+            RequestPriority::Low
+        );
+        // Clients cannot elevate their requests beyond the configured maximum
+        assert_eq!(
+            priority(http::Method::GET, Some("High")),
+            RequestPriority::Normal
+        );
+        assert_eq!(
+            priority(http::Method::POST, Some("urgent")),
+            RequestPriority::Low
+        );
+    }
+
+    #[test]
+    fn test_warehouse_id_from_path() {
+        let warehouse_id = "0195a2a4-1d8f-7b42-9d5e-0c6f5c3c1a01";
+        for path in [
+            format!("/catalog/v1/{warehouse_id}/namespaces"),
+            format!("/catalog/v1/{warehouse_id}/v1/aws/s3/sign"),
+            format!("/management/v1/warehouse/{warehouse_id}"),
+            format!("/management/v1/warehouse/{warehouse_id}/statistics"),
+            format!("/management/v1/permissions/warehouse/{warehouse_id}/assignments"),
+        ] {
+            assert_eq!(
+                warehouse_id_from_path(&path),
+                Some(warehouse_id.parse().unwrap()),
+                "{path}"
+            );
+        }
+        for path in [
+            "/catalog/v1/config",
+            "/catalog/v1/aws/s3/sign",
+            "/management/v1/warehouse",
+            "/management/v1/project",
+            "/health",
+        ] {
+            assert_eq!(warehouse_id_from_path(path), None, "{path}");
+        }
+    }
+}
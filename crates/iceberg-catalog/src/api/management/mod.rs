@@ -1,21 +1,25 @@
 pub mod v1 {
+    pub mod audit;
     pub mod bootstrap;
     pub mod project;
     pub mod role;
+    pub mod table;
+    pub mod table_template;
     pub mod user;
     pub mod warehouse;
 
     use std::marker::PhantomData;
 
+    use audit::{ListAuditLogQuery, ListAuditLogResponse, Service as _};
     use axum::{
         extract::{Path, Query, State as AxumState},
         response::{IntoResponse, Response},
         routing::{get, post},
-        Extension, Json, Router,
+        Extension, Router,
     };
     use bootstrap::{BootstrapRequest, ServerInfo, Service as _};
     use http::StatusCode;
-    use iceberg_ext::catalog::rest::ErrorModel;
+    use iceberg_ext::catalog::rest::{encoding::Json, ErrorModel, LoadTableResult};
     use project::{
         CreateProjectRequest, CreateProjectResponse, GetProjectResponse, ListProjectsResponse,
         RenameProjectRequest, Service as _,
@@ -25,17 +29,43 @@ pub mod v1 {
         SearchRoleResponse, Service as _, UpdateRoleRequest,
     };
     use serde::{Deserialize, Serialize};
+    use table::{
+        DropTablesRequest, DropTablesResponse, ImportTableRequest, LoadTableAtQuery,
+        RenameTablesRequest, ResolveTableIdResponse, Service as _, SetTableDeprecationRequest,
+        SetTableDescriptionRequest, TableExportBundle, TableFilesQuery, TableFilesResponse,
+        TableHistoryQuery, TableHistoryResponse, TableLocksResponse, TableStorageFootprint,
+        UpdateNamespaceTablePropertiesRequest, UpdateNamespaceTablePropertiesResponse,
+    };
+    use table_template::{
+        GetTableTemplateQuery, Service as _, TableTemplate, TableTemplateResponse,
+    };
     use user::{
         CreateUserRequest, SearchUserRequest, SearchUserResponse, Service as _, UpdateUserRequest,
         User,
     };
     use utoipa::{openapi::security::SecurityScheme, OpenApi};
     use warehouse::{
-        CreateWarehouseRequest, CreateWarehouseResponse, GetWarehouseResponse,
-        ListDeletedTabularsQuery, ListWarehousesRequest, ListWarehousesResponse,
-        RenameWarehouseRequest, Service as _, UpdateWarehouseCredentialRequest,
-        UpdateWarehouseDeleteProfileRequest, UpdateWarehouseStorageRequest,
-        WarehouseStatisticsResponse,
+        BatchUpdateNamespacePropertiesRequest, BatchUpdateNamespacePropertiesResponse,
+        CreateNamespaceHierarchyRequest, CreateNamespaceHierarchyResponse, CreateWarehouseRequest,
+        CreateWarehouseResponse, DeleteWarehouseQuery, GetNamespaceUsageQuery,
+        GetWarehouseResponse, ListDeletedTabularsQuery, ListPendingPurgesResponse,
+        ListTableLocationCollisionsResponse, ListWarehouseSecretsResponse, ListWarehousesRequest,
+        ListWarehousesResponse, NamespaceEffectivePropertiesResponse, NamespaceUsageResponse,
+        PreviewTableLocationRequest, PreviewTableLocationResponse, RenameNamespaceRequest,
+        RenameWarehouseRequest, RotateWarehouseCredentialRequest,
+        RotateWarehouseCredentialResponse, Service as _, SetNamespaceCredentialTtlRequest,
+        SetNamespaceStoragePrefixRequest, UpdateWarehouseAllowedAuthenticationMethodsRequest,
+        UpdateWarehouseAllowedFileFormatsRequest, UpdateWarehouseAllowedSchemesRequest,
+        UpdateWarehouseAnnotationsRequest, UpdateWarehouseCommitRateLimitRequest,
+        UpdateWarehouseCredentialRefreshRequest, UpdateWarehouseCredentialRequest,
+        UpdateWarehouseDefaultNamespaceRequest, UpdateWarehouseDefaultViewPropertiesRequest,
+        UpdateWarehouseDeleteProfileRequest, UpdateWarehouseEmptyNamespaceCleanupRequest,
+        UpdateWarehouseFeatureFlagsRequest, UpdateWarehouseImportPrefixesRequest,
+        UpdateWarehouseManifestMergeRequest, UpdateWarehouseMaxTablesPerNamespaceRequest,
+        UpdateWarehouseNamespacePropertySchemaRequest, UpdateWarehousePurgeGracePeriodRequest,
+        UpdateWarehouseSchemaEvolutionPolicyRequest, UpdateWarehouseSnapshotLimitRequest,
+        UpdateWarehouseStoragePrefixOverridesRequest, UpdateWarehouseStorageRequest,
+        ValidateSchemaRequest, ValidateSchemaResponse, WarehouseStatisticsResponse,
     };
 
     use crate::{
@@ -69,6 +99,7 @@ pub mod v1 {
             (name = "server", description = "Manage Server"),
             (name = "project", description = "Manage Projects"),
             (name = "warehouse", description = "Manage Warehouses"),
+            (name = "table", description = "Manage Tables"),
             (name = "user", description = "Manage Users"),
             (name = "role", description = "Manage Roles")
         ),
@@ -82,35 +113,84 @@ pub mod v1 {
             create_role,
             create_user,
             create_warehouse,
+            batch_update_namespace_properties,
+            create_namespace_hierarchy,
             deactivate_warehouse,
             delete_default_project,
             delete_project_by_id,
             delete_role,
             delete_user,
             delete_warehouse,
+            drop_tables,
+            rename_tables,
+            export_table,
             get_default_project,
+            get_namespace_usage,
+            get_namespace_effective_properties,
             get_project_by_id,
             get_role,
+            get_table_template,
             get_server_info,
+            get_table_history,
+            get_table_locks,
+            get_table_storage_footprint,
             get_user,
             get_warehouse,
             get_warehouse_statistics,
+            import_table,
+            list_audit_log,
             list_deleted_tabulars,
+            list_pending_purges,
             list_projects,
             list_roles,
+            list_table_files,
+            list_table_location_collisions,
             list_user,
+            list_warehouse_secrets,
             list_warehouses,
+            load_table_at,
+            preview_table_location,
+            validate_schema,
+            release_table_locks,
+            remove_table_immutability,
+            resolve_table_id,
             rename_default_project,
             rename_project_by_id,
+            rename_namespace,
             rename_warehouse,
+            rotate_storage_credential,
             search_role,
             search_user,
+            set_namespace_credential_ttl,
+            set_namespace_storage_prefix,
+            set_table_deprecation,
+            set_table_description,
+            set_table_template,
             undrop_tabulars,
+            update_namespace_table_properties,
             update_role,
             update_storage_credential,
             update_storage_profile,
             update_user,
+            update_warehouse_allowed_file_formats,
+            update_warehouse_allowed_schemes,
             update_warehouse_delete_profile,
+            update_warehouse_feature_flags,
+            update_warehouse_manifest_merge,
+            update_warehouse_max_tables_per_namespace,
+            update_warehouse_namespace_property_schema,
+            update_warehouse_annotations,
+            update_warehouse_default_view_properties,
+            update_warehouse_commit_rate_limit,
+            update_warehouse_snapshot_limit,
+            update_warehouse_credential_refresh,
+            update_warehouse_empty_namespace_cleanup,
+            update_warehouse_import_prefixes,
+            update_warehouse_default_namespace,
+            update_warehouse_allowed_authentication_methods,
+            update_warehouse_purge_grace_period,
+            update_warehouse_schema_evolution_policy,
+            update_warehouse_storage_prefix_overrides,
             whoami,
         ),
         modifiers(&SecurityAddon)
@@ -388,6 +468,28 @@ pub mod v1 {
         ApiServer::<C, A, S>::search_role(api_context, metadata, request).await
     }
 
+    /// List authorization decisions of the audit log
+    ///
+    /// Requires the server `admin` relation. Decisions are only recorded
+    /// if the audit log is enabled.
+    #[utoipa::path(
+        get,
+        tag = "server",
+        path = "/management/v1/audit-log",
+        params(ListAuditLogQuery),
+        responses(
+            (status = 200, description = "Authorization decisions", body = ListAuditLogResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_audit_log<C: Catalog, A: Authorizer, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Query(query): Query<ListAuditLogQuery>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListAuditLogResponse> {
+        ApiServer::<C, A, S>::list_audit_log(api_context, metadata, query).await
+    }
+
     /// List roles in a project
     #[utoipa::path(
         get,
@@ -687,10 +789,15 @@ pub mod v1 {
     }
 
     /// Delete a warehouse by ID
+    ///
+    /// By default, only empty warehouses can be deleted. With `force=true`, all namespaces,
+    /// tables and views of the warehouse are deleted as well, which requires server admin
+    /// permissions.
     #[utoipa::path(
         delete,
         tag = "warehouse",
         path = "/management/v1/warehouse/{warehouse_id}",
+        params(DeleteWarehouseQuery),
         responses(
             (status = 204, description = "Warehouse deleted successfully"),
             (status = "4XX", body = IcebergErrorResponse),
@@ -698,10 +805,11 @@ pub mod v1 {
     )]
     async fn delete_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<DeleteWarehouseQuery>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
     ) -> Result<(StatusCode, ())> {
-        ApiServer::<C, A, S>::delete_warehouse(warehouse_id.into(), api_context, metadata)
+        ApiServer::<C, A, S>::delete_warehouse(warehouse_id.into(), query, api_context, metadata)
             .await
             .map(|()| (StatusCode::NO_CONTENT, ()))
     }
@@ -753,82 +861,121 @@ pub mod v1 {
         .await
     }
 
-    /// Deactivate a warehouse
+    /// Update the feature flags of a warehouse.
+    ///
+    /// Only the specified flags are changed. Unknown flags are ignored.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/deactivate",
+        path = "/management/v1/warehouse/{warehouse_id}/feature-flags",
+        request_body = UpdateWarehouseFeatureFlagsRequest,
         responses(
-            (status = 200, description = "Warehouse deactivated successfully"),
+            (status = 200, description = "Feature flags updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn deactivate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn update_warehouse_feature_flags<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseFeatureFlagsRequest>,
     ) -> Result<()> {
-        ApiServer::<C, A, S>::deactivate_warehouse(warehouse_id.into(), api_context, metadata).await
+        ApiServer::<C, A, S>::update_warehouse_feature_flags(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    /// Activate a warehouse
+    /// Update the purge grace period of a warehouse.
+    ///
+    /// Storage of dropped and purged tables and views is deleted once the grace period elapsed.
+    /// Until then, the data can be recovered by registering the table again.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/activate",
+        path = "/management/v1/warehouse/{warehouse_id}/purge-grace-period",
+        request_body = UpdateWarehousePurgeGracePeriodRequest,
         responses(
-            (status = 200, description = "Warehouse activated successfully"),
+            (status = 200, description = "Purge grace period updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn activate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn update_warehouse_purge_grace_period<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehousePurgeGracePeriodRequest>,
     ) -> Result<()> {
-        ApiServer::<C, A, S>::activate_warehouse(warehouse_id.into(), api_context, metadata).await
+        ApiServer::<C, A, S>::update_warehouse_purge_grace_period(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    /// Update the storage profile of a warehouse including its storage credential.
+    /// Update the manifest merge threshold of a warehouse.
+    ///
+    /// If a committed snapshot references more manifests than the threshold,
+    /// small manifests are merged into larger ones as part of the commit.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/storage",
-        request_body = UpdateWarehouseStorageRequest,
+        path = "/management/v1/warehouse/{warehouse_id}/manifest-merge",
+        request_body = UpdateWarehouseManifestMergeRequest,
         responses(
-            (status = 200, description = "Storage profile updated successfully"),
+            (status = 200, description = "Manifest merge threshold updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn update_storage_profile<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn update_warehouse_manifest_merge<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UpdateWarehouseStorageRequest>,
+        Json(request): Json<UpdateWarehouseManifestMergeRequest>,
     ) -> Result<()> {
-        ApiServer::<C, A, S>::update_storage(warehouse_id.into(), request, api_context, metadata)
-            .await
+        ApiServer::<C, A, S>::update_warehouse_manifest_merge(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    /// Update the storage credential of a warehouse. The storage profile is not modified.
-    /// This can be used to update credentials before expiration.
+    /// Update the maximum number of tables per namespace of a warehouse.
+    ///
+    /// Creating or registering a table in a namespace that already contains
+    /// the maximum number of tables fails with a conflict.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/storage-credential",
-        request_body = UpdateWarehouseCredentialRequest,
+        path = "/management/v1/warehouse/{warehouse_id}/max-tables-per-namespace",
+        request_body = UpdateWarehouseMaxTablesPerNamespaceRequest,
         responses(
-            (status = 200, description = "Storage credential updated successfully"),
+            (status = 200, description = "Maximum number of tables per namespace updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn update_storage_credential<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn update_warehouse_max_tables_per_namespace<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UpdateWarehouseCredentialRequest>,
+        Json(request): Json<UpdateWarehouseMaxTablesPerNamespaceRequest>,
     ) -> Result<()> {
-        ApiServer::<C, A, S>::update_storage_credential(
+        ApiServer::<C, A, S>::update_warehouse_max_tables_per_namespace(
             warehouse_id.into(),
             request,
             api_context,
@@ -837,155 +984,1586 @@ pub mod v1 {
         .await
     }
 
-    #[derive(Debug, Deserialize, Serialize, utoipa::IntoParams)]
-    pub struct GetWarehouseStatisticsQuery {
-        /// Next page token
-        #[serde(skip_serializing_if = "PageToken::skip_serialize")]
-        #[param(value_type=String)]
-        pub page_token: PageToken,
-        /// Signals an upper bound of the number of results that a client will receive.
-        #[serde(skip_serializing_if = "Option::is_none")]
-        #[serde(default)]
-        pub page_size: Option<i64>,
+    /// Update the schema evolution policy of a warehouse.
+    ///
+    /// Commits that change the schema of a table in a way forbidden by the
+    /// policy are rejected with a bad request listing each violating change.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/schema-evolution-policy",
+        request_body = UpdateWarehouseSchemaEvolutionPolicyRequest,
+        responses(
+            (status = 200, description = "Schema evolution policy updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_schema_evolution_policy<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseSchemaEvolutionPolicyRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_schema_evolution_policy(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    impl GetWarehouseStatisticsQuery {
-        fn to_pagination_query(&self) -> PaginationQuery {
-            PaginationQuery {
-                page_token: self.page_token.clone(),
-                page_size: self.page_size,
-            }
-        }
+    /// Update the file formats allowed for a warehouse.
+    ///
+    /// Commits adding data or delete files in other formats are rejected with a bad request.
+    /// New tables without `write.format.default` property default to the first allowed format.
+    /// By default all formats are allowed.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/allowed-file-formats",
+        request_body = UpdateWarehouseAllowedFileFormatsRequest,
+        responses(
+            (status = 200, description = "Allowed file formats updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_allowed_file_formats<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseAllowedFileFormatsRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_allowed_file_formats(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    /// Get warehouse statistics
-    ///
-    /// Get statistics about the warehouse, currently table and view counts over time.
-    ///
-    /// We lazily create a new statistics entry every hour, in between hours, the existing entry is
-    /// being updated. If there's a change at created_at + 1 hour, a new entry is created.
-    /// If there's been no change, no new entry is created, meaning there may be gaps.
+    /// Update the namespace property schema of a warehouse.
     ///
-    /// Example:
-    /// - 00:16:32: warehouse created:
-    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: null, 0 tables, 0 views
-    /// - 00:30:00: table created:
-    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:30:00, 1 table, 0 views
-    /// - 00:45:00: view created:
-    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:45:00, 1 table, 1 view
-    /// - 01:00:36: table deleted:
-    ///     - timestamp: 02:00:00, created_at: 01:00:36, updated_at: null, 0 tables, 1 view
-    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:45:00, 1 table, 1 view
+    /// Creating namespaces or updating namespace properties with values that do not
+    /// conform to the constraint of their key is rejected with a bad request.
+    /// Properties without a constraint accept any value.
     #[utoipa::path(
-        get,
+        post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/statistics",
-        params(GetWarehouseStatisticsQuery),
+        path = "/management/v1/warehouse/{warehouse_id}/namespace-property-schema",
+        request_body = UpdateWarehouseNamespacePropertySchemaRequest,
         responses(
-            (status = 200, description = "Warehouse statistics", body = WarehouseStatisticsResponse),
+            (status = 200, description = "Namespace property schema updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_warehouse_statistics<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn update_warehouse_namespace_property_schema<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
         Path(warehouse_id): Path<uuid::Uuid>,
-        Query(query): Query<GetWarehouseStatisticsQuery>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<Json<WarehouseStatisticsResponse>> {
-        ApiServer::<C, A, S>::get_warehouse_statistics(
+        Json(request): Json<UpdateWarehouseNamespacePropertySchemaRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_namespace_property_schema(
             warehouse_id.into(),
-            query,
+            request,
             api_context,
             metadata,
         )
         .await
-        .map(Json)
     }
 
-    /// List soft-deleted tabulars
+    /// Update the annotations of a warehouse.
     ///
-    /// List all soft-deleted tabulars in the warehouse that are visible to you.
+    /// Annotations are free-form operational metadata, such as the owning team or
+    /// environment, and replace the previous annotations of the warehouse.
+    /// Warehouses can be filtered by annotation when listing them.
     #[utoipa::path(
-        get,
+        post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/deleted-tabulars",
-        params(ListDeletedTabularsQuery),
+        path = "/management/v1/warehouse/{warehouse_id}/annotations",
+        request_body = UpdateWarehouseAnnotationsRequest,
         responses(
-            (status = 200, description = "List of soft-deleted tabulars", body = ListDeletedTabularsResponse),
+            (status = 200, description = "Annotations updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn list_deleted_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn update_warehouse_annotations<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
-        Query(query): Query<ListDeletedTabularsQuery>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<Json<ListDeletedTabularsResponse>> {
-        ApiServer::<C, A, S>::list_soft_deleted_tabulars(
+        Json(request): Json<UpdateWarehouseAnnotationsRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_annotations(
             warehouse_id.into(),
-            query,
+            request,
             api_context,
             metadata,
         )
         .await
-        .map(Json)
     }
 
+    /// Update the default view properties of a warehouse.
+    ///
+    /// New views inherit these properties, for example a default dialect. Properties
+    /// prefixed with `view-default.` on a namespace or its ancestors take precedence,
+    /// and properties set on the view itself take precedence over all defaults.
+    /// Existing views are not changed.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/deleted_tabulars/undrop",
+        path = "/management/v1/warehouse/{warehouse_id}/default-view-properties",
+        request_body = UpdateWarehouseDefaultViewPropertiesRequest,
         responses(
-            (status = 204, description = "Tabular undropped successfully"),
+            (status = 200, description = "Default view properties updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn undrop_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn update_warehouse_default_view_properties<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UndropTabularsRequest>,
-    ) -> Result<StatusCode> {
-        ApiServer::<C, A, S>::undrop_tabulars(
-            WarehouseIdent::from(warehouse_id),
+        Json(request): Json<UpdateWarehouseDefaultViewPropertiesRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_default_view_properties(
+            warehouse_id.into(),
+            request,
+            api_context,
             metadata,
+        )
+        .await
+    }
+
+    /// Update the commit rate limit of a warehouse.
+    ///
+    /// Commits to tables and views of the warehouse beyond the limit fail with
+    /// `429 Too Many Requests` and a `Retry-After` header, so that a single
+    /// warehouse cannot starve others. The limit is enforced by each server
+    /// instance independently.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/commit-rate-limit",
+        request_body = UpdateWarehouseCommitRateLimitRequest,
+        responses(
+            (status = 200, description = "Commit rate limit updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_commit_rate_limit<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseCommitRateLimitRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_commit_rate_limit(
+            warehouse_id.into(),
             request,
             api_context,
+            metadata,
         )
-        .await?;
-        Ok(StatusCode::NO_CONTENT)
+        .await
     }
 
-    #[derive(Debug, Serialize, utoipa::ToSchema)]
-    pub struct ListDeletedTabularsResponse {
-        /// List of tabulars
-        pub tabulars: Vec<DeletedTabularResponse>,
-        /// Token to fetch the next page
-        pub next_page_token: Option<String>,
+    /// Update the snapshot limit of a warehouse.
+    ///
+    /// Commits that would leave a table with more than `max-snapshots` snapshots
+    /// expire its oldest snapshots until `retain-last` snapshots are left. Snapshots
+    /// referenced by a branch or tag, and snapshots younger than the purge grace period
+    /// of the warehouse, are never expired. Tables with `gc.enabled=false`, immutable and
+    /// read-only tables are not affected. Files of expired snapshots are deleted after the
+    /// purge grace period.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/snapshot-limit",
+        request_body = UpdateWarehouseSnapshotLimitRequest,
+        responses(
+            (status = 200, description = "Snapshot limit updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_snapshot_limit<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseSnapshotLimitRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_snapshot_limit(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    #[derive(Debug, Serialize, utoipa::ToSchema)]
-    pub struct DeletedTabularResponse {
-        /// Unique identifier of the tabular
-        pub id: uuid::Uuid,
-        /// Name of the tabular
-        pub name: String,
-        /// List of namespace parts the tabular belongs to
-        pub namespace: Vec<String>,
-        /// Type of the tabular
-        pub typ: TabularType,
-        /// Warehouse ID where the tabular is stored
-        #[schema(value_type = uuid::Uuid)]
-        pub warehouse_id: WarehouseIdent,
-        /// Date when the tabular was created
-        pub created_at: chrono::DateTime<chrono::Utc>,
-        /// Date when the tabular was deleted
-        pub deleted_at: chrono::DateTime<chrono::Utc>,
-        /// Date when the tabular will not be recoverable anymore
-        pub expiration_date: chrono::DateTime<chrono::Utc>,
+    /// Update the authentication methods allowed for a warehouse
+    ///
+    /// Requests to the warehouse authenticated with a method that is not allowed
+    /// are rejected with 403 before any authorization check.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/allowed-authentication-methods",
+        request_body = UpdateWarehouseAllowedAuthenticationMethodsRequest,
+        responses(
+            (status = 200, description = "Allowed authentication methods updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_allowed_authentication_methods<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseAllowedAuthenticationMethodsRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_allowed_authentication_methods(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    impl From<TabularIdentUuid> for TabularType {
+    /// Update the credential refresh hint of a warehouse.
+    ///
+    /// Expiring credentials vended for tables of the warehouse are returned with
+    /// `lakekeeper.credentials.refresh-before-ms`, the time before expiry at which
+    /// clients should request new credentials from the table credentials endpoint.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/credential-refresh",
+        request_body = UpdateWarehouseCredentialRefreshRequest,
+        responses(
+            (status = 200, description = "Credential refresh hint updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_credential_refresh<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseCredentialRefreshRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_credential_refresh(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Update the cleanup of empty namespaces of a warehouse.
+    ///
+    /// Namespaces without tables, views and child namespaces are removed once they
+    /// have not been created, updated or emptied for the configured time.
+    /// Requires the empty namespace cleanup to be enabled for the server.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/empty-namespace-cleanup",
+        request_body = UpdateWarehouseEmptyNamespaceCleanupRequest,
+        responses(
+            (status = 200, description = "Empty namespace cleanup updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_empty_namespace_cleanup<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseEmptyNamespaceCleanupRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_empty_namespace_cleanup(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// List pending purges
+    ///
+    /// List purges of dropped tables and views whose storage has not been deleted yet,
+    /// including purges that failed.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/pending-purges",
+        responses(
+            (status = 200, description = "List of pending purges", body = ListPendingPurgesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_pending_purges<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ListPendingPurgesResponse>> {
+        ApiServer::<C, A, S>::list_pending_purges(warehouse_id.into(), api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// List tables with colliding locations
+    ///
+    /// List tables whose location equals or is nested in the location of another table
+    /// of the warehouse. Writing to such tables may corrupt the data of both tables.
+    /// Colliding tables are not modified; resolve collisions by moving or dropping tables.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table-location-collisions",
+        responses(
+            (status = 200, description = "Tables with colliding locations", body = ListTableLocationCollisionsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_table_location_collisions<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ListTableLocationCollisionsResponse>> {
+        ApiServer::<C, A, S>::list_table_location_collisions(
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Update the location schemes allowed for a warehouse.
+    ///
+    /// Explicit and derived locations of tables, views and namespaces must use one of
+    /// the allowed schemes. By default all schemes supported by the storage profile are allowed.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/allowed-schemes",
+        request_body = UpdateWarehouseAllowedSchemesRequest,
+        responses(
+            (status = 200, description = "Allowed schemes updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_allowed_schemes<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseAllowedSchemesRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_allowed_schemes(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Update the storage prefix overrides of a warehouse.
+    ///
+    /// Namespaces can place their tables and views below one of these locations
+    /// instead of the base location of the storage profile.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/storage-prefix-overrides",
+        request_body = UpdateWarehouseStoragePrefixOverridesRequest,
+        responses(
+            (status = 200, description = "Storage prefix overrides updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_storage_prefix_overrides<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseStoragePrefixOverridesRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_storage_prefix_overrides(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Update the import prefixes of a warehouse.
+    ///
+    /// Tables can be registered from these locations even though they are outside of
+    /// the base location of the storage profile. Such tables are read-only.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/import-prefixes",
+        request_body = UpdateWarehouseImportPrefixesRequest,
+        responses(
+            (status = 200, description = "Import prefixes updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_import_prefixes<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseImportPrefixesRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_import_prefixes(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set the default namespace of a warehouse.
+    ///
+    /// Table operations that address the default namespace alias (`_default` unless
+    /// configured otherwise) as their namespace target this namespace instead.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/default-namespace",
+        request_body = UpdateWarehouseDefaultNamespaceRequest,
+        responses(
+            (status = 200, description = "Default namespace updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_warehouse_default_namespace<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseDefaultNamespaceRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_default_namespace(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set the storage prefix of a namespace.
+    ///
+    /// Tables and views created in the namespace or in one of its child namespaces
+    /// are placed below the storage prefix. Child namespaces inherit the storage
+    /// prefix of their nearest ancestor unless they set their own.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/storage-prefix",
+        request_body = SetNamespaceStoragePrefixRequest,
+        responses(
+            (status = 200, description = "Namespace storage prefix updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_namespace_storage_prefix<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetNamespaceStoragePrefixRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::set_namespace_storage_prefix(
+            warehouse_id.into(),
+            namespace_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set the default credential TTL of a namespace.
+    ///
+    /// Credentials vended for tables in the namespace or in one of its child namespaces
+    /// are valid for the configured duration instead of the default of the storage
+    /// profile. Child namespaces inherit the TTL of their nearest ancestor unless they
+    /// set their own. Clients can still request shorter lifetimes via the
+    /// `x-lakekeeper-credential-ttl` header.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/credential-ttl",
+        request_body = SetNamespaceCredentialTtlRequest,
+        responses(
+            (status = 200, description = "Namespace credential TTL updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_namespace_credential_ttl<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetNamespaceCredentialTtlRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::set_namespace_credential_ttl(
+            warehouse_id.into(),
+            namespace_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get namespace usage
+    ///
+    /// Number of tables and views of a namespace and the total size of the data referenced
+    /// by the current snapshots of its tables. Intended for billing and chargeback.
+    /// Results may be up to 30 seconds old.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/usage",
+        params(GetNamespaceUsageQuery),
+        responses(
+            (status = 200, description = "Namespace usage", body = NamespaceUsageResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_namespace_usage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<GetNamespaceUsageQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<NamespaceUsageResponse>> {
+        ApiServer::<C, A, S>::get_namespace_usage(
+            warehouse_id.into(),
+            namespace_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Get the effective properties of a namespace
+    ///
+    /// Properties set on the namespace and on each of its ancestors, together with
+    /// the merged set in which values of child namespaces override those of their ancestors.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/effective-properties",
+        responses(
+            (status = 200, description = "Property inheritance chain of the namespace", body = NamespaceEffectivePropertiesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_namespace_effective_properties<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<NamespaceEffectivePropertiesResponse>> {
+        ApiServer::<C, A, S>::get_namespace_effective_properties(
+            warehouse_id.into(),
+            namespace_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Update the properties of several namespaces atomically
+    ///
+    /// All changes are applied in a single transaction: if any namespace does not exist,
+    /// may not be updated or any change is invalid, no namespace is updated.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/batch-update-properties",
+        request_body = BatchUpdateNamespacePropertiesRequest,
+        responses(
+            (status = 200, description = "Properties of all namespaces updated", body = BatchUpdateNamespacePropertiesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn batch_update_namespace_properties<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<BatchUpdateNamespacePropertiesRequest>,
+    ) -> Result<Json<BatchUpdateNamespacePropertiesResponse>> {
+        ApiServer::<C, A, S>::batch_update_namespace_properties(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Create a namespace and all its missing ancestors
+    ///
+    /// Existing levels are left unchanged, the response reports for each level whether
+    /// it was created. All levels are created in a single transaction: if any level
+    /// cannot be created, no namespace is created.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/create-hierarchy",
+        request_body = CreateNamespaceHierarchyRequest,
+        responses(
+            (status = 200, description = "Namespace and all its ancestors exist", body = CreateNamespaceHierarchyResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_namespace_hierarchy<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<CreateNamespaceHierarchyRequest>,
+    ) -> Result<Json<CreateNamespaceHierarchyResponse>> {
+        ApiServer::<C, A, S>::create_namespace_hierarchy(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Preview the location of a new table
+    ///
+    /// Returns the location a table created in the namespace by the caller would receive
+    /// if no location is specified, without creating anything. The ID of the table is
+    /// only assigned on creation and is represented by the `{table-id}` placeholder.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table-location-preview",
+        request_body = PreviewTableLocationRequest,
+        responses(
+            (status = 200, description = "Location the table would receive", body = PreviewTableLocationResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn preview_table_location<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<PreviewTableLocationRequest>,
+    ) -> Result<Json<PreviewTableLocationResponse>> {
+        ApiServer::<C, A, S>::preview_table_location(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Validate a proposed schema
+    ///
+    /// Checks a schema against the structural rules of Iceberg, such as unique field IDs,
+    /// valid types and valid identifier fields, without creating anything. All violations
+    /// are listed, so that clients can correct a schema before creating a table.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/validate-schema",
+        request_body = ValidateSchemaRequest,
+        responses(
+            (status = 200, description = "Result of the validation", body = ValidateSchemaResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn validate_schema<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<ValidateSchemaRequest>,
+    ) -> Result<Json<ValidateSchemaResponse>> {
+        ApiServer::<C, A, S>::validate_schema(warehouse_id.into(), request, api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// Rename a namespace
+    ///
+    /// Child namespaces, tables and views move along with the namespace.
+    /// Their ids and storage locations do not change.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/rename",
+        request_body = RenameNamespaceRequest,
+        responses(
+            (status = 200, description = "Namespace renamed successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn rename_namespace<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RenameNamespaceRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::rename_namespace(warehouse_id.into(), request, api_context, metadata)
+            .await
+    }
+
+    /// Set the description of a table.
+    ///
+    /// The description is stored in the `comment` table property.
+    #[utoipa::path(
+        post,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/description",
+        request_body = SetTableDescriptionRequest,
+        responses(
+            (status = 200, description = "Table description updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_table_description<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetTableDescriptionRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::set_table_description(
+            warehouse_id.into(),
+            table_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Deprecate a table
+    ///
+    /// Loading a deprecated table still succeeds, but the response carries a `Warning`
+    /// header, a `Sunset` header if a sunset is set, and the deprecation in the
+    /// `deprecation` field. Commits can be rejected once the sunset has passed.
+    #[utoipa::path(
+        post,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/deprecation",
+        request_body = SetTableDeprecationRequest,
+        responses(
+            (status = 200, description = "Table deprecation updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_table_deprecation<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetTableDeprecationRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::set_table_deprecation(
+            warehouse_id.into(),
+            table_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Drop multiple tables
+    ///
+    /// Tables are authorized individually and dropped in a single transaction,
+    /// honoring the delete profile of the warehouse. Tables that cannot be dropped,
+    /// for example due to missing permissions, are reported in the response
+    /// without affecting the other tables.
+    #[utoipa::path(
+        post,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/drop",
+        request_body = DropTablesRequest,
+        responses(
+            (status = 200, description = "Outcome for each requested table", body = DropTablesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn drop_tables<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<DropTablesRequest>,
+    ) -> Result<Json<DropTablesResponse>> {
+        ApiServer::<C, A, S>::drop_tables(warehouse_id.into(), request, api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// Rename multiple tables
+    ///
+    /// Tables are renamed or moved to other namespaces in a single transaction:
+    /// either all tables are renamed or none. A table may take over the name of
+    /// another table of the request, for example to swap two tables. Renaming a
+    /// table requires permission to rename it and to create tables in the target namespace.
+    #[utoipa::path(
+        post,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/rename",
+        request_body = RenameTablesRequest,
+        responses(
+            (status = 200, description = "All tables renamed successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn rename_tables<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RenameTablesRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::rename_tables(warehouse_id.into(), request, api_context, metadata)
+            .await
+    }
+
+    /// Create a new version of a table template
+    ///
+    /// Tables created with the property `lakekeeper.template` set to the name of the template
+    /// are pre-filled with the schema, partition spec, write order and properties of its
+    /// latest version. Values of the create request take precedence. Existing tables are
+    /// not affected by new versions.
+    #[utoipa::path(
+        put,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table-template/{name}",
+        request_body = TableTemplate,
+        responses(
+            (status = 200, description = "Created version of the template", body = TableTemplateResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_table_template<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, name)): Path<(uuid::Uuid, String)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<TableTemplate>,
+    ) -> Result<Json<TableTemplateResponse>> {
+        ApiServer::<C, A, S>::set_table_template(
+            warehouse_id.into(),
+            name,
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Get a table template
+    ///
+    /// Returns the latest version of the template unless `version` is specified.
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table-template/{name}",
+        params(GetTableTemplateQuery),
+        responses(
+            (status = 200, description = "Table template", body = TableTemplateResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_template<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, name)): Path<(uuid::Uuid, String)>,
+        Query(query): Query<GetTableTemplateQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<TableTemplateResponse>> {
+        ApiServer::<C, A, S>::get_table_template(
+            warehouse_id.into(),
+            name,
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Update properties of all tables in a namespace
+    ///
+    /// Sets and removes properties on every table of the namespace, optionally including
+    /// the tables of all child namespaces. Each table is authorized and committed
+    /// individually. Tables that cannot be updated, for example due to missing
+    /// permissions, are reported in the response without affecting the other tables.
+    #[utoipa::path(
+        post,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/table-properties",
+        request_body = UpdateNamespaceTablePropertiesRequest,
+        responses(
+            (status = 200, description = "Outcome for each table", body = UpdateNamespaceTablePropertiesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_namespace_table_properties<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateNamespaceTablePropertiesRequest>,
+    ) -> Result<Json<UpdateNamespaceTablePropertiesResponse>> {
+        ApiServer::<C, A, S>::update_namespace_table_properties(
+            warehouse_id.into(),
+            namespace_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Resolve a table id to its current name
+    ///
+    /// Table ids do not change when a table is renamed or moved to another namespace.
+    /// Returns 404 if the table does not exist or is soft-deleted.
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/identifier",
+        responses(
+            (status = 200, description = "Current namespace and name of the table", body = ResolveTableIdResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn resolve_table_id<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ResolveTableIdResponse>> {
+        ApiServer::<C, A, S>::resolve_table_id(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Get the locks held on a table
+    ///
+    /// Lists the database sessions holding a lock on the table, for example
+    /// the open commit transaction of a crashed process. The age of the holding
+    /// transaction helps to judge whether the lock is stale.
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/locks",
+        responses(
+            (status = 200, description = "Sessions holding a lock on the table", body = TableLocksResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_locks<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<TableLocksResponse>> {
+        ApiServer::<C, A, S>::get_table_locks(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Get the commit history of a table
+    ///
+    /// Lists the snapshots of the table, newest first, with their operation,
+    /// summary and parent. The committing principal is included if it was recorded.
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/history",
+        params(TableHistoryQuery),
+        responses(
+            (status = 200, description = "Snapshots of the table", body = TableHistoryResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_history<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<TableHistoryQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<TableHistoryResponse>> {
+        ApiServer::<C, A, S>::get_table_history(
+            warehouse_id.into(),
+            table_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// List the data files of a table
+    ///
+    /// Reads the manifests of a snapshot of the table, by default the current one,
+    /// and returns the path, size and record count of every data file it references.
+    /// Delete files are not included. The page token refers to the snapshot the
+    /// listing started at, so that all pages list the same snapshot.
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/files",
+        params(TableFilesQuery),
+        responses(
+            (status = 200, description = "Data files of the table", body = TableFilesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_table_files<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<TableFilesQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<TableFilesResponse>> {
+        ApiServer::<C, A, S>::list_table_files(
+            warehouse_id.into(),
+            table_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Get the storage footprint of a table
+    ///
+    /// Sizes of the data files, delete files and metadata files, including manifests,
+    /// referenced by the current snapshot of the table. Intended for billing and chargeback.
+    /// Sizes are read from the manifests of the table. Results may be up to 30 seconds old.
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/footprint",
+        responses(
+            (status = 200, description = "Storage footprint of the table", body = TableStorageFootprint),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_storage_footprint<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<TableStorageFootprint>> {
+        ApiServer::<C, A, S>::get_table_storage_footprint(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Force-release the locks held on a table
+    ///
+    /// Terminates all database sessions holding a lock on the table. Their
+    /// transactions are rolled back, so a writer that is still alive fails its
+    /// commit instead of persisting partial changes. Returns the terminated sessions.
+    #[utoipa::path(
+        post,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/locks/release",
+        responses(
+            (status = 200, description = "Sessions that were terminated", body = TableLocksResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn release_table_locks<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<TableLocksResponse>> {
+        ApiServer::<C, A, S>::release_table_locks(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Remove the immutability of a table
+    ///
+    /// Removes the `lakekeeper.immutable` property of a write-once table, so that
+    /// its data can be deleted or overwritten again. Requires server admin
+    /// permissions and is recorded in the audit log.
+    #[utoipa::path(
+        post,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/remove-immutability",
+        responses(
+            (status = 200, description = "Immutability removed successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn remove_table_immutability<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::remove_table_immutability(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Load a table at a snapshot
+    ///
+    /// Returns the table metadata as of the given snapshot or point in time,
+    /// using the schema and partition spec that were effective for the snapshot.
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/time-travel",
+        params(LoadTableAtQuery),
+        responses(
+            (status = 200, description = "Table metadata at the requested snapshot"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn load_table_at<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<LoadTableAtQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<LoadTableResult> {
+        ApiServer::<C, A, S>::load_table_at(
+            warehouse_id.into(),
+            table_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Export a table
+    ///
+    /// Returns the current schema, partition spec, sort order and properties of the table
+    /// as a portable bundle that can be imported into another warehouse.
+    /// Snapshots and data files are not included.
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/export",
+        responses(
+            (status = 200, description = "Table export bundle", body = TableExportBundle),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn export_table<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<TableExportBundle>> {
+        ApiServer::<C, A, S>::export_table(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Import a table
+    ///
+    /// Creates a new table in the namespace from a bundle returned by the table export.
+    /// The location of the new table is determined by the target warehouse and namespace.
+    #[utoipa::path(
+        post,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/import-table",
+        request_body = ImportTableRequest,
+        responses(
+            (status = 200, description = "Metadata of the created table"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn import_table<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<ImportTableRequest>,
+    ) -> Result<LoadTableResult> {
+        ApiServer::<C, A, S>::import_table(
+            warehouse_id.into(),
+            namespace_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Deactivate a warehouse
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/deactivate",
+        responses(
+            (status = 200, description = "Warehouse deactivated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn deactivate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::deactivate_warehouse(warehouse_id.into(), api_context, metadata).await
+    }
+
+    /// Activate a warehouse
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/activate",
+        responses(
+            (status = 200, description = "Warehouse activated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn activate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::activate_warehouse(warehouse_id.into(), api_context, metadata).await
+    }
+
+    /// Update the storage profile of a warehouse including its storage credential.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/storage",
+        request_body = UpdateWarehouseStorageRequest,
+        responses(
+            (status = 200, description = "Storage profile updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_storage_profile<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseStorageRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_storage(warehouse_id.into(), request, api_context, metadata)
+            .await
+    }
+
+    /// Update the storage credential of a warehouse. The storage profile is not modified.
+    /// This can be used to update credentials before expiration.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/storage-credential",
+        request_body = UpdateWarehouseCredentialRequest,
+        responses(
+            (status = 200, description = "Storage credential updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_storage_credential<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseCredentialRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_storage_credential(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Rotate the storage credential of a warehouse
+    ///
+    /// Credential vending switches to the new credential immediately. The previous
+    /// credential remains stored for an overlap window, so that requests which already
+    /// resolved it complete successfully. Emits a `warehouse.storageCredentialRotated` event.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/storage-credential/rotate",
+        request_body = RotateWarehouseCredentialRequest,
+        responses(
+            (status = 200, description = "Storage credential rotated successfully", body = RotateWarehouseCredentialResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn rotate_storage_credential<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RotateWarehouseCredentialRequest>,
+    ) -> Result<Json<RotateWarehouseCredentialResponse>> {
+        ApiServer::<C, A, S>::rotate_storage_credential(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// List the secrets referenced by a warehouse
+    ///
+    /// Returns the ID, credential type and timestamps of each secret. Secret values
+    /// are never returned. Requires the server `admin` or the warehouse `modify` relation.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/secrets",
+        responses(
+            (status = 200, description = "Secrets referenced by the warehouse", body = ListWarehouseSecretsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_warehouse_secrets<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ListWarehouseSecretsResponse>> {
+        ApiServer::<C, A, S>::list_warehouse_secrets(warehouse_id.into(), api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    #[derive(Debug, Deserialize, Serialize, utoipa::IntoParams)]
+    pub struct GetWarehouseStatisticsQuery {
+        /// Next page token
+        #[serde(skip_serializing_if = "PageToken::skip_serialize")]
+        #[param(value_type=String)]
+        pub page_token: PageToken,
+        /// Signals an upper bound of the number of results that a client will receive.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        pub page_size: Option<i64>,
+    }
+
+    impl GetWarehouseStatisticsQuery {
+        fn to_pagination_query(&self) -> PaginationQuery {
+            PaginationQuery {
+                page_token: self.page_token.clone(),
+                page_size: self.page_size,
+            }
+        }
+    }
+
+    /// Get warehouse statistics
+    ///
+    /// Get statistics about the warehouse, currently table and view counts over time.
+    ///
+    /// We lazily create a new statistics entry every hour, in between hours, the existing entry is
+    /// being updated. If there's a change at created_at + 1 hour, a new entry is created.
+    /// If there's been no change, no new entry is created, meaning there may be gaps.
+    ///
+    /// Example:
+    /// - 00:16:32: warehouse created:
+    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: null, 0 tables, 0 views
+    /// - 00:30:00: table created:
+    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:30:00, 1 table, 0 views
+    /// - 00:45:00: view created:
+    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:45:00, 1 table, 1 view
+    /// - 01:00:36: table deleted:
+    ///     - timestamp: 02:00:00, created_at: 01:00:36, updated_at: null, 0 tables, 1 view
+    ///     - timestamp: 01:00:00, created_at: 00:16:32, updated_at: 00:45:00, 1 table, 1 view
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/statistics",
+        params(GetWarehouseStatisticsQuery),
+        responses(
+            (status = 200, description = "Warehouse statistics", body = WarehouseStatisticsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_warehouse_statistics<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<GetWarehouseStatisticsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<WarehouseStatisticsResponse>> {
+        ApiServer::<C, A, S>::get_warehouse_statistics(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// List soft-deleted tabulars
+    ///
+    /// List all soft-deleted tabulars in the warehouse that are visible to you.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/deleted-tabulars",
+        params(ListDeletedTabularsQuery),
+        responses(
+            (status = 200, description = "List of soft-deleted tabulars", body = ListDeletedTabularsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_deleted_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<ListDeletedTabularsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ListDeletedTabularsResponse>> {
+        ApiServer::<C, A, S>::list_soft_deleted_tabulars(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/deleted_tabulars/undrop",
+        responses(
+            (status = 204, description = "Tabular undropped successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn undrop_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UndropTabularsRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::undrop_tabulars(
+            WarehouseIdent::from(warehouse_id),
+            metadata,
+            request,
+            api_context,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    #[derive(Debug, Serialize, utoipa::ToSchema)]
+    pub struct ListDeletedTabularsResponse {
+        /// List of tabulars
+        pub tabulars: Vec<DeletedTabularResponse>,
+        /// Token to fetch the next page
+        pub next_page_token: Option<String>,
+    }
+
+    #[derive(Debug, Serialize, utoipa::ToSchema)]
+    pub struct DeletedTabularResponse {
+        /// Unique identifier of the tabular
+        pub id: uuid::Uuid,
+        /// Name of the tabular
+        pub name: String,
+        /// List of namespace parts the tabular belongs to
+        pub namespace: Vec<String>,
+        /// Type of the tabular
+        pub typ: TabularType,
+        /// Warehouse ID where the tabular is stored
+        #[schema(value_type = uuid::Uuid)]
+        pub warehouse_id: WarehouseIdent,
+        /// Date when the tabular was created
+        pub created_at: chrono::DateTime<chrono::Utc>,
+        /// Date when the tabular was deleted
+        pub deleted_at: chrono::DateTime<chrono::Utc>,
+        /// Date when the tabular will not be recoverable anymore
+        pub expiration_date: chrono::DateTime<chrono::Utc>,
+    }
+
+    impl From<TabularIdentUuid> for TabularType {
         fn from(ident: TabularIdentUuid) -> Self {
             match ident {
                 TabularIdentUuid::Table(_) => TabularType::Table,
@@ -1022,6 +2600,7 @@ pub mod v1 {
                 // Server
                 .route("/info", get(get_server_info))
                 .route("/bootstrap", post(bootstrap))
+                .route("/audit-log", get(list_audit_log))
                 // Role management
                 .route("/role", get(list_roles).post(create_role))
                 .route(
@@ -1086,6 +2665,14 @@ pub mod v1 {
                     "/warehouse/{warehouse_id}/storage-credential",
                     post(update_storage_credential),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/storage-credential/rotate",
+                    post(rotate_storage_credential),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/secrets",
+                    get(list_warehouse_secrets),
+                )
                 // Get warehouse statistics
                 .route(
                     "/warehouse/{warehouse_id}/statistics",
@@ -1103,6 +2690,183 @@ pub mod v1 {
                     "/warehouse/{warehouse_id}/delete-profile",
                     post(update_warehouse_delete_profile),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/feature-flags",
+                    post(update_warehouse_feature_flags),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/allowed-schemes",
+                    post(update_warehouse_allowed_schemes),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/storage-prefix-overrides",
+                    post(update_warehouse_storage_prefix_overrides),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/import-prefixes",
+                    post(update_warehouse_import_prefixes),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/default-namespace",
+                    post(update_warehouse_default_namespace),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/storage-prefix",
+                    post(set_namespace_storage_prefix),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/credential-ttl",
+                    post(set_namespace_credential_ttl),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/usage",
+                    get(get_namespace_usage),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/table-properties",
+                    post(update_namespace_table_properties),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/import-table",
+                    post(import_table),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/effective-properties",
+                    get(get_namespace_effective_properties),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/rename",
+                    post(rename_namespace),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/batch-update-properties",
+                    post(batch_update_namespace_properties),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/create-hierarchy",
+                    post(create_namespace_hierarchy),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table-location-preview",
+                    post(preview_table_location),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/validate-schema",
+                    post(validate_schema),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/purge-grace-period",
+                    post(update_warehouse_purge_grace_period),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/manifest-merge",
+                    post(update_warehouse_manifest_merge),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/max-tables-per-namespace",
+                    post(update_warehouse_max_tables_per_namespace),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/schema-evolution-policy",
+                    post(update_warehouse_schema_evolution_policy),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/allowed-file-formats",
+                    post(update_warehouse_allowed_file_formats),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace-property-schema",
+                    post(update_warehouse_namespace_property_schema),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/annotations",
+                    post(update_warehouse_annotations),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/default-view-properties",
+                    post(update_warehouse_default_view_properties),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/commit-rate-limit",
+                    post(update_warehouse_commit_rate_limit),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/snapshot-limit",
+                    post(update_warehouse_snapshot_limit),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/credential-refresh",
+                    post(update_warehouse_credential_refresh),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/empty-namespace-cleanup",
+                    post(update_warehouse_empty_namespace_cleanup),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/allowed-authentication-methods",
+                    post(update_warehouse_allowed_authentication_methods),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/pending-purges",
+                    get(list_pending_purges),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table-location-collisions",
+                    get(list_table_location_collisions),
+                )
+                .route("/warehouse/{warehouse_id}/table/drop", post(drop_tables))
+                .route(
+                    "/warehouse/{warehouse_id}/table/rename",
+                    post(rename_tables),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table-template/{name}",
+                    get(get_table_template).put(set_table_template),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/deprecation",
+                    post(set_table_deprecation),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/description",
+                    post(set_table_description),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/files",
+                    get(list_table_files),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/footprint",
+                    get(get_table_storage_footprint),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/history",
+                    get(get_table_history),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/identifier",
+                    get(resolve_table_id),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/locks",
+                    get(get_table_locks),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/locks/release",
+                    post(release_table_locks),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/remove-immutability",
+                    post(remove_table_immutability),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/time-travel",
+                    get(load_table_at),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/export",
+                    get(export_table),
+                )
                 .merge(authorizer.new_router())
         }
     }
@@ -0,0 +1,120 @@
+use axum::response::IntoResponse;
+use iceberg_ext::catalog::rest::{encoding::Json, ErrorModel};
+use serde::{Deserialize, Serialize};
+
+use super::default_page_size;
+use crate::{
+    api::{
+        iceberg::{types::PageToken, v1::PaginationQuery},
+        management::v1::ApiServer,
+        ApiContext,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        audit::{AuditLogFilter, AuditOutcome, AuditRecord},
+        authz::{Authorizer, CatalogServerAction},
+        Catalog, Result, SecretStore, State,
+    },
+};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAuditLogQuery {
+    /// Only return decisions for this principal
+    #[serde(default)]
+    pub principal: Option<String>,
+    /// Only return decisions on objects starting with this prefix,
+    /// for example `table:` or `warehouse:<warehouse-id>`
+    #[serde(default)]
+    pub object_prefix: Option<String>,
+    /// Only return decisions with this outcome
+    #[serde(default)]
+    pub outcome: Option<AuditOutcome>,
+    /// Only return decisions made at or after this time
+    #[serde(default)]
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return decisions made before this time
+    #[serde(default)]
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl ListAuditLogQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+
+    #[must_use]
+    pub fn filter(&self) -> AuditLogFilter {
+        AuditLogFilter {
+            principal: self.principal.clone(),
+            object_prefix: self.object_prefix.clone(),
+            outcome: self.outcome,
+            from: self.from,
+            to: self.to,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ListAuditLogResponse {
+    /// Authorization decisions, oldest first
+    pub records: Vec<AuditRecord>,
+    pub next_page_token: Option<String>,
+}
+
+impl IntoResponse for ListAuditLogResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn list_audit_log(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        query: ListAuditLogQuery,
+    ) -> Result<ListAuditLogResponse> {
+        // ------------------- VALIDATIONS -------------------
+        if let (Some(from), Some(to)) = (query.from, query.to) {
+            if from > to {
+                return Err(ErrorModel::bad_request(
+                    "`from` must not be after `to`",
+                    "InvalidAuditLogTimeRange",
+                    None,
+                )
+                .into());
+            }
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_server_action(&request_metadata, &CatalogServerAction::CanReadAuditLog)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        C::list_audit_records(
+            query.filter(),
+            query.pagination_query(),
+            context.v1_state.catalog,
+        )
+        .await
+    }
+}
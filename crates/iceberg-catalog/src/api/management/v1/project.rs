@@ -1,4 +1,4 @@
-use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg_ext::catalog::rest::{encoding::Json, ErrorModel};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -72,13 +72,13 @@ pub struct CreateProjectResponse {
 
 impl axum::response::IntoResponse for CreateProjectResponse {
     fn into_response(self) -> axum::http::Response<axum::body::Body> {
-        (http::StatusCode::CREATED, axum::Json(self)).into_response()
+        (http::StatusCode::CREATED, Json(self)).into_response()
     }
 }
 
 impl axum::response::IntoResponse for GetProjectResponse {
     fn into_response(self) -> axum::http::Response<axum::body::Body> {
-        axum::Json(self).into_response()
+        Json(self).into_response()
     }
 }
 
@@ -234,7 +234,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
 impl axum::response::IntoResponse for ListProjectsResponse {
     fn into_response(self) -> axum::http::Response<axum::body::Body> {
-        axum::Json(self).into_response()
+        Json(self).into_response()
     }
 }
 
@@ -0,0 +1,2528 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{LazyLock, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use iceberg::{
+    spec::{Schema, SortOrder, UnboundPartitionSpec, PROPERTY_FORMAT_VERSION},
+    NamespaceIdent, TableUpdate,
+};
+use iceberg_ext::catalog::rest::{LoadTableResult, TableDeprecation};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        iceberg::{
+            types::PageToken,
+            v1::{
+                tables::TablesService as _, ApiContext, CommitTableRequest,
+                CommitTransactionRequest, CreateTableRequest, DataAccess, NamespaceParameters,
+                PaginationQuery, Prefix, RenameTableRequest, Result, TableIdent, TableParameters,
+            },
+        },
+        management::v1::{
+            default_page_size, warehouse::TabularDeleteProfile, ApiServer, TabularType,
+        },
+    },
+    catalog::{
+        immutable_tables::{self, PROPERTY_IMMUTABLE},
+        maybe_get_secret,
+        table_deprecation::deprecation_updates,
+        table_files::{list_table_data_files, TableFilesCursor, MAX_TABLE_FILES_PAGE_SIZE},
+        table_footprint::table_storage_footprint,
+        table_templates::PROPERTY_TABLE_TEMPLATE,
+        tables::{
+            authorized_table_ident_to_id, commit_authorized_tables, validate_table_description,
+            validate_table_or_view_ident, validate_table_properties, PROPERTY_TABLE_DESCRIPTION,
+        },
+        CatalogServer,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        audit::record_decision,
+        authz::{
+            Authorizer, CatalogNamespaceAction, CatalogServerAction, CatalogTableAction,
+            CatalogWarehouseAction,
+        },
+        contract_verification::ContractVerification as _,
+        event_publisher::EventMetadata,
+        secrets::SecretStore,
+        task_queue::{
+            tabular_expiration_queue::TabularExpirationInput,
+            tabular_purge_queue::TabularPurgeInput,
+        },
+        Catalog, ErrorModel, ListFlags, NamespaceIdentUuid, SnapshotIdOrTimestamp, State,
+        TableIdentUuid, TableLock, TableRename, TabularIdentUuid, Transaction,
+    },
+    WarehouseIdent,
+};
+
+/// Maximum number of tables that can be dropped in a single request.
+pub const MAX_TABLES_PER_DROP_REQUEST: usize = 100;
+
+/// Maximum number of tables that can be renamed in a single request.
+pub const MAX_TABLES_PER_RENAME_REQUEST: usize = 100;
+
+/// Computing the storage footprint reads all manifests of the current snapshot.
+/// Results are cached briefly so that chargeback jobs polling many tables do not
+/// read them from storage each time.
+const TABLE_FOOTPRINT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+static TABLE_FOOTPRINT_CACHE: LazyLock<
+    Mutex<HashMap<(WarehouseIdent, TableIdentUuid), (Instant, TableStorageFootprint)>>,
+> = LazyLock::new(Mutex::default);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetTableDescriptionRequest {
+    /// New description of the table. `null` removes the description.
+    /// The description is stored in the `comment` table property.
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetTableDeprecationRequest {
+    /// Whether the table is deprecated. If false, the deprecation is removed.
+    pub deprecated: bool,
+    /// Message shown to consumers loading the table.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Point in time from which on the table is retired.
+    #[serde(default)]
+    pub sunset: Option<chrono::DateTime<chrono::Utc>>,
+    /// Reject commits to the table once the sunset has passed. Requires `sunset`.
+    #[serde(default)]
+    pub block_writes_after_sunset: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadTableAtQuery {
+    /// Load the table at this snapshot.
+    #[serde(default)]
+    pub snapshot_id: Option<i64>,
+    /// Load the table at the snapshot of the `main` branch that was current
+    /// at this time, in milliseconds since the epoch.
+    #[serde(default)]
+    pub timestamp_ms: Option<i64>,
+}
+
+impl TryFrom<LoadTableAtQuery> for SnapshotIdOrTimestamp {
+    type Error = ErrorModel;
+
+    fn try_from(query: LoadTableAtQuery) -> std::result::Result<Self, Self::Error> {
+        match (query.snapshot_id, query.timestamp_ms) {
+            (Some(snapshot_id), None) => Ok(SnapshotIdOrTimestamp::SnapshotId(snapshot_id)),
+            (None, Some(timestamp_ms)) => Ok(SnapshotIdOrTimestamp::TimestampMs(timestamp_ms)),
+            _ => Err(ErrorModel::bad_request(
+                "Exactly one of `snapshotId` or `timestampMs` must be specified",
+                "InvalidTimeTravelQuery",
+                None,
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct DropTablesRequest {
+    /// Tables to drop.
+    #[schema(value_type = Vec<Object>)]
+    pub tables: Vec<TableIdent>,
+    /// Whether to purge the data of the dropped tables. Defaults to `true`.
+    #[serde(default)]
+    pub purge_requested: Option<bool>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct DropTablesResponse {
+    /// Outcome of each requested table, in the order of the request.
+    pub results: Vec<DropTableResult>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct DropTableResult {
+    #[schema(value_type = Object)]
+    pub table: TableIdent,
+    /// Whether the table was dropped.
+    pub dropped: bool,
+    /// Reason the table was not dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorModel>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RenameTablesRequest {
+    /// Tables to rename, each with its current and its new identifier.
+    /// Either all tables are renamed or none.
+    #[schema(value_type = Vec<Object>)]
+    pub renames: Vec<RenameTableRequest>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateNamespaceTablePropertiesRequest {
+    /// Properties to set on every table.
+    #[serde(default)]
+    pub updates: HashMap<String, String>,
+    /// Properties to remove from every table.
+    #[serde(default)]
+    pub removals: Vec<String>,
+    /// Include tables of all child namespaces, recursively. Default: false
+    #[serde(default)]
+    pub include_descendants: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateNamespaceTablePropertiesResponse {
+    /// Outcome of each table of the namespace, ordered by namespace and name.
+    pub results: Vec<TablePropertiesUpdateResult>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TablePropertiesUpdateResult {
+    #[schema(value_type = Object)]
+    pub table: TableIdent,
+    pub table_id: uuid::Uuid,
+    /// Whether the properties of the table were updated.
+    pub updated: bool,
+    /// Reason the table was not updated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorModel>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ResolveTableIdResponse {
+    /// Current namespace of the table.
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    /// Current name of the table.
+    pub name: String,
+}
+
+/// Version of the [`TableExportBundle`] format.
+pub const TABLE_EXPORT_BUNDLE_VERSION: i32 = 1;
+
+/// Portable definition of a table, as returned by the export and accepted by the import.
+/// Contains the current schema, partition spec, sort order and properties of the table,
+/// but no snapshots or data files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableExportBundle {
+    /// Version of the bundle format. Currently always 1.
+    pub bundle_version: i32,
+    /// Name of the exported table. Used by the import unless a different name is requested.
+    pub name: String,
+    /// Iceberg format version of the table.
+    pub format_version: u8,
+    /// Location of the exported table. Informational only, the import
+    /// determines the location in the target warehouse.
+    pub source_location: String,
+    /// Current schema of the table.
+    #[schema(value_type = Object)]
+    pub schema: Schema,
+    /// Default partition spec of the table.
+    #[schema(value_type = Object)]
+    pub partition_spec: UnboundPartitionSpec,
+    /// Default sort order of the table.
+    #[schema(value_type = Object)]
+    pub sort_order: SortOrder,
+    /// Properties of the table.
+    pub properties: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImportTableRequest {
+    /// Name of the new table. Defaults to the name in the bundle.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Bundle returned by the export of a table.
+    pub bundle: TableExportBundle,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableLocksResponse {
+    /// Database sessions holding a lock on the table, oldest transaction first.
+    pub locks: Vec<TableLockHolder>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableLockHolder {
+    /// Process id of the database session holding the lock.
+    pub pid: i32,
+    /// Application name reported by the session.
+    pub application_name: Option<String>,
+    /// Address of the client connected to the session.
+    pub client_address: Option<String>,
+    /// State of the session. Sessions of crashed processes are usually `idle in transaction`.
+    pub state: Option<String>,
+    /// Start of the transaction holding the lock.
+    pub transaction_started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Age of the transaction holding the lock in seconds.
+    pub age_seconds: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct TableFilesQuery {
+    /// List the files of this snapshot. Defaults to the current snapshot.
+    /// Ignored if a page token is provided.
+    #[serde(default)]
+    pub snapshot_id: Option<i64>,
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100, Maximum: 1000
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableDataFile {
+    /// Full path of the data file, including the scheme.
+    pub file_path: String,
+    pub file_size_in_bytes: u64,
+    pub record_count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableFilesResponse {
+    /// Snapshot the files were listed for. `None` if the table has no snapshot.
+    pub snapshot_id: Option<i64>,
+    /// Data files referenced by the snapshot, in manifest order.
+    pub files: Vec<TableDataFile>,
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableStorageFootprint {
+    /// Current snapshot of the table. `None` if the table has no snapshot.
+    pub snapshot_id: Option<i64>,
+    /// Total size of the live data files of the current snapshot.
+    pub data_files_size_in_bytes: u64,
+    /// Total size of the live position and equality delete files of the current snapshot.
+    pub delete_files_size_in_bytes: u64,
+    /// Total size of the table metadata file, the manifest list and the manifests
+    /// of the current snapshot.
+    pub metadata_files_size_in_bytes: u64,
+    /// Whether sizes are lower bounds because storage did not report the size
+    /// of the table metadata file or the manifest list.
+    pub approximate: bool,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct TableHistoryQuery {
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl TableHistoryQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableHistoryResponse {
+    /// Snapshots of the table, newest first.
+    pub history: Vec<TableHistoryEntry>,
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableHistoryEntry {
+    pub snapshot_id: i64,
+    /// Snapshot the changes were applied to. `None` for the first snapshot.
+    pub parent_snapshot_id: Option<i64>,
+    /// Commit time of the snapshot in milliseconds since the epoch.
+    pub timestamp_ms: i64,
+    /// Operation of the snapshot, for example `append` or `overwrite`.
+    pub operation: String,
+    /// Summary of the changes, for example the number of added records.
+    pub summary: HashMap<String, String>,
+    /// Principal that committed the snapshot. `None` if it was not recorded.
+    pub principal: Option<String>,
+}
+
+impl From<TableLock> for TableLockHolder {
+    fn from(lock: TableLock) -> Self {
+        Self {
+            pid: lock.holder_pid,
+            application_name: lock.application_name,
+            client_address: lock.client_address,
+            state: lock.state,
+            transaction_started_at: lock.transaction_started_at,
+            age_seconds: lock
+                .transaction_started_at
+                .map(|started_at| (chrono::Utc::now() - started_at).num_seconds()),
+        }
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    async fn set_table_description(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        request: SetTableDescriptionRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        validate_table_description(request.description.as_ref(), &request_metadata)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(&request_metadata, table, &CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        // Committing the `comment` property keeps the metadata file, which is what
+        // clients read, in sync with the stored description.
+        let update = match request.description {
+            Some(description) => TableUpdate::SetProperties {
+                updates: HashMap::from([(PROPERTY_TABLE_DESCRIPTION.to_string(), description)]),
+            },
+            None => TableUpdate::RemoveProperties {
+                removals: vec![PROPERTY_TABLE_DESCRIPTION.to_string()],
+            },
+        };
+        CatalogServer::<C, A, S>::commit_table(
+            TableParameters {
+                prefix: Some(Prefix(warehouse_id.to_string())),
+                table: table.table,
+            },
+            CommitTableRequest {
+                identifier: None,
+                requirements: vec![],
+                updates: vec![update],
+            },
+            context,
+            request_metadata,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deprecate a table or remove its deprecation. Loading a deprecated table succeeds,
+    /// but the response carries a `Warning` and, if set, a `Sunset` header.
+    async fn set_table_deprecation(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        request: SetTableDeprecationRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        if request.block_writes_after_sunset && request.sunset.is_none() {
+            return Err(ErrorModel::bad_request(
+                "Writes can only be blocked after a sunset if a sunset is set",
+                "SunsetRequired",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(&request_metadata, table, &CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let deprecation = request.deprecated.then(|| TableDeprecation {
+            message: request.message,
+            sunset: request.sunset,
+            block_writes_after_sunset: request.block_writes_after_sunset,
+        });
+        // Users cannot commit the deprecation properties, the commit is made on behalf of the catalog.
+        commit_authorized_tables(
+            Some(Prefix(warehouse_id.to_string())),
+            warehouse_id,
+            CommitTransactionRequest {
+                table_changes: vec![CommitTableRequest {
+                    identifier: Some(table.table.clone()),
+                    requirements: vec![],
+                    updates: deprecation_updates(deprecation.as_ref()),
+                }],
+            },
+            &HashMap::from([(table.table, table_id)]),
+            context,
+            request_metadata.request_id().to_string(),
+            None,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn load_table_at(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        query: LoadTableAtQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadTableResult> {
+        // ------------------- VALIDATIONS -------------------
+        let at = SnapshotIdOrTimestamp::try_from(query)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let table = C::load_table_at(warehouse_id, table_id, at, t.transaction()).await?;
+        t.commit().await?;
+
+        // Storage access is not vended for historic table states.
+        Ok(LoadTableResult {
+            metadata_location: table.metadata_location.as_ref().map(ToString::to_string),
+            metadata: table.table_metadata,
+            config: None,
+            storage_credentials: None,
+            deprecation: None,
+        })
+    }
+
+    /// Resolve the current namespace and name of a table by its id, e.g.
+    /// after the table was renamed.
+    async fn resolve_table_id(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ResolveTableIdResponse> {
+        // ------------------- AuthZ -------------------
+        // Authorization is checked on the resolved table, unknown ids are reported as 404.
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let table = C::resolve_table_uuid(warehouse_id, table_id, t.transaction()).await?;
+        t.commit().await?;
+        context
+            .v1_state
+            .authz
+            .require_table_action(
+                &request_metadata,
+                Ok(Some(table_id)),
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        Ok(ResolveTableIdResponse {
+            namespace: table.namespace,
+            name: table.name,
+        })
+    }
+
+    async fn get_table_locks(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableLocksResponse> {
+        // ------------------- AuthZ -------------------
+        authorize_table_locks(warehouse_id, table_id, &context, &request_metadata).await?;
+
+        // ------------------- Business Logic -------------------
+        let locks = C::list_table_locks(warehouse_id, table_id, context.v1_state.catalog).await?;
+        Ok(TableLocksResponse {
+            locks: locks.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    async fn release_table_locks(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableLocksResponse> {
+        // ------------------- AuthZ -------------------
+        authorize_table_locks(warehouse_id, table_id, &context, &request_metadata).await?;
+
+        // ------------------- Business Logic -------------------
+        // Terminating the holder rolls back its transaction. A writer that is still
+        // alive fails its commit, as no changes of the transaction are persisted.
+        let released =
+            C::release_table_locks(warehouse_id, table_id, context.v1_state.catalog).await?;
+        if !released.is_empty() {
+            tracing::warn!(
+                "{:?} force-released locks on table {table_id} in warehouse {warehouse_id} held by sessions {:?}",
+                request_metadata.actor(),
+                released.iter().map(|lock| lock.holder_pid).collect::<Vec<_>>()
+            );
+        }
+        Ok(TableLocksResponse {
+            locks: released.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    async fn get_table_history(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        query: TableHistoryQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableHistoryResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        C::table_history(
+            warehouse_id,
+            table_id,
+            query.pagination_query(),
+            context.v1_state.catalog,
+        )
+        .await
+    }
+
+    /// List the data files referenced by a snapshot of a table, as recorded in
+    /// its manifests.
+    async fn list_table_files(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        query: TableFilesQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableFilesResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let page_size = usize::try_from(query.page_size)
+            .ok()
+            .filter(|s| (1..=MAX_TABLE_FILES_PAGE_SIZE).contains(s))
+            .ok_or_else(|| {
+                ErrorModel::bad_request(
+                    format!("Page size must be between 1 and {MAX_TABLE_FILES_PAGE_SIZE}"),
+                    "InvalidPageSize",
+                    None,
+                )
+            })?;
+        let cursor = query
+            .page_token
+            .as_deref()
+            .map(TableFilesCursor::try_from)
+            .transpose()?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let table = C::load_tables(warehouse_id, [table_id], false, t.transaction())
+            .await?
+            .remove(&table_id)
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Table {table_id} not found in warehouse {warehouse_id}"),
+                    "TableNotFound",
+                    None,
+                )
+            })?;
+        t.commit().await?;
+
+        let storage_secret = maybe_get_secret::<C, _>(
+            table.storage_secret_ident,
+            context.v1_state.catalog,
+            &context.v1_state.secrets,
+        )
+        .await?;
+        let file_io = table.storage_profile.file_io(storage_secret.as_ref())?;
+        list_table_data_files(
+            warehouse_id,
+            &table.table_metadata,
+            query.snapshot_id,
+            cursor,
+            page_size,
+            &file_io,
+        )
+        .await
+    }
+
+    /// Sizes of the data, delete and metadata files referenced by the current
+    /// snapshot of a table.
+    async fn get_table_storage_footprint(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableStorageFootprint> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let key = (warehouse_id, table_id);
+        let cached = TABLE_FOOTPRINT_CACHE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+            .filter(|(computed_at, _)| computed_at.elapsed() < TABLE_FOOTPRINT_CACHE_TTL)
+            .map(|(_, footprint)| footprint.clone());
+        if let Some(footprint) = cached {
+            return Ok(footprint);
+        }
+
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let table = C::load_tables(warehouse_id, [table_id], false, t.transaction())
+            .await?
+            .remove(&table_id)
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Table {table_id} not found in warehouse {warehouse_id}"),
+                    "TableNotFound",
+                    None,
+                )
+            })?;
+        t.commit().await?;
+
+        let storage_secret = maybe_get_secret::<C, _>(
+            table.storage_secret_ident,
+            context.v1_state.catalog,
+            &context.v1_state.secrets,
+        )
+        .await?;
+        let file_io = table.storage_profile.file_io(storage_secret.as_ref())?;
+        let metadata_location = table.metadata_location.map(|l| l.to_string());
+        let footprint = table_storage_footprint(
+            warehouse_id,
+            &table.table_metadata,
+            metadata_location.as_deref(),
+            &file_io,
+        )
+        .await?;
+
+        let mut cache = TABLE_FOOTPRINT_CACHE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        cache.retain(|_, (computed_at, _)| computed_at.elapsed() < TABLE_FOOTPRINT_CACHE_TTL);
+        cache.insert(key, (Instant::now(), footprint.clone()));
+        Ok(footprint)
+    }
+
+    /// Remove the write-once flag of an immutable table, so that data can be
+    /// deleted or overwritten again.
+    async fn remove_table_immutability(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_server_action(
+                &request_metadata,
+                &CatalogServerAction::CanRemoveTableImmutability,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let table = C::resolve_table_uuid(warehouse_id, table_id, t.transaction()).await?;
+        t.commit().await?;
+
+        // Users cannot change the flag, the commit is made on behalf of the catalog.
+        commit_authorized_tables(
+            Some(Prefix(warehouse_id.to_string())),
+            warehouse_id,
+            CommitTransactionRequest {
+                table_changes: vec![CommitTableRequest {
+                    identifier: Some(table.clone()),
+                    requirements: vec![],
+                    updates: vec![TableUpdate::RemoveProperties {
+                        removals: vec![PROPERTY_IMMUTABLE.to_string()],
+                    }],
+                }],
+            },
+            &HashMap::from([(table, table_id)]),
+            context,
+            request_metadata.request_id().to_string(),
+            None,
+        )
+        .await?;
+
+        record_decision(
+            &request_metadata,
+            CatalogServerAction::CanRemoveTableImmutability,
+            format!("table:{table_id}"),
+            true,
+        );
+        tracing::warn!(
+            "{:?} removed immutability of table {table_id} in warehouse {warehouse_id}",
+            request_metadata.actor()
+        );
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn drop_tables(
+        warehouse_id: WarehouseIdent,
+        request: DropTablesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<DropTablesResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let DropTablesRequest {
+            tables,
+            purge_requested,
+        } = request;
+        if tables.len() > MAX_TABLES_PER_DROP_REQUEST {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Cannot drop more than {MAX_TABLES_PER_DROP_REQUEST} tables in a single request, got {}",
+                    tables.len()
+                ),
+                "TooManyTables",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+
+        // Failures of individual tables are reported per table. Only errors of the
+        // catalog abort the request, as they leave the transaction unusable.
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let mut results = Vec::with_capacity(tables.len());
+        let mut to_drop = Vec::with_capacity(tables.len());
+        for (i, table) in tables.into_iter().enumerate() {
+            let table_id = match validate_table_or_view_ident(&table) {
+                Ok(()) => {
+                    let table_id = C::table_to_id(
+                        warehouse_id,
+                        &table,
+                        ListFlags {
+                            include_active: true,
+                            include_staged: true,
+                            include_deleted: false,
+                        },
+                        t.transaction(),
+                    )
+                    .await?;
+                    authorizer
+                        .require_table_action(
+                            &request_metadata,
+                            Ok(table_id),
+                            &CatalogTableAction::CanDrop,
+                        )
+                        .await
+                        .map_err(|e| e.error)
+                }
+                Err(e) => Err(e.error),
+            };
+            let error = match table_id {
+                Ok(table_id) if to_drop.iter().any(|(_, id)| *id == table_id) => {
+                    Some(ErrorModel::bad_request(
+                        format!("Table '{table}' is listed more than once"),
+                        "DuplicateTable",
+                        None,
+                    ))
+                }
+                Ok(table_id) => {
+                    to_drop.push((i, table_id));
+                    None
+                }
+                Err(e) => Some(e),
+            };
+            results.push(DropTableResult {
+                table,
+                dropped: false,
+                error,
+            });
+        }
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let purge = purge_requested.unwrap_or(true);
+        let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+        let metadatas = if purge {
+            C::load_tables(
+                warehouse_id,
+                to_drop.iter().map(|(_, table_id)| *table_id),
+                false,
+                t.transaction(),
+            )
+            .await?
+        } else {
+            HashMap::new()
+        };
+
+        let mut dropped = Vec::with_capacity(to_drop.len());
+        for (i, table_id) in to_drop {
+            if let Some(table) = metadatas.get(&table_id) {
+                if let Err(e) =
+                    immutable_tables::check_table_purge(&results[i].table, &table.table_metadata)
+                {
+                    results[i].error = Some(e.error);
+                    continue;
+                }
+            }
+            if let Err(e) = context
+                .v1_state
+                .contract_verifiers
+                .check_drop(TabularIdentUuid::Table(*table_id))
+                .await?
+                .into_result()
+            {
+                results[i].error = Some(e);
+                continue;
+            }
+            let location = match warehouse.tabular_delete_profile {
+                TabularDeleteProfile::Hard {} => {
+                    Some(C::drop_table(table_id, t.transaction()).await?)
+                }
+                TabularDeleteProfile::Soft { .. } => {
+                    C::mark_tabular_as_deleted(TabularIdentUuid::Table(*table_id), t.transaction())
+                        .await?;
+                    None
+                }
+            };
+            dropped.push((i, table_id, location));
+        }
+        t.commit().await?;
+
+        for (i, table_id, location) in dropped {
+            results[i].dropped = true;
+            match warehouse.tabular_delete_profile {
+                TabularDeleteProfile::Hard {} => {
+                    if let (true, Some(location)) = (purge, location) {
+                        context
+                            .v1_state
+                            .queues
+                            .queue_tabular_purge(TabularPurgeInput {
+                                tabular_id: *table_id,
+                                tabular_location: location,
+                                warehouse_ident: warehouse_id,
+                                tabular_type: TabularType::Table,
+                                parent_id: None,
+                                purge_after: TabularPurgeInput::purge_after(
+                                    warehouse.purge_grace_period,
+                                ),
+                            })
+                            .await?;
+                    }
+                    authorizer.delete_table(table_id).await?;
+                }
+                TabularDeleteProfile::Soft { expiration_seconds } => {
+                    context
+                        .v1_state
+                        .queues
+                        .queue_tabular_expiration(TabularExpirationInput {
+                            tabular_id: table_id.into(),
+                            warehouse_ident: warehouse_id,
+                            tabular_type: TabularType::Table,
+                            purge,
+                            expire_at: chrono::Utc::now() + expiration_seconds,
+                        })
+                        .await?;
+                }
+            }
+
+            let table = &results[i].table;
+            let _ = context
+                .v1_state
+                .publisher
+                .publish(
+                    uuid::Uuid::now_v7(),
+                    "dropTable",
+                    serde_json::Value::Null,
+                    EventMetadata {
+                        tabular_id: TabularIdentUuid::Table(*table_id),
+                        warehouse_id,
+                        name: table.name.clone(),
+                        namespace: table.namespace.to_url_string(),
+                        prefix: warehouse_id.to_string(),
+                        num_events: 1,
+                        sequence_number: 0,
+                        trace_id: request_metadata.request_id().to_string(),
+                    },
+                )
+                .await;
+        }
+
+        Ok(DropTablesResponse { results })
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn rename_tables(
+        warehouse_id: WarehouseIdent,
+        request: RenameTablesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let RenameTablesRequest { renames } = request;
+        if renames.len() > MAX_TABLES_PER_RENAME_REQUEST {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Cannot rename more than {MAX_TABLES_PER_RENAME_REQUEST} tables in a single request, got {}",
+                    renames.len()
+                ),
+                "TooManyTables",
+                None,
+            )
+            .into());
+        }
+        let mut sources = HashSet::with_capacity(renames.len());
+        let mut destinations = HashSet::with_capacity(renames.len());
+        for RenameTableRequest {
+            source,
+            destination,
+        } in &renames
+        {
+            validate_table_or_view_ident(source)?;
+            validate_table_or_view_ident(destination)?;
+            if !sources.insert(source) {
+                return Err(ErrorModel::bad_request(
+                    format!("Table '{source}' is renamed more than once"),
+                    "DuplicateTable",
+                    None,
+                )
+                .into());
+            }
+            if !destinations.insert(destination) {
+                return Err(ErrorModel::conflict(
+                    format!("More than one table is renamed to '{destination}'"),
+                    "RenameDestinationConflict",
+                    None,
+                )
+                .into());
+            }
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let list_flags = ListFlags {
+            include_staged: false,
+            include_deleted: false,
+            include_active: true,
+        };
+        let mut table_renames = Vec::with_capacity(renames.len());
+        for RenameTableRequest {
+            source,
+            destination,
+        } in renames
+        {
+            let table_id = authorized_table_ident_to_id::<C, _>(
+                authorizer.clone(),
+                &request_metadata,
+                warehouse_id,
+                &source,
+                list_flags,
+                &CatalogTableAction::CanRename,
+                t.transaction(),
+            )
+            .await?;
+            let namespace_id =
+                C::namespace_to_id(warehouse_id, &destination.namespace, t.transaction()).await; // We can't fail before AuthZ
+            authorizer
+                .require_namespace_action(
+                    &request_metadata,
+                    namespace_id,
+                    &CatalogNamespaceAction::CanCreateTable,
+                )
+                .await?;
+            table_renames.push(TableRename {
+                table_id,
+                source,
+                destination,
+            });
+        }
+
+        // ------------------- BUSINESS LOGIC -------------------
+        table_renames.retain(|rename| rename.source != rename.destination);
+        // Destinations may only be taken by tables that are renamed in the same batch.
+        let released = table_renames
+            .iter()
+            .map(|rename| &rename.source)
+            .collect::<HashSet<_>>();
+        for rename in &table_renames {
+            if released.contains(&rename.destination) {
+                continue;
+            }
+            if C::table_to_id(
+                warehouse_id,
+                &rename.destination,
+                ListFlags {
+                    include_staged: true,
+                    include_deleted: false,
+                    include_active: true,
+                },
+                t.transaction(),
+            )
+            .await?
+            .is_some()
+            {
+                return Err(ErrorModel::conflict(
+                    format!("Table '{}' already exists", rename.destination),
+                    "TableAlreadyExists",
+                    None,
+                )
+                .into());
+            }
+        }
+
+        C::rename_tables(warehouse_id, &table_renames, t.transaction()).await?;
+
+        for rename in &table_renames {
+            context
+                .v1_state
+                .contract_verifiers
+                .check_rename(
+                    TabularIdentUuid::Table(*rename.table_id),
+                    &rename.destination,
+                )
+                .await?
+                .into_result()?;
+        }
+
+        t.commit().await?;
+
+        for rename in table_renames {
+            let _ = context
+                .v1_state
+                .publisher
+                .publish(
+                    uuid::Uuid::now_v7(),
+                    "renameTable",
+                    serde_json::Value::Null,
+                    EventMetadata {
+                        tabular_id: TabularIdentUuid::Table(*rename.table_id),
+                        warehouse_id,
+                        name: rename.source.name,
+                        namespace: rename.source.namespace.to_url_string(),
+                        prefix: warehouse_id.to_string(),
+                        num_events: 1,
+                        sequence_number: 0,
+                        trace_id: request_metadata.request_id().to_string(),
+                    },
+                )
+                .await;
+        }
+
+        Ok(())
+    }
+
+    async fn update_namespace_table_properties(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        request: UpdateNamespaceTablePropertiesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<UpdateNamespaceTablePropertiesResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let UpdateNamespaceTablePropertiesRequest {
+            updates,
+            removals,
+            include_descendants,
+        } = request;
+        if updates.is_empty() && removals.is_empty() {
+            return Err(ErrorModel::bad_request(
+                "At least one property must be set or removed",
+                "EmptyPropertyUpdate",
+                None,
+            )
+            .into());
+        }
+        if let Some(key) = removals.iter().find(|key| updates.contains_key(*key)) {
+            return Err(ErrorModel::bad_request(
+                format!("Property '{key}' cannot be set and removed at the same time"),
+                "ConflictingPropertyUpdate",
+                None,
+            )
+            .into());
+        }
+        validate_table_properties(updates.keys().chain(&removals))?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanListTables,
+            )
+            .await?;
+
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+        let tables = C::list_namespace_tables(
+            warehouse_id,
+            namespace_id,
+            include_descendants,
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        // Every table is committed in its own transaction, so that a table the
+        // caller may not write or a failed commit does not affect the others.
+        let mut update = vec![];
+        if !updates.is_empty() {
+            update.push(TableUpdate::SetProperties { updates });
+        }
+        if !removals.is_empty() {
+            update.push(TableUpdate::RemoveProperties { removals });
+        }
+
+        let mut results = Vec::with_capacity(tables.len());
+        for (table_id, table) in tables {
+            let error = match authorizer
+                .require_table_action(
+                    &request_metadata,
+                    Ok(Some(table_id)),
+                    &CatalogTableAction::CanCommit,
+                )
+                .await
+            {
+                Ok(_) => CatalogServer::<C, A, S>::commit_table(
+                    TableParameters {
+                        prefix: Some(Prefix(warehouse_id.to_string())),
+                        table: table.clone(),
+                    },
+                    CommitTableRequest {
+                        identifier: None,
+                        requirements: vec![],
+                        updates: update.clone(),
+                    },
+                    context.clone(),
+                    request_metadata.clone(),
+                )
+                .await
+                .err()
+                .map(|e| e.error),
+                Err(e) => Some(e.error),
+            };
+            results.push(TablePropertiesUpdateResult {
+                table,
+                table_id: *table_id,
+                updated: error.is_none(),
+                error,
+            });
+        }
+
+        Ok(UpdateNamespaceTablePropertiesResponse { results })
+    }
+
+    /// Export the current definition of a table as a [`TableExportBundle`].
+    async fn export_table(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableExportBundle> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let ident = C::resolve_table_uuid(warehouse_id, table_id, t.transaction()).await?;
+        let table = C::load_tables(warehouse_id, [table_id], false, t.transaction())
+            .await?
+            .remove(&table_id)
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Table {table_id} not found in warehouse {warehouse_id}"),
+                    "TableNotFound",
+                    None,
+                )
+            })?;
+        t.commit().await?;
+
+        let metadata = table.table_metadata;
+        Ok(TableExportBundle {
+            bundle_version: TABLE_EXPORT_BUNDLE_VERSION,
+            name: ident.name,
+            format_version: metadata.format_version() as u8,
+            source_location: metadata.location().to_string(),
+            schema: metadata.current_schema().as_ref().clone(),
+            partition_spec: metadata
+                .default_partition_spec()
+                .as_ref()
+                .clone()
+                .into_unbound(),
+            sort_order: metadata.default_sort_order().as_ref().clone(),
+            properties: metadata.properties().clone(),
+        })
+    }
+
+    /// Create a new table in the namespace from a [`TableExportBundle`].
+    /// The location of the table is determined by the target namespace, as for
+    /// any other table created without an explicit location.
+    async fn import_table(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        request: ImportTableRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadTableResult> {
+        // ------------------- VALIDATIONS -------------------
+        let ImportTableRequest { name, bundle } = request;
+        if bundle.bundle_version != TABLE_EXPORT_BUNDLE_VERSION {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Unsupported table export bundle version {}, expected {TABLE_EXPORT_BUNDLE_VERSION}",
+                    bundle.bundle_version
+                ),
+                "UnsupportedBundleVersion",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanCreateTable,
+            )
+            .await?;
+
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+        t.commit().await?;
+
+        // ------------------- Business Logic -------------------
+        let TableExportBundle {
+            name: bundle_name,
+            format_version,
+            schema,
+            partition_spec,
+            sort_order,
+            mut properties,
+            ..
+        } = bundle;
+        // Templates are resolved per warehouse; the bundle already contains the
+        // values the table was created with.
+        properties.remove(PROPERTY_TABLE_TEMPLATE);
+        properties.insert(
+            PROPERTY_FORMAT_VERSION.to_string(),
+            format_version.to_string(),
+        );
+
+        CatalogServer::<C, A, S>::create_table(
+            NamespaceParameters {
+                prefix: Some(Prefix(warehouse_id.to_string())),
+                namespace: namespace.namespace,
+            },
+            CreateTableRequest {
+                name: name.unwrap_or(bundle_name),
+                location: None,
+                schema,
+                partition_spec: Some(partition_spec),
+                write_order: Some(sort_order),
+                stage_create: None,
+                properties: Some(properties),
+            },
+            DataAccess {
+                vended_credentials: false,
+                remote_signing: false,
+            },
+            context,
+            request_metadata,
+        )
+        .await
+    }
+}
+
+/// Lock management is restricted to warehouse admins, as releasing a lock
+/// aborts the transaction of another writer.
+async fn authorize_table_locks<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    context: &ApiContext<State<A, C, S>>,
+    request_metadata: &RequestMetadata,
+) -> Result<()> {
+    context
+        .v1_state
+        .authz
+        .require_warehouse_action(
+            request_metadata,
+            warehouse_id,
+            &CatalogWarehouseAction::CanManageTableLocks,
+        )
+        .await?;
+    let table = C::get_table_metadata_by_id(
+        warehouse_id,
+        table_id,
+        ListFlags {
+            include_active: true,
+            include_staged: true,
+            include_deleted: true,
+        },
+        context.v1_state.catalog.clone(),
+    )
+    .await?;
+    if table.is_none() {
+        return Err(ErrorModel::not_found(
+            format!("Table {table_id} not found in warehouse {warehouse_id}"),
+            "TableNotFound",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use iceberg::spec::{
+        NestedField, Operation, PrimitiveType, Schema, Snapshot, SnapshotReference,
+        SnapshotRetention, Summary, Transform, Type, UnboundPartitionSpec, MAIN_BRANCH,
+    };
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::{
+        api::{
+            iceberg::v1::{
+                namespace::NamespaceService, tables::TablesService, DataAccess, LoadTableQuery,
+                NamespaceParameters, TableIdent,
+            },
+            management::v1::warehouse::{
+                CreateWarehouseRequest, Service as _, TabularDeleteProfile,
+            },
+        },
+        catalog::tables::{test::create_request, MAX_TABLE_DESCRIPTION_LENGTH},
+        implementations::postgres::{PostgresCatalog, PostgresTransaction},
+        service::{
+            authz::{implementations::openfga::tests::ObjectHidingMock, AllowAllAuthorizer},
+            CreateNamespaceRequest, UserId,
+        },
+    };
+
+    #[sqlx::test]
+    async fn test_set_table_description(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let table_ident = TableIdent {
+            namespace: ns.namespace.clone(),
+            name: "tab-1".to_string(),
+        };
+        let table = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                namespace: ns.namespace.clone(),
+            },
+            create_request(Some(table_ident.name.clone())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let table_id = TableIdentUuid::from(table.metadata.uuid());
+
+        ApiServer::set_table_description(
+            warehouse.warehouse_id,
+            table_id,
+            SetTableDescriptionRequest {
+                description: Some("My table".to_string()),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let metadata = PostgresCatalog::get_table_metadata_by_id(
+            warehouse.warehouse_id,
+            table_id,
+            ListFlags::default(),
+            ctx.v1_state.catalog.clone(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(metadata.description.as_deref(), Some("My table"));
+
+        let loaded = CatalogServer::load_table(
+            TableParameters {
+                prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                table: table_ident,
+            },
+            LoadTableQuery::default(),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            loaded.metadata.properties().get(PROPERTY_TABLE_DESCRIPTION),
+            Some(&"My table".to_string())
+        );
+
+        // Descriptions exceeding the limit are rejected
+        let err = ApiServer::set_table_description(
+            warehouse.warehouse_id,
+            table_id,
+            SetTableDescriptionRequest {
+                description: Some("a".repeat(MAX_TABLE_DESCRIPTION_LENGTH + 1)),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "TableDescriptionTooLong");
+    }
+
+    fn snapshot(
+        snapshot_id: i64,
+        parent: Option<i64>,
+        schema_id: i32,
+        timestamp_ms: i64,
+    ) -> Snapshot {
+        Snapshot::builder()
+            .with_snapshot_id(snapshot_id)
+            .with_parent_snapshot_id(parent)
+            .with_sequence_number(snapshot_id)
+            .with_schema_id(schema_id)
+            .with_timestamp_ms(timestamp_ms)
+            .with_manifest_list(format!("/snap-{snapshot_id}.avro"))
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: HashMap::new(),
+            })
+            .build()
+    }
+
+    fn main_branch(snapshot_id: i64) -> SnapshotReference {
+        SnapshotReference {
+            snapshot_id,
+            retention: SnapshotRetention::Branch {
+                min_snapshots_to_keep: None,
+                max_snapshot_age_ms: None,
+                max_ref_age_ms: None,
+            },
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_load_table_at(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let table_ident = TableIdent {
+            namespace: ns.namespace.clone(),
+            name: "tab-1".to_string(),
+        };
+        let table = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                namespace: ns.namespace.clone(),
+            },
+            create_request(Some(table_ident.name.clone())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let table_id = TableIdentUuid::from(table.metadata.uuid());
+        let commit = |updates| {
+            CatalogServer::commit_table(
+                TableParameters {
+                    prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                    table: table_ident.clone(),
+                },
+                CommitTableRequest {
+                    identifier: None,
+                    requirements: vec![],
+                    updates,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        // Snapshot 1 uses the initial schema and spec
+        let last_updated = table.metadata.last_updated_ms();
+        let updates = table
+            .metadata
+            .into_builder(table.metadata_location)
+            .add_snapshot(snapshot(1, None, 0, last_updated + 1))
+            .unwrap()
+            .set_ref(MAIN_BRANCH, main_branch(1))
+            .unwrap()
+            .build()
+            .unwrap()
+            .changes;
+        let committed = commit(updates).await.unwrap();
+        let first_location = committed.metadata_location.clone();
+
+        // Snapshot 2 is written after evolving the schema and spec
+        let evolved_schema = Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Int)).into(),
+                NestedField::required(2, "name", Type::Primitive(PrimitiveType::String)).into(),
+                NestedField::optional(3, "amount", Type::Primitive(PrimitiveType::Long)).into(),
+            ])
+            .build()
+            .unwrap();
+        let last_updated = committed.metadata.last_updated_ms();
+        let updates = committed
+            .metadata
+            .into_builder(Some(committed.metadata_location))
+            .add_schema(evolved_schema)
+            .set_current_schema(-1)
+            .unwrap()
+            .add_partition_spec(
+                UnboundPartitionSpec::builder()
+                    .add_partition_field(1, "id", Transform::Identity)
+                    .unwrap()
+                    .build(),
+            )
+            .unwrap()
+            .set_default_partition_spec(-1)
+            .unwrap()
+            .add_snapshot(snapshot(2, Some(1), 1, last_updated + 1))
+            .unwrap()
+            .set_ref(MAIN_BRANCH, main_branch(2))
+            .unwrap()
+            .build()
+            .unwrap()
+            .changes;
+        let committed = commit(updates).await.unwrap();
+        let second_location = committed.metadata_location.clone();
+        assert_ne!(first_location, second_location);
+        assert_eq!(committed.metadata.current_schema_id(), 1);
+        assert_eq!(committed.metadata.default_partition_spec_id(), 1);
+
+        let load_at = |snapshot_id, timestamp_ms| {
+            ApiServer::load_table_at(
+                warehouse.warehouse_id,
+                table_id,
+                LoadTableAtQuery {
+                    snapshot_id,
+                    timestamp_ms,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        let at_first = load_at(Some(1), None).await.unwrap();
+        assert_eq!(at_first.metadata_location, Some(first_location.clone()));
+        assert_eq!(at_first.metadata.current_snapshot_id(), Some(1));
+        assert_eq!(at_first.metadata.snapshots().count(), 1);
+        assert_eq!(at_first.metadata.current_schema_id(), 0);
+        assert_eq!(
+            at_first
+                .metadata
+                .current_schema()
+                .as_struct()
+                .fields()
+                .len(),
+            2
+        );
+        assert_eq!(at_first.metadata.default_partition_spec_id(), 0);
+        assert!(at_first
+            .metadata
+            .default_partition_spec()
+            .is_unpartitioned());
+        // Newer schemas remain available, only the current pointer moves
+        assert_eq!(at_first.metadata.schemas_iter().count(), 2);
+
+        let latest = load_at(None, Some(i64::MAX)).await.unwrap();
+        assert_eq!(latest.metadata.current_snapshot_id(), Some(2));
+        assert_eq!(latest.metadata.current_schema_id(), 1);
+        assert_eq!(latest.metadata.default_partition_spec_id(), 1);
+        assert_eq!(latest.metadata.history().len(), 2);
+        assert_eq!(latest.metadata_location, Some(second_location));
+
+        // The metadata file written with snapshot 1 stays effective until snapshot 2
+        let second_timestamp = latest.metadata.snapshot_by_id(2).unwrap().timestamp_ms();
+        let before_second = load_at(None, Some(second_timestamp - 1)).await.unwrap();
+        assert_eq!(before_second.metadata.current_snapshot_id(), Some(1));
+        assert_eq!(before_second.metadata_location, Some(first_location));
+
+        let err = load_at(Some(42), None).await.unwrap_err();
+        assert_eq!(err.error.code, 404);
+        assert_eq!(err.error.r#type, "SnapshotNotFound");
+
+        // No snapshot was current before the first one was added
+        let err = load_at(None, Some(0)).await.unwrap_err();
+        assert_eq!(err.error.r#type, "SnapshotNotFound");
+
+        let err = load_at(Some(1), Some(0)).await.unwrap_err();
+        assert_eq!(err.error.r#type, "InvalidTimeTravelQuery");
+    }
+
+    #[sqlx::test]
+    async fn test_release_stale_table_lock(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let table_ident = TableIdent {
+            namespace: ns.namespace.clone(),
+            name: "tab-1".to_string(),
+        };
+        let table = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                namespace: ns.namespace.clone(),
+            },
+            create_request(Some(table_ident.name.clone())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let table_id = TableIdentUuid::from(table.metadata.uuid());
+        let get_locks = || {
+            ApiServer::get_table_locks(
+                warehouse.warehouse_id,
+                table_id,
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        assert!(get_locks().await.unwrap().locks.is_empty());
+
+        // Simulate a writer that crashed while holding the lock of its commit
+        let mut stale = pool.begin().await.unwrap();
+        let stale_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+            .fetch_one(&mut *stale)
+            .await
+            .unwrap();
+        sqlx::query("SELECT 1 FROM tabular WHERE tabular_id = $1 FOR UPDATE")
+            .bind(table.metadata.uuid())
+            .execute(&mut *stale)
+            .await
+            .unwrap();
+
+        let locks = get_locks().await.unwrap().locks;
+        assert_eq!(locks.len(), 1);
+        assert_eq!(locks[0].pid, stale_pid);
+        assert_eq!(locks[0].state.as_deref(), Some("idle in transaction"));
+        assert!(locks[0].age_seconds.is_some_and(|age| age >= 0));
+
+        let released = ApiServer::release_table_locks(
+            warehouse.warehouse_id,
+            table_id,
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap()
+        .locks;
+        assert_eq!(
+            released.iter().map(|l| l.pid).collect::<Vec<_>>(),
+            vec![stale_pid]
+        );
+
+        // The transaction of the former holder was rolled back
+        assert!(stale.commit().await.is_err());
+
+        let committed = CatalogServer::commit_table(
+            TableParameters {
+                prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                table: table_ident,
+            },
+            CommitTableRequest {
+                identifier: None,
+                requirements: vec![],
+                updates: vec![TableUpdate::SetProperties {
+                    updates: HashMap::from([("key".to_string(), "value".to_string())]),
+                }],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            committed.metadata.properties().get("key"),
+            Some(&"value".to_string())
+        );
+        assert!(get_locks().await.unwrap().locks.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_drop_tables_reports_denied_tables(pool: PgPool) {
+        let hiding_mock = ObjectHidingMock::new();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            hiding_mock.to_authorizer(),
+            TabularDeleteProfile::Hard {},
+            Some(UserId::new_unchecked("oidc", "test-user-id")),
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+
+        let mut tables = vec![];
+        for name in ["tab-1", "tab-2", "tab-3"] {
+            let table = CatalogServer::create_table(
+                NamespaceParameters {
+                    prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                    namespace: ns.namespace.clone(),
+                },
+                create_request(Some(name.to_string())),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            tables.push(TableIdent {
+                namespace: ns.namespace.clone(),
+                name: name.to_string(),
+            });
+            if name == "tab-2" {
+                hiding_mock.hide(&format!("table:{}", table.metadata.uuid()));
+            }
+        }
+
+        let response = ApiServer::drop_tables(
+            warehouse.warehouse_id,
+            DropTablesRequest {
+                tables: tables.clone(),
+                purge_requested: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let outcomes = response
+            .results
+            .iter()
+            .map(|r| (r.table.name.as_str(), r.dropped))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outcomes,
+            vec![("tab-1", true), ("tab-2", false), ("tab-3", true)]
+        );
+        let error = response.results[1].error.as_ref().unwrap();
+        assert_eq!(error.code, http::StatusCode::FORBIDDEN);
+        assert_eq!(error.r#type, "TableActionForbidden");
+
+        let mut t = PostgresTransaction::begin_read(ctx.v1_state.catalog.clone())
+            .await
+            .unwrap();
+        for (table, exists) in tables.iter().zip([false, true, false]) {
+            let table_id = PostgresCatalog::table_to_id(
+                warehouse.warehouse_id,
+                table,
+                ListFlags::default(),
+                t.transaction(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(table_id.is_some(), exists, "{table}");
+        }
+        t.commit().await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_rename_tables_swaps_atomically(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let other_ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns2".to_string(),
+        )
+        .await;
+        let ident = |namespace: &NamespaceIdent, name: &str| TableIdent {
+            namespace: namespace.clone(),
+            name: name.to_string(),
+        };
+
+        let mut table_ids = HashMap::new();
+        for name in ["tab-a", "tab-b", "tab-c", "tab-d"] {
+            let table = CatalogServer::create_table(
+                NamespaceParameters {
+                    prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                    namespace: ns.namespace.clone(),
+                },
+                create_request(Some(name.to_string())),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            table_ids.insert(name, TableIdentUuid::from(table.metadata.uuid()));
+        }
+        let assert_table_ids = |expected: Vec<(TableIdent, &'static str)>| {
+            let ctx = ctx.clone();
+            let table_ids = table_ids.clone();
+            let warehouse_id = warehouse.warehouse_id;
+            async move {
+                let mut t = PostgresTransaction::begin_read(ctx.v1_state.catalog.clone())
+                    .await
+                    .unwrap();
+                for (table, name) in expected {
+                    let table_id = PostgresCatalog::table_to_id(
+                        warehouse_id,
+                        &table,
+                        ListFlags::default(),
+                        t.transaction(),
+                    )
+                    .await
+                    .unwrap();
+                    assert_eq!(table_id, Some(table_ids[name]), "{table}");
+                }
+                t.commit().await.unwrap();
+            }
+        };
+        let rename = |source: TableIdent, destination: TableIdent| RenameTableRequest {
+            source,
+            destination,
+        };
+
+        // Swap two tables and move a third one to another namespace
+        ApiServer::rename_tables(
+            warehouse.warehouse_id,
+            RenameTablesRequest {
+                renames: vec![
+                    rename(ident(&ns.namespace, "tab-a"), ident(&ns.namespace, "tab-b")),
+                    rename(ident(&ns.namespace, "tab-b"), ident(&ns.namespace, "tab-a")),
+                    rename(
+                        ident(&ns.namespace, "tab-c"),
+                        ident(&other_ns.namespace, "tab-c-moved"),
+                    ),
+                ],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_table_ids(vec![
+            (ident(&ns.namespace, "tab-a"), "tab-b"),
+            (ident(&ns.namespace, "tab-b"), "tab-a"),
+            (ident(&other_ns.namespace, "tab-c-moved"), "tab-c"),
+        ])
+        .await;
+
+        // A collision with a table outside of the batch fails all renames
+        let err = ApiServer::rename_tables(
+            warehouse.warehouse_id,
+            RenameTablesRequest {
+                renames: vec![
+                    rename(ident(&ns.namespace, "tab-a"), ident(&ns.namespace, "tab-e")),
+                    rename(ident(&ns.namespace, "tab-b"), ident(&ns.namespace, "tab-d")),
+                ],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::CONFLICT);
+        assert_eq!(err.error.r#type, "TableAlreadyExists");
+
+        // Two tables cannot be renamed to the same name
+        let err = ApiServer::rename_tables(
+            warehouse.warehouse_id,
+            RenameTablesRequest {
+                renames: vec![
+                    rename(ident(&ns.namespace, "tab-a"), ident(&ns.namespace, "tab-e")),
+                    rename(ident(&ns.namespace, "tab-d"), ident(&ns.namespace, "tab-e")),
+                ],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::CONFLICT);
+        assert_eq!(err.error.r#type, "RenameDestinationConflict");
+
+        assert_table_ids(vec![
+            (ident(&ns.namespace, "tab-a"), "tab-b"),
+            (ident(&ns.namespace, "tab-b"), "tab-a"),
+            (ident(&ns.namespace, "tab-d"), "tab-d"),
+        ])
+        .await;
+    }
+
+    #[sqlx::test]
+    async fn test_resolve_table_id_after_rename(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Soft {
+                expiration_seconds: chrono::Duration::seconds(10),
+            },
+            None,
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let other_ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns2".to_string(),
+        )
+        .await;
+        let table = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: ns.namespace.clone(),
+            },
+            create_request(Some("tab-1".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let table_id = TableIdentUuid::from(table.metadata.uuid());
+        let resolve = || {
+            ApiServer::resolve_table_id(
+                warehouse.warehouse_id,
+                table_id,
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        let resolved = resolve().await.unwrap();
+        assert_eq!(resolved.namespace, ns.namespace);
+        assert_eq!(resolved.name, "tab-1");
+
+        let renamed = TableIdent {
+            namespace: other_ns.namespace.clone(),
+            name: "tab-renamed".to_string(),
+        };
+        CatalogServer::rename_table(
+            prefix.clone(),
+            crate::api::iceberg::v1::RenameTableRequest {
+                source: TableIdent {
+                    namespace: ns.namespace.clone(),
+                    name: "tab-1".to_string(),
+                },
+                destination: renamed.clone(),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let resolved = resolve().await.unwrap();
+        assert_eq!(resolved.namespace, renamed.namespace);
+        assert_eq!(resolved.name, renamed.name);
+
+        // Soft-deleted tables and unknown ids are not resolved
+        ApiServer::drop_tables(
+            warehouse.warehouse_id,
+            DropTablesRequest {
+                tables: vec![renamed],
+                purge_requested: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let err = resolve().await.unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::NOT_FOUND);
+        assert_eq!(err.error.r#type, "TableNotFound");
+
+        let err = ApiServer::resolve_table_id(
+            warehouse.warehouse_id,
+            TableIdentUuid::from(uuid::Uuid::now_v7()),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test]
+    async fn test_update_namespace_table_properties(pool: PgPool) {
+        let hiding_mock = ObjectHidingMock::new();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            hiding_mock.to_authorizer(),
+            TabularDeleteProfile::Hard {},
+            Some(UserId::new_unchecked("oidc", "test-user-id")),
+        )
+        .await;
+        let prefix = Prefix(warehouse.warehouse_id.to_string());
+        let ns = NamespaceIdent::new("ns1".to_string());
+        let child = NamespaceIdent::from_strs(["ns1", "child"]).unwrap();
+        let other = NamespaceIdent::new("ns2".to_string());
+        for namespace in [&ns, &child, &other] {
+            CatalogServer::create_namespace(
+                Some(prefix.clone()),
+                CreateNamespaceRequest {
+                    namespace: namespace.clone(),
+                    properties: None,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+        }
+
+        for (namespace, name) in [
+            (&ns, "tab-1"),
+            (&ns, "tab-2"),
+            (&child, "tab-3"),
+            (&other, "tab-4"),
+        ] {
+            let table = CatalogServer::create_table(
+                NamespaceParameters {
+                    prefix: Some(prefix.clone()),
+                    namespace: namespace.clone(),
+                },
+                create_request(Some(name.to_string())),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            if name == "tab-2" {
+                hiding_mock.hide(&format!("table:{}", table.metadata.uuid()));
+            }
+        }
+
+        let mut t = PostgresTransaction::begin_read(ctx.v1_state.catalog.clone())
+            .await
+            .unwrap();
+        let namespace_id =
+            PostgresCatalog::namespace_to_id(warehouse.warehouse_id, &ns, t.transaction())
+                .await
+                .unwrap()
+                .unwrap();
+        t.commit().await.unwrap();
+
+        let response = ApiServer::update_namespace_table_properties(
+            warehouse.warehouse_id,
+            namespace_id,
+            UpdateNamespaceTablePropertiesRequest {
+                updates: HashMap::from([("retention_class".to_string(), "gold".to_string())]),
+                removals: vec![],
+                include_descendants: true,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let outcomes = response
+            .results
+            .iter()
+            .map(|r| (r.table.name.as_str(), r.updated))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outcomes,
+            vec![("tab-1", true), ("tab-2", false), ("tab-3", true)]
+        );
+        let error = response.results[1].error.as_ref().unwrap();
+        assert_eq!(error.code, http::StatusCode::FORBIDDEN);
+        assert_eq!(error.r#type, "TableActionForbidden");
+
+        for (namespace, name, stamped) in [
+            (&ns, "tab-1", true),
+            (&child, "tab-3", true),
+            (&other, "tab-4", false),
+        ] {
+            let loaded = CatalogServer::load_table(
+                TableParameters {
+                    prefix: Some(prefix.clone()),
+                    table: TableIdent::new(namespace.clone(), name.to_string()),
+                },
+                LoadTableQuery::default(),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(
+                loaded
+                    .metadata
+                    .properties()
+                    .get("retention_class")
+                    .is_some(),
+                stamped,
+                "{name}"
+            );
+        }
+
+        // Setting and removing the same property is rejected
+        let err = ApiServer::update_namespace_table_properties(
+            warehouse.warehouse_id,
+            namespace_id,
+            UpdateNamespaceTablePropertiesRequest {
+                updates: HashMap::from([("retention_class".to_string(), "gold".to_string())]),
+                removals: vec!["retention_class".to_string()],
+                include_descendants: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "ConflictingPropertyUpdate");
+    }
+
+    #[sqlx::test]
+    async fn test_export_import_table(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let schema = Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+                NestedField::required(2, "region", Type::Primitive(PrimitiveType::String)).into(),
+            ])
+            .build()
+            .unwrap();
+        let source = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                namespace: ns.namespace.clone(),
+            },
+            CreateTableRequest {
+                name: "events".to_string(),
+                location: None,
+                schema,
+                partition_spec: Some(
+                    UnboundPartitionSpec::builder()
+                        .add_partition_field(2, "region", Transform::Identity)
+                        .unwrap()
+                        .build(),
+                ),
+                write_order: None,
+                stage_create: None,
+                properties: Some(HashMap::from([(
+                    "retention_class".to_string(),
+                    "gold".to_string(),
+                )])),
+            },
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let bundle = ApiServer::export_table(
+            warehouse.warehouse_id,
+            TableIdentUuid::from(source.metadata.uuid()),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(bundle.name, "events");
+        assert_eq!(bundle.source_location, source.metadata.location());
+        // The bundle is portable JSON
+        let bundle: TableExportBundle =
+            serde_json::from_value(serde_json::to_value(&bundle).unwrap()).unwrap();
+
+        // Import into a different warehouse of the same project
+        let project_id = ApiServer::get_warehouse(
+            warehouse.warehouse_id,
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap()
+        .project_id;
+        let target_profile = crate::catalog::test::test_io_profile();
+        let target_base_location = target_profile.base_location().unwrap().to_string();
+        let target = ApiServer::create_warehouse(
+            CreateWarehouseRequest {
+                warehouse_name: "target-warehouse".to_string(),
+                project_id: Some(project_id.into()),
+                storage_profile: target_profile,
+                storage_credential: None,
+                delete_profile: TabularDeleteProfile::Hard {},
+                annotations: HashMap::new(),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let target_ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            target.warehouse_id.to_string(),
+            "imported".to_string(),
+        )
+        .await;
+        let mut t = PostgresTransaction::begin_read(ctx.v1_state.catalog.clone())
+            .await
+            .unwrap();
+        let target_ns_id = PostgresCatalog::namespace_to_id(
+            target.warehouse_id,
+            &target_ns.namespace,
+            t.transaction(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        t.commit().await.unwrap();
+
+        let imported = ApiServer::import_table(
+            target.warehouse_id,
+            target_ns_id,
+            ImportTableRequest { name: None, bundle },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let metadata = imported.metadata;
+        assert_ne!(metadata.uuid(), source.metadata.uuid());
+        assert_ne!(metadata.location(), source.metadata.location());
+        assert!(
+            metadata
+                .location()
+                .starts_with(target_base_location.trim_end_matches('/')),
+            "{} is not within the target warehouse {target_base_location}",
+            metadata.location()
+        );
+        assert_eq!(metadata.format_version(), source.metadata.format_version());
+        assert_eq!(
+            metadata.current_schema().as_struct(),
+            source.metadata.current_schema().as_struct()
+        );
+        assert_eq!(
+            metadata.default_partition_spec().fields(),
+            source.metadata.default_partition_spec().fields()
+        );
+        assert_eq!(
+            metadata.properties().get("retention_class").unwrap(),
+            "gold"
+        );
+        assert!(metadata.snapshots().next().is_none());
+
+        let loaded = CatalogServer::load_table(
+            TableParameters {
+                prefix: Some(Prefix(target.warehouse_id.to_string())),
+                table: TableIdent::new(target_ns.namespace.clone(), "events".to_string()),
+            },
+            LoadTableQuery::default(),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded.metadata.uuid(), metadata.uuid());
+    }
+}
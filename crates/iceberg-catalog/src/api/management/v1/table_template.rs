@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use iceberg::spec::{Schema, SortOrder, UnboundPartitionSpec};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::{iceberg::v1::ApiContext, management::v1::ApiServer},
+    catalog::table_templates::{validate_table_template, validate_table_template_name},
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogWarehouseAction},
+        Catalog, ErrorModel, Result, SecretStore, State, Transaction,
+    },
+    WarehouseIdent,
+};
+
+/// Values pre-filled when a table is created from the template.
+/// Values of the create request take precedence.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableTemplate {
+    /// Schema of the table. Used if the create request contains a schema without fields.
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub schema: Option<Schema>,
+    /// Partition spec of the table. Used if the create request has no partition spec.
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub partition_spec: Option<UnboundPartitionSpec>,
+    /// Sort order of the table. Used if the create request has no write order.
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub write_order: Option<SortOrder>,
+    /// Properties of the table. Properties of the create request override
+    /// properties with the same key.
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTableTemplateQuery {
+    /// Version of the template. Defaults to the latest version.
+    #[serde(default)]
+    pub version: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableTemplateResponse {
+    /// Name of the template
+    pub name: String,
+    /// Version of the template, starting at 1. Every update creates a new version.
+    pub version: i32,
+    #[serde(flatten)]
+    pub template: TableTemplate,
+    /// Time the version was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn set_table_template(
+        warehouse_id: WarehouseIdent,
+        name: String,
+        template: TableTemplate,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableTemplateResponse> {
+        // ------------------- VALIDATIONS -------------------
+        validate_table_template_name(&name)?;
+        validate_table_template(&template)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanManageTableTemplates,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let response = C::create_table_template_version(
+            warehouse_id,
+            &name,
+            &template,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(response)
+    }
+
+    async fn get_table_template(
+        warehouse_id: WarehouseIdent,
+        name: String,
+        query: GetTableTemplateQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableTemplateResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let template = C::get_table_template(
+            warehouse_id,
+            &name,
+            query.version,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        template.ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Table template '{name}' not found in warehouse {warehouse_id}"),
+                "TableTemplateNotFound",
+                None,
+            )
+            .into()
+        })
+    }
+}
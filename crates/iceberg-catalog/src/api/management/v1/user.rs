@@ -1,5 +1,5 @@
-use axum::{response::IntoResponse, Json};
-use iceberg_ext::catalog::rest::ErrorModel;
+use axum::response::IntoResponse;
+use iceberg_ext::catalog::rest::{encoding::Json, ErrorModel};
 use serde::{Deserialize, Serialize};
 
 use super::default_page_size;
@@ -1,7 +1,19 @@
 mod undrop;
 
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::LazyLock,
+    time::Duration,
+};
+
 use futures::FutureExt;
-use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg::{NamespaceIdent, TableIdent};
+use iceberg_ext::{
+    catalog::rest::{
+        encoding::Json, CreateNamespaceRequest, ErrorModel, UpdateNamespacePropertiesRequest,
+    },
+    configs::Location,
+};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -9,32 +21,64 @@ use uuid::Uuid;
 
 use super::default_page_size;
 pub use crate::service::{
+    authn::AuthenticationMethod,
+    commit_throttle::CommitRateLimit,
+    feature_flags::WarehouseFeatureFlag,
+    namespace_property_schema::NamespacePropertySchema,
+    schema_evolution::SchemaEvolutionPolicy,
+    snapshot_limit::SnapshotLimit,
     storage::{
         AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, S3Credential,
         S3Profile, StorageCredential, StorageProfile,
     },
-    WarehouseStatus,
+    WarehouseSecretUsage, WarehouseStatus,
 };
 use crate::{
     api::{
         iceberg::v1::{PageToken, PaginationQuery},
         management::v1::{
             ApiServer, DeletedTabularResponse, GetWarehouseStatisticsQuery,
-            ListDeletedTabularsResponse,
+            ListDeletedTabularsResponse, TabularType,
         },
         ApiContext, Result,
     },
-    catalog::UnfilteredPage,
+    catalog::{
+        file_format_validation::SUPPORTED_FILE_FORMATS,
+        namespace::{
+            authorized_namespace_ident_to_id, list_descendants_within_limit,
+            new_namespace_properties, require_namespace_not_reserved, resolve_namespace_properties,
+            validate_namespace_ident, validate_namespace_properties_keys, NamespacePropertyChain,
+            NamespacePropertyChanges,
+        },
+        tables::{
+            default_table_parent_location, require_active_warehouse, validate_table_or_view_ident,
+        },
+        views::validate_view_properties,
+        UnfilteredPage,
+    },
     request_metadata::RequestMetadata,
     service::{
-        authz::{Authorizer, CatalogProjectAction, CatalogWarehouseAction},
-        event_publisher::EventMetadata,
+        authn::invalidate_allowed_authentication_methods,
+        authz::{
+            Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogServerAction,
+            CatalogWarehouseAction, NamespaceParent,
+        },
+        event_publisher::{EventMetadata, NamespaceEventMetadata, WarehouseEventMetadata},
+        schema_validation::{validate_schema, SchemaViolation},
+        secret_encryption::{create_storage_secret, get_storage_secret_type},
         secrets::SecretStore,
-        task_queue::TaskFilter,
-        Catalog, ListFlags, NamespaceIdentUuid, State, TableIdentUuid, TabularIdentUuid,
-        Transaction,
+        task_queue::{
+            deleted_warehouse_purge_queue::DeletedWarehousePurgeInput,
+            storage_secret_cleanup_queue::{
+                release_previous_storage_secret, StorageSecretCleanupInput,
+            },
+            tabular_purge_queue::PendingPurge,
+            TaskFilter, TaskStatus,
+        },
+        Catalog, ListFlags, NamespaceIdentUuid, NamespaceUsage, State, TableIdentUuid,
+        TableLocationCollision, TabularIdentUuid, Transaction,
     },
-    ProjectId, WarehouseIdent, DEFAULT_PROJECT_ID,
+    ProjectId, WarehouseIdent, CONFIG, DEFAULT_PROJECT_ID,
 };
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -53,6 +97,101 @@ pub struct ListDeletedTabularsQuery {
     pub page_size: i64,
 }
 
+/// Usage is aggregated over all tables of a namespace. Results are cached
+/// briefly so that frequent polling, e.g. by billing jobs, does not hit the database each time.
+const NAMESPACE_USAGE_CACHE_TTL: Duration = Duration::from_secs(30);
+const MAX_CACHED_NAMESPACE_USAGES: u64 = 10_000;
+
+type NamespaceUsageKey = (WarehouseIdent, NamespaceIdentUuid, bool);
+
+static NAMESPACE_USAGE_CACHE: LazyLock<moka::sync::Cache<NamespaceUsageKey, NamespaceUsage>> =
+    LazyLock::new(|| {
+        moka::sync::Cache::builder()
+            .max_capacity(MAX_CACHED_NAMESPACE_USAGES)
+            .time_to_live(NAMESPACE_USAGE_CACHE_TTL)
+            .build()
+    });
+
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteWarehouseQuery {
+    /// Delete the warehouse even if it contains namespaces, tables or views.
+    /// All of them are deleted as well. Requires server admin permissions.
+    /// Default: false
+    #[serde(default)]
+    pub force: bool,
+    /// Whether to purge the data of the tables and views deleted with a forced deletion.
+    /// Tables and views dropped before keep the purge setting of their drop.
+    /// Default: true
+    #[serde(default)]
+    pub purge_requested: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GetNamespaceUsageQuery {
+    /// Include tables and views of all child namespaces, recursively.
+    /// Default: false
+    #[serde(default)]
+    pub include_descendants: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct NamespaceUsageResponse {
+    /// ID of the namespace
+    pub namespace_id: uuid::Uuid,
+    /// Whether child namespaces are included
+    pub include_descendants: bool,
+    /// Number of tables
+    pub tables: i64,
+    /// Number of views
+    pub views: i64,
+    /// Total size in bytes of the data files referenced by the current snapshots of the tables,
+    /// as reported by the `total-files-size` snapshot summary field.
+    pub data_size_bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct NamespacePropertiesLevel {
+    /// Name of the namespace
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    /// ID of the namespace
+    pub namespace_id: uuid::Uuid,
+    /// Properties set on this namespace
+    pub properties: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct NamespaceEffectivePropertiesResponse {
+    /// The namespace and its ancestors with their own properties,
+    /// ordered from the root to the requested namespace.
+    pub chain: Vec<NamespacePropertiesLevel>,
+    /// Properties of all namespaces in the chain merged.
+    /// Values of child namespaces override those of their ancestors.
+    pub effective_properties: HashMap<String, String>,
+}
+
+impl From<NamespacePropertyChain> for NamespaceEffectivePropertiesResponse {
+    fn from(chain: NamespacePropertyChain) -> Self {
+        Self {
+            chain: chain
+                .levels
+                .into_iter()
+                .map(|level| NamespacePropertiesLevel {
+                    namespace: level.namespace,
+                    namespace_id: *level.namespace_id,
+                    properties: level.properties.unwrap_or_default(),
+                })
+                .collect(),
+            effective_properties: chain.effective_properties,
+        }
+    }
+}
+
 impl ListDeletedTabularsQuery {
     #[must_use]
     pub fn pagination_query(&self) -> PaginationQuery {
@@ -84,6 +223,10 @@ pub struct CreateWarehouseRequest {
     /// 7 days expiration.
     #[serde(default)]
     pub delete_profile: TabularDeleteProfile,
+    /// Free-form operational metadata, such as the owning team or environment.
+    /// Keys must not be empty.
+    #[serde(default)]
+    pub annotations: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, ToSchema)]
@@ -172,6 +315,10 @@ pub struct ListWarehousesRequest {
     #[serde(default)]
     #[param(value_type=Option::<uuid::Uuid>)]
     pub project_id: Option<ProjectId>,
+    /// Optional filter to return only warehouses with the specified
+    /// annotation, formatted as `key=value`, for example `env=prod`.
+    #[serde(default)]
+    pub annotation: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, ToSchema)]
@@ -187,6 +334,417 @@ pub struct UpdateWarehouseDeleteProfileRequest {
     pub delete_profile: TabularDeleteProfile,
 }
 
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseFeatureFlagsRequest {
+    /// Feature flags to set for the warehouse. Flags that are not
+    /// specified remain unchanged, `null` resets a flag to the server default.
+    /// Unknown flags are ignored.
+    #[schema(value_type = HashMap<String, Option<bool>>)]
+    pub feature_flags: HashMap<String, Option<bool>>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseAllowedSchemesRequest {
+    /// Location schemes allowed for tables, views and namespaces of the warehouse,
+    /// for example `["s3"]`. Must be supported by the storage profile and include the
+    /// scheme of its base location. `null` allows all schemes supported by the storage profile.
+    pub allowed_schemes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseStoragePrefixOverridesRequest {
+    /// Locations that namespaces of the warehouse may use as storage prefix instead
+    /// of the base location of the storage profile, for example
+    /// `["s3://eu-bucket/lakekeeper"]`. Locations must use an allowed scheme of the warehouse.
+    /// The locations are accessed with the storage credential of the warehouse.
+    pub storage_prefix_overrides: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseImportPrefixesRequest {
+    /// Locations outside of the base location of the storage profile from which
+    /// tables may be registered, for example `["s3://shared-bucket/exports"]`.
+    /// Tables registered from these locations are read-only and their data is never
+    /// purged. Locations must use an allowed scheme of the warehouse and must not
+    /// overlap with its base location. The locations are accessed with the storage
+    /// credential of the warehouse.
+    pub import_prefixes: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseDefaultNamespaceRequest {
+    /// Namespace that table operations target if they address the default namespace
+    /// alias (`_default` unless configured otherwise), for example `["analytics", "sales"]`.
+    /// `null` disables the alias.
+    #[schema(value_type = Option<Vec<String>>)]
+    pub default_namespace: Option<NamespaceIdent>,
+    /// Create the namespace and its missing ancestors if it does not exist.
+    /// Otherwise the namespace must exist. Default: `false`.
+    #[serde(default)]
+    pub create_if_missing: bool,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetNamespaceStoragePrefixRequest {
+    /// Location below which tables and views of the namespace and of its child
+    /// namespaces are placed. Must be one of the storage prefix overrides of the
+    /// warehouse or located below one of them. `null` removes the override.
+    pub storage_prefix: Option<String>,
+    /// Region of the bucket of the storage prefix if it differs from the region
+    /// of the storage profile of the warehouse. Only supported for S3.
+    #[serde(default)]
+    pub storage_region: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetNamespaceCredentialTtlRequest {
+    /// Default lifetime in seconds of credentials vended for tables of the namespace
+    /// and of its child namespaces. Must be within the bounds configured for the
+    /// server. `null` removes the override.
+    pub credential_ttl_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BatchUpdateNamespacePropertiesRequest {
+    /// Property changes to apply. Each namespace may only be listed once.
+    pub namespaces: Vec<NamespacePropertiesUpdate>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct NamespacePropertiesUpdate {
+    /// Namespace to update.
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    /// Properties to set or overwrite.
+    #[serde(default)]
+    pub updates: Option<HashMap<String, String>>,
+    /// Properties to remove.
+    #[serde(default)]
+    pub removals: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateNamespaceHierarchyRequest {
+    /// Namespace to create. Missing ancestors are created as well.
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    /// Properties of the namespace. Only applied if the namespace itself is created,
+    /// missing ancestors are created without properties.
+    #[serde(default)]
+    pub properties: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateNamespaceHierarchyResponse {
+    /// All levels of the namespace, starting with the top-level namespace.
+    pub namespaces: Vec<NamespaceHierarchyLevel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct NamespaceHierarchyLevel {
+    /// Name of the namespace
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    /// ID of the namespace
+    pub namespace_id: uuid::Uuid,
+    /// Whether the namespace was created by this request or existed before
+    pub created: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BatchUpdateNamespacePropertiesResponse {
+    /// Results in the order of the request.
+    pub namespaces: Vec<NamespacePropertiesUpdateResult>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct NamespacePropertiesUpdateResult {
+    /// Name of the namespace
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    /// ID of the namespace
+    pub namespace_id: uuid::Uuid,
+    /// Keys that were updated
+    pub updated: Vec<String>,
+    /// Keys that were removed
+    pub removed: Vec<String>,
+    /// Keys requested for removal that were not set
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PreviewTableLocationRequest {
+    /// Namespace the table would be created in.
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    /// Name of the table.
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PreviewTableLocationResponse {
+    /// Location the table would receive, with `{table-id}` standing in for the
+    /// ID that is assigned when the table is created.
+    pub location: String,
+    /// Location in which the folder of the table would be created.
+    pub parent_location: String,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ValidateSchemaRequest {
+    /// Proposed schema in the JSON representation of the Iceberg REST specification.
+    #[schema(value_type = Object)]
+    pub schema: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ValidateSchemaResponse {
+    /// Whether the schema complies with all rules. Tables can be created with valid schemas.
+    pub valid: bool,
+    /// Rules violated by the schema. Empty if the schema is valid.
+    pub violations: Vec<SchemaViolation>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RenameNamespaceRequest {
+    /// Namespace to rename.
+    #[schema(value_type = Vec<String>)]
+    pub source: NamespaceIdent,
+    /// New name of the namespace. The parent of the new name must exist.
+    #[schema(value_type = Vec<String>)]
+    pub destination: NamespaceIdent,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehousePurgeGracePeriodRequest {
+    /// Seconds to wait before deleting the storage of dropped and purged tables and views.
+    /// Until then, the data can be recovered by registering the table again.
+    /// `null` deletes the storage immediately.
+    #[schema(minimum = 0, maximum = 31_536_000)]
+    pub purge_grace_period_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseManifestMergeRequest {
+    /// If a committed snapshot references more manifests than this,
+    /// Lakekeeper merges its small manifests as part of the commit.
+    /// Writers can skip the merge for a commit by setting the
+    /// `lakekeeper.skip-manifest-merge` snapshot summary property to `true`.
+    /// `null` disables merging.
+    #[schema(minimum = 1)]
+    pub manifest_merge_threshold: Option<i32>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseSchemaEvolutionPolicyRequest {
+    /// Schema changes allowed when committing to tables of the warehouse.
+    /// Changes that are not explicitly forbidden are allowed.
+    pub schema_evolution_policy: SchemaEvolutionPolicy,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseAllowedFileFormatsRequest {
+    /// File formats allowed for data and delete files of tables in the warehouse,
+    /// for example `["parquet"]`. The first format is used as default write format
+    /// of new tables. `null` allows all formats.
+    pub allowed_file_formats: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseNamespacePropertySchemaRequest {
+    /// Constraints on namespace property values, keyed by property name, for example
+    /// `{"ttl_days": {"type": "integer", "min": 1}}`. Replaces the previous schema.
+    /// Properties without a constraint accept any value.
+    pub namespace_property_schema: NamespacePropertySchema,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseAnnotationsRequest {
+    /// Free-form operational metadata of the warehouse. Replaces the previous annotations.
+    pub annotations: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseCommitRateLimitRequest {
+    /// Maximum rate of commits to tables and views of the warehouse.
+    /// Further commits fail with `429 Too Many Requests`.
+    /// `null` disables throttling.
+    pub commit_rate_limit: Option<CommitRateLimit>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseSnapshotLimitRequest {
+    /// Maximum number of snapshots per table. Commits that would exceed it expire
+    /// the oldest snapshots of the table.
+    /// `null` allows an unlimited number of snapshots.
+    pub snapshot_limit: Option<SnapshotLimit>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseAllowedAuthenticationMethodsRequest {
+    /// Authentication methods requests to the warehouse may use.
+    /// Requests authenticated differently are rejected with 403.
+    /// `null` allows all methods.
+    pub allowed_authentication_methods: Option<Vec<AuthenticationMethod>>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseCredentialRefreshRequest {
+    /// Seconds before the expiry of vended credentials at which clients should
+    /// request new credentials. `null` uses the server default.
+    #[schema(minimum = 0, maximum = 86_400)]
+    pub refresh_before_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseEmptyNamespaceCleanupRequest {
+    /// Seconds after which namespaces without tables, views and child namespaces
+    /// are removed. `null` disables the cleanup.
+    #[schema(minimum = 1, maximum = 315_360_000)]
+    pub cleanup_after_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseDefaultViewPropertiesRequest {
+    /// Properties applied to new views of the warehouse. Replaces the previous defaults.
+    /// Existing views are not changed.
+    pub default_view_properties: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseMaxTablesPerNamespaceRequest {
+    /// Maximum number of tables in a single namespace.
+    /// Creating or registering further tables fails with a conflict.
+    /// `null` allows an unlimited number of tables.
+    #[schema(minimum = 1)]
+    pub max_tables_per_namespace: Option<i32>,
+    /// Whether views count towards the limit. Defaults to `false`.
+    #[serde(default)]
+    pub include_views: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PendingPurgeStatus {
+    /// Waiting for the grace period to elapse or for a worker to pick it up.
+    Pending,
+    /// Currently being deleted.
+    Running,
+    /// All attempts failed. The remaining objects are listed in `last-error`.
+    Failed,
+}
+
+impl From<TaskStatus> for PendingPurgeStatus {
+    fn from(status: TaskStatus) -> Self {
+        match status {
+            TaskStatus::Running => Self::Running,
+            TaskStatus::Failed => Self::Failed,
+            TaskStatus::Pending | TaskStatus::Finished | TaskStatus::Cancelled => Self::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PendingPurgeResponse {
+    /// ID of the dropped table or view.
+    pub tabular_id: Uuid,
+    /// Type of the dropped tabular.
+    pub typ: TabularType,
+    /// Location whose content is deleted.
+    pub location: String,
+    /// Status of the purge.
+    pub status: PendingPurgeStatus,
+    /// Number of attempts made so far.
+    pub attempt: i32,
+    /// Earliest time at which the storage is deleted.
+    pub purge_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Error of the last failed attempt, including the objects that remained.
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListPendingPurgesResponse {
+    /// Purges whose storage has not been deleted yet.
+    pub pending_purges: Vec<PendingPurgeResponse>,
+}
+
+impl From<PendingPurge> for PendingPurgeResponse {
+    fn from(purge: PendingPurge) -> Self {
+        Self {
+            tabular_id: purge.tabular_id,
+            typ: purge.tabular_type,
+            location: purge.tabular_location,
+            status: purge.status.into(),
+            attempt: purge.attempt,
+            purge_after: purge.purge_after,
+            last_error: purge.last_error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableLocationCollisionResponse {
+    /// ID of the table whose location overlaps with `parent-table-id`.
+    pub table_id: Uuid,
+    pub location: String,
+    /// ID of the table whose location equals or contains `location`.
+    pub parent_table_id: Uuid,
+    pub parent_location: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListTableLocationCollisionsResponse {
+    /// Pairs of tables whose locations overlap.
+    pub collisions: Vec<TableLocationCollisionResponse>,
+}
+
+impl From<TableLocationCollision> for TableLocationCollisionResponse {
+    fn from(collision: TableLocationCollision) -> Self {
+        Self {
+            table_id: *collision.table_id,
+            location: collision.location,
+            parent_table_id: *collision.parent_table_id,
+            parent_location: collision.parent_location,
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize, ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct RenameProjectRequest {
@@ -209,6 +767,47 @@ pub struct GetWarehouseResponse {
     pub delete_profile: TabularDeleteProfile,
     /// Whether the warehouse is active.
     pub status: WarehouseStatus,
+    /// Effective feature flags of the warehouse, including server defaults.
+    pub feature_flags: BTreeMap<WarehouseFeatureFlag, bool>,
+    /// Location schemes allowed for the warehouse.
+    pub allowed_schemes: Vec<String>,
+    /// Seconds to wait before deleting the storage of dropped and purged tabulars.
+    pub purge_grace_period_seconds: Option<i64>,
+    /// Number of manifests of a committed snapshot above which small manifests are merged.
+    pub manifest_merge_threshold: Option<i32>,
+    /// Maximum number of tables in a single namespace.
+    pub max_tables_per_namespace: Option<i32>,
+    /// Whether views count towards `max-tables-per-namespace`.
+    pub max_tables_per_namespace_includes_views: bool,
+    /// Locations that namespaces may use as storage prefix.
+    pub storage_prefix_overrides: Vec<String>,
+    /// Schema changes allowed on commit.
+    pub schema_evolution_policy: SchemaEvolutionPolicy,
+    /// File formats allowed for data and delete files. `null` allows all formats.
+    pub allowed_file_formats: Option<Vec<String>>,
+    /// Constraints on namespace property values, keyed by property name.
+    pub namespace_property_schema: NamespacePropertySchema,
+    /// Free-form operational metadata of the warehouse.
+    pub annotations: HashMap<String, String>,
+    /// Properties applied to new views unless set on the namespace or the view.
+    pub default_view_properties: HashMap<String, String>,
+    /// Maximum rate of commits to tables and views. `null` disables throttling.
+    pub commit_rate_limit: Option<CommitRateLimit>,
+    /// Seconds before the expiry of vended credentials at which clients should
+    /// request new credentials. `null` uses the server default.
+    pub credential_refresh_before_seconds: Option<i64>,
+    /// Authentication methods requests to the warehouse may use.
+    /// `null` allows all methods.
+    pub allowed_authentication_methods: Option<Vec<AuthenticationMethod>>,
+    /// Seconds after which empty namespaces are removed. `null` disables the cleanup.
+    pub empty_namespace_cleanup_after_seconds: Option<i64>,
+    /// Locations from which read-only tables may be registered.
+    pub import_prefixes: Vec<String>,
+    /// Namespace addressed by the default namespace alias. `null` if not set.
+    #[schema(value_type = Option<Vec<String>>)]
+    pub default_namespace: Option<NamespaceIdent>,
+    /// Maximum number of snapshots per table. `null` if not limited.
+    pub snapshot_limit: Option<SnapshotLimit>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, ToSchema)]
@@ -226,9 +825,53 @@ pub struct UpdateWarehouseCredentialRequest {
     pub new_storage_credential: Option<StorageCredential>,
 }
 
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RotateWarehouseCredentialRequest {
+    /// New storage credential to use for the warehouse.
+    pub new_storage_credential: StorageCredential,
+    /// Seconds the previous credential remains stored after the rotation.
+    /// Defaults to the server's `storage_credential_rotation_overlap`, at most 30 days.
+    #[schema(minimum = 0, maximum = 2_592_000)]
+    pub overlap_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RotateWarehouseCredentialResponse {
+    /// Time until which the previous credential remains stored.
+    /// `null` if the warehouse had no credential before.
+    pub previous_credential_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Secret referenced by a warehouse. The value of the secret is never returned.
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseSecretResponse {
+    /// ID of the secret in the secret store.
+    pub secret_id: Uuid,
+    /// Purpose for which the warehouse references the secret.
+    pub usage: WarehouseSecretUsage,
+    /// Type of the stored credential, e.g. `s3/access-key`.
+    /// `null` for envelope-encrypted credentials stored before the type was recorded.
+    pub credential_type: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Time the secret was last modified, or created if it was never modified.
+    pub last_rotated_at: chrono::DateTime<chrono::Utc>,
+    /// Time until which the secret is retained. `null` if it is retained indefinitely.
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListWarehouseSecretsResponse {
+    /// Secrets referenced by the warehouse.
+    pub secrets: Vec<WarehouseSecretResponse>,
+}
+
 impl axum::response::IntoResponse for CreateWarehouseResponse {
     fn into_response(self) -> axum::http::Response<axum::body::Body> {
-        (http::StatusCode::CREATED, axum::Json(self)).into_response()
+        (http::StatusCode::CREATED, Json(self)).into_response()
     }
 }
 
@@ -281,6 +924,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             mut storage_profile,
             storage_credential,
             delete_profile,
+            annotations,
         } = request;
         let project_id = project_id
             .or(*DEFAULT_PROJECT_ID)
@@ -302,33 +946,44 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         // ------------------- Business Logic -------------------
         validate_warehouse_name(&warehouse_name)?;
+        validate_warehouse_annotations(&annotations)?;
         storage_profile.normalize()?;
         storage_profile
             .validate_access(storage_credential.as_ref(), None)
             .await?;
 
         let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
-        let secret_id = if let Some(storage_credential) = storage_credential {
-            Some(
-                context
-                    .v1_state
-                    .secrets
-                    .create_secret(storage_credential)
-                    .await?,
-            )
-        } else {
-            None
-        };
-
         let warehouse_id = C::create_warehouse(
             warehouse_name,
             project_id,
-            storage_profile,
+            storage_profile.clone(),
             delete_profile,
-            secret_id,
+            None,
             transaction.transaction(),
         )
         .await?;
+        // The secret is created once the warehouse exists, as it may be encrypted
+        // with the data key of the warehouse.
+        if let Some(storage_credential) = storage_credential {
+            let secret_id = create_storage_secret::<C, _>(
+                warehouse_id,
+                storage_credential,
+                &context.v1_state.secrets,
+                &mut transaction,
+            )
+            .await?;
+            C::update_storage_profile(
+                warehouse_id,
+                storage_profile,
+                Some(secret_id),
+                transaction.transaction(),
+            )
+            .await?;
+        }
+        if !annotations.is_empty() {
+            C::set_warehouse_annotations(warehouse_id, &annotations, transaction.transaction())
+                .await?;
+        }
         authorizer
             .create_warehouse(&request_metadata, warehouse_id, project_id)
             .await?;
@@ -343,6 +998,13 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<ListWarehousesResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let annotation_filter = request
+            .annotation
+            .as_deref()
+            .map(parse_annotation_filter)
+            .transpose()?;
+
         // ------------------- AuthZ -------------------
         let project_id = request_metadata.require_project_id(request.project_id)?;
 
@@ -358,9 +1020,12 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         // ------------------- Business Logic -------------------
         let mut trx = C::Transaction::begin_read(context.v1_state.catalog).await?;
 
-        let warehouses =
+        let mut warehouses =
             C::list_warehouses(project_id, request.warehouse_status, trx.transaction()).await?;
         trx.commit().await?;
+        if let Some((key, value)) = annotation_filter {
+            warehouses.retain(|w| w.annotations.get(key).map(String::as_str) == Some(value));
+        }
 
         let warehouses = futures::future::try_join_all(warehouses.iter().map(|w| {
             authorizer.is_allowed_warehouse_action(
@@ -423,6 +1088,13 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .await?;
 
         // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+        warehouse
+            .feature_flags
+            .require(WarehouseFeatureFlag::WarehouseStatistics)?;
+
         C::get_warehouse_stats(
             warehouse_id,
             query.to_pagination_query(),
@@ -431,84 +1103,1586 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         .await
     }
 
-    async fn delete_warehouse(
+    async fn get_namespace_usage(
         warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        query: GetNamespaceUsageQuery,
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
-    ) -> Result<()> {
+    ) -> Result<NamespaceUsageResponse> {
         // ------------------- AuthZ -------------------
         let authorizer = context.v1_state.authz;
         authorizer
-            .require_warehouse_action(
+            .require_namespace_action(
                 &request_metadata,
-                warehouse_id,
-                &CatalogWarehouseAction::CanDelete,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanGetMetadata,
             )
             .await?;
 
         // ------------------- Business Logic -------------------
-        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
-        C::delete_warehouse(warehouse_id, transaction.transaction()).await?;
-        authorizer
-            .delete_warehouse(&request_metadata, warehouse_id)
+        let include_descendants = query.include_descendants;
+        let key = (warehouse_id, namespace_id, include_descendants);
+        let usage = if let Some(usage) = NAMESPACE_USAGE_CACHE.get(&key) {
+            usage
+        } else {
+            let mut transaction =
+                C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+            let usage = C::namespace_usage(
+                warehouse_id,
+                namespace_id,
+                include_descendants,
+                transaction.transaction(),
+            )
             .await?;
-        transaction.commit().await?;
+            transaction.commit().await?;
 
-        Ok(())
-    }
-    async fn rename_warehouse(
+            NAMESPACE_USAGE_CACHE.insert(key, usage);
+            usage
+        };
+
+        Ok(NamespaceUsageResponse {
+            namespace_id: *namespace_id,
+            include_descendants,
+            tables: usage.tables,
+            views: usage.views,
+            data_size_bytes: usage.data_size_bytes,
+        })
+    }
+
+    async fn get_namespace_effective_properties(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<NamespaceEffectivePropertiesResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let namespace =
+            C::get_namespace(warehouse_id, namespace_id, transaction.transaction()).await?;
+        let chain = resolve_namespace_properties::<C>(namespace, &mut transaction).await?;
+        transaction.commit().await?;
+
+        Ok(chain.into())
+    }
+
+    /// Update the properties of several namespaces of a warehouse in a single
+    /// transaction. Either all namespaces are updated or none: the whole batch
+    /// fails if any namespace does not exist, is not authorized or any change is invalid.
+    async fn batch_update_namespace_properties(
+        warehouse_id: WarehouseIdent,
+        request: BatchUpdateNamespacePropertiesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<BatchUpdateNamespacePropertiesResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let mut seen = HashSet::new();
+        let mut changes = Vec::with_capacity(request.namespaces.len());
+        for update in request.namespaces {
+            validate_namespace_ident(&update.namespace)?;
+            if !seen.insert(
+                update
+                    .namespace
+                    .iter()
+                    .map(|s| s.to_lowercase())
+                    .collect::<Vec<_>>(),
+            ) {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Namespace '{}' is listed more than once",
+                        update.namespace.to_url_string()
+                    ),
+                    "DuplicateNamespaceInBatch",
+                    None,
+                )
+                .into());
+            }
+            let namespace_changes =
+                NamespacePropertyChanges::try_from_request(UpdateNamespacePropertiesRequest {
+                    updates: update.updates,
+                    removals: update.removals,
+                })?;
+            changes.push((update.namespace, namespace_changes));
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let mut namespace_ids = Vec::with_capacity(changes.len());
+        for (namespace, _) in &changes {
+            namespace_ids.push(
+                authorized_namespace_ident_to_id::<C, _>(
+                    authorizer.clone(),
+                    &request_metadata,
+                    &warehouse_id,
+                    namespace,
+                    &CatalogNamespaceAction::CanUpdateProperties,
+                    transaction.transaction(),
+                )
+                .await?,
+            );
+        }
+
+        // ------------------- Business Logic -------------------
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let mut new_properties = Vec::with_capacity(changes.len());
+        let mut results = Vec::with_capacity(changes.len());
+        for ((namespace, namespace_changes), namespace_id) in changes.into_iter().zip(namespace_ids)
+        {
+            let previous =
+                C::get_namespace(warehouse_id, namespace_id, transaction.transaction()).await?;
+            let (properties, response) = namespace_changes
+                .apply(&warehouse.namespace_property_schema, previous.properties)?;
+            new_properties.push((namespace_id, properties));
+            results.push(NamespacePropertiesUpdateResult {
+                namespace,
+                namespace_id: *namespace_id,
+                updated: response.updated,
+                removed: response.removed,
+                missing: response.missing.unwrap_or_default(),
+            });
+        }
+        C::update_many_namespace_properties(
+            warehouse_id,
+            new_properties,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(BatchUpdateNamespacePropertiesResponse {
+            namespaces: results,
+        })
+    }
+
+    /// Create a namespace together with all of its missing ancestors in a single
+    /// transaction. Existing levels are left unchanged. If any level cannot be
+    /// created, for example because it was created concurrently, no namespace is created.
+    async fn create_namespace_hierarchy(
+        warehouse_id: WarehouseIdent,
+        request: CreateNamespaceHierarchyRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<CreateNamespaceHierarchyResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let CreateNamespaceHierarchyRequest {
+            namespace,
+            mut properties,
+        } = request;
+        validate_namespace_ident(&namespace)?;
+        properties
+            .as_ref()
+            .map(|p| validate_namespace_properties_keys(p.keys()))
+            .transpose()?;
+        require_namespace_not_reserved(&namespace)?;
+
+        // Top-level namespace first
+        let mut levels = vec![namespace];
+        while let Some(parent) = levels.last().and_then(NamespaceIdent::parent) {
+            levels.push(parent);
+        }
+        levels.reverse();
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        if levels.len() > 1 {
+            // Pre-check before resolving the ancestors
+            authorizer
+                .require_warehouse_action(
+                    &request_metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanUse,
+                )
+                .await?;
+        }
+
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let mut existing = Vec::with_capacity(levels.len());
+        for level in &levels {
+            match C::namespace_to_id(warehouse_id, level, transaction.transaction()).await? {
+                Some(namespace_id) => existing.push(namespace_id),
+                None => break,
+            }
+        }
+
+        // Creating namespaces requires permission on the deepest existing level only,
+        // all further levels are created below namespaces created by this request.
+        let deepest_existing = existing.last().copied();
+        if existing.len() == levels.len() {
+            authorizer
+                .require_namespace_action(
+                    &request_metadata,
+                    Ok(deepest_existing),
+                    &CatalogNamespaceAction::CanGetMetadata,
+                )
+                .await?;
+        } else if deepest_existing.is_some() {
+            authorizer
+                .require_namespace_action(
+                    &request_metadata,
+                    Ok(deepest_existing),
+                    &CatalogNamespaceAction::CanCreateNamespace,
+                )
+                .await?;
+        } else {
+            authorizer
+                .require_warehouse_action(
+                    &request_metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanCreateNamespace,
+                )
+                .await?;
+        }
+
+        // ------------------- Business Logic -------------------
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let depth = levels.len();
+        let mut parent = deepest_existing.map_or(
+            NamespaceParent::Warehouse(warehouse_id),
+            NamespaceParent::Namespace,
+        );
+        let mut created = Vec::with_capacity(depth - existing.len());
+        let mut results = Vec::with_capacity(depth);
+        for (i, level) in levels.into_iter().enumerate() {
+            if let Some(namespace_id) = existing.get(i) {
+                results.push(NamespaceHierarchyLevel {
+                    namespace: level,
+                    namespace_id: **namespace_id,
+                    created: false,
+                });
+                continue;
+            }
+
+            let namespace_id = NamespaceIdentUuid::default();
+            let level_properties = if i + 1 == depth {
+                properties.take()
+            } else {
+                None
+            };
+            let level_properties =
+                new_namespace_properties(level_properties, &warehouse, namespace_id)?;
+            C::create_namespace(
+                warehouse_id,
+                namespace_id,
+                CreateNamespaceRequest {
+                    namespace: level.clone(),
+                    properties: Some(level_properties),
+                },
+                transaction.transaction(),
+            )
+            .await?;
+            created.push((namespace_id, parent));
+            parent = NamespaceParent::Namespace(namespace_id);
+            results.push(NamespaceHierarchyLevel {
+                namespace: level,
+                namespace_id: *namespace_id,
+                created: true,
+            });
+        }
+
+        for (namespace_id, parent) in created {
+            authorizer
+                .create_namespace(&request_metadata, namespace_id, parent)
+                .await?;
+        }
+        transaction.commit().await?;
+
+        Ok(CreateNamespaceHierarchyResponse {
+            namespaces: results,
+        })
+    }
+
+    /// Compute the location a table created by the caller would receive if no
+    /// location is requested, without creating anything.
+    async fn preview_table_location(
+        warehouse_id: WarehouseIdent,
+        request: PreviewTableLocationRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<PreviewTableLocationResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let PreviewTableLocationRequest { namespace, name } = request;
+        validate_table_or_view_ident(&TableIdent::new(namespace.clone(), name))?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let namespace_id = authorized_namespace_ident_to_id::<C, _>(
+            authorizer,
+            &request_metadata,
+            &warehouse_id,
+            &namespace,
+            &CatalogNamespaceAction::CanCreateTable,
+            transaction.transaction(),
+        )
+        .await?;
+
+        // ------------------- Business Logic -------------------
+        let namespace =
+            C::get_namespace(warehouse_id, namespace_id, transaction.transaction()).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        require_active_warehouse(warehouse.status)?;
+        let parent_location = default_table_parent_location::<C>(
+            &namespace,
+            &warehouse,
+            &request_metadata,
+            &mut transaction,
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(PreviewTableLocationResponse {
+            location: format!("{parent_location}/{{table-id}}"),
+            parent_location: parent_location.to_string(),
+        })
+    }
+
+    /// Check a proposed schema against the structural rules of Iceberg, such as
+    /// unique field IDs, without creating anything.
+    async fn validate_schema(
+        warehouse_id: WarehouseIdent,
+        request: ValidateSchemaRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ValidateSchemaResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let violations = validate_schema(&request.schema);
+        Ok(ValidateSchemaResponse {
+            valid: violations.is_empty(),
+            violations,
+        })
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn delete_warehouse(
+        warehouse_id: WarehouseIdent,
+        query: DeleteWarehouseQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        let DeleteWarehouseQuery {
+            force,
+            purge_requested,
+        } = query;
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanDelete,
+            )
+            .await?;
+        if force {
+            authorizer
+                .require_server_action(
+                    &request_metadata,
+                    &CatalogServerAction::CanForceDeleteWarehouse,
+                )
+                .await?;
+        }
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog.clone()).await?;
+        // The storage profile is needed to purge data once the warehouse is gone
+        let warehouse = if force {
+            Some(C::require_warehouse(warehouse_id, transaction.transaction()).await?)
+        } else {
+            None
+        };
+        let contents = C::delete_warehouse(warehouse_id, force, transaction.transaction()).await?;
+
+        let purge = purge_requested.unwrap_or(true);
+        let mut purge_locations = vec![];
+        for location in contents
+            .tabulars
+            .iter()
+            .filter(|t| t.dropped_with_purge.unwrap_or(purge))
+            .map(|t| &t.location)
+            .chain(&contents.pending_purge_locations)
+        {
+            let location = location.parse::<Location>().map_err(|e| {
+                ErrorModel::internal(
+                    format!("Failed to parse location '{location}' of deleted tabular"),
+                    "ParseError",
+                    Some(Box::new(e)),
+                )
+            })?;
+            // Data below import prefixes is not owned by the warehouse
+            if warehouse
+                .as_ref()
+                .is_some_and(|w| w.is_import_location(&location))
+            {
+                continue;
+            }
+            // The data may be used by a tabular of another warehouse
+            if !C::is_tabular_location_in_use(&location, transaction.transaction()).await? {
+                purge_locations.push(location);
+            }
+        }
+
+        authorizer
+            .delete_warehouse(&request_metadata, warehouse_id)
+            .await?;
+        transaction.commit().await?;
+
+        // Relations of the contents are removed after the commit, as for dropped tables
+        for namespace_id in &contents.namespace_ids {
+            if let Err(e) = authorizer
+                .delete_namespace(&request_metadata, *namespace_id)
+                .await
+            {
+                tracing::error!(
+                    "Failed to delete permissions of namespace {namespace_id} of deleted warehouse {warehouse_id}: {:?}",
+                    e.error
+                );
+            }
+        }
+        for tabular in &contents.tabulars {
+            let result = match tabular.tabular_id {
+                TabularIdentUuid::Table(id) => authorizer.delete_table(id.into()).await,
+                TabularIdentUuid::View(id) => authorizer.delete_view(id.into()).await,
+            };
+            if let Err(e) = result {
+                tracing::error!(
+                    "Failed to delete permissions of {} of deleted warehouse {warehouse_id}: {:?}",
+                    tabular.tabular_id,
+                    e.error
+                );
+            }
+        }
+
+        // The deletion is committed, so failing to queue the purge only leaves
+        // the data behind and does not fail the request.
+        if let Some(warehouse) = warehouse.filter(|_| !purge_locations.is_empty()) {
+            if let Err(e) = context
+                .v1_state
+                .queues
+                .queue_deleted_warehouse_purge(DeletedWarehousePurgeInput {
+                    warehouse_ident: warehouse_id,
+                    storage_profile: warehouse.storage_profile,
+                    storage_secret_id: warehouse.storage_secret_id,
+                    locations: purge_locations,
+                })
+                .await
+            {
+                tracing::error!(
+                    "Failed to queue purge of data of deleted warehouse {warehouse_id}: {:?}",
+                    e.error
+                );
+            }
+        }
+
+        tracing::info!(
+            "{} deleted warehouse {warehouse_id} with {} namespaces and {} tables and views",
+            request_metadata.actor(),
+            contents.namespace_ids.len(),
+            contents.tabulars.len(),
+        );
+        let _ = context
+            .v1_state
+            .publisher
+            .publish_warehouse_event(
+                Uuid::now_v7(),
+                "warehouse.deleted",
+                serde_json::json!({
+                    "force": force,
+                    "deleted-namespaces": contents.namespace_ids.len(),
+                    "deleted-tabulars": contents.tabulars.len(),
+                }),
+                WarehouseEventMetadata {
+                    warehouse_id,
+                    actor: request_metadata.actor().to_string(),
+                    trace_id: request_metadata.request_id().to_string(),
+                },
+            )
+            .await;
+
+        Ok(())
+    }
+    async fn rename_warehouse(
+        warehouse_id: WarehouseIdent,
+        request: RenameWarehouseRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanRename,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        validate_warehouse_name(&request.new_name)?;
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+
+        C::rename_warehouse(warehouse_id, &request.new_name, transaction.transaction()).await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_delete_profile(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseDeleteProfileRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifySoftDeletion,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_deletion_profile(
+            warehouse_id,
+            &request.delete_profile,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_feature_flags(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseFeatureFlagsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let mut feature_flags = C::require_warehouse(warehouse_id, transaction.transaction())
+            .await?
+            .feature_flags;
+        for (name, enabled) in request.feature_flags {
+            if let Some(flag) = WarehouseFeatureFlag::parse(&name) {
+                feature_flags.set(flag, enabled);
+            } else {
+                tracing::warn!(
+                    "Ignoring unknown feature flag '{name}' for warehouse '{warehouse_id}'"
+                );
+            }
+        }
+        C::set_warehouse_feature_flags(warehouse_id, &feature_flags, transaction.transaction())
+            .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_purge_grace_period(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehousePurgeGracePeriodRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let purge_grace_period = request
+            .purge_grace_period_seconds
+            .map(|seconds| {
+                chrono::Duration::try_seconds(seconds)
+                    .filter(|_| (0..=MAX_PURGE_GRACE_PERIOD_SECONDS).contains(&seconds))
+                    .ok_or_else(|| {
+                        ErrorModel::bad_request(
+                            format!("Purge grace period must be between 0 and {MAX_PURGE_GRACE_PERIOD_SECONDS} seconds"),
+                            "InvalidPurgeGracePeriod",
+                            None,
+                        )
+                    })
+            })
+            .transpose()?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifySoftDeletion,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_purge_grace_period(
+            warehouse_id,
+            purge_grace_period,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_manifest_merge(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseManifestMergeRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        if request
+            .manifest_merge_threshold
+            .is_some_and(|threshold| threshold < 1)
+        {
+            return Err(ErrorModel::bad_request(
+                "Manifest merge threshold must be at least 1",
+                "InvalidManifestMergeThreshold",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_manifest_merge_threshold(
+            warehouse_id,
+            request.manifest_merge_threshold,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_schema_evolution_policy(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseSchemaEvolutionPolicyRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_schema_evolution_policy(
+            warehouse_id,
+            request.schema_evolution_policy,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_allowed_file_formats(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseAllowedFileFormatsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let allowed_file_formats = request
+            .allowed_file_formats
+            .map(validate_allowed_file_formats)
+            .transpose()?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_allowed_file_formats(
+            warehouse_id,
+            allowed_file_formats.as_deref(),
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_annotations(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseAnnotationsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        validate_warehouse_annotations(&request.annotations)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_annotations(
+            warehouse_id,
+            &request.annotations,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_commit_rate_limit(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseCommitRateLimitRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        request
+            .commit_rate_limit
+            .as_ref()
+            .map(CommitRateLimit::validate)
+            .transpose()?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_commit_rate_limit(
+            warehouse_id,
+            request.commit_rate_limit,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_snapshot_limit(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseSnapshotLimitRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        request
+            .snapshot_limit
+            .as_ref()
+            .map(SnapshotLimit::validate)
+            .transpose()?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateSnapshotLimit,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_snapshot_limit(
+            warehouse_id,
+            request.snapshot_limit,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_allowed_authentication_methods(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseAllowedAuthenticationMethodsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let allowed_authentication_methods = request
+            .allowed_authentication_methods
+            .map(|methods| methods.into_iter().unique().collect::<Vec<_>>());
+        if allowed_authentication_methods
+            .as_ref()
+            .is_some_and(Vec::is_empty)
+        {
+            return Err(ErrorModel::bad_request(
+                "At least one authentication method must be allowed. Use null to allow all methods.",
+                "EmptyAllowedAuthenticationMethods",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateAuthenticationMethods,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_allowed_authentication_methods(
+            warehouse_id,
+            allowed_authentication_methods.as_deref(),
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+        invalidate_allowed_authentication_methods(warehouse_id);
+
+        Ok(())
+    }
+
+    async fn update_warehouse_credential_refresh(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseCredentialRefreshRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let refresh_before = request
+            .refresh_before_seconds
+            .map(|seconds| {
+                chrono::Duration::try_seconds(seconds)
+                    .filter(|_| (0..=MAX_CREDENTIAL_REFRESH_BEFORE_SECONDS).contains(&seconds))
+                    .ok_or_else(|| {
+                        ErrorModel::bad_request(
+                            format!("Credential refresh hint must be between 0 and {MAX_CREDENTIAL_REFRESH_BEFORE_SECONDS} seconds"),
+                            "InvalidCredentialRefreshHint",
+                            None,
+                        )
+                    })
+            })
+            .transpose()?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateCredentialRefresh,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_credential_refresh_before(
+            warehouse_id,
+            refresh_before,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_empty_namespace_cleanup(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseEmptyNamespaceCleanupRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let cleanup_after = request
+            .cleanup_after_seconds
+            .map(|seconds| {
+                chrono::Duration::try_seconds(seconds)
+                    .filter(|_| (1..=MAX_EMPTY_NAMESPACE_CLEANUP_AFTER_SECONDS).contains(&seconds))
+                    .ok_or_else(|| {
+                        ErrorModel::bad_request(
+                            format!("Empty namespace cleanup delay must be between 1 and {MAX_EMPTY_NAMESPACE_CLEANUP_AFTER_SECONDS} seconds"),
+                            "InvalidEmptyNamespaceCleanup",
+                            None,
+                        )
+                    })
+            })
+            .transpose()?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_empty_namespace_cleanup_after(
+            warehouse_id,
+            cleanup_after,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_default_view_properties(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseDefaultViewPropertiesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        validate_view_properties(request.default_view_properties.keys())?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_default_view_properties(
+            warehouse_id,
+            &request.default_view_properties,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_namespace_property_schema(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseNamespacePropertySchemaRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let namespace_property_schema = request.namespace_property_schema;
+        namespace_property_schema.validate()?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_namespace_property_schema(
+            warehouse_id,
+            &namespace_property_schema,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_max_tables_per_namespace(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseMaxTablesPerNamespaceRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        if request.max_tables_per_namespace.is_some_and(|max| max < 1) {
+            return Err(ErrorModel::bad_request(
+                "Maximum number of tables per namespace must be at least 1",
+                "InvalidMaxTablesPerNamespace",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifyFeatureFlags,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_max_tables_per_namespace(
+            warehouse_id,
+            request.max_tables_per_namespace,
+            request.include_views,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn list_pending_purges(
+        warehouse_id: WarehouseIdent,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListPendingPurgesResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifySoftDeletion,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let pending_purges =
+            C::list_pending_purges(warehouse_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+
+        Ok(ListPendingPurgesResponse {
+            pending_purges: pending_purges.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    async fn list_table_location_collisions(
+        warehouse_id: WarehouseIdent,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListTableLocationCollisionsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let collisions =
+            C::list_table_location_collisions(warehouse_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+
+        Ok(ListTableLocationCollisionsResponse {
+            collisions: collisions.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    async fn update_warehouse_allowed_schemes(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseAllowedSchemesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let allowed_schemes = request
+            .allowed_schemes
+            .map(|schemes| validate_allowed_schemes(&warehouse.storage_profile, schemes))
+            .transpose()?;
+        C::set_warehouse_allowed_schemes(
+            warehouse_id,
+            allowed_schemes.as_deref(),
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_storage_prefix_overrides(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseStoragePrefixOverridesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let storage_prefix_overrides =
+            validate_storage_prefix_overrides(&warehouse, request.storage_prefix_overrides)?;
+        C::set_warehouse_storage_prefix_overrides(
+            warehouse_id,
+            &storage_prefix_overrides,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_import_prefixes(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseImportPrefixesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let import_prefixes = validate_import_prefixes(&warehouse, request.import_prefixes)?;
+        C::set_warehouse_import_prefixes(warehouse_id, &import_prefixes, transaction.transaction())
+            .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_default_namespace(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseDefaultNamespaceRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let UpdateWarehouseDefaultNamespaceRequest {
+            default_namespace,
+            create_if_missing,
+        } = request;
+        if let Some(namespace) = &default_namespace {
+            validate_namespace_ident(namespace)?;
+            // Also rejects the default namespace alias
+            require_namespace_not_reserved(namespace)?;
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateDefaultNamespace,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        if let Some(namespace) = default_namespace.as_ref().filter(|_| create_if_missing) {
+            Self::create_namespace_hierarchy(
+                warehouse_id,
+                CreateNamespaceHierarchyRequest {
+                    namespace: namespace.clone(),
+                    properties: None,
+                },
+                context.clone(),
+                request_metadata,
+            )
+            .await?;
+        }
+
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        if let Some(namespace) = &default_namespace {
+            if C::namespace_to_id(warehouse_id, namespace, transaction.transaction())
+                .await?
+                .is_none()
+            {
+                return Err(ErrorModel::bad_request(
+                    format!("Default namespace {namespace:?} does not exist"),
+                    "DefaultNamespaceNotFound",
+                    None,
+                )
+                .into());
+            }
+        }
+        C::set_warehouse_default_namespace(
+            warehouse_id,
+            default_namespace.as_ref(),
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn set_namespace_storage_prefix(
         warehouse_id: WarehouseIdent,
-        request: RenameWarehouseRequest,
+        namespace_id: NamespaceIdentUuid,
+        request: SetNamespaceStoragePrefixRequest,
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let storage_prefix = request
+            .storage_prefix
+            .map(|prefix| parse_storage_prefix(&prefix))
+            .transpose()?;
+        if let Some(region) = &request.storage_region {
+            if storage_prefix.is_none() {
+                return Err(ErrorModel::bad_request(
+                    "A storage region can only be set together with a storage prefix",
+                    "InvalidStorageRegion",
+                    None,
+                )
+                .into());
+            }
+            if region.is_empty() || region.len() > MAX_STORAGE_REGION_LENGTH {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Storage region must be between 1 and {MAX_STORAGE_REGION_LENGTH} characters long"
+                    ),
+                    "InvalidStorageRegion",
+                    None,
+                )
+                .into());
+            }
+        }
+
         // ------------------- AuthZ -------------------
         let authorizer = context.v1_state.authz;
         authorizer
             .require_warehouse_action(
                 &request_metadata,
                 warehouse_id,
-                &CatalogWarehouseAction::CanRename,
+                &CatalogWarehouseAction::CanUpdateStorage,
             )
             .await?;
 
         // ------------------- Business Logic -------------------
-        validate_warehouse_name(&request.new_name)?;
         let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
-
-        C::rename_warehouse(warehouse_id, &request.new_name, transaction.transaction()).await?;
-
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        if let Some(storage_prefix) = &storage_prefix {
+            warehouse.require_allowed_storage_prefix(storage_prefix)?;
+        }
+        if request.storage_region.is_some()
+            && !matches!(warehouse.storage_profile, StorageProfile::S3(_))
+        {
+            return Err(ErrorModel::bad_request(
+                "Storage regions are only supported for S3 storage profiles",
+                "InvalidStorageRegion",
+                None,
+            )
+            .into());
+        }
+        C::set_namespace_storage_prefix(
+            warehouse_id,
+            namespace_id,
+            storage_prefix.as_ref().map(Location::as_str),
+            request.storage_region.as_deref(),
+            transaction.transaction(),
+        )
+        .await?;
         transaction.commit().await?;
 
         Ok(())
     }
 
-    async fn update_warehouse_delete_profile(
+    async fn set_namespace_credential_ttl(
         warehouse_id: WarehouseIdent,
-        request: UpdateWarehouseDeleteProfileRequest,
+        namespace_id: NamespaceIdentUuid,
+        request: SetNamespaceCredentialTtlRequest,
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        if let Some(seconds) = request.credential_ttl_seconds {
+            let min = CONFIG.min_credential_ttl.as_secs();
+            let max = CONFIG.max_credential_ttl.as_secs();
+            if !u64::try_from(seconds).is_ok_and(|seconds| (min..=max).contains(&seconds)) {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Credential TTL of {seconds} seconds is out of bounds. Allowed are {min} to {max} seconds."
+                    ),
+                    "InvalidCredentialTtl",
+                    None,
+                )
+                .into());
+            }
+        }
+
         // ------------------- AuthZ -------------------
         let authorizer = context.v1_state.authz;
         authorizer
             .require_warehouse_action(
                 &request_metadata,
                 warehouse_id,
-                &CatalogWarehouseAction::CanModifySoftDeletion,
+                &CatalogWarehouseAction::CanUpdateStorage,
             )
             .await?;
 
         // ------------------- Business Logic -------------------
         let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
-        C::set_warehouse_deletion_profile(
+        C::set_namespace_credential_ttl(
             warehouse_id,
-            &request.delete_profile,
+            namespace_id,
+            request
+                .credential_ttl_seconds
+                .map(chrono::Duration::seconds),
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    /// Rename a namespace together with its child namespaces, tables and views.
+    /// Requires permission to delete the namespace and to create namespaces
+    /// in the parent of the destination.
+    #[allow(clippy::too_many_lines)]
+    async fn rename_namespace(
+        warehouse_id: WarehouseIdent,
+        request: RenameNamespaceRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let RenameNamespaceRequest {
+            source,
+            destination,
+        } = request;
+        validate_namespace_ident(&source)?;
+        validate_namespace_ident(&destination)?;
+
+        for namespace in [&source, &destination] {
+            if CONFIG
+                .reserved_namespaces
+                .contains(&namespace.as_ref()[0].to_lowercase())
+            {
+                return Err(ErrorModel::bad_request(
+                    "Namespace is reserved for internal use.",
+                    "ReservedNamespace",
+                    None,
+                )
+                .into());
+            }
+        }
+
+        let lowercase = |n: &NamespaceIdent| n.iter().map(|s| s.to_lowercase()).collect::<Vec<_>>();
+        let (source_lower, destination_lower) = (lowercase(&source), lowercase(&destination));
+        if destination_lower.len() > source_lower.len()
+            && destination_lower.starts_with(&source_lower)
+        {
+            return Err(ErrorModel::bad_request(
+                "Cannot move a namespace into one of its own children",
+                "NamespaceRenameIntoChild",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let namespace_id = authorized_namespace_ident_to_id::<C, _>(
+            authorizer.clone(),
+            &request_metadata,
+            &warehouse_id,
+            &source,
+            &CatalogNamespaceAction::CanDelete,
             transaction.transaction(),
         )
         .await?;
+
+        let new_parent = if let Some(parent) = destination.parent() {
+            let parent_id =
+                C::namespace_to_id(warehouse_id, &parent, transaction.transaction()).await; // Cannot fail before authz
+            let parent_id = authorizer
+                .require_namespace_action(
+                    &request_metadata,
+                    parent_id,
+                    &CatalogNamespaceAction::CanCreateNamespace,
+                )
+                .await?;
+            NamespaceParent::Namespace(parent_id)
+        } else {
+            authorizer
+                .require_warehouse_action(
+                    &request_metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanCreateNamespace,
+                )
+                .await?;
+            NamespaceParent::Warehouse(warehouse_id)
+        };
+
+        // ------------------- Business Logic -------------------
+        if source == destination {
+            return Ok(());
+        }
+        list_descendants_within_limit::<C>(
+            warehouse_id,
+            namespace_id,
+            &source,
+            CONFIG.max_objects_per_recursive_operation,
+            &mut transaction,
+        )
+        .await?;
+
+        let previous_parent = if let Some(parent) = source.parent() {
+            let parent_id = C::namespace_to_id(warehouse_id, &parent, transaction.transaction())
+                .await?
+                .ok_or_else(|| {
+                    ErrorModel::internal(
+                        format!("Parent of namespace {source:?} not found"),
+                        "NamespaceParentNotFound",
+                        None,
+                    )
+                })?;
+            NamespaceParent::Namespace(parent_id)
+        } else {
+            NamespaceParent::Warehouse(warehouse_id)
+        };
+
+        C::rename_namespace(
+            warehouse_id,
+            &source,
+            &destination,
+            transaction.transaction(),
+        )
+        .await?;
+        if previous_parent != new_parent {
+            authorizer
+                .move_namespace(&request_metadata, namespace_id, previous_parent, new_parent)
+                .await?;
+        }
         transaction.commit().await?;
 
+        let _ = context
+            .v1_state
+            .publisher
+            .publish_namespace_event(
+                Uuid::now_v7(),
+                "namespace.renamed",
+                serde_json::json!({
+                    "source": source,
+                    "destination": destination,
+                }),
+                NamespaceEventMetadata {
+                    namespace_id,
+                    warehouse_id,
+                    namespace: destination.to_url_string(),
+                    prefix: warehouse_id.to_string(),
+                    trace_id: request_metadata.request_id().to_string(),
+                },
+            )
+            .await;
+
         Ok(())
     }
 
@@ -606,11 +2780,13 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         let secret_id = if let Some(storage_credential) = storage_credential {
             Some(
-                context
-                    .v1_state
-                    .secrets
-                    .create_secret(storage_credential)
-                    .await?,
+                create_storage_secret::<C, _>(
+                    warehouse_id,
+                    storage_credential,
+                    &context.v1_state.secrets,
+                    &mut transaction,
+                )
+                .await?,
             )
         } else {
             None
@@ -674,11 +2850,13 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         let secret_id = if let Some(new_storage_credential) = new_storage_credential {
             Some(
-                context
-                    .v1_state
-                    .secrets
-                    .create_secret(new_storage_credential)
-                    .await?,
+                create_storage_secret::<C, _>(
+                    warehouse_id,
+                    new_storage_credential,
+                    &context.v1_state.secrets,
+                    &mut transaction,
+                )
+                .await?,
             )
         } else {
             None
@@ -710,6 +2888,198 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         Ok(())
     }
 
+    async fn rotate_storage_credential(
+        warehouse_id: WarehouseIdent,
+        request: RotateWarehouseCredentialRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RotateWarehouseCredentialResponse> {
+        // ------------------- VALIDATIONS -------------------
+        let RotateWarehouseCredentialRequest {
+            new_storage_credential,
+            overlap_seconds,
+        } = request;
+        let overlap = match overlap_seconds {
+            Some(seconds) => chrono::Duration::try_seconds(seconds)
+                .filter(|_| (0..=MAX_CREDENTIAL_ROTATION_OVERLAP_SECONDS).contains(&seconds))
+                .ok_or_else(|| {
+                    ErrorModel::bad_request(
+                        format!("Credential rotation overlap must be between 0 and {MAX_CREDENTIAL_ROTATION_OVERLAP_SECONDS} seconds"),
+                        "InvalidCredentialRotationOverlap",
+                        None,
+                    )
+                })?,
+            None => chrono::Duration::from_std(CONFIG.storage_credential_rotation_overlap)
+                .map_err(|e| {
+                    ErrorModel::internal(
+                        "Invalid storage credential rotation overlap",
+                        "InvalidCredentialRotationOverlap",
+                        Some(Box::new(e)),
+                    )
+                })?,
+        };
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorageCredential,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        // Access is validated before the write transaction, so that no locks are
+        // held while storage is contacted.
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+        warehouse
+            .storage_profile
+            .validate_access(Some(&new_storage_credential), None)
+            .await?;
+
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog.clone()).await?;
+        let secrets = context.v1_state.secrets;
+        let secret_id = create_storage_secret::<C, _>(
+            warehouse_id,
+            new_storage_credential,
+            &secrets,
+            &mut transaction,
+        )
+        .await?;
+        let expires_at = chrono::Utc::now() + overlap;
+        let rotated = C::rotate_storage_secret(
+            warehouse_id,
+            secret_id,
+            expires_at,
+            transaction.transaction(),
+        )
+        .await;
+        let rotated = match rotated {
+            Ok(rotated) => {
+                transaction.commit().await?;
+                rotated
+            }
+            Err(e) => {
+                // Never leave the new secret behind if the warehouse does not use it
+                secrets.delete_secret(&secret_id).await.ok();
+                return Err(e);
+            }
+        };
+
+        tracing::info!(
+            "{} rotated the storage credential of warehouse {warehouse_id}. Previous secret {:?} is retained until {expires_at}",
+            request_metadata.actor(),
+            rotated.previous_secret_id,
+        );
+        let _ = context
+            .v1_state
+            .publisher
+            .publish_warehouse_event(
+                Uuid::now_v7(),
+                "warehouse.storageCredentialRotated",
+                serde_json::json!({
+                    "secret-id": secret_id.into_uuid(),
+                    "previous-secret-id": rotated.previous_secret_id.map(|id| id.into_uuid()),
+                    "previous-secret-expires-at": rotated.previous_secret_id.map(|_| expires_at),
+                }),
+                WarehouseEventMetadata {
+                    warehouse_id,
+                    actor: request_metadata.actor().to_string(),
+                    trace_id: request_metadata.request_id().to_string(),
+                },
+            )
+            .await;
+
+        // A secret retained by an earlier rotation is replaced by the previous secret
+        if let Some(superseded_secret_id) = rotated.superseded_secret_id {
+            if let Err(e) = release_previous_storage_secret::<C, S>(
+                warehouse_id,
+                superseded_secret_id,
+                context.v1_state.catalog.clone(),
+                &secrets,
+            )
+            .await
+            {
+                tracing::warn!(
+                    "Failed to delete superseded storage secret {superseded_secret_id} of warehouse {warehouse_id}: {:?}",
+                    e.error
+                );
+            }
+        }
+        // The rotation is committed, so failing to queue the cleanup only leaves
+        // the previous secret behind and does not fail the request.
+        if let Some(previous_secret_id) = rotated.previous_secret_id {
+            if let Err(e) = context
+                .v1_state
+                .queues
+                .queue_storage_secret_cleanup(StorageSecretCleanupInput {
+                    warehouse_ident: warehouse_id,
+                    secret_id: previous_secret_id,
+                    cleanup_after: Some(expires_at),
+                })
+                .await
+            {
+                tracing::error!(
+                    "Failed to queue deletion of previous storage secret {previous_secret_id} of warehouse {warehouse_id}: {:?}",
+                    e.error
+                );
+            }
+        }
+
+        Ok(RotateWarehouseCredentialResponse {
+            previous_credential_expires_at: rotated.previous_secret_id.map(|_| expires_at),
+        })
+    }
+
+    async fn list_warehouse_secrets(
+        warehouse_id: WarehouseIdent,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListWarehouseSecretsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        if !authorizer
+            .is_allowed_server_action(
+                &request_metadata,
+                &CatalogServerAction::CanListSecretReferences,
+            )
+            .await?
+        {
+            authorizer
+                .require_warehouse_action(
+                    &request_metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanListSecretReferences,
+                )
+                .await?;
+        }
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let references = C::list_warehouse_secret_references(warehouse_id, t.transaction()).await?;
+        t.commit().await?;
+
+        let mut secrets = Vec::with_capacity(references.len());
+        for reference in references {
+            // The credential is never decrypted, only its type and timestamps are read
+            let secret =
+                get_storage_secret_type(&reference.secret_id, &context.v1_state.secrets).await?;
+            secrets.push(WarehouseSecretResponse {
+                secret_id: reference.secret_id.into_uuid(),
+                usage: reference.usage,
+                credential_type: secret.secret,
+                created_at: secret.created_at,
+                last_rotated_at: secret.updated_at.unwrap_or(secret.created_at),
+                expires_at: reference.expires_at,
+            });
+        }
+
+        Ok(ListWarehouseSecretsResponse { secrets })
+    }
+
     async fn undrop_tabulars(
         warehouse_id: WarehouseIdent,
         request_metadata: RequestMetadata,
@@ -733,6 +3103,10 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         // ------------------- Business Logic -------------------
         let catalog = context.v1_state.catalog;
         let mut transaction = C::Transaction::begin_write(catalog.clone()).await?;
+        C::require_warehouse(warehouse_id, transaction.transaction())
+            .await?
+            .feature_flags
+            .require(WarehouseFeatureFlag::UndropTabulars)?;
         let tabs = request
             .targets
             .into_iter()
@@ -769,7 +3143,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
                         prefix: warehouse_id.0.into(),
                         num_events: num_tabulars,
                         sequence_number: i,
-                        trace_id: request_metadata.request_id(),
+                        trace_id: request_metadata.request_id().to_string(),
                     },
                 )
                 .await;
@@ -800,6 +3174,10 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         let pagination_query = query.pagination_query();
         let namespace_id = query.namespace_id;
         let mut t = C::Transaction::begin_read(catalog.clone()).await?;
+        C::require_warehouse(warehouse_id, t.transaction())
+            .await?
+            .feature_flags
+            .require(WarehouseFeatureFlag::UndropTabulars)?;
         let (tabulars, idents, next_page_token) =
             crate::catalog::fetch_until_full_page::<_, _, _, C>(
                 pagination_query.page_size,
@@ -896,31 +3274,205 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             next_page_token,
         })
     }
-}
+}
+
+impl axum::response::IntoResponse for ListWarehousesResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        Json(self).into_response()
+    }
+}
+
+impl axum::response::IntoResponse for GetWarehouseResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        Json(self).into_response()
+    }
+}
+
+impl From<crate::service::GetWarehouseResponse> for GetWarehouseResponse {
+    fn from(warehouse: crate::service::GetWarehouseResponse) -> Self {
+        Self {
+            id: warehouse.id.to_uuid(),
+            name: warehouse.name,
+            project_id: *warehouse.project_id,
+            storage_profile: warehouse.storage_profile,
+            status: warehouse.status,
+            delete_profile: warehouse.tabular_delete_profile,
+            allowed_schemes: warehouse.allowed_schemes(),
+            purge_grace_period_seconds: warehouse
+                .purge_grace_period
+                .map(|grace_period| grace_period.num_seconds()),
+            manifest_merge_threshold: warehouse.manifest_merge_threshold,
+            max_tables_per_namespace: warehouse.max_tables_per_namespace,
+            max_tables_per_namespace_includes_views: warehouse
+                .max_tables_per_namespace_includes_views,
+            storage_prefix_overrides: warehouse.storage_prefix_overrides,
+            schema_evolution_policy: warehouse.schema_evolution_policy,
+            allowed_file_formats: warehouse.allowed_file_formats,
+            namespace_property_schema: warehouse.namespace_property_schema,
+            annotations: warehouse.annotations,
+            default_view_properties: warehouse.default_view_properties,
+            commit_rate_limit: warehouse.commit_rate_limit,
+            credential_refresh_before_seconds: warehouse
+                .credential_refresh_before
+                .map(|refresh_before| refresh_before.num_seconds()),
+            allowed_authentication_methods: warehouse.allowed_authentication_methods,
+            empty_namespace_cleanup_after_seconds: warehouse
+                .empty_namespace_cleanup_after
+                .map(|cleanup_after| cleanup_after.num_seconds()),
+            import_prefixes: warehouse.import_prefixes,
+            default_namespace: warehouse.default_namespace,
+            snapshot_limit: warehouse.snapshot_limit,
+            feature_flags: warehouse.feature_flags.effective(),
+        }
+    }
+}
+
+/// Longest purge grace period of a warehouse.
+const MAX_PURGE_GRACE_PERIOD_SECONDS: i64 = 365 * 24 * 60 * 60;
+
+/// Longest time before the expiry of vended credentials at which clients are
+/// asked to refresh them.
+const MAX_CREDENTIAL_REFRESH_BEFORE_SECONDS: i64 = 24 * 60 * 60;
+
+/// Longest delay after which empty namespaces are removed.
+const MAX_EMPTY_NAMESPACE_CLEANUP_AFTER_SECONDS: i64 = 10 * 365 * 24 * 60 * 60;
+
+/// Longest overlap of a storage credential rotation, after which the previous
+/// credential is deleted.
+const MAX_CREDENTIAL_ROTATION_OVERLAP_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Longest region of a namespace storage prefix.
+const MAX_STORAGE_REGION_LENGTH: usize = 128;
+
+fn parse_storage_prefix(prefix: &str) -> Result<Location> {
+    let mut location = prefix.parse::<Location>().map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Invalid storage prefix '{prefix}': {e}"),
+            "InvalidStoragePrefix",
+            None,
+        )
+    })?;
+    location.without_trailing_slash();
+    Ok(location)
+}
+
+fn validate_storage_prefix_overrides(
+    warehouse: &crate::service::GetWarehouseResponse,
+    storage_prefix_overrides: Vec<String>,
+) -> Result<Vec<String>> {
+    storage_prefix_overrides
+        .into_iter()
+        .map(|prefix| {
+            let location = parse_storage_prefix(&prefix)?;
+            warehouse.require_allowed_scheme(&location)?;
+            Ok(location.to_string())
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(|prefixes| prefixes.into_iter().unique().collect())
+}
+
+/// Import prefixes must not overlap with the base location, so that tables
+/// created in the warehouse are never treated as read-only imports.
+fn validate_import_prefixes(
+    warehouse: &crate::service::GetWarehouseResponse,
+    import_prefixes: Vec<String>,
+) -> Result<Vec<String>> {
+    let base_location = warehouse.storage_profile.base_location()?;
+    import_prefixes
+        .into_iter()
+        .map(|prefix| {
+            let location = parse_storage_prefix(&prefix)?;
+            warehouse.require_allowed_scheme(&location)?;
+            if location.is_sublocation_of(&base_location)
+                || base_location.is_sublocation_of(&location)
+            {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Import prefix '{location}' overlaps with the base location '{base_location}' of the warehouse"
+                    ),
+                    "ImportPrefixOverlapsBaseLocation",
+                    None,
+                )
+                .into());
+            }
+            Ok(location.to_string())
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(|prefixes| prefixes.into_iter().unique().collect())
+}
+
+fn validate_allowed_file_formats(file_formats: Vec<String>) -> Result<Vec<String>> {
+    let file_formats = file_formats
+        .into_iter()
+        .map(|format| format.to_lowercase())
+        .unique()
+        .collect::<Vec<_>>();
+
+    if file_formats.is_empty() {
+        return Err(ErrorModel::bad_request(
+            "At least one file format must be allowed. Use `null` to allow all formats.",
+            "NoFileFormatAllowed",
+            None,
+        )
+        .into());
+    }
+    if let Some(format) = file_formats
+        .iter()
+        .find(|format| !SUPPORTED_FILE_FORMATS.contains(&format.as_str()))
+    {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Unknown file format '{format}'. Supported formats: {}.",
+                SUPPORTED_FILE_FORMATS.join(", ")
+            ),
+            "UnsupportedFileFormat",
+            None,
+        )
+        .into());
+    }
 
-impl axum::response::IntoResponse for ListWarehousesResponse {
-    fn into_response(self) -> axum::http::Response<axum::body::Body> {
-        axum::Json(self).into_response()
-    }
+    Ok(file_formats)
 }
 
-impl axum::response::IntoResponse for GetWarehouseResponse {
-    fn into_response(self) -> axum::http::Response<axum::body::Body> {
-        axum::Json(self).into_response()
+fn validate_allowed_schemes(
+    storage_profile: &StorageProfile,
+    schemes: Vec<String>,
+) -> Result<Vec<String>> {
+    let supported = storage_profile.supported_schemes();
+    let schemes = schemes
+        .into_iter()
+        .map(|scheme| scheme.to_lowercase())
+        .unique()
+        .collect::<Vec<_>>();
+
+    if let Some(scheme) = schemes
+        .iter()
+        .find(|scheme| !supported.contains(&scheme.as_str()))
+    {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Scheme '{scheme}' is not supported by the storage profile. Supported schemes: {}.",
+                supported.join(", ")
+            ),
+            "UnsupportedLocationScheme",
+            None,
+        )
+        .into());
     }
-}
 
-impl From<crate::service::GetWarehouseResponse> for GetWarehouseResponse {
-    fn from(warehouse: crate::service::GetWarehouseResponse) -> Self {
-        Self {
-            id: warehouse.id.to_uuid(),
-            name: warehouse.name,
-            project_id: *warehouse.project_id,
-            storage_profile: warehouse.storage_profile,
-            status: warehouse.status,
-            delete_profile: warehouse.tabular_delete_profile,
-        }
+    // Default locations are derived from the base location and must remain valid.
+    let base_location = storage_profile.base_location()?;
+    let base_scheme = base_location.scheme();
+    if !schemes.iter().any(|scheme| scheme == base_scheme) {
+        return Err(ErrorModel::bad_request(
+            format!("Allowed schemes must include '{base_scheme}', the scheme of the storage profile's base location."),
+            "BaseLocationSchemeNotAllowed",
+            None,
+        )
+        .into());
     }
+
+    Ok(schemes)
 }
 
 fn validate_warehouse_name(warehouse_name: &str) -> Result<()> {
@@ -944,6 +3496,59 @@ fn validate_warehouse_name(warehouse_name: &str) -> Result<()> {
     Ok(())
 }
 
+const MAX_ANNOTATIONS: usize = 64;
+const MAX_ANNOTATION_KEY_LENGTH: usize = 128;
+const MAX_ANNOTATION_VALUE_LENGTH: usize = 512;
+
+fn validate_warehouse_annotations(annotations: &HashMap<String, String>) -> Result<()> {
+    if annotations.len() > MAX_ANNOTATIONS {
+        return Err(ErrorModel::bad_request(
+            format!("Warehouses may have at most {MAX_ANNOTATIONS} annotations"),
+            "TooManyWarehouseAnnotations",
+            None,
+        )
+        .into());
+    }
+
+    for (key, value) in annotations {
+        if key.is_empty() || key.len() > MAX_ANNOTATION_KEY_LENGTH {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Annotation keys must be between 1 and {MAX_ANNOTATION_KEY_LENGTH} chars, got '{key}'"
+                ),
+                "InvalidWarehouseAnnotation",
+                None,
+            )
+            .into());
+        }
+        if value.len() > MAX_ANNOTATION_VALUE_LENGTH {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Value of annotation '{key}' must be shorter than {MAX_ANNOTATION_VALUE_LENGTH} chars"
+                ),
+                "InvalidWarehouseAnnotation",
+                None,
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn parse_annotation_filter(filter: &str) -> Result<(&str, &str)> {
+    filter
+        .split_once('=')
+        .filter(|(key, _)| !key.is_empty())
+        .ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!("Annotation filter must be formatted as `key=value`, got '{filter}'"),
+                "InvalidAnnotationFilter",
+                None,
+            )
+            .into()
+        })
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -983,10 +3588,14 @@ mod test {
         assert_eq!(s3_profile.path_style_access, Some(true));
     }
 
+    use std::{collections::HashMap, sync::Arc};
+
+    use http::StatusCode;
     use iceberg::TableIdent;
     use itertools::Itertools;
     use sqlx::PgPool;
 
+    use super::{MAX_CREDENTIAL_ROTATION_OVERLAP_SECONDS, MAX_PURGE_GRACE_PERIOD_SECONDS};
     use crate::{
         api::{
             iceberg::{
@@ -994,19 +3603,35 @@ mod test {
                 v1::{views::Service, DataAccess, DropParams, NamespaceParameters, ViewParameters},
             },
             management::v1::{
-                warehouse::{ListDeletedTabularsQuery, Service as _, TabularDeleteProfile},
+                warehouse::{
+                    CreateWarehouseRequest, ListDeletedTabularsQuery, ListWarehousesRequest,
+                    RotateWarehouseCredentialRequest, S3Credential, Service as _,
+                    StorageCredential, TabularDeleteProfile, UpdateWarehouseAllowedSchemesRequest,
+                    UpdateWarehouseAnnotationsRequest, UpdateWarehouseFeatureFlagsRequest,
+                    UpdateWarehousePurgeGracePeriodRequest, WarehouseFeatureFlag,
+                    WarehouseSecretUsage,
+                },
                 ApiServer,
             },
             ApiContext,
         },
-        catalog::{test::impl_pagination_tests, CatalogServer},
-        implementations::postgres::{PostgresCatalog, SecretsState},
+        catalog::{maybe_get_secret, test::impl_pagination_tests, CatalogServer},
+        implementations::postgres::{
+            PostgresCatalog, PostgresTransaction, ReadWrite, SecretsState,
+        },
         request_metadata::RequestMetadata,
         service::{
-            authz::implementations::openfga::{tests::ObjectHidingMock, OpenFGAAuthorizer},
-            State, UserId,
+            authz::{
+                implementations::openfga::{tests::ObjectHidingMock, OpenFGAAuthorizer},
+                AllowAllAuthorizer,
+            },
+            task_queue::{
+                storage_secret_cleanup_queue::{instrumented_cleanup, StorageSecretCleanupQueue},
+                TaskQueue as _, TaskQueueConfig,
+            },
+            Catalog as _, SecretStore as _, State, Transaction as _, UserId,
         },
-        WarehouseIdent,
+        SecretIdent, WarehouseIdent,
     };
 
     async fn setup_pagination_test(
@@ -1316,4 +3941,454 @@ mod test {
             assert_eq!(next_page_items[idx], format!("view-{i}"));
         }
     }
+
+    #[sqlx::test]
+    async fn test_feature_flag_gates_deleted_tabulars(pool: sqlx::PgPool) {
+        let prof = crate::catalog::test::test_io_profile();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            prof,
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Soft {
+                expiration_seconds: chrono::Duration::seconds(10),
+            },
+            None,
+        )
+        .await;
+        let list = |ctx: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>| {
+            ApiServer::list_soft_deleted_tabulars(
+                warehouse.warehouse_id,
+                ListDeletedTabularsQuery {
+                    namespace_id: None,
+                    page_size: 10,
+                    page_token: None,
+                },
+                ctx,
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let set_flag =
+            |ctx: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+             enabled: Option<bool>| {
+                ApiServer::update_warehouse_feature_flags(
+                    warehouse.warehouse_id,
+                    UpdateWarehouseFeatureFlagsRequest {
+                        feature_flags: HashMap::from_iter([
+                            ("undrop-tabulars".to_string(), enabled),
+                            ("not-a-flag".to_string(), Some(true)),
+                        ]),
+                    },
+                    ctx,
+                    RequestMetadata::new_unauthenticated(),
+                )
+            };
+
+        // Enabled by default
+        list(ctx.clone()).await.unwrap();
+
+        set_flag(ctx.clone(), Some(false)).await.unwrap();
+        let err = list(ctx.clone()).await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+        assert_eq!(err.error.r#type, "WarehouseFeatureDisabled");
+        let wh = ApiServer::get_warehouse(
+            warehouse.warehouse_id,
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            wh.feature_flags.get(&WarehouseFeatureFlag::UndropTabulars),
+            Some(&false)
+        );
+        assert_eq!(
+            wh.feature_flags
+                .get(&WarehouseFeatureFlag::WarehouseStatistics),
+            Some(&true)
+        );
+
+        set_flag(ctx.clone(), Some(true)).await.unwrap();
+        list(ctx.clone()).await.unwrap();
+
+        // Resetting falls back to the server default
+        set_flag(ctx.clone(), None).await.unwrap();
+        list(ctx).await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_update_warehouse_allowed_schemes(pool: sqlx::PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let update = |ctx: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+                      allowed_schemes: Option<Vec<&str>>| {
+            ApiServer::update_warehouse_allowed_schemes(
+                warehouse.warehouse_id,
+                UpdateWarehouseAllowedSchemesRequest {
+                    allowed_schemes: allowed_schemes
+                        .map(|s| s.into_iter().map(ToString::to_string).collect()),
+                },
+                ctx,
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let get = |ctx: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>| {
+            ApiServer::get_warehouse(
+                warehouse.warehouse_id,
+                ctx,
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        // Defaults to the schemes supported by the storage profile
+        assert_eq!(
+            get(ctx.clone()).await.unwrap().allowed_schemes,
+            vec!["file"]
+        );
+
+        let err = update(ctx.clone(), Some(vec!["s3"])).await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "UnsupportedLocationScheme");
+
+        let err = update(ctx.clone(), Some(vec![])).await.unwrap_err();
+        assert_eq!(err.error.r#type, "BaseLocationSchemeNotAllowed");
+
+        update(ctx.clone(), Some(vec!["FILE", "file"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            get(ctx.clone()).await.unwrap().allowed_schemes,
+            vec!["file"]
+        );
+
+        update(ctx.clone(), None).await.unwrap();
+        assert_eq!(get(ctx).await.unwrap().allowed_schemes, vec!["file"]);
+    }
+
+    #[sqlx::test]
+    async fn test_warehouse_annotations(pool: sqlx::PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let annotations = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        let project_id = ApiServer::get_warehouse(
+            warehouse.warehouse_id,
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap()
+        .project_id;
+
+        let prod = ApiServer::create_warehouse(
+            CreateWarehouseRequest {
+                warehouse_name: "prod-warehouse".to_string(),
+                project_id: Some(project_id.into()),
+                storage_profile: crate::catalog::test::test_io_profile(),
+                storage_credential: None,
+                delete_profile: TabularDeleteProfile::Hard {},
+                annotations: annotations(&[("env", "prod"), ("owner", "team-a")]),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let response = ApiServer::get_warehouse(
+            prod.warehouse_id,
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            response.annotations,
+            annotations(&[("env", "prod"), ("owner", "team-a")])
+        );
+
+        let update = |entries: HashMap<String, String>| {
+            ApiServer::update_warehouse_annotations(
+                warehouse.warehouse_id,
+                UpdateWarehouseAnnotationsRequest {
+                    annotations: entries,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let err = update(annotations(&[("owner", &"x".repeat(513))]))
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "InvalidWarehouseAnnotation");
+        update(annotations(&[("env", "dev")])).await.unwrap();
+
+        let list = |annotation: Option<&str>| {
+            ApiServer::list_warehouses(
+                ListWarehousesRequest {
+                    warehouse_status: None,
+                    project_id: Some(project_id.into()),
+                    annotation: annotation.map(ToString::to_string),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let ids = |response: super::ListWarehousesResponse| {
+            response
+                .warehouses
+                .into_iter()
+                .map(|w| w.id)
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(list(Some("env=prod")).await.unwrap()),
+            vec![*prod.warehouse_id]
+        );
+        assert_eq!(
+            ids(list(Some("env=dev")).await.unwrap()),
+            vec![*warehouse.warehouse_id]
+        );
+        assert_eq!(ids(list(None).await.unwrap()).len(), 2);
+        assert!(ids(list(Some("owner=team-b")).await.unwrap()).is_empty());
+
+        let err = list(Some("env")).await.unwrap_err();
+        assert_eq!(err.error.r#type, "InvalidAnnotationFilter");
+    }
+
+    #[sqlx::test]
+    async fn test_rotate_storage_credential(pool: sqlx::PgPool) {
+        let credential = |key: &str| -> StorageCredential {
+            S3Credential::AccessKey {
+                aws_access_key_id: key.to_string(),
+                aws_secret_access_key: "secret".to_string(),
+            }
+            .into()
+        };
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            Some(credential("key-1")),
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let rotate = |ctx: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+                      key: &str,
+                      overlap_seconds: Option<i64>| {
+            ApiServer::rotate_storage_credential(
+                warehouse.warehouse_id,
+                RotateWarehouseCredentialRequest {
+                    new_storage_credential: credential(key),
+                    overlap_seconds,
+                },
+                ctx,
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let current_secret_id = || async {
+            let mut t = PostgresTransaction::begin_read(ctx.v1_state.catalog.clone())
+                .await
+                .unwrap();
+            let warehouse =
+                PostgresCatalog::require_warehouse(warehouse.warehouse_id, t.transaction())
+                    .await
+                    .unwrap();
+            t.commit().await.unwrap();
+            warehouse.storage_secret_ident.unwrap()
+        };
+        let stored_credential = |secret_id: SecretIdent| {
+            let secrets = ctx.v1_state.secrets.clone();
+            async move {
+                secrets
+                    .get_secret_by_id::<StorageCredential>(&secret_id)
+                    .await
+                    .map(|secret| secret.secret)
+            }
+        };
+
+        let first_secret_id = current_secret_id().await;
+        let response = rotate(ctx.clone(), "key-2", None).await.unwrap();
+        assert!(response
+            .previous_credential_expires_at
+            .is_some_and(|expires_at| expires_at > chrono::Utc::now()));
+
+        // New vends use the new secret, the previous one remains available during the overlap
+        let second_secret_id = current_secret_id().await;
+        assert_ne!(second_secret_id, first_secret_id);
+        assert_eq!(
+            maybe_get_secret::<PostgresCatalog, _>(
+                Some(second_secret_id),
+                ctx.v1_state.catalog.clone(),
+                &ctx.v1_state.secrets,
+            )
+            .await
+            .unwrap(),
+            Some(credential("key-2"))
+        );
+        assert_eq!(
+            stored_credential(first_secret_id).await.unwrap(),
+            credential("key-1")
+        );
+
+        // Rotating again supersedes the first secret, the second one expires immediately
+        rotate(ctx.clone(), "key-3", Some(0)).await.unwrap();
+        assert_eq!(
+            stored_credential(current_secret_id().await).await.unwrap(),
+            credential("key-3")
+        );
+        assert!(stored_credential(first_secret_id).await.is_err());
+
+        // The second secret is deleted by the cleanup queued for the end of the overlap
+        assert!(stored_credential(second_secret_id).await.is_ok());
+        let queue: StorageSecretCleanupQueue = Arc::new(
+            crate::implementations::postgres::task_queues::StorageSecretCleanupQueue::from_config(
+                ReadWrite::from_pools(pool.clone(), pool),
+                TaskQueueConfig::default(),
+            )
+            .unwrap(),
+        );
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("Cleanup of the second secret should be queued");
+        assert_eq!(task.secret_id, second_secret_id);
+        instrumented_cleanup::<PostgresCatalog, _>(
+            queue.clone(),
+            ctx.v1_state.catalog.clone(),
+            &ctx.v1_state.secrets,
+            &task,
+        )
+        .await;
+        assert!(stored_credential(second_secret_id).await.is_err());
+        // The cleanup of the first secret is not due yet
+        assert!(queue.pick_new_task().await.unwrap().is_none());
+
+        for overlap_seconds in [-1, MAX_CREDENTIAL_ROTATION_OVERLAP_SECONDS + 1, i64::MAX] {
+            let err = rotate(ctx.clone(), "key-4", Some(overlap_seconds))
+                .await
+                .unwrap_err();
+            assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+            assert_eq!(err.error.r#type, "InvalidCredentialRotationOverlap");
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_purge_grace_period_is_bounded(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool,
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let update = |seconds| {
+            ApiServer::update_warehouse_purge_grace_period(
+                warehouse.warehouse_id,
+                UpdateWarehousePurgeGracePeriodRequest {
+                    purge_grace_period_seconds: seconds,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        update(Some(MAX_PURGE_GRACE_PERIOD_SECONDS)).await.unwrap();
+        update(None).await.unwrap();
+
+        for seconds in [-1, MAX_PURGE_GRACE_PERIOD_SECONDS + 1, i64::MAX] {
+            let err = update(Some(seconds)).await.unwrap_err();
+            assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+            assert_eq!(err.error.r#type, "InvalidPurgeGracePeriod");
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_list_warehouse_secrets_redacts_values(pool: sqlx::PgPool) {
+        let credential = |key: &str| -> StorageCredential {
+            S3Credential::AccessKey {
+                aws_access_key_id: key.to_string(),
+                aws_secret_access_key: format!("{key}-very-secret"),
+            }
+            .into()
+        };
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            Some(credential("key-1")),
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        ApiServer::rotate_storage_credential(
+            warehouse.warehouse_id,
+            RotateWarehouseCredentialRequest {
+                new_storage_credential: credential("key-2"),
+                overlap_seconds: Some(3600),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let response = ApiServer::list_warehouse_secrets(
+            warehouse.warehouse_id,
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.secrets.len(), 2);
+        let current = &response.secrets[0];
+        assert_eq!(current.usage, WarehouseSecretUsage::StorageCredential);
+        assert_eq!(current.credential_type.as_deref(), Some("s3/access-key"));
+        assert!(current.expires_at.is_none());
+        assert!(current.last_rotated_at >= current.created_at);
+        let previous = &response.secrets[1];
+        assert_eq!(
+            previous.usage,
+            WarehouseSecretUsage::PreviousStorageCredential
+        );
+        assert_eq!(previous.credential_type.as_deref(), Some("s3/access-key"));
+        assert!(previous.expires_at.is_some());
+        assert_ne!(previous.secret_id, current.secret_id);
+
+        // The secret store holds the values, the listing only metadata
+        let stored = ctx
+            .v1_state
+            .secrets
+            .get_secret_by_id::<StorageCredential>(&current.secret_id.into())
+            .await
+            .unwrap();
+        assert_eq!(stored.secret, credential("key-2"));
+        let serialized = serde_json::to_string(&response).unwrap();
+        assert!(serialized.contains(&current.secret_id.to_string()));
+        for value in ["key-1", "key-2", "very-secret"] {
+            assert!(!serialized.contains(value), "{serialized}");
+        }
+    }
 }
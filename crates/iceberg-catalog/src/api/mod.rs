@@ -1,6 +1,14 @@
 pub mod iceberg;
 pub mod management;
 
+#[cfg(feature = "router")]
+pub(crate) mod compression;
+#[cfg(feature = "router")]
+pub(crate) mod load_shedding;
+#[cfg(feature = "router")]
+pub(crate) mod msgpack;
+#[cfg(feature = "router")]
+pub(crate) mod problem_details;
 #[cfg(feature = "router")]
 pub mod router;
 
@@ -0,0 +1,287 @@
+use axum::{extract::Request, middleware::Next, response::Response};
+use http::{header, HeaderMap};
+
+use crate::api::encoding::{
+    with_response_encoding, ResponseEncoding, JSON_CONTENT_TYPE, MSGPACK_CONTENT_TYPE,
+};
+
+/// Support MessagePack as an alternative encoding to JSON.
+///
+/// Responses built via [`Json`](crate::api::encoding::Json) or the response types
+/// of the REST API are serialized directly as MessagePack if the client prefers it
+/// via the `Accept` header. Request bodies are decoded by the same extractor.
+/// Other responses are passed through unchanged and keep their `Content-Type`.
+pub(crate) async fn msgpack_encoding_fn(
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> Response {
+    let encoding = if accepts_msgpack(&headers) {
+        ResponseEncoding::MsgPack
+    } else {
+        ResponseEncoding::Json
+    };
+    with_response_encoding(encoding, next.run(request)).await
+}
+
+/// `true` if the client ranks MessagePack at least as high as JSON.
+fn accepts_msgpack(headers: &HeaderMap) -> bool {
+    let mut msgpack_quality = 0.0_f32;
+    let mut json_quality = 0.0_f32;
+    for media_range in headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|hv| hv.to_str().ok())
+        .flat_map(|hv| hv.split(','))
+    {
+        let mut params = media_range.split(';').map(str::trim);
+        let media_type = params.next().unwrap_or_default();
+        let quality = params
+            .find_map(|p| p.strip_prefix("q="))
+            .and_then(|q| q.parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if media_type.eq_ignore_ascii_case(MSGPACK_CONTENT_TYPE) {
+            msgpack_quality = msgpack_quality.max(quality);
+        } else if media_type.eq_ignore_ascii_case(JSON_CONTENT_TYPE) {
+            json_quality = json_quality.max(quality);
+        }
+    }
+    msgpack_quality > 0.0 && msgpack_quality >= json_quality
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use axum::{
+        body::Body,
+        routing::{get, post},
+        Router,
+    };
+    use http::HeaderValue;
+    use http_body_util::BodyExt;
+    use iceberg::{
+        spec::{
+            FormatVersion, NestedField, PrimitiveType, Schema, SortOrder, TableMetadataBuilder,
+            Type, UnboundPartitionSpec,
+        },
+        NamespaceIdent,
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::api::{
+        encoding::Json, CreateNamespaceRequest, IcebergErrorResponse, LoadTableResult,
+    };
+
+    fn load_table_result() -> LoadTableResult {
+        let schema = Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+                NestedField::optional(2, "name", Type::Primitive(PrimitiveType::String)).into(),
+            ])
+            .build()
+            .unwrap();
+        let metadata = TableMetadataBuilder::new(
+            schema,
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::from([("owner".to_string(), "me".to_string())]),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata;
+        LoadTableResult {
+            metadata_location: Some("s3://bucket/table/metadata/00000.metadata.json".to_string()),
+            metadata,
+            config: Some(HashMap::from([(
+                "s3.region".to_string(),
+                "eu-central-1".to_string(),
+            )])),
+            storage_credentials: None,
+            deprecation: None,
+        }
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .route("/table", get(|| async { load_table_result() }))
+            .route(
+                "/namespace",
+                post(|Json(request): Json<CreateNamespaceRequest>| async move {
+                    Json(request.namespace)
+                }),
+            )
+            .route(
+                "/plain",
+                get(|| async { axum::Json(serde_json::json!({"plain": true})) }),
+            )
+            .layer(axum::middleware::from_fn(msgpack_encoding_fn))
+    }
+
+    async fn body_bytes(response: Response) -> Vec<u8> {
+        response.collect().await.unwrap().to_bytes().to_vec()
+    }
+
+    #[test]
+    fn test_accepts_msgpack() {
+        let accepts = |v: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, HeaderValue::from_static(v));
+            accepts_msgpack(&headers)
+        };
+        assert!(accepts("application/msgpack"));
+        assert!(accepts("application/msgpack, application/json"));
+        assert!(accepts("application/json;q=0.5, application/msgpack"));
+        assert!(!accepts("application/json, application/msgpack;q=0.5"));
+        assert!(!accepts("application/msgpack;q=0"));
+        assert!(!accepts("application/json"));
+        assert!(!accepts_msgpack(&HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn test_load_table_msgpack_round_trip() {
+        let expected = load_table_result();
+
+        let json_response = router()
+            .oneshot(
+                http::Request::builder()
+                    .uri("/table")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            json_response.headers().get(header::CONTENT_TYPE).unwrap(),
+            JSON_CONTENT_TYPE
+        );
+        let from_json: LoadTableResult =
+            serde_json::from_slice(&body_bytes(json_response).await).unwrap();
+
+        let msgpack_response = router()
+            .oneshot(
+                http::Request::builder()
+                    .uri("/table")
+                    .header(header::ACCEPT, MSGPACK_CONTENT_TYPE)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(msgpack_response.status(), http::StatusCode::OK);
+        assert_eq!(
+            msgpack_response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .unwrap(),
+            MSGPACK_CONTENT_TYPE
+        );
+        let from_msgpack: LoadTableResult =
+            rmp_serde::from_slice(&body_bytes(msgpack_response).await).unwrap();
+
+        assert_eq!(from_msgpack, from_json);
+        assert_eq!(from_msgpack, expected);
+    }
+
+    #[tokio::test]
+    async fn test_msgpack_request_body() {
+        let request = CreateNamespaceRequest {
+            namespace: NamespaceIdent::from_vec(vec!["ns1".to_string()]).unwrap(),
+            properties: None,
+        };
+        let response = router()
+            .oneshot(
+                http::Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/namespace")
+                    .header(header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+                    .header(header::ACCEPT, MSGPACK_CONTENT_TYPE)
+                    .body(Body::from(rmp_serde::to_vec_named(&request).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        let namespace: NamespaceIdent = rmp_serde::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(namespace, request.namespace);
+
+        // Malformed bodies are rejected before reaching the handler
+        let response = router()
+            .oneshot(
+                http::Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/namespace")
+                    .header(header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+                    .body(Body::from(vec![0xc1]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+        let error: IcebergErrorResponse =
+            serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(error.error.r#type, "InvalidMsgPackBody");
+
+        // ... and so are bodies exceeding the body limit
+        let response = router()
+            .oneshot(
+                http::Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/namespace")
+                    .header(header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+                    .body(Body::from(vec![0; 3 * 1024 * 1024]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_encoded_as_msgpack() {
+        let response = router()
+            .oneshot(
+                http::Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/namespace")
+                    .header(header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+                    .header(header::ACCEPT, MSGPACK_CONTENT_TYPE)
+                    .body(Body::from(vec![0xc1]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            MSGPACK_CONTENT_TYPE
+        );
+        let error: IcebergErrorResponse =
+            rmp_serde::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(error.error.r#type, "InvalidMsgPackBody");
+    }
+
+    #[tokio::test]
+    async fn test_other_responses_keep_their_encoding() {
+        let response = router()
+            .oneshot(
+                http::Request::builder()
+                    .uri("/plain")
+                    .header(header::ACCEPT, MSGPACK_CONTENT_TYPE)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            JSON_CONTENT_TYPE
+        );
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(body, serde_json::json!({"plain": true}));
+    }
+}
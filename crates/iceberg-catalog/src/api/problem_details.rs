@@ -0,0 +1,135 @@
+use axum::{
+    body::Body,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::{header, HeaderMap, HeaderValue};
+
+use crate::api::{ProblemDetails, PROBLEM_JSON_CONTENT_TYPE};
+
+/// Render error responses as problem+json (RFC 9457) if the client
+/// prefers it via the `Accept` header. All other responses, as well as errors
+/// for clients that don't ask for problem+json, are passed through unchanged.
+pub(crate) async fn problem_details_fn(
+    headers: HeaderMap,
+    request: axum::extract::Request,
+    next: Next,
+) -> Response {
+    let accepts_problem_json = accepts_problem_json(&headers);
+    let mut response = next.run(request).await;
+    if !accepts_problem_json {
+        return response;
+    }
+
+    let Some(problem_details) = response.extensions_mut().remove::<ProblemDetails>() else {
+        return response;
+    };
+    let Ok(body) = serde_json::to_vec(&problem_details) else {
+        return problem_details.into_response();
+    };
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(PROBLEM_JSON_CONTENT_TYPE),
+    );
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn accepts_problem_json(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|hv| hv.to_str().ok())
+        .flat_map(|hv| hv.split(','))
+        .any(|media_range| {
+            let mut params = media_range.split(';').map(str::trim);
+            let media_type = params.next().unwrap_or_default();
+            let quality = params
+                .find_map(|p| p.strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            media_type.eq_ignore_ascii_case(PROBLEM_JSON_CONTENT_TYPE) && quality > 0.0
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{routing::get, Router};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::api::{ErrorModel, IcebergErrorResponse};
+
+    async fn send(accept: Option<&str>) -> Response {
+        let router = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    IcebergErrorResponse::from(ErrorModel::not_found(
+                        "Table does not exist",
+                        "NoSuchTableException",
+                        None,
+                    ))
+                }),
+            )
+            .layer(axum::middleware::from_fn(problem_details_fn));
+        let mut request = http::Request::builder().uri("/");
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body(response: Response) -> serde_json::Value {
+        let bytes = response.collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_accepts_problem_json() {
+        let accepts = |v: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, HeaderValue::from_static(v));
+            accepts_problem_json(&headers)
+        };
+        assert!(accepts("application/problem+json"));
+        assert!(accepts("application/json, application/problem+json;q=0.5"));
+        assert!(!accepts("application/problem+json;q=0"));
+        assert!(!accepts("application/json"));
+        assert!(!accepts_problem_json(&HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn test_error_representations() {
+        // Iceberg clients keep getting the Iceberg error model
+        let response = send(None).await;
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        let iceberg: IcebergErrorResponse = serde_json::from_value(body(response).await).unwrap();
+        assert_eq!(iceberg.error.r#type, "NoSuchTableException");
+        assert_eq!(iceberg.error.code, 404);
+        assert_eq!(iceberg.error.message, "Table does not exist");
+
+        let response = send(Some("application/problem+json")).await;
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            PROBLEM_JSON_CONTENT_TYPE
+        );
+        let problem: ProblemDetails = serde_json::from_value(body(response).await).unwrap();
+        assert_eq!(problem.error_type(), "NoSuchTableException");
+        assert_eq!(problem.r#type, "urn:iceberg:error:NoSuchTableException");
+        assert_eq!(problem.status, 404);
+        assert_eq!(problem.detail, "Table does not exist");
+        // The error id is kept for correlation with server logs
+        assert!(problem.instance.unwrap().starts_with("urn:uuid:"));
+    }
+}
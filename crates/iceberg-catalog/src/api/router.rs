@@ -7,28 +7,36 @@ use http::{header, HeaderValue, Method};
 use limes::Authenticator;
 use tower::ServiceBuilder;
 use tower_http::{
-    catch_panic::CatchPanicLayer, compression::CompressionLayer, cors::AllowOrigin,
-    sensitive_headers::SetSensitiveHeadersLayer, timeout::TimeoutLayer, trace, trace::TraceLayer,
-    ServiceBuilderExt,
+    catch_panic::CatchPanicLayer, cors::AllowOrigin, sensitive_headers::SetSensitiveHeadersLayer,
+    timeout::TimeoutLayer, trace, trace::TraceLayer, ServiceBuilderExt,
 };
 
 use crate::{
     api::{
+        compression::response_compression_layer,
         iceberg::v1::new_v1_full_router,
+        load_shedding::{load_shedding_fn, LoadShedder},
         management::v1::{api_doc as v1_api_doc, ApiServer},
+        msgpack::msgpack_encoding_fn,
+        problem_details::problem_details_fn,
         shutdown_signal, ApiContext,
     },
     request_metadata::create_request_metadata_with_trace_and_project_fn,
     service::{
-        authn::{auth_middleware_fn, AuthMiddlewareState},
+        authn::{auth_middleware_fn, warehouse_authentication_method_fn, AuthMiddlewareState},
         authz::Authorizer,
+        client_certificate::ClientCertificateVerifier,
+        commit_observer::CommitObservers,
         contract_verification::ContractVerifiers,
+        create_request_mutator::CreateRequestMutators,
         event_publisher::CloudEventsPublisher,
         health::ServiceHealthProvider,
         task_queue::TaskQueues,
+        token_verification::IntrospectionVerifier,
         Catalog, SecretStore, State,
     },
     tracing::{MakeRequestUuid7, RestMakeSpan},
+    CONFIG,
 };
 
 lazy_static::lazy_static! {
@@ -47,6 +55,8 @@ pub struct RouterArgs<C: Catalog, A: Authorizer + Clone, S: SecretStore, N: Auth
     pub queues: TaskQueues,
     pub publisher: CloudEventsPublisher,
     pub table_change_checkers: ContractVerifiers,
+    pub commit_observers: CommitObservers,
+    pub create_request_mutators: CreateRequestMutators,
     pub service_health_provider: ServiceHealthProvider,
     pub cors_origins: Option<&'static [HeaderValue]>,
     pub metrics_layer: Option<PrometheusMetricLayer<'static>>,
@@ -63,6 +73,8 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore, N: Authenticator + Debug
             .field("queues", &self.queues)
             .field("publisher", &self.publisher)
             .field("table_change_checkers", &self.table_change_checkers)
+            .field("commit_observers", &self.commit_observers)
+            .field("create_request_mutators", &self.create_request_mutators)
             .field("authenticator", &self.authenticator)
             .field("svhp", &self.service_health_provider)
             .field("cors_origins", &self.cors_origins)
@@ -92,6 +104,8 @@ pub fn new_full_router<
         queues,
         publisher,
         table_change_checkers,
+        commit_observers,
+        create_request_mutators,
         service_health_provider,
         cors_origins,
         metrics_layer,
@@ -127,10 +141,23 @@ pub fn new_full_router<
             ])
     }));
 
-    let maybe_auth_layer = if let Some(authenticator) = authenticator {
+    let token_introspection = IntrospectionVerifier::from_config();
+    let client_certificate = ClientCertificateVerifier::from_config()?;
+    let auth_enabled =
+        authenticator.is_some() || token_introspection.is_some() || client_certificate.is_some();
+    // Runs after authentication, as layers added later wrap earlier ones.
+    let maybe_authentication_method_layer = option_layer(auth_enabled.then(|| {
+        axum::middleware::from_fn_with_state(
+            catalog_state.clone(),
+            warehouse_authentication_method_fn::<C>,
+        )
+    }));
+    let maybe_auth_layer = if auth_enabled {
         option_layer(Some(axum::middleware::from_fn_with_state(
             AuthMiddlewareState {
                 authenticator,
+                token_introspection,
+                client_certificate,
                 authorizer: authorizer.clone(),
             },
             auth_middleware_fn,
@@ -139,10 +166,20 @@ pub fn new_full_router<
         option_layer(None)
     };
 
+    let maybe_load_shedding_layer = option_layer(CONFIG.load_shedding.is_enabled().then(|| {
+        axum::middleware::from_fn_with_state(
+            LoadShedder::new(&CONFIG.load_shedding),
+            load_shedding_fn,
+        )
+    }));
+
+    // Routes added after the load shedding layer, such as `/health`, are never shed.
     let router = Router::new()
         .nest("/catalog/v1", v1_routes)
         .nest("/management/v1", management_routes)
+        .layer(maybe_authentication_method_layer)
         .layer(maybe_auth_layer)
+        .layer(maybe_load_shedding_layer)
         .route(
             "/health",
             get(|| async move {
@@ -161,13 +198,17 @@ pub fn new_full_router<
         .layer(axum::middleware::from_fn(
             create_request_metadata_with_trace_and_project_fn,
         ))
+        .layer(axum::middleware::from_fn(problem_details_fn))
+        .layer(axum::middleware::from_fn(msgpack_encoding_fn))
         .layer(
             ServiceBuilder::new()
-                .set_x_request_id(MakeRequestUuid7)
+                .set_request_id(CONFIG.request_id_header.clone(), MakeRequestUuid7)
                 .layer(SetSensitiveHeadersLayer::new([
                     axum::http::header::AUTHORIZATION,
                 ]))
-                .layer(CompressionLayer::new())
+                .layer(option_layer(CONFIG.response_compression.enabled.then(
+                    || response_compression_layer(&CONFIG.response_compression),
+                )))
                 .layer(
                     TraceLayer::new_for_http()
                         .on_failure(())
@@ -177,7 +218,7 @@ pub fn new_full_router<
                 .layer(TimeoutLayer::new(std::time::Duration::from_secs(30)))
                 .layer(CatchPanicLayer::new())
                 .layer(maybe_cors_layer)
-                .propagate_x_request_id(),
+                .propagate_request_id(CONFIG.request_id_header.clone()),
         )
         .with_state(ApiContext {
             v1_state: State {
@@ -186,6 +227,8 @@ pub fn new_full_router<
                 secrets: secrets_state,
                 publisher,
                 contract_verifiers: table_change_checkers,
+                commit_observers,
+                create_request_mutators,
                 queues,
             },
         });
@@ -202,10 +245,14 @@ pub fn new_full_router<
 /// # Errors
 /// Fails if the webserver panics
 pub async fn serve(listener: tokio::net::TcpListener, router: Router) -> anyhow::Result<()> {
-    axum::serve(listener, router)
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .map_err(|e| anyhow::anyhow!(e).context("error running HTTP server"))
+    // The peer address identifies proxies trusted to forward client certificates
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await
+    .map_err(|e| anyhow::anyhow!(e).context("error running HTTP server"))
 }
 
 #[cfg(test)]
@@ -6,7 +6,10 @@ use iceberg_ext::{
     spec::{TableMetadataBuildResult, TableMetadataBuilder},
 };
 
-use crate::service::{ErrorModel, Result};
+use crate::{
+    service::{ErrorModel, Result},
+    CONFIG,
+};
 
 /// Apply the commits to table metadata.
 pub(super) fn apply_commit(
@@ -16,11 +19,23 @@ pub(super) fn apply_commit(
     updates: Vec<TableUpdate>,
 ) -> Result<TableMetadataBuildResult> {
     // Check requirements
+    let current_metadata = metadata_location.map(|_| &metadata);
     requirements
         .iter()
         .map(|r| {
-            r.check(metadata_location.map(|_| &metadata)).map_err(|e| {
-                ErrorModel::conflict(e.to_string(), e.kind().to_string(), Some(Box::new(e))).into()
+            r.check(current_metadata).map_err(|e| {
+                let error =
+                    ErrorModel::conflict(e.to_string(), e.kind().to_string(), Some(Box::new(e)));
+                if CONFIG.commit_conflict_diagnostics {
+                    error
+                        .append_detail(
+                            conflict_diagnostics(r, current_metadata, metadata_location)
+                                .to_string(),
+                        )
+                        .into()
+                } else {
+                    error.into()
+                }
             })
         })
         .collect::<Result<Vec<_>>>()?;
@@ -63,11 +78,16 @@ pub(super) fn apply_commit(
                     .into());
                 }
             }
+            TableUpdate::RemoveSnapshots { snapshot_ids } => {
+                let snapshot_ids = snapshot_ids.clone();
+                builder = apply_update(update, builder)?;
+                // Partition statistics are registered per snapshot and dropped with it
+                for snapshot_id in snapshot_ids {
+                    builder = builder.remove_partition_statistics(snapshot_id);
+                }
+            }
             _ => {
-                builder = TableUpdate::apply(update, builder).map_err(|e| {
-                    let msg = e.message().to_string();
-                    ErrorModel::bad_request(msg, "InvalidTableUpdate", Some(Box::new(e)))
-                })?;
+                builder = apply_update(update, builder)?;
             }
         }
     }
@@ -86,6 +106,60 @@ pub(super) fn apply_commit(
         })
 }
 
+fn apply_update(
+    update: TableUpdate,
+    builder: TableMetadataBuilder,
+) -> Result<TableMetadataBuilder> {
+    TableUpdate::apply(update, builder).map_err(|e| {
+        let msg = e.message().to_string();
+        ErrorModel::bad_request(msg, "InvalidTableUpdate", Some(Box::new(e))).into()
+    })
+}
+
+/// JSON description of a failed requirement, containing the requirement as sent
+/// by the client, the actual value of the checked field, the current metadata
+/// location and the current snapshot ID of the table.
+///
+/// Requirements are only checked after the caller is authorized to commit
+/// to the table, so all values are part of the metadata the caller may load.
+pub(super) fn conflict_diagnostics(
+    requirement: &TableRequirement,
+    metadata: Option<&TableMetadata>,
+    metadata_location: Option<&Location>,
+) -> serde_json::Value {
+    let actual = metadata.map(|metadata| match requirement {
+        TableRequirement::NotExist | TableRequirement::UuidMatch { .. } => {
+            serde_json::json!(metadata.uuid())
+        }
+        TableRequirement::CurrentSchemaIdMatch { .. } => {
+            serde_json::json!(metadata.current_schema_id())
+        }
+        TableRequirement::LastAssignedFieldIdMatch { .. } => {
+            serde_json::json!(metadata.last_column_id())
+        }
+        TableRequirement::LastAssignedPartitionIdMatch { .. } => {
+            serde_json::json!(metadata.last_partition_id())
+        }
+        TableRequirement::DefaultSpecIdMatch { .. } => {
+            serde_json::json!(metadata.default_partition_spec_id())
+        }
+        TableRequirement::DefaultSortOrderIdMatch { .. } => {
+            serde_json::json!(metadata.default_sort_order_id())
+        }
+        TableRequirement::RefSnapshotIdMatch { r#ref, .. } => serde_json::json!(metadata
+            .refs()
+            .get(r#ref)
+            .map(|reference| reference.snapshot_id)),
+    });
+
+    serde_json::json!({
+        "failed-requirement": requirement,
+        "actual": actual,
+        "current-metadata-location": metadata_location.map(ToString::to_string),
+        "current-snapshot-id": metadata.and_then(TableMetadata::current_snapshot_id),
+    })
+}
+
 fn table_update_as_str(update: &TableUpdate) -> &str {
     match update {
         TableUpdate::UpgradeFormatVersion { .. } => "upgrade_format_version",
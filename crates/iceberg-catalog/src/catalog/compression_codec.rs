@@ -55,16 +55,22 @@ impl CompressionCodec {
         }
     }
 
-    pub async fn decompress(self, payload: Vec<u8>) -> Result<Vec<u8>, IoError> {
+    /// Decompress `payload`. Decompression stops with [`IoError::MetadataTooLarge`]
+    /// as soon as the output exceeds `max_size` bytes.
+    pub async fn decompress(self, payload: Vec<u8>, max_size: u64) -> Result<Vec<u8>, IoError> {
         match self {
             CompressionCodec::None => Ok(payload),
             CompressionCodec::Gzip => {
                 match tokio::task::spawn_blocking(move || {
                     let mut decompressed_metadata = Vec::new();
-                    let mut decoder = flate2::read::GzDecoder::new(payload.as_slice());
+                    let decoder = flate2::read::GzDecoder::new(payload.as_slice());
                     decoder
+                        .take(max_size.saturating_add(1))
                         .read_to_end(&mut decompressed_metadata)
                         .map_err(|e| IoError::FileCompression(Box::new(e)))?;
+                    if decompressed_metadata.len() as u64 > max_size {
+                        return Err(IoError::MetadataTooLarge(max_size));
+                    }
 
                     Ok(decompressed_metadata)
                 })
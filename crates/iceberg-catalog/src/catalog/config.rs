@@ -11,6 +11,7 @@ use crate::{
     },
     request_metadata::RequestMetadata,
     service::{
+        authn::require_allowed_authentication_method,
         authz::{Authorizer, CatalogProjectAction, CatalogWarehouseAction},
         Catalog, ProjectId, SecretStore, State, Transaction,
     },
@@ -51,6 +52,14 @@ impl<A: Authorizer + Clone, C: Catalog, S: SecretStore>
             return Err(ErrorModel::bad_request("No warehouse specified. Please specify the 'warehouse' parameter in the GET /config request.".to_string(), "GetConfigNoWarehouseProvided", None).into());
         };
 
+        // The warehouse is addressed by name, so the authentication middleware cannot check it
+        require_allowed_authentication_method::<C>(
+            warehouse_id,
+            &request_metadata,
+            api_context.v1_state.catalog.clone(),
+        )
+        .await?;
+
         authorizer
             .require_warehouse_action(
                 &request_metadata,
@@ -0,0 +1,138 @@
+use iceberg_ext::configs::Location;
+use uuid::Uuid;
+
+use crate::{
+    request_metadata::RequestMetadata,
+    service::{
+        event_publisher::{CloudEventsPublisher, EventMetadata},
+        storage::{StoragePermissions, TableConfig},
+    },
+    CONFIG,
+};
+
+/// Type of the event emitted whenever storage credentials are vended.
+pub(crate) const CREDENTIALS_VENDED_EVENT: &str = "credentials.vended";
+
+/// Publish a [`CREDENTIALS_VENDED_EVENT`] if enabled and `config` contains vended credentials.
+///
+/// The event records who received which access to which location until when.
+/// The credentials themselves are never part of the event.
+pub(crate) async fn publish_credentials_vended_event(
+    publisher: &CloudEventsPublisher,
+    request_metadata: &RequestMetadata,
+    metadata: EventMetadata,
+    location: &Location,
+    storage_permissions: StoragePermissions,
+    config: &TableConfig,
+) {
+    if CONFIG.credential_vending_events {
+        publish(
+            publisher,
+            request_metadata,
+            metadata,
+            location,
+            storage_permissions,
+            config,
+        )
+        .await;
+    }
+}
+
+async fn publish(
+    publisher: &CloudEventsPublisher,
+    request_metadata: &RequestMetadata,
+    metadata: EventMetadata,
+    location: &Location,
+    storage_permissions: StoragePermissions,
+    config: &TableConfig,
+) {
+    if config.creds.inner().is_empty() {
+        return;
+    }
+
+    let _ = publisher
+        .publish(
+            Uuid::now_v7(),
+            CREDENTIALS_VENDED_EVENT,
+            serde_json::json!({
+                "principal": request_metadata.actor().to_string(),
+                "location": location.to_string(),
+                "access-level": storage_permissions,
+                "expires-at-ms": config.credentials_expire_at_ms(),
+            }),
+            metadata,
+        )
+        .await;
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use iceberg_ext::configs::table::{s3, TableProperties};
+
+    use super::*;
+    use crate::service::{event_publisher::Message, TabularIdentUuid};
+
+    #[tokio::test]
+    async fn test_vend_emits_single_event_without_secrets() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let publisher = CloudEventsPublisher::new(tx);
+        let mut creds = TableProperties::default();
+        creds.insert(&s3::AccessKeyId("AKIAVENDED".to_string()));
+        creds.insert(&s3::SecretAccessKey("vended-secret-key".to_string()));
+        creds.insert(&s3::SessionToken("vended-session-token".to_string()));
+        creds.insert(&s3::SessionTokenExpiresAtMs("1700000000000".to_string()));
+        let config = TableConfig {
+            config: creds.clone(),
+            creds,
+        };
+        let metadata = EventMetadata {
+            tabular_id: TabularIdentUuid::Table(Uuid::now_v7()),
+            warehouse_id: Uuid::now_v7().into(),
+            name: "tab".to_string(),
+            namespace: "ns".to_string(),
+            prefix: String::new(),
+            num_events: 1,
+            sequence_number: 0,
+            trace_id: Uuid::now_v7().to_string(),
+        };
+        let location = Location::from_str("s3://bucket/ns/tab").unwrap();
+
+        publish(
+            &publisher,
+            &RequestMetadata::new_unauthenticated(),
+            metadata.clone(),
+            &location,
+            StoragePermissions::Read,
+            &config,
+        )
+        .await;
+        // Configs without credentials, e.g. for remote signing, are not reported
+        publish(
+            &publisher,
+            &RequestMetadata::new_unauthenticated(),
+            metadata,
+            &location,
+            StoragePermissions::Read,
+            &TableConfig {
+                creds: TableProperties::default(),
+                config: TableProperties::default(),
+            },
+        )
+        .await;
+
+        let Some(Message::Event(event)) = rx.recv().await else {
+            panic!("Expected an event");
+        };
+        assert!(rx.try_recv().is_err());
+        assert_eq!(event.typ, CREDENTIALS_VENDED_EVENT);
+        assert_eq!(event.data["access-level"], "read");
+        assert_eq!(event.data["location"], "s3://bucket/ns/tab");
+        assert_eq!(event.data["expires-at-ms"], 1_700_000_000_000_i64);
+        let data = event.data.to_string();
+        for secret in ["AKIAVENDED", "vended-secret-key", "vended-session-token"] {
+            assert!(!data.contains(secret), "{data}");
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use iceberg::{
+    io::FileIO,
+    spec::{DataFile, TableMetadata},
+    TableIdent, TableUpdate,
+};
+use itertools::Itertools;
+
+use super::file_path_validation::added_data_files;
+use crate::{
+    api::{ErrorModel, Result},
+    service::{
+        backend_timeout::{with_timeout, Backend},
+        GetWarehouseResponse,
+    },
+    CONFIG,
+};
+
+/// File formats that can be allowed for a warehouse.
+pub(crate) const SUPPORTED_FILE_FORMATS: [&str; 3] = ["parquet", "avro", "orc"];
+
+/// Table property holding the file format engines use for new data files.
+pub(crate) const PROPERTY_WRITE_FORMAT_DEFAULT: &str = "write.format.default";
+
+/// Steer the default write format of a new table towards the file formats
+/// allowed by the warehouse.
+///
+/// If `write.format.default` is not set, it is set to the first allowed format.
+///
+/// # Errors
+/// - 400 if `write.format.default` is set to a format the warehouse does not allow
+pub(crate) fn apply_default_write_format(
+    warehouse: &GetWarehouseResponse,
+    properties: &mut Option<HashMap<String, String>>,
+) -> Result<()> {
+    let Some(allowed) = &warehouse.allowed_file_formats else {
+        return Ok(());
+    };
+
+    match properties
+        .as_ref()
+        .and_then(|p| p.get(PROPERTY_WRITE_FORMAT_DEFAULT))
+    {
+        Some(write_format) => require_allowed_write_format(warehouse, write_format),
+        None => {
+            if let Some(default) = allowed.first() {
+                properties
+                    .get_or_insert_with(HashMap::new)
+                    .insert(PROPERTY_WRITE_FORMAT_DEFAULT.to_string(), default.clone());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Require that a commit complies with the file formats allowed by the warehouse:
+/// Files added by snapshots in `updates` must use an allowed format, and a
+/// changed `write.format.default` property must name an allowed format.
+///
+/// `metadata` is the table metadata after `updates` have been applied.
+///
+/// # Errors
+/// - 400 if a file or the default write format uses a format that is not allowed
+/// - 500 if manifests cannot be read
+pub(crate) async fn validate_added_file_formats(
+    warehouse: &GetWarehouseResponse,
+    table: &TableIdent,
+    previous_metadata: &TableMetadata,
+    metadata: &TableMetadata,
+    updates: &[TableUpdate],
+    file_io: &FileIO,
+) -> Result<()> {
+    if warehouse.allowed_file_formats.is_none() {
+        return Ok(());
+    }
+
+    let write_format = metadata.properties().get(PROPERTY_WRITE_FORMAT_DEFAULT);
+    if let Some(write_format) = write_format {
+        if previous_metadata
+            .properties()
+            .get(PROPERTY_WRITE_FORMAT_DEFAULT)
+            != Some(write_format)
+        {
+            require_allowed_write_format(warehouse, write_format)?;
+        }
+    }
+
+    let mut offending_files = vec![];
+    for update in updates {
+        if let TableUpdate::AddSnapshot { snapshot } = update {
+            let added_files = with_timeout(
+                Backend::StorageRead,
+                CONFIG.backend_timeouts.storage_read,
+                added_data_files(metadata, snapshot, file_io),
+            )
+            .await??;
+            offending_files.extend(
+                added_files
+                    .iter()
+                    .map(|file| (file.file_path().to_string(), file_format(file)))
+                    .filter(|(_, format)| !warehouse.allows_file_format(format)),
+            );
+        }
+    }
+
+    if offending_files.is_empty() {
+        return Ok(());
+    }
+
+    let formats = offending_files
+        .iter()
+        .map(|(_, format)| format)
+        .unique()
+        .join(", ");
+    Err(ErrorModel::bad_request(
+        format!(
+            "Commit to table '{table}' adds {} file(s) with formats not allowed for warehouse '{}': {formats}. Allowed formats: {}.",
+            offending_files.len(),
+            warehouse.name,
+            allowed_formats(warehouse)
+        ),
+        "DataFileFormatNotAllowed",
+        None,
+    )
+    .append_details(
+        offending_files
+            .into_iter()
+            .map(|(path, format)| format!("{path} ({format})")),
+    )
+    .into())
+}
+
+fn require_allowed_write_format(
+    warehouse: &GetWarehouseResponse,
+    write_format: &str,
+) -> Result<()> {
+    if warehouse.allows_file_format(write_format) {
+        return Ok(());
+    }
+
+    Err(ErrorModel::bad_request(
+        format!(
+            "Default write format '{write_format}' is not allowed for warehouse '{}'. Allowed formats: {}.",
+            warehouse.name,
+            allowed_formats(warehouse)
+        ),
+        "DataFileFormatNotAllowed",
+        None,
+    )
+    .into())
+}
+
+fn file_format(file: &DataFile) -> String {
+    file.file_format().to_string().to_lowercase()
+}
+
+fn allowed_formats(warehouse: &GetWarehouseResponse) -> String {
+    warehouse
+        .allowed_file_formats
+        .as_deref()
+        .unwrap_or_default()
+        .join(", ")
+}
@@ -0,0 +1,196 @@
+use std::str::FromStr;
+
+use iceberg::{
+    io::FileIO,
+    spec::{DataFile, ManifestStatus, Snapshot, TableMetadata},
+    TableIdent, TableUpdate,
+};
+use iceberg_ext::configs::Location;
+
+use crate::{
+    api::{ErrorModel, Result},
+    service::backend_timeout::{with_timeout, Backend},
+    CONFIG,
+};
+
+/// Maximum number of offending paths included in the error message.
+/// All paths are listed in the error details.
+const MAX_PATHS_IN_MESSAGE: usize = 10;
+
+/// Require that all data and delete files added by snapshots in `updates`
+/// are located below the location of the table.
+///
+/// `metadata` is the table metadata after `updates` have been applied.
+///
+/// # Errors
+/// - 400 listing every file outside of the table location
+/// - 500 if manifests cannot be read
+pub(crate) async fn validate_added_file_paths(
+    table: &TableIdent,
+    metadata: &TableMetadata,
+    updates: &[TableUpdate],
+    file_io: &FileIO,
+) -> Result<()> {
+    let table_location = Location::from_str(metadata.location()).map_err(|e| {
+        ErrorModel::internal(
+            format!("Table location '{}' is invalid", metadata.location()),
+            "InvalidTableLocation",
+            Some(Box::new(e)),
+        )
+    })?;
+
+    let mut offending_paths = vec![];
+    for update in updates {
+        if let TableUpdate::AddSnapshot { snapshot } = update {
+            let added_files = with_timeout(
+                Backend::StorageRead,
+                CONFIG.backend_timeouts.storage_read,
+                added_data_files(metadata, snapshot, file_io),
+            )
+            .await??;
+            offending_paths.extend(
+                added_files
+                    .iter()
+                    .map(|file| file.file_path().to_string())
+                    .filter(|path| {
+                        !Location::from_str(path)
+                            .is_ok_and(|l| l.is_sublocation_of(&table_location))
+                    }),
+            );
+        }
+    }
+
+    if offending_paths.is_empty() {
+        return Ok(());
+    }
+
+    let more = match offending_paths.len().saturating_sub(MAX_PATHS_IN_MESSAGE) {
+        0 => String::new(),
+        n => format!(" and {n} more"),
+    };
+    let message = format!(
+        "Commit to table '{table}' adds files outside of the table location '{table_location}': {}{more}",
+        offending_paths
+            .iter()
+            .take(MAX_PATHS_IN_MESSAGE)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    Err(
+        ErrorModel::bad_request(message, "DataFileOutsideTableLocation", None)
+            .append_details(offending_paths)
+            .into(),
+    )
+}
+
+/// Require that partition statistics files registered by `updates` are located
+/// below the location of the table and exist in storage.
+///
+/// # Errors
+/// - 400 if a statistics file is outside of the table location or does not exist
+/// - 500 if storage cannot be reached
+pub(crate) async fn validate_added_partition_statistics(
+    table: &TableIdent,
+    metadata: &TableMetadata,
+    updates: &[TableUpdate],
+    file_io: &FileIO,
+) -> Result<()> {
+    let table_location = Location::from_str(metadata.location()).map_err(|e| {
+        ErrorModel::internal(
+            format!("Table location '{}' is invalid", metadata.location()),
+            "InvalidTableLocation",
+            Some(Box::new(e)),
+        )
+    })?;
+
+    for update in updates {
+        let TableUpdate::SetPartitionStatistics {
+            partition_statistics,
+        } = update
+        else {
+            continue;
+        };
+        let path = &partition_statistics.statistics_path;
+        if !Location::from_str(path).is_ok_and(|l| l.is_sublocation_of(&table_location)) {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Partition statistics file '{path}' of table '{table}' is outside of the table location '{table_location}'"
+                ),
+                "StatisticsFileOutsideTableLocation",
+                None,
+            )
+            .into());
+        }
+
+        let exists = with_timeout(
+            Backend::StorageRead,
+            CONFIG.backend_timeouts.storage_read,
+            file_io.exists(path),
+        )
+        .await?
+        .map_err(|e| {
+            ErrorModel::internal(
+                format!("Failed to check existence of partition statistics file '{path}': {e}"),
+                "StatisticsFileValidationError",
+                Some(Box::new(e)),
+            )
+        })?;
+        if !exists {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Partition statistics file '{path}' for snapshot {} of table '{table}' does not exist",
+                    partition_statistics.snapshot_id
+                ),
+                "StatisticsFileNotFound",
+                None,
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Data and delete files added by `snapshot` itself.
+pub(super) async fn added_data_files(
+    metadata: &TableMetadata,
+    snapshot: &Snapshot,
+    file_io: &FileIO,
+) -> Result<Vec<DataFile>> {
+    let manifest_list = snapshot
+        .load_manifest_list(file_io, metadata)
+        .await
+        .map_err(|e| io_error("Failed to read manifest list", e))?;
+
+    let mut files = vec![];
+    // Manifests of earlier snapshots only contain files of those snapshots
+    for manifest_file in manifest_list
+        .entries()
+        .iter()
+        .filter(|m| m.added_snapshot_id == snapshot.snapshot_id())
+    {
+        let manifest = manifest_file
+            .load_manifest(file_io)
+            .await
+            .map_err(|e| io_error("Failed to read manifest", e))?;
+        files.extend(
+            manifest
+                .entries()
+                .iter()
+                .filter(|e| {
+                    matches!(e.status(), ManifestStatus::Added)
+                        && e.snapshot_id() == Some(snapshot.snapshot_id())
+                })
+                .map(|e| e.data_file().clone()),
+        );
+    }
+    Ok(files)
+}
+
+fn io_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(
+        format!("{message} during validation of added files: {e}"),
+        "AddedFileValidationError",
+        Some(Box::new(e)),
+    )
+}
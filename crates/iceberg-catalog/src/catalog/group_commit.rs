@@ -0,0 +1,515 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    sync::{LazyLock, Mutex, PoisonError},
+    time::Duration,
+};
+
+use iceberg::{
+    io::FileIO,
+    spec::{
+        FormatVersion, ManifestFile, ManifestListWriter, Operation, Snapshot, SnapshotReference,
+        SnapshotRetention, Summary, TableMetadata, MAIN_BRANCH,
+    },
+    TableRequirement, TableUpdate,
+};
+use itertools::Itertools;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+use super::{maybe_get_secret, require_warehouse_id, tables::commit_table_internal};
+use crate::{
+    api::iceberg::v1::{
+        ApiContext, CommitTableRequest, CommitTableResponse, ErrorModel, Prefix, Result, TableIdent,
+    },
+    request_metadata::RequestMetadata,
+    service::{
+        authz::{Authorizer, CatalogTableAction, CatalogWarehouseAction},
+        backend_timeout::{with_timeout, Backend},
+        secrets::SecretStore,
+        Catalog, ListFlags, State, TableIdentUuid, Transaction,
+    },
+    WarehouseIdent, CONFIG,
+};
+
+/// Prefix of snapshot summary properties holding table totals.
+const TOTAL_PROPERTY_PREFIX: &str = "total-";
+
+/// Appends of a group share the table and the parent snapshot.
+type GroupKey = (WarehouseIdent, TableIdent, Option<i64>);
+
+static PENDING_GROUPS: LazyLock<Mutex<HashMap<GroupKey, Vec<PendingCommit>>>> =
+    LazyLock::new(Mutex::default);
+
+struct PendingCommit {
+    table_id: TableIdentUuid,
+    request: CommitTableRequest,
+    request_metadata: RequestMetadata,
+    result: oneshot::Sender<Result<CommitTableResponse>>,
+}
+
+/// Parent snapshot of the append in `request` if it may be committed as part of a group.
+///
+/// Only commits that add a single `append` snapshot to `main` qualify. Requirements
+/// other than the table UUID and the parent being the current snapshot of `main`
+/// are strict and bypass grouping.
+pub(crate) fn groupable_parent(request: &CommitTableRequest) -> Option<Option<i64>> {
+    let (snapshot, _) = added_append(request)?;
+    let parent = snapshot.parent_snapshot_id();
+    request
+        .requirements
+        .iter()
+        .all(|requirement| match requirement {
+            TableRequirement::UuidMatch { .. } => true,
+            TableRequirement::RefSnapshotIdMatch { r#ref, snapshot_id } => {
+                r#ref == MAIN_BRANCH && *snapshot_id == parent
+            }
+            _ => false,
+        })
+        .then_some(parent)
+}
+
+fn added_append(request: &CommitTableRequest) -> Option<(&Snapshot, &SnapshotReference)> {
+    let [TableUpdate::AddSnapshot { snapshot }, TableUpdate::SetSnapshotRef {
+        ref_name,
+        reference,
+    }] = request.updates.as_slice()
+    else {
+        return None;
+    };
+    (ref_name == MAIN_BRANCH
+        && reference.snapshot_id == snapshot.snapshot_id()
+        && matches!(reference.retention, SnapshotRetention::Branch { .. })
+        && snapshot.summary().operation == Operation::Append)
+        .then_some((snapshot, reference))
+}
+
+/// Commit an append together with concurrent appends to the same table.
+///
+/// The first append of a group waits for `window` before the group is committed
+/// with a single metadata update. Each append is authorized individually before
+/// it joins a group. `parent_snapshot_id` must be obtained from [`groupable_parent`].
+pub(crate) async fn commit_grouped<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    prefix: Option<Prefix>,
+    request: CommitTableRequest,
+    parent_snapshot_id: Option<i64>,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+    window: Duration,
+) -> Result<CommitTableResponse> {
+    let warehouse_id = require_warehouse_id(prefix.clone())?;
+    let table_ident = request.identifier.clone().ok_or_else(|| {
+        ErrorModel::internal("Change without Identifier", "ChangeWithoutIdentifier", None)
+    })?;
+
+    // ------------------- AUTHZ -------------------
+    let authorizer = &state.v1_state.authz;
+    authorizer
+        .require_warehouse_action(
+            &request_metadata,
+            warehouse_id,
+            &CatalogWarehouseAction::CanUse,
+        )
+        .await?;
+    let table_id = C::table_idents_to_ids(
+        warehouse_id,
+        HashSet::from([&table_ident]),
+        ListFlags {
+            include_active: true,
+            include_staged: true,
+            include_deleted: false,
+        },
+        state.v1_state.catalog.clone(),
+    )
+    .await
+    .map(|mut ids| ids.remove(&table_ident).flatten());
+    let table_id = authorizer
+        .require_table_action(&request_metadata, table_id, &CatalogTableAction::CanCommit)
+        .await?;
+
+    // ------------------- BUSINESS LOGIC -------------------
+    let (sender, receiver) = oneshot::channel();
+    let key = (warehouse_id, table_ident, parent_snapshot_id);
+    let pending = PendingCommit {
+        table_id,
+        request,
+        request_metadata,
+        result: sender,
+    };
+    let starts_group = match PENDING_GROUPS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(key.clone())
+    {
+        Entry::Occupied(mut group) => {
+            group.get_mut().push(pending);
+            false
+        }
+        Entry::Vacant(group) => {
+            group.insert(vec![pending]);
+            true
+        }
+    };
+
+    if starts_group {
+        // Committed in a separate task, so that the group is not lost if the
+        // request that started it is cancelled.
+        tokio::spawn(async move {
+            tokio::time::sleep(window).await;
+            let group = PENDING_GROUPS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(&key)
+                .unwrap_or_default();
+            commit_group(prefix, group, state).await;
+        });
+    }
+
+    receiver.await.map_err(|_| {
+        ErrorModel::internal(
+            "Group commit was aborted before the append was committed",
+            "GroupCommitAborted",
+            None,
+        )
+    })?
+}
+
+async fn commit_group<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    prefix: Option<Prefix>,
+    group: Vec<PendingCommit>,
+    state: ApiContext<State<A, C, S>>,
+) {
+    if group.len() > 1 {
+        match commit_merged(prefix.clone(), &group, state.clone()).await {
+            Ok(Some(response)) => {
+                for pending in group {
+                    let _ = pending.result.send(Ok(response.clone()));
+                }
+                return;
+            }
+            Ok(None) => {}
+            // A single rejected append must not fail the others
+            Err(e) if e.error.code < 500 => {
+                tracing::debug!(
+                    "Group of {} appends rejected, committing individually: {}",
+                    group.len(),
+                    e.error
+                );
+            }
+            Err(e) => {
+                for pending in group {
+                    let _ = pending.result.send(Err(ErrorModel::builder()
+                        .message(e.error.message.clone())
+                        .r#type(e.error.r#type.clone())
+                        .code(e.error.code)
+                        .stack(e.error.stack.clone())
+                        .build()
+                        .into()));
+                }
+                return;
+            }
+        }
+    }
+
+    for pending in group {
+        let result = commit_table_internal(
+            prefix.clone(),
+            pending.request,
+            state.clone(),
+            pending.request_metadata,
+        )
+        .await;
+        let _ = pending.result.send(result);
+    }
+}
+
+/// Commit all appends of `group` with a single metadata update.
+/// Returns `None` if the table does not support grouped commits.
+async fn commit_merged<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    prefix: Option<Prefix>,
+    group: &[PendingCommit],
+    state: ApiContext<State<A, C, S>>,
+) -> Result<Option<CommitTableResponse>> {
+    let warehouse_id = require_warehouse_id(prefix.clone())?;
+    let Some(leader) = group.first() else {
+        return Ok(None);
+    };
+    let Some(appends) = group
+        .iter()
+        .map(|pending| added_append(&pending.request))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(None);
+    };
+
+    let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
+    let mut tables =
+        C::load_tables(warehouse_id, [leader.table_id], false, t.transaction()).await?;
+    t.commit().await?;
+    let Some(table) = tables.remove(&leader.table_id) else {
+        return Ok(None);
+    };
+    // Format version 1 manifests carry no sequence numbers.
+    if table.table_metadata.format_version() == FormatVersion::V1 {
+        return Ok(None);
+    }
+
+    let storage_secret = maybe_get_secret::<C, _>(
+        table.storage_secret_ident,
+        state.v1_state.catalog.clone(),
+        &state.v1_state.secrets,
+    )
+    .await?;
+    let file_io = table.storage_profile.file_io(storage_secret.as_ref())?;
+    let snapshots = with_timeout(
+        Backend::StorageWrite,
+        CONFIG.backend_timeouts.storage_write,
+        rebase_snapshots(
+            &table.table_metadata,
+            appends
+                .iter()
+                .map(|(snapshot, _)| (*snapshot).clone())
+                .collect(),
+            &file_io,
+        ),
+    )
+    .await??;
+
+    let requirements = group
+        .iter()
+        .flat_map(|pending| &pending.request.requirements)
+        .filter_map(|requirement| match requirement {
+            TableRequirement::UuidMatch { uuid } => Some(*uuid),
+            _ => None,
+        })
+        .unique()
+        .map(|uuid| TableRequirement::UuidMatch { uuid })
+        .chain(
+            group
+                .iter()
+                .flat_map(|pending| &pending.request.requirements)
+                .any(|requirement| {
+                    matches!(requirement, TableRequirement::RefSnapshotIdMatch { .. })
+                })
+                .then(|| TableRequirement::RefSnapshotIdMatch {
+                    r#ref: MAIN_BRANCH.to_string(),
+                    snapshot_id: appends[0].0.parent_snapshot_id(),
+                }),
+        )
+        .collect();
+    let updates = snapshots
+        .into_iter()
+        .zip(&appends)
+        .flat_map(|(snapshot, (_, reference))| {
+            let reference = SnapshotReference {
+                snapshot_id: snapshot.snapshot_id(),
+                retention: reference.retention.clone(),
+            };
+            [
+                TableUpdate::AddSnapshot { snapshot },
+                TableUpdate::SetSnapshotRef {
+                    ref_name: MAIN_BRANCH.to_string(),
+                    reference,
+                },
+            ]
+        })
+        .collect();
+
+    tracing::debug!(
+        "Committing group of {} appends to table {}",
+        group.len(),
+        leader.table_id
+    );
+    commit_table_internal(
+        prefix,
+        CommitTableRequest {
+            identifier: leader.request.identifier.clone(),
+            requirements,
+            updates,
+        },
+        state,
+        leader.request_metadata.clone(),
+    )
+    .await
+    .map(Some)
+}
+
+/// Rebase every snapshot onto the previous one, as if the appends had been
+/// committed one after another. The first snapshot is kept as it is.
+///
+/// Rebased snapshots get a new manifest list with the manifests of the previous
+/// snapshot and their own manifests, as well as a new parent and sequence number.
+async fn rebase_snapshots(
+    metadata: &TableMetadata,
+    snapshots: Vec<Snapshot>,
+    file_io: &FileIO,
+) -> Result<Vec<Snapshot>> {
+    let mut snapshots = snapshots.into_iter();
+    let Some(first) = snapshots.next() else {
+        return Ok(vec![]);
+    };
+    let base_summary = first
+        .parent_snapshot_id()
+        .and_then(|id| metadata.snapshot_by_id(id))
+        .map(|s| s.summary().additional_properties.clone())
+        .unwrap_or_default();
+    let mut previous_manifests = load_manifests(metadata, &first, file_io).await?;
+    let mut previous = first;
+    let mut rebased = vec![];
+
+    for snapshot in snapshots {
+        let sequence_number = previous.sequence_number() + 1;
+        let mut manifests = previous_manifests.clone();
+        manifests.extend(
+            load_manifests(metadata, &snapshot, file_io)
+                .await?
+                .into_iter()
+                .filter(|m| m.added_snapshot_id == snapshot.snapshot_id())
+                .map(|mut m| {
+                    if m.min_sequence_number == snapshot.sequence_number() {
+                        m.min_sequence_number = sequence_number;
+                    }
+                    m.sequence_number = sequence_number;
+                    m
+                }),
+        );
+
+        let manifest_list_path = format!(
+            "{}/metadata/snap-{}-{}.avro",
+            metadata.location().trim_end_matches('/'),
+            snapshot.snapshot_id(),
+            Uuid::now_v7()
+        );
+        let output = file_io
+            .new_output(&manifest_list_path)
+            .map_err(|e| io_error("Failed to create manifest list", e))?;
+        let mut writer = ManifestListWriter::v2(
+            output,
+            snapshot.snapshot_id(),
+            Some(previous.snapshot_id()),
+            sequence_number,
+        );
+        writer
+            .add_manifests(manifests.clone().into_iter())
+            .map_err(|e| io_error("Failed to write manifest list", e))?;
+        writer
+            .close()
+            .await
+            .map_err(|e| io_error("Failed to write manifest list", e))?;
+
+        let rebased_snapshot = Snapshot::builder()
+            .with_snapshot_id(snapshot.snapshot_id())
+            .with_parent_snapshot_id(Some(previous.snapshot_id()))
+            .with_sequence_number(sequence_number)
+            .with_timestamp_ms(snapshot.timestamp_ms().max(previous.timestamp_ms()))
+            .with_manifest_list(manifest_list_path)
+            .with_summary(Summary {
+                operation: snapshot.summary().operation.clone(),
+                additional_properties: rebase_totals(
+                    snapshot.summary().additional_properties.clone(),
+                    &base_summary,
+                    &previous.summary().additional_properties,
+                ),
+            })
+            .with_schema_id(
+                snapshot
+                    .schema_id()
+                    .unwrap_or_else(|| metadata.current_schema_id()),
+            )
+            .build();
+        previous_manifests = manifests;
+        rebased.push(std::mem::replace(&mut previous, rebased_snapshot));
+    }
+    rebased.push(previous);
+
+    Ok(rebased)
+}
+
+async fn load_manifests(
+    metadata: &TableMetadata,
+    snapshot: &Snapshot,
+    file_io: &FileIO,
+) -> Result<Vec<ManifestFile>> {
+    Ok(snapshot
+        .load_manifest_list(file_io, metadata)
+        .await
+        .map_err(|e| io_error("Failed to read manifest list", e))?
+        .entries()
+        .to_vec())
+}
+
+/// Move the `total-*` properties of a summary computed on top of `base` onto
+/// `previous`. Totals that cannot be parsed are removed.
+fn rebase_totals(
+    mut properties: HashMap<String, String>,
+    base: &HashMap<String, String>,
+    previous: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let total = |properties: &HashMap<String, String>, key: &str| {
+        properties.get(key).map(|v| v.parse::<i64>().ok())
+    };
+    let keys = properties
+        .keys()
+        .filter(|key| key.starts_with(TOTAL_PROPERTY_PREFIX))
+        .cloned()
+        .collect::<Vec<_>>();
+    for key in keys {
+        let rebased = match (
+            total(&properties, &key),
+            total(base, &key).unwrap_or(Some(0)),
+            total(previous, &key),
+        ) {
+            (Some(Some(value)), Some(base), Some(Some(previous))) => Some(value - base + previous),
+            _ => None,
+        };
+        match rebased {
+            Some(value) => properties.insert(key, value.to_string()),
+            None => properties.remove(&key),
+        };
+    }
+    properties
+}
+
+fn io_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(
+        format!("{message} during group commit: {e}"),
+        "GroupCommitError",
+        Some(Box::new(e)),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rebase_totals() {
+        let properties = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        let rebased = rebase_totals(
+            properties(&[
+                ("added-records", "5"),
+                ("total-records", "15"),
+                ("total-data-files", "3"),
+                ("total-delete-files", "invalid"),
+            ]),
+            &properties(&[("total-records", "10"), ("total-data-files", "2")]),
+            &properties(&[
+                ("total-records", "17"),
+                ("total-data-files", "4"),
+                ("total-delete-files", "0"),
+            ]),
+        );
+        assert_eq!(
+            rebased,
+            properties(&[
+                ("added-records", "5"),
+                ("total-records", "22"),
+                ("total-data-files", "5"),
+            ])
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use http::StatusCode;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    api::{ErrorModel, Result},
+    request_metadata::RequestMetadata,
+    service::{Catalog, IdempotencyRecord, Transaction},
+    WarehouseIdent, CONFIG,
+};
+
+/// Scope of a request carrying an `Idempotency-Key` header.
+///
+/// Keys are scoped to the warehouse and the authenticated principal,
+/// so that one principal can never replay the result of another.
+#[derive(Debug, Clone)]
+pub(crate) struct IdempotencyScope {
+    warehouse_id: WarehouseIdent,
+    principal: String,
+    key: String,
+    operation: &'static str,
+}
+
+impl IdempotencyScope {
+    /// Returns `None` if the request has no `Idempotency-Key` header.
+    pub(crate) fn from_request(
+        request_metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        operation: &'static str,
+    ) -> Option<Self> {
+        let key = request_metadata.idempotency_key()?;
+        let principal = request_metadata
+            .user_id()
+            .map_or_else(|| "anonymous".to_string(), ToString::to_string);
+        Some(Self {
+            warehouse_id,
+            principal,
+            key: key.to_string(),
+            operation,
+        })
+    }
+
+    /// Load the stored response of a previous request with the same key.
+    ///
+    /// # Errors
+    /// - 422 if the key was previously used for a different operation
+    pub(crate) async fn load<'a, C: Catalog, R: DeserializeOwned>(
+        &self,
+        transaction: <C::Transaction as Transaction<C::State>>::Transaction<'a>,
+    ) -> Result<Option<R>> {
+        let Some(record) =
+            C::load_idempotency_record(self.warehouse_id, &self.principal, &self.key, transaction)
+                .await?
+        else {
+            return Ok(None);
+        };
+
+        if record.operation != self.operation {
+            return Err(ErrorModel::new(
+                format!(
+                    "Idempotency-Key was already used for operation '{}'",
+                    record.operation
+                ),
+                "IdempotencyKeyReused",
+                StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+                None,
+            )
+            .into());
+        }
+
+        tracing::debug!(
+            "Replaying stored response of '{}' for Idempotency-Key '{}'",
+            self.operation,
+            self.key
+        );
+        let response = serde_json::from_value(record.response).map_err(|e| {
+            ErrorModel::internal(
+                "Failed to deserialize stored idempotent response",
+                "IdempotencyRecordCorrupted",
+                Some(Box::new(e)),
+            )
+        })?;
+        Ok(Some(response))
+    }
+
+    /// Store `response` for the key. Must run in the transaction that creates the resource.
+    pub(crate) async fn store<'a, C: Catalog, R: Serialize>(
+        &self,
+        response: &R,
+        transaction: <C::Transaction as Transaction<C::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        let response = serde_json::to_value(response).map_err(|e| {
+            ErrorModel::internal(
+                "Failed to serialize idempotent response",
+                "IdempotencyRecordSerializationError",
+                Some(Box::new(e)),
+            )
+        })?;
+        C::store_idempotency_record(
+            self.warehouse_id,
+            &self.principal,
+            &self.key,
+            IdempotencyRecord {
+                operation: self.operation.to_string(),
+                response,
+            },
+            chrono::Utc::now() + CONFIG.idempotency_key_ttl_seconds,
+            transaction,
+        )
+        .await
+    }
+}
+
+/// Periodically deletes expired idempotency keys, which are otherwise only
+/// overwritten if the same key is used again. Never returns; spawn it as a background task.
+pub async fn idempotency_key_prune_task<C: Catalog>(
+    catalog_state: C::State,
+    interval: std::time::Duration,
+) {
+    loop {
+        tokio::time::sleep(interval).await;
+
+        match prune_expired_keys::<C>(catalog_state.clone()).await {
+            Ok(pruned) => tracing::debug!(pruned, "Pruned expired idempotency keys"),
+            Err(e) => tracing::error!("Failed to prune idempotency keys: {:?}", e.error),
+        }
+    }
+}
+
+async fn prune_expired_keys<C: Catalog>(catalog_state: C::State) -> Result<u64> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let pruned = C::prune_idempotency_records(t.transaction()).await?;
+    t.commit().await?;
+    Ok(pruned)
+}
@@ -0,0 +1,143 @@
+use iceberg::{
+    spec::{Operation, TableMetadata},
+    TableIdent, TableUpdate,
+};
+
+use crate::api::{ErrorModel, Result};
+
+/// Table property marking a table as write-once. Can only be set when the table
+/// is created and only be removed by a server admin.
+pub(crate) const PROPERTY_IMMUTABLE: &str = "lakekeeper.immutable";
+
+/// Whether the table only accepts appends.
+pub(crate) fn is_immutable(metadata: &TableMetadata) -> bool {
+    metadata
+        .properties()
+        .get(PROPERTY_IMMUTABLE)
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// Require that a commit complies with the immutability of the table:
+/// Read-only tables, registered from an import prefix, reject all commits. Snapshots added to an immutable table
+/// must be appends, overwrites, deletes and replaces are rejected. The history of
+/// an immutable table cannot be changed either: Snapshots and references cannot
+/// be removed, and references can only move to descendants of their snapshot.
+///
+/// The immutability flag can only be changed by the catalog itself, so commits
+/// with `flag_change_allowed` are made on behalf of the catalog, never of a user.
+/// All other restrictions apply to every commit.
+///
+/// # Errors
+/// - 403 if the table is read-only, the commit changes a flag or removes data from
+///   an immutable table
+pub(crate) fn check_table_commit(
+    table: &TableIdent,
+    read_only: bool,
+    previous_metadata: &TableMetadata,
+    new_metadata: &TableMetadata,
+    updates: &[TableUpdate],
+    flag_change_allowed: bool,
+) -> Result<()> {
+    if read_only {
+        return Err(ErrorModel::forbidden(
+            format!("Table '{table}' was registered from an import location and is read-only"),
+            "ReadOnlyTable",
+            None,
+        )
+        .into());
+    }
+
+    let immutable = is_immutable(previous_metadata);
+    if !flag_change_allowed && immutable != is_immutable(new_metadata) {
+        return Err(ErrorModel::forbidden(
+            format!(
+                "Property '{PROPERTY_IMMUTABLE}' of table '{table}' can only be set when the table is created"
+            ),
+            "ImmutableTableViolation",
+            None,
+        )
+        .into());
+    }
+    if !immutable {
+        return Ok(());
+    }
+
+    for update in updates {
+        match update {
+            TableUpdate::AddSnapshot { snapshot } => {
+                let operation = &snapshot.summary().operation;
+                if *operation != Operation::Append {
+                    return Err(immutable_table_violation(format!(
+                        "Table '{table}' is immutable and only accepts appends, got '{}' snapshot",
+                        operation.as_str()
+                    )));
+                }
+            }
+            TableUpdate::RemoveSnapshots { .. } => {
+                return Err(immutable_table_violation(format!(
+                    "Snapshots of immutable table '{table}' cannot be removed"
+                )));
+            }
+            TableUpdate::RemoveSnapshotRef { ref_name } => {
+                return Err(immutable_table_violation(format!(
+                    "Reference '{ref_name}' of immutable table '{table}' cannot be removed"
+                )));
+            }
+            TableUpdate::SetSnapshotRef {
+                ref_name,
+                reference,
+            } => {
+                let Some(previous) = previous_metadata.refs().get(ref_name) else {
+                    continue;
+                };
+                if !is_ancestor_of(new_metadata, previous.snapshot_id, reference.snapshot_id) {
+                    return Err(immutable_table_violation(format!(
+                        "Reference '{ref_name}' of immutable table '{table}' can only move to descendants of snapshot {}, got snapshot {}",
+                        previous.snapshot_id, reference.snapshot_id
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Require that the data of a table may be deleted when it is dropped.
+/// Immutable tables can only be dropped without purge, so that their data is retained.
+///
+/// # Errors
+/// - 403 if the table is immutable
+pub(crate) fn check_table_purge(
+    table: impl std::fmt::Display,
+    metadata: &TableMetadata,
+) -> Result<()> {
+    if is_immutable(metadata) {
+        return Err(immutable_table_violation(format!(
+            "Table '{table}' is immutable, its data cannot be purged. Drop it without purge or remove its immutability first."
+        )));
+    }
+    Ok(())
+}
+
+/// Whether `ancestor_id` is `snapshot_id` or one of its ancestors in `metadata`.
+fn is_ancestor_of(metadata: &TableMetadata, ancestor_id: i64, snapshot_id: i64) -> bool {
+    let mut current = Some(snapshot_id);
+    // Bounded by the number of snapshots, in case parents form a cycle
+    for _ in 0..=metadata.snapshots().len() {
+        match current {
+            Some(id) if id == ancestor_id => return true,
+            Some(id) => {
+                current = metadata
+                    .snapshot_by_id(id)
+                    .and_then(|snapshot| snapshot.parent_snapshot_id());
+            }
+            None => return false,
+        }
+    }
+    false
+}
+
+fn immutable_table_violation(message: String) -> crate::api::IcebergErrorResponse {
+    ErrorModel::forbidden(message, "ImmutableTableViolation", None).into()
+}
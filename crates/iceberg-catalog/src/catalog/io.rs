@@ -1,5 +1,11 @@
+use std::{future::Future, sync::LazyLock, time::Duration};
+
+use axum_prometheus::metrics;
 use futures::{stream::BoxStream, StreamExt};
-use iceberg::{io::FileIO, spec::TableMetadata};
+use iceberg::{
+    io::{FileIO, FileRead as _, InputFile},
+    spec::TableMetadata,
+};
 use iceberg_ext::{catalog::rest::IcebergErrorResponse, configs::Location};
 use serde::Serialize;
 
@@ -7,9 +13,25 @@ use super::compression_codec::CompressionCodec;
 use crate::{
     api::{ErrorModel, Result},
     retry::retry_fn,
-    service::storage::az::reduce_scheme_string as reduce_azure_scheme,
+    service::{
+        backend_timeout::{with_timeout, Backend, BackendTimeoutError},
+        storage::{
+            az::reduce_scheme_string as reduce_azure_scheme, ConditionalWriteError,
+            ConditionalWriter, StorageCredential, StorageProfile,
+        },
+    },
+    CONFIG,
 };
 
+const STORAGE_READ_RETRIES_METRIC: &str = "lakekeeper_storage_read_retries_total";
+
+/// Files are read in ranges of this size.
+const READ_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Storage profiles that were reported to not support conditional writes.
+static UNSUPPORTED_CONDITIONAL_WRITES: LazyLock<moka::sync::Cache<String, ()>> =
+    LazyLock::new(|| moka::sync::Cache::new(1000));
+
 fn normalize_location(location: &Location) -> String {
     if location.as_str().starts_with("abfs") {
         reduce_azure_scheme(location.as_str(), false)
@@ -31,6 +53,75 @@ pub(crate) async fn write_metadata_file(
     metadata: impl Serialize,
     compression_codec: CompressionCodec,
     file_io: &FileIO,
+) -> Result<(), IoError> {
+    let metadata_bytes = serialize_metadata(metadata, compression_codec).await?;
+    write_metadata_bytes(metadata_location, metadata_bytes, file_io).await
+}
+
+/// Writer for new metadata files of `storage_profile`, if conditional metadata writes are enabled.
+pub(crate) fn metadata_conditional_writer(
+    storage_profile: &StorageProfile,
+    storage_secret: Option<&StorageCredential>,
+) -> Option<Box<dyn ConditionalWriter>> {
+    CONFIG
+        .conditional_metadata_writes
+        .then(|| storage_profile.conditional_writer(storage_secret))
+}
+
+/// Write a metadata file that must not exist yet. If `conditional_writer` is set,
+/// the write fails with [`IoError::FileExists`] if a file exists at `metadata_location`.
+/// Storages without conditional writes fall back to [`write_metadata_file`].
+pub(crate) async fn write_new_metadata_file(
+    metadata_location: &Location,
+    metadata: impl Serialize,
+    compression_codec: CompressionCodec,
+    file_io: &FileIO,
+    conditional_writer: Option<&dyn ConditionalWriter>,
+) -> Result<(), IoError> {
+    let metadata_bytes = serialize_metadata(metadata, compression_codec).await?;
+    let Some(conditional_writer) = conditional_writer else {
+        return write_metadata_bytes(metadata_location, metadata_bytes, file_io).await;
+    };
+
+    tracing::debug!("Conditionally writing metadata file to {metadata_location}");
+    // Not retried: a retry of a write that succeeded would fail the precondition.
+    let result = with_timeout(
+        Backend::StorageWrite,
+        CONFIG.backend_timeouts.storage_write,
+        conditional_writer.write_if_absent(metadata_location, metadata_bytes.clone()),
+    )
+    .await?;
+    match result {
+        Ok(()) => Ok(()),
+        Err(ConditionalWriteError::PreconditionFailed(location)) => {
+            Err(IoError::FileExists(location))
+        }
+        Err(ConditionalWriteError::Unsupported(reason)) => {
+            // Reasons name the storage profile, so each profile is reported once
+            if !UNSUPPORTED_CONDITIONAL_WRITES.contains_key(&reason) {
+                tracing::warn!(
+                    "Writing metadata files without precondition, as conditional writes are not supported: {reason}"
+                );
+                UNSUPPORTED_CONDITIONAL_WRITES.insert(reason, ());
+            }
+            write_metadata_bytes(metadata_location, metadata_bytes, file_io).await
+        }
+        Err(ConditionalWriteError::Request(e)) => Err(IoError::FileWrite(e)),
+    }
+}
+
+async fn serialize_metadata(
+    metadata: impl Serialize,
+    compression_codec: CompressionCodec,
+) -> Result<Vec<u8>, IoError> {
+    let buf = serde_json::to_vec(&metadata).map_err(IoError::Serialization)?;
+    compression_codec.compress(buf).await
+}
+
+async fn write_metadata_bytes(
+    metadata_location: &Location,
+    metadata_bytes: Vec<u8>,
+    file_io: &FileIO,
 ) -> Result<(), IoError> {
     let metadata_location = normalize_location(metadata_location);
     tracing::debug!("Writing metadata file to {}", metadata_location);
@@ -39,57 +130,94 @@ pub(crate) async fn write_metadata_file(
         .new_output(metadata_location)
         .map_err(IoError::FileCreation)?;
 
-    let buf = serde_json::to_vec(&metadata).map_err(IoError::Serialization)?;
-
-    let metadata_bytes = compression_codec.compress(buf).await?;
-
-    retry_fn(|| async {
-        metadata_file
-            .write(metadata_bytes.clone().into())
-            .await
-            .map_err(IoError::FileWriterCreation)
-    })
-    .await
+    with_timeout(
+        Backend::StorageWrite,
+        CONFIG.backend_timeouts.storage_write,
+        retry_fn(|| async {
+            metadata_file
+                .write(metadata_bytes.clone().into())
+                .await
+                .map_err(IoError::FileWriterCreation)
+        }),
+    )
+    .await?
 }
 
 pub(crate) async fn delete_file(file_io: &FileIO, location: &Location) -> Result<(), IoError> {
     let location = normalize_location(location);
 
-    retry_fn(|| async {
-        file_io
-            .clone()
-            .delete(location.clone())
-            .await
-            .map_err(IoError::FileDelete)
-    })
-    .await
+    with_timeout(
+        Backend::StorageWrite,
+        CONFIG.backend_timeouts.storage_write,
+        retry_fn(|| async {
+            file_io
+                .clone()
+                .delete(location.clone())
+                .await
+                .map_err(IoError::FileDelete)
+        }),
+    )
+    .await?
 }
 
 pub(crate) async fn read_file(file_io: &FileIO, file: &Location) -> Result<Vec<u8>, IoError> {
+    read_file_with_limit(file_io, file, CONFIG.max_metadata_file_size_bytes).await
+}
+
+/// Read `file`, failing with [`IoError::MetadataTooLarge`] if the file or its
+/// decompressed content is larger than `max_size` bytes.
+async fn read_file_with_limit(
+    file_io: &FileIO,
+    file: &Location,
+    max_size: u64,
+) -> Result<Vec<u8>, IoError> {
     let file = normalize_location(file);
 
-    let content: Vec<_> = retry_fn(|| async {
+    let content = retry_read("read", || async {
         // InputFile isn't clone hence it's here
-        file_io
+        let input = file_io
             .clone()
             .new_input(file.clone())
-            .map_err(IoError::FileInput)?
-            .read()
-            .await
-            .map_err(|e| IoError::FileRead(Box::new(e)))
-            .map(Into::into)
+            .map_err(IoError::FileInput)?;
+        read_bounded(&input, max_size).await
     })
     .await?;
 
     if file.as_str().ends_with(".gz.metadata.json") {
         let codec = CompressionCodec::Gzip;
-        let content = codec.decompress(content).await?;
+        let content = codec.decompress(content, max_size).await?;
         Ok(content)
     } else {
         Ok(content)
     }
 }
 
+/// Read `input` in chunks. The size is checked before the first chunk is
+/// downloaded, so oversized files are never buffered.
+async fn read_bounded(input: &InputFile, max_size: u64) -> Result<Vec<u8>, IoError> {
+    let size = input.metadata().await.map_err(IoError::FileInput)?.size;
+    if size > max_size {
+        return Err(IoError::MetadataTooLarge(max_size));
+    }
+
+    let reader = input.reader().await.map_err(IoError::FileInput)?;
+    let mut content = Vec::with_capacity(usize::try_from(size).unwrap_or_default());
+    let mut offset = 0;
+    while offset < size {
+        let end = (offset + READ_CHUNK_SIZE).min(size);
+        let chunk = reader
+            .read(offset..end)
+            .await
+            .map_err(|e| IoError::FileRead(Box::new(e)))?;
+        if chunk.is_empty() {
+            break;
+        }
+        offset += chunk.len() as u64;
+        content.extend_from_slice(&chunk);
+    }
+    Ok(content)
+}
+
 pub(crate) async fn read_metadata_file(
     file_io: &FileIO,
     file: &Location,
@@ -108,14 +236,18 @@ pub(crate) async fn read_metadata_file(
 pub(crate) async fn remove_all(file_io: &FileIO, location: &Location) -> Result<(), IoError> {
     let location = normalize_location(location);
 
-    retry_fn(|| async {
-        file_io
-            .clone()
-            .remove_all(location.clone())
-            .await
-            .map_err(IoError::FileRemoveAll)
-    })
-    .await
+    with_timeout(
+        Backend::StoragePurge,
+        CONFIG.backend_timeouts.storage_purge,
+        retry_fn(|| async {
+            file_io
+                .clone()
+                .remove_all(location.clone())
+                .await
+                .map_err(IoError::FileRemoveAll)
+        }),
+    )
+    .await?
 }
 
 pub(crate) const DEFAULT_LIST_LOCATION_PAGE_SIZE: usize = 1000;
@@ -130,14 +262,11 @@ pub(crate) async fn list_location<'a>(
     tracing::debug!("Listing location: {}", location);
     let size = page_size.unwrap_or(DEFAULT_LIST_LOCATION_PAGE_SIZE);
 
-    let entries = retry_fn(|| async {
+    let entries = retry_read("list", || async {
         file_io
             .list_paginated(location.clone().as_str(), true, size)
             .await
-            .map_err(|e| {
-                tracing::warn!(?e, "Failed to list files in location. Retry three times...");
-                IoError::List(e)
-            })
+            .map_err(IoError::List)
     })
     .await?
     .map(|res| match res {
@@ -150,6 +279,79 @@ pub(crate) async fn list_location<'a>(
     Ok(entries.boxed())
 }
 
+/// Run the storage read `f` and retry it with exponential backoff and jitter while it
+/// fails with a transient error, see [`is_transient_read_error`].
+///
+/// All attempts together are bounded by the `storage_read` backend timeout.
+async fn retry_read<T, F, Fut>(operation: &'static str, f: F) -> Result<T, IoError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, IoError>>,
+{
+    let retry = &CONFIG.storage_read_retry;
+    with_timeout(
+        Backend::StorageRead,
+        CONFIG.backend_timeouts.storage_read,
+        async {
+            let mut backoff = retry.initial_backoff;
+            let mut attempt = 0;
+            loop {
+                match f().await {
+                    Err(e) if attempt < retry.max_retries && is_transient_read_error(&e) => {
+                        attempt += 1;
+                        let delay = with_jitter(backoff);
+                        tracing::warn!(
+                            "Transient storage error during {operation}, retrying in {}ms ({attempt}/{}): {e:?}",
+                            delay.as_millis(),
+                            retry.max_retries,
+                        );
+                        metrics::counter!(STORAGE_READ_RETRIES_METRIC, "operation" => operation)
+                            .increment(1);
+                        tokio::time::sleep(delay).await;
+                        backoff = (backoff * 2).min(retry.max_backoff);
+                    }
+                    result => return result,
+                }
+            }
+        },
+    )
+    .await?
+}
+
+/// Spread retries of concurrent requests by waiting between half and the full backoff.
+fn with_jitter(backoff: Duration) -> Duration {
+    let half = backoff / 2;
+    half + half.mul_f64(rand::random::<f64>())
+}
+
+/// Whether a failed storage read is worth retrying.
+///
+/// Object stores report throttling and server errors (HTTP 429, 500, 502, 503 and 504)
+/// as temporary errors. Missing objects and denied permissions are never retried.
+/// The storage errors are only available as sources of the `iceberg::Error`, so they
+/// are recognized by their message, formatted as `<kind> (<status>) at <operation>`.
+fn is_transient_read_error(error: &IoError) -> bool {
+    if !matches!(
+        error,
+        IoError::FileRead(_) | IoError::FileInput(_) | IoError::List(_)
+    ) {
+        return false;
+    }
+
+    let mut source = std::error::Error::source(error);
+    while let Some(e) = source {
+        let message = e.to_string();
+        if message.starts_with("NotFound") || message.starts_with("PermissionDenied") {
+            return false;
+        }
+        if message.starts_with("RateLimited") || message.contains("(temporary)") {
+            return true;
+        }
+        source = e.source();
+    }
+    false
+}
+
 #[derive(thiserror::Error, Debug, strum::IntoStaticStr)]
 pub enum IoError {
     #[error("Failed to create file. Please check the storage credentials.")]
@@ -180,6 +382,12 @@ pub enum IoError {
     FileRemoveAll(#[source] iceberg::Error),
     #[error("Failed to list files in location. Please check the storage credentials.")]
     List(#[source] iceberg::Error),
+    #[error("Metadata file exceeds the maximum size of {0} bytes.")]
+    MetadataTooLarge(u64),
+    #[error("A file already exists at {0}. The location was written concurrently.")]
+    FileExists(String),
+    #[error(transparent)]
+    Timeout(#[from] BackendTimeoutError),
 }
 
 impl IoError {
@@ -211,9 +419,186 @@ impl From<IoError> for IcebergErrorResponse {
             IoError::TableMetadataDeserialization(e) => {
                 ErrorModel::bad_request(format!("{message} {e}"), typ, Some(boxed)).into()
             }
+            IoError::MetadataTooLarge(_) => ErrorModel::bad_request(message, typ, None).into(),
+            IoError::FileExists(_) => ErrorModel::conflict(message, typ, None).into(),
+            IoError::Timeout(e) => (*e).into(),
         }
     }
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use std::{
+        str::FromStr as _,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    use super::*;
+
+    /// Mimics the errors of the object store client.
+    #[derive(thiserror::Error, Debug)]
+    #[error("{0}")]
+    struct StorageError(&'static str);
+
+    fn read_error(message: &'static str) -> IoError {
+        IoError::FileRead(Box::new(StorageError(message)))
+    }
+
+    #[tokio::test]
+    async fn test_read_succeeds_after_transient_error() {
+        let attempts = AtomicU32::new(0);
+        let content = retry_read("read", || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(read_error(
+                    "Unexpected (temporary) at read => service unavailable, status: 503",
+                ))
+            } else {
+                Ok(b"{}".to_vec())
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(content, b"{}");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_gives_up_after_max_retries() {
+        let attempts = AtomicU32::new(0);
+        let err = retry_read("read", || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(read_error("RateLimited (temporary) at read => slow down"))
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err.to_type(), "FileRead");
+        assert_eq!(
+            attempts.load(Ordering::SeqCst),
+            CONFIG.storage_read_retry.max_retries + 1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_does_not_retry_permanent_errors() {
+        for message in [
+            "NotFound (permanent) at read => object not found",
+            "PermissionDenied (permanent) at read => access denied",
+        ] {
+            let attempts = AtomicU32::new(0);
+            retry_read("read", || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(read_error(message))
+            })
+            .await
+            .unwrap_err();
+            assert_eq!(attempts.load(Ordering::SeqCst), 1, "{message}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_metadata_file_is_rejected() {
+        let file_io = iceberg::io::FileIOBuilder::new("file").build().unwrap();
+        let dir = std::env::temp_dir().join(format!("lakekeeper-{}", uuid::Uuid::now_v7()));
+        let location = |name: &str| {
+            Location::from_str(&format!("file://{}/{name}", dir.to_string_lossy())).unwrap()
+        };
+        let blob = vec![b' '; 4096];
+
+        let plain = location("00000-plain.metadata.json");
+        file_io
+            .new_output(plain.to_string())
+            .unwrap()
+            .write(blob.clone().into())
+            .await
+            .unwrap();
+        let err = read_file_with_limit(&file_io, &plain, 1024)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, IoError::MetadataTooLarge(1024)), "{err:?}");
+        let err = IcebergErrorResponse::from(err);
+        assert_eq!(err.error.r#type, "MetadataTooLarge");
+        assert_eq!(
+            read_file_with_limit(&file_io, &plain, 4096).await.unwrap(),
+            blob
+        );
+
+        // Compressed files are checked while decompressing
+        let compressed = location("00000-compressed.gz.metadata.json");
+        let payload = CompressionCodec::Gzip.compress(blob).await.unwrap();
+        assert!(payload.len() < 1024);
+        file_io
+            .new_output(compressed.to_string())
+            .unwrap()
+            .write(payload.into())
+            .await
+            .unwrap();
+        let err = read_file_with_limit(&file_io, &compressed, 1024)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, IoError::MetadataTooLarge(1024)), "{err:?}");
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    fn temp_location(name: &str) -> Location {
+        let dir = std::env::temp_dir().join(format!("lakekeeper-{}", uuid::Uuid::now_v7()));
+        Location::from_str(&format!("file://{}/{name}", dir.to_string_lossy())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_conditional_write_precondition_failure_is_conflict() {
+        let file_io = iceberg::io::FileIOBuilder::new("file").build().unwrap();
+        let location = temp_location("00001-conditional.metadata.json");
+
+        let mut writer = crate::service::storage::MockConditionalWriter::new();
+        writer
+            .expect_write_if_absent()
+            .times(1)
+            .returning(|location, _| {
+                Err(ConditionalWriteError::PreconditionFailed(
+                    location.to_string(),
+                ))
+            });
+
+        let err = write_new_metadata_file(
+            &location,
+            serde_json::json!({"format-version": 2}),
+            CompressionCodec::None,
+            &file_io,
+            Some(&writer),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, IoError::FileExists(_)), "{err:?}");
+        let err = IcebergErrorResponse::from(err);
+        assert_eq!(err.error.code, 409);
+        assert_eq!(err.error.r#type, "FileExists");
+        assert!(!file_io.exists(location.to_string()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_conditional_write_falls_back_to_plain_write() {
+        let file_io = iceberg::io::FileIOBuilder::new("file").build().unwrap();
+        let location = temp_location("00001-fallback.metadata.json");
+
+        let mut writer = crate::service::storage::MockConditionalWriter::new();
+        writer
+            .expect_write_if_absent()
+            .times(1)
+            .returning(|_, _| Err(ConditionalWriteError::Unsupported("mock".to_string())));
+
+        write_new_metadata_file(
+            &location,
+            serde_json::json!({"format-version": 2}),
+            CompressionCodec::None,
+            &file_io,
+            Some(&writer),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            read_file(&file_io, &location).await.unwrap(),
+            br#"{"format-version":2}"#
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::{Arc, LazyLock};
+
+use axum_prometheus::metrics;
+use iceberg::{
+    io::FileIO,
+    spec::{Manifest, ManifestFile},
+};
+
+use crate::{WarehouseIdent, CONFIG};
+
+const MANIFEST_CACHE_HITS_METRIC: &str = "lakekeeper_manifest_cache_hits_total";
+/// Approximate size of a parsed manifest entry, used to bound the cache size.
+const APPROXIMATE_ENTRY_SIZE_BYTES: u32 = 1024;
+
+/// Parsed manifests shared by all requests of this server.
+static MANIFEST_CACHE: LazyLock<ManifestCache> =
+    LazyLock::new(|| ManifestCache::new(CONFIG.manifest_cache_size_bytes));
+
+/// Bounded cache of parsed manifests, keyed by the warehouse they were read
+/// through and their path.
+///
+/// Manifests are never modified once written, so entries never become stale and
+/// are only evicted to stay within the configured size. The size of a manifest
+/// is estimated from its number of entries. Entries are only shared within a
+/// warehouse, as the same path may refer to different storage, or to storage the
+/// credentials of another warehouse cannot access.
+#[derive(Debug, Clone)]
+pub(crate) struct ManifestCache {
+    cache: Option<moka::sync::Cache<(WarehouseIdent, String), Arc<Manifest>>>,
+}
+
+impl ManifestCache {
+    /// Cache manifests up to a total of `max_size_bytes`. The cache is disabled if 0.
+    pub(crate) fn new(max_size_bytes: u64) -> Self {
+        let cache = (max_size_bytes > 0).then(|| {
+            moka::sync::Cache::builder()
+                .max_capacity(max_size_bytes)
+                .weigher(|_, manifest: &Arc<Manifest>| {
+                    u32::try_from(manifest.entries().len())
+                        .unwrap_or(u32::MAX)
+                        .saturating_mul(APPROXIMATE_ENTRY_SIZE_BYTES)
+                })
+                .build()
+        });
+        Self { cache }
+    }
+
+    /// Load the manifest of `manifest_file` of a table in `warehouse_id`, reading
+    /// it from storage only if it is not cached.
+    ///
+    /// # Errors
+    /// If the manifest cannot be read from storage or parsed.
+    pub(crate) async fn load_manifest(
+        &self,
+        warehouse_id: WarehouseIdent,
+        manifest_file: &ManifestFile,
+        file_io: &FileIO,
+    ) -> iceberg::Result<Arc<Manifest>> {
+        let Some(cache) = &self.cache else {
+            return manifest_file.load_manifest(file_io).await.map(Arc::new);
+        };
+
+        let key = (warehouse_id, manifest_file.manifest_path.clone());
+        if let Some(manifest) = cache.get(&key) {
+            metrics::counter!(MANIFEST_CACHE_HITS_METRIC).increment(1);
+            return Ok(manifest);
+        }
+        let manifest = Arc::new(manifest_file.load_manifest(file_io).await?);
+        cache.insert(key, manifest.clone());
+        Ok(manifest)
+    }
+}
+
+/// Load the manifest of `manifest_file` of a table in `warehouse_id` via the
+/// manifest cache of this server.
+///
+/// # Errors
+/// If the manifest cannot be read from storage or parsed.
+pub(crate) async fn load_manifest_cached(
+    warehouse_id: WarehouseIdent,
+    manifest_file: &ManifestFile,
+    file_io: &FileIO,
+) -> iceberg::Result<Arc<Manifest>> {
+    MANIFEST_CACHE
+        .load_manifest(warehouse_id, manifest_file, file_io)
+        .await
+}
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+
+use iceberg::{
+    io::FileIO,
+    spec::{
+        FormatVersion, ManifestContentType, ManifestFile, ManifestListWriter, ManifestStatus,
+        ManifestWriterBuilder, Snapshot, TableMetadata,
+    },
+    TableUpdate,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{ErrorModel, Result},
+    service::backend_timeout::{with_timeout, Backend},
+    CONFIG,
+};
+
+/// Snapshot summary property that disables the manifest merge for a single commit.
+pub(crate) const PROPERTY_SKIP_MANIFEST_MERGE: &str = "lakekeeper.skip-manifest-merge";
+/// Manifests of at least this size are kept as they are.
+const TARGET_MANIFEST_SIZE_BYTES: i64 = 8 * 1024 * 1024;
+
+#[derive(Debug, Default)]
+pub(crate) struct MergedUpdates {
+    pub(crate) updates: Vec<TableUpdate>,
+    /// Manifest lists and manifests written by the client for this commit
+    /// which are no longer referenced after the merge.
+    pub(crate) replaced_files: Vec<String>,
+}
+
+/// Merge the manifests of snapshots added by `updates` if a snapshot references
+/// more than `threshold` manifests.
+///
+/// `metadata` is the table metadata after `updates` have been applied.
+/// Merged snapshots keep their id, parent, sequence number and summary, only the
+/// manifest list is replaced. All live entries as well as entries deleted by the
+/// snapshot itself are preserved.
+pub(crate) async fn merge_manifests(
+    metadata: &TableMetadata,
+    updates: Vec<TableUpdate>,
+    threshold: usize,
+    file_io: &FileIO,
+) -> Result<MergedUpdates> {
+    let mut result = MergedUpdates::default();
+    for update in updates {
+        let update = match update {
+            TableUpdate::AddSnapshot { snapshot } => {
+                let merged = with_timeout(
+                    Backend::StorageWrite,
+                    CONFIG.backend_timeouts.storage_write,
+                    merge_snapshot_manifests(metadata, &snapshot, threshold, file_io),
+                )
+                .await??;
+                match merged {
+                    Some((merged_snapshot, replaced_files)) => {
+                        result.replaced_files.extend(replaced_files);
+                        TableUpdate::AddSnapshot {
+                            snapshot: merged_snapshot,
+                        }
+                    }
+                    None => TableUpdate::AddSnapshot { snapshot },
+                }
+            }
+            update => update,
+        };
+        result.updates.push(update);
+    }
+    Ok(result)
+}
+
+fn skip_requested(snapshot: &Snapshot) -> bool {
+    snapshot
+        .summary()
+        .additional_properties
+        .get(PROPERTY_SKIP_MANIFEST_MERGE)
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+async fn merge_snapshot_manifests(
+    metadata: &TableMetadata,
+    snapshot: &Snapshot,
+    threshold: usize,
+    file_io: &FileIO,
+) -> Result<Option<(Snapshot, Vec<String>)>> {
+    // Format version 1 manifests carry no content type and no sequence numbers.
+    if metadata.format_version() == FormatVersion::V1 || skip_requested(snapshot) {
+        return Ok(None);
+    }
+
+    let manifest_list = snapshot
+        .load_manifest_list(file_io, metadata)
+        .await
+        .map_err(|e| io_error("Failed to read manifest list", e))?;
+    let manifests = manifest_list.entries();
+    if manifests.len() <= threshold {
+        return Ok(None);
+    }
+
+    // Only manifests with the same content and partition spec can be merged.
+    let mut groups: BTreeMap<(bool, i32), Vec<&ManifestFile>> = BTreeMap::new();
+    let mut new_manifests = vec![];
+    for manifest in manifests {
+        if manifest.manifest_length >= TARGET_MANIFEST_SIZE_BYTES {
+            new_manifests.push(manifest.clone());
+        } else {
+            groups
+                .entry((
+                    manifest.content == ManifestContentType::Deletes,
+                    manifest.partition_spec_id,
+                ))
+                .or_default()
+                .push(manifest);
+        }
+    }
+
+    let mut replaced_files = vec![];
+    for ((is_deletes, spec_id), group) in groups {
+        for bin in bin_pack(group) {
+            if bin.len() < 2 {
+                new_manifests.extend(bin.into_iter().cloned());
+                continue;
+            }
+            let merged =
+                write_merged_manifest(metadata, snapshot, spec_id, is_deletes, &bin, file_io)
+                    .await?;
+            new_manifests.push(merged);
+            // Manifests of earlier snapshots are still referenced by those snapshots.
+            replaced_files.extend(
+                bin.iter()
+                    .filter(|m| m.added_snapshot_id == snapshot.snapshot_id())
+                    .map(|m| m.manifest_path.clone()),
+            );
+        }
+    }
+
+    if new_manifests.len() >= manifests.len() {
+        return Ok(None);
+    }
+
+    let manifest_list_path = format!(
+        "{}/metadata/snap-{}-{}.avro",
+        metadata.location().trim_end_matches('/'),
+        snapshot.snapshot_id(),
+        Uuid::now_v7()
+    );
+    let output = file_io
+        .new_output(&manifest_list_path)
+        .map_err(|e| io_error("Failed to create manifest list", e))?;
+    let mut writer = ManifestListWriter::v2(
+        output,
+        snapshot.snapshot_id(),
+        snapshot.parent_snapshot_id(),
+        snapshot.sequence_number(),
+    );
+    writer
+        .add_manifests(new_manifests.into_iter())
+        .map_err(|e| io_error("Failed to write manifest list", e))?;
+    writer
+        .close()
+        .await
+        .map_err(|e| io_error("Failed to write manifest list", e))?;
+    replaced_files.push(snapshot.manifest_list().to_string());
+
+    let merged_snapshot = Snapshot::builder()
+        .with_snapshot_id(snapshot.snapshot_id())
+        .with_parent_snapshot_id(snapshot.parent_snapshot_id())
+        .with_sequence_number(snapshot.sequence_number())
+        .with_timestamp_ms(snapshot.timestamp_ms())
+        .with_manifest_list(manifest_list_path)
+        .with_summary(snapshot.summary().clone())
+        .with_schema_id(
+            snapshot
+                .schema_id()
+                .unwrap_or_else(|| metadata.current_schema_id()),
+        )
+        .build();
+
+    tracing::debug!(
+        "Merged {} manifests of snapshot {} into {}",
+        manifests.len(),
+        snapshot.snapshot_id(),
+        merged_snapshot.manifest_list()
+    );
+
+    Ok(Some((merged_snapshot, replaced_files)))
+}
+
+/// Split manifests into bins of at most [`TARGET_MANIFEST_SIZE_BYTES`].
+fn bin_pack(manifests: Vec<&ManifestFile>) -> Vec<Vec<&ManifestFile>> {
+    let mut bins: Vec<Vec<&ManifestFile>> = vec![];
+    let mut current_size = 0;
+    for manifest in manifests {
+        match bins.last_mut() {
+            Some(bin) if current_size + manifest.manifest_length <= TARGET_MANIFEST_SIZE_BYTES => {
+                current_size += manifest.manifest_length;
+                bin.push(manifest);
+            }
+            _ => {
+                current_size = manifest.manifest_length;
+                bins.push(vec![manifest]);
+            }
+        }
+    }
+    bins
+}
+
+async fn write_merged_manifest(
+    metadata: &TableMetadata,
+    snapshot: &Snapshot,
+    spec_id: i32,
+    is_deletes: bool,
+    manifests: &[&ManifestFile],
+    file_io: &FileIO,
+) -> Result<ManifestFile> {
+    let schema = snapshot
+        .schema(metadata)
+        .map_err(|e| io_error("Schema of snapshot not found", e))?;
+    let partition_spec = metadata.partition_spec_by_id(spec_id).ok_or_else(|| {
+        ErrorModel::bad_request(
+            format!("Partition spec {spec_id} referenced by a manifest does not exist"),
+            "PartitionSpecNotFound",
+            None,
+        )
+    })?;
+
+    let path = format!(
+        "{}/metadata/{}-m0.avro",
+        metadata.location().trim_end_matches('/'),
+        Uuid::now_v7()
+    );
+    let output = file_io
+        .new_output(&path)
+        .map_err(|e| io_error("Failed to create manifest", e))?;
+    let builder = ManifestWriterBuilder::new(
+        output,
+        Some(snapshot.snapshot_id()),
+        vec![],
+        schema,
+        partition_spec.as_ref().clone(),
+    );
+    let mut writer = if is_deletes {
+        builder.build_v2_deletes()
+    } else {
+        builder.build_v2_data()
+    };
+
+    for manifest_file in manifests {
+        let manifest = manifest_file
+            .load_manifest(file_io)
+            .await
+            .map_err(|e| io_error("Failed to read manifest", e))?;
+        for entry in manifest.entries() {
+            let added_by_snapshot = entry.snapshot_id() == Some(snapshot.snapshot_id());
+            let entry = entry.as_ref().clone();
+            match entry.status() {
+                ManifestStatus::Added if added_by_snapshot => writer.add_entry(entry),
+                ManifestStatus::Deleted if added_by_snapshot => writer.add_delete_entry(entry),
+                // Deletes of earlier snapshots are not carried forward.
+                ManifestStatus::Deleted => Ok(()),
+                ManifestStatus::Added | ManifestStatus::Existing => {
+                    writer.add_existing_entry(entry)
+                }
+            }
+            .map_err(|e| io_error("Failed to write manifest", e))?;
+        }
+    }
+
+    writer
+        .write_manifest_file()
+        .await
+        .map_err(|e| io_error("Failed to write manifest", e).into())
+}
+
+fn io_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(
+        format!("{message} during manifest merge: {e}"),
+        "ManifestMergeError",
+        Some(Box::new(e)),
+    )
+}
@@ -0,0 +1,89 @@
+use iceberg::spec::TableMetadata;
+use serde_json::Value;
+
+use crate::api::{ErrorModel, Result};
+
+/// Trim `metadata` to the current schema, default partition spec,
+/// default sort order and current snapshot.
+///
+/// Historical schemas, specs, sort orders and snapshots are removed, as well as
+/// the snapshot log and metadata log. References and statistics are only kept
+/// if they belong to the current snapshot.
+///
+/// # Errors
+/// - 500 if the trimmed metadata is inconsistent
+pub(crate) fn minimal_table_metadata(metadata: TableMetadata) -> Result<TableMetadata> {
+    // Parts of `TableMetadata` cannot be removed in place. The JSON representation
+    // is trimmed instead, which also validates the result when it is parsed.
+    let mut value = serde_json::to_value(&metadata).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to serialize table metadata",
+            "TableMetadataSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+    let Value::Object(fields) = &mut value else {
+        return Err(ErrorModel::internal(
+            "Table metadata is not serialized as an object",
+            "TableMetadataSerializationError",
+            None,
+        )
+        .into());
+    };
+
+    let current_schema_id = fields.get("current-schema-id").cloned();
+    let default_spec_id = fields.get("default-spec-id").cloned();
+    let default_sort_order_id = fields.get("default-sort-order-id").cloned();
+    let current_snapshot_id = fields
+        .get("current-snapshot-id")
+        .cloned()
+        .unwrap_or(Value::Null);
+
+    retain_by_id(fields, "schemas", "schema-id", current_schema_id.as_ref());
+    retain_by_id(
+        fields,
+        "partition-specs",
+        "spec-id",
+        default_spec_id.as_ref(),
+    );
+    retain_by_id(
+        fields,
+        "sort-orders",
+        "order-id",
+        default_sort_order_id.as_ref(),
+    );
+    for list in [
+        "snapshots",
+        "snapshot-log",
+        "statistics",
+        "partition-statistics",
+    ] {
+        retain_by_id(fields, list, "snapshot-id", Some(&current_snapshot_id));
+    }
+    if let Some(Value::Object(refs)) = fields.get_mut("refs") {
+        refs.retain(|_, r| r.get("snapshot-id") == Some(&current_snapshot_id));
+    }
+    if let Some(Value::Array(metadata_log)) = fields.get_mut("metadata-log") {
+        metadata_log.clear();
+    }
+
+    serde_json::from_value(value).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to trim table metadata",
+            "TableMetadataSerializationError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+fn retain_by_id(
+    fields: &mut serde_json::Map<String, Value>,
+    list: &str,
+    id_field: &str,
+    id: Option<&Value>,
+) {
+    if let Some(Value::Array(entries)) = fields.get_mut(list) {
+        entries.retain(|entry| entry.get(id_field) == id);
+    }
+}
@@ -1,11 +1,27 @@
 pub(crate) mod commit_tables;
 pub(crate) mod compression_codec;
 mod config;
+pub(crate) mod credential_events;
+pub(crate) mod file_format_validation;
+pub(crate) mod file_path_validation;
+pub(crate) mod group_commit;
+pub mod idempotency;
+pub(crate) mod immutable_tables;
 pub(crate) mod io;
+pub(crate) mod manifest_cache;
+pub(crate) mod manifest_merge;
 mod metrics;
+pub(crate) mod minimal_metadata;
 pub(crate) mod namespace;
+pub mod namespace_cleanup;
 #[cfg(feature = "s3-signer")]
 mod s3_signer;
+pub(crate) mod single_flight;
+pub mod snapshot_expiration;
+pub(crate) mod table_deprecation;
+pub(crate) mod table_files;
+pub(crate) mod table_footprint;
+pub(crate) mod table_templates;
 pub(crate) mod tables;
 pub(crate) mod tabular;
 pub(crate) mod views;
@@ -23,7 +39,10 @@ use crate::{
         iceberg::v1::{PageToken, Prefix, MAX_PAGE_SIZE},
         ErrorModel, Result,
     },
-    service::{authz::Authorizer, secrets::SecretStore, storage::StorageCredential, Catalog},
+    service::{
+        authz::Authorizer, secret_encryption::get_storage_secret, secrets::SecretStore,
+        storage::StorageCredential, Catalog,
+    },
     WarehouseIdent,
 };
 
@@ -65,12 +84,15 @@ fn require_warehouse_id(prefix: Option<Prefix>) -> Result<WarehouseIdent> {
         .try_into()
 }
 
-pub(crate) async fn maybe_get_secret<S: SecretStore>(
+pub(crate) async fn maybe_get_secret<C: Catalog, S: SecretStore>(
     secret: Option<crate::SecretIdent>,
+    catalog_state: C::State,
     state: &S,
 ) -> Result<Option<StorageCredential>, IcebergErrorResponse> {
     if let Some(secret_id) = &secret {
-        Ok(Some(state.get_secret_by_id(secret_id).await?.secret))
+        Ok(Some(
+            get_storage_secret::<C, S>(secret_id, catalog_state, state).await?,
+        ))
     } else {
         Ok(None)
     }
@@ -235,7 +257,7 @@ where
 #[cfg(test)]
 #[allow(dead_code)]
 pub(crate) mod test {
-    use std::sync::Arc;
+    use std::{collections::HashMap, sync::Arc};
 
     use iceberg::NamespaceIdent;
     use iceberg_ext::catalog::rest::{CreateNamespaceRequest, CreateNamespaceResponse};
@@ -260,7 +282,9 @@ pub(crate) mod test {
         request_metadata::RequestMetadata,
         service::{
             authz::Authorizer,
+            commit_observer::CommitObservers,
             contract_verification::ContractVerifiers,
+            create_request_mutator::CreateRequestMutators,
             event_publisher::CloudEventsPublisher,
             storage::{
                 S3Credential, S3Flavor, S3Profile, StorageCredential, StorageProfile, TestProfile,
@@ -367,6 +391,7 @@ pub(crate) mod test {
                 storage_profile,
                 storage_credential,
                 delete_profile,
+                annotations: HashMap::new(),
             },
             api_context.clone(),
             metadata,
@@ -390,12 +415,23 @@ pub(crate) mod test {
                 secrets: SecretsState::from_pools(pool.clone(), pool.clone()),
                 publisher: CloudEventsPublisher::new(tx.clone()),
                 contract_verifiers: ContractVerifiers::new(vec![]),
+                commit_observers: CommitObservers::new(vec![]),
+                create_request_mutators: CreateRequestMutators::new(vec![]),
                 queues: TaskQueues::new(
                     Arc::new(
                         crate::implementations::postgres::task_queues::TabularExpirationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap(),
                     ),
                     Arc::new(
-                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
+                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::SnapshotCleanupQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::StorageSecretCleanupQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::DeletedWarehousePurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
                     ),
                 ),
             },
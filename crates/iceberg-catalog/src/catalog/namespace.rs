@@ -6,23 +6,34 @@ use iceberg::NamespaceIdent;
 use iceberg_ext::configs::{namespace::NamespaceProperties, ConfigProperty as _, Location};
 use itertools::Itertools;
 
-use super::{require_warehouse_id, CatalogServer, UnfilteredPage};
+use super::{
+    immutable_tables, require_warehouse_id, tables::parse_location, CatalogServer, UnfilteredPage,
+};
 use crate::{
     api::{
         iceberg::v1::{
-            namespace::GetNamespacePropertiesQuery, ApiContext, CreateNamespaceRequest,
-            CreateNamespaceResponse, ErrorModel, GetNamespaceResponse, ListNamespacesQuery,
-            ListNamespacesResponse, NamespaceParameters, Prefix, Result,
-            UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
+            namespace::{DropNamespaceQuery, GetNamespacePropertiesQuery},
+            ApiContext, CreateNamespaceRequest, CreateNamespaceResponse, ErrorModel,
+            GetNamespaceResponse, ListNamespacesQuery, ListNamespacesResponse, NamespaceParameters,
+            Prefix, Result, UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
         },
+        management::v1::{warehouse::TabularDeleteProfile, TabularType},
         set_not_found_status_code,
     },
     catalog,
+    catalog::idempotency::IdempotencyScope,
     request_metadata::RequestMetadata,
     service::{
-        authz::{Authorizer, CatalogNamespaceAction, CatalogWarehouseAction, NamespaceParent},
+        authz::{
+            Authorizer, CatalogNamespaceAction, CatalogTableAction, CatalogViewAction,
+            CatalogWarehouseAction, NamespaceParent,
+        },
+        contract_verification::ContractVerification as _,
+        namespace_property_schema::NamespacePropertySchema,
         secrets::SecretStore,
-        Catalog, GetWarehouseResponse, NamespaceIdentUuid, State, Transaction,
+        task_queue::tabular_purge_queue::TabularPurgeInput,
+        Catalog, GetWarehouseResponse, NamespaceDescendants, NamespaceIdentUuid, State,
+        TableIdentUuid, TabularIdentUuid, Transaction, ViewIdentUuid,
     },
     WarehouseIdent, CONFIG,
 };
@@ -51,6 +62,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             page_size: _,
             parent,
             return_uuids,
+            name_prefix,
         } = &query;
         parent.as_ref().map(validate_namespace_ident).transpose()?;
         let return_uuids = *return_uuids;
@@ -83,6 +95,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             query.page_token.clone(),
             |ps, page_token, trx| {
                 let parent = parent.clone();
+                let name_prefix = name_prefix.clone();
                 let authorizer = authorizer.clone();
                 let request_metadata = request_metadata.clone();
                 async move {
@@ -91,6 +104,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                         page_token: page_token.into(),
                         parent,
                         return_uuids: true,
+                        name_prefix,
                     };
 
                     // list_namespaces gives us a HashMap<Id, Ident> and a Vec<(Id, Token)>, in order
@@ -164,18 +178,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             .map(|p| validate_namespace_properties_keys(p.keys()))
             .transpose()?;
 
-        if CONFIG
-            .reserved_namespaces
-            .contains(&namespace.as_ref()[0].to_lowercase())
-        {
-            tracing::debug!("Denying reserved namespace: '{}'", &namespace.as_ref()[0]);
-            return Err(ErrorModel::bad_request(
-                "Namespace is reserved for internal use.",
-                "ReservedNamespace",
-                None,
-            )
-            .into());
-        }
+        require_namespace_not_reserved(namespace)?;
 
         // ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz;
@@ -216,17 +219,21 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         };
 
         // ------------------- BUSINESS LOGIC -------------------
+        let idempotency =
+            IdempotencyScope::from_request(&request_metadata, warehouse_id, "createNamespace");
+        if let Some(idempotency) = &idempotency {
+            if let Some(response) = idempotency.load::<C, _>(t.transaction()).await? {
+                t.commit().await?;
+                return Ok(response);
+            }
+        }
+
         let namespace_id = NamespaceIdentUuid::default();
         let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
-
-        let mut namespace_props = NamespaceProperties::try_from_maybe_props(properties.clone())
-            .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
-        // Set location if not specified - validate location if specified
-        set_namespace_location_property(&mut namespace_props, &warehouse, namespace_id)?;
-        remove_managed_namespace_properties(&mut namespace_props);
+        let properties = new_namespace_properties(properties.clone(), &warehouse, namespace_id)?;
 
         let mut request = request;
-        request.properties = Some(namespace_props.into());
+        request.properties = Some(properties);
 
         let mut r =
             C::create_namespace(warehouse_id, namespace_id, request, t.transaction()).await?;
@@ -235,13 +242,16 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         } else {
             NamespaceParent::Warehouse(warehouse_id)
         };
+        r.properties
+            .as_mut()
+            .map(|p| p.insert(NAMESPACE_ID_PROPERTY.to_string(), namespace_id.to_string()));
+        if let Some(idempotency) = &idempotency {
+            idempotency.store::<C, _>(&r, t.transaction()).await?;
+        }
         authorizer
             .create_namespace(&request_metadata, namespace_id, authz_parent)
             .await?;
         t.commit().await?;
-        r.properties
-            .as_mut()
-            .map(|p| p.insert(NAMESPACE_ID_PROPERTY.to_string(), namespace_id.to_string()));
         Ok(r)
     }
 
@@ -309,9 +319,13 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         Ok(())
     }
 
-    /// Drop a namespace from the catalog. Namespace must be empty.
+    /// Drop a namespace from the catalog. Namespace must be empty unless
+    /// `recursive` is set, in which case child namespaces, tables and views are
+    /// dropped in the same transaction.
+    #[allow(clippy::too_many_lines)]
     async fn drop_namespace(
         parameters: NamespaceParameters,
+        DropNamespaceQuery { recursive }: DropNamespaceQuery,
         state: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<()> {
@@ -344,12 +358,113 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         )
         .await?;
 
+        let descendants = if recursive {
+            let descendants = list_descendants_within_limit::<C>(
+                warehouse_id,
+                namespace_id,
+                &parameters.namespace,
+                CONFIG.max_objects_per_recursive_operation,
+                &mut t,
+            )
+            .await?;
+            authorize_drop_descendants(&authorizer, &request_metadata, &descendants).await?;
+            descendants
+        } else {
+            NamespaceDescendants::default()
+        };
+
         //  ------------------- BUSINESS LOGIC -------------------
+        let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+        if !descendants.tabulars.is_empty()
+            && matches!(
+                warehouse.tabular_delete_profile,
+                TabularDeleteProfile::Soft { .. }
+            )
+        {
+            return Err(ErrorModel::conflict(
+                format!(
+                    "Namespace '{}' contains tables or views. Soft-deleted tables and views keep their namespace, so they cannot be dropped recursively. Drop them individually first.",
+                    parameters.namespace.to_url_string()
+                ),
+                "NamespaceNotEmpty",
+                None,
+            )
+            .into());
+        }
+
+        // Tables are always purged by a recursive drop, which immutable tables forbid
+        let tables = C::load_tables(
+            warehouse_id,
+            descendants
+                .tabulars
+                .iter()
+                .filter_map(|(tabular_id, _)| match tabular_id {
+                    TabularIdentUuid::Table(id) => Some(TableIdentUuid::from(*id)),
+                    TabularIdentUuid::View(_) => None,
+                }),
+            false,
+            t.transaction(),
+        )
+        .await?;
+        for (tabular_id, ident) in &descendants.tabulars {
+            if let Some(table) = tables.get(&TableIdentUuid::from(**tabular_id)) {
+                immutable_tables::check_table_purge(ident, &table.table_metadata)?;
+            }
+        }
+
+        let mut dropped_tabulars = Vec::with_capacity(descendants.tabulars.len());
+        for (tabular_id, _) in &descendants.tabulars {
+            state
+                .v1_state
+                .contract_verifiers
+                .check_drop(*tabular_id)
+                .await?
+                .into_result()?;
+            let location = match tabular_id {
+                TabularIdentUuid::Table(id) => {
+                    C::drop_table(TableIdentUuid::from(*id), t.transaction()).await?
+                }
+                TabularIdentUuid::View(id) => {
+                    C::drop_view(ViewIdentUuid::from(*id), t.transaction()).await?
+                }
+            };
+            dropped_tabulars.push((*tabular_id, location));
+        }
+        // Child namespaces are ordered deepest first, so that each is empty when dropped
+        for (child_id, _) in &descendants.namespaces {
+            C::drop_namespace(warehouse_id, *child_id, t.transaction()).await?;
+            authorizer
+                .delete_namespace(&request_metadata, *child_id)
+                .await?;
+        }
         C::drop_namespace(warehouse_id, namespace_id, t.transaction()).await?;
         authorizer
             .delete_namespace(&request_metadata, namespace_id)
             .await?;
         t.commit().await?;
+
+        for (tabular_id, location) in dropped_tabulars {
+            let (id, tabular_type) = match tabular_id {
+                TabularIdentUuid::Table(id) => (id, TabularType::Table),
+                TabularIdentUuid::View(id) => (id, TabularType::View),
+            };
+            state
+                .v1_state
+                .queues
+                .queue_tabular_purge(TabularPurgeInput {
+                    tabular_id: id,
+                    tabular_location: location,
+                    warehouse_ident: warehouse_id,
+                    tabular_type,
+                    parent_id: None,
+                    purge_after: TabularPurgeInput::purge_after(warehouse.purge_grace_period),
+                })
+                .await?;
+            match tabular_id {
+                TabularIdentUuid::Table(id) => authorizer.delete_table(id.into()).await?,
+                TabularIdentUuid::View(id) => authorizer.delete_view(id.into()).await?,
+            }
+        }
         Ok(())
     }
 
@@ -363,20 +478,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         //  ------------------- VALIDATIONS -------------------
         let warehouse_id = require_warehouse_id(parameters.prefix)?;
         validate_namespace_ident(&parameters.namespace)?;
-        let UpdateNamespacePropertiesRequest { removals, updates } = request;
-        updates
-            .as_ref()
-            .map(|p| validate_namespace_properties_keys(p.keys()))
-            .transpose()?;
-        removals
-            .as_ref()
-            .map(validate_namespace_properties_keys)
-            .transpose()?;
-
-        namespace_location_may_not_change(updates.as_ref(), removals.as_ref())?;
-        let mut updates = NamespaceProperties::try_from_maybe_props(updates.clone())
-            .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
-        remove_managed_namespace_properties(&mut updates);
+        let changes = NamespacePropertyChanges::try_from_request(request)?;
         //  ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz.clone();
         let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
@@ -391,10 +493,13 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         .await?;
 
         //  ------------------- BUSINESS LOGIC -------------------
+        let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
         let previous_properties =
             C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
-        let (new_properties, r) =
-            update_namespace_properties(previous_properties.properties, updates, removals);
+        let (new_properties, r) = changes.apply(
+            &warehouse.namespace_property_schema,
+            previous_properties.properties,
+        )?;
         C::update_namespace_properties(warehouse_id, namespace_id, new_properties, t.transaction())
             .await?;
         t.commit().await?;
@@ -402,6 +507,58 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     }
 }
 
+/// Validated updates and removals of the properties of a single namespace.
+#[derive(Debug)]
+pub(crate) struct NamespacePropertyChanges {
+    /// Updates as requested, checked against the property schema of the warehouse.
+    requested_updates: HashMap<String, String>,
+    updates: NamespaceProperties,
+    removals: Option<Vec<String>>,
+}
+
+impl NamespacePropertyChanges {
+    pub(crate) fn try_from_request(request: UpdateNamespacePropertiesRequest) -> Result<Self> {
+        let UpdateNamespacePropertiesRequest { removals, updates } = request;
+        updates
+            .as_ref()
+            .map(|p| validate_namespace_properties_keys(p.keys()))
+            .transpose()?;
+        removals
+            .as_ref()
+            .map(validate_namespace_properties_keys)
+            .transpose()?;
+
+        namespace_location_may_not_change(updates.as_ref(), removals.as_ref())?;
+        let requested_updates = updates.clone().unwrap_or_default();
+        let mut updates = NamespaceProperties::try_from_maybe_props(updates)
+            .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
+        remove_managed_namespace_properties(&mut updates);
+
+        Ok(Self {
+            requested_updates,
+            updates,
+            removals,
+        })
+    }
+
+    /// Apply the changes to the `previous_properties` of a namespace, returning
+    /// the new properties to persist.
+    pub(crate) fn apply(
+        self,
+        schema: &NamespacePropertySchema,
+        previous_properties: Option<HashMap<String, String>>,
+    ) -> Result<(HashMap<String, String>, UpdateNamespacePropertiesResponse)> {
+        // Only updated properties are checked, so that values set before the
+        // schema was changed do not block unrelated updates.
+        schema.check_properties(&self.requested_updates)?;
+        Ok(update_namespace_properties(
+            previous_properties,
+            self.updates,
+            self.removals,
+        ))
+    }
+}
+
 pub(crate) async fn authorized_namespace_ident_to_id<C: Catalog, A: Authorizer + Clone>(
     authorizer: A,
     metadata: &RequestMetadata,
@@ -421,6 +578,244 @@ pub(crate) async fn authorized_namespace_ident_to_id<C: Catalog, A: Authorizer +
         .map_err(set_not_found_status_code)
 }
 
+/// Replace the default namespace alias by the default namespace of the warehouse.
+/// Other namespaces, and the alias in warehouses without a default namespace,
+/// are returned unchanged.
+pub(crate) async fn resolve_default_namespace<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    namespace: NamespaceIdent,
+    catalog_state: C::State,
+) -> Result<NamespaceIdent> {
+    if !is_default_namespace_alias(&namespace) {
+        return Ok(namespace);
+    }
+
+    let mut t = C::Transaction::begin_read(catalog_state).await?;
+    // Missing warehouses are reported after authorization
+    let default_namespace = C::get_warehouse(warehouse_id, t.transaction())
+        .await?
+        .and_then(|warehouse| warehouse.default_namespace);
+    t.commit().await?;
+
+    Ok(default_namespace.unwrap_or(namespace))
+}
+
+pub(crate) fn is_default_namespace_alias(namespace: &NamespaceIdent) -> bool {
+    namespace.len() == 1 && namespace.as_ref()[0] == CONFIG.default_namespace_alias
+}
+
+/// Child namespaces, tables and views of `namespace`. Fails if a recursive
+/// operation on the namespace would affect more than `max_objects` of them.
+///
+/// # Errors
+/// - 400 if the namespace has more than `max_objects` descendants
+pub(crate) async fn list_descendants_within_limit<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    namespace: &NamespaceIdent,
+    max_objects: usize,
+    t: &mut C::Transaction,
+) -> Result<NamespaceDescendants> {
+    let descendants = C::list_namespace_descendants(
+        warehouse_id,
+        namespace_id,
+        max_objects.saturating_add(1),
+        t.transaction(),
+    )
+    .await?;
+    if descendants.len() > max_objects {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Namespace '{}' contains more than {max_objects} namespaces, tables and views, which is the limit for recursive operations. Narrow the scope by operating on its child namespaces first.",
+                namespace.to_url_string()
+            ),
+            "RecursiveOperationLimitExceeded",
+            None,
+        )
+        .into());
+    }
+    Ok(descendants)
+}
+
+/// Require permission to drop every child namespace, table and view.
+async fn authorize_drop_descendants<A: Authorizer>(
+    authorizer: &A,
+    request_metadata: &RequestMetadata,
+    descendants: &NamespaceDescendants,
+) -> Result<()> {
+    for (tabular_id, _) in &descendants.tabulars {
+        match tabular_id {
+            TabularIdentUuid::Table(id) => {
+                authorizer
+                    .require_table_action(
+                        request_metadata,
+                        Ok(Some(TableIdentUuid::from(*id))),
+                        &CatalogTableAction::CanDrop,
+                    )
+                    .await?;
+            }
+            TabularIdentUuid::View(id) => {
+                authorizer
+                    .require_view_action(
+                        request_metadata,
+                        Ok(Some(ViewIdentUuid::from(*id))),
+                        &CatalogViewAction::CanDrop,
+                    )
+                    .await?;
+            }
+        }
+    }
+    for (child_id, _) in &descendants.namespaces {
+        authorizer
+            .require_namespace_action(
+                request_metadata,
+                Ok(Some(*child_id)),
+                &CatalogNamespaceAction::CanDelete,
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Storage prefix of a namespace, together with the region of its bucket.
+#[derive(Debug, Clone)]
+pub(crate) struct NamespaceStoragePrefix {
+    pub(crate) location: Location,
+    /// Region of the bucket, `None` uses the region of the storage profile.
+    pub(crate) region: Option<String>,
+}
+
+impl NamespaceStoragePrefix {
+    /// Region of the bucket holding `location`. Tabulars created before the storage
+    /// prefix was set or changed are not located below it and keep using the region
+    /// of the storage profile.
+    pub(crate) fn region_of(&self, location: &Location) -> Option<String> {
+        self.region
+            .clone()
+            .filter(|_| location.is_sublocation_of(&self.location))
+    }
+}
+
+/// Storage prefix that tabulars of `namespace` are placed under. Namespaces without
+/// an own storage prefix inherit the prefix and region of their nearest ancestor.
+pub(crate) async fn resolve_storage_prefix<C: Catalog>(
+    namespace: &crate::service::GetNamespaceResponse,
+    t: &mut C::Transaction,
+) -> Result<Option<NamespaceStoragePrefix>> {
+    let mut storage_prefix = namespace
+        .storage_prefix
+        .clone()
+        .map(|prefix| (prefix, namespace.storage_region.clone()));
+    let mut parent = namespace.namespace.parent();
+    while storage_prefix.is_none() {
+        let Some(parent_ident) = parent else {
+            break;
+        };
+        if let Some(parent_id) =
+            C::namespace_to_id(namespace.warehouse_id, &parent_ident, t.transaction()).await?
+        {
+            let parent_namespace =
+                C::get_namespace(namespace.warehouse_id, parent_id, t.transaction()).await?;
+            storage_prefix = parent_namespace
+                .storage_prefix
+                .map(|prefix| (prefix, parent_namespace.storage_region));
+        }
+        parent = parent_ident.parent();
+    }
+
+    storage_prefix
+        .map(|(prefix, region)| {
+            Ok(NamespaceStoragePrefix {
+                location: parse_location(&prefix, StatusCode::INTERNAL_SERVER_ERROR)?,
+                region,
+            })
+        })
+        .transpose()
+}
+
+/// Region of the bucket holding `location` of a tabular in `namespace`, if it
+/// differs from the region of the storage profile of the warehouse.
+pub(crate) async fn resolve_storage_region<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    namespace: &NamespaceIdent,
+    location: &Location,
+    t: &mut C::Transaction,
+) -> Result<Option<String>> {
+    let Some(namespace_id) = C::namespace_to_id(warehouse_id, namespace, t.transaction()).await?
+    else {
+        return Ok(None);
+    };
+    let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+    Ok(resolve_storage_prefix::<C>(&namespace, t)
+        .await?
+        .and_then(|storage_prefix| storage_prefix.region_of(location)))
+}
+
+/// Default lifetime of credentials vended for tables of `namespace`. Namespaces
+/// without an own default inherit the default of their nearest ancestor.
+pub(crate) async fn resolve_credential_ttl<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    namespace: &NamespaceIdent,
+    t: &mut C::Transaction,
+) -> Result<Option<chrono::Duration>> {
+    let mut current = Some(namespace.clone());
+    while let Some(ident) = current {
+        if let Some(namespace_id) =
+            C::namespace_to_id(warehouse_id, &ident, t.transaction()).await?
+        {
+            let credential_ttl = C::get_namespace(warehouse_id, namespace_id, t.transaction())
+                .await?
+                .credential_ttl;
+            if credential_ttl.is_some() {
+                return Ok(credential_ttl);
+            }
+        }
+        current = ident.parent();
+    }
+    Ok(None)
+}
+
+/// Properties of a namespace and of all its ancestors.
+#[derive(Debug, Clone)]
+pub(crate) struct NamespacePropertyChain {
+    /// The namespace and its existing ancestors, ordered from the root to the namespace itself.
+    pub(crate) levels: Vec<crate::service::GetNamespaceResponse>,
+    /// Properties of all levels merged. Values of child namespaces override those of their ancestors.
+    pub(crate) effective_properties: HashMap<String, String>,
+}
+
+/// Collect the properties of `namespace` and its ancestors, walking up the parents
+/// of the namespace identifier.
+pub(crate) async fn resolve_namespace_properties<C: Catalog>(
+    namespace: crate::service::GetNamespaceResponse,
+    t: &mut C::Transaction,
+) -> Result<NamespacePropertyChain> {
+    let mut parent = namespace.namespace.parent();
+    let warehouse_id = namespace.warehouse_id;
+    let mut levels = vec![namespace];
+    while let Some(parent_ident) = parent {
+        if let Some(parent_id) =
+            C::namespace_to_id(warehouse_id, &parent_ident, t.transaction()).await?
+        {
+            levels.push(C::get_namespace(warehouse_id, parent_id, t.transaction()).await?);
+        }
+        parent = parent_ident.parent();
+    }
+    levels.reverse();
+
+    let effective_properties = levels
+        .iter()
+        .filter_map(|level| level.properties.as_ref())
+        .flatten()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+
+    Ok(NamespacePropertyChain {
+        levels,
+        effective_properties,
+    })
+}
+
 pub(crate) fn uppercase_first_letter(s: &str) -> String {
     let mut c = s.chars();
     match c.next() {
@@ -488,6 +883,43 @@ pub(crate) fn validate_namespace_ident(namespace: &NamespaceIdent) -> Result<()>
     Ok(())
 }
 
+pub(crate) fn require_namespace_not_reserved(namespace: &NamespaceIdent) -> Result<()> {
+    if CONFIG
+        .reserved_namespaces
+        .contains(&namespace.as_ref()[0].to_lowercase())
+    {
+        tracing::debug!("Denying reserved namespace: '{}'", &namespace.as_ref()[0]);
+        return Err(ErrorModel::bad_request(
+            "Namespace is reserved for internal use.",
+            "ReservedNamespace",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Validate the requested properties of a new namespace against the warehouse
+/// and add the managed properties, such as the location.
+pub(crate) fn new_namespace_properties(
+    properties: Option<HashMap<String, String>>,
+    warehouse: &GetWarehouseResponse,
+    namespace_id: NamespaceIdentUuid,
+) -> Result<HashMap<String, String>> {
+    if let Some(properties) = &properties {
+        warehouse
+            .namespace_property_schema
+            .check_properties(properties)?;
+    }
+
+    let mut namespace_props = NamespaceProperties::try_from_maybe_props(properties)
+        .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
+    // Set location if not specified - validate location if specified
+    set_namespace_location_property(&mut namespace_props, warehouse, namespace_id)?;
+    remove_managed_namespace_properties(&mut namespace_props);
+    Ok(namespace_props.into())
+}
+
 fn remove_managed_namespace_properties(namespace_props: &mut NamespaceProperties) {
     namespace_props.remove_untyped(NAMESPACE_ID_PROPERTY);
     namespace_props.remove_untyped(MANAGED_ACCESS_PROPERTY);
@@ -506,6 +938,7 @@ fn set_namespace_location_property(
     // For customer specified location, we need to check if we can write to the location.
     // If no location is specified, we use our default location.
     let location = if let Some(location) = location {
+        warehouse.require_allowed_scheme(&location)?;
         warehouse
             .storage_profile
             .require_allowed_location(&location)?;
@@ -597,19 +1030,33 @@ fn namespace_location_may_not_change(
 #[cfg(test)]
 mod tests {
 
-    use std::{collections::HashSet, hash::RandomState};
+    use std::{
+        collections::{HashMap, HashSet},
+        hash::RandomState,
+    };
 
     use iceberg::NamespaceIdent;
-    use iceberg_ext::catalog::rest::CreateNamespaceRequest;
+    use iceberg_ext::catalog::rest::{CreateNamespaceRequest, UpdateNamespacePropertiesRequest};
     use sqlx::PgPool;
 
     use crate::{
         api::{
             iceberg::{
                 types::{PageToken, Prefix},
-                v1::namespace::Service,
+                v1::{
+                    namespace::{DropNamespaceQuery, GetNamespacePropertiesQuery, Service},
+                    tables::TablesService as _,
+                    DataAccess, NamespaceParameters,
+                },
+            },
+            management::v1::{
+                warehouse::{
+                    BatchUpdateNamespacePropertiesRequest, CreateNamespaceHierarchyRequest,
+                    NamespacePropertiesUpdate, Service as _, TabularDeleteProfile,
+                    UpdateWarehouseNamespacePropertySchemaRequest,
+                },
+                ApiServer,
             },
-            management::v1::warehouse::TabularDeleteProfile,
             ApiContext,
         },
         catalog::{test::impl_pagination_tests, CatalogServer},
@@ -618,9 +1065,13 @@ mod tests {
         },
         request_metadata::RequestMetadata,
         service::{
-            authz::implementations::openfga::{tests::ObjectHidingMock, OpenFGAAuthorizer},
-            ListNamespacesQuery, State, Transaction, UserId,
+            authz::{
+                implementations::openfga::{tests::ObjectHidingMock, OpenFGAAuthorizer},
+                AllowAllAuthorizer,
+            },
+            Catalog as _, ListNamespacesQuery, NamespaceIdentUuid, State, Transaction, UserId,
         },
+        CONFIG,
     };
 
     async fn ns_paginate_test_setup(
@@ -725,6 +1176,7 @@ mod tests {
                 page_size: Some(11),
                 parent: None,
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -740,6 +1192,7 @@ mod tests {
                 page_size: Some(10),
                 parent: None,
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -755,6 +1208,7 @@ mod tests {
                 page_size: Some(6),
                 parent: None,
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -779,6 +1233,7 @@ mod tests {
                 page_size: Some(6),
                 parent: None,
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -807,6 +1262,7 @@ mod tests {
                 page_size: Some(5),
                 parent: None,
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -834,6 +1290,7 @@ mod tests {
                 page_size: Some(5),
                 parent: None,
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -853,6 +1310,82 @@ mod tests {
         }
     }
 
+    #[sqlx::test]
+    async fn test_list_namespaces_omits_properties(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        CatalogServer::create_namespace(
+            prefix.clone(),
+            CreateNamespaceRequest {
+                namespace: NamespaceIdent::new("ns-with-props".to_string()),
+                properties: Some(HashMap::from_iter([(
+                    "large-property".to_string(),
+                    "x".repeat(10_000),
+                )])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // The catalog backend only returns identifiers, properties are never loaded
+        let mut t = PostgresTransaction::begin_read(ctx.v1_state.catalog.clone())
+            .await
+            .unwrap();
+        let listed = PostgresCatalog::list_namespaces(
+            warehouse.warehouse_id,
+            &ListNamespacesQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: None,
+                parent: None,
+                return_uuids: true,
+                name_prefix: None,
+            },
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+        assert_eq!(
+            listed.into_hashmap().into_values().collect::<Vec<_>>(),
+            vec![NamespaceIdent::new("ns-with-props".to_string())]
+        );
+
+        let response = CatalogServer::list_namespaces(
+            prefix,
+            ListNamespacesQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: None,
+                parent: None,
+                return_uuids: true,
+                name_prefix: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let response = serde_json::to_value(&response).unwrap();
+        let mut keys = response
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(keys, vec!["namespace-uuids", "namespaces"]);
+        assert!(!response.to_string().contains("large-property"));
+    }
+
     #[test]
     fn test_update_ns_properties() {
         use super::*;
@@ -899,4 +1432,552 @@ mod tests {
         assert!(result.missing.is_none());
         assert!(new_props.is_empty());
     }
+
+    #[sqlx::test]
+    async fn test_namespace_property_schema(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        ApiServer::update_warehouse_namespace_property_schema(
+            warehouse.warehouse_id,
+            UpdateWarehouseNamespacePropertySchemaRequest {
+                namespace_property_schema: serde_json::from_value(serde_json::json!({
+                    "cost_center": {"type": "string", "pattern": "CC-[0-9]{4}"},
+                    "ttl_days": {"type": "integer", "min": 1},
+                }))
+                .unwrap(),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let err = CatalogServer::create_namespace(
+            prefix.clone(),
+            CreateNamespaceRequest {
+                namespace: NamespaceIdent::new("invalid".to_string()),
+                properties: Some(HashMap::from_iter([(
+                    "cost_center".to_string(),
+                    "marketing".to_string(),
+                )])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "NamespacePropertySchemaViolation");
+
+        let namespace = NamespaceIdent::new("ns".to_string());
+        CatalogServer::create_namespace(
+            prefix.clone(),
+            CreateNamespaceRequest {
+                namespace: namespace.clone(),
+                properties: Some(HashMap::from_iter([(
+                    "cost_center".to_string(),
+                    "CC-1234".to_string(),
+                )])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let parameters = NamespaceParameters { prefix, namespace };
+
+        // Conforming update, unconstrained keys accept any value
+        CatalogServer::update_namespace_properties(
+            parameters.clone(),
+            UpdateNamespacePropertiesRequest {
+                removals: None,
+                updates: Some(HashMap::from_iter([
+                    ("ttl_days".to_string(), "30".to_string()),
+                    ("owner".to_string(), "anyone".to_string()),
+                ])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // Nonconforming update
+        let err = CatalogServer::update_namespace_properties(
+            parameters.clone(),
+            UpdateNamespacePropertiesRequest {
+                removals: None,
+                updates: Some(HashMap::from_iter([(
+                    "ttl_days".to_string(),
+                    "forever".to_string(),
+                )])),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "NamespacePropertySchemaViolation");
+        assert!(
+            err.error.message.contains("ttl_days"),
+            "{}",
+            err.error.message
+        );
+        assert!(
+            err.error.message.contains("an integer >= 1"),
+            "{}",
+            err.error.message
+        );
+
+        let properties = CatalogServer::load_namespace_metadata(
+            parameters,
+            GetNamespacePropertiesQuery { return_uuid: false },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap()
+        .properties
+        .unwrap();
+        assert_eq!(properties.get("ttl_days"), Some(&"30".to_string()));
+    }
+
+    #[sqlx::test]
+    async fn test_namespace_effective_properties(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+
+        let levels = [
+            (vec!["a"], vec![("owner", "team-a"), ("retention", "30d")]),
+            (vec!["a", "b"], vec![("retention", "90d"), ("tier", "gold")]),
+            (vec!["a", "b", "c"], vec![("tier", "silver")]),
+        ];
+        let mut namespace_ids = vec![];
+        for (name, properties) in &levels {
+            let response = CatalogServer::create_namespace(
+                prefix.clone(),
+                CreateNamespaceRequest {
+                    namespace: NamespaceIdent::from_strs(name).unwrap(),
+                    properties: Some(
+                        properties
+                            .iter()
+                            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                            .collect(),
+                    ),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            namespace_ids.push(
+                response.properties.unwrap()[super::NAMESPACE_ID_PROPERTY]
+                    .parse::<uuid::Uuid>()
+                    .unwrap(),
+            );
+        }
+
+        let response = ApiServer::get_namespace_effective_properties(
+            warehouse.warehouse_id,
+            namespace_ids[2].into(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // Ordered from the root to the requested namespace, each with its own properties
+        assert_eq!(
+            response
+                .chain
+                .iter()
+                .map(|level| level.namespace_id)
+                .collect::<Vec<_>>(),
+            namespace_ids
+        );
+        for (level, (name, properties)) in response.chain.iter().zip(&levels) {
+            assert_eq!(level.namespace, NamespaceIdent::from_strs(name).unwrap());
+            for (key, value) in properties {
+                assert_eq!(level.properties.get(*key).map(String::as_str), Some(*value));
+            }
+        }
+        assert_eq!(response.chain[0].properties.get("tier"), None);
+
+        // Child values override ancestors
+        for (key, value) in [
+            ("owner", "team-a"),
+            ("retention", "90d"),
+            ("tier", "silver"),
+        ] {
+            assert_eq!(
+                response.effective_properties.get(key).map(String::as_str),
+                Some(value),
+                "{key}"
+            );
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_concurrent_create_namespace_has_single_winner(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "parent".to_string(),
+        )
+        .await;
+
+        for namespace in [
+            NamespaceIdent::new("race".to_string()),
+            NamespaceIdent::from_strs(["parent", "race"]).unwrap(),
+        ] {
+            let create = || {
+                CatalogServer::create_namespace(
+                    prefix.clone(),
+                    CreateNamespaceRequest {
+                        namespace: namespace.clone(),
+                        properties: None,
+                    },
+                    ctx.clone(),
+                    RequestMetadata::new_unauthenticated(),
+                )
+            };
+            let (first, second) = tokio::join!(create(), create());
+            let results = [first, second];
+
+            assert_eq!(
+                results.iter().filter(|r| r.is_ok()).count(),
+                1,
+                "{namespace:?}"
+            );
+            let err = results
+                .into_iter()
+                .find_map(Result::err)
+                .expect("The losing request must fail");
+            assert_eq!(err.error.code, http::StatusCode::CONFLICT);
+            assert_eq!(err.error.r#type, "NamespaceAlreadyExists");
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_batch_update_namespace_properties_is_atomic(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        for name in ["a", "b"] {
+            crate::catalog::test::create_ns(
+                ctx.clone(),
+                warehouse.warehouse_id.to_string(),
+                name.to_string(),
+            )
+            .await;
+        }
+        let batch = |names: &[&str]| BatchUpdateNamespacePropertiesRequest {
+            namespaces: names
+                .iter()
+                .map(|name| NamespacePropertiesUpdate {
+                    namespace: NamespaceIdent::new((*name).to_string()),
+                    updates: Some(HashMap::from_iter([(
+                        "rollout".to_string(),
+                        "v2".to_string(),
+                    )])),
+                    removals: None,
+                })
+                .collect(),
+        };
+        let rollout = |name: &str| {
+            let ctx = ctx.clone();
+            let prefix = prefix.clone();
+            let name = name.to_string();
+            async move {
+                CatalogServer::load_namespace_metadata(
+                    NamespaceParameters {
+                        prefix,
+                        namespace: NamespaceIdent::new(name),
+                    },
+                    GetNamespacePropertiesQuery { return_uuid: false },
+                    ctx,
+                    RequestMetadata::new_unauthenticated(),
+                )
+                .await
+                .unwrap()
+                .properties
+                .unwrap_or_default()
+                .get("rollout")
+                .cloned()
+            }
+        };
+
+        // One missing namespace rolls back the whole batch
+        let err = ApiServer::batch_update_namespace_properties(
+            warehouse.warehouse_id,
+            batch(&["a", "missing", "b"]),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::NOT_FOUND);
+        assert_eq!(rollout("a").await, None);
+        assert_eq!(rollout("b").await, None);
+
+        let err = ApiServer::batch_update_namespace_properties(
+            warehouse.warehouse_id,
+            batch(&["a", "A"]),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "DuplicateNamespaceInBatch");
+
+        let response = ApiServer::batch_update_namespace_properties(
+            warehouse.warehouse_id,
+            batch(&["a", "b"]),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.namespaces.len(), 2);
+        assert!(response
+            .namespaces
+            .iter()
+            .all(|result| result.updated == vec!["rollout".to_string()]));
+        assert_eq!(rollout("a").await.as_deref(), Some("v2"));
+        assert_eq!(rollout("b").await.as_deref(), Some("v2"));
+    }
+
+    #[sqlx::test]
+    async fn test_create_namespace_hierarchy(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let a = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "a".to_string(),
+        )
+        .await;
+        let a_id = a.properties.unwrap()[super::NAMESPACE_ID_PROPERTY].clone();
+        let ident = |parts: &[&str]| {
+            NamespaceIdent::from_vec(parts.iter().map(ToString::to_string).collect()).unwrap()
+        };
+        let create = || {
+            ApiServer::create_namespace_hierarchy(
+                warehouse.warehouse_id,
+                CreateNamespaceHierarchyRequest {
+                    namespace: ident(&["a", "b", "c"]),
+                    properties: Some(HashMap::from_iter([(
+                        "owner".to_string(),
+                        "team-c".to_string(),
+                    )])),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        let response = create().await.unwrap();
+        let levels = response
+            .namespaces
+            .iter()
+            .map(|level| (level.namespace.clone(), level.created))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            levels,
+            vec![
+                (ident(&["a"]), false),
+                (ident(&["a", "b"]), true),
+                (ident(&["a", "b", "c"]), true),
+            ]
+        );
+        assert_eq!(response.namespaces[0].namespace_id.to_string(), a_id);
+
+        let load = |parts: &'static [&'static str]| {
+            CatalogServer::load_namespace_metadata(
+                NamespaceParameters {
+                    prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                    namespace: ident(parts),
+                },
+                GetNamespacePropertiesQuery { return_uuid: true },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let b = load(&["a", "b"]).await.unwrap();
+        assert_eq!(b.namespace_uuid, Some(response.namespaces[1].namespace_id));
+        assert!(!b.properties.unwrap().contains_key("owner"));
+        let c = load(&["a", "b", "c"]).await.unwrap();
+        assert_eq!(c.namespace_uuid, Some(response.namespaces[2].namespace_id));
+        assert_eq!(c.properties.unwrap()["owner"], "team-c");
+
+        // All levels exist now
+        let response = create().await.unwrap();
+        assert!(response.namespaces.iter().all(|level| !level.created));
+    }
+
+    #[sqlx::test]
+    async fn test_recursive_drop_namespace(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        let ident = |parts: &[&str]| NamespaceIdent::from_strs(parts).unwrap();
+        ApiServer::create_namespace_hierarchy(
+            warehouse.warehouse_id,
+            CreateNamespaceHierarchyRequest {
+                namespace: ident(&["a", "b", "c"]),
+                properties: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: ident(&["a", "b", "c"]),
+            },
+            crate::catalog::tables::test::create_request(Some("tab".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let drop_a = |recursive: bool| {
+            CatalogServer::drop_namespace(
+                NamespaceParameters {
+                    prefix: prefix.clone(),
+                    namespace: ident(&["a"]),
+                },
+                DropNamespaceQuery { recursive },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        drop_a(false).await.unwrap_err();
+        drop_a(true).await.unwrap();
+
+        for parts in [&["a"][..], &["a", "b"], &["a", "b", "c"]] {
+            CatalogServer::namespace_exists(
+                NamespaceParameters {
+                    prefix: prefix.clone(),
+                    namespace: ident(parts),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap_err();
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_recursive_drop_exceeding_object_limit_is_rejected(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        let mut t = PostgresTransaction::begin_write(ctx.v1_state.catalog.clone())
+            .await
+            .unwrap();
+        let wide = NamespaceIdent::new("wide".to_string());
+        let children = (0..=CONFIG.max_objects_per_recursive_operation)
+            .map(|i| NamespaceIdent::from_strs(["wide".to_string(), i.to_string()]).unwrap());
+        for namespace in std::iter::once(wide.clone()).chain(children) {
+            PostgresCatalog::create_namespace(
+                warehouse.warehouse_id,
+                NamespaceIdentUuid::default(),
+                CreateNamespaceRequest {
+                    namespace,
+                    properties: None,
+                },
+                t.transaction(),
+            )
+            .await
+            .unwrap();
+        }
+        t.commit().await.unwrap();
+
+        let err = CatalogServer::drop_namespace(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: wide.clone(),
+            },
+            DropNamespaceQuery { recursive: true },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, 400);
+        assert_eq!(err.error.r#type, "RecursiveOperationLimitExceeded");
+
+        // Nothing was dropped
+        CatalogServer::namespace_exists(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: NamespaceIdent::from_strs(["wide", "0"]).unwrap(),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+    }
 }
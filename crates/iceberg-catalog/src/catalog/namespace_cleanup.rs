@@ -0,0 +1,308 @@
+use std::time::Duration;
+
+use axum_prometheus::metrics;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::Result,
+    request_metadata::RequestMetadata,
+    service::{
+        authz::Authorizer,
+        task_queue::{seconds_to_std_duration, std_duration_to_seconds},
+        Catalog, GetWarehouseResponse, Transaction, WarehouseStatus,
+    },
+    CONFIG,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NamespaceCleanupConfig {
+    /// Periodically remove empty namespaces of warehouses that enable the cleanup.
+    pub enabled: bool,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub interval: Duration,
+    /// First-level namespaces that are never removed, together with all their
+    /// children. Reserved namespaces are always exempt.
+    pub exempt_namespaces: Vec<String>,
+}
+
+impl Default for NamespaceCleanupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(3600),
+            exempt_namespaces: vec![],
+        }
+    }
+}
+
+/// Periodically runs [`cleanup_empty_namespaces`]. Never returns; spawn it as a background task.
+pub async fn namespace_cleanup_task<C: Catalog, A: Authorizer>(
+    catalog_state: C::State,
+    authorizer: A,
+    config: NamespaceCleanupConfig,
+) {
+    loop {
+        tokio::time::sleep(config.interval).await;
+
+        match cleanup_empty_namespaces::<C, A>(catalog_state.clone(), &authorizer, &config).await {
+            Ok(removed) => tracing::info!(removed, "Finished empty namespace cleanup"),
+            Err(e) => tracing::error!("Empty namespace cleanup failed: {:?}", e.error),
+        }
+    }
+}
+
+/// Remove namespaces without tables, views and child namespaces from all active
+/// warehouses with an empty namespace cleanup delay, once they have not been
+/// created, updated or emptied for that delay. Returns the number of removed namespaces.
+pub async fn cleanup_empty_namespaces<C: Catalog, A: Authorizer>(
+    catalog_state: C::State,
+    authorizer: &A,
+    config: &NamespaceCleanupConfig,
+) -> Result<usize> {
+    let exempt_namespaces = config
+        .exempt_namespaces
+        .iter()
+        .chain(CONFIG.reserved_namespaces.iter())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let projects = C::list_projects(None, t.transaction()).await?;
+    t.commit().await?;
+
+    let mut removed = 0;
+    for project in projects {
+        let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+        let warehouses = C::list_warehouses(
+            project.project_id,
+            Some(vec![WarehouseStatus::Active]),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        for warehouse in warehouses {
+            removed += cleanup_warehouse::<C, A>(
+                catalog_state.clone(),
+                authorizer,
+                &warehouse,
+                &exempt_namespaces,
+            )
+            .await?;
+        }
+    }
+
+    Ok(removed)
+}
+
+async fn cleanup_warehouse<C: Catalog, A: Authorizer>(
+    catalog_state: C::State,
+    authorizer: &A,
+    warehouse: &GetWarehouseResponse,
+    exempt_namespaces: &[String],
+) -> Result<usize> {
+    let Some(inactive_since) = warehouse
+        .empty_namespace_cleanup_after
+        .and_then(|cleanup_after| chrono::Utc::now().checked_sub_signed(cleanup_after))
+    else {
+        return Ok(0);
+    };
+
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let removed = C::delete_empty_namespaces(
+        warehouse.id,
+        inactive_since,
+        exempt_namespaces,
+        t.transaction(),
+    )
+    .await?;
+    t.commit().await?;
+
+    let metadata = RequestMetadata::new_background_task();
+    for namespace_id in &removed {
+        tracing::info!(
+            "Removed empty namespace {namespace_id} of warehouse {}",
+            warehouse.id
+        );
+        if let Err(e) = authorizer.delete_namespace(&metadata, *namespace_id).await {
+            tracing::error!(
+                "Failed to delete authorization relations of removed namespace {namespace_id}: {:?}",
+                e.error
+            );
+        }
+    }
+    metrics::counter!("lakekeeper_removed_empty_namespaces_total")
+        .increment(removed.len().try_into().unwrap_or(u64::MAX));
+
+    Ok(removed.len())
+}
+
+#[cfg(test)]
+mod test {
+    use iceberg::{NamespaceIdent, TableIdent};
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::{
+        api::{
+            iceberg::{
+                types::{DropParams, Prefix},
+                v1::{
+                    namespace::Service as _, tables::TablesService as _, DataAccess,
+                    NamespaceParameters, TableParameters,
+                },
+            },
+            management::v1::{
+                warehouse::{
+                    Service as _, TabularDeleteProfile, UpdateWarehouseEmptyNamespaceCleanupRequest,
+                },
+                ApiServer,
+            },
+        },
+        catalog::{tables::test::create_request, CatalogServer},
+        implementations::postgres::PostgresCatalog,
+        service::{authz::AllowAllAuthorizer, CreateNamespaceRequest},
+    };
+
+    #[sqlx::test]
+    async fn test_cleanup_empty_namespaces(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        let config = NamespaceCleanupConfig {
+            enabled: true,
+            interval: Duration::from_secs(1),
+            exempt_namespaces: vec!["archive".to_string()],
+        };
+        let create_namespace = |name: &[&str]| {
+            CatalogServer::create_namespace(
+                prefix.clone(),
+                CreateNamespaceRequest {
+                    namespace: NamespaceIdent::from_strs(name).unwrap(),
+                    properties: None,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let namespace_exists = |name: &[&str]| {
+            CatalogServer::namespace_exists(
+                NamespaceParameters {
+                    prefix: prefix.clone(),
+                    namespace: NamespaceIdent::from_strs(name).unwrap(),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        create_namespace(&["stale"]).await.unwrap();
+        create_namespace(&["stale", "child"]).await.unwrap();
+        create_namespace(&["archive"]).await.unwrap();
+        create_namespace(&["recent"]).await.unwrap();
+        create_namespace(&["used"]).await.unwrap();
+        for namespace in ["recent", "used"] {
+            CatalogServer::create_table(
+                NamespaceParameters {
+                    prefix: prefix.clone(),
+                    namespace: NamespaceIdent::new(namespace.to_string()),
+                },
+                create_request(Some("tab-1".to_string())),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+        }
+
+        // Disabled for the warehouse by default
+        let removed = cleanup_empty_namespaces::<PostgresCatalog, _>(
+            ctx.v1_state.catalog.clone(),
+            &AllowAllAuthorizer,
+            &config,
+        )
+        .await
+        .unwrap();
+        assert_eq!(removed, 0);
+
+        ApiServer::update_warehouse_empty_namespace_cleanup(
+            warehouse.warehouse_id,
+            UpdateWarehouseEmptyNamespaceCleanupRequest {
+                cleanup_after_seconds: Some(1),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+        // Emptied just now, so that the delay has not passed yet
+        CatalogServer::drop_table(
+            TableParameters {
+                prefix: prefix.clone(),
+                table: TableIdent::new(NamespaceIdent::new("recent".to_string()), "tab-1".into()),
+            },
+            DropParams {
+                purge_requested: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let removed = cleanup_empty_namespaces::<PostgresCatalog, _>(
+            ctx.v1_state.catalog.clone(),
+            &AllowAllAuthorizer,
+            &config,
+        )
+        .await
+        .unwrap();
+        assert_eq!(removed, 2);
+        // The parent became empty by removing its child
+        namespace_exists(&["stale", "child"]).await.unwrap_err();
+        namespace_exists(&["stale"]).await.unwrap_err();
+        namespace_exists(&["archive"]).await.unwrap();
+        namespace_exists(&["recent"]).await.unwrap();
+        namespace_exists(&["used"]).await.unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        let removed = cleanup_empty_namespaces::<PostgresCatalog, _>(
+            ctx.v1_state.catalog.clone(),
+            &AllowAllAuthorizer,
+            &config,
+        )
+        .await
+        .unwrap();
+        assert_eq!(removed, 1);
+        namespace_exists(&["recent"]).await.unwrap_err();
+        namespace_exists(&["used"]).await.unwrap();
+
+        for seconds in [0, 315_360_001, i64::MAX] {
+            let err = ApiServer::update_warehouse_empty_namespace_cleanup(
+                warehouse.warehouse_id,
+                UpdateWarehouseEmptyNamespaceCleanupRequest {
+                    cleanup_after_seconds: Some(seconds),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.error.code, http::StatusCode::BAD_REQUEST);
+            assert_eq!(err.error.r#type, "InvalidEmptyNamespaceCleanup");
+        }
+    }
+}
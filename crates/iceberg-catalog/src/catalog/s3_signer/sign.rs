@@ -5,6 +5,7 @@ use aws_sigv4::{
     sign::v4,
     {self},
 };
+use http::StatusCode;
 
 use super::{super::CatalogServer, error::SignError};
 use crate::{
@@ -12,13 +13,19 @@ use crate::{
         iceberg::types::Prefix, ApiContext, ErrorModel, IcebergErrorResponse, Result,
         S3SignRequest, S3SignResponse,
     },
-    catalog::require_warehouse_id,
+    catalog::{
+        namespace::resolve_storage_region,
+        require_warehouse_id,
+        tables::{credential_location, parse_location},
+    },
     request_metadata::RequestMetadata,
     service::{
+        authn::require_allowed_authentication_method,
         authz::{Authorizer, CatalogTableAction, CatalogWarehouseAction},
+        secret_encryption::get_storage_secret,
         secrets::SecretStore,
-        storage::{S3Location, S3Profile, StorageCredential},
-        Catalog, GetTableMetadataResponse, ListFlags, State, TableIdentUuid,
+        storage::{S3Location, S3Profile},
+        Catalog, GetTableMetadataResponse, ListFlags, State, TableIdentUuid, Transaction,
     },
 };
 
@@ -39,6 +46,13 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         request_metadata: RequestMetadata,
     ) -> Result<S3SignResponse> {
         let warehouse_id = require_warehouse_id(prefix.clone())?;
+        // Checked here as well, as signing requests may reach the signer on any route
+        require_allowed_authentication_method::<C>(
+            warehouse_id,
+            &request_metadata,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
         let authorizer = state.v1_state.authz;
         authorizer
             .require_warehouse_action(
@@ -69,7 +83,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         // that do not support custom data in tokens. Perspectively, we should
         // try to get per-table signer.uri support in Spark.
         let GetTableMetadataResponse {
-            table: _,
+            table,
             table_id,
             namespace_id: _,
             warehouse_id: _,
@@ -77,6 +91,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             metadata_location: _,
             storage_secret_ident,
             storage_profile,
+            description: _,
         } = if let Ok(table_id) = require_table_id(table.clone()) {
             let metadata = C::get_table_metadata_by_id(
                 warehouse_id,
@@ -87,7 +102,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                     include_deleted: false,
                     include_active: true,
                 },
-                state.v1_state.catalog,
+                state.v1_state.catalog.clone(),
             )
             .await;
             authorizer
@@ -136,22 +151,36 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             e
         };
 
+        let table_location =
+            parse_location(&location, StatusCode::INTERNAL_SERVER_ERROR).map_err(extend_err)?;
+        // Tables below a namespace storage prefix may live in a bucket of another region
+        let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
+        let storage_region =
+            resolve_storage_region::<C>(warehouse_id, &table.namespace, &table_location, &mut t)
+                .await?;
+        t.commit().await?;
+
         let storage_profile = storage_profile
+            .with_region(storage_region.as_deref())
+            .into_owned()
             .try_into_s3()
             .map_err(|e| extend_err(IcebergErrorResponse::from(e)))?;
 
         validate_region(&request_region, &storage_profile).map_err(extend_err)?;
-        validate_uri(&parsed_url, &location).map_err(extend_err)?;
+        // Only sign requests within the location vended credentials would be scoped to
+        let credential_location =
+            credential_location(&request_metadata, &table_location).map_err(extend_err)?;
+        validate_uri(&parsed_url, credential_location.as_str()).map_err(extend_err)?;
 
         // If all is good, we need the storage secret
         let storage_secret = if let Some(storage_secret_ident) = storage_secret_ident {
             Some(
-                state
-                    .v1_state
-                    .secrets
-                    .get_secret_by_id::<StorageCredential>(&storage_secret_ident)
-                    .await?
-                    .secret,
+                get_storage_secret::<C, _>(
+                    &storage_secret_ident,
+                    state.v1_state.catalog.clone(),
+                    &state.v1_state.secrets,
+                )
+                .await?,
             )
         } else {
             None
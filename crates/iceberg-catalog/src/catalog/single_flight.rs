@@ -0,0 +1,102 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use tokio::sync::OnceCell;
+
+/// Deduplicates concurrent fetches of the same key: While a fetch for a key is in
+/// flight, further calls for that key wait for it and receive its result instead
+/// of fetching again.
+///
+/// Results are not cached. Once a fetch has completed, the next call fetches again.
+/// If a fetch fails, one of the waiters fetches instead, so errors are never shared.
+#[derive(Debug)]
+pub(crate) struct SingleFlight<K, V> {
+    in_flight: Mutex<HashMap<K, Arc<OnceCell<V>>>>,
+}
+
+impl<K, V> Default for SingleFlight<K, V> {
+    fn default() -> Self {
+        Self {
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> SingleFlight<K, V> {
+    /// Run `fetch` unless a fetch for `key` is already in flight,
+    /// in which case its result is returned.
+    pub(crate) async fn run<E, F>(&self, key: K, fetch: F) -> Result<V, E>
+    where
+        F: Future<Output = Result<V, E>>,
+    {
+        let cell = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key.clone())
+            .or_default()
+            .clone();
+
+        let result = cell.get_or_try_init(|| fetch).await.cloned();
+
+        // Calls after this point must not receive the result of this fetch.
+        let mut in_flight = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if in_flight
+            .get(&key)
+            .is_some_and(|current| Arc::ptr_eq(current, &cell))
+        {
+            in_flight.remove(&key);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_concurrent_fetches_are_deduplicated() {
+        let single_flight = SingleFlight::<&str, usize>::default();
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async {
+            let n = fetches.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            Ok::<_, ()>(n)
+        };
+
+        let results =
+            futures::future::join_all((0..50).map(|_| single_flight.run("my-table", fetch())))
+                .await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(results.iter().all(|r| *r == Ok(0)));
+
+        // Completed fetches are not cached
+        assert_eq!(single_flight.run("my-table", fetch()).await, Ok(1));
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        // Waiters retry if the fetch fails
+        let results = futures::future::join_all((0..3).map(|i| {
+            single_flight.run("my-table", async move {
+                if i == 0 {
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                    Err(())
+                } else {
+                    Ok(i)
+                }
+            })
+        }))
+        .await;
+        assert_eq!(results, vec![Err(()), Ok(1), Ok(1)]);
+    }
+}
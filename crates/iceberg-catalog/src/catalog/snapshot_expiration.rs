@@ -0,0 +1,829 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use axum_prometheus::metrics;
+use iceberg::{
+    spec::{SnapshotRetention, TableMetadata},
+    TableIdent, TableRequirement, TableUpdate,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{immutable_tables::is_immutable, tables::commit_authorized_tables};
+use crate::{
+    api::{
+        iceberg::{
+            types::PageToken,
+            v1::{CommitTableRequest, CommitTransactionRequest, PaginationQuery, Prefix},
+        },
+        ApiContext, Result,
+    },
+    service::{
+        authz::Authorizer,
+        secrets::SecretStore,
+        snapshot_limit::SnapshotLimit,
+        task_queue::{seconds_to_std_duration, std_duration_to_seconds},
+        Catalog, GetWarehouseResponse, ListFlags, LoadTableResponse, NamespaceIdentUuid, State,
+        TableIdentUuid, TabularIdentUuid, Transaction, WarehouseStatus,
+    },
+};
+
+const PAGE_SIZE: i64 = 100;
+
+/// Maximum age of snapshots to retain, following Iceberg conventions.
+pub(crate) const PROPERTY_MAX_SNAPSHOT_AGE_MS: &str = "history.expire.max-snapshot-age-ms";
+/// Minimum number of snapshots to retain per branch, following Iceberg conventions.
+pub(crate) const PROPERTY_MIN_SNAPSHOTS_TO_KEEP: &str = "history.expire.min-snapshots-to-keep";
+/// Tables with `gc.enabled=false` are never expired.
+const PROPERTY_GC_ENABLED: &str = "gc.enabled";
+/// Age of snapshots retained if only the number of snapshots to keep is
+/// configured, the default of Iceberg.
+const DEFAULT_MAX_SNAPSHOT_AGE: chrono::Duration = chrono::Duration::days(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotExpirationConfig {
+    /// Periodically expire snapshots of tables with an expiration policy.
+    pub enabled: bool,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub interval: Duration,
+    /// Maximum number of pages of tables listed per warehouse in a single run.
+    pub max_pages: u32,
+}
+
+impl Default for SnapshotExpirationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(3600),
+            max_pages: 100,
+        }
+    }
+}
+
+/// Snapshots to retain for a table. Snapshots referenced by a branch or tag are
+/// always retained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SnapshotExpirationPolicy {
+    /// Number of ancestors retained per branch, including the snapshot of the branch.
+    pub(crate) min_snapshots_to_keep: usize,
+    /// Snapshots newer than this are retained.
+    pub(crate) max_snapshot_age: chrono::Duration,
+}
+
+impl SnapshotExpirationPolicy {
+    /// Policy configured by the `history.expire.*` properties. Table properties
+    /// override namespace properties, which act as defaults for all tables of the namespace.
+    ///
+    /// Returns `None` if neither the table nor the namespace configure expiration,
+    /// or if garbage collection is disabled for the table. Unset properties fall
+    /// back to the Iceberg defaults of one snapshot and five days.
+    pub(crate) fn from_properties(
+        table_properties: &HashMap<String, String>,
+        namespace_properties: &HashMap<String, String>,
+    ) -> Option<Self> {
+        if gc_disabled(table_properties) {
+            return None;
+        }
+
+        let property = |key: &str| {
+            let value = table_properties
+                .get(key)
+                .or_else(|| namespace_properties.get(key))?;
+            let parsed = value.parse::<i64>().ok().filter(|v| *v >= 0);
+            if parsed.is_none() {
+                tracing::warn!("Ignoring invalid value '{value}' of property '{key}'");
+            }
+            parsed
+        };
+        let min_snapshots_to_keep = property(PROPERTY_MIN_SNAPSHOTS_TO_KEEP);
+        let max_snapshot_age_ms = property(PROPERTY_MAX_SNAPSHOT_AGE_MS);
+        if min_snapshots_to_keep.is_none() && max_snapshot_age_ms.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            min_snapshots_to_keep: min_snapshots_to_keep
+                .map_or(1, |v| usize::try_from(v).unwrap_or(usize::MAX)),
+            max_snapshot_age: max_snapshot_age_ms
+                .map_or(DEFAULT_MAX_SNAPSHOT_AGE, chrono::Duration::milliseconds),
+        })
+    }
+
+    /// Ids of the snapshots of `metadata` that are not retained at `now_ms`.
+    ///
+    /// Retention settings of a branch override the policy for its ancestors.
+    /// Snapshots younger than `purge_grace_period` are always retained, so that
+    /// files which are no longer referenced after expiration stay in place for
+    /// at least as long as those of dropped tables.
+    pub(crate) fn expired_snapshot_ids(
+        &self,
+        metadata: &TableMetadata,
+        now_ms: i64,
+        purge_grace_period: Option<chrono::Duration>,
+    ) -> Vec<i64> {
+        let cutoff_ms = |max_age_ms: i64| {
+            let grace_cutoff = now_ms - purge_grace_period.map_or(0, |p| p.num_milliseconds());
+            grace_cutoff.min(now_ms - max_age_ms)
+        };
+        let default_max_age_ms = self.max_snapshot_age.num_milliseconds();
+
+        let mut retained = HashSet::new();
+        for reference in metadata.refs().values() {
+            retained.insert(reference.snapshot_id);
+            let SnapshotRetention::Branch {
+                min_snapshots_to_keep,
+                max_snapshot_age_ms,
+                ..
+            } = &reference.retention
+            else {
+                continue;
+            };
+            let min_snapshots_to_keep = min_snapshots_to_keep
+                .map_or(self.min_snapshots_to_keep, |v| {
+                    usize::try_from(v).unwrap_or_default()
+                });
+            let cutoff = cutoff_ms(max_snapshot_age_ms.unwrap_or(default_max_age_ms));
+
+            let mut kept = 0;
+            let mut ancestor = metadata.snapshot_by_id(reference.snapshot_id);
+            while let Some(snapshot) = ancestor {
+                if kept >= min_snapshots_to_keep && snapshot.timestamp_ms() < cutoff {
+                    break;
+                }
+                retained.insert(snapshot.snapshot_id());
+                kept += 1;
+                ancestor = snapshot
+                    .parent_snapshot_id()
+                    .and_then(|id| metadata.snapshot_by_id(id));
+            }
+        }
+
+        let cutoff = cutoff_ms(default_max_age_ms);
+        let mut expired = metadata
+            .snapshots()
+            .filter(|s| !retained.contains(&s.snapshot_id()) && s.timestamp_ms() < cutoff)
+            .map(|s| s.snapshot_id())
+            .collect::<Vec<_>>();
+        expired.sort_unstable();
+        expired
+    }
+}
+
+fn gc_disabled(table_properties: &HashMap<String, String>) -> bool {
+    table_properties
+        .get(PROPERTY_GC_ENABLED)
+        .is_some_and(|v| v.eq_ignore_ascii_case("false"))
+}
+
+/// Ids of the oldest snapshots of `metadata` to expire so that `retain-last`
+/// snapshots are left, if `metadata` has more than `max-snapshots` snapshots.
+///
+/// Snapshots referenced by a branch or tag and snapshots younger than
+/// `purge_grace_period` are never expired, so fewer than `retain-last` may be
+/// left only if none of them is eligible. Tables with `gc.enabled=false`,
+/// and immutable tables are not expired. Read-only tables are skipped by the caller.
+pub(crate) fn snapshot_ids_exceeding_limit(
+    limit: &SnapshotLimit,
+    metadata: &TableMetadata,
+    now_ms: i64,
+    purge_grace_period: Option<chrono::Duration>,
+) -> Vec<i64> {
+    let n_snapshots = metadata.snapshots().len();
+    if n_snapshots <= usize::try_from(limit.max_snapshots).unwrap_or(usize::MAX)
+        || gc_disabled(metadata.properties())
+        || is_immutable(metadata)
+    {
+        return vec![];
+    }
+    let n_expired =
+        n_snapshots.saturating_sub(usize::try_from(limit.retain_last).unwrap_or(usize::MAX));
+
+    let referenced = metadata
+        .refs()
+        .values()
+        .map(|r| r.snapshot_id)
+        .collect::<HashSet<_>>();
+    let cutoff = now_ms - purge_grace_period.map_or(0, |p| p.num_milliseconds());
+    let mut candidates = metadata
+        .snapshots()
+        .filter(|s| !referenced.contains(&s.snapshot_id()) && s.timestamp_ms() < cutoff)
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|s| (s.timestamp_ms(), s.sequence_number(), s.snapshot_id()));
+
+    let mut expired = candidates
+        .into_iter()
+        .take(n_expired)
+        .map(|s| s.snapshot_id())
+        .collect::<Vec<_>>();
+    expired.sort_unstable();
+    expired
+}
+
+/// Result of a single expiration run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotExpirationReport {
+    /// Number of tables with an expiration policy.
+    pub checked: usize,
+    /// Number of snapshots removed from table metadata.
+    pub expired_snapshots: usize,
+    /// Tables that changed concurrently. They are expired in the next run.
+    pub conflicts: usize,
+    /// Tables whose expiration failed for other reasons.
+    pub failed: usize,
+    /// True if a listing hit `max_pages`, so that some tables were not checked.
+    pub truncated: bool,
+}
+
+/// Periodically runs [`expire_snapshots`]. Never returns; spawn it as a background task.
+pub async fn snapshot_expiration_task<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    state: ApiContext<State<A, C, S>>,
+    config: SnapshotExpirationConfig,
+) {
+    loop {
+        tokio::time::sleep(config.interval).await;
+
+        match expire_snapshots(state.clone(), &config).await {
+            Ok(report) => tracing::info!(
+                checked = report.checked,
+                expired_snapshots = report.expired_snapshots,
+                conflicts = report.conflicts,
+                failed = report.failed,
+                truncated = report.truncated,
+                "Finished snapshot expiration"
+            ),
+            Err(e) => tracing::error!("Snapshot expiration failed: {:?}", e.error),
+        }
+    }
+}
+
+/// Remove snapshots that are no longer retained by the expiration policy of their
+/// table from the metadata of all tables in active warehouses.
+///
+/// Expiration is a regular commit that requires all references of the table to be
+/// unchanged, so that it never interferes with concurrent writers. Files referenced
+/// only by expired snapshots are not deleted.
+pub async fn expire_snapshots<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    state: ApiContext<State<A, C, S>>,
+    config: &SnapshotExpirationConfig,
+) -> Result<SnapshotExpirationReport> {
+    let catalog_state = state.v1_state.catalog.clone();
+    let mut report = SnapshotExpirationReport::default();
+
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let projects = C::list_projects(None, t.transaction()).await?;
+    t.commit().await?;
+
+    for project in projects {
+        let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+        let warehouses = C::list_warehouses(
+            project.project_id,
+            Some(vec![WarehouseStatus::Active]),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        for warehouse in warehouses {
+            expire_warehouse_snapshots(state.clone(), config, &warehouse, &mut report).await?;
+        }
+    }
+
+    Ok(report)
+}
+
+async fn expire_warehouse_snapshots<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    state: ApiContext<State<A, C, S>>,
+    config: &SnapshotExpirationConfig,
+    warehouse: &GetWarehouseResponse,
+    report: &mut SnapshotExpirationReport,
+) -> Result<()> {
+    let catalog_state = state.v1_state.catalog.clone();
+    let mut namespace_properties = HashMap::new();
+    let mut page_token = PageToken::NotSpecified;
+    for page in 0.. {
+        if page >= config.max_pages {
+            tracing::warn!(
+                "Stopped listing tables of warehouse {} after {} pages. Remaining tables are not expired in this run.",
+                warehouse.id,
+                config.max_pages
+            );
+            report.truncated = true;
+            break;
+        }
+        let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+        let tabulars = C::list_tabulars(
+            warehouse.id,
+            None,
+            ListFlags::default(),
+            t.transaction(),
+            PaginationQuery::new(page_token.clone(), Some(PAGE_SIZE)),
+        )
+        .await?;
+        let is_last_page = i64::try_from(tabulars.len()).unwrap_or(i64::MAX) < PAGE_SIZE;
+        page_token = tabulars.next_token().map(ToString::to_string).into();
+        let idents = tabulars
+            .into_iter()
+            .filter_map(|(id, (ident, _))| match id {
+                TabularIdentUuid::Table(id) => Some((TableIdentUuid::from(id), ident.into_inner())),
+                TabularIdentUuid::View(_) => None,
+            })
+            .collect::<HashMap<_, _>>();
+        let tables =
+            C::load_tables(warehouse.id, idents.keys().copied(), false, t.transaction()).await?;
+        for table in tables.values() {
+            if let std::collections::hash_map::Entry::Vacant(entry) =
+                namespace_properties.entry(table.namespace_id)
+            {
+                let namespace =
+                    C::get_namespace(warehouse.id, table.namespace_id, t.transaction()).await?;
+                entry.insert(namespace.properties.unwrap_or_default());
+            }
+        }
+        t.commit().await?;
+
+        for (table_id, table) in tables {
+            let Some(ident) = idents.get(&table_id) else {
+                continue;
+            };
+            expire_table_snapshots(
+                state.clone(),
+                warehouse,
+                ident,
+                &table,
+                &namespace_properties,
+                report,
+            )
+            .await;
+        }
+        if is_last_page {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+async fn expire_table_snapshots<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    state: ApiContext<State<A, C, S>>,
+    warehouse: &GetWarehouseResponse,
+    ident: &TableIdent,
+    table: &LoadTableResponse,
+    namespace_properties: &HashMap<NamespaceIdentUuid, HashMap<String, String>>,
+    report: &mut SnapshotExpirationReport,
+) {
+    let metadata = &table.table_metadata;
+    // The history of immutable and read-only tables is never changed
+    if table.read_only || is_immutable(metadata) {
+        return;
+    }
+    let Some(policy) = SnapshotExpirationPolicy::from_properties(
+        metadata.properties(),
+        namespace_properties
+            .get(&table.namespace_id)
+            .unwrap_or(&HashMap::new()),
+    ) else {
+        return;
+    };
+    report.checked += 1;
+
+    let expired = policy.expired_snapshot_ids(
+        metadata,
+        chrono::Utc::now().timestamp_millis(),
+        warehouse.purge_grace_period,
+    );
+    if expired.is_empty() {
+        return;
+    }
+    let n_expired = expired.len();
+
+    let requirements =
+        std::iter::once(TableRequirement::UuidMatch {
+            uuid: metadata.uuid(),
+        })
+        .chain(metadata.refs().iter().map(|(name, reference)| {
+            TableRequirement::RefSnapshotIdMatch {
+                r#ref: name.clone(),
+                snapshot_id: Some(reference.snapshot_id),
+            }
+        }))
+        .collect();
+    let result = commit_authorized_tables(
+        Some(Prefix(warehouse.id.to_string())),
+        warehouse.id,
+        CommitTransactionRequest {
+            table_changes: vec![CommitTableRequest {
+                identifier: Some(ident.clone()),
+                requirements,
+                updates: vec![TableUpdate::RemoveSnapshots {
+                    snapshot_ids: expired,
+                }],
+            }],
+        },
+        &HashMap::from([(ident.clone(), table.table_id)]),
+        state,
+        Uuid::now_v7().to_string(),
+        None,
+    )
+    .await;
+
+    match result {
+        Ok(_) => {
+            tracing::debug!("Expired {n_expired} snapshots of table {}", table.table_id);
+            report.expired_snapshots += n_expired;
+            metrics::counter!("lakekeeper_expired_snapshots_total")
+                .increment(n_expired.try_into().unwrap_or(u64::MAX));
+        }
+        Err(e) if e.error.code == http::StatusCode::CONFLICT.as_u16() => {
+            tracing::debug!(
+                "Table {} changed during snapshot expiration: {}",
+                table.table_id,
+                e.error
+            );
+            report.conflicts += 1;
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to expire snapshots of table {}: {:?}",
+                table.table_id,
+                e.error
+            );
+            report.failed += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use iceberg::spec::{
+        FormatVersion, Operation, Snapshot, SnapshotReference, SortOrder, Summary,
+        TableMetadataBuilder, UnboundPartitionSpec, MAIN_BRANCH,
+    };
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::{
+        api::{
+            iceberg::v1::{
+                tables::TablesService as _, DataAccess, LoadTableQuery, TableParameters,
+            },
+            management::v1::warehouse::TabularDeleteProfile,
+        },
+        catalog::{
+            immutable_tables::PROPERTY_IMMUTABLE, tables::test::create_request, CatalogServer,
+        },
+        request_metadata::RequestMetadata,
+        service::authz::AllowAllAuthorizer,
+    };
+
+    fn snapshot(id: i64, parent: Option<i64>, timestamp_ms: i64) -> Snapshot {
+        Snapshot::builder()
+            .with_snapshot_id(id)
+            .with_parent_snapshot_id(parent)
+            .with_timestamp_ms(timestamp_ms)
+            .with_sequence_number(id)
+            .with_schema_id(0)
+            .with_manifest_list(format!("/snap-{id}.avro"))
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: HashMap::new(),
+            })
+            .build()
+    }
+
+    /// Add a chain of snapshots with the given timestamps to `main`.
+    fn add_main_snapshots(
+        mut builder: TableMetadataBuilder,
+        first_id: i64,
+        timestamps_ms: &[i64],
+    ) -> TableMetadataBuilder {
+        let mut parent = None;
+        for (id, timestamp_ms) in (first_id..).zip(timestamps_ms) {
+            builder = builder
+                .add_snapshot(snapshot(id, parent, *timestamp_ms))
+                .unwrap()
+                .set_ref(
+                    MAIN_BRANCH,
+                    SnapshotReference {
+                        snapshot_id: id,
+                        retention: SnapshotRetention::Branch {
+                            min_snapshots_to_keep: None,
+                            max_snapshot_age_ms: None,
+                            max_ref_age_ms: None,
+                        },
+                    },
+                )
+                .unwrap();
+            parent = Some(id);
+        }
+        builder
+    }
+
+    fn properties(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_table_properties_override_namespace_defaults() {
+        let namespace = properties(&[
+            (PROPERTY_MIN_SNAPSHOTS_TO_KEEP, "5"),
+            (PROPERTY_MAX_SNAPSHOT_AGE_MS, "1000"),
+        ]);
+        assert_eq!(
+            SnapshotExpirationPolicy::from_properties(
+                &properties(&[(PROPERTY_MIN_SNAPSHOTS_TO_KEEP, "2")]),
+                &namespace
+            ),
+            Some(SnapshotExpirationPolicy {
+                min_snapshots_to_keep: 2,
+                max_snapshot_age: chrono::Duration::seconds(1),
+            })
+        );
+        assert_eq!(
+            SnapshotExpirationPolicy::from_properties(
+                &properties(&[(PROPERTY_MIN_SNAPSHOTS_TO_KEEP, "2")]),
+                &HashMap::new()
+            ),
+            Some(SnapshotExpirationPolicy {
+                min_snapshots_to_keep: 2,
+                max_snapshot_age: chrono::Duration::days(5),
+            })
+        );
+        assert_eq!(
+            SnapshotExpirationPolicy::from_properties(
+                &properties(&[(PROPERTY_MAX_SNAPSHOT_AGE_MS, "60000")]),
+                &HashMap::new()
+            ),
+            Some(SnapshotExpirationPolicy {
+                min_snapshots_to_keep: 1,
+                max_snapshot_age: chrono::Duration::minutes(1),
+            })
+        );
+        assert_eq!(
+            SnapshotExpirationPolicy::from_properties(
+                &properties(&[(PROPERTY_GC_ENABLED, "false")]),
+                &namespace
+            ),
+            None
+        );
+        assert_eq!(
+            SnapshotExpirationPolicy::from_properties(&HashMap::new(), &HashMap::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expired_snapshot_ids() {
+        let now = chrono::Utc::now().timestamp_millis();
+        let builder = TableMetadataBuilder::new(
+            create_request(None).schema,
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap();
+        let builder = add_main_snapshots(builder, 1, &[now - 500, now - 400, now - 300, now - 200]);
+        // A tag keeps snapshot 1 alive
+        let metadata = builder
+            .set_ref(
+                "v1",
+                SnapshotReference {
+                    snapshot_id: 1,
+                    retention: SnapshotRetention::Tag {
+                        max_ref_age_ms: None,
+                    },
+                },
+            )
+            .unwrap()
+            .build()
+            .unwrap()
+            .metadata;
+
+        let policy = SnapshotExpirationPolicy {
+            min_snapshots_to_keep: 1,
+            max_snapshot_age: chrono::Duration::zero(),
+        };
+        assert_eq!(
+            policy.expired_snapshot_ids(&metadata, now, None),
+            vec![2, 3]
+        );
+
+        let policy = SnapshotExpirationPolicy {
+            min_snapshots_to_keep: 1,
+            max_snapshot_age: chrono::Duration::milliseconds(350),
+        };
+        assert_eq!(policy.expired_snapshot_ids(&metadata, now, None), vec![2]);
+
+        // Snapshots within the purge grace period are retained
+        assert_eq!(
+            policy.expired_snapshot_ids(&metadata, now, Some(chrono::Duration::milliseconds(450))),
+            Vec::<i64>::new()
+        );
+    }
+
+    #[test]
+    fn test_snapshot_ids_exceeding_limit() {
+        let now = chrono::Utc::now().timestamp_millis();
+        let builder = TableMetadataBuilder::new(
+            create_request(None).schema,
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap();
+        let builder = add_main_snapshots(
+            builder,
+            1,
+            &[now - 500, now - 400, now - 300, now - 200, now - 100],
+        );
+        // A tag keeps snapshot 1 alive
+        let metadata = builder
+            .set_ref(
+                "v1",
+                SnapshotReference {
+                    snapshot_id: 1,
+                    retention: SnapshotRetention::Tag {
+                        max_ref_age_ms: None,
+                    },
+                },
+            )
+            .unwrap()
+            .build()
+            .unwrap()
+            .metadata;
+
+        let limit = |max_snapshots, retain_last| SnapshotLimit {
+            max_snapshots,
+            retain_last,
+        };
+        assert_eq!(
+            snapshot_ids_exceeding_limit(&limit(5, 2), &metadata, now, None),
+            Vec::<i64>::new()
+        );
+        assert_eq!(
+            snapshot_ids_exceeding_limit(&limit(4, 2), &metadata, now, None),
+            vec![2, 3, 4]
+        );
+        assert_eq!(
+            snapshot_ids_exceeding_limit(&limit(4, 4), &metadata, now, None),
+            vec![2]
+        );
+
+        // Snapshots within the purge grace period are retained
+        assert_eq!(
+            snapshot_ids_exceeding_limit(
+                &limit(4, 2),
+                &metadata,
+                now,
+                Some(chrono::Duration::milliseconds(350))
+            ),
+            vec![2]
+        );
+
+        // Snapshots of write-once tables are never expired
+        let protected = metadata
+            .clone()
+            .into_builder(None)
+            .set_properties(properties(&[(PROPERTY_IMMUTABLE, "true")]))
+            .unwrap()
+            .build()
+            .unwrap()
+            .metadata;
+        assert_eq!(
+            snapshot_ids_exceeding_limit(&limit(4, 2), &protected, now, None),
+            Vec::<i64>::new()
+        );
+
+        let metadata = metadata
+            .into_builder(None)
+            .set_properties(properties(&[(PROPERTY_GC_ENABLED, "false")]))
+            .unwrap()
+            .build()
+            .unwrap()
+            .metadata;
+        assert_eq!(
+            snapshot_ids_exceeding_limit(&limit(4, 2), &metadata, now, None),
+            Vec::<i64>::new()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_task_expires_snapshots_beyond_retention(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        let mut request = create_request(Some("tab-1".to_string()));
+        request.properties = Some(properties(&[
+            (PROPERTY_MIN_SNAPSHOTS_TO_KEEP, "2"),
+            (PROPERTY_MAX_SNAPSHOT_AGE_MS, "0"),
+        ]));
+        let table = CatalogServer::create_table(
+            crate::api::iceberg::v1::NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: ns.namespace.clone(),
+            },
+            request,
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let table_ident = TableIdent::new(ns.namespace.clone(), "tab-1".to_string());
+
+        // Snapshots in the past, so that they are eligible for expiration right away
+        let last_updated = table.metadata.last_updated_ms();
+        let updates = add_main_snapshots(
+            table.metadata.into_builder(table.metadata_location),
+            1,
+            &[
+                last_updated - 40,
+                last_updated - 30,
+                last_updated - 20,
+                last_updated - 10,
+            ],
+        )
+        .build()
+        .unwrap()
+        .changes;
+        CatalogServer::commit_table(
+            TableParameters {
+                prefix: prefix.clone(),
+                table: table_ident.clone(),
+            },
+            CommitTableRequest {
+                identifier: Some(table_ident.clone()),
+                requirements: vec![],
+                updates,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let report = expire_snapshots(
+            ctx.clone(),
+            &SnapshotExpirationConfig {
+                enabled: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.checked, 1);
+        assert_eq!(report.expired_snapshots, 2);
+        assert_eq!(report.failed + report.conflicts, 0);
+
+        let loaded = CatalogServer::load_table(
+            TableParameters {
+                prefix,
+                table: table_ident,
+            },
+            LoadTableQuery::default(),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let mut snapshot_ids = loaded
+            .metadata
+            .snapshots()
+            .map(|s| s.snapshot_id())
+            .collect::<Vec<_>>();
+        snapshot_ids.sort_unstable();
+        assert_eq!(snapshot_ids, vec![3, 4]);
+        assert_eq!(loaded.metadata.current_snapshot_id(), Some(4));
+
+        // Nothing is left to expire
+        let report = expire_snapshots(ctx, &SnapshotExpirationConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(report.expired_snapshots, 0);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use iceberg::{spec::TableMetadata, TableIdent, TableUpdate};
+use iceberg_ext::catalog::rest::TableDeprecation;
+
+use crate::{
+    api::{ErrorModel, Result},
+    request_metadata::RequestMetadata,
+};
+
+/// Table property marking a table as deprecated. Set via the management API only.
+pub(crate) const PROPERTY_DEPRECATED: &str = "lakekeeper.deprecated";
+/// Message shown to consumers of a deprecated table.
+pub(crate) const PROPERTY_DEPRECATION_MESSAGE: &str = "lakekeeper.deprecation-message";
+/// RFC 3339 timestamp from which on a deprecated table is retired.
+pub(crate) const PROPERTY_SUNSET: &str = "lakekeeper.sunset";
+/// Reject commits to a deprecated table once its sunset has passed.
+pub(crate) const PROPERTY_BLOCK_WRITES_AFTER_SUNSET: &str = "lakekeeper.block-writes-after-sunset";
+
+const DEPRECATION_PROPERTIES: [&str; 4] = [
+    PROPERTY_DEPRECATED,
+    PROPERTY_DEPRECATION_MESSAGE,
+    PROPERTY_SUNSET,
+    PROPERTY_BLOCK_WRITES_AFTER_SUNSET,
+];
+
+/// Deprecation of the table, if it is deprecated.
+pub(crate) fn table_deprecation(metadata: &TableMetadata) -> Option<TableDeprecation> {
+    let properties = metadata.properties();
+    let is_true = |key: &str| {
+        properties
+            .get(key)
+            .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+    };
+    if !is_true(PROPERTY_DEPRECATED) {
+        return None;
+    }
+
+    Some(TableDeprecation {
+        message: properties.get(PROPERTY_DEPRECATION_MESSAGE).cloned(),
+        sunset: properties
+            .get(PROPERTY_SUNSET)
+            .and_then(|sunset| DateTime::parse_from_rfc3339(sunset).ok())
+            .map(|sunset| sunset.with_timezone(&Utc)),
+        block_writes_after_sunset: is_true(PROPERTY_BLOCK_WRITES_AFTER_SUNSET),
+    })
+}
+
+/// Updates storing `deprecation` in the table properties. `None` removes the deprecation.
+pub(crate) fn deprecation_updates(deprecation: Option<&TableDeprecation>) -> Vec<TableUpdate> {
+    let mut properties = HashMap::new();
+    if let Some(deprecation) = deprecation {
+        properties.insert(PROPERTY_DEPRECATED.to_string(), "true".to_string());
+        if let Some(message) = &deprecation.message {
+            properties.insert(PROPERTY_DEPRECATION_MESSAGE.to_string(), message.clone());
+        }
+        if let Some(sunset) = deprecation.sunset {
+            properties.insert(PROPERTY_SUNSET.to_string(), sunset.to_rfc3339());
+        }
+        if deprecation.block_writes_after_sunset {
+            properties.insert(
+                PROPERTY_BLOCK_WRITES_AFTER_SUNSET.to_string(),
+                "true".to_string(),
+            );
+        }
+    }
+    let removals = DEPRECATION_PROPERTIES
+        .iter()
+        .filter(|key| !properties.contains_key(**key))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    let mut updates = vec![];
+    if !properties.is_empty() {
+        updates.push(TableUpdate::SetProperties {
+            updates: properties,
+        });
+    }
+    if !removals.is_empty() {
+        updates.push(TableUpdate::RemoveProperties { removals });
+    }
+    updates
+}
+
+/// Notify the client that `table` is deprecated via a `Warning` header and,
+/// if a sunset is set, a `Sunset` header.
+pub(crate) fn notify_deprecation(
+    table: &TableIdent,
+    deprecation: &TableDeprecation,
+    request_metadata: &RequestMetadata,
+) {
+    let mut warning = format!("Table '{table}' is deprecated");
+    if let Some(sunset) = deprecation.sunset {
+        warning.push_str(&format!(" and retired from {}", sunset.to_rfc3339()));
+        request_metadata.set_sunset(sunset);
+    }
+    if let Some(message) = &deprecation.message {
+        warning.push_str(&format!(": {message}"));
+    }
+    request_metadata.add_warning(warning);
+}
+
+/// Require that a commit of a user complies with the deprecation of the table:
+/// The deprecation can only be changed via the management API, and deprecated
+/// tables that block writes reject commits once their sunset has passed.
+///
+/// # Errors
+/// - 403 if the commit changes the deprecation or the table is past its sunset
+pub(crate) fn check_table_commit(
+    table: &TableIdent,
+    previous_metadata: &TableMetadata,
+    new_metadata: &TableMetadata,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let (previous, new) = (previous_metadata.properties(), new_metadata.properties());
+    if DEPRECATION_PROPERTIES
+        .iter()
+        .any(|key| previous.get(*key) != new.get(*key))
+    {
+        return Err(ErrorModel::forbidden(
+            format!("Deprecation of table '{table}' can only be changed via the management API"),
+            "TableDeprecationViolation",
+            None,
+        )
+        .into());
+    }
+
+    if let Some(TableDeprecation {
+        sunset: Some(sunset),
+        block_writes_after_sunset: true,
+        ..
+    }) = table_deprecation(previous_metadata)
+    {
+        if now >= sunset {
+            return Err(ErrorModel::forbidden(
+                format!(
+                    "Table '{table}' is retired since {} and no longer accepts commits",
+                    sunset.to_rfc3339()
+                ),
+                "TableSunset",
+                None,
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
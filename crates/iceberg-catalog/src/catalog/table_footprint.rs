@@ -0,0 +1,94 @@
+use iceberg::{
+    io::FileIO,
+    spec::{DataContentType, ManifestStatus, TableMetadata},
+};
+
+use crate::{
+    api::{management::v1::table::TableStorageFootprint, Result},
+    catalog::manifest_cache::load_manifest_cached,
+    service::ErrorModel,
+    WarehouseIdent,
+};
+
+/// Sum up the sizes of the files referenced by the current snapshot of a table,
+/// broken down into data files, delete files and metadata files.
+///
+/// Data and delete file sizes are read from the manifests, manifest sizes from
+/// the manifest list. The sizes of the table metadata file and of the manifest
+/// list are requested from storage. If storage does not report them, they are
+/// left out and the footprint is marked as approximate.
+///
+/// # Errors
+/// - 500 if the manifest list or a manifest cannot be read
+pub(crate) async fn table_storage_footprint(
+    warehouse_id: WarehouseIdent,
+    metadata: &TableMetadata,
+    metadata_location: Option<&str>,
+    file_io: &FileIO,
+) -> Result<TableStorageFootprint> {
+    let mut footprint = TableStorageFootprint {
+        snapshot_id: metadata.current_snapshot_id(),
+        data_files_size_in_bytes: 0,
+        delete_files_size_in_bytes: 0,
+        metadata_files_size_in_bytes: 0,
+        approximate: false,
+    };
+    add_metadata_file_size(&mut footprint, metadata_location, file_io).await;
+
+    let Some(snapshot) = metadata.current_snapshot() else {
+        return Ok(footprint);
+    };
+    add_metadata_file_size(&mut footprint, Some(snapshot.manifest_list()), file_io).await;
+
+    let manifest_list = snapshot
+        .load_manifest_list(file_io, metadata)
+        .await
+        .map_err(|e| io_error("Failed to read manifest list", e))?;
+    for manifest_file in manifest_list.entries() {
+        footprint.metadata_files_size_in_bytes +=
+            u64::try_from(manifest_file.manifest_length).unwrap_or_default();
+
+        let manifest = load_manifest_cached(warehouse_id, manifest_file, file_io)
+            .await
+            .map_err(|e| io_error("Failed to read manifest", e))?;
+        for entry in manifest.entries() {
+            if matches!(entry.status(), ManifestStatus::Deleted) {
+                continue;
+            }
+            let data_file = entry.data_file();
+            match data_file.content_type() {
+                DataContentType::Data => {
+                    footprint.data_files_size_in_bytes += data_file.file_size_in_bytes();
+                }
+                DataContentType::PositionDeletes | DataContentType::EqualityDeletes => {
+                    footprint.delete_files_size_in_bytes += data_file.file_size_in_bytes();
+                }
+            }
+        }
+    }
+
+    Ok(footprint)
+}
+
+async fn add_metadata_file_size(
+    footprint: &mut TableStorageFootprint,
+    location: Option<&str>,
+    file_io: &FileIO,
+) {
+    let size = match location.map(|l| file_io.new_input(l)) {
+        Some(Ok(input)) => input.metadata().await.ok().map(|m| m.size),
+        _ => None,
+    };
+    match size {
+        Some(size) => footprint.metadata_files_size_in_bytes += size,
+        None => footprint.approximate = true,
+    }
+}
+
+fn io_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(
+        format!("{message} while computing the storage footprint of the table: {e}"),
+        "TableStorageFootprintError",
+        Some(Box::new(e)),
+    )
+}
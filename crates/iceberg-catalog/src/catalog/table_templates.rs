@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+
+use iceberg::spec::{Schema, SortOrder, UnboundPartitionSpec};
+
+use crate::{
+    api::{
+        iceberg::v1::{CreateTableRequest, ErrorModel, Result},
+        management::v1::table_template::{TableTemplate, TableTemplateResponse},
+    },
+    catalog::tables::validate_table_properties,
+    service::{Catalog, Transaction},
+    WarehouseIdent,
+};
+
+/// Table property of a create request referencing the template to create the
+/// table from.
+pub(crate) const PROPERTY_TABLE_TEMPLATE: &str = "lakekeeper.template";
+/// Table property recording the version of the template a table was created from.
+pub(crate) const PROPERTY_TABLE_TEMPLATE_VERSION: &str = "lakekeeper.template-version";
+
+const MAX_TABLE_TEMPLATE_NAME_LENGTH: usize = 128;
+
+/// Require that `name` is a valid template name: Between 1 and 128 ASCII
+/// alphanumeric characters, `-` or `_`.
+pub(crate) fn validate_table_template_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || name.len() > MAX_TABLE_TEMPLATE_NAME_LENGTH
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Invalid table template name '{name}'. Names must consist of 1 to {MAX_TABLE_TEMPLATE_NAME_LENGTH} alphanumeric characters, '-' or '_'"
+            ),
+            "InvalidTableTemplateName",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Validate the contents of a template: Its properties must be valid table properties
+/// and must not reference another template. If the template contains a schema, its
+/// partition spec and sort order must only reference fields of that schema.
+pub(crate) fn validate_table_template(template: &TableTemplate) -> Result<()> {
+    validate_table_properties(template.properties.keys())?;
+    if template.properties.contains_key(PROPERTY_TABLE_TEMPLATE) {
+        return Err(ErrorModel::bad_request(
+            format!("Table templates must not set the property '{PROPERTY_TABLE_TEMPLATE}'"),
+            "InvalidTableTemplate",
+            None,
+        )
+        .into());
+    }
+    if let Some(schema) = &template.schema {
+        validate_template_fields(
+            schema,
+            template.partition_spec.as_ref(),
+            template.write_order.as_ref(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Require that the partition spec and sort order only reference fields of `schema`.
+fn validate_template_fields(
+    schema: &Schema,
+    partition_spec: Option<&UnboundPartitionSpec>,
+    write_order: Option<&SortOrder>,
+) -> Result<()> {
+    let source_ids = partition_spec
+        .into_iter()
+        .flat_map(|spec| spec.fields().iter().map(|field| field.source_id))
+        .chain(
+            write_order
+                .into_iter()
+                .flat_map(|order| order.fields.iter().map(|field| field.source_id)),
+        );
+    for source_id in source_ids {
+        if schema.field_by_id(source_id).is_none() {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Table template references field {source_id} which is not part of the table schema"
+                ),
+                "InvalidTableTemplate",
+                None,
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// If the request references a template via [`PROPERTY_TABLE_TEMPLATE`], fill all
+/// values not set in the request from the latest version of the template.
+/// The template is validated again, as validation rules may have changed since
+/// it was stored, and the partition spec and sort order taken from the template
+/// must fit the schema of the table.
+///
+/// # Errors
+/// - 400 if the referenced template does not exist in the warehouse
+/// - 400 if the template is not valid for the table to create
+pub(crate) async fn apply_table_template<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    request: &mut CreateTableRequest,
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<()> {
+    let Some(name) = request
+        .properties
+        .as_ref()
+        .and_then(|p| p.get(PROPERTY_TABLE_TEMPLATE))
+        .cloned()
+    else {
+        return Ok(());
+    };
+
+    let template = C::get_table_template(warehouse_id, &name, None, transaction)
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!("Table template '{name}' does not exist in warehouse {warehouse_id}"),
+                "TableTemplateNotFound",
+                None,
+            )
+        })?;
+    validate_table_template(&template.template)?;
+    merge_table_template(request, template)
+}
+
+fn merge_table_template(
+    request: &mut CreateTableRequest,
+    template: TableTemplateResponse,
+) -> Result<()> {
+    let TableTemplateResponse {
+        version, template, ..
+    } = template;
+    if request.schema.as_struct().fields().is_empty() {
+        if let Some(schema) = template.schema {
+            request.schema = schema;
+        }
+    }
+    let partition_spec = template
+        .partition_spec
+        .filter(|_| request.partition_spec.is_none());
+    let write_order = template
+        .write_order
+        .filter(|_| request.write_order.is_none());
+    validate_template_fields(
+        &request.schema,
+        partition_spec.as_ref(),
+        write_order.as_ref(),
+    )?;
+    if partition_spec.is_some() {
+        request.partition_spec = partition_spec;
+    }
+    if write_order.is_some() {
+        request.write_order = write_order;
+    }
+
+    let properties = request.properties.get_or_insert_with(HashMap::new);
+    for (key, value) in template.properties {
+        properties.entry(key).or_insert(value);
+    }
+    properties.insert(
+        PROPERTY_TABLE_TEMPLATE_VERSION.to_string(),
+        version.to_string(),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use iceberg::spec::{
+        NestedField, PrimitiveType, Schema, Transform, Type, UnboundPartitionSpec,
+    };
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::{
+        api::{
+            iceberg::v1::{tables::TablesService, DataAccess, NamespaceParameters, Prefix},
+            management::v1::{
+                table_template::{Service as _, TableTemplate},
+                warehouse::TabularDeleteProfile,
+                ApiServer,
+            },
+        },
+        catalog::CatalogServer,
+        request_metadata::RequestMetadata,
+        service::authz::AllowAllAuthorizer,
+    };
+
+    fn schema() -> Schema {
+        Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+                NestedField::required(2, "region", Type::Primitive(PrimitiveType::String)).into(),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    fn partition_spec(field: &str, source_id: i32) -> UnboundPartitionSpec {
+        UnboundPartitionSpec::builder()
+            .add_partition_field(source_id, field, Transform::Identity)
+            .unwrap()
+            .build()
+    }
+
+    fn create_request(name: &str, properties: HashMap<String, String>) -> CreateTableRequest {
+        CreateTableRequest {
+            name: name.to_string(),
+            location: None,
+            schema: Schema::builder().build().unwrap(),
+            partition_spec: None,
+            write_order: None,
+            stage_create: None,
+            properties: Some(properties),
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_create_table_from_template(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let namespace_parameters = NamespaceParameters {
+            prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+            namespace: ns.namespace.clone(),
+        };
+
+        let template = ApiServer::set_table_template(
+            warehouse.warehouse_id,
+            "events".to_string(),
+            TableTemplate {
+                schema: Some(schema()),
+                partition_spec: Some(partition_spec("region", 2)),
+                write_order: None,
+                properties: HashMap::from([
+                    ("retention_class".to_string(), "gold".to_string()),
+                    ("owner".to_string(), "analytics".to_string()),
+                ]),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(template.version, 1);
+
+        let table = CatalogServer::create_table(
+            namespace_parameters.clone(),
+            create_request(
+                "tab-1",
+                HashMap::from([
+                    (PROPERTY_TABLE_TEMPLATE.to_string(), "events".to_string()),
+                    ("owner".to_string(), "billing".to_string()),
+                ]),
+            ),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let metadata = table.metadata;
+        assert_eq!(metadata.current_schema().as_struct().fields().len(), 2);
+        let spec_fields = metadata.default_partition_spec().fields();
+        assert_eq!(spec_fields.len(), 1);
+        assert_eq!(spec_fields[0].name, "region");
+        let properties = metadata.properties();
+        assert_eq!(properties.get("retention_class").unwrap(), "gold");
+        assert_eq!(properties.get("owner").unwrap(), "billing");
+        assert_eq!(
+            properties.get(PROPERTY_TABLE_TEMPLATE_VERSION).unwrap(),
+            "1"
+        );
+
+        // A partition spec of the request overrides the one of the template
+        let mut request = create_request(
+            "tab-2",
+            HashMap::from([(PROPERTY_TABLE_TEMPLATE.to_string(), "events".to_string())]),
+        );
+        request.partition_spec = Some(partition_spec("id", 1));
+        let table = CatalogServer::create_table(
+            namespace_parameters.clone(),
+            request,
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let spec_fields = table.metadata.default_partition_spec().fields();
+        assert_eq!(spec_fields.len(), 1);
+        assert_eq!(spec_fields[0].name, "id");
+
+        // Unknown templates are rejected
+        let err = CatalogServer::create_table(
+            namespace_parameters,
+            create_request(
+                "tab-3",
+                HashMap::from([(PROPERTY_TABLE_TEMPLATE.to_string(), "unknown".to_string())]),
+            ),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "TableTemplateNotFound");
+    }
+
+    #[sqlx::test]
+    async fn test_template_must_fit_table_schema(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let namespace_parameters = NamespaceParameters {
+            prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+            namespace: ns.namespace.clone(),
+        };
+
+        // Partition specs must reference fields of the template schema
+        let err = ApiServer::set_table_template(
+            warehouse.warehouse_id,
+            "invalid".to_string(),
+            TableTemplate {
+                schema: Some(schema()),
+                partition_spec: Some(partition_spec("unknown", 3)),
+                ..Default::default()
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "InvalidTableTemplate");
+
+        // Templates without schema are validated against the schema of the request
+        ApiServer::set_table_template(
+            warehouse.warehouse_id,
+            "regional".to_string(),
+            TableTemplate {
+                partition_spec: Some(partition_spec("region", 2)),
+                ..Default::default()
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let mut request = create_request(
+            "tab-1",
+            HashMap::from([(PROPERTY_TABLE_TEMPLATE.to_string(), "regional".to_string())]),
+        );
+        request.schema = Schema::builder()
+            .with_fields(vec![NestedField::required(
+                1,
+                "id",
+                Type::Primitive(PrimitiveType::Long),
+            )
+            .into()])
+            .build()
+            .unwrap();
+        let err = CatalogServer::create_table(
+            namespace_parameters.clone(),
+            request,
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "InvalidTableTemplate");
+
+        let mut request = create_request(
+            "tab-2",
+            HashMap::from([(PROPERTY_TABLE_TEMPLATE.to_string(), "regional".to_string())]),
+        );
+        request.schema = schema();
+        let table = CatalogServer::create_table(
+            namespace_parameters,
+            request,
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let spec_fields = table.metadata.default_partition_spec().fields();
+        assert_eq!(spec_fields.len(), 1);
+        assert_eq!(spec_fields[0].name, "region");
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     str::FromStr as _,
+    sync::{Arc, LazyLock},
 };
 
 use futures::FutureExt;
@@ -24,49 +25,88 @@ use uuid::Uuid;
 
 use super::{
     commit_tables::apply_commit,
-    io::{delete_file, read_metadata_file, write_metadata_file},
+    credential_events::publish_credentials_vended_event,
+    io::{delete_file, metadata_conditional_writer, read_metadata_file, write_new_metadata_file},
+    manifest_merge::merge_manifests,
     maybe_get_secret,
-    namespace::{authorized_namespace_ident_to_id, validate_namespace_ident},
-    require_warehouse_id, CatalogServer,
+    namespace::{
+        authorized_namespace_ident_to_id, resolve_credential_ttl, resolve_default_namespace,
+        resolve_storage_prefix, resolve_storage_region, validate_namespace_ident,
+    },
+    require_warehouse_id,
+    single_flight::SingleFlight,
+    snapshot_expiration::snapshot_ids_exceeding_limit,
+    CatalogServer,
 };
 use crate::{
     api::{
         iceberg::{
             types::DropParams,
             v1::{
-                ApiContext, CommitTableRequest, CommitTableResponse, CommitTransactionRequest,
-                CreateTableRequest, DataAccess, ErrorModel, ListTablesQuery, ListTablesResponse,
-                LoadTableResult, NamespaceParameters, PaginationQuery, Prefix,
-                RegisterTableRequest, RenameTableRequest, Result, TableIdent, TableParameters,
+                tables::LoadTableFields, ApiContext, CommitTableRequest, CommitTableResponse,
+                CommitTransactionRequest, CreateTableRequest, DataAccess, ErrorModel,
+                ListTablesQuery, ListTablesResponse, LoadTableQuery, LoadTableResult,
+                NamespaceParameters, PaginationQuery, Prefix, RegisterTableRequest,
+                RenameTableRequest, Result, TableIdent, TableParameters,
             },
         },
         management::v1::{warehouse::TabularDeleteProfile, TabularType},
         set_not_found_status_code,
     },
     catalog,
-    catalog::{compression_codec::CompressionCodec, tabular::list_entities},
+    catalog::{
+        compression_codec::CompressionCodec,
+        file_format_validation::{apply_default_write_format, validate_added_file_formats},
+        file_path_validation::{validate_added_file_paths, validate_added_partition_statistics},
+        group_commit::{commit_grouped, groupable_parent},
+        idempotency::IdempotencyScope,
+        immutable_tables,
+        minimal_metadata::minimal_table_metadata,
+        table_deprecation,
+        table_templates::apply_table_template,
+        tabular::list_entities,
+    },
     request_metadata::RequestMetadata,
     retry::retry_fn,
     service::{
         authz::{Authorizer, CatalogNamespaceAction, CatalogTableAction, CatalogWarehouseAction},
+        commit_concurrency::acquire_table_commit_slots,
+        commit_observer::TableCommitEvent,
+        commit_throttle::throttle_commit,
         contract_verification::{ContractVerification, ContractVerificationOutcome},
         event_publisher::{CloudEventsPublisher, EventMetadata},
+        feature_flags::WarehouseFeatureFlag,
+        secret_encryption::get_storage_secret,
         secrets::SecretStore,
-        storage::{StorageLocations as _, StoragePermissions, StorageProfile, ValidationError},
+        storage::{
+            StorageLocations as _, StoragePermissions, StorageProfile, ValidationError,
+            TEAM_LOCATION_SEGMENT,
+        },
         task_queue::{
+            snapshot_cleanup_queue::SnapshotCleanupInput,
             tabular_expiration_queue::TabularExpirationInput,
             tabular_purge_queue::TabularPurgeInput,
         },
-        Catalog, CreateTableResponse, GetNamespaceResponse, ListFlags,
-        LoadTableResponse as CatalogLoadTableResult, State, TableCommit, TableCreation,
-        TableIdentUuid, TabularDetails, TabularIdentUuid, Transaction, WarehouseStatus,
+        Catalog, CreateTableResponse, GetNamespaceResponse, GetStorageConfigResponse,
+        GetWarehouseResponse, ListFlags, LoadTableResponse as CatalogLoadTableResult,
+        NamespaceIdentUuid, State, TableCommit, TableCreation, TableIdentUuid, TabularDetails,
+        TabularIdentUuid, Transaction, WarehouseStatus,
     },
-    WarehouseIdent,
+    WarehouseIdent, CONFIG,
 };
 
 const PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED: &str =
     "write.metadata.delete-after-commit.enabled";
 const PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED_DEFAULT: bool = false;
+/// Table property holding the description of a table.
+pub(crate) const PROPERTY_TABLE_DESCRIPTION: &str = "comment";
+/// Maximum length of a table description in characters.
+pub(crate) const MAX_TABLE_DESCRIPTION_LENGTH: usize = 4096;
+
+/// Concurrent loads of a table with the same metadata location share a single fetch.
+static TABLE_LOADS: LazyLock<
+    SingleFlight<(WarehouseIdent, TableIdentUuid, String), Arc<CatalogLoadTableResult>>,
+> = LazyLock::new(SingleFlight::default);
 
 #[async_trait::async_trait]
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
@@ -81,9 +121,13 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         request_metadata: RequestMetadata,
     ) -> Result<ListTablesResponse> {
         let return_uuids = query.return_uuids;
+        let name_prefix = query.name_prefix;
         // ------------------- VALIDATIONS -------------------
         let NamespaceParameters { namespace, prefix } = parameters;
         let warehouse_id = require_warehouse_id(prefix)?;
+        let namespace =
+            resolve_default_namespace::<C>(warehouse_id, namespace, state.v1_state.catalog.clone())
+                .await?;
         validate_namespace_ident(&namespace)?;
 
         // ------------------- AUTHZ -------------------
@@ -109,6 +153,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                     list_tables,
                     table_action,
                     namespace,
+                    name_prefix,
                     authorizer,
                     request_metadata,
                     warehouse_id
@@ -138,16 +183,30 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         // ------------------- VALIDATIONS -------------------
         let NamespaceParameters { namespace, prefix } = parameters;
         let warehouse_id = require_warehouse_id(prefix.clone())?;
+        let namespace =
+            resolve_default_namespace::<C>(warehouse_id, namespace, state.v1_state.catalog.clone())
+                .await?;
         let table = TableIdent::new(namespace.clone(), request.name.clone());
         validate_table_or_view_ident(&table)?;
 
+        // Additions of mutators are validated like user-specified values
+        let location_sub_prefix = state
+            .v1_state
+            .create_request_mutators
+            .mutate_create_table(&mut request, &table, &request_metadata)
+            .await?;
+
         if let Some(properties) = &request.properties {
             validate_table_properties(properties.keys())?;
+            validate_table_description(
+                properties.get(PROPERTY_TABLE_DESCRIPTION),
+                &request_metadata,
+            )?;
         }
 
         // ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz.clone();
-        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
         let namespace_id = authorized_namespace_ident_to_id::<C, _>(
             authorizer.clone(),
             &request_metadata,
@@ -159,6 +218,61 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         .await?;
 
         // ------------------- BUSINESS LOGIC -------------------
+        let idempotency =
+            IdempotencyScope::from_request(&request_metadata, warehouse_id, "createTable");
+        if let Some(idempotency) = &idempotency {
+            if let Some(stored) = idempotency
+                .load::<C, LoadTableResult>(t.transaction())
+                .await?
+            {
+                let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+                require_active_warehouse(warehouse.status)?;
+                let credential_ttl = vended_credential_ttl::<C>(
+                    warehouse_id,
+                    &table.namespace,
+                    &warehouse.storage_profile,
+                    &data_access,
+                    &request_metadata,
+                    &mut t,
+                )
+                .await?;
+                let storage_region = resolve_storage_region::<C>(
+                    warehouse_id,
+                    &table.namespace,
+                    &parse_location(
+                        stored.metadata.location(),
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    )?,
+                    &mut t,
+                )
+                .await?;
+                t.commit().await?;
+                let event_metadata = EventMetadata {
+                    tabular_id: TabularIdentUuid::Table(stored.metadata.uuid()),
+                    warehouse_id,
+                    name: table.name.clone(),
+                    namespace: table.namespace.to_url_string(),
+                    prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
+                    num_events: 1,
+                    sequence_number: 0,
+                    trace_id: request_metadata.request_id().to_string(),
+                };
+                return replay_create_table::<C, _>(
+                    stored,
+                    &warehouse,
+                    &data_access,
+                    state.v1_state.catalog.clone(),
+                    &state.v1_state.secrets,
+                    &state.v1_state.publisher,
+                    &request_metadata,
+                    event_metadata,
+                    credential_ttl,
+                    storage_region.as_deref(),
+                )
+                .await;
+            }
+        }
+
         let id = Uuid::now_v7();
         let tabular_id = TabularIdentUuid::Table(id);
         let table_id = TableIdentUuid::from(id);
@@ -167,13 +281,50 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
         let storage_profile = &warehouse.storage_profile;
         require_active_warehouse(warehouse.status)?;
+        require_namespace_capacity::<C>(
+            &warehouse,
+            namespace_id,
+            false,
+            &request_metadata,
+            t.transaction(),
+        )
+        .await?;
+        apply_table_template::<C>(warehouse_id, &mut request, t.transaction()).await?;
+        if request.location.is_none() && !location_sub_prefix.is_empty() {
+            let mut parent_location = default_table_parent_location::<C>(
+                &namespace,
+                &warehouse,
+                &request_metadata,
+                &mut t,
+            )
+            .await?;
+            for segment in &location_sub_prefix {
+                parent_location.push(segment);
+            }
+            request.location = Some(
+                storage_profile
+                    .default_tabular_location(&parent_location, tabular_id)
+                    .to_string(),
+            );
+        }
 
+        let team = storage_team(&request_metadata)?;
+        let storage_prefix = resolve_storage_prefix::<C>(&namespace, &mut t).await?;
         let table_location = determine_tabular_location(
             &namespace,
             request.location.clone(),
             tabular_id,
-            storage_profile,
+            &warehouse,
+            team.as_deref(),
+            storage_prefix.as_ref().map(|p| &p.location),
         )?;
+        let storage_region = storage_prefix
+            .as_ref()
+            .and_then(|p| p.region_of(&table_location));
+        let regional_profile = storage_profile.with_region(storage_region.as_deref());
+        let storage_profile = &*regional_profile;
+
+        apply_default_write_format(&warehouse, &mut request.properties)?;
 
         // Update the request for event
         request.location = Some(table_location.to_string());
@@ -206,6 +357,8 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 table_ident: &table,
                 table_metadata,
                 metadata_location: metadata_location.as_ref(),
+                storage_team: team.as_deref(),
+                read_only: false,
             },
             t.transaction(),
         )
@@ -214,7 +367,10 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         // We don't commit the transaction yet, first we need to write the metadata file.
         let storage_secret = if let Some(secret_id) = &warehouse.storage_secret_id {
             let secret_state = state.v1_state.secrets;
-            Some(secret_state.get_secret_by_id(secret_id).await?.secret)
+            Some(
+                get_storage_secret::<C, _>(secret_id, state.v1_state.catalog.clone(), &secret_state)
+                    .await?,
+            )
         } else {
             None
         };
@@ -255,30 +411,77 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         if let Some(metadata_location) = &metadata_location {
             let compression_codec = CompressionCodec::try_from_metadata(&table_metadata)?;
-            write_metadata_file(
+            let conditional_writer =
+                metadata_conditional_writer(storage_profile, storage_secret.as_ref());
+            write_new_metadata_file(
                 metadata_location,
                 &table_metadata,
                 compression_codec,
                 &file_io,
+                conditional_writer.as_deref(),
             )
             .await?;
         };
 
+        if let Some(idempotency) = &idempotency {
+            // Credentials are vended per request and must not be persisted
+            let response = LoadTableResult {
+                metadata_location: metadata_location.as_ref().map(ToString::to_string),
+                metadata: table_metadata.clone(),
+                config: None,
+                storage_credentials: None,
+                deprecation: None,
+            };
+            idempotency
+                .store::<C, _>(&response, t.transaction())
+                .await?;
+        }
+
         // This requires the storage secret
         // because the table config might contain vended-credentials based
         // on the `data_access` parameter.
+        let credential_location = credential_location(&request_metadata, &table_location)?;
+        let credential_ttl = vended_credential_ttl::<C>(
+            warehouse_id,
+            &table.namespace,
+            storage_profile,
+            &data_access,
+            &request_metadata,
+            &mut t,
+        )
+        .await?;
         let config = storage_profile
             .generate_table_config(
                 &data_access,
                 storage_secret.as_ref(),
-                &table_location,
+                &credential_location,
                 StoragePermissions::ReadWriteDelete,
+                credential_ttl,
             )
-            .await?;
+            .await?
+            .with_refresh_hint(warehouse.credential_refresh_before);
+        publish_credentials_vended_event(
+            &state.v1_state.publisher,
+            &request_metadata,
+            EventMetadata {
+                tabular_id: TabularIdentUuid::Table(*tabular_id),
+                warehouse_id,
+                name: table.name.clone(),
+                namespace: table.namespace.to_url_string(),
+                prefix: prefix.clone().map(Prefix::into_string).unwrap_or_default(),
+                num_events: 1,
+                sequence_number: 0,
+                trace_id: request_metadata.request_id().to_string(),
+            },
+            &credential_location,
+            StoragePermissions::ReadWriteDelete,
+            &config,
+        )
+        .await;
 
         let storage_credentials = (!config.creds.inner().is_empty()).then(|| {
             vec![StorageCredential {
-                prefix: table_location.to_string(),
+                prefix: credential_location.to_string(),
                 config: config.creds.into(),
             }]
         });
@@ -288,6 +491,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             metadata: table_metadata,
             config: Some(config.config.into()),
             storage_credentials,
+            deprecation: None,
         };
 
         authorizer
@@ -315,7 +519,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
                 num_events: 1,
                 sequence_number: 0,
-                trace_id: request_metadata.request_id(),
+                trace_id: request_metadata.request_id().to_string(),
             },
             body,
             "createTable",
@@ -335,6 +539,9 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         // ------------------- VALIDATIONS -------------------
         let NamespaceParameters { namespace, prefix } = parameters;
         let warehouse_id = require_warehouse_id(prefix.clone())?;
+        let namespace =
+            resolve_default_namespace::<C>(warehouse_id, namespace, state.v1_state.catalog.clone())
+                .await?;
         let table = TableIdent::new(namespace.clone(), request.name.clone());
         validate_table_or_view_ident(&table)?;
         let metadata_location =
@@ -342,7 +549,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         // ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz.clone();
-        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
         let namespace_id = authorized_namespace_ident_to_id::<C, _>(
             authorizer.clone(),
             &request_metadata,
@@ -358,18 +565,85 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let storage_profile = &warehouse.storage_profile;
 
         require_active_warehouse(warehouse.status)?;
-        storage_profile.require_allowed_location(&metadata_location)?;
+        require_namespace_capacity::<C>(
+            &warehouse,
+            namespace_id,
+            false,
+            &request_metadata,
+            t.transaction(),
+        )
+        .await?;
+        let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+        let storage_prefix = resolve_storage_prefix::<C>(&namespace, &mut t).await?;
+        let storage_prefix_location = storage_prefix.as_ref().map(|p| &p.location);
+        if let Some(storage_prefix) = storage_prefix_location {
+            warehouse.require_allowed_storage_prefix(storage_prefix)?;
+        }
+        // Tables below an import prefix are registered read-only.
+        let from_import_prefix = warehouse.is_import_location(&metadata_location);
+        if from_import_prefix {
+            warehouse.require_allowed_scheme(&metadata_location)?;
+        } else {
+            require_allowed_tabular_location(
+                &warehouse,
+                storage_prefix_location,
+                &metadata_location,
+            )?;
+        }
+        let storage_region = storage_prefix
+            .as_ref()
+            .and_then(|p| p.region_of(&metadata_location));
+        let regional_profile = storage_profile.with_region(storage_region.as_deref());
+        let storage_profile = &*regional_profile;
 
         let storage_secret =
-            maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+            maybe_get_secret::<C, _>(
+                warehouse.storage_secret_id,
+                state.v1_state.catalog.clone(),
+                &state.v1_state.secrets,
+            ).await?;
         let file_io = storage_profile.file_io(storage_secret.as_ref())?;
         let table_metadata = read_metadata_file(&file_io, &metadata_location).await?;
         let table_location = parse_location(table_metadata.location(), StatusCode::BAD_REQUEST)?;
 
         validate_table_properties(table_metadata.properties().keys())?;
-        storage_profile.require_allowed_location(&table_location)?;
+        if from_import_prefix {
+            if !warehouse.is_import_location(&table_location) {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Location '{table_location}' of table registered from import location '{metadata_location}' must be below an import prefix of the warehouse"
+                    ),
+                    "InvalidLocation",
+                    None,
+                )
+                .into());
+            }
+        } else {
+            require_allowed_tabular_location(&warehouse, storage_prefix_location, &table_location)?;
+        }
+        // Table ids must be unique, as tables are resolved by their id.
+        let table_metadata = if C::is_tabular_id_in_use(
+            TabularIdentUuid::Table(table_metadata.uuid()),
+            t.transaction(),
+        )
+        .await?
+        {
+            if !request.assign_new_uuid {
+                return Err(ErrorModel::conflict(
+                    format!(
+                        "Table UUID {} of metadata '{metadata_location}' is already used by another table or view. Set `assign-new-uuid` to register the table with a new UUID.",
+                        table_metadata.uuid()
+                    ),
+                    "DuplicateTableUUID",
+                    None,
+                )
+                .into());
+            }
+            assign_new_table_uuid(table_metadata)?
+        } else {
+            table_metadata
+        };
 
-        let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
         let tabular_id = TableIdentUuid::from(table_metadata.uuid());
 
         let CreateTableResponse {
@@ -381,6 +655,8 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 table_ident: &table,
                 table_metadata,
                 metadata_location: Some(&metadata_location),
+                storage_team: None,
+                read_only: from_import_prefix,
             },
             t.transaction(),
         )
@@ -394,7 +670,12 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 },
                 storage_secret.as_ref(),
                 &table_location,
-                StoragePermissions::ReadWriteDelete,
+                if from_import_prefix {
+                    StoragePermissions::Read
+                } else {
+                    StoragePermissions::ReadWriteDelete
+                },
+                None,
             )
             .await?;
 
@@ -419,7 +700,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
                 num_events: 1,
                 sequence_number: 0,
-                trace_id: request_metadata.request_id(),
+                trace_id: request_metadata.request_id().to_string(),
             },
             maybe_body_to_json(&request),
             "registerTable",
@@ -432,6 +713,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             metadata: table_metadata,
             config: Some(config.config.into()),
             storage_credentials: None,
+            deprecation: None,
         })
     }
 
@@ -439,13 +721,20 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     #[allow(clippy::too_many_lines)]
     async fn load_table(
         parameters: TableParameters,
+        query: LoadTableQuery,
         data_access: DataAccess,
         state: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<LoadTableResult> {
         // ------------------- VALIDATIONS -------------------
-        let TableParameters { prefix, table } = parameters;
-        let warehouse_id = require_warehouse_id(prefix)?;
+        let TableParameters { prefix, mut table } = parameters;
+        let warehouse_id = require_warehouse_id(prefix.clone())?;
+        table.namespace = resolve_default_namespace::<C>(
+            warehouse_id,
+            table.namespace,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
         // It is important to throw a 404 if a table cannot be found,
         // because spark might check if `table`.`branch` exists, which should return 404.
         // Only then will it treat it as a branch.
@@ -478,13 +767,54 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         .await?;
 
         // ------------------- BUSINESS LOGIC -------------------
-        let mut metadatas = C::load_tables(
-            warehouse_id,
-            vec![table_id.ident],
-            list_flags.include_deleted,
-            t.transaction(),
-        )
-        .await?;
+        // Authorization is checked per request above, only the fetch is shared.
+        let current_metadata_location = if CONFIG.deduplicate_table_loads {
+            C::get_table_metadata_location(warehouse_id, table_id.ident, t.transaction()).await?
+        } else {
+            None
+        };
+        let load = async {
+            let mut metadatas = C::load_tables(
+                warehouse_id,
+                vec![table_id.ident],
+                list_flags.include_deleted,
+                t.transaction(),
+            )
+            .await?;
+            remove_table(&table_id.ident, &table, &mut metadatas).map(Arc::new)
+        };
+        let loaded = match current_metadata_location {
+            Some(location) => {
+                TABLE_LOADS
+                    .run((warehouse_id, table_id.ident, location), load)
+                    .await?
+            }
+            None => load.await?,
+        };
+        let (credential_ttl, storage_region) = if storage_permissions.is_some() {
+            let credential_ttl = vended_credential_ttl::<C>(
+                warehouse_id,
+                &table.namespace,
+                &loaded.storage_profile,
+                &data_access,
+                &request_metadata,
+                &mut t,
+            )
+            .await?;
+            let storage_region = resolve_storage_region::<C>(
+                warehouse_id,
+                &table.namespace,
+                &parse_location(
+                    loaded.table_metadata.location(),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )?,
+                &mut t,
+            )
+            .await?;
+            (credential_ttl, storage_region)
+        } else {
+            (None, None)
+        };
         t.commit().await?;
         let CatalogLoadTableResult {
             table_id: _,
@@ -493,27 +823,66 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             metadata_location,
             storage_secret_ident,
             storage_profile,
-        } = remove_table(&table_id.ident, &table, &mut metadatas)?;
+            credential_refresh_before,
+            read_only,
+        } = Arc::unwrap_or_clone(loaded);
         require_not_staged(metadata_location.as_ref())?;
+        let deprecation = table_deprecation::table_deprecation(&table_metadata);
+        if let Some(deprecation) = &deprecation {
+            table_deprecation::notify_deprecation(&table, deprecation, &request_metadata);
+        }
+        // Data of read-only tables is not written through the catalog.
+        let storage_permissions = storage_permissions.map(|permissions| {
+            if read_only {
+                StoragePermissions::Read
+            } else {
+                permissions
+            }
+        });
 
         let table_location =
             parse_location(table_metadata.location(), StatusCode::INTERNAL_SERVER_ERROR)?;
 
         // ToDo: This is a small inefficiency: We fetch the secret even if it might
         // not be required based on the `data_access` parameter.
+        let credential_location = credential_location(&request_metadata, &table_location)?;
         let storage_config = if let Some(storage_permissions) = storage_permissions {
             let storage_secret =
-                maybe_get_secret(storage_secret_ident, &state.v1_state.secrets).await?;
-            Some(
-                storage_profile
-                    .generate_table_config(
-                        &data_access,
-                        storage_secret.as_ref(),
-                        &table_location,
-                        storage_permissions,
-                    )
-                    .await?,
+                maybe_get_secret::<C, _>(
+                storage_secret_ident,
+                state.v1_state.catalog.clone(),
+                &state.v1_state.secrets,
+            ).await?;
+            let storage_config = storage_profile
+                .with_region(storage_region.as_deref())
+                .generate_table_config(
+                    &data_access,
+                    storage_secret.as_ref(),
+                    &credential_location,
+                    storage_permissions,
+                    credential_ttl,
+                )
+                .await?
+                .with_refresh_hint(credential_refresh_before);
+            publish_credentials_vended_event(
+                &state.v1_state.publisher,
+                &request_metadata,
+                EventMetadata {
+                    tabular_id: TabularIdentUuid::Table(*table_id.ident),
+                    warehouse_id,
+                    name: table.name.clone(),
+                    namespace: table.namespace.to_url_string(),
+                    prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
+                    num_events: 1,
+                    sequence_number: 0,
+                    trace_id: request_metadata.request_id().to_string(),
+                },
+                &credential_location,
+                storage_permissions,
+                &storage_config,
             )
+            .await;
+            Some(storage_config)
         } else {
             None
         };
@@ -521,17 +890,23 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let storage_credentials = storage_config.as_ref().and_then(|c| {
             (!c.creds.inner().is_empty()).then(|| {
                 vec![StorageCredential {
-                    prefix: table_location.to_string(),
+                    prefix: credential_location.to_string(),
                     config: c.creds.clone().into(),
                 }]
             })
         });
 
+        let table_metadata = match query.fields {
+            LoadTableFields::Full => table_metadata,
+            LoadTableFields::Minimal => minimal_table_metadata(table_metadata)?,
+        };
+
         let load_table_result = LoadTableResult {
             metadata_location: metadata_location.as_ref().map(ToString::to_string),
             metadata: table_metadata,
             config: storage_config.map(|c| c.config.into()),
             storage_credentials,
+            deprecation,
         };
 
         Ok(load_table_result)
@@ -544,10 +919,16 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         request_metadata: RequestMetadata,
     ) -> Result<LoadCredentialsResponse> {
         // ------------------- VALIDATIONS -------------------
-        let TableParameters { prefix, table } = parameters;
-        let warehouse_id = require_warehouse_id(prefix)?;
+        let TableParameters { prefix, mut table } = parameters;
+        let warehouse_id = require_warehouse_id(prefix.clone())?;
+        table.namespace = resolve_default_namespace::<C>(
+            warehouse_id,
+            table.namespace,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
 
-        let mut t = C::Transaction::begin_read(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
         let (table_id, storage_permissions) = Self::resolve_and_authorize_table_access(
             &request_metadata,
             &table,
@@ -567,27 +948,76 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             None,
         ))?;
 
-        let (storage_secret_ident, storage_profile) =
-            C::load_storage_profile(warehouse_id, table_id.ident, t.transaction()).await?;
+        let GetStorageConfigResponse {
+            storage_profile,
+            storage_secret_ident,
+            refresh_before,
+            read_only,
+        } = C::load_storage_profile(warehouse_id, table_id.ident, t.transaction()).await?;
+        // Data of read-only tables is not written through the catalog.
+        let storage_permission = if read_only {
+            StoragePermissions::Read
+        } else {
+            storage_permission
+        };
+        let credential_ttl = vended_credential_ttl::<C>(
+            warehouse_id,
+            &table.namespace,
+            &storage_profile,
+            &data_access,
+            &request_metadata,
+            &mut t,
+        )
+        .await?;
+        let table_location = parse_location(
+            table_id.location.as_str(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )?;
+        let storage_region =
+            resolve_storage_region::<C>(warehouse_id, &table.namespace, &table_location, &mut t)
+                .await?;
         let storage_secret =
-            maybe_get_secret(storage_secret_ident, &state.v1_state.secrets).await?;
+            maybe_get_secret::<C, _>(
+                storage_secret_ident,
+                state.v1_state.catalog.clone(),
+                &state.v1_state.secrets,
+            ).await?;
+        let credential_location = credential_location(&request_metadata, &table_location)?;
         let storage_config = storage_profile
+            .with_region(storage_region.as_deref())
             .generate_table_config(
                 &data_access,
                 storage_secret.as_ref(),
-                &parse_location(
-                    table_id.location.as_str(),
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                )?,
+                &credential_location,
                 storage_permission,
+                credential_ttl,
             )
-            .await?;
+            .await?
+            .with_refresh_hint(refresh_before);
+        publish_credentials_vended_event(
+            &state.v1_state.publisher,
+            &request_metadata,
+            EventMetadata {
+                tabular_id: TabularIdentUuid::Table(*table_id.ident),
+                warehouse_id,
+                name: table.name.clone(),
+                namespace: table.namespace.to_url_string(),
+                prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
+                num_events: 1,
+                sequence_number: 0,
+                trace_id: request_metadata.request_id().to_string(),
+            },
+            &credential_location,
+            storage_permission,
+            &storage_config,
+        )
+        .await;
 
         let storage_credentials = if storage_config.creds.inner().is_empty() {
             vec![]
         } else {
             vec![StorageCredential {
-                prefix: table_id.location.clone(),
+                prefix: credential_location.to_string(),
                 config: storage_config.creds.into(),
             }]
         };
@@ -598,40 +1028,34 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     }
 
     /// Commit updates to a table
-    #[allow(clippy::too_many_lines)]
     async fn commit_table(
         parameters: TableParameters,
         mut request: CommitTableRequest,
         state: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<CommitTableResponse> {
-        request.identifier = Some(determine_table_ident(
-            parameters.table,
-            request.identifier.as_ref(),
-        )?);
-        let t = commit_tables_internal(
-            parameters.prefix,
-            CommitTransactionRequest {
-                table_changes: vec![request],
-            },
-            state,
-            request_metadata,
+        let identifier = determine_table_ident(parameters.table, request.identifier.as_ref())?;
+        let namespace = resolve_default_namespace::<C>(
+            require_warehouse_id(parameters.prefix.clone())?,
+            identifier.namespace,
+            state.v1_state.catalog.clone(),
         )
         .await?;
-        let Some(item) = t.into_iter().next() else {
-            return Err(ErrorModel::internal(
-                "No new metadata returned by backend",
-                "NoNewMetadataReturned",
-                None,
-            )
-            .into());
-        };
-
-        Ok(CommitTableResponse {
-            metadata_location: item.new_metadata_location.to_string(),
-            metadata: item.new_metadata,
-            config: None,
-        })
+        request.identifier = Some(TableIdent::new(namespace, identifier.name));
+        if CONFIG.group_commit.enabled {
+            if let Some(parent_snapshot_id) = groupable_parent(&request) {
+                return commit_grouped(
+                    parameters.prefix,
+                    request,
+                    parent_snapshot_id,
+                    state,
+                    request_metadata,
+                    CONFIG.group_commit.window,
+                )
+                .await;
+            }
+        }
+        commit_table_internal(parameters.prefix, request, state, request_metadata).await
     }
 
     #[allow(clippy::too_many_lines)]
@@ -643,8 +1067,14 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         request_metadata: RequestMetadata,
     ) -> Result<()> {
         // ------------------- VALIDATIONS -------------------
-        let TableParameters { prefix, table } = parameters;
+        let TableParameters { prefix, mut table } = parameters;
         let warehouse_id = require_warehouse_id(prefix.clone())?;
+        table.namespace = resolve_default_namespace::<C>(
+            warehouse_id,
+            table.namespace,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
         validate_table_or_view_ident(&table)?;
 
         // ------------------- AUTHZ -------------------
@@ -683,6 +1113,15 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
 
+        if purge {
+            if let Some(previous) = C::load_tables(warehouse_id, [table_id], false, t.transaction())
+                .await?
+                .remove(&table_id)
+            {
+                immutable_tables::check_table_purge(&table, &previous.table_metadata)?;
+            }
+        }
+
         state
             .v1_state
             .contract_verifiers
@@ -708,6 +1147,9 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                             warehouse_ident: warehouse_id,
                             tabular_type: TabularType::Table,
                             parent_id: None,
+                            purge_after: TabularPurgeInput::purge_after(
+                                warehouse.purge_grace_period,
+                            ),
                         })
                         .await?;
 
@@ -746,7 +1188,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                     .unwrap_or_default(),
                 num_events: 1,
                 sequence_number: 0,
-                trace_id: request_metadata.request_id(),
+                trace_id: request_metadata.request_id().to_string(),
             },
             serde_json::Value::Null,
             "dropTable",
@@ -764,8 +1206,14 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         request_metadata: RequestMetadata,
     ) -> Result<()> {
         // ------------------- VALIDATIONS -------------------
-        let TableParameters { prefix, table } = parameters;
+        let TableParameters { prefix, mut table } = parameters;
         let warehouse_id = require_warehouse_id(prefix.clone())?;
+        table.namespace = resolve_default_namespace::<C>(
+            warehouse_id,
+            table.namespace,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
         validate_table_or_view_ident(&table)?;
 
         // ------------------- AUTHZ -------------------
@@ -803,9 +1251,17 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let warehouse_id = require_warehouse_id(prefix.clone())?;
         let body = maybe_body_to_json(&request);
         let RenameTableRequest {
-            source,
-            destination,
+            mut source,
+            mut destination,
         } = request;
+        for table in [&mut source, &mut destination] {
+            table.namespace = resolve_default_namespace::<C>(
+                warehouse_id,
+                table.namespace.clone(),
+                state.v1_state.catalog.clone(),
+            )
+            .await?;
+        }
         validate_table_or_view_ident(&source)?;
         validate_table_or_view_ident(&destination)?;
 
@@ -872,7 +1328,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
                 num_events: 1,
                 sequence_number: 0,
-                trace_id: request_metadata.request_id(),
+                trace_id: request_metadata.request_id().to_string(),
             },
             body,
             "renameTable",
@@ -949,6 +1405,38 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> CatalogServer<C, A, S> {
     }
 }
 
+/// Commit updates to a single table, bypassing group commits.
+pub(super) async fn commit_table_internal<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    prefix: Option<Prefix>,
+    request: CommitTableRequest,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<CommitTableResponse> {
+    let t = commit_tables_internal(
+        prefix,
+        CommitTransactionRequest {
+            table_changes: vec![request],
+        },
+        state,
+        request_metadata,
+    )
+    .await?;
+    let Some(item) = t.into_iter().next() else {
+        return Err(ErrorModel::internal(
+            "No new metadata returned by backend",
+            "NoNewMetadataReturned",
+            None,
+        )
+        .into());
+    };
+
+    Ok(CommitTableResponse {
+        metadata_location: item.new_metadata_location.to_string(),
+        metadata: item.new_metadata,
+        config: None,
+    })
+}
+
 #[allow(clippy::too_many_lines)]
 async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
     prefix: Option<Prefix>,
@@ -959,7 +1447,7 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     // ------------------- VALIDATIONS -------------------
     let warehouse_id = require_warehouse_id(prefix.clone())?;
     for change in &request.table_changes {
-        validate_table_updates(&change.updates)?;
+        validate_table_updates(&change.updates, &request_metadata)?;
         change
             .identifier
             .as_ref()
@@ -977,7 +1465,7 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     }
 
     // ------------------- AUTHZ -------------------
-    let authorizer = state.v1_state.authz;
+    let authorizer = state.v1_state.authz.clone();
     authorizer
         .require_warehouse_action(
             &request_metadata,
@@ -986,10 +1474,6 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         )
         .await?;
 
-    let include_staged = true;
-    let include_deleted = false;
-    let include_active = true;
-
     let identifiers = request
         .table_changes
         .iter()
@@ -1000,9 +1484,9 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         warehouse_id,
         identifiers,
         ListFlags {
-            include_active,
-            include_staged,
-            include_deleted,
+            include_active: true,
+            include_staged: true,
+            include_deleted: false,
         },
         state.v1_state.catalog.clone(),
     )
@@ -1042,8 +1526,56 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         .into());
     }
 
-    let mut transaction = C::Transaction::begin_write(state.v1_state.catalog).await?;
+    commit_authorized_tables(
+        prefix,
+        warehouse_id,
+        request,
+        &table_ids,
+        state,
+        request_metadata.request_id().to_string(),
+        Some(&request_metadata),
+    )
+    .await
+}
+
+/// Commit changes to tables that have already been resolved to `table_ids` and
+/// authorized. Requirements of each change are checked against the current
+/// metadata, so that concurrent commits are rejected with a conflict.
+///
+/// Also used by background maintenance tasks, which act on behalf of the catalog
+/// instead of a user. Only commits with `user_request` are subject to the commit
+/// rate limit of the warehouse and the limit on concurrent commits per table.
+/// The write-once restrictions of immutable tables apply to all commits, only the
+/// catalog may change the immutability of a table.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn commit_authorized_tables<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    prefix: Option<Prefix>,
+    warehouse_id: WarehouseIdent,
+    mut request: CommitTransactionRequest,
+    table_ids: &HashMap<TableIdent, TableIdentUuid>,
+    state: ApiContext<State<A, C, S>>,
+    trace_id: String,
+    user_request: Option<&RequestMetadata>,
+) -> Result<Vec<CommitContext>> {
+    let include_deleted = false;
+    // Held until the commit is finished. Acquired before the transaction begins,
+    // so that waiting commits do not occupy database connections.
+    let _commit_slots = match user_request {
+        Some(request_metadata) => {
+            acquire_table_commit_slots(
+                table_ids.values().copied(),
+                &CONFIG.commit_concurrency,
+                request_metadata,
+            )
+            .await?
+        }
+        None => vec![],
+    };
+    let mut transaction = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
     let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+    if let Some(request_metadata) = user_request {
+        throttle_commit(warehouse_id, warehouse.commit_rate_limit, request_metadata)?;
+    }
 
     // Store data for events before it is moved
     let mut events = vec![];
@@ -1068,6 +1600,115 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     )
     .await?;
 
+    let storage_secret =
+        maybe_get_secret::<C, _>(
+                warehouse.storage_secret_id,
+                state.v1_state.catalog.clone(),
+                &state.v1_state.secrets,
+            ).await?;
+    let file_io = warehouse.storage_profile.file_io(storage_secret.as_ref())?;
+
+    // Merge small manifests of added snapshots before the snapshots are recorded
+    let mut replaced_manifest_files = vec![];
+    if let Some(threshold) = warehouse.manifest_merge_threshold {
+        let threshold = usize::try_from(threshold).unwrap_or(usize::MAX);
+        for change in &mut request.table_changes {
+            if !change
+                .updates
+                .iter()
+                .any(|u| matches!(u, TableUpdate::AddSnapshot { .. }))
+            {
+                continue;
+            }
+            let Some(previous_table) = change
+                .identifier
+                .as_ref()
+                .and_then(|ident| table_ids.get(ident))
+                .and_then(|table_id| previous_metadatas.get(table_id))
+            else {
+                continue;
+            };
+            let new_metadata = apply_commit(
+                previous_table.table_metadata.clone(),
+                previous_table.metadata_location.as_ref(),
+                &change.requirements,
+                change.updates.clone(),
+            )?
+            .metadata;
+            let merged = merge_manifests(
+                &new_metadata,
+                std::mem::take(&mut change.updates),
+                threshold,
+                &file_io,
+            )
+            .await?;
+            change.updates = merged.updates;
+            replaced_manifest_files.extend(merged.replaced_files);
+        }
+    }
+
+    // Expire the oldest snapshots of tables that would exceed the snapshot limit.
+    // Files of expired snapshots are deleted by a cleanup task after the purge grace period.
+    let mut snapshot_cleanups = vec![];
+    if let Some(snapshot_limit) = warehouse.snapshot_limit {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        for change in &mut request.table_changes {
+            if !change
+                .updates
+                .iter()
+                .any(|u| matches!(u, TableUpdate::AddSnapshot { .. }))
+            {
+                continue;
+            }
+            let Some((table_id, previous_table)) = change
+                .identifier
+                .as_ref()
+                .and_then(|ident| table_ids.get(ident))
+                .and_then(|table_id| {
+                    previous_metadatas
+                        .get(table_id)
+                        .map(|previous_table| (*table_id, previous_table))
+                })
+                .filter(|(_, previous_table)| !previous_table.read_only)
+            else {
+                continue;
+            };
+            let new_metadata = apply_commit(
+                previous_table.table_metadata.clone(),
+                previous_table.metadata_location.as_ref(),
+                &change.requirements,
+                change.updates.clone(),
+            )?
+            .metadata;
+            let expired = snapshot_ids_exceeding_limit(
+                &snapshot_limit,
+                &new_metadata,
+                now_ms,
+                warehouse.purge_grace_period,
+            );
+            if !expired.is_empty() {
+                tracing::debug!(
+                    "Expiring {} snapshots of table {} exceeding the snapshot limit",
+                    expired.len(),
+                    new_metadata.uuid()
+                );
+                snapshot_cleanups.push(SnapshotCleanupInput {
+                    table_id,
+                    warehouse_ident: warehouse_id,
+                    snapshots: expired
+                        .iter()
+                        .filter_map(|id| new_metadata.snapshot_by_id(*id))
+                        .map(|snapshot| snapshot.as_ref().clone())
+                        .collect(),
+                    cleanup_after: TabularPurgeInput::purge_after(warehouse.purge_grace_period),
+                });
+                change.updates.push(TableUpdate::RemoveSnapshots {
+                    snapshot_ids: expired,
+                });
+            }
+        }
+    }
+
     let mut expired_metadata_logs: Vec<MetadataLog> = vec![];
 
     // Apply changes
@@ -1124,6 +1765,9 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 .saturating_sub(previous_table.table_metadata.metadata_log().len());
 
             Ok(CommitContext {
+                table_ident,
+                table_id,
+                read_only: previous_table.read_only,
                 new_metadata,
                 new_metadata_location,
                 new_compression_codec,
@@ -1135,14 +1779,75 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         })
         .collect::<Result<Vec<_>>>()?;
 
-    // Commit changes in DB
-    C::commit_table_transaction(
-        warehouse_id,
-        commits.iter().map(CommitContext::commit),
-        transaction.transaction(),
-    )
-    .await?;
-
+    for commit in &commits {
+        warehouse.schema_evolution_policy.check_table_commit(
+            &commit.table_ident,
+            &commit.previous_metadata,
+            &commit.new_metadata,
+        )?;
+        immutable_tables::check_table_commit(
+            &commit.table_ident,
+            commit.read_only,
+            &commit.previous_metadata,
+            &commit.new_metadata,
+            &commit.updates,
+            user_request.is_none(),
+        )?;
+        if user_request.is_some() {
+            table_deprecation::check_table_commit(
+                &commit.table_ident,
+                &commit.previous_metadata,
+                &commit.new_metadata,
+                chrono::Utc::now(),
+            )?;
+        }
+    }
+
+    if warehouse
+        .feature_flags
+        .is_enabled(WarehouseFeatureFlag::ValidateDataFilePaths)
+    {
+        for commit in &commits {
+            validate_added_file_paths(
+                &commit.table_ident,
+                &commit.new_metadata,
+                &commit.updates,
+                &file_io,
+            )
+            .await?;
+        }
+    }
+
+    for commit in &commits {
+        validate_added_partition_statistics(
+            &commit.table_ident,
+            &commit.new_metadata,
+            &commit.updates,
+            &file_io,
+        )
+        .await?;
+    }
+
+    for commit in &commits {
+        validate_added_file_formats(
+            &warehouse,
+            &commit.table_ident,
+            &commit.previous_metadata,
+            &commit.new_metadata,
+            &commit.updates,
+            &file_io,
+        )
+        .await?;
+    }
+
+    // Commit changes in DB
+    C::commit_table_transaction(
+        warehouse_id,
+        commits.iter().map(CommitContext::commit),
+        transaction.transaction(),
+    )
+    .await?;
+
     // Check contract verification
     let futures = commits.iter().map(|c| {
         state
@@ -1158,20 +1863,17 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         .collect::<Result<Vec<()>, ErrorModel>>()?;
 
     // We don't commit the transaction yet, first we need to write the metadata file.
-    let storage_secret =
-        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
-
-    // Write metadata files
-    let file_io = warehouse.storage_profile.file_io(storage_secret.as_ref())?;
-
+    let conditional_writer =
+        metadata_conditional_writer(&warehouse.storage_profile, storage_secret.as_ref());
     let write_futures: Vec<_> = commits
         .iter()
         .map(|commit| {
-            write_metadata_file(
+            write_new_metadata_file(
                 &commit.new_metadata_location,
                 &commit.new_metadata,
                 commit.new_compression_codec,
                 &file_io,
+                conditional_writer.as_deref(),
             )
         })
         .collect();
@@ -1179,17 +1881,51 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
 
     transaction.commit().await?;
 
+    // Queue the deletion of files of expired snapshots - failures are logged, as the
+    // commit is already persisted. Files are left behind if queueing fails.
+    for cleanup in snapshot_cleanups {
+        let table_id = cleanup.table_id;
+        if let Err(e) = state.v1_state.queues.queue_snapshot_cleanup(cleanup).await {
+            tracing::warn!(
+                "Failed to queue cleanup of expired snapshots of table {table_id}: {:?}",
+                e.error
+            );
+        }
+    }
+
+    // Notify observers - failures are logged but do not affect the already persisted commit
+    let commit_events = commits
+        .iter()
+        .map(|c| TableCommitEvent {
+            warehouse_id,
+            table_id: c.table_id,
+            table_ident: c.table_ident.clone(),
+            parent_snapshot_id: c.previous_metadata.current_snapshot_id(),
+            snapshot_id: c.new_metadata.current_snapshot_id(),
+            summary: c
+                .new_metadata
+                .current_snapshot()
+                .map(|s| s.summary().clone()),
+            principal: user_request
+                .and_then(RequestMetadata::user_id)
+                .map(ToString::to_string),
+        })
+        .collect::<Vec<_>>();
+    state
+        .v1_state
+        .commit_observers
+        .notify_table_commits(&commit_events)
+        .await;
+
     // Delete files in parallel - if one delete fails, we still want to delete the rest
     let expired_locations = expired_metadata_logs
         .into_iter()
-        .filter_map(|expired_metadata_log| {
-            Location::parse_value(&expired_metadata_log.metadata_file)
+        .map(|expired_metadata_log| expired_metadata_log.metadata_file)
+        .chain(replaced_manifest_files)
+        .filter_map(|file| {
+            Location::parse_value(&file)
                 .map_err(|e| {
-                    tracing::warn!(
-                        "Failed to parse expired metadata file location {}: {:?}",
-                        expired_metadata_log.metadata_file,
-                        e
-                    );
+                    tracing::warn!("Failed to parse expired file location {}: {:?}", file, e);
                 })
                 .ok()
         })
@@ -1203,7 +1939,7 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     .await
     .into_iter()
     .map(|r| {
-        r.map_err(|e| tracing::warn!("Failed to delete expired metadata file: {:?}", e))
+        r.map_err(|e| tracing::warn!("Failed to delete expired file: {:?}", e))
             .ok()
     });
 
@@ -1224,7 +1960,7 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                     .unwrap_or_default(),
                 num_events: number_of_events,
                 sequence_number: event_sequence_number,
-                trace_id: request_metadata.request_id(),
+                trace_id: trace_id.clone(),
             },
             body,
             "updateTable",
@@ -1272,7 +2008,10 @@ pub(crate) fn extract_count_from_metadata_location(location: &Location) -> Optio
     }
 }
 
-struct CommitContext {
+pub(crate) struct CommitContext {
+    pub table_ident: TableIdent,
+    pub table_id: TableIdentUuid,
+    pub read_only: bool,
     pub new_metadata: iceberg::spec::TableMetadata,
     pub new_metadata_location: Location,
     pub previous_metadata: iceberg::spec::TableMetadata,
@@ -1511,6 +2250,58 @@ pub(crate) fn determine_table_ident(
     Ok(identifier.clone())
 }
 
+/// Complete a stored `createTable` response with freshly vended credentials.
+#[allow(clippy::too_many_arguments)]
+async fn replay_create_table<C: Catalog, S: SecretStore>(
+    mut stored: LoadTableResult,
+    warehouse: &GetWarehouseResponse,
+    data_access: &DataAccess,
+    catalog_state: C::State,
+    secrets: &S,
+    publisher: &CloudEventsPublisher,
+    request_metadata: &RequestMetadata,
+    event_metadata: EventMetadata,
+    credential_ttl: Option<chrono::Duration>,
+    storage_region: Option<&str>,
+) -> Result<LoadTableResult> {
+    let table_location = parse_location(
+        stored.metadata.location(),
+        StatusCode::INTERNAL_SERVER_ERROR,
+    )?;
+    let credential_location = credential_location(request_metadata, &table_location)?;
+    let storage_secret = maybe_get_secret::<C, S>(warehouse.storage_secret_id, catalog_state, secrets).await?;
+    let config = warehouse
+        .storage_profile
+        .with_region(storage_region)
+        .generate_table_config(
+            data_access,
+            storage_secret.as_ref(),
+            &credential_location,
+            StoragePermissions::ReadWriteDelete,
+            credential_ttl,
+        )
+        .await?
+        .with_refresh_hint(warehouse.credential_refresh_before);
+    publish_credentials_vended_event(
+        publisher,
+        request_metadata,
+        event_metadata,
+        &credential_location,
+        StoragePermissions::ReadWriteDelete,
+        &config,
+    )
+    .await;
+
+    stored.storage_credentials = (!config.creds.inner().is_empty()).then(|| {
+        vec![StorageCredential {
+            prefix: credential_location.to_string(),
+            config: config.creds.into(),
+        }]
+    });
+    stored.config = Some(config.config.into());
+    Ok(stored)
+}
+
 pub(super) fn parse_location(location: &str, code: StatusCode) -> Result<Location> {
     Location::from_str(location)
         .map_err(|e| {
@@ -1523,19 +2314,235 @@ pub(super) fn parse_location(location: &str, code: StatusCode) -> Result<Locatio
         .map_err(Into::into)
 }
 
-pub(super) fn determine_tabular_location(
-    namespace: &GetNamespaceResponse,
-    request_table_location: Option<String>,
-    table_id: TabularIdentUuid,
+/// Team of the principal, taken from the claim configured as `openid_team_claim`.
+///
+/// The team is used verbatim as a path segment of the storage prefix. Teams with
+/// characters other than ASCII letters, digits, `-` and `_` are rejected instead of
+/// being normalized, so that distinct teams never share a storage prefix.
+pub(super) fn storage_team(request_metadata: &RequestMetadata) -> Result<Option<String>> {
+    let (Some(claim), Some(authentication)) = (
+        CONFIG.openid_team_claim.as_deref(),
+        request_metadata.authentication(),
+    ) else {
+        return Ok(None);
+    };
+    team_from_claims(authentication.claims(), claim).map(Some)
+}
+
+/// Location that vended credentials of a table are scoped to.
+///
+/// If `credential_prefix_claim` is configured and present in the token of the principal,
+/// credentials are narrowed to the sub-prefix of the table location named by the claim.
+/// Otherwise, credentials cover the whole table location.
+pub(super) fn credential_location(
+    request_metadata: &RequestMetadata,
+    table_location: &Location,
+) -> Result<Location> {
+    let (Some(claim), Some(authentication)) = (
+        CONFIG.credential_prefix_claim.as_deref(),
+        request_metadata.authentication(),
+    ) else {
+        return Ok(table_location.clone());
+    };
+    credential_location_from_claims(authentication.claims(), claim, table_location)
+}
+
+/// Lifetime of the credentials vended for a table in `namespace`, if it differs from
+/// the default of the storage profile. See [`effective_credential_ttl`].
+async fn vended_credential_ttl<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    namespace: &NamespaceIdent,
     storage_profile: &StorageProfile,
+    data_access: &DataAccess,
+    request_metadata: &RequestMetadata,
+    t: &mut C::Transaction,
+) -> Result<Option<chrono::Duration>> {
+    if !data_access.vended_credentials {
+        return Ok(None);
+    }
+    let namespace_ttl = resolve_credential_ttl::<C>(warehouse_id, namespace, t).await?;
+    Ok(effective_credential_ttl(
+        namespace_ttl,
+        request_metadata.requested_credential_ttl(),
+        storage_profile.default_credential_ttl(),
+    ))
+}
+
+/// The default credential TTL of the namespace, kept within the bounds of the server,
+/// shortened to the TTL requested by the client. `None` if neither is set, so that
+/// the default of the storage profile applies.
+fn effective_credential_ttl(
+    namespace_ttl: Option<chrono::Duration>,
+    requested_ttl: Option<chrono::Duration>,
+    storage_default: chrono::Duration,
+) -> Option<chrono::Duration> {
+    let min_ttl =
+        chrono::Duration::from_std(CONFIG.min_credential_ttl).unwrap_or(chrono::Duration::zero());
+    let max_ttl =
+        chrono::Duration::from_std(CONFIG.max_credential_ttl).unwrap_or(chrono::Duration::MAX);
+    let default_ttl = namespace_ttl.map(|ttl| ttl.clamp(min_ttl, max_ttl));
+    match requested_ttl {
+        Some(requested) => Some(
+            requested
+                .max(min_ttl)
+                .min(default_ttl.unwrap_or(storage_default)),
+        ),
+        None => default_ttl,
+    }
+}
+
+fn credential_location_from_claims(
+    claims: &serde_json::Value,
+    claim: &str,
+    table_location: &Location,
 ) -> Result<Location> {
-    let request_table_location = request_table_location
-        .map(|l| parse_location(&l, StatusCode::BAD_REQUEST))
-        .transpose()?;
+    // Multi-valued claims use their first entry
+    let value = match claims.get(claim) {
+        Some(serde_json::Value::Array(values)) => values.first(),
+        value => value,
+    };
+    let Some(sub_prefix) = value.and_then(serde_json::Value::as_str) else {
+        return Ok(table_location.clone());
+    };
 
-    let mut location = if let Some(location) = request_table_location {
-        storage_profile.require_allowed_location(&location)?;
-        location
+    let segments = sub_prefix
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let mut location = table_location.clone();
+    location.without_trailing_slash().extend(&segments);
+    // The sub-prefix may only ever narrow the credentials
+    if segments.is_empty()
+        || segments
+            .iter()
+            .any(|segment| *segment == "." || *segment == "..")
+        || location == *table_location
+        || !location.is_sublocation_of(table_location)
+    {
+        return Err(ErrorModel::forbidden(
+            format!("Token does not contain a valid sub-prefix of the table location in claim '{claim}'"),
+            "InvalidCredentialPrefixClaim",
+            None,
+        )
+        .into());
+    }
+    Ok(location)
+}
+
+fn team_from_claims(claims: &serde_json::Value, claim: &str) -> Result<String> {
+    // Multi-valued claims are only accepted if they hold a single team, as the
+    // storage prefix must not depend on the order of the entries.
+    let value = match claims.get(claim) {
+        Some(serde_json::Value::Array(values)) if values.len() > 1 => {
+            return Err(ErrorModel::forbidden(
+                format!("Token contains more than one team in claim '{claim}'"),
+                "AmbiguousTeamClaim",
+                None,
+            )
+            .into());
+        }
+        Some(serde_json::Value::Array(values)) => values.first(),
+        value => value,
+    };
+    let Some(team) = value
+        .and_then(serde_json::Value::as_str)
+        .filter(|team| !team.is_empty())
+    else {
+        return Err(ErrorModel::forbidden(
+            format!("Token does not contain a valid team in claim '{claim}'"),
+            "TeamClaimMissing",
+            None,
+        )
+        .into());
+    };
+
+    if !team
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ErrorModel::forbidden(
+            format!("Team in claim '{claim}' may only contain ASCII letters, digits, '-' and '_'"),
+            "InvalidTeamClaim",
+            None,
+        )
+        .into());
+    }
+    Ok(team.to_string())
+}
+
+/// Resolve the location below which a table created by the caller in `namespace`
+/// is placed if no location is requested. The same rules as on creation apply,
+/// the table itself receives a folder named after its ID inside this location.
+pub(crate) async fn default_table_parent_location<C: Catalog>(
+    namespace: &GetNamespaceResponse,
+    warehouse: &GetWarehouseResponse,
+    request_metadata: &RequestMetadata,
+    t: &mut C::Transaction,
+) -> Result<Location> {
+    let team = storage_team(request_metadata)?;
+    let storage_prefix = resolve_storage_prefix::<C>(namespace, t)
+        .await?
+        .map(|p| p.location);
+    if let Some(storage_prefix) = &storage_prefix {
+        warehouse.require_allowed_storage_prefix(storage_prefix)?;
+    }
+    let team_location = team_location(warehouse, team.as_deref(), storage_prefix.as_ref())?;
+    let mut location = default_tabular_parent_location(
+        namespace,
+        warehouse,
+        team_location,
+        storage_prefix.as_ref(),
+    )?;
+    location.without_trailing_slash();
+    Ok(location)
+}
+
+fn team_location(
+    warehouse: &GetWarehouseResponse,
+    team: Option<&str>,
+    storage_prefix: Option<&Location>,
+) -> Result<Option<Location>> {
+    team.map(|team| match storage_prefix {
+        Some(storage_prefix) => {
+            let mut team_location = storage_prefix.clone();
+            team_location
+                .without_trailing_slash()
+                .push(TEAM_LOCATION_SEGMENT)
+                .push(team);
+            Ok(team_location)
+        }
+        None => warehouse
+            .storage_profile
+            .default_team_location(team)
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to generate team location",
+                    "InvalidTeamLocation",
+                    Some(Box::new(e)),
+                )
+                .into()
+            }),
+    })
+    .transpose()
+}
+
+/// Location below which tabulars without a requested location are placed.
+fn default_tabular_parent_location(
+    namespace: &GetNamespaceResponse,
+    warehouse: &GetWarehouseResponse,
+    team_location: Option<Location>,
+    storage_prefix: Option<&Location>,
+) -> Result<Location> {
+    if let Some(mut team_location) = team_location {
+        // Team locations take precedence over namespace locations to keep teams isolated.
+        team_location.push(&namespace.namespace_id.to_string());
+        Ok(team_location)
+    } else if let Some(storage_prefix) = storage_prefix {
+        let mut namespace_location = storage_prefix.clone();
+        namespace_location
+            .without_trailing_slash()
+            .push(&namespace.namespace_id.to_string());
+        Ok(namespace_location)
     } else {
         let namespace_props = NamespaceProperties::from_props_unchecked(
             namespace.properties.clone().unwrap_or_default(),
@@ -1543,7 +2550,8 @@ pub(super) fn determine_tabular_location(
 
         let namespace_location = match namespace_props.get_location() {
             Some(location) => location,
-            None => storage_profile
+            None => warehouse
+                .storage_profile
                 .default_namespace_location(namespace.namespace_id)
                 .map_err(|e| {
                     ErrorModel::internal(
@@ -1554,13 +2562,138 @@ pub(super) fn determine_tabular_location(
                 })?,
         };
 
-        storage_profile.default_tabular_location(&namespace_location, table_id)
+        // The namespace location might use a scheme that is no longer allowed.
+        warehouse.require_allowed_scheme(&namespace_location)?;
+        Ok(namespace_location)
+    }
+}
+
+pub(super) fn determine_tabular_location(
+    namespace: &GetNamespaceResponse,
+    request_table_location: Option<String>,
+    table_id: TabularIdentUuid,
+    warehouse: &GetWarehouseResponse,
+    team: Option<&str>,
+    storage_prefix: Option<&Location>,
+) -> Result<Location> {
+    let request_table_location = request_table_location
+        .map(|l| parse_location(&l, StatusCode::BAD_REQUEST))
+        .transpose()?;
+    if let Some(storage_prefix) = storage_prefix {
+        warehouse.require_allowed_storage_prefix(storage_prefix)?;
+    }
+    let team_location = team_location(warehouse, team, storage_prefix)?;
+
+    let mut location = if let Some(location) = request_table_location {
+        require_allowed_tabular_location(warehouse, storage_prefix, &location)?;
+        if let Some(team_location) = &team_location {
+            if !location.is_sublocation_of(team_location) {
+                return Err(ErrorModel::forbidden(
+                    format!(
+                        "Location '{location}' is outside of the team location '{team_location}'"
+                    ),
+                    "LocationOutsideTeamLocation",
+                    None,
+                )
+                .into());
+            }
+        }
+        location
+    } else {
+        let parent_location =
+            default_tabular_parent_location(namespace, warehouse, team_location, storage_prefix)?;
+        warehouse
+            .storage_profile
+            .default_tabular_location(&parent_location, table_id)
     };
     // all locations are without a trailing slash
     location.without_trailing_slash();
     Ok(location)
 }
 
+/// Require that `location` may hold a tabular. Namespaces with a storage prefix
+/// keep all of their tabulars below that prefix, all other tabulars must be placed
+/// below the base location of the storage profile.
+pub(super) fn require_allowed_tabular_location(
+    warehouse: &GetWarehouseResponse,
+    storage_prefix: Option<&Location>,
+    location: &Location,
+) -> Result<()> {
+    warehouse.require_allowed_scheme(location)?;
+    let Some(storage_prefix) = storage_prefix else {
+        return warehouse
+            .storage_profile
+            .require_allowed_location(location)
+            .map_err(Into::into);
+    };
+
+    let mut prefix_folder = storage_prefix.clone();
+    prefix_folder.with_trailing_slash();
+    if location == &prefix_folder || !location.is_sublocation_of(&prefix_folder) {
+        return Err(ErrorModel::forbidden(
+            format!("Location '{location}' is outside of the namespace storage prefix '{storage_prefix}'"),
+            "LocationOutsideNamespaceStoragePrefix",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Fail with a conflict if the namespace already contains the maximum number of
+/// tables allowed by the warehouse. Views only count if the warehouse is configured so.
+/// Require that another tabular may be added to the namespace. Warns via
+/// `request_metadata` if the namespace approaches the limit.
+pub(super) async fn require_namespace_capacity<C: Catalog>(
+    warehouse: &GetWarehouseResponse,
+    namespace_id: NamespaceIdentUuid,
+    is_view: bool,
+    request_metadata: &RequestMetadata,
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<()> {
+    let Some(max_tables) = warehouse.max_tables_per_namespace else {
+        return Ok(());
+    };
+    let includes_views = warehouse.max_tables_per_namespace_includes_views;
+    if is_view && !includes_views {
+        return Ok(());
+    }
+
+    let counts = C::count_tabulars_in_namespace(namespace_id, transaction).await?;
+    let count = if includes_views {
+        counts.tables + counts.views
+    } else {
+        counts.tables
+    };
+    let counted = if includes_views {
+        "tables and views"
+    } else {
+        "tables"
+    };
+    if count >= i64::from(max_tables) {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Namespace already contains {count} {counted}, which is the maximum of {max_tables} allowed by the warehouse."
+            ),
+            "MaxTablesPerNamespaceExceeded",
+            None,
+        )
+        .into());
+    }
+
+    let new_count = u64::try_from(count + 1).unwrap_or_default();
+    if CONFIG
+        .soft_limits
+        .is_reached(new_count, u64::from(max_tables))
+    {
+        request_metadata.add_warning(format!(
+            "Namespace contains {new_count} {counted}, approaching the maximum of {max_tables} allowed by the warehouse."
+        ));
+    }
+
+    Ok(())
+}
+
 fn require_table_id(
     table_ident: &TableIdent,
     table_id: Option<TableIdentUuid>,
@@ -1638,11 +2771,18 @@ async fn emit_change_event(
 
 // Quick validation of properties for early fails.
 // Full validation is performed when changes are applied.
-fn validate_table_updates(updates: &Vec<TableUpdate>) -> Result<()> {
+fn validate_table_updates(
+    updates: &Vec<TableUpdate>,
+    request_metadata: &RequestMetadata,
+) -> Result<()> {
     for update in updates {
         match update {
             TableUpdate::SetProperties { updates } => {
                 validate_table_properties(updates.keys())?;
+                validate_table_description(
+                    updates.get(PROPERTY_TABLE_DESCRIPTION),
+                    request_metadata,
+                )?;
             }
             TableUpdate::RemoveProperties { removals } => {
                 validate_table_properties(removals)?;
@@ -1687,6 +2827,36 @@ where
     Ok(())
 }
 
+/// Require that `description` does not exceed [`MAX_TABLE_DESCRIPTION_LENGTH`].
+/// Warns via `request_metadata` if it approaches the limit.
+pub(crate) fn validate_table_description(
+    description: Option<&String>,
+    request_metadata: &RequestMetadata,
+) -> Result<()> {
+    let Some(length) = description.map(|d| d.chars().count()) else {
+        return Ok(());
+    };
+    if length > MAX_TABLE_DESCRIPTION_LENGTH {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Table description must not be longer than {MAX_TABLE_DESCRIPTION_LENGTH} characters"
+            ),
+            "TableDescriptionTooLong",
+            None,
+        )
+        .into());
+    }
+    if CONFIG
+        .soft_limits
+        .is_reached(length as u64, MAX_TABLE_DESCRIPTION_LENGTH as u64)
+    {
+        request_metadata.add_warning(format!(
+            "Table description has {length} characters, approaching the maximum of {MAX_TABLE_DESCRIPTION_LENGTH}."
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) fn validate_table_or_view_ident(table: &TableIdent) -> Result<()> {
     let TableIdent {
         ref namespace,
@@ -1782,22 +2952,43 @@ pub(crate) fn create_table_request_into_table_metadata(
     Ok(table_metadata)
 }
 
+/// Replace the UUID of registered table metadata whose UUID is already in use.
+fn assign_new_table_uuid(table_metadata: TableMetadata) -> Result<TableMetadata> {
+    Ok(
+        TableMetadataBuilder::new_from_metadata(table_metadata, None)
+            .assign_uuid(Uuid::now_v7())
+            .build()
+            .map_err(|e| {
+                let msg = e.message().to_string();
+                ErrorModel::bad_request(msg, "BuildTableMetadataError", Some(Box::new(e)))
+            })?
+            .metadata,
+    )
+}
+
 #[cfg(test)]
 pub(crate) mod test {
-    use std::{collections::HashMap, str::FromStr};
+    use std::{
+        collections::{HashMap, HashSet},
+        str::FromStr,
+        sync::Arc,
+    };
 
     use http::StatusCode;
     use iceberg::{
         spec::{
-            NestedField, Operation, PrimitiveType, Schema, Snapshot, SnapshotReference,
-            SnapshotRetention, Summary, TableMetadata, Transform, Type, UnboundPartitionField,
+            DataContentType, DataFileBuilder, DataFileFormat, ManifestEntry, ManifestListWriter,
+            ManifestStatus, ManifestWriterBuilder, NestedField, Operation, PartitionStatisticsFile,
+            PrimitiveType, Schema, Snapshot, SnapshotReference, SnapshotRetention, Struct, Summary,
+            TableMetadata, TableMetadataBuilder, Transform, Type, UnboundPartitionField,
             UnboundPartitionSpec, MAIN_BRANCH, PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX,
         },
-        TableIdent,
+        TableIdent, TableRequirement, TableUpdate,
     };
     use iceberg_ext::{
         catalog::rest::{
-            CommitTableRequest, CreateNamespaceResponse, CreateTableRequest, LoadTableResult,
+            CommitTableRequest, CreateNamespaceRequest, CreateNamespaceResponse,
+            CreateTableRequest, LoadTableResult,
         },
         configs::Location,
     };
@@ -1808,25 +2999,69 @@ pub(crate) mod test {
     use crate::{
         api::{
             iceberg::{
-                types::{PageToken, Prefix},
+                types::{DropParams, PageToken, Prefix},
                 v1::{
-                    tables::TablesService as _, DataAccess, ListTablesQuery, NamespaceParameters,
-                    TableParameters,
+                    namespace::Service as _,
+                    tables::{LoadTableFields, TablesService as _},
+                    DataAccess, ListNamespacesQuery, ListTablesQuery, LoadTableQuery,
+                    NamespaceParameters, PaginationQuery, RegisterTableRequest, TableParameters,
+                },
+            },
+            management::v1::{
+                table::{
+                    Service as _, SetTableDeprecationRequest, TableFilesQuery, TableHistoryQuery,
+                    TableStorageFootprint,
+                },
+                warehouse::{
+                    PreviewTableLocationRequest, Service as _, SetNamespaceCredentialTtlRequest,
+                    SetNamespaceStoragePrefixRequest, TabularDeleteProfile,
+                    UpdateWarehouseAllowedFileFormatsRequest,
+                    UpdateWarehouseCredentialRefreshRequest,
+                    UpdateWarehouseDefaultNamespaceRequest, UpdateWarehouseFeatureFlagsRequest,
+                    UpdateWarehouseImportPrefixesRequest, UpdateWarehouseManifestMergeRequest,
+                    UpdateWarehouseMaxTablesPerNamespaceRequest,
+                    UpdateWarehouseSchemaEvolutionPolicyRequest,
+                    UpdateWarehouseSnapshotLimitRequest,
+                    UpdateWarehouseStoragePrefixOverridesRequest,
                 },
+                ApiServer,
             },
-            management::v1::warehouse::TabularDeleteProfile,
             ApiContext,
         },
-        catalog::{tables::validate_table_properties, test::impl_pagination_tests, CatalogServer},
-        implementations::postgres::{PostgresCatalog, SecretsState},
+        catalog::{
+            compression_codec::CompressionCodec, immutable_tables::PROPERTY_IMMUTABLE,
+            io::write_metadata_file, manifest_merge::PROPERTY_SKIP_MANIFEST_MERGE,
+            table_deprecation, tables::validate_table_properties, test::impl_pagination_tests,
+            CatalogServer,
+        },
+        implementations::postgres::{
+            PostgresCatalog, PostgresTransaction, ReadWrite, SecretsState,
+        },
         request_metadata::RequestMetadata,
         service::{
             authz::{
                 implementations::openfga::{tests::ObjectHidingMock, OpenFGAAuthorizer},
                 AllowAllAuthorizer,
             },
-            State, UserId,
+            commit_observer::{
+                CommitAttributionObserver, CommitObserver, CommitObservers, TableCommitEvent,
+            },
+            create_request_mutator::{
+                CreateRequestMutator, CreateRequestMutators, CreateTableMutation,
+            },
+            feature_flags::WarehouseFeatureFlags,
+            namespace_property_schema::NamespacePropertySchema,
+            schema_evolution::SchemaEvolutionPolicy,
+            snapshot_limit::SnapshotLimit,
+            storage::{S3Profile, CREDENTIALS_REFRESH_BEFORE_MS},
+            task_queue::{
+                snapshot_cleanup_queue::{instrumented_cleanup, SnapshotCleanupQueue},
+                TaskQueue as _, TaskQueueConfig,
+            },
+            Catalog as _, ErrorModel, GetNamespaceResponse, NamespaceIdentUuid, State,
+            TabularIdentUuid, Transaction as _, UserId, WarehouseStatus,
         },
+        WarehouseIdent,
     };
 
     #[test]
@@ -1955,6 +3190,7 @@ pub(crate) mod test {
                     name: "tab-1".to_string(),
                 },
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2110,6 +3346,7 @@ pub(crate) mod test {
                     name: "tab-1".to_string(),
                 },
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2171,6 +3408,7 @@ pub(crate) mod test {
                     name: "tab-1".to_string(),
                 },
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2251,6 +3489,7 @@ pub(crate) mod test {
                     name: "tab-1".to_string(),
                 },
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2297,6 +3536,7 @@ pub(crate) mod test {
                 prefix: ns_params.prefix.clone(),
                 table: table_ident.clone(),
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2339,6 +3579,7 @@ pub(crate) mod test {
                 prefix: ns_params.prefix.clone(),
                 table: table_ident.clone(),
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2382,6 +3623,7 @@ pub(crate) mod test {
                 prefix: ns_params.prefix,
                 table: table_ident.clone(),
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2460,6 +3702,7 @@ pub(crate) mod test {
                 prefix: ns_params.prefix.clone(),
                 table: table_ident.clone(),
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2522,6 +3765,7 @@ pub(crate) mod test {
                 prefix: ns_params.prefix.clone(),
                 table: table_ident.clone(),
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2579,6 +3823,7 @@ pub(crate) mod test {
                 prefix: ns_params.prefix.clone(),
                 table: table_ident.clone(),
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2617,6 +3862,7 @@ pub(crate) mod test {
                 prefix: ns_params.prefix.clone(),
                 table: table_ident.clone(),
             },
+            LoadTableQuery::default(),
             DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -2638,39 +3884,243 @@ pub(crate) mod test {
         assert_table_metadata_are_equal(&builder.metadata, &tab.metadata);
     }
 
-    async fn commit_test_setup(
-        pool: PgPool,
-    ) -> (
-        ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
-        CreateNamespaceResponse,
-        NamespaceParameters,
-        LoadTableResult,
-    ) {
-        let (ctx, ns, ns_params, _) = table_test_setup(pool).await;
-        let table = CatalogServer::create_table(
-            ns_params.clone(),
-            create_request(Some("tab-1".to_string())),
-            DataAccess {
-                vended_credentials: true,
-                remote_signing: false,
+    #[sqlx::test]
+    async fn test_load_table_minimal_fields(pool: PgPool) {
+        let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
+        let table_ident = TableIdent {
+            namespace: ns.namespace.clone(),
+            name: "tab-1".to_string(),
+        };
+
+        let mut metadata = table.metadata;
+        let mut metadata_location = table.metadata_location;
+        for snapshot_id in [1, 2] {
+            let snap = Snapshot::builder()
+                .with_snapshot_id(snapshot_id)
+                .with_parent_snapshot_id((snapshot_id > 1).then_some(snapshot_id - 1))
+                .with_timestamp_ms(metadata.last_updated_ms() + 1)
+                .with_sequence_number(snapshot_id)
+                .with_schema_id(0)
+                .with_manifest_list(format!("/snap-{snapshot_id}.avro"))
+                .with_summary(Summary {
+                    operation: Operation::Append,
+                    additional_properties: HashMap::new(),
+                })
+                .build();
+            let builder = metadata
+                .into_builder(metadata_location)
+                .add_snapshot(snap)
+                .unwrap()
+                .set_ref(
+                    MAIN_BRANCH,
+                    SnapshotReference {
+                        snapshot_id,
+                        retention: SnapshotRetention::Branch {
+                            min_snapshots_to_keep: None,
+                            max_snapshot_age_ms: None,
+                            max_ref_age_ms: None,
+                        },
+                    },
+                )
+                .unwrap()
+                .build()
+                .unwrap();
+            super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(table_ident.clone()),
+                        requirements: vec![],
+                        updates: builder.changes,
+                    }],
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            let loaded = CatalogServer::load_table(
+                TableParameters {
+                    prefix: ns_params.prefix.clone(),
+                    table: table_ident.clone(),
+                },
+                LoadTableQuery::default(),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            metadata = loaded.metadata;
+            metadata_location = loaded.metadata_location;
+        }
+        assert_eq!(metadata.snapshots().count(), 2);
+        assert_eq!(metadata.history().len(), 2);
+
+        let minimal = CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: table_ident.clone(),
             },
+            LoadTableQuery {
+                fields: LoadTableFields::Minimal,
+            },
+            DataAccess::none(),
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
         )
         .await
         .unwrap();
-        (ctx, ns, ns_params, table)
+        assert_eq!(minimal.metadata_location, metadata_location);
+        let minimal = minimal.metadata;
+        assert_eq!(
+            minimal.snapshots().map(|s| s.snapshot_id()).collect_vec(),
+            vec![2]
+        );
+        assert_eq!(minimal.current_snapshot(), metadata.current_snapshot());
+        assert!(minimal.snapshot_by_id(1).is_none());
+        assert!(minimal.history().iter().all(|l| l.snapshot_id == 2));
+        assert!(minimal.metadata_log().is_empty());
+        assert_eq!(minimal.current_schema(), metadata.current_schema());
+        assert_eq!(minimal.schemas_iter().count(), 1);
+        assert_eq!(
+            minimal.default_partition_spec(),
+            metadata.default_partition_spec()
+        );
+        assert_eq!(minimal.default_sort_order(), metadata.default_sort_order());
     }
 
-    async fn table_test_setup(
-        pool: PgPool,
-    ) -> (
-        ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
-        CreateNamespaceResponse,
-        NamespaceParameters,
-        String,
-    ) {
-        let prof = crate::catalog::test::test_io_profile();
+    #[sqlx::test]
+    async fn test_out_of_band_metadata_writes_do_not_change_loaded_table(pool: PgPool) {
+        let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
+        let table_ident = TableIdent {
+            namespace: ns.namespace.clone(),
+            name: "tab-1".to_string(),
+        };
+        let table_params = TableParameters {
+            prefix: ns_params.prefix.clone(),
+            table: table_ident.clone(),
+        };
+        let load = || {
+            CatalogServer::load_table(
+                table_params.clone(),
+                LoadTableQuery::default(),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        // An external writer places a newer metadata file and a version hint
+        // next to the metadata of the table.
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let external_metadata = table
+            .metadata
+            .clone()
+            .into_builder(table.metadata_location.clone())
+            .set_properties(HashMap::from([(
+                "written-by".to_string(),
+                "external".to_string(),
+            )]))
+            .unwrap()
+            .build()
+            .unwrap()
+            .metadata;
+        let external_location = Location::from_str(&format!(
+            "{}/metadata/99999-{}.metadata.json",
+            table.metadata.location(),
+            Uuid::now_v7()
+        ))
+        .unwrap();
+        crate::catalog::io::write_metadata_file(
+            &external_location,
+            &external_metadata,
+            crate::catalog::compression_codec::CompressionCodec::None,
+            &file_io,
+        )
+        .await
+        .unwrap();
+        file_io
+            .new_output(format!(
+                "{}/metadata/version-hint.text",
+                table.metadata.location()
+            ))
+            .unwrap()
+            .write("99999".into())
+            .await
+            .unwrap();
+
+        let loaded = load().await.unwrap();
+        assert_eq!(loaded.metadata_location, table.metadata_location);
+        assert!(!loaded.metadata.properties().contains_key("written-by"));
+
+        // Commits through the catalog move the pointer, the external file is ignored
+        super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![CommitTableRequest {
+                    identifier: Some(table_ident.clone()),
+                    requirements: vec![],
+                    updates: vec![TableUpdate::SetProperties {
+                        updates: HashMap::from([("owner".to_string(), "catalog".to_string())]),
+                    }],
+                }],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let loaded = load().await.unwrap();
+        let metadata_location = loaded.metadata_location.unwrap();
+        assert_ne!(Some(&metadata_location), table.metadata_location.as_ref());
+        assert_ne!(metadata_location, external_location.to_string());
+        assert_eq!(
+            loaded
+                .metadata
+                .properties()
+                .get("owner")
+                .map(String::as_str),
+            Some("catalog")
+        );
+        assert!(!loaded.metadata.properties().contains_key("written-by"));
+    }
+
+    async fn commit_test_setup(
+        pool: PgPool,
+    ) -> (
+        ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+        CreateNamespaceResponse,
+        NamespaceParameters,
+        LoadTableResult,
+    ) {
+        let (ctx, ns, ns_params, _) = table_test_setup(pool).await;
+        let table = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request(Some("tab-1".to_string())),
+            DataAccess {
+                vended_credentials: true,
+                remote_signing: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        (ctx, ns, ns_params, table)
+    }
+
+    async fn table_test_setup(
+        pool: PgPool,
+    ) -> (
+        ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+        CreateNamespaceResponse,
+        NamespaceParameters,
+        String,
+    ) {
+        let prof = crate::catalog::test::test_io_profile();
         let base_loc = prof.base_location().unwrap().to_string();
         let (ctx, warehouse) = crate::catalog::test::setup(
             pool.clone(),
@@ -2754,6 +4204,45 @@ pub(crate) mod test {
         .unwrap();
     }
 
+    #[sqlx::test]
+    async fn test_default_location_does_not_depend_on_names(pool: PgPool) {
+        let (ctx, _, ns_params, base_location) = table_test_setup(pool).await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            ns_params.prefix.clone().unwrap().into_string(),
+            "sales data".to_string(),
+        )
+        .await;
+        let ns_params = NamespaceParameters {
+            prefix: ns_params.prefix,
+            namespace: ns.namespace,
+        };
+        let table = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request(Some("daily report/v1 ?#%".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // Locations are built from the ids of the namespace and table, so names never need to be encoded
+        let location = Location::from_str(table.metadata.location()).unwrap();
+        assert_eq!(location.to_string(), table.metadata.location());
+        assert!(location.is_sublocation_of(&Location::from_str(&base_location).unwrap()));
+        let segments = location
+            .as_str()
+            .trim_start_matches(base_location.trim_end_matches('/'))
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+        assert_eq!(segments.len(), 2, "{location}");
+        for segment in segments {
+            Uuid::parse_str(segment).unwrap();
+        }
+    }
+
     #[sqlx::test]
     async fn test_cannot_create_table_at_same_location(pool: PgPool) {
         let (ctx, _, ns_params, base_location) = table_test_setup(pool).await;
@@ -2996,6 +4485,7 @@ pub(crate) mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(11),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3011,6 +4501,7 @@ pub(crate) mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3026,6 +4517,7 @@ pub(crate) mod test {
                 page_token: PageToken::Present(all.next_page_token.unwrap()),
                 page_size: Some(10),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3042,6 +4534,7 @@ pub(crate) mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(6),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3067,6 +4560,7 @@ pub(crate) mod test {
                 page_token: PageToken::Present(first_six.next_page_token.unwrap()),
                 page_size: Some(6),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3100,6 +4594,7 @@ pub(crate) mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(5),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3126,6 +4621,7 @@ pub(crate) mod test {
                 page_token: PageToken::Present(page.next_page_token.unwrap()),
                 page_size: Some(6),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -3146,4 +4642,3061 @@ pub(crate) mod test {
             assert_eq!(next_page_items[idx], format!("tab-{i}"));
         }
     }
+
+    #[sqlx::test]
+    async fn test_list_tables_by_name_prefix(pool: sqlx::PgPool) {
+        let prof = crate::catalog::test::test_io_profile();
+
+        let hiding_mock = ObjectHidingMock::new();
+        let authz = hiding_mock.to_authorizer();
+
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            prof,
+            None,
+            authz,
+            TabularDeleteProfile::Hard {},
+            Some(UserId::new_unchecked("oidc", "test-user-id")),
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let ns_params = NamespaceParameters {
+            prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+            namespace: ns.namespace.clone(),
+        };
+        // `_` in the prefix must not act as a wildcard, so `ordersx` is not listed
+        let mut names = vec!["ordersx".to_string()];
+        for i in 0..5 {
+            names.push(format!("orders_{i}"));
+            names.push(format!("returns_{i}"));
+        }
+        let mut hidden = None;
+        for name in names {
+            let table = CatalogServer::create_table(
+                ns_params.clone(),
+                create_request(Some(name.clone())),
+                DataAccess {
+                    vended_credentials: true,
+                    remote_signing: false,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            if name == "orders_1" {
+                hidden = Some(table.metadata.uuid());
+            }
+        }
+        // Authorization still applies to tables matching the prefix
+        hiding_mock.hide(&format!("table:{}", hidden.unwrap()));
+
+        let mut listed = vec![];
+        let mut page_token = PageToken::NotSpecified;
+        let mut pages = 0;
+        loop {
+            let page = CatalogServer::list_tables(
+                ns_params.clone(),
+                ListTablesQuery {
+                    page_token,
+                    page_size: Some(2),
+                    return_uuids: false,
+                    name_prefix: Some("orders_".to_string()),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            pages += 1;
+            assert!(page.identifiers.len() <= 2);
+            listed.extend(page.identifiers.into_iter().map(|i| i.name));
+            match page.next_page_token {
+                Some(token) => page_token = PageToken::Present(token),
+                None => break,
+            }
+        }
+
+        assert!(pages >= 2);
+        assert_eq!(listed, vec!["orders_0", "orders_2", "orders_3", "orders_4"]);
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        commits: std::sync::Mutex<Vec<TableCommitEvent>>,
+    }
+
+    #[async_trait::async_trait]
+    impl CommitObserver for RecordingObserver {
+        fn name(&self) -> &'static str {
+            "RecordingObserver"
+        }
+
+        async fn on_table_commit(&self, commit: &TableCommitEvent) -> Result<(), ErrorModel> {
+            self.commits.lock().unwrap().push(commit.clone());
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct FailingObserver;
+
+    #[async_trait::async_trait]
+    impl CommitObserver for FailingObserver {
+        fn name(&self) -> &'static str {
+            "FailingObserver"
+        }
+
+        async fn on_table_commit(&self, _commit: &TableCommitEvent) -> Result<(), ErrorModel> {
+            Err(ErrorModel::internal(
+                "Observer failed",
+                "ObserverFailed",
+                None,
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_commit_observer_receives_snapshot_ids(pg_pool: PgPool) {
+        let (mut ctx, ns, ns_params, table) = commit_test_setup(pg_pool).await;
+        let observer = Arc::new(RecordingObserver::default());
+        ctx.v1_state.commit_observers =
+            CommitObservers::new(vec![Arc::new(FailingObserver), observer.clone()]);
+
+        let table_ident = TableIdent {
+            namespace: ns.namespace.clone(),
+            name: "tab-1".to_string(),
+        };
+        let mut metadata = table.metadata;
+        let mut metadata_location = table.metadata_location;
+
+        for (snapshot_id, parent_snapshot_id) in [(1, None), (2, Some(1))] {
+            let snap = Snapshot::builder()
+                .with_snapshot_id(snapshot_id)
+                .with_parent_snapshot_id(parent_snapshot_id)
+                .with_timestamp_ms(metadata.last_updated_ms() + 1)
+                .with_sequence_number(snapshot_id - 1)
+                .with_schema_id(0)
+                .with_manifest_list(format!("/snap-{snapshot_id}.avro"))
+                .with_summary(Summary {
+                    operation: Operation::Append,
+                    additional_properties: HashMap::from_iter(vec![(
+                        "added-data-files".to_string(),
+                        "1".to_string(),
+                    )]),
+                })
+                .build();
+
+            let builder = metadata
+                .into_builder(metadata_location)
+                .add_snapshot(snap)
+                .unwrap()
+                .set_ref(
+                    MAIN_BRANCH,
+                    SnapshotReference {
+                        snapshot_id,
+                        retention: SnapshotRetention::Branch {
+                            min_snapshots_to_keep: None,
+                            max_snapshot_age_ms: None,
+                            max_ref_age_ms: None,
+                        },
+                    },
+                )
+                .unwrap()
+                .build()
+                .unwrap();
+
+            // A failing observer must not fail the commit
+            super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(table_ident.clone()),
+                        requirements: vec![],
+                        updates: builder.changes,
+                    }],
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+
+            let tab = CatalogServer::load_table(
+                TableParameters {
+                    prefix: ns_params.prefix.clone(),
+                    table: table_ident.clone(),
+                },
+                LoadTableQuery::default(),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(tab.metadata.current_snapshot_id(), Some(snapshot_id));
+            metadata = tab.metadata;
+            metadata_location = tab.metadata_location;
+        }
+
+        let commits = observer.commits.lock().unwrap().clone();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].table_ident, table_ident);
+        assert_eq!(commits[0].parent_snapshot_id, None);
+        assert_eq!(commits[0].snapshot_id, Some(1));
+        assert_eq!(commits[1].parent_snapshot_id, Some(1));
+        assert_eq!(commits[1].snapshot_id, Some(2));
+        assert_eq!(
+            commits[1].summary.as_ref().map(|s| s.operation.clone()),
+            Some(Operation::Append)
+        );
+    }
+
+    /// Stamps the organization of the requesting principal on new tables and
+    /// places them in a folder of that organization.
+    #[derive(Debug)]
+    struct OrgMutator {
+        orgs: HashMap<String, String>,
+    }
+
+    #[async_trait::async_trait]
+    impl CreateRequestMutator for OrgMutator {
+        fn name(&self) -> &'static str {
+            "OrgMutator"
+        }
+
+        async fn mutate_create_table(
+            &self,
+            _request: &CreateTableRequest,
+            _table: &TableIdent,
+            request_metadata: &RequestMetadata,
+        ) -> Result<CreateTableMutation, ErrorModel> {
+            let Some(org) = request_metadata
+                .user_id()
+                .and_then(|user_id| self.orgs.get(&user_id.to_string()))
+            else {
+                return Ok(CreateTableMutation::default());
+            };
+            Ok(CreateTableMutation {
+                properties: HashMap::from([
+                    ("org".to_string(), org.clone()),
+                    ("owner".to_string(), format!("{org}-admins")),
+                ]),
+                location_sub_prefix: Some(format!("org-{org}")),
+            })
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_create_request_mutator_stamps_org_of_principal(pool: PgPool) {
+        let (mut ctx, _, ns_params, _) = table_test_setup(pool).await;
+        let user_id = UserId::new_unchecked("oidc", "alice");
+        ctx.v1_state.create_request_mutators =
+            CreateRequestMutators::new(vec![Arc::new(OrgMutator {
+                orgs: HashMap::from([(user_id.to_string(), "acme".to_string())]),
+            })]);
+
+        let mut request = create_request(Some("tab-1".to_string()));
+        request.properties = Some(HashMap::from([("owner".to_string(), "alice".to_string())]));
+        let table = CatalogServer::create_table(
+            ns_params.clone(),
+            request,
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::random_human(user_id),
+        )
+        .await
+        .unwrap();
+        let properties = table.metadata.properties();
+        assert_eq!(properties.get("org").map(String::as_str), Some("acme"));
+        // User-specified properties are never overwritten
+        assert_eq!(properties.get("owner").map(String::as_str), Some("alice"));
+        let location = Location::from_str(table.metadata.location()).unwrap();
+        assert_eq!(
+            location.url().path_segments().unwrap().nth_back(1),
+            Some("org-acme")
+        );
+
+        // Principals without an organization are not affected
+        let table = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request(Some("tab-2".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::random_human(UserId::new_unchecked("oidc", "bob")),
+        )
+        .await
+        .unwrap();
+        assert!(!table.metadata.properties().contains_key("org"));
+        assert!(!table.metadata.location().contains("org-"));
+    }
+
+    #[sqlx::test]
+    async fn test_idempotent_create_table(pool: PgPool) {
+        let (ctx, _, ns_params, _) = table_test_setup(pool.clone()).await;
+        let user_id = UserId::new_unchecked("oidc", "test-user");
+        let request_metadata =
+            RequestMetadata::random_human(user_id).with_idempotency_key("create-tab-1");
+
+        let create = |request_metadata: RequestMetadata| {
+            CatalogServer::create_table(
+                ns_params.clone(),
+                create_request(Some("tab-1".to_string())),
+                DataAccess::none(),
+                ctx.clone(),
+                request_metadata,
+            )
+        };
+        let first = create(request_metadata.clone()).await.unwrap();
+        // The retry returns the stored result instead of failing with a conflict
+        let second = create(request_metadata).await.unwrap();
+        assert_eq!(first, second);
+
+        let tables = CatalogServer::list_tables(
+            ns_params.clone(),
+            ListTablesQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: None,
+                return_uuids: true,
+                name_prefix: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tables.identifiers.len(), 1);
+
+        let stored: i64 = sqlx::query_scalar("SELECT count(*) FROM idempotency_key")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, 1);
+
+        // Keys are scoped per principal, another user re-executes the request
+        let err = create(
+            RequestMetadata::random_human(UserId::new_unchecked("oidc", "other-user"))
+                .with_idempotency_key("create-tab-1"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_location_scheme_must_be_allowed_for_warehouse() {
+        let warehouse = crate::service::GetWarehouseResponse {
+            id: WarehouseIdent::from(Uuid::now_v7()),
+            name: "s3-warehouse".to_string(),
+            project_id: crate::ProjectId::default(),
+            storage_profile: S3Profile::builder()
+                .bucket("test-bucket".to_string())
+                .region("us-east-1".to_string())
+                .sts_enabled(false)
+                .build()
+                .into(),
+            storage_secret_id: None,
+            status: WarehouseStatus::Active,
+            tabular_delete_profile: TabularDeleteProfile::Hard {},
+            feature_flags: WarehouseFeatureFlags::default(),
+            allowed_schemes: None,
+            purge_grace_period: None,
+            manifest_merge_threshold: None,
+            max_tables_per_namespace: None,
+            max_tables_per_namespace_includes_views: false,
+            storage_prefix_overrides: vec![],
+            schema_evolution_policy: SchemaEvolutionPolicy::default(),
+            allowed_file_formats: None,
+            namespace_property_schema: NamespacePropertySchema::default(),
+            annotations: HashMap::new(),
+            default_view_properties: HashMap::new(),
+            commit_rate_limit: None,
+            credential_refresh_before: None,
+            allowed_authentication_methods: None,
+            empty_namespace_cleanup_after: None,
+            import_prefixes: vec![],
+            default_namespace: None,
+            snapshot_limit: None,
+        };
+        let namespace = GetNamespaceResponse {
+            namespace: iceberg::NamespaceIdent::new("ns1".to_string()),
+            namespace_id: NamespaceIdentUuid::default(),
+            warehouse_id: warehouse.id,
+            properties: None,
+            storage_prefix: None,
+            storage_region: None,
+            credential_ttl: None,
+        };
+        let table_id = TabularIdentUuid::Table(Uuid::now_v7());
+
+        // Explicit locations of other backends are rejected
+        let err = super::determine_tabular_location(
+            &namespace,
+            Some("gs://test-bucket/ns1/tab1".to_string()),
+            table_id,
+            &warehouse,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "LocationSchemeNotAllowed");
+
+        let location = super::determine_tabular_location(
+            &namespace,
+            Some("s3://test-bucket/ns1/tab1".to_string()),
+            table_id,
+            &warehouse,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(location.scheme(), "s3");
+
+        // Alternate schemes are only allowed if enabled on the profile
+        let err = super::determine_tabular_location(
+            &namespace,
+            Some("s3a://test-bucket/ns1/tab1".to_string()),
+            table_id,
+            &warehouse,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "LocationSchemeNotAllowed");
+
+        // Derived locations use the scheme of the namespace location
+        let namespace = GetNamespaceResponse {
+            properties: Some(HashMap::from_iter([(
+                "location".to_string(),
+                "s3a://test-bucket/ns1/".to_string(),
+            )])),
+            ..namespace
+        };
+        let err =
+            super::determine_tabular_location(&namespace, None, table_id, &warehouse, None, None)
+                .unwrap_err();
+        assert_eq!(err.error.r#type, "LocationSchemeNotAllowed");
+    }
+
+    #[test]
+    fn test_team_from_claims() {
+        let claims = serde_json::json!({
+            "team": "Data_Science",
+            "groups": ["Platform"],
+            "ambiguous": ["Platform", "Analytics"],
+            "spaces": "Data Science",
+            "slash": "a/b",
+            "empty": "",
+            "no-groups": [],
+        });
+        assert_eq!(
+            super::team_from_claims(&claims, "team").unwrap(),
+            "Data_Science"
+        );
+        assert_eq!(
+            super::team_from_claims(&claims, "groups").unwrap(),
+            "Platform"
+        );
+        for claim in ["empty", "missing", "no-groups"] {
+            let err = super::team_from_claims(&claims, claim).unwrap_err();
+            assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+            assert_eq!(err.error.r#type, "TeamClaimMissing");
+        }
+        for claim in ["spaces", "slash"] {
+            let err = super::team_from_claims(&claims, claim).unwrap_err();
+            assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+            assert_eq!(err.error.r#type, "InvalidTeamClaim");
+        }
+        let err = super::team_from_claims(&claims, "ambiguous").unwrap_err();
+        assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+        assert_eq!(err.error.r#type, "AmbiguousTeamClaim");
+    }
+
+    #[test]
+    fn test_credentials_are_narrowed_to_claimed_sub_prefix() {
+        let table_location = Location::from_str("s3://test-bucket/ns/table-1").unwrap();
+        let claims = serde_json::json!({
+            "partition": "data/region=eu/",
+            "partitions": ["data/region=us", "data/region=eu"],
+            "escape": "data/../../table-2",
+            "empty": "/",
+        });
+
+        let location =
+            super::credential_location_from_claims(&claims, "partition", &table_location).unwrap();
+        assert_eq!(
+            location.to_string(),
+            "s3://test-bucket/ns/table-1/data/region=eu"
+        );
+        assert!(location.is_sublocation_of(&table_location));
+        assert!(
+            !Location::from_str("s3://test-bucket/ns/table-1/data/region=us")
+                .unwrap()
+                .is_sublocation_of(&location)
+        );
+        assert_eq!(
+            super::credential_location_from_claims(&claims, "partitions", &table_location)
+                .unwrap()
+                .to_string(),
+            "s3://test-bucket/ns/table-1/data/region=us"
+        );
+
+        // Without the claim, credentials cover the table location
+        assert_eq!(
+            super::credential_location_from_claims(&claims, "missing", &table_location).unwrap(),
+            table_location
+        );
+
+        for claim in ["escape", "empty"] {
+            let err = super::credential_location_from_claims(&claims, claim, &table_location)
+                .unwrap_err();
+            assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+            assert_eq!(err.error.r#type, "InvalidCredentialPrefixClaim");
+        }
+    }
+
+    #[test]
+    fn test_tables_of_different_teams_use_distinct_prefixes() {
+        let warehouse = crate::service::GetWarehouseResponse {
+            id: WarehouseIdent::from(Uuid::now_v7()),
+            name: "shared-warehouse".to_string(),
+            project_id: crate::ProjectId::default(),
+            storage_profile: S3Profile::builder()
+                .bucket("test-bucket".to_string())
+                .region("us-east-1".to_string())
+                .sts_enabled(false)
+                .build()
+                .into(),
+            storage_secret_id: None,
+            status: WarehouseStatus::Active,
+            tabular_delete_profile: TabularDeleteProfile::Hard {},
+            feature_flags: WarehouseFeatureFlags::default(),
+            allowed_schemes: None,
+            purge_grace_period: None,
+            manifest_merge_threshold: None,
+            max_tables_per_namespace: None,
+            max_tables_per_namespace_includes_views: false,
+            storage_prefix_overrides: vec![],
+            schema_evolution_policy: SchemaEvolutionPolicy::default(),
+            allowed_file_formats: None,
+            namespace_property_schema: NamespacePropertySchema::default(),
+            annotations: HashMap::new(),
+            default_view_properties: HashMap::new(),
+            commit_rate_limit: None,
+            credential_refresh_before: None,
+            allowed_authentication_methods: None,
+            empty_namespace_cleanup_after: None,
+            import_prefixes: vec![],
+            default_namespace: None,
+            snapshot_limit: None,
+        };
+        let namespace = GetNamespaceResponse {
+            namespace: iceberg::NamespaceIdent::new("ns1".to_string()),
+            namespace_id: NamespaceIdentUuid::default(),
+            warehouse_id: warehouse.id,
+            properties: Some(HashMap::from_iter([(
+                "location".to_string(),
+                "s3://test-bucket/ns1/".to_string(),
+            )])),
+            storage_prefix: None,
+            storage_region: None,
+            credential_ttl: None,
+        };
+        let table_id = TabularIdentUuid::Table(Uuid::now_v7());
+
+        let team_location = |team: &str| {
+            warehouse
+                .storage_profile
+                .default_team_location(team)
+                .unwrap()
+        };
+        let alpha = super::determine_tabular_location(
+            &namespace,
+            None,
+            TabularIdentUuid::Table(Uuid::now_v7()),
+            &warehouse,
+            Some("alpha"),
+            None,
+        )
+        .unwrap();
+        let beta = super::determine_tabular_location(
+            &namespace,
+            None,
+            TabularIdentUuid::Table(Uuid::now_v7()),
+            &warehouse,
+            Some("beta"),
+            None,
+        )
+        .unwrap();
+        assert!(alpha.is_sublocation_of(&team_location("alpha")));
+        assert!(beta.is_sublocation_of(&team_location("beta")));
+        assert!(!alpha.is_sublocation_of(&team_location("beta")));
+        assert!(!beta.is_sublocation_of(&team_location("alpha")));
+
+        // Teams that only differ in case do not share a prefix
+        let claims = serde_json::json!({
+            "lower": "data-science",
+            "upper": "Data-Science",
+        });
+        let prefixes = ["lower", "upper"]
+            .into_iter()
+            .map(|claim| {
+                let team = super::team_from_claims(&claims, claim).unwrap();
+                super::determine_tabular_location(
+                    &namespace,
+                    None,
+                    table_id,
+                    &warehouse,
+                    Some(&team),
+                    None,
+                )
+                .unwrap()
+                .to_string()
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(prefixes.len(), 2);
+
+        // The mapping is deterministic
+        let alpha_again = super::determine_tabular_location(
+            &namespace,
+            None,
+            table_id,
+            &warehouse,
+            Some("alpha"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            alpha_again,
+            super::determine_tabular_location(
+                &namespace,
+                None,
+                table_id,
+                &warehouse,
+                Some("alpha"),
+                None
+            )
+            .unwrap()
+        );
+
+        // Explicit locations must stay within the team prefix
+        let mut explicit = team_location("alpha");
+        explicit.push("my-table");
+        super::determine_tabular_location(
+            &namespace,
+            Some(explicit.to_string()),
+            table_id,
+            &warehouse,
+            Some("alpha"),
+            None,
+        )
+        .unwrap();
+        let err = super::determine_tabular_location(
+            &namespace,
+            Some(explicit.to_string()),
+            table_id,
+            &warehouse,
+            Some("beta"),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+        assert_eq!(err.error.r#type, "LocationOutsideTeamLocation");
+    }
+
+    async fn commit_append_with_manifest(
+        ctx: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+        ns_params: &NamespaceParameters,
+        metadata: TableMetadata,
+        file_io: &iceberg::io::FileIO,
+        skip_manifest_merge: bool,
+    ) -> TableMetadata {
+        try_commit_append_with_manifest(
+            ctx,
+            ns_params,
+            metadata,
+            file_io,
+            skip_manifest_merge,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Commit a snapshot that adds a single data file, by default below the table location.
+    async fn try_commit_append_with_manifest(
+        ctx: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+        ns_params: &NamespaceParameters,
+        metadata: TableMetadata,
+        file_io: &iceberg::io::FileIO,
+        skip_manifest_merge: bool,
+        data_file_path: Option<String>,
+    ) -> crate::api::Result<TableMetadata> {
+        let snapshot_id = metadata
+            .current_snapshot()
+            .map_or(1, |s| s.snapshot_id() + 1);
+        let request = append_request(
+            ns_params,
+            metadata,
+            file_io,
+            snapshot_id,
+            skip_manifest_merge,
+            data_file_path,
+        )
+        .await;
+        super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![request],
+            },
+            ctx,
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .map(|commits| commits.into_iter().next().unwrap().new_metadata)
+    }
+
+    /// Request adding snapshot `snapshot_id` on top of the current snapshot,
+    /// with a single new data file. The file format follows the extension of `data_file_path`.
+    async fn append_request(
+        ns_params: &NamespaceParameters,
+        metadata: TableMetadata,
+        file_io: &iceberg::io::FileIO,
+        snapshot_id: i64,
+        skip_manifest_merge: bool,
+        data_file_path: Option<String>,
+    ) -> CommitTableRequest {
+        let location = metadata.location().trim_end_matches('/').to_string();
+        let parent = metadata.current_snapshot().cloned();
+        let sequence_number = metadata.last_sequence_number() + 1;
+
+        let mut manifests = match &parent {
+            Some(parent) => parent
+                .load_manifest_list(file_io, &metadata)
+                .await
+                .unwrap()
+                .entries()
+                .to_vec(),
+            None => vec![],
+        };
+        let data_file_path =
+            data_file_path.unwrap_or_else(|| format!("{location}/data/file-{snapshot_id}.parquet"));
+        let file_format = match data_file_path.rsplit_once('.') {
+            Some((_, "orc")) => DataFileFormat::Orc,
+            Some((_, "avro")) => DataFileFormat::Avro,
+            _ => DataFileFormat::Parquet,
+        };
+        let data_file = DataFileBuilder::default()
+            .content(DataContentType::Data)
+            .file_path(data_file_path)
+            .file_format(file_format)
+            .partition(Struct::empty())
+            .record_count(1)
+            .file_size_in_bytes(100)
+            .build()
+            .unwrap();
+        let mut writer = ManifestWriterBuilder::new(
+            file_io
+                .new_output(format!("{location}/metadata/{}-m0.avro", Uuid::now_v7()))
+                .unwrap(),
+            Some(snapshot_id),
+            vec![],
+            metadata.current_schema().clone(),
+            metadata.default_partition_spec().as_ref().clone(),
+        )
+        .build_v2_data();
+        writer
+            .add_entry(
+                ManifestEntry::builder()
+                    .status(ManifestStatus::Added)
+                    .data_file(data_file)
+                    .build(),
+            )
+            .unwrap();
+        manifests.push(writer.write_manifest_file().await.unwrap());
+
+        let manifest_list = format!("{location}/metadata/snap-{snapshot_id}.avro");
+        let mut list_writer = ManifestListWriter::v2(
+            file_io.new_output(&manifest_list).unwrap(),
+            snapshot_id,
+            parent.as_ref().map(Snapshot::snapshot_id),
+            sequence_number,
+        );
+        list_writer.add_manifests(manifests.into_iter()).unwrap();
+        list_writer.close().await.unwrap();
+
+        let mut additional_properties = HashMap::new();
+        if skip_manifest_merge {
+            additional_properties
+                .insert(PROPERTY_SKIP_MANIFEST_MERGE.to_string(), "true".to_string());
+        }
+        let snapshot = Snapshot::builder()
+            .with_snapshot_id(snapshot_id)
+            .with_parent_snapshot_id(parent.as_ref().map(Snapshot::snapshot_id))
+            .with_timestamp_ms(metadata.last_updated_ms() + 1)
+            .with_sequence_number(sequence_number)
+            .with_schema_id(metadata.current_schema_id())
+            .with_manifest_list(manifest_list)
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties,
+            })
+            .build();
+        let updates = metadata
+            .into_builder(None)
+            .add_snapshot(snapshot)
+            .unwrap()
+            .set_ref(
+                MAIN_BRANCH,
+                SnapshotReference {
+                    snapshot_id,
+                    retention: SnapshotRetention::Branch {
+                        min_snapshots_to_keep: None,
+                        max_snapshot_age_ms: None,
+                        max_ref_age_ms: None,
+                    },
+                },
+            )
+            .unwrap()
+            .build()
+            .unwrap()
+            .changes;
+
+        CommitTableRequest {
+            identifier: Some(TableIdent {
+                namespace: ns_params.namespace.clone(),
+                name: "tab-1".to_string(),
+            }),
+            requirements: vec![],
+            updates,
+        }
+    }
+
+    async fn live_data_files(
+        metadata: &TableMetadata,
+        file_io: &iceberg::io::FileIO,
+    ) -> (usize, Vec<String>) {
+        let manifest_list = metadata
+            .current_snapshot()
+            .unwrap()
+            .load_manifest_list(file_io, metadata)
+            .await
+            .unwrap();
+        let mut files = vec![];
+        for manifest_file in manifest_list.entries() {
+            let manifest = manifest_file.load_manifest(file_io).await.unwrap();
+            files.extend(
+                manifest
+                    .entries()
+                    .iter()
+                    .filter(|e| e.is_alive())
+                    .map(|e| e.file_path().to_string()),
+            );
+        }
+        files.sort();
+        (manifest_list.entries().len(), files)
+    }
+
+    #[sqlx::test]
+    async fn test_manifest_merge_on_commit(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let set_threshold = |threshold| {
+            ApiServer::update_warehouse_manifest_merge(
+                warehouse_id,
+                UpdateWarehouseManifestMergeRequest {
+                    manifest_merge_threshold: Some(threshold),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        set_threshold(3).await.unwrap();
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+
+        let mut metadata = table.metadata;
+        for _ in 0..3 {
+            metadata =
+                commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, false)
+                    .await;
+        }
+        // Below the threshold manifests are kept as written by the client
+        let (n_manifests, files_before) = live_data_files(&metadata, &file_io).await;
+        assert_eq!(n_manifests, 3);
+        assert_eq!(files_before.len(), 3);
+
+        metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, false).await;
+        let (n_manifests, files) = live_data_files(&metadata, &file_io).await;
+        assert_eq!(n_manifests, 1);
+        assert_eq!(files.len(), 4);
+        assert!(files_before.iter().all(|f| files.contains(f)));
+        let snapshot = metadata.current_snapshot().unwrap();
+        assert_eq!(snapshot.snapshot_id(), 4);
+        assert_ne!(
+            snapshot.manifest_list(),
+            format!(
+                "{}/metadata/snap-4.avro",
+                metadata.location().trim_end_matches('/')
+            )
+        );
+
+        // Merging can be skipped per commit
+        set_threshold(1).await.unwrap();
+        metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true).await;
+        let (n_manifests, files) = live_data_files(&metadata, &file_io).await;
+        assert_eq!(n_manifests, 2);
+        assert_eq!(files.len(), 5);
+
+        metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, false).await;
+        let (n_manifests, files) = live_data_files(&metadata, &file_io).await;
+        assert_eq!(n_manifests, 1);
+        assert_eq!(files.len(), 6);
+    }
+
+    #[sqlx::test]
+    async fn test_snapshot_limit_expires_oldest_snapshots_on_commit(pool: PgPool) {
+        let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        ApiServer::update_warehouse_snapshot_limit(
+            warehouse_id,
+            UpdateWarehouseSnapshotLimitRequest {
+                snapshot_limit: Some(SnapshotLimit {
+                    max_snapshots: 3,
+                    retain_last: 2,
+                }),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+
+        let mut metadata = table.metadata;
+        for _ in 0..3 {
+            metadata =
+                commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true)
+                    .await;
+        }
+        assert_eq!(metadata.snapshots().len(), 3);
+
+        // The commit exceeding the limit expires the oldest snapshots
+        metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true).await;
+        let mut snapshot_ids = metadata
+            .snapshots()
+            .map(|s| s.snapshot_id())
+            .collect::<Vec<_>>();
+        snapshot_ids.sort_unstable();
+        assert_eq!(snapshot_ids, vec![3, 4]);
+        assert_eq!(metadata.current_snapshot().unwrap().snapshot_id(), 4);
+
+        let tab = CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: TableIdent {
+                    namespace: ns.namespace.clone(),
+                    name: "tab-1".to_string(),
+                },
+            },
+            LoadTableQuery::default(),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tab.metadata.snapshots().len(), 2);
+
+        // Commits up to the limit expire no snapshots
+        metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true).await;
+        assert_eq!(metadata.snapshots().len(), 3);
+    }
+
+    #[sqlx::test]
+    async fn test_snapshot_limit_queues_cleanup_of_expired_files(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool.clone()).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        ApiServer::update_warehouse_snapshot_limit(
+            warehouse_id,
+            UpdateWarehouseSnapshotLimitRequest {
+                snapshot_limit: Some(SnapshotLimit {
+                    max_snapshots: 2,
+                    retain_last: 1,
+                }),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+
+        let mut metadata = table.metadata;
+        for _ in 0..2 {
+            metadata =
+                commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true)
+                    .await;
+        }
+        let expired_manifest_lists = metadata
+            .snapshots()
+            .map(|s| s.manifest_list().to_string())
+            .collect::<Vec<_>>();
+        metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true).await;
+        assert_eq!(metadata.snapshots().len(), 1);
+
+        let queue: SnapshotCleanupQueue = Arc::new(
+            crate::implementations::postgres::task_queues::SnapshotCleanupQueue::from_config(
+                ReadWrite::from_pools(pool.clone(), pool),
+                TaskQueueConfig::default(),
+            )
+            .unwrap(),
+        );
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("Cleanup of expired snapshots should be queued");
+        assert_eq!(task.snapshots.len(), 2);
+        for manifest_list in &expired_manifest_lists {
+            assert!(file_io.exists(manifest_list).await.unwrap());
+        }
+
+        instrumented_cleanup::<PostgresCatalog, _>(
+            queue.clone(),
+            ctx.v1_state.catalog.clone(),
+            &ctx.v1_state.secrets,
+            &task,
+        )
+        .await;
+
+        // Manifest lists of expired snapshots are deleted, files still referenced are kept
+        for manifest_list in &expired_manifest_lists {
+            assert!(!file_io.exists(manifest_list).await.unwrap());
+        }
+        let retained = metadata.current_snapshot().unwrap();
+        assert!(file_io.exists(retained.manifest_list()).await.unwrap());
+        let manifest_list = retained
+            .load_manifest_list(&file_io, &metadata)
+            .await
+            .unwrap();
+        for manifest_file in manifest_list.entries() {
+            assert!(file_io.exists(&manifest_file.manifest_path).await.unwrap());
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_data_file_path_validation(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let location = table.metadata.location().trim_end_matches('/').to_string();
+        // Shares the table location as string prefix, but is a different folder
+        let outside = format!("{location}-other/data/file.parquet");
+
+        // Disabled by default
+        let metadata = try_commit_append_with_manifest(
+            ctx.clone(),
+            &ns_params,
+            table.metadata,
+            &file_io,
+            true,
+            Some(outside.clone()),
+        )
+        .await
+        .unwrap();
+
+        ApiServer::update_warehouse_feature_flags(
+            warehouse_id,
+            UpdateWarehouseFeatureFlagsRequest {
+                feature_flags: HashMap::from_iter([(
+                    "validate-data-file-paths".to_string(),
+                    Some(true),
+                )]),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let err = try_commit_append_with_manifest(
+            ctx.clone(),
+            &ns_params,
+            metadata.clone(),
+            &file_io,
+            true,
+            Some(outside.clone()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "DataFileOutsideTableLocation");
+        assert!(err.error.message.contains(&outside));
+        assert_eq!(err.error.stack, vec![outside]);
+
+        // Files of earlier snapshots are not validated again
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true).await;
+        assert_eq!(metadata.snapshots().count(), 2);
+    }
+
+    #[sqlx::test]
+    async fn test_register_partition_statistics(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let location = table.metadata.location().trim_end_matches('/').to_string();
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, table.metadata, &file_io, true)
+                .await;
+        let snapshot_id = metadata.current_snapshot().unwrap().snapshot_id();
+
+        let commit = |updates: Vec<TableUpdate>| {
+            super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(TableIdent {
+                            namespace: ns_params.namespace.clone(),
+                            name: "tab-1".to_string(),
+                        }),
+                        requirements: vec![],
+                        updates,
+                    }],
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let set_statistics = |path: String| TableUpdate::SetPartitionStatistics {
+            partition_statistics: PartitionStatisticsFile {
+                snapshot_id,
+                statistics_path: path,
+                file_size_in_bytes: 5,
+            },
+        };
+        let load = || {
+            CatalogServer::load_table(
+                TableParameters {
+                    prefix: ns_params.prefix.clone(),
+                    table: TableIdent {
+                        namespace: ns_params.namespace.clone(),
+                        name: "tab-1".to_string(),
+                    },
+                },
+                LoadTableQuery::default(),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        // Files must exist
+        let missing = format!("{location}/metadata/missing.stats");
+        let err = commit(vec![set_statistics(missing)]).await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "StatisticsFileNotFound");
+
+        // Files must be located below the table location
+        let outside = format!("{location}-other/metadata/partition.stats");
+        file_io
+            .new_output(&outside)
+            .unwrap()
+            .write("stats".into())
+            .await
+            .unwrap();
+        let err = commit(vec![set_statistics(outside)]).await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "StatisticsFileOutsideTableLocation");
+
+        let stats_path = format!("{location}/metadata/partition-{snapshot_id}.stats");
+        file_io
+            .new_output(&stats_path)
+            .unwrap()
+            .write("stats".into())
+            .await
+            .unwrap();
+        commit(vec![set_statistics(stats_path.clone())])
+            .await
+            .unwrap();
+
+        let loaded = load().await.unwrap();
+        let statistics = loaded
+            .metadata
+            .partition_statistics_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(statistics.len(), 1);
+        assert_eq!(statistics[0].snapshot_id, snapshot_id);
+        assert_eq!(statistics[0].statistics_path, stats_path);
+
+        // Removing the snapshot removes its statistics
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, loaded.metadata, &file_io, true)
+                .await;
+        assert_eq!(metadata.partition_statistics_iter().count(), 1);
+        commit(vec![TableUpdate::RemoveSnapshots {
+            snapshot_ids: vec![snapshot_id],
+        }])
+        .await
+        .unwrap();
+        let loaded = load().await.unwrap();
+        assert!(loaded.metadata.snapshot_by_id(snapshot_id).is_none());
+        assert_eq!(loaded.metadata.partition_statistics_iter().count(), 0);
+    }
+
+    #[sqlx::test]
+    async fn test_allowed_file_formats(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let location = table.metadata.location().trim_end_matches('/').to_string();
+        let orc_file = format!("{location}/data/file.orc");
+
+        ApiServer::update_warehouse_allowed_file_formats(
+            warehouse_id,
+            UpdateWarehouseAllowedFileFormatsRequest {
+                allowed_file_formats: Some(vec!["Parquet".to_string()]),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let err = try_commit_append_with_manifest(
+            ctx.clone(),
+            &ns_params,
+            table.metadata.clone(),
+            &file_io,
+            true,
+            Some(orc_file.clone()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "DataFileFormatNotAllowed");
+        assert!(err.error.message.contains("orc"), "{}", err.error.message);
+        assert_eq!(err.error.stack, vec![format!("{orc_file} (orc)")]);
+
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, table.metadata, &file_io, true)
+                .await;
+        assert_eq!(metadata.snapshots().count(), 1);
+
+        // The default write format of new tables is steered towards allowed formats
+        let create = |properties: Option<HashMap<String, String>>| {
+            let mut request = create_request(Some("tab-2".to_string()));
+            request.properties = properties;
+            CatalogServer::create_table(
+                ns_params.clone(),
+                request,
+                DataAccess {
+                    vended_credentials: true,
+                    remote_signing: false,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let err = create(Some(HashMap::from_iter([(
+            "write.format.default".to_string(),
+            "orc".to_string(),
+        )])))
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "DataFileFormatNotAllowed");
+
+        let created = create(None).await.unwrap();
+        assert_eq!(
+            created.metadata.properties().get("write.format.default"),
+            Some(&"parquet".to_string())
+        );
+
+        let err = ApiServer::update_warehouse_allowed_file_formats(
+            warehouse_id,
+            UpdateWarehouseAllowedFileFormatsRequest {
+                allowed_file_formats: Some(vec!["csv".to_string()]),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "UnsupportedFileFormat");
+    }
+
+    #[sqlx::test]
+    async fn test_group_commit_of_concurrent_appends(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let initial_log_len = table.metadata.metadata_log().len();
+
+        // Ungrouped appends write metadata once per append
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, table.metadata, &file_io, true)
+                .await;
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true).await;
+        let ungrouped_writes = metadata.metadata_log().len() - initial_log_len;
+        assert_eq!(ungrouped_writes, 2);
+
+        let mut strict =
+            append_request(&ns_params, metadata.clone(), &file_io, 3, true, None).await;
+        strict
+            .requirements
+            .push(TableRequirement::CurrentSchemaIdMatch {
+                current_schema_id: metadata.current_schema_id(),
+            });
+        assert!(super::groupable_parent(&strict).is_none());
+
+        // Both appends are based on snapshot 2
+        let first = append_request(&ns_params, metadata.clone(), &file_io, 3, true, None).await;
+        let second = append_request(&ns_params, metadata.clone(), &file_io, 4, true, None).await;
+        assert_eq!(super::groupable_parent(&first), Some(Some(2)));
+        assert_eq!(super::groupable_parent(&second), Some(Some(2)));
+        let commit = |request| {
+            super::commit_grouped(
+                ns_params.prefix.clone(),
+                request,
+                Some(2),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+                std::time::Duration::from_millis(100),
+            )
+        };
+        let (first, second) = tokio::join!(commit(first), commit(second));
+        let (first, second) = (first.unwrap(), second.unwrap());
+        assert_eq!(first.metadata_location, second.metadata_location);
+
+        let grouped = first.metadata;
+        let grouped_writes = grouped.metadata_log().len() - metadata.metadata_log().len();
+        assert_eq!(grouped_writes, 1);
+        assert!(grouped_writes < ungrouped_writes);
+        assert_eq!(grouped.snapshots().count(), 4);
+
+        // The later append is rebased onto the earlier one
+        let current = grouped.current_snapshot().unwrap();
+        let parent = grouped
+            .snapshot_by_id(current.parent_snapshot_id().unwrap())
+            .unwrap();
+        assert_eq!(parent.parent_snapshot_id(), Some(2));
+        assert_eq!(current.sequence_number(), parent.sequence_number() + 1);
+        let (_, files) = live_data_files(&grouped, &file_io).await;
+        assert_eq!(files.len(), 4);
+    }
+
+    #[sqlx::test]
+    async fn test_stale_snapshot_commit_reports_diagnostics(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, table.metadata, &file_io, true)
+                .await;
+        let stale = metadata.clone();
+        commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true).await;
+
+        // Based on snapshot 1, while main already points to snapshot 2
+        let mut request = append_request(&ns_params, stale, &file_io, 3, true, None).await;
+        request
+            .requirements
+            .push(TableRequirement::RefSnapshotIdMatch {
+                r#ref: MAIN_BRANCH.to_string(),
+                snapshot_id: Some(1),
+            });
+        let err = super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![request],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+
+        let diagnostics: serde_json::Value =
+            serde_json::from_str(err.error.stack.last().unwrap()).unwrap();
+        assert_eq!(
+            diagnostics["failed-requirement"]["type"],
+            "assert-ref-snapshot-id"
+        );
+        assert_eq!(diagnostics["failed-requirement"]["snapshot-id"], 1);
+        assert_eq!(diagnostics["actual"], 2);
+        assert_eq!(diagnostics["current-snapshot-id"], 2);
+
+        let loaded = CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: TableIdent {
+                    namespace: ns_params.namespace.clone(),
+                    name: "tab-1".to_string(),
+                },
+            },
+            LoadTableQuery::default(),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            diagnostics["current-metadata-location"],
+            loaded.metadata_location.unwrap()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_max_tables_per_namespace(pool: PgPool) {
+        let (ctx, _, ns_params, _) = table_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        ApiServer::update_warehouse_max_tables_per_namespace(
+            warehouse_id,
+            UpdateWarehouseMaxTablesPerNamespaceRequest {
+                max_tables_per_namespace: Some(2),
+                include_views: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let create = |name: &str| {
+            CatalogServer::create_table(
+                ns_params.clone(),
+                create_request(Some(name.to_string())),
+                DataAccess {
+                    vended_credentials: true,
+                    remote_signing: false,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        create("tab-1").await.unwrap();
+        create("tab-2").await.unwrap();
+
+        let err = create("tab-3").await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        assert_eq!(err.error.r#type, "MaxTablesPerNamespaceExceeded");
+        assert!(err.error.message.contains("contains 2 tables"));
+    }
+
+    #[sqlx::test]
+    async fn test_max_tables_per_namespace_concurrent_creates(pool: PgPool) {
+        let (ctx, _, ns_params, _) = table_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        ApiServer::update_warehouse_max_tables_per_namespace(
+            warehouse_id,
+            UpdateWarehouseMaxTablesPerNamespaceRequest {
+                max_tables_per_namespace: Some(1),
+                include_views: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // The namespace is empty, so there are no tabular rows to lock
+        let results = futures::future::join_all((0..5).map(|i| {
+            CatalogServer::create_table(
+                ns_params.clone(),
+                create_request(Some(format!("tab-{i}"))),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        }))
+        .await;
+
+        let (created, rejected): (Vec<_>, Vec<_>) = results.into_iter().partition(Result::is_ok);
+        assert_eq!(created.len(), 1);
+        for err in rejected.into_iter().map(Result::unwrap_err) {
+            assert_eq!(err.error.r#type, "MaxTablesPerNamespaceExceeded");
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_table_limit_soft_limit_warns(pool: PgPool) {
+        let (ctx, _, ns_params, _) = table_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        ApiServer::update_warehouse_max_tables_per_namespace(
+            warehouse_id,
+            UpdateWarehouseMaxTablesPerNamespaceRequest {
+                max_tables_per_namespace: Some(5),
+                include_views: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let create = |name: String| {
+            let ctx = ctx.clone();
+            let ns_params = ns_params.clone();
+            async move {
+                let request_metadata = RequestMetadata::new_unauthenticated();
+                CatalogServer::create_table(
+                    ns_params,
+                    create_request(Some(name)),
+                    DataAccess::none(),
+                    ctx,
+                    request_metadata.clone(),
+                )
+                .await
+                .map(|_| request_metadata.warnings())
+            }
+        };
+        for i in 1..=3 {
+            assert!(create(format!("tab-{i}")).await.unwrap().is_empty());
+        }
+        // The default soft limit is 80% of the hard limit
+        let warnings = create("tab-4".to_string()).await.unwrap();
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("contains 4 tables, approaching the maximum of 5"));
+        assert_eq!(create("tab-5".to_string()).await.unwrap().len(), 1);
+
+        let err = create("tab-6".to_string()).await.unwrap_err();
+        assert_eq!(err.error.r#type, "MaxTablesPerNamespaceExceeded");
+    }
+
+    #[sqlx::test]
+    async fn test_namespace_storage_prefix_override(pool: PgPool) {
+        let (ctx, ns, ns_params, base_loc) = table_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let namespace_id =
+            NamespaceIdentUuid::from_str(&ns.properties.as_ref().unwrap()["namespace_id"]).unwrap();
+        let eu_bucket = format!("file://tmp/eu-{}", Uuid::now_v7());
+        let eu_prefix = format!("{eu_bucket}/finance");
+        let set_storage_prefix = || {
+            ApiServer::set_namespace_storage_prefix(
+                warehouse_id,
+                namespace_id,
+                SetNamespaceStoragePrefixRequest {
+                    storage_prefix: Some(eu_prefix.clone()),
+                    storage_region: None,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let create = |params: NamespaceParameters| {
+            CatalogServer::create_table(
+                params,
+                create_request(Some("tab-1".to_string())),
+                DataAccess {
+                    vended_credentials: true,
+                    remote_signing: false,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        // The prefix must be within the storage prefix overrides of the warehouse
+        let err = set_storage_prefix().await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "StoragePrefixNotAllowed");
+
+        ApiServer::update_warehouse_storage_prefix_overrides(
+            warehouse_id,
+            UpdateWarehouseStoragePrefixOverridesRequest {
+                storage_prefix_overrides: vec![eu_bucket.clone()],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // Regions are only supported for S3 profiles
+        let err = ApiServer::set_namespace_storage_prefix(
+            warehouse_id,
+            namespace_id,
+            SetNamespaceStoragePrefixRequest {
+                storage_prefix: Some(eu_prefix.clone()),
+                storage_region: Some("eu-central-1".to_string()),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "InvalidStorageRegion");
+
+        set_storage_prefix().await.unwrap();
+
+        let table = create(ns_params.clone()).await.unwrap();
+        assert!(table
+            .metadata
+            .location()
+            .starts_with(&format!("{eu_prefix}/{namespace_id}/")));
+
+        // Child namespaces inherit the storage prefix of their parent
+        let child = CatalogServer::create_namespace(
+            ns_params.prefix.clone(),
+            CreateNamespaceRequest {
+                namespace: iceberg::NamespaceIdent::from_vec(vec![
+                    "ns1".to_string(),
+                    "child".to_string(),
+                ])
+                .unwrap(),
+                properties: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let table = create(NamespaceParameters {
+            prefix: ns_params.prefix.clone(),
+            namespace: child.namespace,
+        })
+        .await
+        .unwrap();
+        assert!(table
+            .metadata
+            .location()
+            .starts_with(&format!("{eu_prefix}/")));
+
+        // Other namespaces keep using the base location of the warehouse
+        let other = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse_id.to_string(),
+            "ns2".to_string(),
+        )
+        .await;
+        let table = create(NamespaceParameters {
+            prefix: ns_params.prefix.clone(),
+            namespace: other.namespace,
+        })
+        .await
+        .unwrap();
+        assert!(table.metadata.location().starts_with(&base_loc));
+    }
+
+    #[sqlx::test]
+    async fn test_preview_table_location_matches_created_table(pool: PgPool) {
+        let (ctx, ns, ns_params, _) = table_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let namespace_id =
+            NamespaceIdentUuid::from_str(&ns.properties.as_ref().unwrap()["namespace_id"]).unwrap();
+        let preview_and_create = |name: &'static str| {
+            let ctx = ctx.clone();
+            let ns_params = ns_params.clone();
+            async move {
+                let preview = ApiServer::preview_table_location(
+                    warehouse_id,
+                    PreviewTableLocationRequest {
+                        namespace: ns_params.namespace.clone(),
+                        name: name.to_string(),
+                    },
+                    ctx.clone(),
+                    RequestMetadata::new_unauthenticated(),
+                )
+                .await
+                .unwrap();
+                let table = CatalogServer::create_table(
+                    ns_params,
+                    create_request(Some(name.to_string())),
+                    DataAccess::none(),
+                    ctx,
+                    RequestMetadata::new_unauthenticated(),
+                )
+                .await
+                .unwrap();
+                (preview, table.metadata.location().to_string())
+            }
+        };
+
+        let (preview, location) = preview_and_create("tab-1").await;
+        let (parent, table_id) = location.rsplit_once('/').unwrap();
+        assert_eq!(preview.parent_location, parent);
+        assert_eq!(
+            preview.location.replace("{table-id}", table_id),
+            location,
+            "{preview:?}"
+        );
+
+        // Namespace overrides are applied as on creation
+        let eu_bucket = format!("file://tmp/eu-{}", Uuid::now_v7());
+        ApiServer::update_warehouse_storage_prefix_overrides(
+            warehouse_id,
+            UpdateWarehouseStoragePrefixOverridesRequest {
+                storage_prefix_overrides: vec![eu_bucket.clone()],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        ApiServer::set_namespace_storage_prefix(
+            warehouse_id,
+            namespace_id,
+            SetNamespaceStoragePrefixRequest {
+                storage_prefix: Some(eu_bucket.clone()),
+                storage_region: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let (preview, location) = preview_and_create("tab-2").await;
+        assert_eq!(
+            preview.parent_location,
+            format!("{eu_bucket}/{namespace_id}")
+        );
+        let (parent, _) = location.rsplit_once('/').unwrap();
+        assert_eq!(preview.parent_location, parent);
+
+        // The namespace must exist
+        let err = ApiServer::preview_table_location(
+            warehouse_id,
+            PreviewTableLocationRequest {
+                namespace: iceberg::NamespaceIdent::new("missing".to_string()),
+                name: "tab-3".to_string(),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test]
+    async fn test_list_changed_tables(pool: PgPool) {
+        let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        for name in ["tab-2", "tab-3"] {
+            CatalogServer::create_table(
+                ns_params.clone(),
+                create_request(Some(name.to_string())),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+        }
+
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        let cutoff = chrono::Utc::now();
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        let updates = table
+            .metadata
+            .into_builder(table.metadata_location)
+            .set_properties(HashMap::from([("p1".into(), "v1".into())]))
+            .unwrap()
+            .build()
+            .unwrap()
+            .changes;
+        let committed = super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![CommitTableRequest {
+                    identifier: Some(TableIdent {
+                        namespace: ns.namespace.clone(),
+                        name: "tab-1".to_string(),
+                    }),
+                    requirements: vec![],
+                    updates,
+                }],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .next()
+        .unwrap();
+
+        let list_changed = |since, page_token, page_size| {
+            let catalog = ctx.v1_state.catalog.clone();
+            async move {
+                let mut t = PostgresTransaction::begin_read(catalog).await.unwrap();
+                let changed = PostgresCatalog::list_changed_tables(
+                    warehouse_id,
+                    since,
+                    t.transaction(),
+                    PaginationQuery {
+                        page_token,
+                        page_size,
+                    },
+                )
+                .await
+                .unwrap();
+                t.commit().await.unwrap();
+                changed
+            }
+        };
+
+        let changed = list_changed(cutoff, PageToken::NotSpecified, None).await;
+        assert_eq!(changed.len(), 1);
+        let (_, changed_table, _) = changed.into_iter_with_page_tokens().next().unwrap();
+        assert_eq!(changed_table.table.name, "tab-1");
+        assert!(changed_table.updated_at > cutoff);
+        assert_eq!(
+            changed_table.metadata_location,
+            committed.new_metadata_location
+        );
+
+        // Tables are paginated in the order they were last changed
+        let epoch = chrono::DateTime::<chrono::Utc>::UNIX_EPOCH;
+        let first = list_changed(epoch, PageToken::NotSpecified, Some(2)).await;
+        let (first_names, tokens): (Vec<_>, Vec<_>) = first
+            .into_iter_with_page_tokens()
+            .map(|(_, t, token)| (t.table.name, token))
+            .unzip();
+        assert_eq!(first_names, vec!["tab-2", "tab-3"]);
+        let second = list_changed(
+            epoch,
+            PageToken::Present(tokens.last().unwrap().clone()),
+            Some(2),
+        )
+        .await;
+        let second_names = second
+            .into_iter_with_page_tokens()
+            .map(|(_, t, _)| t.table.name)
+            .collect_vec();
+        assert_eq!(second_names, vec!["tab-1"]);
+
+        // Tables committed together share their change time. Paging through them
+        // one by one must neither skip nor repeat a table.
+        let set_property = |name: &str| CommitTableRequest {
+            identifier: Some(TableIdent {
+                namespace: ns.namespace.clone(),
+                name: name.to_string(),
+            }),
+            requirements: vec![],
+            updates: vec![TableUpdate::SetProperties {
+                updates: HashMap::from([("p2".into(), "v2".into())]),
+            }],
+        };
+        super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![set_property("tab-2"), set_property("tab-3")],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let latest = list_changed(epoch, PageToken::NotSpecified, None)
+            .await
+            .into_iter_with_page_tokens()
+            .map(|(_, t, _)| t)
+            .collect_vec();
+        let commit_time = latest.last().unwrap().updated_at;
+        assert_eq!(latest[latest.len() - 2].updated_at, commit_time);
+
+        let mut names = vec![];
+        let mut page_token = PageToken::NotSpecified;
+        loop {
+            let page = list_changed(commit_time, page_token, Some(1)).await;
+            let Some((_, t, token)) = page.into_iter_with_page_tokens().next() else {
+                break;
+            };
+            names.push(t.table.name);
+            page_token = PageToken::Present(token);
+        }
+        names.sort();
+        assert_eq!(names, vec!["tab-2", "tab-3"]);
+    }
+
+    #[sqlx::test]
+    async fn test_schema_evolution_policy(pool: PgPool) {
+        let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        ApiServer::update_warehouse_schema_evolution_policy(
+            warehouse_id,
+            UpdateWarehouseSchemaEvolutionPolicyRequest {
+                schema_evolution_policy: SchemaEvolutionPolicy {
+                    allow_column_drops: false,
+                    allow_type_changes: false,
+                    allow_required_field_additions: false,
+                },
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let commit_schema = |fields: Vec<NestedField>| {
+            let updates = table
+                .metadata
+                .clone()
+                .into_builder(table.metadata_location.clone())
+                .add_schema(
+                    Schema::builder()
+                        .with_fields(fields.into_iter().map(Into::into))
+                        .build()
+                        .unwrap(),
+                )
+                .set_current_schema(-1)
+                .unwrap()
+                .build()
+                .unwrap()
+                .changes;
+            super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(TableIdent {
+                            namespace: ns.namespace.clone(),
+                            name: "tab-1".to_string(),
+                        }),
+                        requirements: vec![],
+                        updates,
+                    }],
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let id = NestedField::required(1, "id", Type::Primitive(PrimitiveType::Int));
+        let name = NestedField::required(2, "name", Type::Primitive(PrimitiveType::String));
+
+        let err = commit_schema(vec![id.clone()]).await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "SchemaEvolutionPolicyViolation");
+        assert!(err.error.message.contains("dropped column 'name'"));
+
+        let committed = commit_schema(vec![
+            id,
+            name,
+            NestedField::optional(3, "age", Type::Primitive(PrimitiveType::Int)),
+        ])
+        .await
+        .unwrap();
+        assert_eq!(
+            committed[0]
+                .new_metadata
+                .current_schema()
+                .as_struct()
+                .fields()
+                .len(),
+            3
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_immutable_table_accepts_appends_only(pool: PgPool) {
+        let (ctx, _, ns_params, _) = table_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let mut request = create_request(Some("tab-1".to_string()));
+        request.properties = Some(HashMap::from([(
+            PROPERTY_IMMUTABLE.to_string(),
+            "true".to_string(),
+        )]));
+        let table = CatalogServer::create_table(
+            ns_params.clone(),
+            request,
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let table_id = super::TableIdentUuid::from(table.metadata.uuid());
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, table.metadata, &file_io, false)
+                .await;
+        assert_eq!(metadata.current_snapshot_id(), Some(1));
+
+        let commit = |updates: Vec<TableUpdate>| {
+            super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(TableIdent {
+                            namespace: ns_params.namespace.clone(),
+                            name: "tab-1".to_string(),
+                        }),
+                        requirements: vec![],
+                        updates,
+                    }],
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let rewrite = |operation: Operation| {
+            let parent = metadata.current_snapshot().unwrap();
+            let snapshot = Snapshot::builder()
+                .with_snapshot_id(2)
+                .with_parent_snapshot_id(Some(1))
+                .with_timestamp_ms(metadata.last_updated_ms() + 1)
+                .with_sequence_number(metadata.last_sequence_number() + 1)
+                .with_schema_id(metadata.current_schema_id())
+                .with_manifest_list(parent.manifest_list())
+                .with_summary(Summary {
+                    operation,
+                    additional_properties: HashMap::new(),
+                })
+                .build();
+            metadata
+                .clone()
+                .into_builder(None)
+                .add_snapshot(snapshot)
+                .unwrap()
+                .set_ref(
+                    MAIN_BRANCH,
+                    SnapshotReference {
+                        snapshot_id: 2,
+                        retention: SnapshotRetention::Branch {
+                            min_snapshots_to_keep: None,
+                            max_snapshot_age_ms: None,
+                            max_ref_age_ms: None,
+                        },
+                    },
+                )
+                .unwrap()
+                .build()
+                .unwrap()
+                .changes
+        };
+
+        for operation in [Operation::Overwrite, Operation::Delete, Operation::Replace] {
+            let err = commit(rewrite(operation)).await.unwrap_err();
+            assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+            assert_eq!(err.error.r#type, "ImmutableTableViolation");
+        }
+
+        // The flag cannot be removed by users
+        let err = commit(vec![TableUpdate::RemoveProperties {
+            removals: vec![PROPERTY_IMMUTABLE.to_string()],
+        }])
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+
+        // Once a server admin removed the flag, data can be overwritten
+        ApiServer::remove_table_immutability(
+            warehouse_id,
+            table_id,
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let committed = commit(rewrite(Operation::Overwrite)).await.unwrap();
+        assert!(!committed[0]
+            .new_metadata
+            .properties()
+            .contains_key(PROPERTY_IMMUTABLE));
+    }
+
+    #[sqlx::test]
+    async fn test_immutable_table_history_cannot_change(pool: PgPool) {
+        let (ctx, _, ns_params, _) = table_test_setup(pool).await;
+        let mut request = create_request(Some("tab-1".to_string()));
+        request.properties = Some(HashMap::from([(
+            PROPERTY_IMMUTABLE.to_string(),
+            "true".to_string(),
+        )]));
+        let table = CatalogServer::create_table(
+            ns_params.clone(),
+            request,
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, table.metadata, &file_io, false)
+                .await;
+        let metadata =
+            commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, false).await;
+        assert_eq!(metadata.current_snapshot_id(), Some(2));
+
+        let table_ident = TableIdent {
+            namespace: ns_params.namespace.clone(),
+            name: "tab-1".to_string(),
+        };
+        let commit = |updates: Vec<TableUpdate>| {
+            super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(table_ident.clone()),
+                        requirements: vec![],
+                        updates,
+                    }],
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let set_ref = |ref_name: &str, snapshot_id: i64| TableUpdate::SetSnapshotRef {
+            ref_name: ref_name.to_string(),
+            reference: SnapshotReference {
+                snapshot_id,
+                retention: SnapshotRetention::Branch {
+                    min_snapshots_to_keep: None,
+                    max_snapshot_age_ms: None,
+                    max_ref_age_ms: None,
+                },
+            },
+        };
+
+        for updates in [
+            vec![TableUpdate::RemoveSnapshots {
+                snapshot_ids: vec![1],
+            }],
+            vec![TableUpdate::RemoveSnapshotRef {
+                ref_name: MAIN_BRANCH.to_string(),
+            }],
+            // Rollback of main to its parent
+            vec![set_ref(MAIN_BRANCH, 1)],
+        ] {
+            let err = commit(updates).await.unwrap_err();
+            assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+            assert_eq!(err.error.r#type, "ImmutableTableViolation");
+        }
+
+        // New branches can point to any snapshot and move forward
+        commit(vec![set_ref("audit", 1)]).await.unwrap();
+        commit(vec![set_ref("audit", 2)]).await.unwrap();
+
+        // The data of immutable tables cannot be purged
+        let drop_table = |purge_requested: Option<bool>| {
+            CatalogServer::drop_table(
+                TableParameters {
+                    prefix: ns_params.prefix.clone(),
+                    table: table_ident.clone(),
+                },
+                DropParams { purge_requested },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        for purge_requested in [None, Some(true)] {
+            let err = drop_table(purge_requested).await.unwrap_err();
+            assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+            assert_eq!(err.error.r#type, "ImmutableTableViolation");
+        }
+        drop_table(Some(false)).await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_deprecated_table_warns_and_blocks_writes_after_sunset(pool: PgPool) {
+        let (ctx, _, ns_params, _) = table_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let table = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request(Some("tab-1".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let table_id = super::TableIdentUuid::from(table.metadata.uuid());
+        let table_ident = TableIdent {
+            namespace: ns_params.namespace.clone(),
+            name: "tab-1".to_string(),
+        };
+
+        let deprecate = |sunset: chrono::DateTime<chrono::Utc>| {
+            ApiServer::set_table_deprecation(
+                warehouse_id,
+                table_id,
+                SetTableDeprecationRequest {
+                    deprecated: true,
+                    message: Some("Use `tab-2` instead".to_string()),
+                    sunset: Some(sunset),
+                    block_writes_after_sunset: true,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let commit = |updates: Vec<TableUpdate>| {
+            super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(table_ident.clone()),
+                        requirements: vec![],
+                        updates,
+                    }],
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let set_owner = || {
+            commit(vec![TableUpdate::SetProperties {
+                updates: HashMap::from([("owner".to_string(), "me".to_string())]),
+            }])
+        };
+
+        let sunset = chrono::Utc::now() + chrono::Duration::days(30);
+        deprecate(sunset).await.unwrap();
+
+        // Loading succeeds, but warns about the deprecation
+        let request_metadata = RequestMetadata::new_unauthenticated();
+        let loaded = CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: table_ident.clone(),
+            },
+            LoadTableQuery::default(),
+            DataAccess::none(),
+            ctx.clone(),
+            request_metadata.clone(),
+        )
+        .await
+        .unwrap();
+        let deprecation = loaded.deprecation.unwrap();
+        assert_eq!(deprecation.message.as_deref(), Some("Use `tab-2` instead"));
+        assert_eq!(
+            deprecation.sunset.map(|s| s.timestamp()),
+            Some(sunset.timestamp())
+        );
+        let warnings = request_metadata.warnings();
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("is deprecated"));
+        assert!(warnings[0].contains("Use `tab-2` instead"));
+        assert_eq!(
+            request_metadata.sunset().map(|s| s.timestamp()),
+            Some(sunset.timestamp())
+        );
+
+        // Writes are accepted until the sunset, the deprecation cannot be committed by users
+        set_owner().await.unwrap();
+        let err = commit(vec![TableUpdate::RemoveProperties {
+            removals: vec![table_deprecation::PROPERTY_DEPRECATED.to_string()],
+        }])
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "TableDeprecationViolation");
+
+        // Past the sunset, writes are blocked
+        deprecate(chrono::Utc::now() - chrono::Duration::minutes(1))
+            .await
+            .unwrap();
+        let err = set_owner().await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+        assert_eq!(err.error.r#type, "TableSunset");
+    }
+
+    #[sqlx::test]
+    async fn test_table_history_lists_commits_newest_first(pool: PgPool) {
+        let (mut ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        ctx.v1_state.commit_observers = CommitObservers::new(vec![Arc::new(
+            CommitAttributionObserver::<PostgresCatalog>::new(ctx.v1_state.catalog.clone()),
+        )]);
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let table_id = super::TableIdentUuid::from(table.metadata.uuid());
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let user_id = UserId::new_unchecked("oidc", "test-user");
+
+        let mut metadata = table.metadata;
+        for snapshot_id in 1..=3 {
+            let request =
+                append_request(&ns_params, metadata, &file_io, snapshot_id, true, None).await;
+            metadata = super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![request],
+                },
+                ctx.clone(),
+                RequestMetadata::random_human(user_id.clone()),
+            )
+            .await
+            .unwrap()
+            .remove(0)
+            .new_metadata;
+        }
+
+        let history = |page_token: Option<String>, page_size: i64| {
+            ApiServer::get_table_history(
+                warehouse_id,
+                table_id,
+                TableHistoryQuery {
+                    page_token,
+                    page_size,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let all = history(None, 10).await.unwrap().history;
+        assert_eq!(
+            all.iter()
+                .map(|e| (e.snapshot_id, e.parent_snapshot_id))
+                .collect_vec(),
+            vec![(3, Some(2)), (2, Some(1)), (1, None)]
+        );
+        for entry in &all {
+            assert_eq!(entry.operation, "append");
+            assert_eq!(entry.principal.as_deref(), Some("oidc~test-user"));
+            assert_eq!(
+                Some(entry.timestamp_ms),
+                metadata
+                    .snapshot_by_id(entry.snapshot_id)
+                    .map(|s| s.timestamp_ms())
+            );
+        }
+
+        let first = history(None, 2).await.unwrap();
+        assert_eq!(first.history, all[..2]);
+        let second = history(first.next_page_token, 2).await.unwrap();
+        assert_eq!(second.history, all[2..]);
+    }
+
+    #[sqlx::test]
+    async fn test_credential_refresh_hint(pool: PgPool) {
+        let (ctx, ns, ns_params, _) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        ApiServer::update_warehouse_credential_refresh(
+            warehouse_id,
+            UpdateWarehouseCredentialRefreshRequest {
+                refresh_before_seconds: Some(600),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let parameters = TableParameters {
+            prefix: ns_params.prefix.clone(),
+            table: TableIdent {
+                namespace: ns.namespace.clone(),
+                name: "tab-1".to_string(),
+            },
+        };
+        let data_access = DataAccess {
+            vended_credentials: true,
+            remote_signing: false,
+        };
+        let expires_at = |config: &HashMap<String, String>| {
+            config["s3.session-token-expires-at-ms"]
+                .parse::<i64>()
+                .unwrap()
+        };
+
+        let loaded = CatalogServer::load_table(
+            parameters.clone(),
+            LoadTableQuery::default(),
+            data_access.clone(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let credentials = loaded.storage_credentials.unwrap().remove(0).config;
+        assert_eq!(
+            credentials.get(CREDENTIALS_REFRESH_BEFORE_MS),
+            Some(&"600000".to_string())
+        );
+
+        // Refreshing re-vends credentials without loading the table
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        let refreshed = CatalogServer::load_table_credentials(
+            parameters,
+            data_access,
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let refreshed = &refreshed.storage_credentials[0].config;
+        assert_eq!(
+            refreshed.get(CREDENTIALS_REFRESH_BEFORE_MS),
+            Some(&"600000".to_string())
+        );
+        assert!(expires_at(refreshed) > expires_at(&credentials));
+
+        for seconds in [-1, 86_401, i64::MAX] {
+            let err = ApiServer::update_warehouse_credential_refresh(
+                warehouse_id,
+                UpdateWarehouseCredentialRefreshRequest {
+                    refresh_before_seconds: Some(seconds),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+            assert_eq!(err.error.r#type, "InvalidCredentialRefreshHint");
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_create_table_requires_existing_namespace(pool: PgPool) {
+        let (ctx, ns, ns_params, _) = table_test_setup(pool).await;
+        let mut child = ns.namespace.clone().inner();
+        child.push("child".to_string());
+        let child = iceberg::NamespaceIdent::from_vec(child).unwrap();
+        let child_params = NamespaceParameters {
+            prefix: ns_params.prefix.clone(),
+            namespace: child.clone(),
+        };
+        let create = || {
+            CatalogServer::create_table(
+                child_params.clone(),
+                create_request(Some("tab-1".to_string())),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        // Missing namespaces are never created implicitly
+        let err = create().await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+        let namespaces = CatalogServer::list_namespaces(
+            ns_params.prefix.clone(),
+            ListNamespacesQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: None,
+                parent: Some(ns.namespace.clone()),
+                return_uuids: false,
+                name_prefix: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert!(namespaces.namespaces.is_empty());
+
+        CatalogServer::create_namespace(
+            ns_params.prefix.clone(),
+            CreateNamespaceRequest {
+                namespace: child,
+                properties: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        create().await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_register_table_from_import_prefix(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let import_prefix = format!("file://tmp/{}", Uuid::now_v7());
+        let write_external_table = |prefix: String| {
+            let file_io = file_io.clone();
+            let metadata = TableMetadataBuilder::new_from_metadata(table.metadata.clone(), None)
+                .assign_uuid(Uuid::now_v7())
+                .set_location(format!("{prefix}/orders"))
+                .build()
+                .unwrap()
+                .metadata;
+            async move {
+                let metadata_location = Location::from_str(&format!(
+                    "{prefix}/orders/metadata/00000-{}.metadata.json",
+                    Uuid::now_v7()
+                ))
+                .unwrap();
+                write_metadata_file(
+                    &metadata_location,
+                    &metadata,
+                    CompressionCodec::None,
+                    &file_io,
+                )
+                .await
+                .unwrap();
+                metadata_location.to_string()
+            }
+        };
+
+        ApiServer::update_warehouse_import_prefixes(
+            warehouse_id,
+            UpdateWarehouseImportPrefixesRequest {
+                import_prefixes: vec![import_prefix.clone()],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // Tables from an import prefix are registered read-only without changing their metadata
+        let metadata_location = write_external_table(import_prefix).await;
+        let registered = CatalogServer::register_table(
+            ns_params.clone(),
+            RegisterTableRequest {
+                name: "orders".to_string(),
+                metadata_location,
+                assign_new_uuid: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            registered.metadata.properties(),
+            table.metadata.properties()
+        );
+
+        let err = super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![CommitTableRequest {
+                    identifier: Some(TableIdent {
+                        namespace: ns_params.namespace.clone(),
+                        name: "orders".to_string(),
+                    }),
+                    requirements: vec![],
+                    updates: vec![TableUpdate::SetProperties {
+                        updates: HashMap::from([("owner".to_string(), "me".to_string())]),
+                    }],
+                }],
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+        assert_eq!(err.error.r#type, "ReadOnlyTable");
+
+        // Locations outside of the import prefixes and the warehouse are rejected
+        let metadata_location =
+            write_external_table(format!("file://tmp/{}", Uuid::now_v7())).await;
+        let err = CatalogServer::register_table(
+            ns_params.clone(),
+            RegisterTableRequest {
+                name: "other-orders".to_string(),
+                metadata_location,
+                assign_new_uuid: false,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "InvalidLocation");
+    }
+
+    #[sqlx::test]
+    async fn test_default_namespace_alias(pool: PgPool) {
+        let (ctx, _, ns_params, _) = table_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let default_namespace =
+            iceberg::NamespaceIdent::from_vec(vec!["analytics".to_string(), "sales".to_string()])
+                .unwrap();
+        let alias_params = NamespaceParameters {
+            prefix: ns_params.prefix.clone(),
+            namespace: iceberg::NamespaceIdent::new("_default".to_string()),
+        };
+
+        // Without a default namespace, the alias is an ordinary (missing) namespace
+        let err = CatalogServer::create_table(
+            alias_params.clone(),
+            create_request(Some("orders".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+
+        // The default namespace must exist unless it is created
+        let set_default_namespace = |create_if_missing| {
+            ApiServer::update_warehouse_default_namespace(
+                warehouse_id,
+                UpdateWarehouseDefaultNamespaceRequest {
+                    default_namespace: Some(default_namespace.clone()),
+                    create_if_missing,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let err = set_default_namespace(false).await.unwrap_err();
+        assert_eq!(err.error.r#type, "DefaultNamespaceNotFound");
+        set_default_namespace(true).await.unwrap();
+
+        CatalogServer::create_table(
+            alias_params.clone(),
+            create_request(Some("orders".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // The table is created in the default namespace
+        CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: TableIdent::new(default_namespace.clone(), "orders".to_string()),
+            },
+            LoadTableQuery::default(),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let tables = CatalogServer::list_tables(
+            alias_params,
+            ListTablesQuery {
+                page_token: PageToken::NotSpecified,
+                page_size: None,
+                return_uuids: false,
+                name_prefix: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            tables.identifiers,
+            vec![TableIdent::new(default_namespace, "orders".to_string())]
+        );
+
+        // The alias itself cannot be the default namespace
+        let err = ApiServer::update_warehouse_default_namespace(
+            warehouse_id,
+            UpdateWarehouseDefaultNamespaceRequest {
+                default_namespace: Some(iceberg::NamespaceIdent::new("_default".to_string())),
+                create_if_missing: true,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "ReservedNamespace");
+    }
+
+    #[sqlx::test]
+    async fn test_register_table_with_duplicate_uuid(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        // Copy of "tab-1" at a different location, carrying the UUID of "tab-1"
+        let (parent, _) = table.metadata.location().rsplit_once('/').unwrap();
+        let location = format!("{parent}/tab-copy-{}", Uuid::now_v7());
+        let metadata = TableMetadataBuilder::new_from_metadata(table.metadata.clone(), None)
+            .set_location(location.clone())
+            .build()
+            .unwrap()
+            .metadata;
+        let metadata_location = Location::from_str(&format!(
+            "{location}/metadata/00000-{}.metadata.json",
+            Uuid::now_v7()
+        ))
+        .unwrap();
+        write_metadata_file(
+            &metadata_location,
+            &metadata,
+            CompressionCodec::None,
+            &file_io,
+        )
+        .await
+        .unwrap();
+
+        let register = |name: &str, assign_new_uuid: bool| {
+            CatalogServer::register_table(
+                ns_params.clone(),
+                RegisterTableRequest {
+                    name: name.to_string(),
+                    metadata_location: metadata_location.to_string(),
+                    assign_new_uuid,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        let err = register("tab-copy", false).await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        assert_eq!(err.error.r#type, "DuplicateTableUUID");
+
+        let registered = register("tab-copy", true).await.unwrap();
+        assert_ne!(registered.metadata.uuid(), table.metadata.uuid());
+        assert_eq!(
+            registered.metadata.current_schema(),
+            table.metadata.current_schema()
+        );
+        let loaded = CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: TableIdent::new(ns_params.namespace.clone(), "tab-copy".to_string()),
+            },
+            LoadTableQuery::default(),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded.metadata.uuid(), registered.metadata.uuid());
+    }
+
+    #[sqlx::test]
+    async fn test_namespace_credential_ttl(pool: PgPool) {
+        let (ctx, ns, ns_params, _) = table_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let data_access = DataAccess {
+            vended_credentials: true,
+            remote_signing: false,
+        };
+        let create_namespace = |name: &[&str]| {
+            CatalogServer::create_namespace(
+                ns_params.prefix.clone(),
+                CreateNamespaceRequest {
+                    namespace: iceberg::NamespaceIdent::from_strs(name).unwrap(),
+                    properties: None,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let set_credential_ttl = |namespace: &CreateNamespaceResponse, seconds: i64| {
+            ApiServer::set_namespace_credential_ttl(
+                warehouse_id,
+                NamespaceIdentUuid::from_str(
+                    &namespace.properties.as_ref().unwrap()["namespace_id"],
+                )
+                .unwrap(),
+                SetNamespaceCredentialTtlRequest {
+                    credential_ttl_seconds: Some(seconds),
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        // Remaining lifetime of the credentials vended when loading the table, in minutes
+        let load_ttl_minutes = |namespace: iceberg::NamespaceIdent, metadata: RequestMetadata| {
+            let ctx = ctx.clone();
+            let data_access = data_access.clone();
+            let prefix = ns_params.prefix.clone();
+            async move {
+                let loaded = CatalogServer::load_table(
+                    TableParameters {
+                        prefix,
+                        table: TableIdent {
+                            namespace,
+                            name: "tab-1".to_string(),
+                        },
+                    },
+                    LoadTableQuery::default(),
+                    data_access,
+                    ctx,
+                    metadata,
+                )
+                .await
+                .unwrap();
+                let config = loaded.storage_credentials.unwrap().remove(0).config;
+                let expires_at_ms = config["s3.session-token-expires-at-ms"]
+                    .parse::<i64>()
+                    .unwrap();
+                (expires_at_ms - chrono::Utc::now().timestamp_millis() + 30_000) / 60_000
+            }
+        };
+
+        let batch = create_namespace(&["batch"]).await.unwrap();
+        create_namespace(&["batch", "daily"]).await.unwrap();
+        let daily = iceberg::NamespaceIdent::from_strs(["batch", "daily"]).unwrap();
+        for namespace in [ns.namespace.clone(), daily.clone()] {
+            CatalogServer::create_table(
+                NamespaceParameters {
+                    prefix: ns_params.prefix.clone(),
+                    namespace,
+                },
+                create_request(Some("tab-1".to_string())),
+                DataAccess::none(),
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+            .await
+            .unwrap();
+        }
+
+        // TTLs outside of the server bounds are rejected
+        let err = set_credential_ttl(&batch, 30 * 24 * 3600)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "InvalidCredentialTtl");
+        set_credential_ttl(&batch, 6 * 3600).await.unwrap();
+
+        // Tables of child namespaces receive the TTL of their nearest ancestor
+        assert_eq!(
+            load_ttl_minutes(daily.clone(), RequestMetadata::new_unauthenticated()).await,
+            6 * 60
+        );
+        // Tables elsewhere receive the default of the warehouse
+        assert_eq!(
+            load_ttl_minutes(ns.namespace.clone(), RequestMetadata::new_unauthenticated()).await,
+            60
+        );
+        // Requests may shorten the TTL
+        assert_eq!(
+            load_ttl_minutes(
+                daily,
+                RequestMetadata::new_unauthenticated()
+                    .with_requested_credential_ttl(chrono::Duration::minutes(30)),
+            )
+            .await,
+            30
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_list_table_files(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let table_id = super::TableIdentUuid::from(table.metadata.uuid());
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+        let location = table.metadata.location().trim_end_matches('/').to_string();
+
+        let list_files = |snapshot_id: Option<i64>, page_token: Option<String>| {
+            ApiServer::list_table_files(
+                warehouse_id,
+                table_id,
+                TableFilesQuery {
+                    snapshot_id,
+                    page_token,
+                    page_size: 1,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        // Tables without snapshots have no files
+        let empty = list_files(None, None).await.unwrap();
+        assert_eq!(empty.snapshot_id, None);
+        assert!(empty.files.is_empty());
+        assert!(empty.next_page_token.is_none());
+
+        let mut metadata = table.metadata;
+        for _ in 0..3 {
+            metadata =
+                commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true)
+                    .await;
+        }
+
+        let mut files = vec![];
+        let mut page_token = None;
+        loop {
+            let page = list_files(None, page_token).await.unwrap();
+            assert_eq!(page.snapshot_id, Some(3));
+            assert!(page.files.len() <= 1);
+            files.extend(page.files);
+            page_token = page.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+        assert_eq!(
+            files
+                .iter()
+                .map(|f| (f.file_path.clone(), f.file_size_in_bytes, f.record_count))
+                .sorted()
+                .collect_vec(),
+            (1..=3)
+                .map(|id| (format!("{location}/data/file-{id}.parquet"), 100, 1))
+                .collect_vec()
+        );
+
+        // Earlier snapshots only reference the files added up to that snapshot
+        let first = list_files(Some(1), None).await.unwrap();
+        assert_eq!(
+            first
+                .files
+                .iter()
+                .map(|f| f.file_path.clone())
+                .collect_vec(),
+            vec![format!("{location}/data/file-1.parquet")]
+        );
+        assert!(first.next_page_token.is_none());
+
+        let err = list_files(Some(42), None).await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+        assert_eq!(err.error.r#type, "SnapshotNotFound");
+    }
+
+    #[sqlx::test]
+    async fn test_list_table_files_reads_manifests_once(pool: PgPool) {
+        let (ctx, _, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let table_id = super::TableIdentUuid::from(table.metadata.uuid());
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+
+        let mut metadata = table.metadata;
+        for _ in 0..2 {
+            metadata =
+                commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true)
+                    .await;
+        }
+        let list_files = || {
+            ApiServer::list_table_files(
+                warehouse_id,
+                table_id,
+                TableFilesQuery {
+                    snapshot_id: None,
+                    page_token: None,
+                    page_size: 100,
+                },
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+
+        let first = list_files().await.unwrap();
+        assert_eq!(first.files.len(), 2);
+
+        // Listing the same snapshot again must not read its manifests from storage
+        let manifest_list = metadata
+            .current_snapshot()
+            .unwrap()
+            .load_manifest_list(&file_io, &metadata)
+            .await
+            .unwrap();
+        assert!(!manifest_list.entries().is_empty());
+        for manifest_file in manifest_list.entries() {
+            file_io.delete(&manifest_file.manifest_path).await.unwrap();
+        }
+        let second = list_files().await.unwrap();
+        assert_eq!(second, first);
+    }
+
+    #[sqlx::test]
+    async fn test_table_storage_footprint(pool: PgPool) {
+        let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
+        let warehouse_id = WarehouseIdent::try_from(ns_params.prefix.clone().unwrap()).unwrap();
+        let table_id = super::TableIdentUuid::from(table.metadata.uuid());
+        let file_io = crate::catalog::test::test_io_profile()
+            .file_io(None)
+            .unwrap();
+
+        let mut metadata = table.metadata;
+        for _ in 0..3 {
+            metadata =
+                commit_append_with_manifest(ctx.clone(), &ns_params, metadata, &file_io, true)
+                    .await;
+        }
+
+        let footprint = ApiServer::get_table_storage_footprint(
+            warehouse_id,
+            table_id,
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        // Each append adds one manifest with a single data file of 100 bytes
+        let snapshot = metadata.current_snapshot().unwrap();
+        let manifest_list = snapshot
+            .load_manifest_list(&file_io, &metadata)
+            .await
+            .unwrap();
+        assert_eq!(manifest_list.entries().len(), 3);
+        let file_size = |location: String| {
+            let input = file_io.new_input(location).unwrap();
+            async move { input.metadata().await.unwrap().size }
+        };
+        let metadata_location = CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix,
+                table: TableIdent {
+                    namespace: ns.namespace.clone(),
+                    name: "tab-1".to_string(),
+                },
+            },
+            LoadTableQuery::default(),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap()
+        .metadata_location
+        .unwrap();
+        let metadata_files_size = file_size(metadata_location).await
+            + file_size(snapshot.manifest_list().to_string()).await
+            + manifest_list
+                .entries()
+                .iter()
+                .map(|m| u64::try_from(m.manifest_length).unwrap())
+                .sum::<u64>();
+
+        assert_eq!(
+            footprint,
+            TableStorageFootprint {
+                snapshot_id: Some(3),
+                data_files_size_in_bytes: 300,
+                delete_files_size_in_bytes: 0,
+                metadata_files_size_in_bytes: metadata_files_size,
+                approximate: false,
+            }
+        );
+    }
 }
@@ -13,7 +13,7 @@ pub(crate) fn default_table_flags() -> ListFlags {
 }
 
 macro_rules! list_entities {
-    ($entity:ident, $list_fn:ident, $action:ident, $namespace:ident, $authorizer:ident, $request_metadata:ident, $warehouse_id:ident) => {
+    ($entity:ident, $list_fn:ident, $action:ident, $namespace:ident, $name_prefix:ident, $authorizer:ident, $request_metadata:ident, $warehouse_id:ident) => {
         |ps, page_token, trx| {
             use ::paste::paste;
             paste! {
@@ -21,6 +21,7 @@ macro_rules! list_entities {
             }
             use crate::catalog::UnfilteredPage;
             let namespace = $namespace.clone();
+            let name_prefix = $name_prefix.clone();
             let authorizer = $authorizer.clone();
             let request_metadata = $request_metadata.clone();
             async move {
@@ -31,6 +32,7 @@ macro_rules! list_entities {
                 let entities = C::$list_fn(
                     $warehouse_id,
                     &namespace,
+                    name_prefix.as_deref(),
                     default_flags(),
                     trx.transaction(),
                     query,
@@ -104,7 +104,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     }
 }
 
-fn validate_view_properties<'a, I>(properties: I) -> Result<()>
+pub(crate) fn validate_view_properties<'a, I>(properties: I) -> Result<()>
 where
     I: IntoIterator<Item = &'a String>,
 {
@@ -153,7 +153,9 @@ mod test {
         },
         service::{
             authz::AllowAllAuthorizer,
+            commit_observer::CommitObservers,
             contract_verification::ContractVerifiers,
+            create_request_mutator::CreateRequestMutators,
             event_publisher::CloudEventsPublisher,
             storage::{StorageProfile, TestProfile},
             task_queue::TaskQueues,
@@ -206,12 +208,23 @@ mod test {
                 secrets: SecretsState::from_pools(pool.clone(), pool.clone()),
                 publisher: CloudEventsPublisher::new(tx.clone()),
                 contract_verifiers: ContractVerifiers::new(vec![]),
+                commit_observers: CommitObservers::new(vec![]),
+                create_request_mutators: CreateRequestMutators::new(vec![]),
                 queues: TaskQueues::new(
                     Arc::new(
                         crate::implementations::postgres::task_queues::TabularExpirationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap(),
                     ),
                     Arc::new(
-                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
+                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::SnapshotCleanupQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::StorageSecretCleanupQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::DeletedWarehousePurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
                     )
                 )
             },
@@ -10,7 +10,8 @@ use crate::{
     },
     catalog::{
         compression_codec::CompressionCodec,
-        io::write_metadata_file,
+        io::{metadata_conditional_writer, write_new_metadata_file},
+        namespace::resolve_storage_region,
         require_warehouse_id,
         tables::{
             determine_table_ident, extract_count_from_metadata_location, maybe_body_to_json,
@@ -21,8 +22,10 @@ use crate::{
     request_metadata::RequestMetadata,
     service::{
         authz::{Authorizer, CatalogViewAction, CatalogWarehouseAction},
+        commit_throttle::throttle_commit,
         contract_verification::ContractVerification,
         event_publisher::EventMetadata,
+        secret_encryption::get_storage_secret,
         secrets::SecretStore,
         storage::{StorageLocations as _, StoragePermissions},
         Catalog, GetWarehouseResponse, State, TabularIdentUuid, Transaction, ViewIdentUuid,
@@ -61,7 +64,7 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
             &CatalogWarehouseAction::CanUse,
         )
         .await?;
-    let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+    let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
     let view_id = C::view_to_id(warehouse_id, &identifier, t.transaction()).await; // We can't fail before AuthZ;
 
     let view_id = authorizer
@@ -87,8 +90,28 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
         storage_secret_id,
         status,
         tabular_delete_profile: _,
+        feature_flags: _,
+        allowed_schemes: _,
+        purge_grace_period: _,
+        manifest_merge_threshold: _,
+        max_tables_per_namespace: _,
+        max_tables_per_namespace_includes_views: _,
+        storage_prefix_overrides: _,
+        schema_evolution_policy: _,
+        allowed_file_formats: _,
+        namespace_property_schema: _,
+        annotations: _,
+        default_view_properties: _,
+        commit_rate_limit,
+        credential_refresh_before: _,
+        allowed_authentication_methods: _,
+        empty_namespace_cleanup_after: _,
+        import_prefixes: _,
+        default_namespace: _,
+        snapshot_limit: _,
     } = C::require_warehouse(warehouse_id, t.transaction()).await?;
     require_active_warehouse(status)?;
+    throttle_commit(warehouse_id, commit_rate_limit, &request_metadata)?;
 
     check_asserts(requirements.as_ref(), view_id)?;
 
@@ -98,6 +121,11 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     } = C::load_view(view_id, false, t.transaction()).await?;
     let view_location = parse_view_location(&before_update_metadata.location)?;
     let before_update_metadata_location = parse_view_location(&before_update_metadata_location)?;
+    let storage_region =
+        resolve_storage_region::<C>(warehouse_id, identifier.namespace(), &view_location, &mut t)
+            .await?;
+    let regional_profile = storage_profile.with_region(storage_region.as_deref());
+    let storage_profile = &*regional_profile;
 
     state
         .v1_state
@@ -132,23 +160,25 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     // We don't commit the transaction yet, first we need to write the metadata file.
     let storage_secret = if let Some(secret_id) = &storage_secret_id {
         Some(
-            state
-                .v1_state
-                .secrets
-                .get_secret_by_id(secret_id)
-                .await?
-                .secret,
+            get_storage_secret::<C, _>(
+                secret_id,
+                state.v1_state.catalog.clone(),
+                &state.v1_state.secrets,
+            )
+            .await?,
         )
     } else {
         None
     };
 
     let file_io = storage_profile.file_io(storage_secret.as_ref())?;
-    write_metadata_file(
+    let conditional_writer = metadata_conditional_writer(storage_profile, storage_secret.as_ref());
+    write_new_metadata_file(
         &metadata_location,
         &requested_update_metadata,
         CompressionCodec::try_from_metadata(&requested_update_metadata)?,
         &file_io,
+        conditional_writer.as_deref(),
     )
     .await?;
 
@@ -166,6 +196,7 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
             &metadata_location,
             // TODO: This should be a permission based on authz
             StoragePermissions::ReadWriteDelete,
+            None,
         )
         .await?;
     t.commit().await?;
@@ -188,7 +219,7 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
                     .unwrap_or_default(),
                 num_events: 1,
                 sequence_number: 0,
-                trace_id: request_metadata.request_id(),
+                trace_id: request_metadata.request_id().to_string(),
             },
         )
         .await;
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use iceberg::{spec::ViewMetadataBuilder, TableIdent, ViewCreation};
 use iceberg_ext::catalog::rest::{CreateViewRequest, ErrorModel, LoadViewResult};
 use uuid::Uuid;
@@ -12,11 +14,13 @@ use crate::{
     },
     catalog::{
         compression_codec::CompressionCodec,
-        io::write_metadata_file,
-        maybe_get_secret, require_warehouse_id,
+        io::{metadata_conditional_writer, write_new_metadata_file},
+        maybe_get_secret,
+        namespace::{resolve_namespace_properties, resolve_storage_prefix},
+        require_warehouse_id,
         tables::{
             determine_tabular_location, maybe_body_to_json, require_active_warehouse,
-            validate_table_or_view_ident,
+            require_namespace_capacity, storage_team, validate_table_or_view_ident,
         },
         views::validate_view_properties,
     },
@@ -78,21 +82,48 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     // ------------------- BUSINESS LOGIC -------------------
     let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
     let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
-    let storage_profile = warehouse.storage_profile;
+    let storage_profile = &warehouse.storage_profile;
     require_active_warehouse(warehouse.status)?;
+    require_namespace_capacity::<C>(
+        &warehouse,
+        namespace_id,
+        true,
+        &request_metadata,
+        t.transaction(),
+    )
+    .await?;
 
     let view_id: TabularIdentUuid = TabularIdentUuid::View(uuid::Uuid::now_v7());
 
+    let team = storage_team(&request_metadata)?;
+    let storage_prefix = resolve_storage_prefix::<C>(&namespace, &mut t).await?;
     let view_location = determine_tabular_location(
         &namespace,
         request.location.clone(),
         view_id,
-        &storage_profile,
+        &warehouse,
+        team.as_deref(),
+        storage_prefix.as_ref().map(|p| &p.location),
     )?;
+    let storage_region = storage_prefix
+        .as_ref()
+        .and_then(|p| p.region_of(&view_location));
+    let regional_profile = storage_profile.with_region(storage_region.as_deref());
+    let storage_profile = &*regional_profile;
+
+    let namespace_properties = resolve_namespace_properties::<C>(namespace, &mut t)
+        .await?
+        .effective_properties;
 
     // Update the request for event
     let mut request = request;
     request.location = Some(view_location.to_string());
+    apply_default_view_properties(
+        &warehouse.default_view_properties,
+        &namespace_properties,
+        &mut request.properties,
+    );
+    validate_view_properties(request.properties.keys())?;
     let request = request; // make it immutable
 
     let metadata_location = storage_profile.default_metadata_location(
@@ -136,12 +167,24 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     .await?;
 
     // We don't commit the transaction yet, first we need to write the metadata file.
-    let storage_secret =
-        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    let storage_secret = maybe_get_secret::<C, _>(
+        warehouse.storage_secret_id,
+        state.v1_state.catalog.clone(),
+        &state.v1_state.secrets,
+    )
+    .await?;
 
     let file_io = storage_profile.file_io(storage_secret.as_ref())?;
     let compression_codec = CompressionCodec::try_from_metadata(&metadata)?;
-    write_metadata_file(&metadata_location, &metadata, compression_codec, &file_io).await?;
+    let conditional_writer = metadata_conditional_writer(storage_profile, storage_secret.as_ref());
+    write_new_metadata_file(
+        &metadata_location,
+        &metadata,
+        compression_codec,
+        &file_io,
+        conditional_writer.as_deref(),
+    )
+    .await?;
     tracing::debug!("Wrote new metadata file to: '{}'", metadata_location);
 
     // Generate the storage profile. This requires the storage secret
@@ -156,6 +199,7 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
             storage_secret.as_ref(),
             &view_location,
             StoragePermissions::Read,
+            None,
         )
         .await?;
 
@@ -184,7 +228,7 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
                 num_events: 1,
                 sequence_number: 0,
-                trace_id: request_metadata.request_id(),
+                trace_id: request_metadata.request_id().to_string(),
             },
         )
         .await;
@@ -198,6 +242,35 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     Ok(load_view_result)
 }
 
+/// Prefix of namespace properties that are applied to new views of the namespace
+/// and its children, for example `view-default.dialect`.
+const NAMESPACE_VIEW_DEFAULT_PREFIX: &str = "view-default.";
+
+/// Add inherited defaults to the properties of a new view.
+///
+/// Namespace defaults override warehouse defaults. Properties set on the view
+/// itself override all defaults.
+fn apply_default_view_properties(
+    warehouse_defaults: &HashMap<String, String>,
+    namespace_properties: &HashMap<String, String>,
+    properties: &mut HashMap<String, String>,
+) {
+    let namespace_defaults = namespace_properties.iter().filter_map(|(key, value)| {
+        key.strip_prefix(NAMESPACE_VIEW_DEFAULT_PREFIX)
+            .filter(|key| !key.is_empty())
+            .map(|key| (key.to_string(), value.clone()))
+    });
+    let defaults = warehouse_defaults
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .chain(namespace_defaults)
+        .collect::<HashMap<_, _>>();
+
+    for (key, value) in defaults {
+        properties.entry(key).or_insert(value);
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use iceberg::NamespaceIdent;
@@ -206,6 +279,13 @@ pub(crate) mod test {
 
     use super::*;
     use crate::{
+        api::{
+            iceberg::v1::ViewParameters,
+            management::v1::{
+                warehouse::{Service as _, UpdateWarehouseDefaultViewPropertiesRequest},
+                ApiServer,
+            },
+        },
         implementations::postgres::{
             namespace::tests::initialize_namespace, secrets::SecretsState,
         },
@@ -290,6 +370,84 @@ pub(crate) mod test {
             .expect("Recreate with same name but different ns should work.");
     }
 
+    #[sqlx::test]
+    async fn test_create_view_inherits_default_properties(pool: PgPool) {
+        let (api_context, _, whi) = crate::catalog::views::test::setup(pool, None).await;
+        let update_warehouse_defaults = |defaults: &[(&str, &str)]| {
+            ApiServer::update_warehouse_default_view_properties(
+                whi,
+                UpdateWarehouseDefaultViewPropertiesRequest {
+                    default_view_properties: defaults
+                        .iter()
+                        .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                        .collect(),
+                },
+                api_context.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        update_warehouse_defaults(&[("dialect", "spark"), ("owner", "platform")])
+            .await
+            .unwrap();
+
+        let namespace = NamespaceIdent::from_vec(vec![Uuid::now_v7().to_string()]).unwrap();
+        initialize_namespace(
+            api_context.v1_state.catalog.clone(),
+            whi,
+            &namespace,
+            Some(HashMap::from_iter([
+                ("view-default.dialect".to_string(), "trino".to_string()),
+                (
+                    "view-default.engine_version".to_string(),
+                    "Trino 440".to_string(),
+                ),
+            ])),
+        )
+        .await;
+
+        let view = create_view(
+            api_context.clone(),
+            namespace.clone(),
+            create_view_request(None, None),
+            Some(whi.to_string()),
+        )
+        .await
+        .unwrap();
+        let properties = view.metadata.properties();
+        // Namespace defaults override warehouse defaults
+        assert_eq!(properties.get("dialect"), Some(&"trino".to_string()));
+        assert_eq!(properties.get("owner"), Some(&"platform".to_string()));
+        // Explicit properties win
+        assert_eq!(
+            properties.get("engine_version"),
+            Some(&"Spark 3.5.1".to_string())
+        );
+        assert!(!properties.contains_key("view-default.dialect"));
+
+        // Changing defaults does not change existing views
+        update_warehouse_defaults(&[("owner", "analytics")])
+            .await
+            .unwrap();
+        let loaded = crate::catalog::views::load::load_view(
+            ViewParameters {
+                prefix: Some(Prefix(whi.to_string())),
+                view: TableIdent::new(namespace, "myview".to_string()),
+            },
+            api_context,
+            DataAccess {
+                vended_credentials: false,
+                remote_signing: false,
+            },
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            loaded.metadata.properties().get("owner"),
+            Some(&"platform".to_string())
+        );
+    }
+
     pub(crate) fn create_view_request(
         name: Option<&str>,
         location: Option<&str>,
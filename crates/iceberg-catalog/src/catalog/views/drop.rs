@@ -82,6 +82,7 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                         warehouse_ident: warehouse_id,
                         tabular_type: TabularType::View,
                         parent_id: None,
+                        purge_after: TabularPurgeInput::purge_after(warehouse.purge_grace_period),
                     })
                     .await?;
                 tracing::debug!("Queued purge task for dropped view '{view_id}'.");
@@ -122,7 +123,7 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
                 num_events: 1,
                 sequence_number: 0,
-                trace_id: request_metadata.request_id(),
+                trace_id: request_metadata.request_id().to_string(),
             },
         )
         .await;
@@ -22,6 +22,7 @@ pub(crate) async fn list_views<C: Catalog, A: Authorizer + Clone, S: SecretStore
     request_metadata: RequestMetadata,
 ) -> Result<ListTablesResponse> {
     let return_uuids = query.return_uuids;
+    let name_prefix = query.name_prefix;
     // ------------------- VALIDATIONS -------------------
     let NamespaceParameters { namespace, prefix } = parameters;
     let warehouse_id = require_warehouse_id(prefix)?;
@@ -59,6 +60,7 @@ pub(crate) async fn list_views<C: Catalog, A: Authorizer + Clone, S: SecretStore
                 list_views,
                 view_action,
                 namespace,
+                name_prefix,
                 authorizer,
                 request_metadata,
                 warehouse_id
@@ -216,6 +218,7 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(11),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -231,6 +234,7 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -246,6 +250,7 @@ mod test {
                 page_token: PageToken::Present(all.next_page_token.unwrap()),
                 page_size: Some(10),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -262,6 +267,7 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(6),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -287,6 +293,7 @@ mod test {
                 page_token: PageToken::Present(first_six.next_page_token.unwrap()),
                 page_size: Some(6),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -321,6 +328,7 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(5),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -347,6 +355,7 @@ mod test {
                 page_token: PageToken::Present(page.next_page_token.unwrap()),
                 page_size: Some(6),
                 return_uuids: true,
+                name_prefix: None,
             },
             ctx.clone(),
             RequestMetadata::new_unauthenticated(),
@@ -6,6 +6,7 @@ use crate::{
         set_not_found_status_code, ApiContext,
     },
     catalog::{
+        namespace::resolve_storage_region,
         require_warehouse_id,
         tables::{require_active_warehouse, validate_table_or_view_ident},
         views::parse_view_location,
@@ -13,6 +14,7 @@ use crate::{
     request_metadata::RequestMetadata,
     service::{
         authz::{Authorizer, CatalogViewAction, CatalogWarehouseAction},
+        secret_encryption::get_storage_secret,
         storage::{StorageCredential, StoragePermissions},
         Catalog, GetWarehouseResponse, Result, SecretStore, State, Transaction,
         ViewMetadataWithLocation,
@@ -52,7 +54,7 @@ pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             &CatalogWarehouseAction::CanUse,
         )
         .await?;
-    let mut t = C::Transaction::begin_read(state.v1_state.catalog).await?;
+    let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
     let view_id = C::view_to_id(warehouse_id, &view, t.transaction()).await; // We can't fail before AuthZ
     let view_id = authorizer
         .require_view_action(
@@ -72,6 +74,25 @@ pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         storage_secret_id,
         status,
         tabular_delete_profile: _,
+        feature_flags: _,
+        allowed_schemes: _,
+        purge_grace_period: _,
+        manifest_merge_threshold: _,
+        max_tables_per_namespace: _,
+        max_tables_per_namespace_includes_views: _,
+        storage_prefix_overrides: _,
+        schema_evolution_policy: _,
+        allowed_file_formats: _,
+        namespace_property_schema: _,
+        annotations: _,
+        default_view_properties: _,
+        commit_rate_limit: _,
+        credential_refresh_before: _,
+        allowed_authentication_methods: _,
+        empty_namespace_cleanup_after: _,
+        import_prefixes: _,
+        default_namespace: _,
+        snapshot_limit: _,
     } = C::require_warehouse(warehouse_id, t.transaction()).await?;
     require_active_warehouse(status)?;
 
@@ -81,29 +102,33 @@ pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     } = C::load_view(view_id, false, t.transaction()).await?;
 
     let view_location = parse_view_location(&view_metadata.location)?;
+    let storage_region =
+        resolve_storage_region::<C>(warehouse_id, &view.namespace, &view_location, &mut t).await?;
 
     t.commit().await?;
 
     let storage_secret: Option<StorageCredential> = if let Some(secret_id) = &storage_secret_id {
         Some(
-            state
-                .v1_state
-                .secrets
-                .get_secret_by_id(secret_id)
-                .await?
-                .secret,
+            get_storage_secret::<C, _>(
+                secret_id,
+                state.v1_state.catalog.clone(),
+                &state.v1_state.secrets,
+            )
+            .await?,
         )
     } else {
         None
     };
 
     let access = storage_profile
+        .with_region(storage_region.as_deref())
         .generate_table_config(
             &data_access,
             storage_secret.as_ref(),
             &view_location,
             // TODO: This should be a permission based on authz
             StoragePermissions::ReadWriteDelete,
+            None,
         )
         .await?;
     let load_table_result = LoadViewResult {
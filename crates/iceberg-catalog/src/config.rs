@@ -4,8 +4,9 @@
 
 use core::result::Result::Ok;
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashMap, HashSet},
     convert::Infallible,
+    net::IpAddr,
     ops::{Deref, DerefMut},
     path::PathBuf,
     str::FromStr,
@@ -13,13 +14,29 @@ use std::{
 };
 
 use anyhow::{anyhow, Context};
-use http::HeaderValue;
+use http::{HeaderName, HeaderValue};
 use itertools::Itertools;
 use serde::{Deserialize, Deserializer, Serialize};
 use url::Url;
 use veil::Redact;
 
-use crate::{service::task_queue::TaskQueueConfig, ProjectId, WarehouseIdent};
+use crate::{
+    catalog::{
+        namespace_cleanup::NamespaceCleanupConfig, snapshot_expiration::SnapshotExpirationConfig,
+    },
+    logging::LogFormat,
+    request_metadata::X_REQUEST_ID_HEADER,
+    service::{
+        audit::AuditLogConfig,
+        authz::reconciler::AuthzReconcilerConfig,
+        backend_timeout::BackendTimeouts,
+        contract_verification::ContractVerifierUnavailablePolicy,
+        feature_flags::WarehouseFeatureFlag,
+        task_queue::{seconds_to_std_duration, std_duration_to_seconds, TaskQueueConfig},
+        token_verification::{DEFAULT_TOKEN_CLOCK_SKEW_LEEWAY, MAX_TOKEN_CLOCK_SKEW_LEEWAY},
+    },
+    ProjectId, WarehouseIdent,
+};
 
 const DEFAULT_RESERVED_NAMESPACES: [&str; 3] = ["system", "examples", "information_schema"];
 const DEFAULT_ENCRYPTION_KEY: &str = "<This is unsafe, please set a proper key>";
@@ -57,6 +74,9 @@ fn get_config() -> DynAppConfig {
     config
         .reserved_namespaces
         .extend(DEFAULT_RESERVED_NAMESPACES.into_iter().map(str::to_string));
+    config
+        .reserved_namespaces
+        .insert(config.default_namespace_alias.to_lowercase());
 
     // Fail early if the base_uri is not a valid URL
     if let Some(uri) = &config.base_uri {
@@ -70,6 +90,21 @@ fn get_config() -> DynAppConfig {
         tracing::warn!("THIS IS UNSAFE! Using default encryption key for secrets in postgres, please set a proper key using ICEBERG_REST__PG_ENCRYPTION_KEY environment variable.");
     }
 
+    if config.token_clock_skew_leeway > MAX_TOKEN_CLOCK_SKEW_LEEWAY {
+        tracing::warn!(
+            "Token clock skew leeway of {}s exceeds the maximum, using {}s instead.",
+            config.token_clock_skew_leeway.as_secs(),
+            MAX_TOKEN_CLOCK_SKEW_LEEWAY.as_secs()
+        );
+        config.token_clock_skew_leeway = MAX_TOKEN_CLOCK_SKEW_LEEWAY;
+    }
+
+    for flag in config.warehouse_feature_flags.keys() {
+        if WarehouseFeatureFlag::parse(flag).is_none() {
+            tracing::warn!("Ignoring unknown warehouse feature flag '{flag}' in configuration.");
+        }
+    }
+
     config
 }
 
@@ -101,6 +136,14 @@ pub struct DynAppConfig {
         serialize_with = "serialize_origin"
     )]
     pub allow_origin: Option<Vec<HeaderValue>>,
+    /// Header carrying the correlation ID of a request. An inbound value is kept,
+    /// otherwise a new ID is generated. The ID is attached to logs and events and
+    /// returned in the same response header.
+    #[serde(
+        deserialize_with = "deserialize_header_name",
+        serialize_with = "serialize_header_name"
+    )]
+    pub request_id_header: HeaderName,
     /// Reserved namespaces that cannot be created by users.
     /// This is used to prevent users to create certain
     /// (sub)-namespaces. By default, `system` and `examples` are
@@ -110,6 +153,10 @@ pub struct DynAppConfig {
         serialize_with = "serialize_reserved_namespaces"
     )]
     pub reserved_namespaces: ReservedNamespaces,
+    /// Single-part namespace that table operations may use to address the
+    /// default namespace of a warehouse. The alias is reserved and cannot be
+    /// created as a namespace.
+    pub default_namespace_alias: String,
     // ------------- POSTGRES IMPLEMENTATION -------------
     #[redact]
     pub(crate) pg_encryption_key: String,
@@ -129,6 +176,8 @@ pub struct DynAppConfig {
     pub(crate) pg_connection_max_lifetime: Option<u64>,
     pub pg_read_pool_connections: u32,
     pub pg_write_pool_connections: u32,
+    /// Retries of catalog reads on transient database errors.
+    pub pg_retry: PgRetryConfig,
 
     // ------------- NATS CLOUDEVENTS -------------
     pub nats_address: Option<Url>,
@@ -143,6 +192,13 @@ pub struct DynAppConfig {
     // ------------- TRACING CLOUDEVENTS ----------
     pub log_cloudevents: Option<bool>,
 
+    // ------------- LOGGING -------------
+    /// Format of the log output. Defaults to `json`.
+    pub log_format: LogFormat,
+    /// Key of the hash of principals in logs. Random per process if not set.
+    #[redact]
+    pub(crate) log_principal_hash_key: Option<String>,
+
     // ------------- AUTHENTICATION -------------
     pub openid_provider_uri: Option<Url>,
     /// Expected audience for the provided token.
@@ -163,6 +219,29 @@ pub struct DynAppConfig {
     pub enable_kubernetes_authentication: bool,
     /// Claim to use in provided JWT tokens as the subject.
     pub openid_subject_claim: Option<String>,
+    /// Claim in provided JWT tokens that holds the team of the principal.
+    /// If set, tables and views are stored below a per-team prefix
+    /// of the warehouse.
+    pub openid_team_claim: Option<String>,
+    /// Claim in provided JWT tokens that holds a path relative to the table location.
+    /// If set and present in the token, vended credentials are narrowed to this
+    /// sub-prefix of the table location.
+    pub credential_prefix_claim: Option<String>,
+    /// Identity providers that issue opaque tokens, keyed by IdP id.
+    /// Tokens that are not JWTs are validated via the introspection
+    /// endpoint of these providers instead of JWKS.
+    pub token_introspection: BTreeMap<String, TokenIntrospectionConfig>,
+    /// Tolerance for clock skew between identity providers, clients and Lakekeeper,
+    /// applied to the `nbf` and `exp` of introspected tokens.
+    /// Capped at 300 seconds.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub token_clock_skew_leeway: std::time::Duration,
+    /// Authenticate clients via certificates forwarded by a TLS-terminating proxy.
+    /// If a client certificate is present, it takes precedence over bearer tokens.
+    pub client_certificate_authentication: Option<ClientCertificateAuthConfig>,
 
     // ------------- AUTHORIZATION - OPENFGA -------------
     #[serde(default)]
@@ -181,17 +260,121 @@ pub struct DynAppConfig {
     pub kv2: Option<KV2Config>,
     // ------------- Secrets -------------
     pub secret_backend: SecretBackend,
+    /// Encrypt storage credentials with a data key of their warehouse before they
+    /// are handed to the secret backend. Disabled if not set.
+    #[serde(default, deserialize_with = "deserialize_secret_envelope_encryption")]
+    pub secret_envelope_encryption: Option<SecretEnvelopeEncryptionConfig>,
 
     // ------------- Queues -------------
     pub queue_config: TaskQueueConfig,
 
+    // ------------- Authz Reconciler -------------
+    pub authz_reconciler: AuthzReconcilerConfig,
+
+    // ------------- Snapshot Expiration -------------
+    pub snapshot_expiration: SnapshotExpirationConfig,
+
+    // ------------- Namespace Cleanup -------------
+    pub namespace_cleanup: NamespaceCleanupConfig,
+
+    // ------------- Audit Log -------------
+    pub audit_log: AuditLogConfig,
+
+    // ------------- Contract Verification -------------
+    /// Whether changes are blocked or allowed if a contract verifier fails.
+    pub contract_verifier_unavailable_policy: ContractVerifierUnavailablePolicy,
+
+    // ------------- Backend Timeouts -------------
+    pub backend_timeouts: BackendTimeouts,
+    /// Retries of storage reads on transient errors.
+    pub storage_read_retry: StorageReadRetryConfig,
+    /// Metadata files larger than this are not read. Applies to the
+    /// decompressed size of compressed metadata files.
+    pub max_metadata_file_size_bytes: u64,
+    /// Write new metadata files only if no file exists at their location yet.
+    /// Storages without conditional writes fall back to unconditional writes.
+    pub conditional_metadata_writes: bool,
+    /// Recursive operations on namespaces, such as recursive drops and renames,
+    /// are rejected if they affect more child namespaces, tables and views.
+    pub max_objects_per_recursive_operation: usize,
+    /// Total size of parsed manifests cached for listing the files of tables.
+    /// Disabled if 0.
+    pub manifest_cache_size_bytes: u64,
+
+    // ------------- HTTP -------------
+    /// Compression of responses negotiated via the `Accept-Encoding` header.
+    pub response_compression: ResponseCompressionConfig,
+    /// Limits on concurrently processed requests.
+    pub load_shedding: LoadSheddingConfig,
+    /// Warnings for requests that approach a hard limit.
+    pub soft_limits: SoftLimitsConfig,
+
     // ------------- Tabular -------------
+    /// Batching of concurrent appends to the same table into a single commit.
+    pub group_commit: GroupCommitConfig,
+    /// Bound on the number of commits to the same table processed concurrently.
+    pub commit_concurrency: CommitConcurrencyConfig,
+    /// Share a single fetch of the table metadata between concurrent loads of
+    /// the same table version.
+    pub deduplicate_table_loads: bool,
+    /// Attach the failed requirement and the current state of the table to
+    /// conflict errors of commits.
+    pub commit_conflict_diagnostics: bool,
     /// Delay in seconds after which a tabular will be deleted
     #[serde(
         deserialize_with = "seconds_to_duration",
         serialize_with = "duration_to_seconds"
     )]
     pub default_tabular_expiration_delay_seconds: chrono::Duration,
+    /// Duration for which results of requests with an `Idempotency-Key` header are retained
+    #[serde(
+        deserialize_with = "seconds_to_duration",
+        serialize_with = "duration_to_seconds"
+    )]
+    pub idempotency_key_ttl_seconds: chrono::Duration,
+    /// Interval at which expired idempotency keys are deleted.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub idempotency_key_prune_interval: std::time::Duration,
+
+    // ------------- Storage -------------
+    /// Time the previous storage secret of a warehouse remains usable after a
+    /// credential rotation, unless the rotation request specifies otherwise.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub storage_credential_rotation_overlap: std::time::Duration,
+    /// Time before the expiry of vended credentials at which clients should
+    /// request new credentials, unless the warehouse specifies otherwise.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub credential_refresh_before: std::time::Duration,
+    /// Shortest lifetime of vended credentials that namespaces may configure
+    /// as default or clients may request.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub min_credential_ttl: std::time::Duration,
+    /// Longest lifetime of vended credentials that namespaces may configure as default.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub max_credential_ttl: std::time::Duration,
+    /// Emit a `credentials.vended` event whenever storage credentials are vended.
+    /// Disabled by default, as credentials are vended on most table loads.
+    pub credential_vending_events: bool,
+
+    // ------------- Feature Flags -------------
+    /// Server-wide defaults for warehouse feature flags.
+    /// Warehouses may override each flag individually.
+    pub warehouse_feature_flags: HashMap<String, bool>,
 
     // ------------- Internal -------------
     /// Optional server id. We recommend to not change this unless multiple catalogs
@@ -261,6 +444,21 @@ where
         .transpose()
 }
 
+fn deserialize_header_name<'de, D>(deserializer: D) -> Result<HeaderName, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let name = String::deserialize(deserializer)?;
+    HeaderName::try_from(name.trim()).map_err(serde::de::Error::custom)
+}
+
+fn serialize_header_name<S>(value: &HeaderName, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.as_str().serialize(serializer)
+}
+
 #[allow(clippy::ref_option)]
 fn serialize_origin<S>(value: &Option<Vec<HeaderValue>>, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -310,6 +508,364 @@ pub struct OpenFGAConfig {
     /// Authentication configuration
     #[serde(default)]
     pub auth: OpenFGAAuth,
+    /// Relations checked for catalog actions, keyed by object type and action.
+    /// Actions without a mapping check the relation of the same name.
+    #[serde(default)]
+    pub relation_mapping: BTreeMap<String, BTreeMap<String, OpenFGARelationMapping>>,
+    /// Create the store and publish the active authorization model on startup
+    /// if they are missing.
+    #[serde(default)]
+    pub bootstrap: bool,
+    /// Maximum number of pages of 100 tuples read by listings such as role
+    /// assignments. Listings that reach the limit return a partial result
+    /// with a continuation token.
+    #[serde(default = "default_openfga_max_pages")]
+    pub max_pages: u32,
+    /// Remember the outcome of each check for the duration of a request, so that
+    /// repeated checks of the same relation within a request query `OpenFGA` only once.
+    #[serde(default = "default_openfga_request_check_cache")]
+    pub request_check_cache: bool,
+    /// Record the latency and outcome of checks, reads and writes against `OpenFGA`.
+    #[serde(default = "default_openfga_metrics")]
+    pub metrics: bool,
+    /// Store the relations of each project in its own store `{store_name}-{project_id}`,
+    /// created on first use. Server-level relations remain in the store `store_name`.
+    #[serde(default)]
+    pub store_per_project: bool,
+    /// Directory service resolving the groups of principals. Principals are treated
+    /// as assignees of the roles returned for them. See `HttpGroupResolver`.
+    #[serde(default)]
+    pub group_resolver_url: Option<Url>,
+    /// Seconds for which the groups of a principal are cached.
+    #[serde(default = "default_openfga_group_cache_ttl_seconds")]
+    pub group_cache_ttl_seconds: u64,
+}
+
+/// Relation and object type checked for a catalog action instead of the
+/// defaults of the Lakekeeper authorization model.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct OpenFGARelationMapping {
+    pub relation: String,
+    /// Type of the object the relation is checked on. Defaults to the type of
+    /// the object the action is performed on. The id of the object is kept.
+    #[serde(default)]
+    pub object_type: Option<String>,
+}
+
+/// OAuth 2.0 Token Introspection (RFC 7662) endpoint of an identity provider.
+#[derive(Clone, Serialize, Deserialize, PartialEq, veil::Redact)]
+pub struct TokenIntrospectionConfig {
+    pub introspection_endpoint: Url,
+    /// If set, the `iss` of introspected tokens must match.
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// Client credentials used to authenticate against the introspection endpoint.
+    pub client_id: String,
+    #[redact]
+    pub client_secret: String,
+}
+
+/// Master keys of the envelope encryption of storage credentials. Each warehouse
+/// has its own data key, which is stored wrapped with the master key.
+#[derive(Clone, Serialize, Deserialize, PartialEq, veil::Redact)]
+pub struct SecretEnvelopeEncryptionConfig {
+    /// Base64-encoded 256 bit key. Data keys are wrapped with this key.
+    #[redact]
+    pub master_key: String,
+    /// Base64-encoded keys previously used as `master_key`, comma-separated.
+    /// Data keys still wrapped with one of them are re-wrapped on startup.
+    #[redact]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_audience",
+        serialize_with = "serialize_audience"
+    )]
+    pub previous_master_keys: Option<Vec<String>>,
+}
+
+/// Mutual TLS is terminated by a proxy in front of Lakekeeper, which forwards the
+/// verified client certificate in a header. Lakekeeper verifies the certificate
+/// again against its own trust anchors before mapping it to a principal.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClientCertificateAuthConfig {
+    /// PEM bundle with the CA certificates that client certificates must chain up to.
+    /// Intermediate CAs may be part of the bundle or be forwarded after the client certificate.
+    pub trust_anchor: PathBuf,
+    /// Header holding the client certificate, either URL-encoded PEM or base64-encoded DER.
+    /// The proxy must overwrite this header on every request.
+    #[serde(default = "default_client_certificate_header")]
+    pub header: String,
+    /// Template of the principal id. Supported placeholders are `{subject.cn}`,
+    /// `{subject.o}`, `{subject.ou}`, `{san.dns}`, `{san.uri}` and `{san.email}`.
+    #[serde(default = "default_client_certificate_principal_template")]
+    pub principal_template: String,
+    /// Comma separated addresses of the proxies allowed to forward client certificates.
+    /// The header is removed from requests of all other peers.
+    #[serde(
+        deserialize_with = "deserialize_ip_addrs",
+        serialize_with = "serialize_ip_addrs"
+    )]
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+fn default_client_certificate_header() -> String {
+    "x-ssl-client-cert".to_string()
+}
+
+fn default_client_certificate_principal_template() -> String {
+    "{subject.cn}".to_string()
+}
+
+fn deserialize_ip_addrs<'de, D>(deserializer: D) -> Result<Vec<IpAddr>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer)?
+        .split(',')
+        .map(str::trim)
+        .filter(|addr| !addr.is_empty())
+        .map(|addr| IpAddr::from_str(addr).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+fn serialize_ip_addrs<S>(value: &[IpAddr], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+/// Retry policy for catalog reads and idempotent writes that fail with a
+/// transient database error, such as a connection reset during a failover.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PgRetryConfig {
+    /// Number of retries after the initial attempt. `0` disables retries.
+    pub max_retries: u32,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub initial_backoff: std::time::Duration,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub max_backoff: std::time::Duration,
+}
+
+impl Default for PgRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: std::time::Duration::from_millis(50),
+            max_backoff: std::time::Duration::from_secs(1),
+        }
+    }
+}
+
+/// Retry policy for storage reads that fail with a transient error, such as
+/// throttling or a server error of the object store.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StorageReadRetryConfig {
+    /// Number of retries after the initial attempt. `0` disables retries.
+    pub max_retries: u32,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub initial_backoff: std::time::Duration,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub max_backoff: std::time::Duration,
+}
+
+impl Default for StorageReadRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: std::time::Duration::from_millis(100),
+            max_backoff: std::time::Duration::from_secs(2),
+        }
+    }
+}
+
+/// Transport compression of HTTP responses with gzip, deflate, brotli or zstd.
+/// This is independent of the compression of metadata files in storage.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResponseCompressionConfig {
+    /// If false, responses are never compressed.
+    pub enabled: bool,
+    /// Responses smaller than this are sent uncompressed.
+    pub min_size_bytes: u64,
+}
+
+impl Default for ResponseCompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_size_bytes: 32,
+        }
+    }
+}
+
+/// Requests exceeding one of the limits are rejected with
+/// `503 Service Unavailable`. Requests exceeding the global limit may wait in a
+/// bounded queue first, from which they are admitted by priority.
+/// Health endpoints are never rejected.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LoadSheddingConfig {
+    /// Maximum number of requests processed concurrently. Unlimited if not set.
+    pub max_in_flight_requests: Option<usize>,
+    /// Maximum number of requests processed concurrently for a single
+    /// warehouse. Unlimited if not set.
+    pub max_in_flight_requests_per_warehouse: Option<usize>,
+    /// Seconds clients are asked to wait via the `Retry-After` header.
+    pub retry_after_seconds: u64,
+    /// Maximum number of requests waiting for a slot once `max_in_flight_requests`
+    /// is reached. Requests are rejected right away if 0.
+    pub max_queued_requests: usize,
+    /// Time a request waits in the queue before it is rejected.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub max_queue_wait: std::time::Duration,
+    /// Highest priority clients may request via the `x-lakekeeper-priority` header.
+    /// Higher requested priorities are lowered to this one.
+    pub max_requested_priority: RequestPriority,
+}
+
+impl LoadSheddingConfig {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.max_in_flight_requests.is_some() || self.max_in_flight_requests_per_warehouse.is_some()
+    }
+}
+
+impl Default for LoadSheddingConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight_requests: None,
+            max_in_flight_requests_per_warehouse: None,
+            retry_after_seconds: 1,
+            max_queued_requests: 0,
+            max_queue_wait: std::time::Duration::from_secs(5),
+            max_requested_priority: RequestPriority::Normal,
+        }
+    }
+}
+
+/// Priority of a request waiting for admission. Without a requested priority,
+/// reading requests are `normal` and writing requests `low`.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestPriority {
+    Low,
+    Normal,
+    High,
+}
+
+impl FromStr for RequestPriority {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "low" => Ok(Self::Low),
+            "normal" => Ok(Self::Normal),
+            "high" => Ok(Self::High),
+            _ => Err(anyhow!("Request priority not supported: '{}'", s)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RequestPriority {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        RequestPriority::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Requests that bring a resource close to a hard limit, such as the maximum
+/// number of tables in a namespace, succeed but carry a `Warning` header
+/// describing the approaching limit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SoftLimitsConfig {
+    /// If false, no warnings are attached. Hard limits are always enforced.
+    pub enabled: bool,
+    /// Fraction of a hard limit from which on a warning is attached.
+    pub threshold: f64,
+}
+
+impl SoftLimitsConfig {
+    /// Whether `value` reached the soft limit for `hard_limit` without exceeding the hard limit.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn is_reached(&self, value: u64, hard_limit: u64) -> bool {
+        self.enabled && value <= hard_limit && value as f64 >= self.threshold * hard_limit as f64
+    }
+}
+
+impl Default for SoftLimitsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 0.8,
+        }
+    }
+}
+
+/// Concurrent appends to the same table that arrive within `window` are
+/// committed together with a single metadata update. Each append keeps its own
+/// snapshot. Commits with other updates or requirements are never grouped.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupCommitConfig {
+    pub enabled: bool,
+    /// Time the first commit of a group waits for further commits.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub window: std::time::Duration,
+}
+
+impl Default for GroupCommitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: std::time::Duration::from_millis(10),
+        }
+    }
+}
+
+/// Commits to a table beyond the limit wait for a commit in flight to finish.
+/// If none finishes in time, they are rejected with `429 Too Many Requests`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommitConcurrencyConfig {
+    /// Maximum number of commits to a single table processed concurrently.
+    /// Unlimited if not set. Must be greater than 0.
+    #[serde(default, deserialize_with = "deserialize_max_in_flight_commits")]
+    pub max_in_flight_commits_per_table: Option<usize>,
+    /// Time a commit waits for a slot before it is rejected.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub max_wait: std::time::Duration,
+    /// Seconds clients are asked to wait via the `Retry-After` header.
+    pub retry_after_seconds: u64,
+}
+
+impl Default for CommitConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight_commits_per_table: None,
+            max_wait: std::time::Duration::from_secs(5),
+            retry_after_seconds: 1,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -351,10 +907,12 @@ impl Default for DynAppConfig {
             enable_default_project: true,
             prefix_template: "{warehouse_id}".to_string(),
             allow_origin: None,
+            request_id_header: HeaderName::from_static(X_REQUEST_ID_HEADER),
             reserved_namespaces: ReservedNamespaces(HashSet::from([
                 "system".to_string(),
                 "examples".to_string(),
             ])),
+            default_namespace_alias: "_default".to_string(),
             pg_encryption_key: DEFAULT_ENCRYPTION_KEY.to_string(),
             pg_database_url_read: None,
             pg_database_url_write: None,
@@ -370,6 +928,7 @@ impl Default for DynAppConfig {
             pg_test_before_acquire: false,
             pg_connection_max_lifetime: None,
             pg_read_pool_connections: 10,
+            pg_retry: PgRetryConfig::default(),
             pg_write_pool_connections: 5,
             nats_address: None,
             nats_topic: None,
@@ -378,12 +937,19 @@ impl Default for DynAppConfig {
             nats_password: None,
             nats_token: None,
             log_cloudevents: None,
+            log_format: LogFormat::Json,
+            log_principal_hash_key: None,
             openid_provider_uri: None,
             openid_audience: None,
             openid_additional_issuers: None,
             openid_scope: None,
             enable_kubernetes_authentication: false,
             openid_subject_claim: None,
+            openid_team_claim: None,
+            credential_prefix_claim: None,
+            token_introspection: BTreeMap::new(),
+            token_clock_skew_leeway: DEFAULT_TOKEN_CLOCK_SKEW_LEEWAY,
+            client_certificate_authentication: None,
             listen_port: 8181,
             health_check_frequency_seconds: 10,
             health_check_jitter_millis: 500,
@@ -391,8 +957,35 @@ impl Default for DynAppConfig {
             authz_backend: AuthZBackend::AllowAll,
             openfga: None,
             secret_backend: SecretBackend::Postgres,
+            secret_envelope_encryption: None,
             queue_config: TaskQueueConfig::default(),
+            authz_reconciler: AuthzReconcilerConfig::default(),
+            snapshot_expiration: SnapshotExpirationConfig::default(),
+            namespace_cleanup: NamespaceCleanupConfig::default(),
+            audit_log: AuditLogConfig::default(),
+            contract_verifier_unavailable_policy: ContractVerifierUnavailablePolicy::default(),
+            backend_timeouts: BackendTimeouts::default(),
+            storage_read_retry: StorageReadRetryConfig::default(),
+            max_metadata_file_size_bytes: 512 * 1024 * 1024,
+            conditional_metadata_writes: false,
+            max_objects_per_recursive_operation: 1000,
+            manifest_cache_size_bytes: 64 * 1024 * 1024,
+            response_compression: ResponseCompressionConfig::default(),
+            load_shedding: LoadSheddingConfig::default(),
+            soft_limits: SoftLimitsConfig::default(),
+            group_commit: GroupCommitConfig::default(),
+            commit_concurrency: CommitConcurrencyConfig::default(),
+            deduplicate_table_loads: true,
+            commit_conflict_diagnostics: true,
             default_tabular_expiration_delay_seconds: chrono::Duration::days(7),
+            idempotency_key_ttl_seconds: chrono::Duration::hours(24),
+            idempotency_key_prune_interval: std::time::Duration::from_secs(60 * 60),
+            storage_credential_rotation_overlap: std::time::Duration::from_secs(15 * 60),
+            credential_refresh_before: std::time::Duration::from_secs(5 * 60),
+            min_credential_ttl: std::time::Duration::from_secs(15 * 60),
+            max_credential_ttl: std::time::Duration::from_secs(12 * 60 * 60),
+            credential_vending_events: false,
+            warehouse_feature_flags: HashMap::new(),
             server_id: uuid::Uuid::nil(),
         }
     }
@@ -410,6 +1003,8 @@ impl DynAppConfig {
 
     pub fn authn_enabled(&self) -> bool {
         self.openid_provider_uri.is_some()
+            || !self.token_introspection.is_empty()
+            || self.client_certificate_authentication.is_some()
     }
 }
 
@@ -523,12 +1118,70 @@ struct OpenFGAConfigSerde {
     client_secret: Option<String>,
     /// Token Endpoint to use when exchanging client credentials for an access token.
     token_endpoint: Option<Url>,
+    #[serde(default)]
+    relation_mapping: BTreeMap<String, BTreeMap<String, OpenFGARelationMapping>>,
+    #[serde(default)]
+    bootstrap: bool,
+    #[serde(default = "default_openfga_max_pages")]
+    max_pages: u32,
+    #[serde(default = "default_openfga_request_check_cache")]
+    request_check_cache: bool,
+    #[serde(default = "default_openfga_metrics")]
+    metrics: bool,
+    #[serde(default)]
+    store_per_project: bool,
+    #[serde(default)]
+    group_resolver_url: Option<Url>,
+    #[serde(default = "default_openfga_group_cache_ttl_seconds")]
+    group_cache_ttl_seconds: u64,
 }
 
 fn default_openfga_store_name() -> String {
     "lakekeeper".to_string()
 }
 
+fn default_openfga_max_pages() -> u32 {
+    500
+}
+
+fn default_openfga_request_check_cache() -> bool {
+    true
+}
+
+fn default_openfga_metrics() -> bool {
+    true
+}
+
+fn default_openfga_group_cache_ttl_seconds() -> u64 {
+    60
+}
+
+fn deserialize_secret_envelope_encryption<'de, D>(
+    deserializer: D,
+) -> Result<Option<SecretEnvelopeEncryptionConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let config = Option::<SecretEnvelopeEncryptionConfig>::deserialize(deserializer)?;
+    if let Some(config) = &config {
+        crate::service::secret_encryption::EnvelopeEncryption::try_from_config(config)
+            .map_err(|e| serde::de::Error::custom(e.error.message))?;
+    }
+    Ok(config)
+}
+
+fn deserialize_max_in_flight_commits<'de, D>(deserializer: D) -> Result<Option<usize>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<usize>::deserialize(deserializer)? {
+        Some(0) => Err(serde::de::Error::custom(
+            "commit_concurrency max_in_flight_commits_per_table must be greater than 0",
+        )),
+        limit => Ok(limit),
+    }
+}
+
 fn deserialize_openfga_config<'de, D>(deserializer: D) -> Result<Option<OpenFGAConfig>, D::Error>
 where
     D: Deserializer<'de>,
@@ -540,6 +1193,14 @@ where
         api_key,
         endpoint,
         store_name,
+        relation_mapping,
+        bootstrap,
+        max_pages,
+        request_check_cache,
+        metrics,
+        store_per_project,
+        group_resolver_url,
+        group_cache_ttl_seconds,
     }) = Option::<OpenFGAConfigSerde>::deserialize(deserializer)?
     else {
         return Ok(None);
@@ -569,6 +1230,14 @@ where
         endpoint,
         store_name,
         auth,
+        relation_mapping,
+        bootstrap,
+        max_pages,
+        request_check_cache,
+        metrics,
+        store_per_project,
+        group_resolver_url,
+        group_cache_ttl_seconds,
     }))
 }
 
@@ -606,6 +1275,14 @@ where
         api_key,
         endpoint: value.endpoint.clone(),
         store_name: value.store_name.clone(),
+        relation_mapping: value.relation_mapping.clone(),
+        bootstrap: value.bootstrap,
+        max_pages: value.max_pages,
+        request_check_cache: value.request_check_cache,
+        metrics: value.metrics,
+        store_per_project: value.store_per_project,
+        group_resolver_url: value.group_resolver_url.clone(),
+        group_cache_ttl_seconds: value.group_cache_ttl_seconds,
     }
     .serialize(serializer)
 }
@@ -659,6 +1336,18 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_request_id_header() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert_eq!(config.request_id_header, "x-request-id");
+            jail.set_env("LAKEKEEPER_TEST__REQUEST_ID_HEADER", "X-Correlation-ID");
+            let config = get_config();
+            assert_eq!(config.request_id_header, "x-correlation-id");
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_wildcard_allow_origin() {
         figment::Jail::expect_with(|jail| {
@@ -764,6 +1453,299 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_backend_timeouts() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__BACKEND_TIMEOUTS__AUTHZ_CHECK", "500ms");
+            let config = get_config();
+            assert_eq!(
+                config.backend_timeouts.authz_check,
+                std::time::Duration::from_millis(500)
+            );
+            assert_eq!(
+                config.backend_timeouts.catalog_query,
+                BackendTimeouts::default().catalog_query
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_token_introspection() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__TOKEN_INTROSPECTION__CORP__INTROSPECTION_ENDPOINT",
+                "https://idp.example.com/introspect",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__TOKEN_INTROSPECTION__CORP__CLIENT_ID",
+                "lakekeeper",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__TOKEN_INTROSPECTION__CORP__CLIENT_SECRET",
+                "secret",
+            );
+            let config = get_config();
+            let corp = config.token_introspection.get("corp").unwrap();
+            assert_eq!(
+                corp.introspection_endpoint.as_str(),
+                "https://idp.example.com/introspect"
+            );
+            assert_eq!(corp.client_id, "lakekeeper");
+            assert_eq!(corp.issuer, None);
+            assert_eq!(
+                config.token_clock_skew_leeway,
+                std::time::Duration::from_secs(60)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_token_clock_skew_leeway_is_capped() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__TOKEN_CLOCK_SKEW_LEEWAY", "30");
+            let config = get_config();
+            assert_eq!(
+                config.token_clock_skew_leeway,
+                std::time::Duration::from_secs(30)
+            );
+
+            jail.set_env("LAKEKEEPER_TEST__TOKEN_CLOCK_SKEW_LEEWAY", "86400");
+            let config = get_config();
+            assert_eq!(config.token_clock_skew_leeway, MAX_TOKEN_CLOCK_SKEW_LEEWAY);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_client_certificate_authentication() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert_eq!(config.client_certificate_authentication, None);
+            assert!(!config.authn_enabled());
+
+            jail.set_env(
+                "LAKEKEEPER_TEST__CLIENT_CERTIFICATE_AUTHENTICATION__TRUST_ANCHOR",
+                "/etc/lakekeeper/ca.pem",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__CLIENT_CERTIFICATE_AUTHENTICATION__TRUSTED_PROXIES",
+                "10.0.0.1, fd00::1",
+            );
+            let config = get_config();
+            let mtls = config.client_certificate_authentication.as_ref().unwrap();
+            assert_eq!(mtls.trust_anchor, PathBuf::from("/etc/lakekeeper/ca.pem"));
+            assert_eq!(mtls.header, "x-ssl-client-cert");
+            assert_eq!(mtls.principal_template, "{subject.cn}");
+            assert_eq!(
+                mtls.trusted_proxies,
+                vec![
+                    IpAddr::from_str("10.0.0.1").unwrap(),
+                    IpAddr::from_str("fd00::1").unwrap()
+                ]
+            );
+            assert!(config.authn_enabled());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_response_compression() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__RESPONSE_COMPRESSION__MIN_SIZE_BYTES",
+                "1048576",
+            );
+            let config = get_config();
+            assert!(config.response_compression.enabled);
+            assert_eq!(config.response_compression.min_size_bytes, 1_048_576);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_load_shedding() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(!config.load_shedding.is_enabled());
+
+            jail.set_env(
+                "LAKEKEEPER_TEST__LOAD_SHEDDING__MAX_IN_FLIGHT_REQUESTS",
+                "512",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__LOAD_SHEDDING__MAX_IN_FLIGHT_REQUESTS_PER_WAREHOUSE",
+                "128",
+            );
+            let config = get_config();
+            assert!(config.load_shedding.is_enabled());
+            assert_eq!(config.load_shedding.max_in_flight_requests, Some(512));
+            assert_eq!(
+                config.load_shedding.max_in_flight_requests_per_warehouse,
+                Some(128)
+            );
+            assert_eq!(config.load_shedding.retry_after_seconds, 1);
+            assert_eq!(config.load_shedding.max_queued_requests, 0);
+            assert_eq!(
+                config.load_shedding.max_requested_priority,
+                RequestPriority::Normal
+            );
+
+            jail.set_env("LAKEKEEPER_TEST__LOAD_SHEDDING__MAX_QUEUED_REQUESTS", "64");
+            jail.set_env("LAKEKEEPER_TEST__LOAD_SHEDDING__MAX_QUEUE_WAIT", "2");
+            jail.set_env(
+                "LAKEKEEPER_TEST__LOAD_SHEDDING__MAX_REQUESTED_PRIORITY",
+                "High",
+            );
+            let config = get_config();
+            assert_eq!(config.load_shedding.max_queued_requests, 64);
+            assert_eq!(
+                config.load_shedding.max_queue_wait,
+                std::time::Duration::from_secs(2)
+            );
+            assert_eq!(
+                config.load_shedding.max_requested_priority,
+                RequestPriority::High
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_soft_limits() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.soft_limits.is_reached(8, 10));
+            assert!(!config.soft_limits.is_reached(7, 10));
+
+            jail.set_env("LAKEKEEPER_TEST__SOFT_LIMITS__THRESHOLD", "0.5");
+            let config = get_config();
+            assert!(config.soft_limits.is_reached(5, 10));
+            assert!(config.soft_limits.is_reached(10, 10));
+            assert!(!config.soft_limits.is_reached(11, 10));
+
+            jail.set_env("LAKEKEEPER_TEST__SOFT_LIMITS__ENABLED", "false");
+            let config = get_config();
+            assert!(!config.soft_limits.is_reached(10, 10));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_group_commit() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(!config.group_commit.enabled);
+            assert_eq!(
+                config.group_commit.window,
+                std::time::Duration::from_millis(10)
+            );
+
+            jail.set_env("LAKEKEEPER_TEST__GROUP_COMMIT__ENABLED", "true");
+            jail.set_env("LAKEKEEPER_TEST__GROUP_COMMIT__WINDOW", "25ms");
+            let config = get_config();
+            assert!(config.group_commit.enabled);
+            assert_eq!(
+                config.group_commit.window,
+                std::time::Duration::from_millis(25)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_commit_concurrency() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert_eq!(
+                config.commit_concurrency,
+                CommitConcurrencyConfig::default()
+            );
+            assert_eq!(
+                config.commit_concurrency.max_in_flight_commits_per_table,
+                None
+            );
+
+            jail.set_env(
+                "LAKEKEEPER_TEST__COMMIT_CONCURRENCY__MAX_IN_FLIGHT_COMMITS_PER_TABLE",
+                "1",
+            );
+            jail.set_env("LAKEKEEPER_TEST__COMMIT_CONCURRENCY__MAX_WAIT", "500ms");
+            let config = get_config();
+            assert_eq!(
+                config.commit_concurrency.max_in_flight_commits_per_table,
+                Some(1)
+            );
+            assert_eq!(
+                config.commit_concurrency.max_wait,
+                std::time::Duration::from_millis(500)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_storage_credential_rotation_overlap() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert_eq!(
+                config.storage_credential_rotation_overlap,
+                std::time::Duration::from_secs(900)
+            );
+
+            jail.set_env(
+                "LAKEKEEPER_TEST__STORAGE_CREDENTIAL_ROTATION_OVERLAP",
+                "3600",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.storage_credential_rotation_overlap,
+                std::time::Duration::from_secs(3600)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_credential_ttl_bounds() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert_eq!(
+                config.min_credential_ttl,
+                std::time::Duration::from_secs(900)
+            );
+            assert_eq!(
+                config.max_credential_ttl,
+                std::time::Duration::from_secs(43200)
+            );
+
+            jail.set_env("LAKEKEEPER_TEST__MAX_CREDENTIAL_TTL", "86400");
+            let config = get_config();
+            assert_eq!(
+                config.max_credential_ttl,
+                std::time::Duration::from_secs(86400)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_warehouse_feature_flags() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__WAREHOUSE_FEATURE_FLAGS__UNDROP_TABULARS",
+                "false",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.warehouse_feature_flags.get("undrop_tabulars"),
+                Some(&false)
+            );
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_openfga_config_no_auth() {
         figment::Jail::expect_with(|jail| {
@@ -775,6 +1757,13 @@ mod test {
             assert_eq!(authz_config.store_name, "store_name");
 
             assert_eq!(authz_config.auth, OpenFGAAuth::Anonymous);
+            assert!(!authz_config.bootstrap);
+            assert_eq!(authz_config.max_pages, 500);
+            assert!(authz_config.request_check_cache);
+            assert!(authz_config.metrics);
+            assert!(!authz_config.store_per_project);
+            assert_eq!(authz_config.group_resolver_url, None);
+            assert_eq!(authz_config.group_cache_ttl_seconds, 60);
 
             Ok(())
         });
@@ -785,10 +1774,30 @@ mod test {
         figment::Jail::expect_with(|jail| {
             jail.set_env("LAKEKEEPER_TEST__AUTHZ_BACKEND", "openfga");
             jail.set_env("LAKEKEEPER_TEST__OPENFGA__API_KEY", "api_key");
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__BOOTSTRAP", "true");
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__MAX_PAGES", "20");
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__REQUEST_CHECK_CACHE", "false");
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__METRICS", "false");
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__STORE_PER_PROJECT", "true");
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENFGA__GROUP_RESOLVER_URL",
+                "http://directory:8080/groups",
+            );
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__GROUP_CACHE_TTL_SECONDS", "300");
             let config = get_config();
             let authz_config = config.openfga.unwrap();
             assert_eq!(config.authz_backend, AuthZBackend::OpenFGA);
             assert_eq!(authz_config.store_name, "lakekeeper");
+            assert!(authz_config.bootstrap);
+            assert_eq!(authz_config.max_pages, 20);
+            assert!(!authz_config.request_check_cache);
+            assert!(!authz_config.metrics);
+            assert!(authz_config.store_per_project);
+            assert_eq!(
+                authz_config.group_resolver_url,
+                Some("http://directory:8080/groups".parse().unwrap())
+            );
+            assert_eq!(authz_config.group_cache_ttl_seconds, 300);
 
             assert_eq!(
                 authz_config.auth,
@@ -798,6 +1807,46 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_openfga_relation_mapping() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__AUTHZ_BACKEND", "openfga");
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENFGA__RELATION_MAPPING__TABLE__CAN_COMMIT__RELATION",
+                "writer",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENFGA__RELATION_MAPPING__TABLE__CAN_COMMIT__OBJECT_TYPE",
+                "dataset",
+            );
+            let config = get_config();
+            let authz_config = config.openfga.unwrap();
+            assert_eq!(
+                authz_config.relation_mapping["table"]["can_commit"],
+                OpenFGARelationMapping {
+                    relation: "writer".to_string(),
+                    object_type: Some("dataset".to_string()),
+                }
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "commit_concurrency max_in_flight_commits_per_table must be greater than 0"
+    )]
+    fn test_commit_concurrency_rejects_zero_commits_in_flight() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__COMMIT_CONCURRENCY__MAX_IN_FLIGHT_COMMITS_PER_TABLE",
+                "0",
+            );
+            get_config();
+            Ok(())
+        });
+    }
+
     #[test]
     #[should_panic(expected = "openfga client_secret is required when client_id is specified")]
     fn test_openfga_client_config_fails_without_token() {
@@ -810,6 +1859,47 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_secret_envelope_encryption_previous_master_keys() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__SECRET_ENVELOPE_ENCRYPTION__MASTER_KEY",
+                "Y2NjY2NjY2NjY2NjY2NjY2NjY2NjY2NjY2NjY2NjY2M=",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__SECRET_ENVELOPE_ENCRYPTION__PREVIOUS_MASTER_KEYS",
+                "YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=,YmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmI=",
+            );
+            let config = get_config();
+            let encryption = config.secret_envelope_encryption.unwrap();
+            assert_eq!(
+                encryption.master_key,
+                "Y2NjY2NjY2NjY2NjY2NjY2NjY2NjY2NjY2NjY2NjY2M="
+            );
+            assert_eq!(
+                encryption.previous_master_keys,
+                Some(vec![
+                    "YWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWFhYWE=".to_string(),
+                    "YmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmJiYmI=".to_string()
+                ])
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Secret envelope encryption master key must be 32 bytes long")]
+    fn test_secret_envelope_encryption_rejects_invalid_master_key() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__SECRET_ENVELOPE_ENCRYPTION__MASTER_KEY",
+                "a2V5LTM=",
+            );
+            get_config();
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_openfga_client_credentials() {
         figment::Jail::expect_with(|jail| {
@@ -0,0 +1,317 @@
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use super::dbutils::DBErrorHandler as _;
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::audit::ListAuditLogResponse,
+    },
+    implementations::postgres::pagination::{PaginateToken, V1PaginateToken},
+    service::{
+        audit::{AuditLogFilter, AuditOutcome, AuditRecord},
+        Result,
+    },
+};
+
+pub(crate) async fn insert_audit_records(
+    records: &[AuditRecord],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let mut ids = Vec::with_capacity(records.len());
+    let mut created_at = Vec::with_capacity(records.len());
+    let mut principals = Vec::with_capacity(records.len());
+    let mut groups = Vec::with_capacity(records.len());
+    let mut actions = Vec::with_capacity(records.len());
+    let mut objects = Vec::with_capacity(records.len());
+    let mut allowed = Vec::with_capacity(records.len());
+    let mut request_ids = Vec::with_capacity(records.len());
+    for record in records {
+        ids.push(record.id);
+        created_at.push(record.created_at);
+        principals.push(record.principal.clone());
+        groups.push(serde_json::json!(record.groups));
+        actions.push(record.action.clone());
+        objects.push(record.object.clone());
+        allowed.push(record.outcome == AuditOutcome::Allowed);
+        request_ids.push(record.request_id.clone());
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO audit_log (id, created_at, principal, action, object, allowed, request_id, groups)
+        SELECT * FROM UNNEST($1::uuid[], $2::timestamptz[], $3::text[], $4::text[], $5::text[], $6::boolean[], $7::text[], $8::jsonb[])
+        ON CONFLICT (id) DO NOTHING
+        "#,
+        &ids,
+        &created_at,
+        &principals as &[Option<String>],
+        &actions,
+        &objects,
+        &allowed,
+        &request_ids,
+        &groups
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error writing audit records"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn list_audit_records<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    filter: &AuditLogFilter,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<ListAuditLogResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?;
+
+    let (token_ts, token_id) = token
+        .as_ref()
+        .map(
+            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
+                (created_at, id)
+            },
+        )
+        .unzip();
+
+    let object_pattern = filter
+        .object_prefix
+        .as_deref()
+        .map(|prefix| format!("{}%", escape_like(prefix)));
+
+    let records = sqlx::query!(
+        r#"
+        SELECT id, created_at, principal, action, object, allowed, request_id,
+            groups as "groups: Json<Vec<String>>"
+        FROM audit_log a
+        WHERE ($1::text IS NULL OR a.principal = $1)
+            AND ($2::text IS NULL OR a.object LIKE $2)
+            AND ($3::boolean IS NULL OR a.allowed = $3)
+            AND ($4::timestamptz IS NULL OR a.created_at >= $4)
+            AND ($5::timestamptz IS NULL OR a.created_at < $5)
+            --- PAGINATION
+            AND ((a.created_at > $6 OR $6 IS NULL) OR (a.created_at = $6 AND a.id > $7))
+        ORDER BY a.created_at, a.id ASC
+        LIMIT $8
+        "#,
+        filter.principal,
+        object_pattern,
+        filter.outcome.map(|o| o == AuditOutcome::Allowed),
+        filter.from,
+        filter.to,
+        token_ts,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching audit records"))?
+    .into_iter()
+    .map(|r| AuditRecord {
+        id: r.id,
+        created_at: r.created_at,
+        principal: r.principal,
+        groups: r.groups.0,
+        action: r.action,
+        object: r.object,
+        outcome: if r.allowed {
+            AuditOutcome::Allowed
+        } else {
+            AuditOutcome::Denied
+        },
+        request_id: r.request_id,
+    })
+    .collect::<Vec<_>>();
+
+    let next_page_token = records.last().map(|r| {
+        PaginateToken::V1(V1PaginateToken::<Uuid> {
+            created_at: r.created_at,
+            id: r.id,
+        })
+        .to_string()
+    });
+
+    Ok(ListAuditLogResponse {
+        records,
+        next_page_token,
+    })
+}
+
+pub(crate) async fn prune_audit_records(
+    before: chrono::DateTime<chrono::Utc>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<u64> {
+    let pruned = sqlx::query!(
+        r#"
+        DELETE FROM audit_log WHERE created_at < $1
+        "#,
+        before
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error pruning audit records"))?
+    .rows_affected();
+
+    Ok(pruned)
+}
+
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+
+    use super::*;
+    use crate::{
+        api::iceberg::types::PageToken,
+        implementations::postgres::{CatalogState, PostgresTransaction},
+        service::Transaction as _,
+    };
+
+    fn record(
+        principal: &str,
+        object: &str,
+        outcome: AuditOutcome,
+        created_at: chrono::DateTime<Utc>,
+    ) -> AuditRecord {
+        AuditRecord {
+            id: Uuid::now_v7(),
+            created_at,
+            principal: Some(principal.to_string()),
+            groups: vec![],
+            action: "can_get_metadata".to_string(),
+            object: object.to_string(),
+            outcome,
+            request_id: Uuid::now_v7().to_string(),
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_query_audit_log_by_principal_and_time_range(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let now = Utc::now();
+        let records = vec![
+            record(
+                "oidc~alice",
+                "table:1",
+                AuditOutcome::Allowed,
+                now - Duration::hours(3),
+            ),
+            record(
+                "oidc~alice",
+                "table:2",
+                AuditOutcome::Denied,
+                now - Duration::hours(2),
+            ),
+            record(
+                "oidc~alice",
+                "view:1",
+                AuditOutcome::Allowed,
+                now - Duration::hours(1),
+            ),
+            record(
+                "oidc~bob",
+                "table:1",
+                AuditOutcome::Allowed,
+                now - Duration::hours(2),
+            ),
+            record("oidc~alice", "table:3", AuditOutcome::Allowed, now),
+        ];
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        insert_audit_records(&records, t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+
+        let filter = AuditLogFilter {
+            principal: Some("oidc~alice".to_string()),
+            from: Some(now - Duration::minutes(150)),
+            to: Some(now - Duration::minutes(30)),
+            ..Default::default()
+        };
+        let result = list_audit_records(&filter, PaginationQuery::empty(), &pool)
+            .await
+            .unwrap();
+        let objects = result
+            .records
+            .iter()
+            .map(|r| r.object.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(objects, vec!["table:2", "view:1"]);
+
+        // Object prefix and outcome
+        let filter = AuditLogFilter {
+            principal: Some("oidc~alice".to_string()),
+            object_prefix: Some("table:".to_string()),
+            outcome: Some(AuditOutcome::Allowed),
+            ..Default::default()
+        };
+        let result = list_audit_records(&filter, PaginationQuery::empty(), &pool)
+            .await
+            .unwrap();
+        let objects = result
+            .records
+            .iter()
+            .map(|r| r.object.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(objects, vec!["table:1", "table:3"]);
+
+        // Pagination
+        let first = list_audit_records(
+            &AuditLogFilter::default(),
+            PaginationQuery {
+                page_size: Some(3),
+                page_token: PageToken::NotSpecified,
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.records.len(), 3);
+        let second = list_audit_records(
+            &AuditLogFilter::default(),
+            PaginationQuery {
+                page_size: Some(3),
+                page_token: PageToken::Present(first.next_page_token.unwrap()),
+            },
+            &pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.records.len(), 2);
+
+        // Pruning removes records older than the retention
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let pruned = prune_audit_records(now - Duration::minutes(90), t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+        assert_eq!(pruned, 3);
+        let remaining =
+            list_audit_records(&AuditLogFilter::default(), PaginationQuery::empty(), &pool)
+                .await
+                .unwrap();
+        assert_eq!(remaining.records.len(), 2);
+    }
+}
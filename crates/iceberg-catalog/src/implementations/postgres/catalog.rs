@@ -8,21 +8,43 @@ use iceberg_ext::{
 use itertools::Itertools;
 
 use super::{
+    audit::{insert_audit_records, list_audit_records, prune_audit_records},
     bootstrap::{bootstrap, get_validation_data},
+    dbutils::retry_transient,
+    idempotency::{load_idempotency_record, prune_idempotency_records, store_idempotency_record},
     namespace::{
-        create_namespace, drop_namespace, get_namespace, list_namespaces, namespace_to_id,
+        create_namespace, delete_empty_namespaces, drop_namespace, get_namespace,
+        list_namespace_descendants, list_namespace_tables, list_namespaces, namespace_to_id,
+        namespace_usage, rename_namespace, set_namespace_credential_ttl,
+        set_namespace_storage_prefix, update_many_namespace_properties,
         update_namespace_properties,
     },
     role::{create_role, delete_role, list_roles, update_role},
+    table_template::{create_table_template_version, get_table_template},
     tabular::table::{
-        drop_table, get_table_metadata_by_id, get_table_metadata_by_s3_location, list_tables,
-        load_tables, rename_table, resolve_table_ident, table_idents_to_ids,
+        drop_table, get_table_metadata_by_id, get_table_metadata_by_s3_location,
+        get_table_metadata_location, insert_commit_attribution, list_changed_tables,
+        list_table_location_collisions, list_table_locks, list_tables, load_table_at, load_tables,
+        release_table_locks, rename_table, rename_tables, resolve_table_ident, resolve_table_uuid,
+        table_history, table_idents_to_ids,
     },
     warehouse::{
-        create_project, create_warehouse, delete_project, delete_warehouse,
-        get_config_for_warehouse, get_project, get_warehouse, get_warehouse_by_name, list_projects,
-        list_warehouses, rename_project, rename_warehouse, set_warehouse_deletion_profile,
-        set_warehouse_status, update_storage_profile,
+        create_project, create_warehouse, create_warehouse_data_key, delete_project,
+        delete_warehouse, get_config_for_warehouse, get_project, get_warehouse,
+        get_warehouse_allowed_authentication_methods, get_warehouse_by_name,
+        get_warehouse_data_key, list_projects, list_warehouse_data_keys_to_rewrap,
+        list_warehouse_secret_references, list_warehouses, release_previous_storage_secret,
+        rename_project, rename_warehouse, rotate_storage_secret,
+        set_warehouse_allowed_authentication_methods, set_warehouse_allowed_file_formats,
+        set_warehouse_allowed_schemes, set_warehouse_annotations, set_warehouse_commit_rate_limit,
+        set_warehouse_credential_refresh_before, set_warehouse_default_namespace,
+        set_warehouse_default_view_properties, set_warehouse_deletion_profile,
+        set_warehouse_empty_namespace_cleanup_after, set_warehouse_feature_flags,
+        set_warehouse_import_prefixes, set_warehouse_manifest_merge_threshold,
+        set_warehouse_max_tables_per_namespace, set_warehouse_namespace_property_schema,
+        set_warehouse_purge_grace_period, set_warehouse_schema_evolution_policy,
+        set_warehouse_snapshot_limit, set_warehouse_status, set_warehouse_storage_prefix_overrides,
+        update_storage_profile, update_warehouse_data_key,
     },
     CatalogState, PostgresTransaction,
 };
@@ -30,7 +52,10 @@ use crate::{
     api::{
         iceberg::v1::{PaginatedMapping, PaginationQuery},
         management::v1::{
+            audit::ListAuditLogResponse,
             role::{ListRolesResponse, Role, SearchRoleResponse},
+            table::TableHistoryResponse,
+            table_template::{TableTemplate, TableTemplateResponse},
             user::{ListUsersResponse, SearchUserResponse, UserLastUpdatedWith, UserType},
             warehouse::{TabularDeleteProfile, WarehouseStatisticsResponse},
         },
@@ -38,22 +63,37 @@ use crate::{
     implementations::postgres::{
         role::search_role,
         tabular::{
-            clear_tabular_deleted_at, list_tabulars, mark_tabular_as_deleted,
+            clear_tabular_deleted_at, count_tabulars_in_namespace, is_tabular_id_in_use,
+            is_tabular_location_in_use, list_tabulars, mark_tabular_as_deleted,
             table::{commit_table_transaction, create_table, load_storage_profile},
             view::{create_view, drop_view, list_views, load_view, rename_view, view_ident_to_id},
         },
+        task_queues::list_pending_purges,
         user::{create_or_update_user, delete_user, list_users, search_user},
         warehouse::get_warehouse_stats,
     },
     request_metadata::RequestMetadata,
     service::{
-        authn::UserId, storage::StorageProfile, Catalog, CreateNamespaceRequest,
-        CreateNamespaceResponse, CreateOrUpdateUserResponse, CreateTableResponse, DeletionDetails,
-        GetNamespaceResponse, GetProjectResponse, GetTableMetadataResponse, GetWarehouseResponse,
-        ListFlags, ListNamespacesQuery, LoadTableResponse, NamespaceIdent, NamespaceIdentUuid,
-        ProjectId, Result, RoleId, StartupValidationData, TableCommit, TableCreation, TableIdent,
-        TableIdentUuid, TabularIdentOwned, TabularIdentUuid, Transaction, UndropTabularResponse,
-        ViewIdentUuid, WarehouseIdent, WarehouseStatus,
+        audit::{AuditLogFilter, AuditRecord},
+        authn::{AuthenticationMethod, UserId},
+        commit_throttle::CommitRateLimit,
+        feature_flags::WarehouseFeatureFlags,
+        namespace_property_schema::NamespacePropertySchema,
+        schema_evolution::SchemaEvolutionPolicy,
+        secret_encryption::WrappedDataKey,
+        snapshot_limit::SnapshotLimit,
+        storage::StorageProfile,
+        task_queue::tabular_purge_queue::PendingPurge,
+        Catalog, ChangedTable, CreateNamespaceRequest, CreateNamespaceResponse,
+        CreateOrUpdateUserResponse, CreateTableResponse, DeletionDetails, GetNamespaceResponse,
+        GetProjectResponse, GetStorageConfigResponse, GetTableMetadataResponse,
+        GetWarehouseResponse, IdempotencyRecord, ListFlags, ListNamespacesQuery, LoadTableResponse,
+        NamespaceDescendants, NamespaceIdent, NamespaceIdentUuid, NamespaceTabularCounts,
+        NamespaceUsage, ProjectId, Result, RoleId, RotatedStorageSecrets, SnapshotIdOrTimestamp,
+        StartupValidationData, TableCommit, TableCreation, TableIdent, TableIdentUuid,
+        TableLocationCollision, TableLock, TableRename, TabularIdentOwned, TabularIdentUuid,
+        Transaction, UndropTabularResponse, ViewIdentUuid, WarehouseIdent,
+        WarehouseSecretReference, WarehouseStatus,
     },
     SecretIdent,
 };
@@ -108,7 +148,8 @@ impl Catalog for super::PostgresCatalog {
         search_term: &str,
         catalog_state: Self::State,
     ) -> Result<SearchRoleResponse> {
-        search_role(search_term, &catalog_state.read_pool()).await
+        retry_transient(|| async { search_role(search_term, &catalog_state.read_pool()).await })
+            .await
     }
 
     async fn list_roles<'a>(
@@ -118,13 +159,16 @@ impl Catalog for super::PostgresCatalog {
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListRolesResponse> {
-        list_roles(
-            filter_project_id,
-            filter_role_id,
-            filter_name,
-            pagination,
-            &catalog_state.read_pool(),
-        )
+        retry_transient(|| async {
+            list_roles(
+                filter_project_id,
+                filter_role_id.clone(),
+                filter_name.clone(),
+                pagination.clone(),
+                &catalog_state.read_pool(),
+            )
+            .await
+        })
         .await
     }
 
@@ -159,7 +203,8 @@ impl Catalog for super::PostgresCatalog {
         search_term: &str,
         catalog_state: Self::State,
     ) -> Result<SearchUserResponse> {
-        search_user(search_term, &catalog_state.read_pool()).await
+        retry_transient(|| async { search_user(search_term, &catalog_state.read_pool()).await })
+            .await
     }
 
     /// Return Ok(vec[]) if the user does not exist.
@@ -169,12 +214,15 @@ impl Catalog for super::PostgresCatalog {
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListUsersResponse> {
-        list_users(
-            filter_user_id,
-            filter_name,
-            pagination,
-            &catalog_state.read_pool(),
-        )
+        retry_transient(|| async {
+            list_users(
+                filter_user_id.clone(),
+                filter_name.clone(),
+                pagination.clone(),
+                &catalog_state.read_pool(),
+            )
+            .await
+        })
         .await
     }
 
@@ -190,7 +238,20 @@ impl Catalog for super::PostgresCatalog {
         project_id: ProjectId,
         catalog_state: CatalogState,
     ) -> Result<Option<WarehouseIdent>> {
-        get_warehouse_by_name(warehouse_name, project_id, catalog_state).await
+        retry_transient(|| {
+            get_warehouse_by_name(warehouse_name, project_id.clone(), catalog_state.clone())
+        })
+        .await
+    }
+
+    async fn get_warehouse_allowed_authentication_methods(
+        warehouse_id: WarehouseIdent,
+        catalog_state: CatalogState,
+    ) -> Result<Option<Vec<AuthenticationMethod>>> {
+        retry_transient(|| {
+            get_warehouse_allowed_authentication_methods(warehouse_id, catalog_state.clone())
+        })
+        .await
     }
 
     async fn get_config_for_warehouse(
@@ -198,7 +259,10 @@ impl Catalog for super::PostgresCatalog {
         catalog_state: CatalogState,
         request_metadata: &RequestMetadata,
     ) -> Result<Option<CatalogConfig>> {
-        get_config_for_warehouse(warehouse_id, catalog_state, request_metadata).await
+        retry_transient(|| {
+            get_config_for_warehouse(warehouse_id, catalog_state.clone(), request_metadata)
+        })
+        .await
     }
 
     async fn list_namespaces<'a>(
@@ -251,6 +315,92 @@ impl Catalog for super::PostgresCatalog {
         update_namespace_properties(warehouse_id, namespace_id, properties, transaction).await
     }
 
+    async fn update_many_namespace_properties<'a>(
+        warehouse_id: WarehouseIdent,
+        properties: Vec<(NamespaceIdentUuid, HashMap<String, String>)>,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        update_many_namespace_properties(warehouse_id, properties, transaction).await
+    }
+
+    async fn set_namespace_storage_prefix<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        storage_prefix: Option<&str>,
+        storage_region: Option<&str>,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_namespace_storage_prefix(
+            warehouse_id,
+            namespace_id,
+            storage_prefix,
+            storage_region,
+            transaction,
+        )
+        .await
+    }
+
+    async fn set_namespace_credential_ttl<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        credential_ttl: Option<chrono::Duration>,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_namespace_credential_ttl(warehouse_id, namespace_id, credential_ttl, transaction).await
+    }
+
+    async fn rename_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        source: &NamespaceIdent,
+        destination: &NamespaceIdent,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        rename_namespace(warehouse_id, source, destination, transaction).await
+    }
+
+    async fn count_tabulars_in_namespace<'a>(
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<NamespaceTabularCounts> {
+        count_tabulars_in_namespace(namespace_id, transaction).await
+    }
+
+    async fn namespace_usage<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        include_descendants: bool,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<NamespaceUsage> {
+        namespace_usage(warehouse_id, namespace_id, include_descendants, transaction).await
+    }
+
+    async fn list_namespace_descendants<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        limit: usize,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<NamespaceDescendants> {
+        list_namespace_descendants(warehouse_id, namespace_id, limit, transaction).await
+    }
+
+    async fn list_namespace_tables<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        include_descendants: bool,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Vec<(TableIdentUuid, TableIdent)>> {
+        list_namespace_tables(warehouse_id, namespace_id, include_descendants, transaction).await
+    }
+
+    async fn delete_empty_namespaces<'a>(
+        warehouse_id: WarehouseIdent,
+        inactive_since: chrono::DateTime<chrono::Utc>,
+        exempt_namespaces: &[String],
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceIdentUuid>> {
+        delete_empty_namespaces(warehouse_id, inactive_since, exempt_namespaces, transaction).await
+    }
+
     async fn create_table<'a>(
         table_creation: TableCreation<'_>,
         transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
@@ -258,9 +408,81 @@ impl Catalog for super::PostgresCatalog {
         create_table(table_creation, transaction).await
     }
 
+    async fn list_changed_tables<'a>(
+        warehouse_id: WarehouseIdent,
+        since: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+        pagination_query: PaginationQuery,
+    ) -> Result<PaginatedMapping<TableIdentUuid, ChangedTable>> {
+        list_changed_tables(warehouse_id, since, &mut **transaction, pagination_query).await
+    }
+
+    // Locks are held on the primary, so the write pool is used for both.
+    async fn list_table_locks(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TableLock>> {
+        list_table_locks(warehouse_id, table_id, &catalog_state.write_pool()).await
+    }
+
+    async fn release_table_locks(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TableLock>> {
+        release_table_locks(warehouse_id, table_id, &catalog_state.write_pool()).await
+    }
+
+    async fn table_history(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        pagination: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<TableHistoryResponse> {
+        retry_transient(|| async {
+            table_history(
+                warehouse_id,
+                table_id,
+                pagination.clone(),
+                &catalog_state.read_pool(),
+            )
+            .await
+        })
+        .await
+    }
+
+    async fn create_table_template_version<'a>(
+        warehouse_id: WarehouseIdent,
+        name: &str,
+        template: &TableTemplate,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<TableTemplateResponse> {
+        create_table_template_version(warehouse_id, name, template, transaction).await
+    }
+
+    async fn get_table_template<'a>(
+        warehouse_id: WarehouseIdent,
+        name: &str,
+        version: Option<i32>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<TableTemplateResponse>> {
+        get_table_template(warehouse_id, name, version, transaction).await
+    }
+
+    async fn insert_commit_attribution<'a>(
+        table_id: TableIdentUuid,
+        snapshot_id: i64,
+        principal: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        insert_commit_attribution(table_id, snapshot_id, principal, transaction).await
+    }
+
     async fn list_tables<'a>(
         warehouse_id: WarehouseIdent,
         namespace: &NamespaceIdent,
+        name_prefix: Option<&str>,
         list_flags: ListFlags,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
         pagination_query: PaginationQuery,
@@ -268,6 +490,7 @@ impl Catalog for super::PostgresCatalog {
         list_tables(
             warehouse_id,
             namespace,
+            name_prefix,
             list_flags,
             &mut **transaction,
             pagination_query,
@@ -301,7 +524,16 @@ impl Catalog for super::PostgresCatalog {
         list_flags: ListFlags,
         catalog_state: Self::State,
     ) -> Result<HashMap<TableIdent, Option<TableIdentUuid>>> {
-        table_idents_to_ids(warehouse_id, tables, list_flags, &catalog_state.read_pool()).await
+        retry_transient(|| async {
+            table_idents_to_ids(
+                warehouse_id,
+                tables.clone(),
+                list_flags,
+                &catalog_state.read_pool(),
+            )
+            .await
+        })
+        .await
     }
 
     // Should also load staged tables but not tables of inactive warehouses
@@ -314,11 +546,20 @@ impl Catalog for super::PostgresCatalog {
         load_tables(warehouse_id, tables, include_deleted, transaction).await
     }
 
+    async fn load_table_at<'a>(
+        warehouse_id: WarehouseIdent,
+        table: TableIdentUuid,
+        at: SnapshotIdOrTimestamp,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<LoadTableResponse> {
+        load_table_at(warehouse_id, table, at, transaction).await
+    }
+
     async fn load_storage_profile(
         warehouse_id: WarehouseIdent,
         tabular_id: TableIdentUuid,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
-    ) -> Result<(Option<SecretIdent>, StorageProfile)> {
+    ) -> Result<GetStorageConfigResponse> {
         load_storage_profile(warehouse_id, tabular_id, transaction).await
     }
 
@@ -328,7 +569,26 @@ impl Catalog for super::PostgresCatalog {
         list_flags: ListFlags,
         catalog_state: Self::State,
     ) -> Result<Option<GetTableMetadataResponse>> {
-        get_table_metadata_by_id(warehouse_id, table, list_flags, catalog_state).await
+        retry_transient(|| {
+            get_table_metadata_by_id(warehouse_id, table, list_flags, catalog_state.clone())
+        })
+        .await
+    }
+
+    async fn resolve_table_uuid<'a>(
+        warehouse_id: WarehouseIdent,
+        table: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<TableIdent> {
+        resolve_table_uuid(warehouse_id, table, &mut **transaction).await
+    }
+
+    async fn get_table_metadata_location<'a>(
+        warehouse_id: WarehouseIdent,
+        table: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<String>> {
+        get_table_metadata_location(warehouse_id, table, &mut **transaction).await
     }
 
     async fn get_table_metadata_by_s3_location(
@@ -337,7 +597,15 @@ impl Catalog for super::PostgresCatalog {
         list_flags: ListFlags,
         catalog_state: Self::State,
     ) -> Result<Option<GetTableMetadataResponse>> {
-        get_table_metadata_by_s3_location(warehouse_id, location, list_flags, catalog_state).await
+        retry_transient(|| {
+            get_table_metadata_by_s3_location(
+                warehouse_id,
+                location,
+                list_flags,
+                catalog_state.clone(),
+            )
+        })
+        .await
     }
 
     async fn rename_table<'a>(
@@ -350,6 +618,14 @@ impl Catalog for super::PostgresCatalog {
         rename_table(warehouse_id, source_id, source, destination, transaction).await
     }
 
+    async fn rename_tables<'a>(
+        warehouse_id: WarehouseIdent,
+        renames: &[TableRename],
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        rename_tables(warehouse_id, renames, transaction).await
+    }
+
     async fn drop_table<'a>(
         table_id: TableIdentUuid,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
@@ -377,6 +653,34 @@ impl Catalog for super::PostgresCatalog {
         mark_tabular_as_deleted(table_id, None, transaction).await
     }
 
+    async fn is_tabular_location_in_use<'a>(
+        location: &Location,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool> {
+        is_tabular_location_in_use(location, transaction).await
+    }
+
+    async fn is_tabular_id_in_use<'a>(
+        tabular_id: TabularIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool> {
+        is_tabular_id_in_use(tabular_id, transaction).await
+    }
+
+    async fn list_pending_purges<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<PendingPurge>> {
+        list_pending_purges(warehouse_id, transaction).await
+    }
+
+    async fn list_table_location_collisions<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<TableLocationCollision>> {
+        list_table_location_collisions(warehouse_id, &mut **transaction).await
+    }
+
     async fn commit_table_transaction<'a>(
         warehouse_id: WarehouseIdent,
         commits: impl IntoIterator<Item = TableCommit> + Send,
@@ -461,9 +765,10 @@ impl Catalog for super::PostgresCatalog {
 
     async fn delete_warehouse<'a>(
         warehouse_id: WarehouseIdent,
+        force: bool,
         transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
-    ) -> Result<()> {
-        delete_warehouse(warehouse_id, transaction).await
+    ) -> Result<DeletedWarehouseContents> {
+        delete_warehouse(warehouse_id, force, transaction).await
     }
 
     async fn rename_warehouse<'a>(
@@ -482,6 +787,235 @@ impl Catalog for super::PostgresCatalog {
         set_warehouse_deletion_profile(warehouse_id, deletion_profile, &mut **transaction).await
     }
 
+    async fn set_warehouse_feature_flags<'a>(
+        warehouse_id: WarehouseIdent,
+        feature_flags: &WarehouseFeatureFlags,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_feature_flags(warehouse_id, feature_flags, transaction).await
+    }
+
+    async fn set_warehouse_allowed_schemes<'a>(
+        warehouse_id: WarehouseIdent,
+        allowed_schemes: Option<&[String]>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_allowed_schemes(warehouse_id, allowed_schemes, transaction).await
+    }
+
+    async fn set_warehouse_purge_grace_period<'a>(
+        warehouse_id: WarehouseIdent,
+        purge_grace_period: Option<chrono::Duration>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_purge_grace_period(warehouse_id, purge_grace_period, transaction).await
+    }
+
+    async fn set_warehouse_manifest_merge_threshold<'a>(
+        warehouse_id: WarehouseIdent,
+        manifest_merge_threshold: Option<i32>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_manifest_merge_threshold(warehouse_id, manifest_merge_threshold, transaction)
+            .await
+    }
+
+    async fn set_warehouse_schema_evolution_policy<'a>(
+        warehouse_id: WarehouseIdent,
+        schema_evolution_policy: SchemaEvolutionPolicy,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_schema_evolution_policy(warehouse_id, schema_evolution_policy, transaction)
+            .await
+    }
+
+    async fn set_warehouse_allowed_file_formats<'a>(
+        warehouse_id: WarehouseIdent,
+        allowed_file_formats: Option<&[String]>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_allowed_file_formats(warehouse_id, allowed_file_formats, transaction).await
+    }
+
+    async fn set_warehouse_namespace_property_schema<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_property_schema: &NamespacePropertySchema,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_namespace_property_schema(
+            warehouse_id,
+            namespace_property_schema,
+            transaction,
+        )
+        .await
+    }
+
+    async fn set_warehouse_annotations<'a>(
+        warehouse_id: WarehouseIdent,
+        annotations: &HashMap<String, String>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_annotations(warehouse_id, annotations, transaction).await
+    }
+
+    async fn set_warehouse_commit_rate_limit<'a>(
+        warehouse_id: WarehouseIdent,
+        commit_rate_limit: Option<CommitRateLimit>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_commit_rate_limit(warehouse_id, commit_rate_limit, transaction).await
+    }
+
+    async fn set_warehouse_credential_refresh_before<'a>(
+        warehouse_id: WarehouseIdent,
+        credential_refresh_before: Option<chrono::Duration>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_credential_refresh_before(
+            warehouse_id,
+            credential_refresh_before,
+            transaction,
+        )
+        .await
+    }
+
+    async fn set_warehouse_empty_namespace_cleanup_after<'a>(
+        warehouse_id: WarehouseIdent,
+        cleanup_after: Option<chrono::Duration>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_empty_namespace_cleanup_after(warehouse_id, cleanup_after, transaction).await
+    }
+
+    async fn set_warehouse_allowed_authentication_methods<'a>(
+        warehouse_id: WarehouseIdent,
+        allowed_authentication_methods: Option<&[AuthenticationMethod]>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_allowed_authentication_methods(
+            warehouse_id,
+            allowed_authentication_methods,
+            transaction,
+        )
+        .await
+    }
+
+    async fn set_warehouse_default_view_properties<'a>(
+        warehouse_id: WarehouseIdent,
+        default_view_properties: &HashMap<String, String>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_default_view_properties(warehouse_id, default_view_properties, transaction)
+            .await
+    }
+
+    async fn set_warehouse_import_prefixes<'a>(
+        warehouse_id: WarehouseIdent,
+        import_prefixes: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_import_prefixes(warehouse_id, import_prefixes, transaction).await
+    }
+
+    async fn set_warehouse_default_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        default_namespace: Option<&NamespaceIdent>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_default_namespace(warehouse_id, default_namespace, transaction).await
+    }
+
+    async fn set_warehouse_snapshot_limit<'a>(
+        warehouse_id: WarehouseIdent,
+        snapshot_limit: Option<SnapshotLimit>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_snapshot_limit(warehouse_id, snapshot_limit, transaction).await
+    }
+
+    async fn set_warehouse_storage_prefix_overrides<'a>(
+        warehouse_id: WarehouseIdent,
+        storage_prefix_overrides: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_storage_prefix_overrides(warehouse_id, storage_prefix_overrides, transaction)
+            .await
+    }
+
+    async fn set_warehouse_max_tables_per_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        max_tables_per_namespace: Option<i32>,
+        includes_views: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_max_tables_per_namespace(
+            warehouse_id,
+            max_tables_per_namespace,
+            includes_views,
+            transaction,
+        )
+        .await
+    }
+
+    async fn load_idempotency_record<'a>(
+        warehouse_id: WarehouseIdent,
+        principal: &str,
+        idempotency_key: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<IdempotencyRecord>> {
+        load_idempotency_record(warehouse_id, principal, idempotency_key, transaction).await
+    }
+
+    async fn store_idempotency_record<'a>(
+        warehouse_id: WarehouseIdent,
+        principal: &str,
+        idempotency_key: &str,
+        record: IdempotencyRecord,
+        expires_at: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        store_idempotency_record(
+            warehouse_id,
+            principal,
+            idempotency_key,
+            record,
+            expires_at,
+            transaction,
+        )
+        .await
+    }
+
+    async fn prune_idempotency_records<'a>(
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<u64> {
+        prune_idempotency_records(transaction).await
+    }
+
+    async fn insert_audit_records<'a>(
+        records: &[AuditRecord],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        insert_audit_records(records, transaction).await
+    }
+
+    async fn list_audit_records(
+        filter: AuditLogFilter,
+        pagination: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListAuditLogResponse> {
+        retry_transient(|| async {
+            list_audit_records(&filter, pagination.clone(), &catalog_state.read_pool()).await
+        })
+        .await
+    }
+
+    async fn prune_audit_records<'a>(
+        before: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<u64> {
+        prune_audit_records(before, transaction).await
+    }
+
     async fn set_warehouse_status<'a>(
         warehouse_id: WarehouseIdent,
         status: WarehouseStatus,
@@ -505,6 +1039,66 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    async fn rotate_storage_secret<'a>(
+        warehouse_id: WarehouseIdent,
+        storage_secret_id: SecretIdent,
+        previous_secret_expires_at: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<RotatedStorageSecrets> {
+        rotate_storage_secret(
+            warehouse_id,
+            storage_secret_id,
+            previous_secret_expires_at,
+            transaction,
+        )
+        .await
+    }
+
+    async fn release_previous_storage_secret<'a>(
+        warehouse_id: WarehouseIdent,
+        secret_id: SecretIdent,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<bool> {
+        release_previous_storage_secret(warehouse_id, secret_id, transaction).await
+    }
+
+    async fn list_warehouse_secret_references<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Vec<WarehouseSecretReference>> {
+        list_warehouse_secret_references(warehouse_id, transaction).await
+    }
+
+    async fn get_warehouse_data_key<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Option<WrappedDataKey>> {
+        get_warehouse_data_key(warehouse_id, transaction).await
+    }
+
+    async fn create_warehouse_data_key<'a>(
+        warehouse_id: WarehouseIdent,
+        data_key: &WrappedDataKey,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<bool> {
+        create_warehouse_data_key(warehouse_id, data_key, transaction).await
+    }
+
+    async fn update_warehouse_data_key<'a>(
+        warehouse_id: WarehouseIdent,
+        data_key: &WrappedDataKey,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        update_warehouse_data_key(warehouse_id, data_key, transaction).await
+    }
+
+    async fn list_warehouse_data_keys_to_rewrap<'a>(
+        master_key_id: &str,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Vec<(WarehouseIdent, WrappedDataKey)>> {
+        list_warehouse_data_keys_to_rewrap(master_key_id, transaction).await
+    }
+
     async fn view_to_id<'a>(
         warehouse_id: WarehouseIdent,
         view: &TableIdent,
@@ -543,6 +1137,7 @@ impl Catalog for super::PostgresCatalog {
     async fn list_views<'a>(
         warehouse_id: WarehouseIdent,
         namespace: &NamespaceIdent,
+        name_prefix: Option<&str>,
         include_deleted: bool,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
         pagination_query: PaginationQuery,
@@ -550,6 +1145,7 @@ impl Catalog for super::PostgresCatalog {
         list_views(
             warehouse_id,
             namespace,
+            name_prefix,
             include_deleted,
             &mut **transaction,
             pagination_query,
@@ -607,6 +1203,7 @@ impl Catalog for super::PostgresCatalog {
             warehouse_id,
             None,
             namespace_id,
+            None,
             list_flags,
             &mut **transaction,
             None,
@@ -621,6 +1218,9 @@ impl Catalog for super::PostgresCatalog {
         pagination_query: PaginationQuery,
         state: Self::State,
     ) -> Result<WarehouseStatisticsResponse> {
-        get_warehouse_stats(state.read_pool(), warehouse_id, pagination_query).await
+        retry_transient(|| {
+            get_warehouse_stats(state.read_pool(), warehouse_id, pagination_query.clone())
+        })
+        .await
     }
 }
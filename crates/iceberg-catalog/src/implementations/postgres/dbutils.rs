@@ -1,4 +1,10 @@
-use crate::api::ErrorModel;
+use std::future::Future;
+
+use crate::{
+    api::{ErrorModel, Result},
+    service::backend_timeout::{with_timeout, Backend, BackendTimeoutError},
+    CONFIG,
+};
 
 pub(crate) trait DBErrorHandler
 where
@@ -13,6 +19,18 @@ impl DBErrorHandler for sqlx::Error {
     fn into_error_model(self, message: impl Into<String>) -> ErrorModel {
         match self {
             Self::Database(ref db) => {
+                // query_canceled is raised both when `statement_timeout` is exceeded and
+                // when the statement is canceled on request, e.g. by an admin.
+                // Only the former is a timeout, the rest are database errors.
+                if db.code().as_deref() == Some("57014")
+                    && db.message().contains("statement timeout")
+                {
+                    return BackendTimeoutError {
+                        backend: Backend::CatalogQuery,
+                        timeout: CONFIG.backend_timeouts.catalog_query,
+                    }
+                    .into();
+                }
                 if db.is_unique_violation() {
                     return ErrorModel::conflict(
                         message,
@@ -37,3 +55,158 @@ impl DBErrorHandler for sqlx::Error {
         }
     }
 }
+
+/// `true` for errors that are expected to go away when the operation is repeated,
+/// such as connection resets during a failover or serialization failures.
+///
+/// Logical errors, for example unique violations, are never transient.
+/// Neither is an exhausted pool: retrying would only add load to a saturated pool.
+pub(crate) fn is_transient_error(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Io(_) => true,
+        sqlx::Error::Database(db) => db.code().is_some_and(|code| {
+            // https://www.postgresql.org/docs/current/errcodes-appendix.html
+            code.starts_with("08")
+                || matches!(&*code, "40001" | "40P01" | "57P01" | "57P02" | "57P03")
+        }),
+        _ => false,
+    }
+}
+
+/// Run `f` and retry it with exponential backoff while it fails with a transient
+/// database error, see [`is_transient_error`].
+///
+/// Only use this for reads and idempotent writes: a statement whose connection
+/// broke might still have been applied. All attempts together are bounded by the
+/// `catalog_query` backend timeout.
+///
+/// Statements within an open transaction are never retried, including reads:
+/// a failed statement aborts the transaction, so `f` must acquire its own connection.
+pub(crate) async fn retry_transient<T, F, Fut>(f: F) -> Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let retry = &CONFIG.pg_retry;
+    with_timeout(
+        Backend::CatalogQuery,
+        CONFIG.backend_timeouts.catalog_query,
+        async {
+            let mut backoff = retry.initial_backoff;
+            let mut attempt = 0;
+            loop {
+                match f().await {
+                    Err(e) if attempt < retry.max_retries && is_transient(&e.error) => {
+                        attempt += 1;
+                        tracing::warn!(
+                            "Transient database error, retrying in {}ms ({attempt}/{}): {}",
+                            backoff.as_millis(),
+                            retry.max_retries,
+                            e.error
+                        );
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(retry.max_backoff);
+                    }
+                    result => return result,
+                }
+            }
+        },
+    )
+    .await?
+}
+
+/// `LIKE` pattern matching all strings starting with `prefix`.
+/// Wildcards in the prefix are escaped.
+pub(crate) fn like_prefix_pattern(prefix: &str) -> String {
+    let escaped = prefix
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{escaped}%")
+}
+
+fn is_transient(error: &ErrorModel) -> bool {
+    error
+        .source
+        .as_deref()
+        .and_then(|source| source.downcast_ref::<sqlx::Error>())
+        .is_some_and(is_transient_error)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    fn connection_reset() -> ErrorModel {
+        sqlx::Error::Io(std::io::Error::from(std::io::ErrorKind::ConnectionReset))
+            .into_error_model("Error fetching warehouse")
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_succeeds_after_connection_reset() {
+        let attempts = AtomicU32::new(0);
+        let result = retry_transient(|| async {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(connection_reset().into())
+            } else {
+                Ok(42)
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(result, 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_gives_up() {
+        let attempts = AtomicU32::new(0);
+        let err = retry_transient(|| async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(connection_reset().into())
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "DatabaseError");
+        assert_eq!(
+            attempts.load(Ordering::SeqCst),
+            CONFIG.pg_retry.max_retries + 1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_ignores_logical_errors() {
+        let attempts = AtomicU32::new(0);
+        let err = retry_transient(|| async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(
+                sqlx::Error::RowNotFound
+                    .into_error_model("Error fetching warehouse")
+                    .into(),
+            )
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "DatabaseError");
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_ignores_pool_timeout() {
+        let attempts = AtomicU32::new(0);
+        let err = retry_transient(|| async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(
+                sqlx::Error::PoolTimedOut
+                    .into_error_model("Error fetching warehouse")
+                    .into(),
+            )
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "DatabaseError");
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
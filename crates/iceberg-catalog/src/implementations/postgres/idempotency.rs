@@ -0,0 +1,224 @@
+use super::dbutils::DBErrorHandler as _;
+use crate::{
+    api::{ErrorModel, Result},
+    service::IdempotencyRecord,
+    WarehouseIdent,
+};
+
+pub(crate) async fn load_idempotency_record(
+    warehouse_id: WarehouseIdent,
+    principal: &str,
+    idempotency_key: &str,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<IdempotencyRecord>> {
+    let record = sqlx::query!(
+        r#"
+        SELECT operation, response
+        FROM idempotency_key
+        WHERE warehouse_id = $1 AND principal = $2 AND idempotency_key = $3
+        AND expires_at > now()
+        "#,
+        *warehouse_id,
+        principal,
+        idempotency_key
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching idempotency key"))?;
+
+    Ok(record.map(|r| IdempotencyRecord {
+        operation: r.operation,
+        response: r.response,
+    }))
+}
+
+pub(crate) async fn store_idempotency_record(
+    warehouse_id: WarehouseIdent,
+    principal: &str,
+    idempotency_key: &str,
+    record: IdempotencyRecord,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    // Expired keys may be overwritten, active keys may not.
+    let row_count = sqlx::query!(
+        r#"
+        INSERT INTO idempotency_key (warehouse_id, principal, idempotency_key, operation, response, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (warehouse_id, principal, idempotency_key) DO UPDATE
+        SET operation = EXCLUDED.operation,
+            response = EXCLUDED.response,
+            created_at = now(),
+            expires_at = EXCLUDED.expires_at
+        WHERE idempotency_key.expires_at <= now()
+        "#,
+        *warehouse_id,
+        principal,
+        idempotency_key,
+        record.operation,
+        record.response,
+        expires_at
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error storing idempotency key"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::conflict(
+            "A request with the same Idempotency-Key is currently being processed",
+            "IdempotencyKeyInUse",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn prune_idempotency_records(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<u64> {
+    let pruned = sqlx::query!(
+        r#"
+        DELETE FROM idempotency_key WHERE expires_at <= now()
+        "#
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error pruning idempotency keys"))?
+    .rows_affected();
+
+    Ok(pruned)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        implementations::postgres::{
+            warehouse::test::initialize_warehouse, CatalogState, PostgresTransaction,
+        },
+        service::Transaction as _,
+    };
+
+    #[sqlx::test]
+    async fn test_expired_keys_behave_as_new(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let record = |n: i32| IdempotencyRecord {
+            operation: "createNamespace".to_string(),
+            response: serde_json::json!({ "n": n }),
+        };
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        store_idempotency_record(
+            warehouse_id,
+            "user",
+            "key",
+            record(1),
+            chrono::Utc::now() - chrono::Duration::hours(1),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        // Expired records are not returned
+        let loaded = load_idempotency_record(warehouse_id, "user", "key", t.transaction())
+            .await
+            .unwrap();
+        assert!(loaded.is_none());
+
+        // ... and can be overwritten
+        store_idempotency_record(
+            warehouse_id,
+            "user",
+            "key",
+            record(2),
+            chrono::Utc::now() + chrono::Duration::hours(1),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        let loaded = load_idempotency_record(warehouse_id, "user", "key", t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(loaded, Some(record(2)));
+
+        // Active records can not be overwritten
+        let err = store_idempotency_record(
+            warehouse_id,
+            "user",
+            "key",
+            record(3),
+            chrono::Utc::now() + chrono::Duration::hours(1),
+            t.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "IdempotencyKeyInUse");
+
+        // Keys are scoped per principal
+        let loaded = load_idempotency_record(warehouse_id, "other-user", "key", t.transaction())
+            .await
+            .unwrap();
+        assert!(loaded.is_none());
+        t.commit().await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_prune_removes_expired_keys(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let record = IdempotencyRecord {
+            operation: "createNamespace".to_string(),
+            response: serde_json::json!({}),
+        };
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        for (key, expires_at) in [
+            ("expired-1", chrono::Utc::now() - chrono::Duration::hours(1)),
+            (
+                "expired-2",
+                chrono::Utc::now() - chrono::Duration::minutes(1),
+            ),
+            ("active", chrono::Utc::now() + chrono::Duration::hours(1)),
+        ] {
+            store_idempotency_record(
+                warehouse_id,
+                "user",
+                key,
+                record.clone(),
+                expires_at,
+                t.transaction(),
+            )
+            .await
+            .unwrap();
+        }
+        t.commit().await.unwrap();
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let pruned = prune_idempotency_records(t.transaction()).await.unwrap();
+        t.commit().await.unwrap();
+        assert_eq!(pruned, 2);
+
+        let remaining = sqlx::query_scalar!(r#"SELECT idempotency_key FROM idempotency_key"#)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec!["active".to_string()]);
+        let mut t = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let loaded = load_idempotency_record(warehouse_id, "user", "active", t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(loaded, Some(record));
+        t.commit().await.unwrap();
+    }
+}
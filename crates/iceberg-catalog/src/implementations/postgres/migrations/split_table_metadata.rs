@@ -65,6 +65,7 @@ async fn split_table_metadata(
                     warehouse_id,
                     None,
                     None,
+                    None,
                     ListFlags::all(),
                     &mut **transaction,
                     Some(TabularType::Table),
@@ -100,6 +101,8 @@ async fn split_table_metadata(
                             table_ident: tab.0.clone().as_table()?,
                             metadata_location: table.metadata_location.as_ref(),
                             table_metadata: table.table_metadata,
+                            storage_team: None,
+                            read_only: false,
                         },
                         transaction,
                     )
@@ -163,6 +166,8 @@ mod test {
                     table_ident: &TableIdent {
                         namespace: namespace.clone(),
                         name: js.uuid().to_string(),
+                        storage_team: None,
+                        read_only: false,
                     },
                     table_metadata: js,
                     metadata_location: None,
@@ -205,6 +210,8 @@ mod test {
                     table_ident: &TableIdent {
                         namespace: namespace.clone(),
                         name: js.uuid().to_string(),
+                        storage_team: None,
+                        read_only: false,
                     },
                     table_metadata: js,
                     metadata_location: None,
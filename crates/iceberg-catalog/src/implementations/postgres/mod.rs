@@ -1,11 +1,14 @@
+pub(crate) mod audit;
 mod bootstrap;
 mod catalog;
 pub(crate) mod dbutils;
+pub(crate) mod idempotency;
 pub mod migrations;
 pub(crate) mod namespace;
 mod pagination;
 pub(crate) mod role;
 pub(crate) mod secrets;
+pub(crate) mod table_template;
 pub mod tabular;
 pub mod task_queues;
 pub(crate) mod user;
@@ -23,12 +26,15 @@ use sqlx::{
 pub use tabular::DeletionKind;
 use tokio::sync::RwLock;
 
-use self::dbutils::DBErrorHandler;
+use self::dbutils::{retry_transient, DBErrorHandler};
 use crate::{
     api::Result,
     config::{DynAppConfig, PgSslMode},
-    service::health::{Health, HealthExt, HealthStatus},
-    CONFIG,
+    service::{
+        authz::implementations::openfga::{ProjectResolver, ProjectScopedObject},
+        health::{Health, HealthExt, HealthStatus},
+    },
+    ProjectId, CONFIG,
 };
 
 /// # Errors
@@ -51,6 +57,20 @@ pub async fn get_writer_pool(pool_opts: PgPoolOptions) -> anyhow::Result<sqlx::P
     Ok(pool)
 }
 
+/// Pool to run migrations with. Connects like the write pool, but statements
+/// are not subject to the `catalog_query` timeout, as migrations of large
+/// catalogs may take much longer than a request.
+///
+/// # Errors
+/// Returns an error if the pool cannot be created.
+pub async fn get_migration_pool(pool_opts: PgPoolOptions) -> anyhow::Result<sqlx::PgPool> {
+    let pool = pool_opts
+        .connect_with(build_connect_ops(ConnectionType::Migration)?)
+        .await
+        .map_err(|e| anyhow::anyhow!(e).context("Error creating migration pool."))?;
+    Ok(pool)
+}
+
 #[derive(Debug, Clone)]
 pub struct PostgresCatalog {}
 
@@ -64,30 +84,37 @@ pub struct PostgresTransaction {
 impl crate::service::Transaction<CatalogState> for PostgresTransaction {
     type Transaction<'a> = &'a mut sqlx::Transaction<'static, sqlx::Postgres>;
 
+    // Nothing has happened within a transaction yet, so starting it can be retried.
     async fn begin_write(db_state: CatalogState) -> Result<Self> {
-        let transaction = db_state
-            .write_pool()
-            .begin()
-            .await
-            .map_err(|e| e.into_error_model("Error starting transaction".to_string()))?;
-
-        Ok(Self { transaction })
+        retry_transient(|| async {
+            let transaction = db_state
+                .write_pool()
+                .begin()
+                .await
+                .map_err(|e| e.into_error_model("Error starting transaction".to_string()))?;
+
+            Ok(Self { transaction })
+        })
+        .await
     }
 
     async fn begin_read(db_state: CatalogState) -> Result<Self> {
-        let mut transaction = db_state
-            .read_pool()
-            .begin()
-            .await
-            .map_err(|e| e.into_error_model("Error starting transaction".to_string()))?;
-
-        transaction
-            .execute("SET TRANSACTION READ ONLY")
-            .await
-            .map_err(|e| {
-                e.into_error_model("Error setting transaction to read-only".to_string())
-            })?;
-        Ok(Self { transaction })
+        retry_transient(|| async {
+            let mut transaction = db_state
+                .read_pool()
+                .begin()
+                .await
+                .map_err(|e| e.into_error_model("Error starting transaction".to_string()))?;
+
+            transaction
+                .execute("SET TRANSACTION READ ONLY")
+                .await
+                .map_err(|e| {
+                    e.into_error_model("Error setting transaction to read-only".to_string())
+                })?;
+            Ok(Self { transaction })
+        })
+        .await
     }
 
     async fn commit(self) -> Result<()> {
@@ -207,6 +234,15 @@ impl CatalogState {
     }
 }
 
+#[async_trait]
+impl ProjectResolver for CatalogState {
+    async fn resolve_project(&self, object: ProjectScopedObject) -> Result<Option<ProjectId>> {
+        // Objects are often authorized right after they are created, which read
+        // replicas might not have seen yet.
+        warehouse::get_project_of_object(object, &self.read_write.write_pool).await
+    }
+}
+
 impl DynAppConfig {
     pub fn to_pool_opts(&self) -> PgPoolOptions {
         sqlx::pool::PoolOptions::default()
@@ -222,6 +258,7 @@ impl DynAppConfig {
 enum ConnectionType {
     Read,
     Write,
+    Migration,
 }
 
 fn build_connect_ops(typ: ConnectionType) -> anyhow::Result<PgConnectOptions> {
@@ -230,12 +267,14 @@ fn build_connect_ops(typ: ConnectionType) -> anyhow::Result<PgConnectOptions> {
             .pg_database_url_read
             .as_deref()
             .or(CONFIG.pg_database_url_write.as_deref()),
-        ConnectionType::Write => CONFIG.pg_database_url_write.as_deref(),
+        ConnectionType::Write | ConnectionType::Migration => {
+            CONFIG.pg_database_url_write.as_deref()
+        }
     };
 
     let host = match typ {
         ConnectionType::Read => CONFIG.pg_host_r.as_deref().or(CONFIG.pg_host_w.as_deref()),
-        ConnectionType::Write => CONFIG.pg_host_w.as_deref(),
+        ConnectionType::Write | ConnectionType::Migration => CONFIG.pg_host_w.as_deref(),
     };
     let opts = if let Some(cfg) = url {
         PgConnectOptions::from_str(cfg)?
@@ -268,5 +307,14 @@ fn build_connect_ops(typ: ConnectionType) -> anyhow::Result<PgConnectOptions> {
     } else {
         opts.disable_statement_logging()
     };
+    // Postgres cancels statements exceeding the timeout with `query_canceled`,
+    // which is reported as a `CatalogQueryTimeout` (see `DBErrorHandler`).
+    let opts = match typ {
+        ConnectionType::Read | ConnectionType::Write => opts.options([(
+            "statement_timeout",
+            CONFIG.backend_timeouts.catalog_query.as_millis(),
+        )]),
+        ConnectionType::Migration => opts,
+    };
     Ok(opts)
 }
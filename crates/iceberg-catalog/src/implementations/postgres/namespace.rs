@@ -6,14 +6,18 @@ use iceberg_ext::catalog::rest::IcebergErrorResponse;
 use sqlx::types::Json;
 use uuid::Uuid;
 
-use super::dbutils::DBErrorHandler;
+use super::dbutils::{like_prefix_pattern, DBErrorHandler};
 use crate::{
     api::iceberg::v1::{PaginatedMapping, MAX_PAGE_SIZE},
     catalog::namespace::MAX_NAMESPACE_DEPTH,
-    implementations::postgres::pagination::{PaginateToken, V1PaginateToken},
+    implementations::postgres::{
+        pagination::{PaginateToken, V1PaginateToken},
+        tabular::TabularType,
+    },
     service::{
         CreateNamespaceRequest, CreateNamespaceResponse, ErrorModel, GetNamespaceResponse,
-        ListNamespacesQuery, NamespaceIdent, NamespaceIdentUuid, Result,
+        ListNamespacesQuery, NamespaceDescendants, NamespaceIdent, NamespaceIdentUuid,
+        NamespaceUsage, Result, TableIdent, TableIdentUuid, TabularIdentUuid,
     },
     WarehouseIdent,
 };
@@ -29,7 +33,10 @@ pub(crate) async fn get_namespace(
             namespace_name as "namespace_name: Vec<String>",
             n.namespace_id,
             n.warehouse_id,
-            namespace_properties as "properties: Json<Option<HashMap<String, String>>>"
+            namespace_properties as "properties: Json<Option<HashMap<String, String>>>",
+            n.storage_prefix,
+            n.storage_region,
+            n.credential_ttl_seconds
         FROM namespace n
         INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
         WHERE n.warehouse_id = $1 AND n.namespace_id = $2
@@ -62,6 +69,9 @@ pub(crate) async fn get_namespace(
         properties: row.properties.deref().clone(),
         namespace_id: row.namespace_id.into(),
         warehouse_id: row.warehouse_id.into(),
+        storage_prefix: row.storage_prefix,
+        storage_region: row.storage_region,
+        credential_ttl: row.credential_ttl_seconds.map(chrono::Duration::seconds),
     })
 }
 
@@ -73,6 +83,7 @@ pub(crate) async fn list_namespaces(
         page_size,
         parent,
         return_uuids: _,
+        name_prefix,
     }: &ListNamespacesQuery,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<PaginatedMapping<NamespaceIdentUuid, NamespaceIdent>> {
@@ -82,6 +93,10 @@ pub(crate) async fn list_namespaces(
     let parent = parent
         .as_ref()
         .and_then(|p| if p.is_empty() { None } else { Some(p.clone()) });
+    let name_pattern = name_prefix
+        .as_deref()
+        .filter(|p| !p.is_empty())
+        .map(like_prefix_pattern);
     let token = page_token
         .as_option()
         .map(PaginateToken::try_from)
@@ -117,6 +132,7 @@ pub(crate) async fn list_namespaces(
             AND w.status = 'active'
             AND array_length("namespace_name", 1) = $2 + 1
             AND "namespace_name"[1:$2] = $3
+            AND ($7::text IS NULL OR "namespace_name"[array_length("namespace_name", 1)] LIKE $7)
             --- PAGINATION
             AND ((n.created_at > $4 OR $4 IS NULL) OR (n.created_at = $4 AND n.namespace_id > $5))
             ORDER BY n.created_at, n.namespace_id ASC
@@ -127,7 +143,8 @@ pub(crate) async fn list_namespaces(
             &*parent,
             token_ts,
             token_id,
-            page_size
+            page_size,
+            name_pattern
         )
         .fetch_all(&mut **transaction)
         .await
@@ -147,6 +164,7 @@ pub(crate) async fn list_namespaces(
             WHERE n.warehouse_id = $1
             AND array_length("namespace_name", 1) = 1
             AND w.status = 'active'
+            AND ($5::text IS NULL OR "namespace_name"[array_length("namespace_name", 1)] LIKE $5)
             AND ((n.created_at > $2 OR $2 IS NULL) OR (n.created_at = $2 AND n.namespace_id > $3))
             ORDER BY n.created_at, n.namespace_id ASC
             LIMIT $4
@@ -154,7 +172,8 @@ pub(crate) async fn list_namespaces(
             *warehouse_id,
             token_ts,
             token_id,
-            page_size
+            page_size,
+            name_pattern
         )
         .fetch_all(&mut **transaction)
         .await
@@ -193,6 +212,9 @@ pub(crate) async fn list_namespaces(
     Ok(namespace_map)
 }
 
+/// Insert a namespace in a single statement. Concurrent creations of the same namespace
+/// are serialized by the `unique_namespace_per_warehouse` constraint: exactly one insert
+/// succeeds, all others fail with a 409 `NamespaceAlreadyExists`.
 pub(crate) async fn create_namespace(
     warehouse_id: WarehouseIdent,
     namespace_id: NamespaceIdentUuid,
@@ -420,6 +442,510 @@ pub(crate) async fn update_namespace_properties(
     Ok(())
 }
 
+pub(crate) async fn update_many_namespace_properties(
+    warehouse_id: WarehouseIdent,
+    properties: Vec<(NamespaceIdentUuid, HashMap<String, String>)>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let (namespace_ids, properties): (Vec<_>, Vec<_>) = properties
+        .into_iter()
+        .map(|(namespace_id, properties)| {
+            serde_json::to_value(properties)
+                .map(|properties| (*namespace_id, properties))
+                .map_err(|e| {
+                    ErrorModel::internal(
+                        "Error serializing namespace properties",
+                        "NamespacePropertiesSerializationError",
+                        Some(Box::new(e)),
+                    )
+                })
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?
+        .into_iter()
+        .unzip();
+
+    let updated = sqlx::query_scalar!(
+        r#"
+        UPDATE namespace n
+        SET namespace_properties = u.properties
+        FROM UNNEST($2::uuid[], $3::jsonb[]) AS u(namespace_id, properties)
+        WHERE n.warehouse_id = $1 AND n.namespace_id = u.namespace_id
+        AND n.warehouse_id IN (
+            SELECT warehouse_id FROM warehouse WHERE status = 'active'
+        )
+        RETURNING n.namespace_id
+        "#,
+        *warehouse_id,
+        &namespace_ids,
+        &properties
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error updating namespace properties".to_string()))?;
+
+    let missing = namespace_ids
+        .iter()
+        .filter(|id| !updated.contains(id))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(ErrorModel::not_found(
+            format!(
+                "Namespaces with ids {} not found in warehouse {warehouse_id}",
+                missing.join(", ")
+            ),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_namespace_storage_prefix(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    storage_prefix: Option<&str>,
+    storage_region: Option<&str>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+        UPDATE namespace
+        SET storage_prefix = $1, storage_region = $4
+        WHERE warehouse_id = $2 AND namespace_id = $3
+        AND warehouse_id IN (
+            SELECT warehouse_id FROM warehouse WHERE status = 'active'
+        )
+        "#,
+        storage_prefix,
+        *warehouse_id,
+        *namespace_id,
+        storage_region
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting namespace storage prefix"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found(
+            format!("Namespace with id {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_namespace_credential_ttl(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    credential_ttl: Option<chrono::Duration>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+        UPDATE namespace
+        SET credential_ttl_seconds = $1
+        WHERE warehouse_id = $2 AND namespace_id = $3
+        AND warehouse_id IN (
+            SELECT warehouse_id FROM warehouse WHERE status = 'active'
+        )
+        "#,
+        credential_ttl.map(|ttl| ttl.num_seconds()),
+        *warehouse_id,
+        *namespace_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting namespace credential TTL"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found(
+            format!("Namespace with id {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Count the active tables and views of a namespace and sum up the data size of
+/// the tables. The data size is taken from the `total-files-size` summary field
+/// of the snapshot the `main` branch points to. Values that are not numeric or
+/// exceed the range of a bigint are not counted.
+pub(crate) async fn namespace_usage(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    include_descendants: bool,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<NamespaceUsage> {
+    let usage = sqlx::query!(
+        r#"
+        WITH root AS (
+            SELECT namespace_name FROM namespace
+            WHERE warehouse_id = $1 AND namespace_id = $2
+        ),
+        namespaces AS (
+            SELECT n.namespace_id FROM namespace n, root r
+            WHERE n.warehouse_id = $1
+            AND (
+                n.namespace_id = $2
+                OR ($3 AND n.namespace_name[1:array_length(r.namespace_name, 1)] = r.namespace_name)
+            )
+        )
+        SELECT
+            EXISTS (SELECT 1 FROM root) AS "exists!",
+            count(*) FILTER (WHERE t.typ = 'table') AS "tables!",
+            count(*) FILTER (WHERE t.typ = 'view') AS "views!",
+            coalesce(sum(f.bytes), 0) <= 9223372036854775807 AS "data_size_in_range!",
+            least(coalesce(sum(f.bytes), 0), 9223372036854775807)::bigint AS "data_size_bytes!"
+        FROM tabular t
+        JOIN namespaces n ON n.namespace_id = t.namespace_id
+        LEFT JOIN table_refs r ON r.table_id = t.tabular_id AND r.table_ref_name = 'main'
+        LEFT JOIN table_snapshot s ON s.table_id = r.table_id AND s.snapshot_id = r.snapshot_id
+        LEFT JOIN LATERAL (
+            SELECT (s.summary->>'total-files-size')::numeric AS bytes
+            WHERE s.summary->>'total-files-size' ~ '^[0-9]{1,19}$'
+        ) f ON f.bytes <= 9223372036854775807
+        WHERE t.deleted_at IS NULL
+        "#,
+        *warehouse_id,
+        *namespace_id,
+        include_descendants
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error computing namespace usage"))?;
+
+    if !usage.exists {
+        return Err(ErrorModel::not_found(
+            format!("Namespace with id {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    if !usage.data_size_in_range {
+        return Err(ErrorModel::bad_request(
+            format!("Data size of namespace {namespace_id} exceeds the supported range"),
+            "NamespaceUsageOutOfRange",
+            None,
+        )
+        .into());
+    }
+
+    Ok(NamespaceUsage {
+        tables: usage.tables,
+        views: usage.views,
+        data_size_bytes: usage.data_size_bytes,
+    })
+}
+
+/// Child namespaces of a namespace, deepest first, and the tables and views of the
+/// namespace and its children. Each list is limited to `limit` entries.
+pub(crate) async fn list_namespace_descendants(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    limit: usize,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<NamespaceDescendants> {
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let namespaces = sqlx::query!(
+        r#"
+        WITH root AS (
+            SELECT namespace_name FROM namespace
+            WHERE warehouse_id = $1 AND namespace_id = $2
+        )
+        SELECT n.namespace_id, n.namespace_name
+        FROM namespace n, root r
+        WHERE n.warehouse_id = $1
+            AND n.namespace_id != $2
+            AND n.namespace_name[1:array_length(r.namespace_name, 1)] = r.namespace_name
+        ORDER BY array_length(n.namespace_name, 1) DESC, n.namespace_name
+        LIMIT $3
+        "#,
+        *warehouse_id,
+        *namespace_id,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error listing child namespaces"))?;
+
+    let tabulars = sqlx::query!(
+        r#"
+        WITH root AS (
+            SELECT namespace_name FROM namespace
+            WHERE warehouse_id = $1 AND namespace_id = $2
+        )
+        SELECT t.tabular_id, t.typ as "typ: TabularType", n.namespace_name, t.name
+        FROM tabular t
+        JOIN namespace n ON n.namespace_id = t.namespace_id, root r
+        WHERE n.warehouse_id = $1
+            AND n.namespace_name[1:array_length(r.namespace_name, 1)] = r.namespace_name
+            AND t.deleted_at IS NULL
+        ORDER BY n.namespace_name, t.name
+        LIMIT $3
+        "#,
+        *warehouse_id,
+        *namespace_id,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error listing tables and views of namespace"))?;
+
+    let parse_namespace = |name: Vec<String>| {
+        NamespaceIdent::from_vec(name).map_err(|e| {
+            ErrorModel::internal(
+                "Error parsing namespace",
+                "NamespaceParseError",
+                Some(Box::new(e)),
+            )
+        })
+    };
+    Ok(NamespaceDescendants {
+        namespaces: namespaces
+            .into_iter()
+            .map(|n| Ok((n.namespace_id.into(), parse_namespace(n.namespace_name)?)))
+            .collect::<Result<_, ErrorModel>>()?,
+        tabulars: tabulars
+            .into_iter()
+            .map(|t| {
+                let tabular_id = match t.typ {
+                    TabularType::Table => TabularIdentUuid::Table(t.tabular_id),
+                    TabularType::View => TabularIdentUuid::View(t.tabular_id),
+                };
+                Ok((
+                    tabular_id,
+                    TableIdent::new(parse_namespace(t.namespace_name)?, t.name),
+                ))
+            })
+            .collect::<Result<_, ErrorModel>>()?,
+    })
+}
+
+/// Active tables of a namespace, optionally including the tables of all child
+/// namespaces, ordered by namespace and name.
+pub(crate) async fn list_namespace_tables(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    include_descendants: bool,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<(TableIdentUuid, TableIdent)>> {
+    let tables = sqlx::query!(
+        r#"
+        WITH root AS (
+            SELECT namespace_name FROM namespace
+            WHERE warehouse_id = $1 AND namespace_id = $2
+        )
+        SELECT t.tabular_id, n.namespace_name, t.name
+        FROM tabular t
+        JOIN namespace n ON n.namespace_id = t.namespace_id, root r
+        WHERE n.warehouse_id = $1
+            AND (
+                n.namespace_id = $2
+                OR ($3 AND n.namespace_name[1:array_length(r.namespace_name, 1)] = r.namespace_name)
+            )
+            AND t.typ = 'table'
+            AND t.deleted_at IS NULL
+            AND t.metadata_location IS NOT NULL
+        ORDER BY n.namespace_name, t.name
+        "#,
+        *warehouse_id,
+        *namespace_id,
+        include_descendants
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error listing tables of namespace"))?;
+
+    tables
+        .into_iter()
+        .map(|t| {
+            let namespace = NamespaceIdent::from_vec(t.namespace_name).map_err(|e| {
+                ErrorModel::internal(
+                    "Error parsing namespace",
+                    "NamespaceParseError",
+                    Some(Box::new(e)),
+                )
+            })?;
+            Ok((
+                TableIdentUuid::from(t.tabular_id),
+                TableIdent::new(namespace, t.name),
+            ))
+        })
+        .collect()
+}
+
+/// Delete namespaces of the warehouse without tables, views and child namespaces
+/// that were not created, updated or emptied after `inactive_since`. Parents that
+/// become empty are deleted in the same transaction if they are inactive as well.
+/// Namespaces whose first level is in `exempt_namespaces` are never deleted.
+///
+/// Candidates are locked with `SKIP LOCKED` and re-checked in a new snapshot
+/// before they are deleted: Tables and views created concurrently lock their
+/// namespace, so they either block the deletion or prevent it.
+///
+/// Only one replica cleans up a warehouse at a time. If another transaction
+/// holds the cleanup lock of the warehouse, nothing is deleted.
+pub(crate) async fn delete_empty_namespaces(
+    warehouse_id: WarehouseIdent,
+    inactive_since: chrono::DateTime<Utc>,
+    exempt_namespaces: &[String],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<NamespaceIdentUuid>> {
+    let locked = sqlx::query_scalar!(
+        r#"SELECT pg_try_advisory_xact_lock(hashtextextended('empty_namespace_cleanup:' || $1::uuid::text, 0)) as "locked!""#,
+        *warehouse_id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error locking warehouse for empty namespace cleanup"))?;
+    if !locked {
+        tracing::debug!("Empty namespaces of warehouse {warehouse_id} are cleaned up elsewhere");
+        return Ok(vec![]);
+    }
+
+    let mut deleted = vec![];
+    loop {
+        let candidates = sqlx::query_scalar!(
+            r#"
+            SELECT n.namespace_id
+            FROM namespace n
+            WHERE n.warehouse_id = $1
+                AND NOT n.namespace_name[1] = ANY($3::text[])
+                AND greatest(n.created_at, n.updated_at, n.last_tabular_removed_at) < $2
+                AND NOT EXISTS (SELECT 1 FROM tabular t WHERE t.namespace_id = n.namespace_id)
+                AND NOT EXISTS (
+                    SELECT 1 FROM namespace c
+                    WHERE c.warehouse_id = $1
+                        AND c.namespace_id != n.namespace_id
+                        AND c.namespace_name[1:array_length(n.namespace_name, 1)] = n.namespace_name
+                )
+            FOR UPDATE OF n SKIP LOCKED
+            "#,
+            *warehouse_id,
+            inactive_since,
+            exempt_namespaces
+        )
+        .fetch_all(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error fetching empty namespaces"))?;
+        if candidates.is_empty() {
+            break;
+        }
+
+        let removed = sqlx::query_scalar!(
+            r#"
+            DELETE FROM namespace n
+            WHERE n.namespace_id = ANY($2::uuid[])
+                AND NOT EXISTS (SELECT 1 FROM tabular t WHERE t.namespace_id = n.namespace_id)
+                AND NOT EXISTS (
+                    SELECT 1 FROM namespace c
+                    WHERE c.warehouse_id = $1
+                        AND c.namespace_id != n.namespace_id
+                        AND c.namespace_name[1:array_length(n.namespace_name, 1)] = n.namespace_name
+                )
+            RETURNING n.namespace_id
+            "#,
+            *warehouse_id,
+            &candidates
+        )
+        .fetch_all(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error deleting empty namespaces"))?;
+        if removed.is_empty() {
+            break;
+        }
+        deleted.extend(removed.into_iter().map(NamespaceIdentUuid::from));
+    }
+
+    Ok(deleted)
+}
+
+/// Rename `source` to `destination`, including all child namespaces.
+/// Tables and views reference their namespace by id and move along.
+pub(crate) async fn rename_namespace(
+    warehouse_id: WarehouseIdent,
+    source: &NamespaceIdent,
+    destination: &NamespaceIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let max_depth = sqlx::query_scalar!(
+        r#"
+        SELECT max(array_length(namespace_name, 1)) AS "max_depth"
+        FROM namespace
+        WHERE warehouse_id = $1
+        AND namespace_name[1:array_length($2::text[], 1)] = $2
+        AND warehouse_id IN (
+            SELECT warehouse_id FROM warehouse WHERE status = 'active'
+        )
+        "#,
+        *warehouse_id,
+        &**source
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespaces to rename"))?;
+
+    let Some(max_depth) = max_depth else {
+        return Err(ErrorModel::not_found(
+            format!("Namespace {source:?} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    };
+
+    let source_depth = i32::try_from(source.len()).unwrap_or(i32::MAX);
+    let destination_depth = i32::try_from(destination.len()).unwrap_or(i32::MAX);
+    let new_max_depth = max_depth - source_depth + destination_depth;
+    if new_max_depth > MAX_NAMESPACE_DEPTH {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Renaming namespace {source:?} to {destination:?} would nest child namespaces {new_max_depth} levels deep. Maximum is {MAX_NAMESPACE_DEPTH}."
+            ),
+            "NamespaceDepthExceeded",
+            None,
+        )
+        .into());
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE namespace
+        SET namespace_name = $3 || namespace_name[array_length($2::text[], 1) + 1:]
+        WHERE warehouse_id = $1
+        AND namespace_name[1:array_length($2::text[], 1)] = $2
+        "#,
+        *warehouse_id,
+        &**source,
+        &**destination
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => ErrorModel::conflict(
+            format!("Namespace {destination:?} already exists"),
+            "NamespaceAlreadyExists",
+            Some(Box::new(db_error)),
+        ),
+        _ => e.into_error_model("Error renaming namespace"),
+    })?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::{
@@ -430,7 +956,7 @@ pub(crate) mod tests {
         implementations::postgres::{
             tabular::table::tests::initialize_table, CatalogState, PostgresTransaction,
         },
-        service::{Catalog as _, Transaction as _},
+        service::{Catalog as _, ListFlags, TableIdent, TableIdentUuid, Transaction as _},
     };
 
     pub(crate) async fn initialize_namespace(
@@ -518,6 +1044,7 @@ pub(crate) mod tests {
                 page_size: None,
                 parent: None,
                 return_uuids: false,
+                name_prefix: None,
             },
             transaction.transaction(),
         )
@@ -606,6 +1133,7 @@ pub(crate) mod tests {
                 page_size: Some(1),
                 parent: None,
                 return_uuids: false,
+                name_prefix: None,
             },
             t.transaction(),
         )
@@ -632,6 +1160,7 @@ pub(crate) mod tests {
                 page_size: Some(2),
                 parent: None,
                 return_uuids: false,
+                name_prefix: None,
             },
             t.transaction(),
         )
@@ -659,6 +1188,7 @@ pub(crate) mod tests {
                 page_size: Some(3),
                 parent: None,
                 return_uuids: false,
+                name_prefix: None,
             },
             t.transaction(),
         )
@@ -669,6 +1199,49 @@ pub(crate) mod tests {
         assert_eq!(namespaces.into_hashmap(), HashMap::new());
     }
 
+    #[sqlx::test]
+    async fn test_list_namespaces_by_name_prefix(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        for name in [
+            vec!["sales_eu"],
+            vec!["salesx"],
+            vec!["hr"],
+            vec!["hr", "sales_archive"],
+            vec!["hr", "payroll"],
+        ] {
+            let namespace = NamespaceIdent::from_strs(name).unwrap();
+            initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        }
+
+        let mut t = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        for (parent, expected) in [
+            (None, vec!["sales_eu"]),
+            (Some(vec!["hr"]), vec!["hr", "sales_archive"]),
+        ] {
+            let namespaces = PostgresCatalog::list_namespaces(
+                warehouse_id,
+                &ListNamespacesQuery {
+                    page_token: crate::api::iceberg::v1::PageToken::NotSpecified,
+                    page_size: None,
+                    parent: parent.map(|p| NamespaceIdent::from_strs(p).unwrap()),
+                    return_uuids: false,
+                    name_prefix: Some("sales_".to_string()),
+                },
+                t.transaction(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(
+                namespaces.into_hashmap().into_values().collect::<Vec<_>>(),
+                vec![NamespaceIdent::from_strs(expected).unwrap()]
+            );
+        }
+    }
+
     #[sqlx::test]
     async fn test_cannot_drop_nonempty_namespace(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -771,4 +1344,303 @@ pub(crate) mod tests {
         assert_eq!(response.error.code, StatusCode::CONFLICT);
         assert_eq!(response.error.r#type, "NamespaceAlreadyExists");
     }
+
+    #[sqlx::test]
+    async fn test_rename_namespace(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let ns = |names: &[&str]| {
+            NamespaceIdent::from_vec(names.iter().map(ToString::to_string).collect()).unwrap()
+        };
+        let (parent_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &ns(&["a"]), None).await;
+        let (child_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &ns(&["a", "b"]), None).await;
+        initialize_namespace(state.clone(), warehouse_id, &ns(&["x"]), None).await;
+        let parent_table =
+            initialize_table(warehouse_id, state.clone(), false, Some(ns(&["a"])), None).await;
+        let child_table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(ns(&["a", "b"])),
+            None,
+        )
+        .await;
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = rename_namespace(
+            warehouse_id,
+            &ns(&["a"]),
+            &ns(&["X"]),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        assert_eq!(err.error.r#type, "NamespaceAlreadyExists");
+        transaction.rollback().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        rename_namespace(
+            warehouse_id,
+            &ns(&["a"]),
+            &ns(&["x", "c"]),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        for (namespace, expected) in [
+            (ns(&["a"]), None),
+            (ns(&["a", "b"]), None),
+            (ns(&["x", "c"]), Some(parent_id)),
+            (ns(&["x", "c", "b"]), Some(child_id)),
+        ] {
+            let namespace_id = namespace_to_id(warehouse_id, &namespace, transaction.transaction())
+                .await
+                .unwrap();
+            assert_eq!(namespace_id, expected, "{namespace:?}");
+        }
+
+        for (table, namespace) in [
+            (parent_table, ns(&["x", "c"])),
+            (child_table, ns(&["x", "c", "b"])),
+        ] {
+            let renamed = TableIdent {
+                namespace,
+                name: table.table_ident.name.clone(),
+            };
+            let table_id = PostgresCatalog::table_to_id(
+                warehouse_id,
+                &renamed,
+                ListFlags::default(),
+                transaction.transaction(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(table_id, Some(table.table_id));
+            let old_table_id = PostgresCatalog::table_to_id(
+                warehouse_id,
+                &table.table_ident,
+                ListFlags::default(),
+                transaction.transaction(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(old_table_id, None);
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_rename_namespace_depth_is_limited(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let ns = |names: &[&str]| {
+            NamespaceIdent::from_vec(names.iter().map(ToString::to_string).collect()).unwrap()
+        };
+        for depth in 1..=3 {
+            let names = ["a", "b", "c"];
+            initialize_namespace(state.clone(), warehouse_id, &ns(&names[..depth]), None).await;
+        }
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = rename_namespace(
+            warehouse_id,
+            &ns(&["a"]),
+            &ns(&["w", "x", "y", "z"]),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "NamespaceDepthExceeded");
+
+        let err = rename_namespace(
+            warehouse_id,
+            &ns(&["missing"]),
+            &ns(&["x"]),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+    }
+
+    async fn set_main_branch_size(pool: &sqlx::PgPool, table_id: TableIdentUuid, size: &str) {
+        sqlx::query(
+            r#"
+            UPDATE table_snapshot
+            SET summary = summary || jsonb_build_object('total-files-size', $2::text)
+            WHERE table_id = $1 AND snapshot_id = 1
+            "#,
+        )
+        .bind(*table_id)
+        .bind(size)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO table_refs (table_id, table_ref_name, snapshot_id, retention)
+            VALUES ($1, 'main', 1, '{"type": "branch"}')
+            "#,
+        )
+        .bind(*table_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_namespace_usage(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let ns = |names: &[&str]| {
+            NamespaceIdent::from_vec(names.iter().map(ToString::to_string).collect()).unwrap()
+        };
+        let (namespace_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &ns(&["a"]), None).await;
+        initialize_namespace(state.clone(), warehouse_id, &ns(&["a", "b"]), None).await;
+        let (overflowing_namespace_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &ns(&["c"]), None).await;
+        for (namespace, size) in [
+            (ns(&["a"]), "1000"),
+            (ns(&["a"]), "234"),
+            // Beyond the range of a bigint, not counted
+            (ns(&["a"]), "99999999999999999999"),
+            (ns(&["a", "b"]), "5"),
+            (ns(&["c"]), "9223372036854775807"),
+            (ns(&["c"]), "1"),
+        ] {
+            let table =
+                initialize_table(warehouse_id, state.clone(), false, Some(namespace), None).await;
+            set_main_branch_size(&pool, table.table_id, size).await;
+        }
+
+        let usage = |namespace_id, include_descendants| {
+            let state = state.clone();
+            async move {
+                let mut transaction = PostgresTransaction::begin_read(state).await.unwrap();
+                namespace_usage(
+                    warehouse_id,
+                    namespace_id,
+                    include_descendants,
+                    transaction.transaction(),
+                )
+                .await
+            }
+        };
+        assert_eq!(
+            usage(namespace_id, false).await.unwrap(),
+            NamespaceUsage {
+                tables: 3,
+                views: 0,
+                data_size_bytes: 1234,
+            }
+        );
+        assert_eq!(
+            usage(namespace_id, true).await.unwrap(),
+            NamespaceUsage {
+                tables: 4,
+                views: 0,
+                data_size_bytes: 1239,
+            }
+        );
+        let err = usage(overflowing_namespace_id, false).await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "NamespaceUsageOutOfRange");
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let err = namespace_usage(
+            warehouse_id,
+            NamespaceIdentUuid::default(),
+            false,
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test]
+    async fn test_update_many_namespace_properties_fails_as_a_whole(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let properties = |value: &str| HashMap::from_iter([("k".to_string(), value.to_string())]);
+        let mut namespace_ids = vec![];
+        for name in ["a", "b"] {
+            let (namespace_id, _) = initialize_namespace(
+                state.clone(),
+                warehouse_id,
+                &NamespaceIdent::new(name.to_string()),
+                Some(properties("old")),
+            )
+            .await;
+            namespace_ids.push(namespace_id);
+        }
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = update_many_namespace_properties(
+            warehouse_id,
+            vec![
+                (namespace_ids[0], properties("new")),
+                (NamespaceIdentUuid::default(), properties("new")),
+                (namespace_ids[1], properties("new")),
+            ],
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+        transaction.rollback().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        for namespace_id in &namespace_ids {
+            let namespace = get_namespace(warehouse_id, *namespace_id, transaction.transaction())
+                .await
+                .unwrap();
+            assert_eq!(namespace.properties, Some(properties("old")));
+        }
+        update_many_namespace_properties(
+            warehouse_id,
+            namespace_ids
+                .iter()
+                .map(|id| (*id, properties("new")))
+                .collect(),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        for namespace_id in namespace_ids {
+            let namespace = get_namespace(warehouse_id, namespace_id, transaction.transaction())
+                .await
+                .unwrap();
+            assert_eq!(namespace.properties, Some(properties("new")));
+        }
+    }
 }
@@ -0,0 +1,120 @@
+use sqlx::types::Json;
+
+use super::dbutils::DBErrorHandler as _;
+use crate::{
+    api::management::v1::table_template::{TableTemplate, TableTemplateResponse},
+    service::Result,
+    WarehouseIdent,
+};
+
+pub(crate) async fn create_table_template_version(
+    warehouse_id: WarehouseIdent,
+    name: &str,
+    template: &TableTemplate,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<TableTemplateResponse> {
+    let created = sqlx::query!(
+        r#"
+        INSERT INTO table_template (warehouse_id, name, version, template)
+        SELECT $1, $2, coalesce(max(version), 0) + 1, $3
+        FROM table_template
+        WHERE warehouse_id = $1 AND name = $2
+        RETURNING version, created_at
+        "#,
+        *warehouse_id,
+        name,
+        Json(template) as _,
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error creating table template"))?;
+
+    Ok(TableTemplateResponse {
+        name: name.to_string(),
+        version: created.version,
+        template: template.clone(),
+        created_at: created.created_at,
+    })
+}
+
+pub(crate) async fn get_table_template(
+    warehouse_id: WarehouseIdent,
+    name: &str,
+    version: Option<i32>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<TableTemplateResponse>> {
+    let template = sqlx::query!(
+        r#"
+        SELECT name, version, template as "template: Json<TableTemplate>", created_at
+        FROM table_template
+        WHERE warehouse_id = $1 AND name = $2
+            AND ($3::integer IS NULL OR version = $3)
+        ORDER BY version DESC
+        LIMIT 1
+        "#,
+        *warehouse_id,
+        name,
+        version,
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table template"))?;
+
+    Ok(template.map(|t| TableTemplateResponse {
+        name: t.name,
+        version: t.version,
+        template: t.template.0,
+        created_at: t.created_at,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        implementations::postgres::{
+            warehouse::test::initialize_warehouse, CatalogState, PostgresTransaction,
+        },
+        service::Transaction as _,
+    };
+
+    #[sqlx::test]
+    async fn test_table_template_versions(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        for value in ["gold", "silver"] {
+            let template = TableTemplate {
+                properties: HashMap::from([("retention_class".to_string(), value.to_string())]),
+                ..Default::default()
+            };
+            create_table_template_version(warehouse_id, "events", &template, t.transaction())
+                .await
+                .unwrap();
+        }
+
+        let latest = get_table_template(warehouse_id, "events", None, t.transaction())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.version, 2);
+        assert_eq!(latest.template.properties["retention_class"], "silver");
+
+        let first = get_table_template(warehouse_id, "events", Some(1), t.transaction())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.template.properties["retention_class"], "gold");
+
+        let missing = get_table_template(warehouse_id, "other", None, t.transaction())
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+        t.commit().await.unwrap();
+    }
+}
@@ -13,15 +13,16 @@ use iceberg_ext::{configs::Location, NamespaceIdent};
 use sqlx::{postgres::PgArguments, Arguments, Execute, FromRow, Postgres, QueryBuilder};
 use uuid::Uuid;
 
-use super::dbutils::DBErrorHandler as _;
+use super::dbutils::{like_prefix_pattern, DBErrorHandler as _};
 use crate::{
     api::iceberg::v1::{PaginatedMapping, PaginationQuery, MAX_PAGE_SIZE},
     implementations::postgres::pagination::{PaginateToken, V1PaginateToken},
     service::{
         storage::{join_location, split_location},
         task_queue::TaskId,
-        DeletionDetails, ErrorModel, NamespaceIdentUuid, Result, TableIdent, TableIdentUuid,
-        TabularIdentBorrowed, TabularIdentOwned, TabularIdentUuid, UndropTabularResponse,
+        DeletionDetails, ErrorModel, NamespaceIdentUuid, NamespaceTabularCounts, Result,
+        TableIdent, TableIdentUuid, TabularIdentBorrowed, TabularIdentOwned, TabularIdentUuid,
+        UndropTabularResponse,
     },
     WarehouseIdent,
 };
@@ -294,9 +295,17 @@ pub(crate) async fn create_tabular(
     )
     .fetch_one(&mut **transaction)
     .await
-    .map_err(|e| {
-        tracing::warn!(?e, "Error creating new {typ}");
-        e.into_error_model(format!("Error creating {typ}"))
+    .map_err(|e| match e {
+        // The namespace was removed concurrently, e.g. by the empty namespace cleanup
+        sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => ErrorModel::not_found(
+            "Namespace not found",
+            "NamespaceNotFound",
+            Some(Box::new(e)),
+        ),
+        _ => {
+            tracing::warn!(?e, "Error creating new {typ}");
+            e.into_error_model(format!("Error creating {typ}"))
+        }
     })?;
 
     let location_is_taken = sqlx::query_scalar!(
@@ -333,11 +342,106 @@ pub(crate) async fn create_tabular(
     Ok(tabular_id)
 }
 
+/// Check whether `location` overlaps with the location of any existing tabular,
+/// including soft-deleted ones.
+pub(crate) async fn is_tabular_location_in_use(
+    location: &Location,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<bool> {
+    let (_, fs_location) = split_location(location.url().as_str())?;
+    let partial_locations = get_partial_fs_locations(location)?;
+
+    let in_use = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1
+               FROM tabular
+               WHERE fs_location = ANY($1) OR
+                     (length($2) < length(fs_location) AND ((TRIM(TRAILING '/' FROM fs_location) || '/') LIKE $2 || '/%'))
+           ) as "exists!""#,
+        &partial_locations,
+        fs_location
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::warn!(?e, "Error checking whether location is in use");
+        e.into_error_model("Error checking whether location is in use".to_string())
+    })?;
+
+    Ok(in_use)
+}
+
+/// Check whether a table or view with `tabular_id` exists in any warehouse,
+/// including staged and soft-deleted ones.
+pub(crate) async fn is_tabular_id_in_use(
+    tabular_id: TabularIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<bool> {
+    let in_use = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM tabular WHERE tabular_id = $1
+           ) as "exists!""#,
+        *tabular_id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::warn!(?e, "Error checking whether tabular id is in use");
+        e.into_error_model("Error checking whether tabular id is in use".to_string())
+    })?;
+
+    Ok(in_use)
+}
+
+/// Count the tables and views of a namespace that are not soft-deleted.
+/// The namespace row is locked so that concurrent creations are serialized.
+pub(crate) async fn count_tabulars_in_namespace(
+    namespace_id: NamespaceIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<NamespaceTabularCounts> {
+    // Locked in a separate statement: the lock must be taken even if the namespace
+    // is empty, and the count must see tabulars committed while waiting for it.
+    sqlx::query_scalar!(
+        r#"SELECT namespace_id FROM namespace WHERE namespace_id = $1 FOR UPDATE"#,
+        *namespace_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error locking namespace"))?
+    .ok_or_else(|| {
+        ErrorModel::not_found(
+            format!("Namespace {namespace_id} not found"),
+            "NamespaceNotFound",
+            None,
+        )
+    })?;
+
+    let counts = sqlx::query!(
+        r#"
+        SELECT
+            count(*) FILTER (WHERE typ = 'table') AS "tables!",
+            count(*) FILTER (WHERE typ = 'view') AS "views!"
+        FROM tabular
+        WHERE namespace_id = $1 AND deleted_at IS NULL
+        "#,
+        *namespace_id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error counting tabulars in namespace"))?;
+
+    Ok(NamespaceTabularCounts {
+        tables: counts.tables,
+        views: counts.views,
+    })
+}
+
 #[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 pub(crate) async fn list_tabulars<'e, 'c, E>(
     warehouse_id: WarehouseIdent,
     namespace: Option<&NamespaceIdent>,
     namespace_id: Option<NamespaceIdentUuid>,
+    name_prefix: Option<&str>,
     list_flags: crate::service::ListFlags,
     catalog_state: E,
     typ: Option<TabularType>,
@@ -368,6 +472,10 @@ where
         )
         .unzip();
 
+    let name_pattern = name_prefix
+        .filter(|p| !p.is_empty())
+        .map(like_prefix_pattern);
+
     let tables = sqlx::query!(
         r#"
         SELECT
@@ -394,6 +502,7 @@ where
             AND (t.deleted_at IS NULL OR $5)
             AND (t.metadata_location IS NOT NULL OR $6)
             AND (t.table_migrated != $7)
+            AND ($12::text IS NULL OR t.name LIKE $12)
             AND ((t.created_at > $8 OR $8 IS NULL) OR (t.created_at = $8 AND t.tabular_id > $9))
             ORDER BY t.created_at, t.tabular_id ASC
             LIMIT $10
@@ -409,6 +518,7 @@ where
         token_id,
         page_size,
         namespace_id.map(|n| *n),
+        name_pattern,
     )
     .fetch_all(catalog_state)
     .await
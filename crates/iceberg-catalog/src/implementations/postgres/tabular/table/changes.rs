@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use iceberg_ext::configs::Location;
+use uuid::Uuid;
+
+use crate::{
+    api::iceberg::v1::{PaginatedMapping, PaginationQuery, MAX_PAGE_SIZE},
+    implementations::postgres::{
+        dbutils::DBErrorHandler as _,
+        pagination::{PaginateToken, V1PaginateToken},
+        tabular::try_parse_namespace_ident,
+    },
+    service::{ChangedTable, ErrorModel, Result, TableIdent, TableIdentUuid},
+    WarehouseIdent,
+};
+
+pub(crate) async fn list_changed_tables<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseIdent,
+    since: DateTime<Utc>,
+    transaction: E,
+    pagination_query: PaginationQuery,
+) -> Result<PaginatedMapping<TableIdentUuid, ChangedTable>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let page_size = pagination_query
+        .page_size
+        .map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token = pagination_query
+        .page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?;
+
+    // Keyset pagination on (changed_at, tabular_id): tables that change while a client
+    // pages through the result move to the end instead of shifting other tables.
+    // Tables committed in the same transaction share their change time, so the
+    // timestamp alone is no cursor and `since` is inclusive.
+    let (token_ts, token_id) = token
+        .as_ref()
+        .map(
+            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
+                (created_at, id)
+            },
+        )
+        .unzip();
+
+    let tables = sqlx::query!(
+        r#"
+        SELECT
+            t.tabular_id,
+            t.name as "table_name",
+            n.namespace_name,
+            t.metadata_location as "metadata_location!",
+            COALESCE(t.updated_at, t.created_at) as "changed_at!"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        WHERE n.warehouse_id = $1
+            AND w.status = 'active'
+            AND t.typ = 'table'
+            AND t.deleted_at IS NULL
+            AND t.metadata_location IS NOT NULL
+            AND COALESCE(t.updated_at, t.created_at) >= $2
+            AND (
+                (COALESCE(t.updated_at, t.created_at) > $3 OR $3 IS NULL)
+                OR (COALESCE(t.updated_at, t.created_at) = $3 AND t.tabular_id > $4)
+            )
+        ORDER BY COALESCE(t.updated_at, t.created_at), t.tabular_id ASC
+        LIMIT $5
+        "#,
+        *warehouse_id,
+        since,
+        token_ts,
+        token_id,
+        page_size,
+    )
+    .fetch_all(transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching changed tables"))?;
+
+    let mut changed_tables = PaginatedMapping::with_capacity(tables.len());
+    for table in tables {
+        let table_id = TableIdentUuid::from(table.tabular_id);
+        changed_tables.insert(
+            table_id,
+            ChangedTable {
+                table_id,
+                table: TableIdent {
+                    namespace: try_parse_namespace_ident(table.namespace_name)?,
+                    name: table.table_name,
+                },
+                metadata_location: Location::from_str(&table.metadata_location).map_err(|e| {
+                    ErrorModel::internal(
+                        "Error parsing metadata location",
+                        "InternalMetadataLocationParseError",
+                        Some(Box::new(e)),
+                    )
+                })?,
+                updated_at: table.changed_at,
+            },
+            PaginateToken::V1(V1PaginateToken {
+                created_at: table.changed_at,
+                id: table.tabular_id,
+            })
+            .to_string(),
+        );
+    }
+
+    Ok(changed_tables)
+}
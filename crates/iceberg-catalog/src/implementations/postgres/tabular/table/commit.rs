@@ -196,6 +196,7 @@ async fn handle_atomic_updates(
                 .filter_map(|s| new_metadata.schema_by_id(s))
                 .collect::<Vec<_>>()
                 .into_iter(),
+            Some(new_metadata.default_partition_spec().spec_id()),
             transaction,
             new_metadata.uuid(),
         )
@@ -8,7 +8,10 @@ use iceberg_ext::catalog::rest::ErrorModel;
 use sqlx::{PgConnection, Postgres, Transaction};
 use uuid::Uuid;
 
-use crate::{api, implementations::postgres::dbutils::DBErrorHandler};
+use crate::{
+    api, catalog::tables::PROPERTY_TABLE_DESCRIPTION,
+    implementations::postgres::dbutils::DBErrorHandler,
+};
 
 pub(super) async fn remove_schemas(
     table_id: Uuid,
@@ -451,9 +454,12 @@ pub(super) async fn remove_snapshots(
     Ok(())
 }
 
+/// `partition_spec_id` is the default partition spec of the table at the time
+/// the snapshots are added, if known.
 pub(super) async fn insert_snapshots(
     tabular_id: Uuid,
     snapshots: impl ExactSizeIterator<Item = &SnapshotRef>,
+    partition_spec_id: Option<i32>,
     transaction: &mut Transaction<'_, Postgres>,
 ) -> api::Result<()> {
     let snap_cnt = snapshots.len();
@@ -491,8 +497,9 @@ pub(super) async fn insert_snapshots(
                                           manifest_list,
                                           summary,
                                           schema_id,
-                                          timestamp_ms)
-            SELECT * FROM UNNEST(
+                                          timestamp_ms,
+                                          partition_spec_id)
+            SELECT *, $9::INT FROM UNNEST(
                 $1::BIGINT[],
                 $2::UUID[],
                 $3::BIGINT[],
@@ -509,7 +516,8 @@ pub(super) async fn insert_snapshots(
         &manifs,
         &summaries,
         &schemas as _,
-        &timestamps
+        &timestamps,
+        partition_spec_id
     )
     .execute(&mut **transaction)
     .await
@@ -539,13 +547,29 @@ pub(crate) async fn set_table_properties(
         &keys,
         &vals
     )
-    .execute(transaction)
+    .execute(&mut *transaction)
     .await
     .map_err(|e| {
         let message = "Error inserting table property".to_string();
         tracing::warn!("{}", message);
         e.into_error_model(message)
     })?;
+
+    // The description is stored separately, but always mirrors the `comment` property
+    sqlx::query!(
+        r#"UPDATE "table" SET description = $2 WHERE table_id = $1"#,
+        table_id,
+        properties
+            .get(PROPERTY_TABLE_DESCRIPTION)
+            .map(String::as_str)
+    )
+    .execute(transaction)
+    .await
+    .map_err(|e| {
+        let message = "Error setting table description".to_string();
+        tracing::warn!("{}", message);
+        e.into_error_model(message)
+    })?;
     Ok(())
 }
 
@@ -27,6 +27,8 @@ pub(crate) async fn create_table(
         table_ident,
         table_metadata,
         metadata_location,
+        storage_team,
+        read_only,
     }: TableCreation<'_>,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> api::Result<CreateTableResponse> {
@@ -55,6 +57,12 @@ pub(crate) async fn create_table(
     .await?;
 
     insert_table(&table_metadata, transaction, tabular_id).await?;
+    if let Some(storage_team) = storage_team {
+        set_storage_team(tabular_id, storage_team, transaction).await?;
+    }
+    if read_only {
+        set_read_only(tabular_id, transaction).await?;
+    }
 
     common::insert_schemas(table_metadata.schemas_iter(), transaction, tabular_id).await?;
     common::set_current_schema(table_metadata.current_schema_id(), transaction, tabular_id).await?;
@@ -72,7 +80,8 @@ pub(crate) async fn create_table(
     )
     .await?;
 
-    common::insert_snapshots(tabular_id, table_metadata.snapshots(), transaction).await?;
+    // The partition spec of snapshots in a new table's history is not known.
+    common::insert_snapshots(tabular_id, table_metadata.snapshots(), None, transaction).await?;
     common::insert_snapshot_refs(&table_metadata, transaction).await?;
     common::insert_snapshot_log(table_metadata.history().iter(), transaction, tabular_id).await?;
 
@@ -186,3 +195,33 @@ async fn insert_table(
     })?;
     Ok(())
 }
+
+async fn set_storage_team(
+    tabular_id: Uuid,
+    storage_team: &str,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"UPDATE "table" SET storage_team = $2 WHERE table_id = $1"#,
+        tabular_id,
+        storage_team
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error recording storage team of table".to_string()))?;
+    Ok(())
+}
+
+async fn set_read_only(
+    tabular_id: Uuid,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"UPDATE "table" SET read_only = true WHERE table_id = $1"#,
+        tabular_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error marking table as read-only".to_string()))?;
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use iceberg::spec::Summary;
+use sqlx::types::Json;
+
+use crate::{
+    api::{
+        iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE},
+        management::v1::table::{TableHistoryEntry, TableHistoryResponse},
+    },
+    implementations::postgres::{
+        dbutils::DBErrorHandler as _,
+        pagination::{PaginateToken, V1PaginateToken},
+    },
+    service::{ErrorModel, Result, TableIdentUuid},
+    WarehouseIdent,
+};
+
+pub(crate) async fn insert_commit_attribution(
+    table_id: TableIdentUuid,
+    snapshot_id: i64,
+    principal: &str,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO table_commit_attribution (table_id, snapshot_id, principal)
+        SELECT $1, $2, $3
+        WHERE EXISTS (SELECT 1 FROM "table" WHERE table_id = $1)
+        ON CONFLICT (table_id, snapshot_id) DO NOTHING
+        "#,
+        *table_id,
+        snapshot_id,
+        principal
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error recording commit attribution"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn table_history<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+    connection: E,
+) -> Result<TableHistoryResponse>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    // Snapshots are identified by their commit time and id
+    let token = page_token
+        .as_option()
+        .map(PaginateToken::<String>::try_from)
+        .transpose()?
+        .map(|PaginateToken::V1(V1PaginateToken { created_at, id })| {
+            id.parse::<i64>()
+                .map(|snapshot_id| (created_at.timestamp_millis(), snapshot_id))
+                .map_err(|e| {
+                    ErrorModel::bad_request(
+                        "Invalid paginate token",
+                        "PaginateTokenParseError",
+                        Some(Box::new(e)),
+                    )
+                })
+        })
+        .transpose()?;
+    let (token_ts, token_id) = token.unzip();
+
+    let snapshots = sqlx::query!(
+        r#"
+        SELECT s.snapshot_id, s.parent_snapshot_id, s.timestamp_ms,
+            s.summary as "summary: Json<Summary>",
+            a.principal as "principal?"
+        FROM table_snapshot s
+        INNER JOIN tabular t ON t.tabular_id = s.table_id
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        LEFT JOIN table_commit_attribution a
+            ON a.table_id = s.table_id AND a.snapshot_id = s.snapshot_id
+        WHERE n.warehouse_id = $1
+            AND s.table_id = $2
+            AND t.deleted_at IS NULL
+            --- PAGINATION
+            AND ($3::bigint IS NULL OR s.timestamp_ms < $3
+                OR (s.timestamp_ms = $3 AND s.snapshot_id < $4))
+        ORDER BY s.timestamp_ms DESC, s.snapshot_id DESC
+        LIMIT $5
+        "#,
+        *warehouse_id,
+        *table_id,
+        token_ts,
+        token_id,
+        page_size,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table history"))?;
+
+    let history = snapshots
+        .into_iter()
+        .map(|s| TableHistoryEntry {
+            snapshot_id: s.snapshot_id,
+            parent_snapshot_id: s.parent_snapshot_id,
+            timestamp_ms: s.timestamp_ms,
+            operation: s.summary.operation.as_str().to_string(),
+            summary: s.summary.0.additional_properties,
+            principal: s.principal,
+        })
+        .collect::<Vec<_>>();
+
+    let next_page_token = history.last().and_then(|entry| {
+        chrono::DateTime::from_timestamp_millis(entry.timestamp_ms).map(|created_at| {
+            PaginateToken::V1(V1PaginateToken {
+                created_at,
+                id: entry.snapshot_id.to_string(),
+            })
+            .to_string()
+        })
+    });
+
+    Ok(TableHistoryResponse {
+        history,
+        next_page_token,
+    })
+}
@@ -0,0 +1,62 @@
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{storage::join_location, Result, TableLocationCollision},
+    WarehouseIdent,
+};
+
+/// List tables of the warehouse whose location equals or is nested in the location
+/// of another table. Soft-deleted tables are included, as purging them would delete
+/// the data of the other table.
+///
+/// Instead of comparing all pairs of tables, each location is split into its parent
+/// paths, which are looked up in the index on the trimmed location.
+pub(crate) async fn list_table_location_collisions<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseIdent,
+    connection: E,
+) -> Result<Vec<TableLocationCollision>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let collisions = sqlx::query!(
+        r#"
+        SELECT
+            t.tabular_id as table_id,
+            t.fs_protocol,
+            t.fs_location,
+            p.tabular_id as parent_table_id,
+            p.fs_protocol as parent_fs_protocol,
+            p.fs_location as parent_fs_location
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        CROSS JOIN LATERAL (
+            SELECT array_to_string(parts[1:i], '/') as prefix
+            FROM string_to_array(TRIM(TRAILING '/' FROM t.fs_location), '/') parts,
+                generate_series(1, cardinality(parts)) i
+        ) l
+        INNER JOIN tabular p ON TRIM(TRAILING '/' FROM p.fs_location) = l.prefix
+        INNER JOIN namespace pn ON p.namespace_id = pn.namespace_id
+        WHERE n.warehouse_id = $1
+            AND pn.warehouse_id = $1
+            AND t.typ = 'table'
+            AND p.typ = 'table'
+            AND t.tabular_id <> p.tabular_id
+            -- Report tables sharing the same location only once
+            AND (TRIM(TRAILING '/' FROM t.fs_location) <> l.prefix OR t.tabular_id > p.tabular_id)
+        ORDER BY t.tabular_id, p.tabular_id
+        "#,
+        *warehouse_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing table location collisions".to_string()))?
+    .into_iter()
+    .map(|row| TableLocationCollision {
+        table_id: row.table_id.into(),
+        location: join_location(&row.fs_protocol, &row.fs_location),
+        parent_table_id: row.parent_table_id.into(),
+        parent_location: join_location(&row.parent_fs_protocol, &row.parent_fs_location),
+    })
+    .collect();
+
+    Ok(collisions)
+}
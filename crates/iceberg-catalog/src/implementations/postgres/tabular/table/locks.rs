@@ -0,0 +1,103 @@
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{Result, TableIdentUuid, TableLock},
+    WarehouseIdent,
+};
+
+// Row locks are not listed in `pg_locks`. A transaction that updated or locked a row
+// stores its id in `xmax` of the row version visible to others, which identifies the
+// holding session in `pg_stat_activity` while the transaction is open.
+
+pub(crate) async fn list_table_locks<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    connection: E,
+) -> Result<Vec<TableLock>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let locks = sqlx::query!(
+        r#"
+        SELECT
+            a.pid as "pid!",
+            a.application_name,
+            a.client_addr::text as "client_address",
+            a.state,
+            a.xact_start as "transaction_started_at"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        INNER JOIN "table" tt ON tt.table_id = t.tabular_id
+        INNER JOIN pg_stat_activity a ON a.backend_xid IN (t.xmax, tt.xmax)
+        WHERE n.warehouse_id = $1
+            AND t.tabular_id = $2
+            AND a.pid <> pg_backend_pid()
+        ORDER BY a.xact_start ASC
+        "#,
+        *warehouse_id,
+        *table_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing table locks".to_string()))?
+    .into_iter()
+    .map(|row| TableLock {
+        holder_pid: row.pid,
+        application_name: row.application_name,
+        client_address: row.client_address,
+        state: row.state,
+        transaction_started_at: row.transaction_started_at,
+    })
+    .collect();
+
+    Ok(locks)
+}
+
+/// Terminate the sessions holding a lock on the table. Their transactions are
+/// rolled back, so writers fail their commit instead of persisting partial changes.
+pub(crate) async fn release_table_locks<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    connection: E,
+) -> Result<Vec<TableLock>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    // Holders are looked up and terminated in a single statement, so that a
+    // session whose transaction ended in the meantime is not terminated.
+    let released = sqlx::query!(
+        r#"
+        SELECT
+            a.pid as "pid!",
+            a.application_name,
+            a.client_addr::text as "client_address",
+            a.state,
+            a.xact_start as "transaction_started_at",
+            pg_terminate_backend(a.pid) as "terminated!"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        INNER JOIN "table" tt ON tt.table_id = t.tabular_id
+        INNER JOIN pg_stat_activity a ON a.backend_xid IN (t.xmax, tt.xmax)
+        WHERE n.warehouse_id = $1
+            AND t.tabular_id = $2
+            AND a.pid <> pg_backend_pid()
+        ORDER BY a.xact_start ASC
+        "#,
+        *warehouse_id,
+        *table_id,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error releasing table locks".to_string()))?
+    .into_iter()
+    .filter(|row| row.terminated)
+    .map(|row| TableLock {
+        holder_pid: row.pid,
+        application_name: row.application_name,
+        client_address: row.client_address,
+        state: row.state,
+        transaction_started_at: row.transaction_started_at,
+    })
+    .collect();
+
+    Ok(released)
+}
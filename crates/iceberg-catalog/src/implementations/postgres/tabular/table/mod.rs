@@ -1,6 +1,11 @@
+mod changes;
 mod commit;
 mod common;
 mod create;
+mod history;
+mod location_collisions;
+mod locks;
+mod time_travel;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -10,8 +15,10 @@ use std::{
     sync::Arc,
 };
 
+pub(crate) use changes::list_changed_tables;
 pub(crate) use commit::commit_table_transaction;
 pub(crate) use create::create_table;
+pub(crate) use history::{insert_commit_attribution, table_history};
 use http::StatusCode;
 use iceberg::{
     spec::{
@@ -21,7 +28,10 @@ use iceberg::{
     TableUpdate,
 };
 use iceberg_ext::{configs::Location, spec::TableMetadata, NamespaceIdent};
+pub(crate) use location_collisions::list_table_location_collisions;
+pub(crate) use locks::{list_table_locks, release_table_locks};
 use sqlx::types::Json;
+pub(crate) use time_travel::load_table_at;
 use uuid::Uuid;
 
 use super::get_partial_fs_locations;
@@ -37,8 +47,8 @@ use crate::{
     },
     service::{
         storage::{join_location, split_location, StorageProfile},
-        ErrorModel, GetTableMetadataResponse, LoadTableResponse, Result, TableIdent,
-        TableIdentUuid, TabularDetails,
+        ErrorModel, GetStorageConfigResponse, GetTableMetadataResponse, LoadTableResponse, Result,
+        TableIdent, TableIdentUuid, TableRename, TabularDetails,
     },
     SecretIdent, WarehouseIdent,
 };
@@ -142,7 +152,9 @@ pub(crate) async fn load_tables_old(
             t."metadata" as "metadata: Json<TableMetadata>",
             ti."metadata_location",
             w.storage_profile as "storage_profile: Json<StorageProfile>",
-            w."storage_secret_id"
+            w."storage_secret_id",
+            w.credential_refresh_before_seconds,
+            t.read_only
         FROM "table" t
         INNER JOIN tabular ti ON t.table_id = ti.tabular_id
         INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
@@ -193,6 +205,10 @@ pub(crate) async fn load_tables_old(
                     metadata_location,
                     storage_secret_ident: table.storage_secret_id.map(SecretIdent::from),
                     storage_profile: table.storage_profile.deref().clone(),
+                    credential_refresh_before: table
+                        .credential_refresh_before_seconds
+                        .and_then(chrono::Duration::try_seconds),
+                    read_only: table.read_only,
                 },
             ))
         })
@@ -202,6 +218,7 @@ pub(crate) async fn load_tables_old(
 pub(crate) async fn list_tables<'e, 'c: 'e, E>(
     warehouse_id: WarehouseIdent,
     namespace: &NamespaceIdent,
+    name_prefix: Option<&str>,
     list_flags: crate::service::ListFlags,
     transaction: E,
     pagination_query: PaginationQuery,
@@ -213,6 +230,7 @@ where
         warehouse_id,
         Some(namespace),
         None,
+        name_prefix,
         list_flags,
         transaction,
         Some(TabularType::Table),
@@ -269,6 +287,8 @@ struct TableQueryStruct {
     table_fs_protocol: String,
     storage_profile: Json<StorageProfile>,
     storage_secret_id: Option<Uuid>,
+    credential_refresh_before_seconds: Option<i64>,
+    read_only: bool,
     table_properties_keys: Option<Vec<String>>,
     table_properties_values: Option<Vec<String>>,
     default_partition_spec_id: Option<i32>,
@@ -498,11 +518,13 @@ pub(crate) async fn load_storage_profile(
     warehouse_id: WarehouseIdent,
     table: TableIdentUuid,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> Result<(Option<SecretIdent>, StorageProfile)> {
+) -> Result<GetStorageConfigResponse> {
     let secret = sqlx::query!(
         r#"
         SELECT w.storage_secret_id,
-        w.storage_profile as "storage_profile: Json<StorageProfile>"
+        w.storage_profile as "storage_profile: Json<StorageProfile>",
+        w.credential_refresh_before_seconds,
+        t.read_only
         FROM "table" t
         INNER JOIN tabular ti ON t.table_id = ti.tabular_id
         INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
@@ -518,10 +540,14 @@ pub(crate) async fn load_storage_profile(
     .await
     .map_err(|e| e.into_error_model("Error fetching storage secret".to_string()))?;
 
-    Ok((
-        secret.storage_secret_id.map(SecretIdent::from),
-        secret.storage_profile.0,
-    ))
+    Ok(GetStorageConfigResponse {
+        storage_profile: secret.storage_profile.0,
+        storage_secret_ident: secret.storage_secret_id.map(SecretIdent::from),
+        refresh_before: secret
+            .credential_refresh_before_seconds
+            .and_then(chrono::Duration::try_seconds),
+        read_only: secret.read_only,
+    })
 }
 
 #[allow(clippy::too_many_lines)]
@@ -551,6 +577,8 @@ pub(crate) async fn load_tables(
             ti."metadata_location",
             w.storage_profile as "storage_profile: Json<StorageProfile>",
             w."storage_secret_id",
+            w.credential_refresh_before_seconds,
+            t.read_only,
             ts.schema_ids,
             tcs.schema_id as "current_schema",
             tdps.partition_spec_id as "default_partition_spec_id",
@@ -689,6 +717,10 @@ pub(crate) async fn load_tables(
         let namespace_id = table.namespace_id.into();
         let storage_secret_ident = table.storage_secret_id.map(SecretIdent::from);
         let storage_profile = table.storage_profile.deref().clone();
+        let credential_refresh_before = table
+            .credential_refresh_before_seconds
+            .and_then(chrono::Duration::try_seconds);
+        let read_only = table.read_only;
 
         let Some(table_metadata) = table.into_table_metadata()? else {
             tracing::warn!(
@@ -707,6 +739,8 @@ pub(crate) async fn load_tables(
                 metadata_location,
                 storage_secret_ident,
                 storage_profile,
+                credential_refresh_before,
+                read_only,
             },
         );
     }
@@ -744,7 +778,8 @@ pub(crate) async fn get_table_metadata_by_id(
             t."metadata" as "metadata: Json<TableMetadata>",
             ti."metadata_location",
             w.storage_profile as "storage_profile: Json<StorageProfile>",
-            w."storage_secret_id"
+            w."storage_secret_id",
+            t."description"
         FROM "table" t
         INNER JOIN tabular ti ON t.table_id = ti.tabular_id
         INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
@@ -788,9 +823,79 @@ pub(crate) async fn get_table_metadata_by_id(
         metadata_location: table.metadata_location,
         storage_secret_ident: table.storage_secret_id.map(SecretIdent::from),
         storage_profile: table.storage_profile.deref().clone(),
+        description: table.description,
     }))
 }
 
+pub(crate) async fn resolve_table_uuid<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseIdent,
+    table: TableIdentUuid,
+    transaction: E,
+) -> Result<TableIdent>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let row = sqlx::query!(
+        r#"
+        SELECT ti.name as "table_name", n.namespace_name
+        FROM tabular ti
+        INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        WHERE w.warehouse_id = $1 AND ti.tabular_id = $2
+            AND ti.typ = 'table'
+            AND w.status = 'active'
+            AND ti.deleted_at IS NULL
+            AND ti.metadata_location IS NOT NULL
+        "#,
+        *warehouse_id,
+        *table,
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error resolving table id".to_string()))?
+    .ok_or_else(|| {
+        ErrorModel::not_found(
+            format!("Table with id {table} does not exist"),
+            "TableNotFound",
+            None,
+        )
+    })?;
+
+    Ok(TableIdent {
+        namespace: try_parse_namespace_ident(row.namespace_name)?,
+        name: row.table_name,
+    })
+}
+
+pub(crate) async fn get_table_metadata_location<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseIdent,
+    table: TableIdentUuid,
+    transaction: E,
+) -> Result<Option<String>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let row = sqlx::query!(
+        r#"
+        SELECT ti.metadata_location
+        FROM tabular ti
+        INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        WHERE w.warehouse_id = $1 AND ti.tabular_id = $2
+            AND ti.typ = 'table'
+            AND w.status = 'active'
+            AND ti.deleted_at IS NULL
+        "#,
+        *warehouse_id,
+        *table,
+    )
+    .fetch_optional(transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table metadata location".to_string()))?;
+
+    Ok(row.and_then(|row| row.metadata_location))
+}
+
 pub(crate) async fn get_table_metadata_by_s3_location(
     warehouse_id: WarehouseIdent,
     location: &Location,
@@ -813,7 +918,8 @@ pub(crate) async fn get_table_metadata_by_s3_location(
              t."metadata" as "metadata: Json<TableMetadata>",
              ti."metadata_location",
              w.storage_profile as "storage_profile: Json<StorageProfile>",
-             w."storage_secret_id"
+             w."storage_secret_id",
+             t."description"
          FROM "table" t
          INNER JOIN tabular ti ON t.table_id = ti.tabular_id
          INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
@@ -860,6 +966,7 @@ pub(crate) async fn get_table_metadata_by_s3_location(
         metadata_location: table.metadata_location,
         storage_secret_ident: table.storage_secret_id.map(SecretIdent::from),
         storage_profile: table.storage_profile.deref().clone(),
+        description: table.description,
     }))
 }
 
@@ -883,6 +990,46 @@ pub(crate) async fn rename_table(
     Ok(())
 }
 
+/// Rename multiple tables atomically. All tables are first renamed to temporary
+/// names, so that the unique constraint on names is not violated by tables that
+/// take over names released by other tables of the batch.
+pub(crate) async fn rename_tables(
+    warehouse_id: WarehouseIdent,
+    renames: &[TableRename],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let temporary_idents = renames
+        .iter()
+        .map(|rename| TableIdent {
+            namespace: rename.source.namespace.clone(),
+            name: format!("lakekeeper-rename-{}", rename.table_id),
+        })
+        .collect::<Vec<_>>();
+
+    for (rename, temporary) in renames.iter().zip(&temporary_idents) {
+        rename_table(
+            warehouse_id,
+            rename.table_id,
+            &rename.source,
+            temporary,
+            transaction,
+        )
+        .await?;
+    }
+    for (rename, temporary) in renames.iter().zip(&temporary_idents) {
+        rename_table(
+            warehouse_id,
+            rename.table_id,
+            temporary,
+            &rename.destination,
+            transaction,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 pub(crate) async fn drop_table(
     table_id: TableIdentUuid,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
@@ -1096,6 +1243,8 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            storage_team: None,
+            read_only: false,
         };
         let mut transaction = state.write_pool().begin().await.unwrap();
         let _create_result = create_table(create, &mut transaction).await.unwrap();
@@ -1137,6 +1286,8 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            storage_team: None,
+            read_only: false,
         };
 
         let create_result = create_table(request.clone(), &mut transaction)
@@ -1205,6 +1356,8 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            storage_team: None,
+            read_only: false,
         };
 
         let _create_result = create_table(request.clone(), &mut transaction)
@@ -1250,6 +1403,8 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            storage_team: None,
+            read_only: false,
         };
         let mut transaction = pool.begin().await.unwrap();
         let create_result = create_table(request, &mut transaction).await.unwrap();
@@ -1534,6 +1689,7 @@ pub(crate) mod tests {
         let tables = list_tables(
             warehouse_id,
             &namespace,
+            None,
             ListFlags::default(),
             &state.read_pool(),
             PaginationQuery::empty(),
@@ -1547,6 +1703,7 @@ pub(crate) mod tests {
         let tables = list_tables(
             warehouse_id,
             &table1.namespace,
+            None,
             ListFlags::default(),
             &state.read_pool(),
             PaginationQuery::empty(),
@@ -1560,6 +1717,7 @@ pub(crate) mod tests {
         let tables = list_tables(
             warehouse_id,
             &table2.namespace,
+            None,
             ListFlags::default(),
             &state.read_pool(),
             PaginationQuery::empty(),
@@ -1570,6 +1728,7 @@ pub(crate) mod tests {
         let tables = list_tables(
             warehouse_id,
             &table2.namespace,
+            None,
             ListFlags {
                 include_staged: true,
                 ..ListFlags::default()
@@ -1593,6 +1752,7 @@ pub(crate) mod tests {
         let tables = list_tables(
             warehouse_id,
             &namespace,
+            None,
             ListFlags::default(),
             &state.read_pool(),
             PaginationQuery::empty(),
@@ -1629,6 +1789,7 @@ pub(crate) mod tests {
         let tables = list_tables(
             warehouse_id,
             &namespace,
+            None,
             ListFlags {
                 include_staged: true,
                 ..ListFlags::default()
@@ -1648,6 +1809,7 @@ pub(crate) mod tests {
         let tables = list_tables(
             warehouse_id,
             &namespace,
+            None,
             ListFlags {
                 include_staged: true,
                 ..ListFlags::default()
@@ -1667,6 +1829,7 @@ pub(crate) mod tests {
         let tables = list_tables(
             warehouse_id,
             &namespace,
+            None,
             ListFlags {
                 include_staged: true,
                 ..ListFlags::default()
@@ -1851,4 +2014,58 @@ pub(crate) mod tests {
         .unwrap()
         .is_none());
     }
+
+    #[sqlx::test]
+    async fn test_list_table_location_collisions(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table1 = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let table2 = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let table3 = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let table4 = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+
+        assert!(list_table_location_collisions(warehouse_id, &pool)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Tables 1 and 2 share a location, table 3 is nested in it.
+        // Table 4 only shares a string prefix, which is not a collision.
+        let location = sqlx::query_scalar::<_, String>(
+            "SELECT fs_location FROM tabular WHERE tabular_id = $1",
+        )
+        .bind(*table1.table_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let location = location.trim_end_matches('/');
+        for (table, fs_location) in [
+            (&table2, format!("{location}/")),
+            (&table3, format!("{location}/data/nested")),
+            (&table4, format!("{location}-copy")),
+        ] {
+            sqlx::query("UPDATE tabular SET fs_location = $1 WHERE tabular_id = $2")
+                .bind(fs_location)
+                .bind(*table.table_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let collisions = list_table_location_collisions(warehouse_id, &pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.table_id, c.parent_table_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            collisions,
+            vec![
+                (table2.table_id, table1.table_id),
+                (table3.table_id, table1.table_id),
+                (table3.table_id, table2.table_id),
+            ]
+        );
+    }
 }
@@ -0,0 +1,240 @@
+use std::{collections::HashSet, str::FromStr};
+
+use iceberg::spec::{TableMetadata, MAIN_BRANCH};
+use iceberg_ext::configs::Location;
+use serde_json::Value;
+
+use super::load_tables;
+use crate::{
+    implementations::postgres::dbutils::DBErrorHandler as _,
+    service::{ErrorModel, LoadTableResponse, Result, SnapshotIdOrTimestamp, TableIdentUuid},
+    WarehouseIdent,
+};
+
+pub(crate) async fn load_table_at(
+    warehouse_id: WarehouseIdent,
+    table: TableIdentUuid,
+    at: SnapshotIdOrTimestamp,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<LoadTableResponse> {
+    let mut tables = load_tables(warehouse_id, [table], false, transaction).await?;
+    let table_response = tables.remove(&table).ok_or_else(|| {
+        ErrorModel::not_found(
+            format!("Table {table} not found"),
+            "NoSuchTableException",
+            None,
+        )
+    })?;
+
+    let snapshot_id = resolve_snapshot_id(&table_response.table_metadata, at)?;
+    let metadata_location = effective_timestamp_ms(&table_response.table_metadata, snapshot_id, at)
+        .and_then(|timestamp_ms| {
+            metadata_location_at(
+                &table_response.table_metadata,
+                table_response.metadata_location.as_ref(),
+                timestamp_ms,
+            )
+        });
+    let partition_spec_id = sqlx::query_scalar!(
+        r#"SELECT partition_spec_id FROM table_snapshot WHERE table_id = $1 AND snapshot_id = $2"#,
+        *table,
+        snapshot_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching partition spec of snapshot"))?
+    .flatten();
+
+    let table_metadata = table_metadata_at(
+        table_response.table_metadata,
+        snapshot_id,
+        partition_spec_id,
+    )?;
+
+    Ok(LoadTableResponse {
+        table_metadata,
+        metadata_location,
+        ..table_response
+    })
+}
+
+/// Time at which the state requested by `at` was current on `main`.
+/// `None` if `snapshot_id` never was the current snapshot of `main`.
+fn effective_timestamp_ms(
+    metadata: &TableMetadata,
+    snapshot_id: i64,
+    at: SnapshotIdOrTimestamp,
+) -> Option<i64> {
+    match at {
+        SnapshotIdOrTimestamp::TimestampMs(timestamp_ms) => Some(timestamp_ms),
+        SnapshotIdOrTimestamp::SnapshotId(_) => metadata
+            .history()
+            .iter()
+            .rev()
+            .find(|log| log.snapshot_id == snapshot_id)
+            .map(|log| log.timestamp_ms),
+    }
+}
+
+/// Metadata file that was current at `timestamp_ms`.
+/// `None` if that file is no longer recorded in the metadata log.
+fn metadata_location_at(
+    metadata: &TableMetadata,
+    current_location: Option<&Location>,
+    timestamp_ms: i64,
+) -> Option<Location> {
+    if metadata.last_updated_ms() <= timestamp_ms {
+        return current_location.cloned();
+    }
+    metadata
+        .metadata_log()
+        .iter()
+        .rev()
+        .find(|log| log.timestamp_ms <= timestamp_ms)
+        .and_then(|log| Location::from_str(&log.metadata_file).ok())
+}
+
+fn resolve_snapshot_id(metadata: &TableMetadata, at: SnapshotIdOrTimestamp) -> Result<i64> {
+    let snapshot_id = match at {
+        SnapshotIdOrTimestamp::SnapshotId(snapshot_id) => Some(snapshot_id),
+        // The snapshot log records which snapshot was current on `main` over time.
+        SnapshotIdOrTimestamp::TimestampMs(timestamp_ms) => metadata
+            .history()
+            .iter()
+            .rev()
+            .find(|log| log.timestamp_ms <= timestamp_ms)
+            .map(|log| log.snapshot_id),
+    };
+
+    snapshot_id
+        .filter(|id| metadata.snapshot_by_id(*id).is_some())
+        .ok_or_else(|| {
+            let requested = match at {
+                SnapshotIdOrTimestamp::SnapshotId(id) => format!("id {id}"),
+                SnapshotIdOrTimestamp::TimestampMs(ts) => format!("timestamp {ts}ms"),
+            };
+            ErrorModel::not_found(
+                format!("No snapshot found for {requested}. It may have been expired."),
+                "SnapshotNotFound",
+                None,
+            )
+            .into()
+        })
+}
+
+/// Rewrite `metadata` to the state it had when `snapshot_id` was current:
+/// `main` points to the snapshot, only the snapshot and its ancestors are kept,
+/// and the current schema and default spec are the ones of the snapshot.
+fn table_metadata_at(
+    metadata: TableMetadata,
+    snapshot_id: i64,
+    partition_spec_id: Option<i32>,
+) -> Result<TableMetadata> {
+    let snapshot = metadata.snapshot_by_id(snapshot_id).ok_or_else(|| {
+        ErrorModel::internal(
+            "Snapshot vanished while loading table",
+            "InternalSnapshotNotFound",
+            None,
+        )
+    })?;
+    let timestamp_ms = snapshot.timestamp_ms();
+    let schema_id = snapshot
+        .schema_id()
+        .unwrap_or_else(|| metadata.current_schema_id());
+    let spec_id = partition_spec_id
+        .filter(|id| metadata.partition_spec_by_id(*id).is_some())
+        .unwrap_or_else(|| metadata.default_partition_spec_id());
+
+    let mut ancestors = HashSet::from([snapshot_id]);
+    let mut parent = snapshot.parent_snapshot_id();
+    while let Some(parent_snapshot) = parent.and_then(|id| metadata.snapshot_by_id(id)) {
+        if !ancestors.insert(parent_snapshot.snapshot_id()) {
+            break;
+        }
+        parent = parent_snapshot.parent_snapshot_id();
+    }
+
+    let mut value = serde_json::to_value(&metadata).map_err(|e| {
+        ErrorModel::internal(
+            "Error serializing table metadata",
+            "InternalTableMetadataSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+    let Value::Object(fields) = &mut value else {
+        return Err(ErrorModel::internal(
+            "Table metadata is not serialized as an object",
+            "InternalTableMetadataSerializationError",
+            None,
+        )
+        .into());
+    };
+
+    // Other branches and tags may point to snapshots that did not exist yet.
+    let mut main_ref = fields
+        .get("refs")
+        .and_then(|refs| refs.get(MAIN_BRANCH))
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!({"type": "branch"}));
+    main_ref["snapshot-id"] = snapshot_id.into();
+    fields.insert(
+        "refs".to_string(),
+        Value::Object(serde_json::Map::from_iter([(
+            MAIN_BRANCH.to_string(),
+            main_ref,
+        )])),
+    );
+    fields.insert("current-snapshot-id".to_string(), snapshot_id.into());
+    fields.insert("current-schema-id".to_string(), schema_id.into());
+    fields.insert("default-spec-id".to_string(), spec_id.into());
+
+    let in_ancestors = |v: &Value| {
+        v.get("snapshot-id")
+            .and_then(Value::as_i64)
+            .is_some_and(|id| ancestors.contains(&id))
+    };
+    for key in ["snapshots", "statistics", "partition-statistics"] {
+        if let Some(Value::Array(entries)) = fields.get_mut(key) {
+            entries.retain(in_ancestors);
+        }
+    }
+    if let Some(Value::Array(entries)) = fields.get_mut("snapshot-log") {
+        entries.retain(|v| {
+            in_ancestors(v)
+                && v.get("timestamp-ms")
+                    .and_then(Value::as_i64)
+                    .is_some_and(|ts| ts <= timestamp_ms)
+        });
+    }
+
+    // Format version 1 additionally carries the current schema and spec inline.
+    let find_by_id = |key: &str, id_key: &str, id: i32| {
+        fields
+            .get(key)
+            .and_then(Value::as_array)
+            .and_then(|entries| {
+                entries
+                    .iter()
+                    .find(|v| v.get(id_key).and_then(Value::as_i64) == Some(i64::from(id)))
+            })
+            .cloned()
+    };
+    let v1_schema = find_by_id("schemas", "schema-id", schema_id);
+    let v1_spec_fields =
+        find_by_id("partition-specs", "spec-id", spec_id).and_then(|s| s.get("fields").cloned());
+    if let Some(schema) = v1_schema.filter(|_| fields.contains_key("schema")) {
+        fields.insert("schema".to_string(), schema);
+    }
+    if let Some(spec_fields) = v1_spec_fields.filter(|_| fields.contains_key("partition-spec")) {
+        fields.insert("partition-spec".to_string(), spec_fields);
+    }
+
+    serde_json::from_value(value).map_err(|e| {
+        ErrorModel::internal(
+            "Error building table metadata at snapshot",
+            "InternalTableMetadataParseError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
@@ -419,6 +419,7 @@ pub(crate) async fn set_current_view_metadata_version(
 pub(crate) async fn list_views<'e, 'c: 'e, E>(
     warehouse_id: WarehouseIdent,
     namespace: &NamespaceIdent,
+    name_prefix: Option<&str>,
     include_deleted: bool,
     transaction: E,
     paginate_query: PaginationQuery,
@@ -430,6 +431,7 @@ where
         warehouse_id,
         Some(namespace),
         None,
+        name_prefix,
         ListFlags {
             include_deleted,
             include_staged: false,
@@ -519,6 +521,7 @@ pub(crate) mod tests {
         NamespaceIdent, TableIdent,
     };
     use iceberg_ext::configs::Location;
+    use itertools::Itertools;
     use serde_json::json;
     use sqlx::PgPool;
     use uuid::Uuid;
@@ -527,11 +530,15 @@ pub(crate) mod tests {
         api::iceberg::v1::PaginationQuery,
         implementations::postgres::{
             namespace::tests::initialize_namespace,
-            tabular::{mark_tabular_as_deleted, view::load_view},
+            tabular::{
+                mark_tabular_as_deleted,
+                table::{list_tables, tests::initialize_table},
+                view::load_view,
+            },
             warehouse::test::initialize_warehouse,
             CatalogState,
         },
-        service::{TabularIdentUuid, ViewIdentUuid},
+        service::{ListFlags, TabularIdentUuid, ViewIdentUuid},
         WarehouseIdent,
     };
 
@@ -714,6 +721,7 @@ pub(crate) mod tests {
         let views = super::list_views(
             warehouse_id,
             &namespace,
+            None,
             false,
             &state.read_pool(),
             PaginationQuery::empty(),
@@ -830,6 +838,95 @@ pub(crate) mod tests {
         assert_eq!(e.error.code, 404);
     }
 
+    #[sqlx::test]
+    async fn list_views_excludes_tables(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = NamespaceIdent::from_vec(vec!["my_namespace".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let namespace_id =
+            crate::implementations::postgres::tabular::table::tests::get_namespace_id(
+                state.clone(),
+                warehouse_id,
+                &namespace,
+            )
+            .await;
+
+        let mut view_ids = Vec::new();
+        for name in ["view-1", "view-2"] {
+            let location = format!("s3://my_bucket/{name}")
+                .parse::<Location>()
+                .unwrap();
+            let request = view_request(None, &location);
+            view_ids.push(ViewIdentUuid::from(request.uuid()));
+            let mut tx = pool.begin().await.unwrap();
+            super::create_view(
+                namespace_id,
+                &format!(
+                    "s3://my_bucket/{name}/metadata/metadata-{}.gz.json",
+                    Uuid::now_v7()
+                )
+                .parse()
+                .unwrap(),
+                &mut tx,
+                name,
+                request,
+                &location,
+            )
+            .await
+            .unwrap();
+            tx.commit().await.unwrap();
+        }
+        let table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            None,
+        )
+        .await;
+
+        let views = super::list_views(
+            warehouse_id,
+            &namespace,
+            None,
+            false,
+            &state.read_pool(),
+            PaginationQuery::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(views.len(), 2);
+        let listed = views
+            .into_iter()
+            .map(|(id, ident)| (id, ident.name))
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            listed,
+            vec![
+                (view_ids[0], "view-1".to_string()),
+                (view_ids[1], "view-2".to_string())
+            ]
+        );
+
+        let tables = list_tables(
+            warehouse_id,
+            &namespace,
+            None,
+            ListFlags::default(),
+            &state.read_pool(),
+            PaginationQuery::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(
+            tables.into_iter().next().unwrap(),
+            (table.table_id, table.table_ident)
+        );
+    }
+
     async fn prepare_view(
         pool: PgPool,
     ) -> (
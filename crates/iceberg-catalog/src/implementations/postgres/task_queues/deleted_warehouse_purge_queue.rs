@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use iceberg_ext::{catalog::rest::ErrorModel, configs::Location};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use super::{cancel_pending_tasks, TaskFilter};
+use crate::{
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        task_queues::{pick_task, queue_task, record_failure, record_success},
+    },
+    service::{
+        storage::StorageProfile,
+        task_queue::{
+            deleted_warehouse_purge_queue::{
+                DeletedWarehousePurgeInput, DeletedWarehousePurgeTask,
+            },
+            TaskQueue, TaskQueueConfig,
+        },
+    },
+};
+
+super::impl_pg_task_queue!(DeletedWarehousePurgeQueue);
+
+#[async_trait]
+impl TaskQueue for DeletedWarehousePurgeQueue {
+    type Task = DeletedWarehousePurgeTask;
+    type Input = DeletedWarehousePurgeInput;
+
+    fn config(&self) -> &TaskQueueConfig {
+        &self.pg_queue.config
+    }
+
+    fn queue_name(&self) -> &'static str {
+        "deleted_warehouse_purges"
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn pick_new_task(&self) -> crate::api::Result<Option<Self::Task>> {
+        let task = pick_task(
+            &self.pg_queue.read_write.write_pool,
+            self.queue_name(),
+            &self.pg_queue.max_age,
+        )
+        .await?;
+
+        let Some(task) = task else {
+            tracing::debug!("No task found in {}", self.queue_name());
+            return Ok(None);
+        };
+
+        let purge = sqlx::query!(
+            r#"
+            SELECT
+                warehouse_id,
+                storage_profile as "storage_profile: Json<StorageProfile>",
+                storage_secret_id,
+                locations
+            FROM deleted_warehouse_purges
+            WHERE task_id = $1
+            "#,
+            task.task_id
+        )
+        .fetch_one(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "error selecting deleted warehouse purge");
+            e.into_error_model("failed to read task after picking one up")
+        })?;
+
+        let locations = purge
+            .locations
+            .iter()
+            .map(|location| {
+                location.parse::<Location>().map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Failed to parse location '{location}' of deleted warehouse"),
+                        "ParseError",
+                        Some(Box::new(e)),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(DeletedWarehousePurgeTask {
+            warehouse_ident: purge.warehouse_id.into(),
+            storage_profile: purge.storage_profile.0,
+            storage_secret_id: purge.storage_secret_id.map(Into::into),
+            locations,
+            task,
+        }))
+    }
+
+    async fn record_success(&self, id: Uuid) -> crate::api::Result<()> {
+        record_success(id, &self.pg_queue.read_write.write_pool).await
+    }
+
+    async fn record_failure(&self, id: Uuid, error_details: &str) -> crate::api::Result<()> {
+        record_failure(
+            &self.pg_queue.read_write.write_pool,
+            id,
+            self.config().max_retries,
+            error_details,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, input), fields(warehouse_id = %input.warehouse_ident))]
+    async fn enqueue(&self, input: DeletedWarehousePurgeInput) -> crate::api::Result<()> {
+        let mut transaction = self
+            .pg_queue
+            .read_write
+            .write_pool
+            .begin()
+            .await
+            .map_err(|e| {
+                e.into_error_model("failed begin transaction to deleted warehouse purge task")
+            })?;
+
+        tracing::debug!(
+            "Queuing purge of {} locations of deleted warehouse: '{}'",
+            input.locations.len(),
+            input.warehouse_ident
+        );
+
+        let Some(task_id) = queue_task(
+            &mut transaction,
+            self.queue_name(),
+            None,
+            *input.warehouse_ident,
+            input.warehouse_ident,
+            None,
+        )
+        .await?
+        else {
+            tracing::debug!("Task already exists");
+            transaction.commit().await.map_err(|e| {
+                tracing::error!(?e, "failed to commit");
+                e.into_error_model("failed commiting transaction")
+            })?;
+            return Ok(());
+        };
+
+        let locations = input
+            .locations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        sqlx::query!(
+            r#"INSERT INTO deleted_warehouse_purges(task_id, warehouse_id, storage_profile, storage_secret_id, locations)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (task_id) DO NOTHING"#,
+            task_id,
+            *input.warehouse_ident,
+            Json(&input.storage_profile) as _,
+            input.storage_secret_id.map(|id| id.into_uuid()),
+            &locations,
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to insert into deleted_warehouse_purges");
+            e.into_error_model("failed to insert into deleted warehouse purges")
+        })?;
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "failed to commit");
+            e.into_error_model("failed to commit deleted warehouse purge task")
+        })?;
+
+        Ok(())
+    }
+
+    async fn cancel_pending_tasks(&self, filter: TaskFilter) -> crate::api::Result<()> {
+        cancel_pending_tasks(&self.pg_queue, filter, self.queue_name()).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sqlx::PgPool;
+
+    use super::super::test::setup;
+    use crate::service::{
+        storage::{StorageProfile, TestProfile},
+        task_queue::{
+            deleted_warehouse_purge_queue::DeletedWarehousePurgeInput, TaskQueue, TaskQueueConfig,
+        },
+    };
+
+    #[sqlx::test]
+    async fn test_queue_deleted_warehouse_purge_task(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::DeletedWarehousePurgeQueue { pg_queue };
+        let input = DeletedWarehousePurgeInput {
+            warehouse_ident: uuid::Uuid::new_v4().into(),
+            storage_profile: StorageProfile::Test(TestProfile::default()),
+            storage_secret_id: None,
+            locations: vec![
+                "s3://bucket/tab-1".parse().unwrap(),
+                "s3://bucket/tab-2".parse().unwrap(),
+            ],
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        queue.enqueue(input.clone()).await.unwrap();
+
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("There should be a task");
+
+        assert_eq!(task.warehouse_ident, input.warehouse_ident);
+        assert_eq!(task.storage_profile, input.storage_profile);
+        assert_eq!(task.locations, input.locations);
+
+        let task = queue.pick_new_task().await.unwrap();
+        assert!(
+            task.is_none(),
+            "There should only be one task, idempotency didn't work."
+        );
+    }
+}
@@ -1,10 +1,17 @@
+mod deleted_warehouse_purge_queue;
+mod snapshot_cleanup_queue;
+mod storage_secret_cleanup_queue;
 mod tabular_expiration_queue;
 mod tabular_purge_queue;
 
 use chrono::{DateTime, Utc};
+pub use deleted_warehouse_purge_queue::DeletedWarehousePurgeQueue;
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
+pub use snapshot_cleanup_queue::SnapshotCleanupQueue;
 use sqlx::{PgConnection, PgPool};
+pub use storage_secret_cleanup_queue::StorageSecretCleanupQueue;
 pub use tabular_expiration_queue::TabularExpirationQueue;
+pub(crate) use tabular_purge_queue::list_pending_purges;
 pub use tabular_purge_queue::TabularPurgeQueue;
 use uuid::Uuid;
 
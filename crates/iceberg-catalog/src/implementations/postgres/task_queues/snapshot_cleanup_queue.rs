@@ -0,0 +1,200 @@
+use async_trait::async_trait;
+use iceberg::spec::Snapshot;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use super::{cancel_pending_tasks, TaskFilter};
+use crate::{
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        task_queues::{pick_task, queue_task, record_failure, record_success},
+    },
+    service::task_queue::{
+        snapshot_cleanup_queue::{SnapshotCleanupInput, SnapshotCleanupTask},
+        TaskQueue, TaskQueueConfig,
+    },
+};
+
+super::impl_pg_task_queue!(SnapshotCleanupQueue);
+
+#[async_trait]
+impl TaskQueue for SnapshotCleanupQueue {
+    type Task = SnapshotCleanupTask;
+    type Input = SnapshotCleanupInput;
+
+    fn config(&self) -> &TaskQueueConfig {
+        &self.pg_queue.config
+    }
+
+    fn queue_name(&self) -> &'static str {
+        "snapshot_cleanups"
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn pick_new_task(&self) -> crate::api::Result<Option<Self::Task>> {
+        let task = pick_task(
+            &self.pg_queue.read_write.write_pool,
+            self.queue_name(),
+            &self.pg_queue.max_age,
+        )
+        .await?;
+
+        let Some(task) = task else {
+            tracing::debug!("No task found in {}", self.queue_name());
+            return Ok(None);
+        };
+
+        let cleanup = sqlx::query!(
+            r#"
+            SELECT table_id, warehouse_id, snapshots as "snapshots: Json<Vec<Snapshot>>"
+            FROM snapshot_cleanups
+            WHERE task_id = $1
+            "#,
+            task.task_id
+        )
+        .fetch_one(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "error selecting snapshot cleanup");
+            e.into_error_model("failed to read task after picking one up")
+        })?;
+
+        Ok(Some(SnapshotCleanupTask {
+            table_id: cleanup.table_id.into(),
+            warehouse_ident: cleanup.warehouse_id.into(),
+            snapshots: cleanup.snapshots.0,
+            task,
+        }))
+    }
+
+    async fn record_success(&self, id: Uuid) -> crate::api::Result<()> {
+        record_success(id, &self.pg_queue.read_write.write_pool).await
+    }
+
+    async fn record_failure(&self, id: Uuid, error_details: &str) -> crate::api::Result<()> {
+        record_failure(
+            &self.pg_queue.read_write.write_pool,
+            id,
+            self.config().max_retries,
+            error_details,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, input), fields(table_id = %input.table_id))]
+    async fn enqueue(&self, input: SnapshotCleanupInput) -> crate::api::Result<()> {
+        let mut transaction = self
+            .pg_queue
+            .read_write
+            .write_pool
+            .begin()
+            .await
+            .map_err(|e| e.into_error_model("failed begin transaction to snapshot cleanup task"))?;
+
+        tracing::debug!(
+            "Queuing cleanup of {} expired snapshots of table '{}' under warehouse: '{}'",
+            input.snapshots.len(),
+            input.table_id,
+            input.warehouse_ident
+        );
+
+        let Some(task_id) = queue_task(
+            &mut transaction,
+            self.queue_name(),
+            None,
+            input.idempotency_key(),
+            input.warehouse_ident,
+            input.cleanup_after,
+        )
+        .await?
+        else {
+            tracing::debug!("Task already exists");
+            transaction.commit().await.map_err(|e| {
+                tracing::error!(?e, "failed to commit");
+                e.into_error_model("failed commiting transaction")
+            })?;
+            return Ok(());
+        };
+
+        sqlx::query!(
+            r#"INSERT INTO snapshot_cleanups(task_id, table_id, warehouse_id, snapshots)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (task_id) DO NOTHING"#,
+            task_id,
+            *input.table_id,
+            *input.warehouse_ident,
+            Json(&input.snapshots) as _,
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to insert into snapshot_cleanups");
+            e.into_error_model("failed to insert into snapshot cleanups")
+        })?;
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "failed to commit");
+            e.into_error_model("failed to commit snapshot cleanup task")
+        })?;
+
+        Ok(())
+    }
+
+    async fn cancel_pending_tasks(&self, filter: TaskFilter) -> crate::api::Result<()> {
+        cancel_pending_tasks(&self.pg_queue, filter, self.queue_name()).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use iceberg::spec::{Operation, Summary};
+    use sqlx::PgPool;
+
+    use super::super::test::setup;
+    use crate::service::task_queue::{
+        snapshot_cleanup_queue::SnapshotCleanupInput, TaskQueue, TaskQueueConfig,
+    };
+
+    #[sqlx::test]
+    async fn test_queue_snapshot_cleanup_task(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::SnapshotCleanupQueue { pg_queue };
+        let snapshot = iceberg::spec::Snapshot::builder()
+            .with_snapshot_id(1)
+            .with_parent_snapshot_id(None)
+            .with_timestamp_ms(0)
+            .with_sequence_number(1)
+            .with_schema_id(0)
+            .with_manifest_list("s3://bucket/table/metadata/snap-1.avro")
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: std::collections::HashMap::new(),
+            })
+            .build();
+        let input = SnapshotCleanupInput {
+            table_id: uuid::Uuid::new_v4().into(),
+            warehouse_ident: uuid::Uuid::new_v4().into(),
+            snapshots: vec![snapshot],
+            cleanup_after: None,
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        queue.enqueue(input.clone()).await.unwrap();
+
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("There should be a task");
+
+        assert_eq!(task.warehouse_ident, input.warehouse_ident);
+        assert_eq!(task.table_id, input.table_id);
+        assert_eq!(task.snapshots, input.snapshots);
+
+        let task = queue.pick_new_task().await.unwrap();
+        assert!(
+            task.is_none(),
+            "There should only be one task, idempotency didn't work."
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{cancel_pending_tasks, TaskFilter};
+use crate::{
+    implementations::postgres::{
+        dbutils::DBErrorHandler,
+        task_queues::{pick_task, queue_task, record_failure, record_success},
+    },
+    service::task_queue::{
+        storage_secret_cleanup_queue::{StorageSecretCleanupInput, StorageSecretCleanupTask},
+        TaskQueue, TaskQueueConfig,
+    },
+};
+
+super::impl_pg_task_queue!(StorageSecretCleanupQueue);
+
+#[async_trait]
+impl TaskQueue for StorageSecretCleanupQueue {
+    type Task = StorageSecretCleanupTask;
+    type Input = StorageSecretCleanupInput;
+
+    fn config(&self) -> &TaskQueueConfig {
+        &self.pg_queue.config
+    }
+
+    fn queue_name(&self) -> &'static str {
+        "storage_secret_cleanups"
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn pick_new_task(&self) -> crate::api::Result<Option<Self::Task>> {
+        let task = pick_task(
+            &self.pg_queue.read_write.write_pool,
+            self.queue_name(),
+            &self.pg_queue.max_age,
+        )
+        .await?;
+
+        let Some(task) = task else {
+            tracing::debug!("No task found in {}", self.queue_name());
+            return Ok(None);
+        };
+
+        let cleanup = sqlx::query!(
+            r#"
+            SELECT warehouse_id, secret_id
+            FROM storage_secret_cleanups
+            WHERE task_id = $1
+            "#,
+            task.task_id
+        )
+        .fetch_one(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "error selecting storage secret cleanup");
+            e.into_error_model("failed to read task after picking one up")
+        })?;
+
+        Ok(Some(StorageSecretCleanupTask {
+            warehouse_ident: cleanup.warehouse_id.into(),
+            secret_id: cleanup.secret_id.into(),
+            task,
+        }))
+    }
+
+    async fn record_success(&self, id: Uuid) -> crate::api::Result<()> {
+        record_success(id, &self.pg_queue.read_write.write_pool).await
+    }
+
+    async fn record_failure(&self, id: Uuid, error_details: &str) -> crate::api::Result<()> {
+        record_failure(
+            &self.pg_queue.read_write.write_pool,
+            id,
+            self.config().max_retries,
+            error_details,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self, input), fields(secret_id = %input.secret_id))]
+    async fn enqueue(&self, input: StorageSecretCleanupInput) -> crate::api::Result<()> {
+        let mut transaction = self
+            .pg_queue
+            .read_write
+            .write_pool
+            .begin()
+            .await
+            .map_err(|e| {
+                e.into_error_model("failed begin transaction to storage secret cleanup task")
+            })?;
+
+        tracing::debug!(
+            "Queuing release of previous storage secret '{}' of warehouse: '{}'",
+            input.secret_id,
+            input.warehouse_ident
+        );
+
+        let Some(task_id) = queue_task(
+            &mut transaction,
+            self.queue_name(),
+            None,
+            input.secret_id.into_uuid(),
+            input.warehouse_ident,
+            input.cleanup_after,
+        )
+        .await?
+        else {
+            tracing::debug!("Task already exists");
+            transaction.commit().await.map_err(|e| {
+                tracing::error!(?e, "failed to commit");
+                e.into_error_model("failed commiting transaction")
+            })?;
+            return Ok(());
+        };
+
+        sqlx::query!(
+            r#"INSERT INTO storage_secret_cleanups(task_id, warehouse_id, secret_id)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (task_id) DO NOTHING"#,
+            task_id,
+            *input.warehouse_ident,
+            input.secret_id.into_uuid(),
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to insert into storage_secret_cleanups");
+            e.into_error_model("failed to insert into storage secret cleanups")
+        })?;
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "failed to commit");
+            e.into_error_model("failed to commit storage secret cleanup task")
+        })?;
+
+        Ok(())
+    }
+
+    async fn cancel_pending_tasks(&self, filter: TaskFilter) -> crate::api::Result<()> {
+        cancel_pending_tasks(&self.pg_queue, filter, self.queue_name()).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sqlx::PgPool;
+
+    use super::super::test::setup;
+    use crate::service::task_queue::{
+        storage_secret_cleanup_queue::StorageSecretCleanupInput, TaskQueue, TaskQueueConfig,
+    };
+
+    #[sqlx::test]
+    async fn test_queue_storage_secret_cleanup_task(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::StorageSecretCleanupQueue { pg_queue };
+        let input = StorageSecretCleanupInput {
+            warehouse_ident: uuid::Uuid::new_v4().into(),
+            secret_id: uuid::Uuid::new_v4().into(),
+            cleanup_after: None,
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        queue.enqueue(input.clone()).await.unwrap();
+
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("There should be a task");
+
+        assert_eq!(task.warehouse_ident, input.warehouse_ident);
+        assert_eq!(task.secret_id, input.secret_id);
+
+        let task = queue.pick_new_task().await.unwrap();
+        assert!(
+            task.is_none(),
+            "There should only be one task, idempotency didn't work."
+        );
+    }
+}
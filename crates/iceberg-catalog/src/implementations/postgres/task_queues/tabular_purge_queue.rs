@@ -10,9 +10,10 @@ use crate::{
         task_queues::{pick_task, queue_task, record_failure, record_success},
     },
     service::task_queue::{
-        tabular_purge_queue::{TabularPurgeInput, TabularPurgeTask},
-        TaskQueue, TaskQueueConfig,
+        tabular_purge_queue::{PendingPurge, TabularPurgeInput, TabularPurgeTask},
+        TaskQueue, TaskQueueConfig, TaskStatus,
     },
+    WarehouseIdent,
 };
 
 super::impl_pg_task_queue!(TabularPurgeQueue);
@@ -91,6 +92,7 @@ impl TaskQueue for TabularPurgeQueue {
             warehouse_ident,
             tabular_type,
             parent_id,
+            purge_after,
         }: TabularPurgeInput,
     ) -> crate::api::Result<()> {
         let mut transaction = self
@@ -114,7 +116,7 @@ impl TaskQueue for TabularPurgeQueue {
             parent_id,
             idempotency_key,
             warehouse_ident,
-            None,
+            purge_after,
         )
         .await?
         else {
@@ -167,6 +169,51 @@ impl TaskQueue for TabularPurgeQueue {
     }
 }
 
+pub(crate) async fn list_pending_purges(
+    warehouse_id: WarehouseIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> crate::api::Result<Vec<PendingPurge>> {
+    let purges = sqlx::query!(
+        r#"
+        SELECT
+            t.task_id,
+            p.tabular_id,
+            p.typ as "tabular_type: DbTabularType",
+            p.tabular_location,
+            t.status as "status: TaskStatus",
+            t.attempt,
+            t.suspend_until,
+            t.last_error_details
+        FROM tabular_purges p
+        JOIN task t ON t.task_id = p.task_id
+        WHERE p.warehouse_id = $1
+        AND t.status IN ('pending', 'running', 'failed')
+        ORDER BY t.task_id
+        "#,
+        *warehouse_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::error!(?e, "error selecting pending purges");
+        e.into_error_model("failed to list pending purges")
+    })?;
+
+    Ok(purges
+        .into_iter()
+        .map(|purge| PendingPurge {
+            task_id: purge.task_id,
+            tabular_id: purge.tabular_id,
+            tabular_type: purge.tabular_type.into(),
+            tabular_location: purge.tabular_location,
+            status: purge.status,
+            attempt: purge.attempt,
+            purge_after: purge.suspend_until,
+            last_error: purge.last_error_details,
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod test {
     use sqlx::PgPool;
@@ -187,6 +234,7 @@ mod test {
             tabular_type: crate::api::management::v1::TabularType::Table,
             parent_id: None,
             tabular_location: String::new(),
+            purge_after: None,
         };
         queue.enqueue(input.clone()).await.unwrap();
         queue.enqueue(input.clone()).await.unwrap();
@@ -1,5 +1,9 @@
-use std::{collections::HashSet, ops::Deref};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+};
 
+use iceberg::NamespaceIdent;
 use sqlx::{types::Json, Error, PgPool};
 
 use super::{dbutils::DBErrorHandler as _, CatalogState};
@@ -11,9 +15,25 @@ use crate::{
         },
         CatalogConfig, ErrorModel, Result,
     },
-    implementations::postgres::pagination::{PaginateToken, V1PaginateToken},
+    implementations::postgres::{
+        pagination::{PaginateToken, V1PaginateToken},
+        tabular::TabularType,
+    },
     request_metadata::RequestMetadata,
-    service::{storage::StorageProfile, GetProjectResponse, GetWarehouseResponse, WarehouseStatus},
+    service::{
+        authn::AuthenticationMethod,
+        authz::implementations::openfga::ProjectScopedObject,
+        commit_throttle::CommitRateLimit,
+        feature_flags::WarehouseFeatureFlags,
+        namespace_property_schema::NamespacePropertySchema,
+        schema_evolution::SchemaEvolutionPolicy,
+        secret_encryption::WrappedDataKey,
+        snapshot_limit::SnapshotLimit,
+        storage::{join_location, StorageProfile},
+        DeletedTabular, DeletedWarehouseContents, GetProjectResponse, GetWarehouseResponse,
+        NamespaceIdentUuid, RotatedStorageSecrets, TabularIdentUuid, WarehouseSecretReference,
+        WarehouseSecretUsage, WarehouseStatus,
+    },
     ProjectId, SecretIdent, WarehouseIdent,
 };
 
@@ -40,6 +60,29 @@ pub(super) async fn get_warehouse_by_name(
     Ok(warehouse_id.map(Into::into))
 }
 
+pub(super) async fn get_warehouse_allowed_authentication_methods(
+    warehouse_id: WarehouseIdent,
+    catalog_state: CatalogState,
+) -> Result<Option<Vec<AuthenticationMethod>>> {
+    let allowed_authentication_methods = sqlx::query_scalar!(
+        r#"
+            SELECT
+                allowed_authentication_methods as "allowed_authentication_methods: Json<Vec<AuthenticationMethod>>"
+            FROM warehouse
+            WHERE warehouse_id = $1
+            AND status = 'active'
+            "#,
+        *warehouse_id
+    )
+    .fetch_optional(&catalog_state.read_pool())
+    .await
+    .map_err(map_select_warehouse_err)?;
+
+    Ok(allowed_authentication_methods
+        .flatten()
+        .map(|methods| methods.0))
+}
+
 pub(super) async fn set_warehouse_deletion_profile<
     'c,
     'e: 'c,
@@ -99,6 +142,21 @@ pub(super) async fn get_config_for_warehouse(
     Ok(storage_profile.map(|p| p.generate_catalog_config(warehouse_id, request_metadata)))
 }
 
+/// Unique constraint on the name of a warehouse within its project. The name
+/// uses a case-insensitive collation, so names differing only in case conflict.
+const UNIQUE_WAREHOUSE_NAME_IN_PROJECT: &str = "unique_warehouse_name_in_project";
+
+fn warehouse_name_conflict(e: sqlx::Error) -> ErrorModel {
+    ErrorModel::conflict(
+        "Warehouse with this name already exists in the project.",
+        "WarehouseNameAlreadyExists",
+        Some(Box::new(e)),
+    )
+}
+
+/// Concurrent creations of warehouses with the same name are serialized by the
+/// unique constraint on the name: The first succeeds, all others fail with
+/// `409 Conflict` once the first commits.
 pub(crate) async fn create_warehouse(
     warehouse_name: String,
     project_id: ProjectId,
@@ -148,12 +206,7 @@ pub(crate) async fn create_warehouse(
     .await
     .map_err(|e| match &e {
         sqlx::Error::Database(db_err) => match db_err.constraint() {
-            // ToDo: Get constraint name from const
-            Some("unique_warehouse_name_in_project") => ErrorModel::conflict(
-                "Warehouse with this name already exists in the project.",
-                "WarehouseNameAlreadyExists",
-                Some(Box::new(e)),
-            ),
+            Some(UNIQUE_WAREHOUSE_NAME_IN_PROJECT) => warehouse_name_conflict(e),
             Some("warehouse_project_id_fk") => {
                 ErrorModel::not_found("Project not found", "ProjectNotFound", Some(Box::new(e)))
             }
@@ -284,6 +337,68 @@ pub(crate) async fn delete_project(
     Ok(())
 }
 
+/// Project of `object`, `None` if the object does not exist.
+pub(crate) async fn get_project_of_object(
+    object: ProjectScopedObject,
+    pool: &PgPool,
+) -> Result<Option<ProjectId>> {
+    let project_id = match object {
+        ProjectScopedObject::Project(project_id) => return Ok(Some(project_id)),
+        ProjectScopedObject::Role(role_id) => {
+            sqlx::query_scalar!(
+                r#"SELECT project_id FROM role WHERE id = $1"#,
+                uuid::Uuid::from(role_id)
+            )
+            .fetch_optional(pool)
+            .await
+        }
+        ProjectScopedObject::Warehouse(warehouse_id) => {
+            sqlx::query_scalar!(
+                r#"SELECT project_id FROM warehouse WHERE warehouse_id = $1"#,
+                *warehouse_id
+            )
+            .fetch_optional(pool)
+            .await
+        }
+        ProjectScopedObject::Namespace(namespace_id) => {
+            sqlx::query_scalar!(
+                r#"
+                SELECT w.project_id
+                FROM namespace n
+                INNER JOIN warehouse w ON w.warehouse_id = n.warehouse_id
+                WHERE n.namespace_id = $1
+                "#,
+                *namespace_id
+            )
+            .fetch_optional(pool)
+            .await
+        }
+        ProjectScopedObject::Table(table_id) => get_project_of_tabular(*table_id, pool).await,
+        ProjectScopedObject::View(view_id) => get_project_of_tabular(*view_id, pool).await,
+    }
+    .map_err(|e| e.into_error_model("Error fetching project of object"))?;
+
+    Ok(project_id.map(ProjectId::from))
+}
+
+async fn get_project_of_tabular(
+    tabular_id: uuid::Uuid,
+    pool: &PgPool,
+) -> std::result::Result<Option<uuid::Uuid>, Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT w.project_id
+        FROM tabular t
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        INNER JOIN warehouse w ON w.warehouse_id = n.warehouse_id
+        WHERE t.tabular_id = $1
+        "#,
+        tabular_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
 pub(crate) async fn list_warehouses<
     'e,
     'c: 'e,
@@ -302,6 +417,25 @@ pub(crate) async fn list_warehouses<
         status: WarehouseStatus,
         tabular_delete_mode: DbTabularDeleteProfile,
         tabular_expiration_seconds: Option<i64>,
+        feature_flags: Json<HashMap<String, bool>>,
+        allowed_schemes: Option<Vec<String>>,
+        purge_grace_period_seconds: Option<i64>,
+        manifest_merge_threshold: Option<i32>,
+        max_tables_per_namespace: Option<i32>,
+        max_tables_per_namespace_includes_views: bool,
+        storage_prefix_overrides: Vec<String>,
+        schema_evolution_policy: Json<SchemaEvolutionPolicy>,
+        allowed_file_formats: Option<Vec<String>>,
+        namespace_property_schema: Json<NamespacePropertySchema>,
+        annotations: Json<HashMap<String, String>>,
+        default_view_properties: Json<HashMap<String, String>>,
+        commit_rate_limit: Option<Json<CommitRateLimit>>,
+        credential_refresh_before_seconds: Option<i64>,
+        allowed_authentication_methods: Option<Json<Vec<AuthenticationMethod>>>,
+        empty_namespace_cleanup_after_seconds: Option<i64>,
+        import_prefixes: Vec<String>,
+        default_namespace: Option<Vec<String>>,
+        snapshot_limit: Option<Json<SnapshotLimit>>,
     }
 
     let include_status = include_status.unwrap_or_else(|| vec![WarehouseStatus::Active]);
@@ -315,7 +449,26 @@ pub(crate) async fn list_warehouses<
                 storage_secret_id,
                 status AS "status: WarehouseStatus",
                 tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
-                tabular_expiration_seconds
+                tabular_expiration_seconds,
+                feature_flags as "feature_flags: Json<HashMap<String, bool>>",
+                allowed_schemes,
+                purge_grace_period_seconds,
+                manifest_merge_threshold,
+                max_tables_per_namespace,
+                max_tables_per_namespace_includes_views,
+                storage_prefix_overrides,
+                schema_evolution_policy as "schema_evolution_policy: Json<SchemaEvolutionPolicy>",
+                allowed_file_formats,
+                namespace_property_schema as "namespace_property_schema: Json<NamespacePropertySchema>",
+                annotations as "annotations: Json<HashMap<String, String>>",
+                default_view_properties as "default_view_properties: Json<HashMap<String, String>>",
+                commit_rate_limit as "commit_rate_limit: Json<CommitRateLimit>",
+                credential_refresh_before_seconds,
+                allowed_authentication_methods as "allowed_authentication_methods: Json<Vec<AuthenticationMethod>>",
+                empty_namespace_cleanup_after_seconds,
+                import_prefixes,
+                default_namespace,
+                snapshot_limit as "snapshot_limit: Json<SnapshotLimit>"
             FROM warehouse
             WHERE project_id = $1
             AND status = ANY($2)
@@ -353,6 +506,37 @@ pub(crate) async fn list_warehouses<
                 storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
                 status: warehouse.status,
                 tabular_delete_profile,
+                feature_flags: WarehouseFeatureFlags::from_raw(
+                    warehouse.warehouse_id.into(),
+                    warehouse.feature_flags.0,
+                ),
+                allowed_schemes: warehouse.allowed_schemes,
+                purge_grace_period: warehouse
+                    .purge_grace_period_seconds
+                    .and_then(chrono::Duration::try_seconds),
+                manifest_merge_threshold: warehouse.manifest_merge_threshold,
+                max_tables_per_namespace: warehouse.max_tables_per_namespace,
+                max_tables_per_namespace_includes_views: warehouse
+                    .max_tables_per_namespace_includes_views,
+                storage_prefix_overrides: warehouse.storage_prefix_overrides,
+                schema_evolution_policy: warehouse.schema_evolution_policy.0,
+                allowed_file_formats: warehouse.allowed_file_formats,
+                namespace_property_schema: warehouse.namespace_property_schema.0,
+                annotations: warehouse.annotations.0,
+                default_view_properties: warehouse.default_view_properties.0,
+                commit_rate_limit: warehouse.commit_rate_limit.map(|limit| limit.0),
+                credential_refresh_before: warehouse
+                    .credential_refresh_before_seconds
+                    .and_then(chrono::Duration::try_seconds),
+                allowed_authentication_methods: warehouse
+                    .allowed_authentication_methods
+                    .map(|methods| methods.0),
+                empty_namespace_cleanup_after: warehouse
+                    .empty_namespace_cleanup_after_seconds
+                    .and_then(chrono::Duration::try_seconds),
+                import_prefixes: warehouse.import_prefixes,
+                default_namespace: parse_default_namespace(warehouse.default_namespace)?,
+                snapshot_limit: warehouse.snapshot_limit.map(|limit| limit.0),
             })
         })
         .collect::<Result<Vec<_>>>()
@@ -371,7 +555,26 @@ pub(crate) async fn get_warehouse(
             storage_secret_id,
             status AS "status: WarehouseStatus",
             tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
-            tabular_expiration_seconds
+            tabular_expiration_seconds,
+            feature_flags as "feature_flags: Json<HashMap<String, bool>>",
+            allowed_schemes,
+            purge_grace_period_seconds,
+            manifest_merge_threshold,
+            max_tables_per_namespace,
+            max_tables_per_namespace_includes_views,
+            storage_prefix_overrides,
+            schema_evolution_policy as "schema_evolution_policy: Json<SchemaEvolutionPolicy>",
+            allowed_file_formats,
+            namespace_property_schema as "namespace_property_schema: Json<NamespacePropertySchema>",
+            annotations as "annotations: Json<HashMap<String, String>>",
+            default_view_properties as "default_view_properties: Json<HashMap<String, String>>",
+            commit_rate_limit as "commit_rate_limit: Json<CommitRateLimit>",
+            credential_refresh_before_seconds,
+            allowed_authentication_methods as "allowed_authentication_methods: Json<Vec<AuthenticationMethod>>",
+            empty_namespace_cleanup_after_seconds,
+            import_prefixes,
+            default_namespace,
+            snapshot_limit as "snapshot_limit: Json<SnapshotLimit>"
         FROM warehouse
         WHERE warehouse_id = $1
         "#,
@@ -405,12 +608,57 @@ pub(crate) async fn get_warehouse(
             storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
             status: warehouse.status,
             tabular_delete_profile,
+            feature_flags: WarehouseFeatureFlags::from_raw(warehouse_id, warehouse.feature_flags.0),
+            allowed_schemes: warehouse.allowed_schemes,
+            purge_grace_period: warehouse
+                .purge_grace_period_seconds
+                .and_then(chrono::Duration::try_seconds),
+            manifest_merge_threshold: warehouse.manifest_merge_threshold,
+            max_tables_per_namespace: warehouse.max_tables_per_namespace,
+            max_tables_per_namespace_includes_views: warehouse
+                .max_tables_per_namespace_includes_views,
+            storage_prefix_overrides: warehouse.storage_prefix_overrides,
+            schema_evolution_policy: warehouse.schema_evolution_policy.0,
+            allowed_file_formats: warehouse.allowed_file_formats,
+            namespace_property_schema: warehouse.namespace_property_schema.0,
+            annotations: warehouse.annotations.0,
+            default_view_properties: warehouse.default_view_properties.0,
+            commit_rate_limit: warehouse.commit_rate_limit.map(|limit| limit.0),
+            credential_refresh_before: warehouse
+                .credential_refresh_before_seconds
+                .and_then(chrono::Duration::try_seconds),
+            allowed_authentication_methods: warehouse
+                .allowed_authentication_methods
+                .map(|methods| methods.0),
+            empty_namespace_cleanup_after: warehouse
+                .empty_namespace_cleanup_after_seconds
+                .and_then(chrono::Duration::try_seconds),
+            import_prefixes: warehouse.import_prefixes,
+            default_namespace: parse_default_namespace(warehouse.default_namespace)?,
+            snapshot_limit: warehouse.snapshot_limit.map(|limit| limit.0),
         }))
     } else {
         Ok(None)
     }
 }
 
+fn parse_default_namespace(
+    default_namespace: Option<Vec<String>>,
+) -> Result<Option<NamespaceIdent>> {
+    default_namespace
+        .map(|namespace| {
+            NamespaceIdent::from_vec(namespace).map_err(|e| {
+                ErrorModel::internal(
+                    "Invalid default namespace of warehouse",
+                    "InvalidDefaultNamespace",
+                    Some(Box::new(e)),
+                )
+                .into()
+            })
+        })
+        .transpose()
+}
+
 pub(crate) async fn list_projects<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
     project_ids: Option<HashSet<ProjectId>>,
     connection: E,
@@ -440,8 +688,16 @@ pub(crate) async fn list_projects<'e, 'c: 'e, E: sqlx::Executor<'c, Database = s
 
 pub(crate) async fn delete_warehouse(
     warehouse_id: WarehouseIdent,
+    force: bool,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> Result<()> {
+) -> Result<DeletedWarehouseContents> {
+    let contents = if force {
+        delete_warehouse_contents(warehouse_id, transaction).await?
+    } else {
+        require_empty_warehouse(warehouse_id, transaction).await?;
+        DeletedWarehouseContents::default()
+    };
+
     let row_count = sqlx::query_scalar!(
         r#"DELETE FROM warehouse WHERE warehouse_id = $1"#,
         *warehouse_id
@@ -468,9 +724,136 @@ pub(crate) async fn delete_warehouse(
         return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
     }
 
+    Ok(contents)
+}
+
+async fn require_empty_warehouse(
+    warehouse_id: WarehouseIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let counts = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT count(*) FROM namespace WHERE warehouse_id = $1) AS "namespaces!",
+            count(*) FILTER (WHERE t.typ = 'table') AS "tables!",
+            count(*) FILTER (WHERE t.typ = 'view') AS "views!"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        WHERE n.warehouse_id = $1
+        "#,
+        *warehouse_id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error counting warehouse contents"))?;
+
+    if counts.namespaces > 0 {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Warehouse is not empty. It contains {} namespaces, {} tables and {} views. Delete them first or force the deletion.",
+                counts.namespaces, counts.tables, counts.views
+            ),
+            "WarehouseNotEmpty",
+            None,
+        )
+        .into());
+    }
     Ok(())
 }
 
+/// Delete all namespaces, tables, views and queued tasks of a warehouse.
+async fn delete_warehouse_contents(
+    warehouse_id: WarehouseIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<DeletedWarehouseContents> {
+    let tabulars = sqlx::query!(
+        r#"
+        WITH deleted AS (
+            DELETE FROM tabular t
+            USING namespace n
+            WHERE t.namespace_id = n.namespace_id AND n.warehouse_id = $1
+            RETURNING t.tabular_id, t.typ, t.fs_protocol, t.fs_location
+        )
+        SELECT
+            d.tabular_id as "tabular_id!",
+            d.typ as "typ!: TabularType",
+            d.fs_protocol as "fs_protocol!",
+            d.fs_location as "fs_location!",
+            te.deletion_kind = 'purge' as "dropped_with_purge?"
+        FROM deleted d
+        LEFT JOIN tabular_expirations te ON te.tabular_id = d.tabular_id
+        "#,
+        *warehouse_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting tables and views of warehouse"))?
+    .into_iter()
+    .map(|row| DeletedTabular {
+        tabular_id: match row.typ {
+            TabularType::Table => TabularIdentUuid::Table(row.tabular_id),
+            TabularType::View => TabularIdentUuid::View(row.tabular_id),
+        },
+        location: join_location(&row.fs_protocol, &row.fs_location),
+        dropped_with_purge: row.dropped_with_purge,
+    })
+    .collect();
+
+    sqlx::query!(
+        r#"DELETE FROM tabular_expirations WHERE warehouse_id = $1"#,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting expirations of warehouse"))?;
+
+    let pending_purge_locations = sqlx::query_scalar!(
+        r#"DELETE FROM tabular_purges WHERE warehouse_id = $1 RETURNING tabular_location"#,
+        *warehouse_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting purges of warehouse"))?;
+
+    sqlx::query!(
+        r#"DELETE FROM snapshot_cleanups WHERE warehouse_id = $1"#,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting snapshot cleanups of warehouse"))?;
+
+    sqlx::query!(
+        r#"DELETE FROM storage_secret_cleanups WHERE warehouse_id = $1"#,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting storage secret cleanups of warehouse"))?;
+
+    sqlx::query!(r#"DELETE FROM task WHERE warehouse_id = $1"#, *warehouse_id)
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error deleting tasks of warehouse"))?;
+
+    let namespace_ids = sqlx::query_scalar!(
+        r#"DELETE FROM namespace WHERE warehouse_id = $1 RETURNING namespace_id"#,
+        *warehouse_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting namespaces of warehouse"))?
+    .into_iter()
+    .map(NamespaceIdentUuid::from)
+    .collect();
+
+    Ok(DeletedWarehouseContents {
+        namespace_ids,
+        tabulars,
+        pending_purge_locations,
+    })
+}
+
 pub(crate) async fn rename_warehouse(
     warehouse_id: WarehouseIdent,
     new_name: &str,
@@ -486,7 +869,14 @@ pub(crate) async fn rename_warehouse(
     )
     .execute(&mut **transaction)
     .await
-    .map_err(|e| e.into_error_model("Error renaming warehouse"))?
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_err)
+            if db_err.constraint() == Some(UNIQUE_WAREHOUSE_NAME_IN_PROJECT) =>
+        {
+            warehouse_name_conflict(e)
+        }
+        _ => e.into_error_model("Error renaming warehouse"),
+    })?
     .rows_affected();
 
     if row_count == 0 {
@@ -520,34 +910,24 @@ pub(crate) async fn set_warehouse_status(
     Ok(())
 }
 
-pub(crate) async fn update_storage_profile(
+pub(crate) async fn set_warehouse_feature_flags(
     warehouse_id: WarehouseIdent,
-    storage_profile: StorageProfile,
-    storage_secret_id: Option<SecretIdent>,
+    feature_flags: &WarehouseFeatureFlags,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<()> {
-    let storage_profile_ser = serde_json::to_value(storage_profile).map_err(|e| {
-        ErrorModel::internal(
-            "Error serializing storage profile",
-            "StorageProfileSerializationError",
-            Some(Box::new(e)),
-        )
-    })?;
-
     let row_count = sqlx::query!(
         r#"
             UPDATE warehouse
-            SET storage_profile = $1, storage_secret_id = $2
-            WHERE warehouse_id = $3
+            SET feature_flags = $1
+            WHERE warehouse_id = $2
             AND status = 'active'
         "#,
-        storage_profile_ser,
-        storage_secret_id.map(|id| id.into_uuid()),
+        Json(feature_flags.to_raw()) as _,
         *warehouse_id
     )
     .execute(&mut **transaction)
     .await
-    .map_err(|e| e.into_error_model("Error updating storage profile"))?
+    .map_err(|e| e.into_error_model("Error setting warehouse feature flags"))?
     .rows_affected();
 
     if row_count == 0 {
@@ -557,75 +937,795 @@ pub(crate) async fn update_storage_profile(
     Ok(())
 }
 
-fn map_select_warehouse_err(e: Error) -> ErrorModel {
-    ErrorModel::internal(
-        "Error fetching warehouse",
-        "WarehouseFetchError",
-        Some(Box::new(e)),
+pub(crate) async fn set_warehouse_allowed_schemes(
+    warehouse_id: WarehouseIdent,
+    allowed_schemes: Option<&[String]>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET allowed_schemes = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        allowed_schemes as _,
+        *warehouse_id
     )
-}
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse allowed schemes"))?
+    .rows_affected();
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
-#[sqlx(type_name = "tabular_delete_mode", rename_all = "kebab-case")]
-enum DbTabularDeleteProfile {
-    Soft,
-    Hard,
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
 }
 
-impl From<TabularDeleteProfile> for DbTabularDeleteProfile {
-    fn from(value: TabularDeleteProfile) -> Self {
-        match value {
-            TabularDeleteProfile::Soft { .. } => DbTabularDeleteProfile::Soft,
-            TabularDeleteProfile::Hard {} => DbTabularDeleteProfile::Hard,
-        }
+pub(crate) async fn set_warehouse_purge_grace_period(
+    warehouse_id: WarehouseIdent,
+    purge_grace_period: Option<chrono::Duration>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET purge_grace_period_seconds = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        purge_grace_period.map(|d| d.num_seconds()),
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse purge grace period"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
     }
+
+    Ok(())
 }
 
-pub(crate) async fn get_warehouse_stats(
-    conn: PgPool,
-    warehouse_ident: WarehouseIdent,
-    PaginationQuery {
-        page_size,
-        page_token,
-    }: PaginationQuery,
-) -> crate::api::Result<WarehouseStatisticsResponse> {
-    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+pub(crate) async fn set_warehouse_schema_evolution_policy(
+    warehouse_id: WarehouseIdent,
+    schema_evolution_policy: SchemaEvolutionPolicy,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET schema_evolution_policy = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        Json(schema_evolution_policy) as _,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse schema evolution policy"))?
+    .rows_affected();
 
-    let token = page_token
-        .as_option()
-        .map(PaginateToken::try_from)
-        .transpose()?;
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
 
-    let (token_ts, _): (_, Option<String>) = token
-        .map(|PaginateToken::V1(V1PaginateToken { created_at, id })| (created_at, id))
-        .unzip();
+    Ok(())
+}
 
-    let stats = sqlx::query!(
+pub(crate) async fn set_warehouse_allowed_file_formats(
+    warehouse_id: WarehouseIdent,
+    allowed_file_formats: Option<&[String]>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
         r#"
-        SELECT number_of_views, number_of_tables, created_at, updated_at, timestamp
-        FROM warehouse_statistics
-        WHERE warehouse_id = $1
-        AND (timestamp < $2 OR $2 IS NULL)
-        ORDER BY timestamp DESC
-        LIMIT $3
+            UPDATE warehouse
+            SET allowed_file_formats = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
         "#,
-        warehouse_ident.0,
-        token_ts,
-        page_size
+        allowed_file_formats as _,
+        *warehouse_id
     )
-    .fetch_all(&conn)
+    .execute(&mut **transaction)
     .await
-    .map_err(|e| {
-        tracing::error!(error=?e, "Error fetching warehouse stats");
-        e.into_error_model("failed to get stats")
-    })?;
+    .map_err(|e| e.into_error_model("Error setting warehouse allowed file formats"))?
+    .rows_affected();
 
-    let next_page_token = stats.last().map(|s| {
-        PaginateToken::V1(V1PaginateToken {
-            created_at: s.timestamp,
-            id: String::new(),
-        })
-        .to_string()
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_namespace_property_schema(
+    warehouse_id: WarehouseIdent,
+    namespace_property_schema: &NamespacePropertySchema,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET namespace_property_schema = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        Json(namespace_property_schema) as _,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse namespace property schema"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_annotations(
+    warehouse_id: WarehouseIdent,
+    annotations: &HashMap<String, String>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET annotations = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        Json(annotations) as _,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse annotations"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_commit_rate_limit(
+    warehouse_id: WarehouseIdent,
+    commit_rate_limit: Option<CommitRateLimit>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET commit_rate_limit = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        commit_rate_limit.map(Json) as _,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse commit rate limit"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_credential_refresh_before(
+    warehouse_id: WarehouseIdent,
+    credential_refresh_before: Option<chrono::Duration>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET credential_refresh_before_seconds = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        credential_refresh_before.map(|d| d.num_seconds()),
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse credential refresh hint"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_empty_namespace_cleanup_after(
+    warehouse_id: WarehouseIdent,
+    cleanup_after: Option<chrono::Duration>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET empty_namespace_cleanup_after_seconds = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        cleanup_after.map(|d| d.num_seconds()),
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse empty namespace cleanup"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_allowed_authentication_methods(
+    warehouse_id: WarehouseIdent,
+    allowed_authentication_methods: Option<&[AuthenticationMethod]>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET allowed_authentication_methods = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        allowed_authentication_methods.map(Json) as _,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse allowed authentication methods"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_default_view_properties(
+    warehouse_id: WarehouseIdent,
+    default_view_properties: &HashMap<String, String>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET default_view_properties = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        Json(default_view_properties) as _,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse default view properties"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_manifest_merge_threshold(
+    warehouse_id: WarehouseIdent,
+    manifest_merge_threshold: Option<i32>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET manifest_merge_threshold = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        manifest_merge_threshold,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse manifest merge threshold"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_max_tables_per_namespace(
+    warehouse_id: WarehouseIdent,
+    max_tables_per_namespace: Option<i32>,
+    includes_views: bool,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET max_tables_per_namespace = $1,
+                max_tables_per_namespace_includes_views = $2
+            WHERE warehouse_id = $3
+            AND status = 'active'
+        "#,
+        max_tables_per_namespace,
+        includes_views,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse max tables per namespace"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_import_prefixes(
+    warehouse_id: WarehouseIdent,
+    import_prefixes: &[String],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET import_prefixes = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        import_prefixes,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse import prefixes"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_default_namespace(
+    warehouse_id: WarehouseIdent,
+    default_namespace: Option<&NamespaceIdent>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET default_namespace = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        default_namespace.map(|namespace| namespace.as_ref().as_slice()) as _,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse default namespace"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_snapshot_limit(
+    warehouse_id: WarehouseIdent,
+    snapshot_limit: Option<SnapshotLimit>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET snapshot_limit = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        snapshot_limit.map(Json) as _,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse snapshot limit"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn set_warehouse_storage_prefix_overrides(
+    warehouse_id: WarehouseIdent,
+    storage_prefix_overrides: &[String],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET storage_prefix_overrides = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+        "#,
+        storage_prefix_overrides,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse storage prefix overrides"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn update_storage_profile(
+    warehouse_id: WarehouseIdent,
+    storage_profile: StorageProfile,
+    storage_secret_id: Option<SecretIdent>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let storage_profile_ser = serde_json::to_value(storage_profile).map_err(|e| {
+        ErrorModel::internal(
+            "Error serializing storage profile",
+            "StorageProfileSerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET storage_profile = $1, storage_secret_id = $2
+            WHERE warehouse_id = $3
+            AND status = 'active'
+        "#,
+        storage_profile_ser,
+        storage_secret_id.map(|id| id.into_uuid()),
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error updating storage profile"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn rotate_storage_secret(
+    warehouse_id: WarehouseIdent,
+    storage_secret_id: SecretIdent,
+    previous_secret_expires_at: chrono::DateTime<chrono::Utc>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<RotatedStorageSecrets> {
+    // Lock the row so that concurrent rotations retain each replaced secret exactly once.
+    let previous = sqlx::query!(
+        r#"
+            SELECT storage_secret_id, previous_storage_secret_id
+            FROM warehouse
+            WHERE warehouse_id = $1
+            AND status = 'active'
+            FOR UPDATE
+        "#,
+        *warehouse_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching warehouse storage secret"))?
+    .ok_or_else(|| ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None))?;
+
+    sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET storage_secret_id = $1,
+                previous_storage_secret_id = $2,
+                previous_storage_secret_expires_at = $3
+            WHERE warehouse_id = $4
+        "#,
+        storage_secret_id.into_uuid(),
+        previous.storage_secret_id,
+        previous_secret_expires_at,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error rotating storage secret"))?;
+
+    Ok(RotatedStorageSecrets {
+        previous_secret_id: previous.storage_secret_id.map(Into::into),
+        superseded_secret_id: previous.previous_storage_secret_id.map(Into::into),
+    })
+}
+
+pub(crate) async fn list_warehouse_secret_references(
+    warehouse_id: WarehouseIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<WarehouseSecretReference>> {
+    let row = sqlx::query!(
+        r#"
+            SELECT storage_secret_id, previous_storage_secret_id, previous_storage_secret_expires_at
+            FROM warehouse
+            WHERE warehouse_id = $1
+        "#,
+        *warehouse_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching warehouse secret references"))?
+    .ok_or_else(|| ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None))?;
+
+    let current = row
+        .storage_secret_id
+        .map(|secret_id| WarehouseSecretReference {
+            secret_id: secret_id.into(),
+            usage: WarehouseSecretUsage::StorageCredential,
+            expires_at: None,
+        });
+    let previous = row
+        .previous_storage_secret_id
+        .map(|secret_id| WarehouseSecretReference {
+            secret_id: secret_id.into(),
+            usage: WarehouseSecretUsage::PreviousStorageCredential,
+            expires_at: row.previous_storage_secret_expires_at,
+        });
+    Ok(current.into_iter().chain(previous).collect())
+}
+
+pub(crate) async fn release_previous_storage_secret(
+    warehouse_id: WarehouseIdent,
+    secret_id: SecretIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<bool> {
+    sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET previous_storage_secret_id = NULL,
+                previous_storage_secret_expires_at = NULL
+            WHERE warehouse_id = $1
+            AND previous_storage_secret_id = $2
+        "#,
+        *warehouse_id,
+        secret_id.into_uuid()
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error releasing previous storage secret"))?;
+
+    let in_use = sqlx::query_scalar!(
+        r#"
+            SELECT EXISTS (
+                SELECT 1 FROM warehouse
+                WHERE storage_secret_id = $1 OR previous_storage_secret_id = $1
+            ) as "in_use!"
+        "#,
+        secret_id.into_uuid()
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error checking storage secret usage"))?;
+
+    Ok(!in_use)
+}
+
+pub(crate) async fn get_warehouse_data_key(
+    warehouse_id: WarehouseIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<WrappedDataKey>> {
+    let data_key = sqlx::query!(
+        r#"
+            SELECT master_key_id, wrapped_key
+            FROM warehouse_data_key
+            WHERE warehouse_id = $1
+        "#,
+        *warehouse_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching warehouse data key"))?
+    .map(|row| WrappedDataKey {
+        master_key_id: row.master_key_id,
+        wrapped_key: row.wrapped_key,
+    });
+
+    Ok(data_key)
+}
+
+pub(crate) async fn create_warehouse_data_key(
+    warehouse_id: WarehouseIdent,
+    data_key: &WrappedDataKey,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<bool> {
+    let row_count = sqlx::query!(
+        r#"
+            INSERT INTO warehouse_data_key (warehouse_id, master_key_id, wrapped_key)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (warehouse_id) DO NOTHING
+        "#,
+        *warehouse_id,
+        data_key.master_key_id,
+        data_key.wrapped_key
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error creating warehouse data key"))?
+    .rows_affected();
+
+    Ok(row_count > 0)
+}
+
+pub(crate) async fn update_warehouse_data_key(
+    warehouse_id: WarehouseIdent,
+    data_key: &WrappedDataKey,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse_data_key
+            SET master_key_id = $1, wrapped_key = $2
+            WHERE warehouse_id = $3
+        "#,
+        data_key.master_key_id,
+        data_key.wrapped_key,
+        *warehouse_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error updating warehouse data key"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(
+            ErrorModel::not_found("Warehouse data key not found", "DataKeyNotFound", None).into(),
+        );
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn list_warehouse_data_keys_to_rewrap(
+    master_key_id: &str,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<(WarehouseIdent, WrappedDataKey)>> {
+    let data_keys = sqlx::query!(
+        r#"
+            SELECT warehouse_id, master_key_id, wrapped_key
+            FROM warehouse_data_key
+            WHERE master_key_id <> $1
+            FOR UPDATE
+        "#,
+        master_key_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error listing warehouse data keys"))?
+    .into_iter()
+    .map(|row| {
+        (
+            row.warehouse_id.into(),
+            WrappedDataKey {
+                master_key_id: row.master_key_id,
+                wrapped_key: row.wrapped_key,
+            },
+        )
+    })
+    .collect();
+
+    Ok(data_keys)
+}
+
+fn map_select_warehouse_err(e: Error) -> ErrorModel {
+    ErrorModel::internal(
+        "Error fetching warehouse",
+        "WarehouseFetchError",
+        Some(Box::new(e)),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "tabular_delete_mode", rename_all = "kebab-case")]
+enum DbTabularDeleteProfile {
+    Soft,
+    Hard,
+}
+
+impl From<TabularDeleteProfile> for DbTabularDeleteProfile {
+    fn from(value: TabularDeleteProfile) -> Self {
+        match value {
+            TabularDeleteProfile::Soft { .. } => DbTabularDeleteProfile::Soft,
+            TabularDeleteProfile::Hard {} => DbTabularDeleteProfile::Hard,
+        }
+    }
+}
+
+pub(crate) async fn get_warehouse_stats(
+    conn: PgPool,
+    warehouse_ident: WarehouseIdent,
+    PaginationQuery {
+        page_size,
+        page_token,
+    }: PaginationQuery,
+) -> crate::api::Result<WarehouseStatisticsResponse> {
+    let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token = page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?;
+
+    let (token_ts, _): (_, Option<String>) = token
+        .map(|PaginateToken::V1(V1PaginateToken { created_at, id })| (created_at, id))
+        .unzip();
+
+    let stats = sqlx::query!(
+        r#"
+        SELECT number_of_views, number_of_tables, created_at, updated_at, timestamp
+        FROM warehouse_statistics
+        WHERE warehouse_id = $1
+        AND (timestamp < $2 OR $2 IS NULL)
+        ORDER BY timestamp DESC
+        LIMIT $3
+        "#,
+        warehouse_ident.0,
+        token_ts,
+        page_size
+    )
+    .fetch_all(&conn)
+    .await
+    .map_err(|e| {
+        tracing::error!(error=?e, "Error fetching warehouse stats");
+        e.into_error_model("failed to get stats")
+    })?;
+
+    let next_page_token = stats.last().map(|s| {
+        PaginateToken::V1(V1PaginateToken {
+            created_at: s.timestamp,
+            id: String::new(),
+        })
+        .to_string()
     });
 
     let stats = stats
@@ -651,7 +1751,9 @@ pub(crate) mod test {
     use super::*;
     use crate::{
         api::iceberg::types::PageToken,
-        implementations::postgres::{PostgresCatalog, PostgresTransaction},
+        implementations::postgres::{
+            tabular::table::tests::initialize_table, PostgresCatalog, PostgresTransaction,
+        },
         service::{
             storage::{S3Flavor, S3Profile},
             Catalog as _, Transaction,
@@ -876,6 +1978,66 @@ pub(crate) mod test {
         assert_eq!(warehouse.unwrap().name, "new_name");
     }
 
+    #[sqlx::test]
+    async fn test_concurrent_create_warehouse_with_same_name(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let project_id = ProjectId::from(uuid::Uuid::new_v4());
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        PostgresCatalog::create_project(project_id, "project".to_string(), t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+
+        let create = |name: &'static str| {
+            let state = state.clone();
+            async move {
+                let mut t = PostgresTransaction::begin_write(state).await?;
+                let warehouse_id = PostgresCatalog::create_warehouse(
+                    name.to_string(),
+                    project_id,
+                    StorageProfile::S3(
+                        S3Profile::builder()
+                            .bucket("test_bucket".to_string())
+                            .region("us-east-1".to_string())
+                            .flavor(S3Flavor::S3Compat)
+                            .sts_enabled(false)
+                            .build(),
+                    ),
+                    TabularDeleteProfile::Hard {},
+                    None,
+                    t.transaction(),
+                )
+                .await?;
+                t.commit().await?;
+                Ok::<_, crate::api::IcebergErrorResponse>(warehouse_id)
+            }
+        };
+        // Names only differing in case conflict as well
+        let (first, second) = tokio::join!(create("my-warehouse"), create("My-Warehouse"));
+
+        let (created, failed): (Vec<_>, Vec<_>) = [first, second]
+            .into_iter()
+            .partition(std::result::Result::is_ok);
+        assert_eq!(created.len(), 1);
+        let err = failed.into_iter().next().unwrap().unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        assert_eq!(err.error.r#type, "WarehouseNameAlreadyExists");
+
+        // Renaming to a taken name conflicts as well
+        let other_id =
+            initialize_warehouse(state.clone(), None, Some(&project_id), None, false).await;
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = PostgresCatalog::rename_warehouse(other_id, "MY-WAREHOUSE", t.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        assert_eq!(err.error.r#type, "WarehouseNameAlreadyExists");
+    }
+
     #[sqlx::test]
     async fn test_rename_project(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -927,6 +2089,78 @@ pub(crate) mod test {
         t.commit().await.unwrap();
     }
 
+    #[sqlx::test]
+    async fn test_delete_non_empty_warehouse(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let staged = initialize_table(
+            warehouse_id,
+            state.clone(),
+            true,
+            Some(table.namespace.clone()),
+            None,
+        )
+        .await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = PostgresCatalog::delete_warehouse(warehouse_id, false, t.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        assert_eq!(err.error.r#type, "WarehouseNotEmpty");
+        assert!(err
+            .error
+            .message
+            .contains("1 namespaces, 2 tables and 0 views"));
+        t.rollback().await.unwrap();
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let contents = PostgresCatalog::delete_warehouse(warehouse_id, true, t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+
+        assert_eq!(contents.namespace_ids, vec![table.namespace_id]);
+        assert_eq!(
+            contents
+                .tabulars
+                .iter()
+                .map(|t| t.tabular_id)
+                .collect::<HashSet<_>>(),
+            HashSet::from([
+                TabularIdentUuid::Table(*table.table_id),
+                TabularIdentUuid::Table(*staged.table_id)
+            ])
+        );
+        assert!(contents
+            .tabulars
+            .iter()
+            .all(|t| t.dropped_with_purge.is_none()));
+
+        let mut t = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        assert!(
+            PostgresCatalog::get_warehouse(warehouse_id, t.transaction())
+                .await
+                .unwrap()
+                .is_none()
+        );
+        t.commit().await.unwrap();
+        let remaining: i64 = sqlx::query_scalar(
+            "SELECT (SELECT count(*) FROM namespace) + (SELECT count(*) FROM tabular)",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
     #[sqlx::test]
     async fn test_warehouse_statistics_pagination(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -15,6 +15,7 @@ pub use service::{ProjectId, SecretIdent, WarehouseIdent};
 pub mod implementations;
 
 pub mod api;
+pub mod logging;
 mod request_metadata;
 
 #[cfg(feature = "router")]
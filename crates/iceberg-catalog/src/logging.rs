@@ -0,0 +1,154 @@
+//! Setup of the log output of the server.
+
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use tracing::Subscriber;
+use tracing_subscriber::{
+    filter::LevelFilter,
+    fmt::MakeWriter,
+    util::{SubscriberInitExt, TryInitError},
+    EnvFilter,
+};
+
+use crate::CONFIG;
+
+/// Key of the HMAC of principals, random per process unless configured.
+static PRINCIPAL_HASH_KEY: LazyLock<ring::hmac::Key> = LazyLock::new(|| {
+    CONFIG.log_principal_hash_key.as_ref().map_or_else(
+        || {
+            ring::hmac::Key::generate(ring::hmac::HMAC_SHA256, &ring::rand::SystemRandom::new())
+                .expect("Failed to generate the key for principal hashes")
+        },
+        |key| ring::hmac::Key::new(ring::hmac::HMAC_SHA256, key.as_bytes()),
+    )
+});
+
+/// Format of the log output.
+///
+/// Both formats include the timestamp, level and message of each event as well as
+/// the fields of the request span: `operation`, `warehouse_id`, `request_id`
+/// (the correlation ID of the request) and `principal_hash`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+    /// One JSON object per line. Fields of the current span are nested under `span`.
+    #[default]
+    #[serde(alias = "JSON", alias = "Json")]
+    Json,
+    /// Human-readable text.
+    #[serde(alias = "TEXT", alias = "Text", alias = "pretty")]
+    Text,
+}
+
+/// Install the global subscriber writing logs to stdout in the given format.
+/// Records of the `log` crate, as emitted by some dependencies, are forwarded to it.
+/// The level is controlled via `RUST_LOG` and defaults to `info`.
+///
+/// # Errors
+/// If a global subscriber or logger has already been installed.
+pub fn init_logging(format: LogFormat) -> Result<(), TryInitError> {
+    subscriber(format, std::io::stdout).try_init()
+}
+
+fn subscriber<W>(format: LogFormat, writer: W) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_file(true)
+        .with_line_number(true)
+        .with_env_filter(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::INFO.into())
+                .from_env_lossy(),
+        );
+    match format {
+        LogFormat::Json => Box::new(
+            builder
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .finish(),
+        ),
+        LogFormat::Text => Box::new(builder.finish()),
+    }
+}
+
+/// Hash of a principal for the `principal_hash` field of the request span.
+/// Logs can be correlated by principal without exposing user ids. The hash is
+/// keyed, so it cannot be reversed by hashing guessed user ids.
+pub(crate) fn hash_principal(principal: &str) -> String {
+    let tag = ring::hmac::sign(&PRINCIPAL_HASH_KEY, principal.as_bytes());
+    tag.as_ref()[..16]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'w> MakeWriter<'w> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'w self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_json_log_event_fields() {
+        let buffer = Buffer::default();
+        tracing::subscriber::with_default(subscriber(LogFormat::Json, buffer.clone()), || {
+            let span = tracing::info_span!(
+                "request",
+                operation = "GET /catalog/v1/{prefix}/namespaces",
+                warehouse_id = "0195d9b6-0dd0-7db2-9b4d-1e7d5c9c6a00",
+                request_id = "0195d9b6-1111-7db2-9b4d-1e7d5c9c6a00",
+                principal_hash = tracing::field::Empty,
+            );
+            let _guard = span.enter();
+            span.record("principal_hash", hash_principal("oidc~alice"));
+            tracing::info!("Listing namespaces");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line = output.lines().next().unwrap();
+        let event: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(event["timestamp"].is_string());
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(event["message"], "Listing namespaces");
+        let span = &event["span"];
+        assert_eq!(span["operation"], "GET /catalog/v1/{prefix}/namespaces");
+        assert_eq!(span["warehouse_id"], "0195d9b6-0dd0-7db2-9b4d-1e7d5c9c6a00");
+        assert_eq!(span["request_id"], "0195d9b6-1111-7db2-9b4d-1e7d5c9c6a00");
+        assert_eq!(span["principal_hash"], hash_principal("oidc~alice"));
+        assert!(!line.contains("alice"));
+    }
+
+    #[test]
+    fn test_hash_principal_is_stable() {
+        assert_eq!(hash_principal("oidc~alice"), hash_principal("oidc~alice"));
+        assert_ne!(hash_principal("oidc~alice"), hash_principal("oidc~bob"));
+        assert_eq!(hash_principal("oidc~alice").len(), 32);
+    }
+}
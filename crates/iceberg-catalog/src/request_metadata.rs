@@ -1,18 +1,33 @@
-use std::str::FromStr;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
 
 use axum::{
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use http::HeaderMap;
+use http::{header, HeaderMap, HeaderValue};
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
 use limes::Authentication;
 use uuid::Uuid;
 
-use crate::{service::authn::Actor, ProjectId, WarehouseIdent, CONFIG, DEFAULT_PROJECT_ID};
+use crate::{
+    service::{
+        authn::{Actor, AuthenticationMethod},
+        RoleId,
+    },
+    ProjectId, WarehouseIdent, CONFIG, DEFAULT_PROJECT_ID,
+};
 
 pub const PROJECT_ID_HEADER: &str = "x-project-ident";
 pub const X_REQUEST_ID_HEADER: &str = "x-request-id";
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Lifetime in seconds requested by the client for vended credentials.
+pub const CREDENTIAL_TTL_HEADER: &str = "x-lakekeeper-credential-ttl";
+/// Point in time from which on the requested resource is retired (RFC 8594).
+pub const SUNSET_HEADER: &str = "sunset";
 
 const X_FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
 const X_FORWARDED_PROTO_HEADER: &str = "x-forwarded-proto";
@@ -21,11 +36,22 @@ const X_FORWARDED_PORT_HEADER: &str = "x-forwarded-port";
 /// A struct to hold metadata about a request.
 #[derive(Debug, Clone)]
 pub struct RequestMetadata {
-    request_id: Uuid,
+    request_id: String,
     project_id: Option<ProjectId>,
     authentication: Option<Authentication>,
+    authentication_method: Option<AuthenticationMethod>,
     base_url: String,
     actor: Actor,
+    idempotency_key: Option<String>,
+    requested_credential_ttl: Option<chrono::Duration>,
+    // Shared between clones, so that warnings added while handling the request reach the response.
+    warnings: Arc<Mutex<Vec<String>>>,
+    retry_after: Arc<Mutex<Option<u64>>>,
+    sunset: Arc<Mutex<Option<chrono::DateTime<chrono::Utc>>>>,
+    // Groups of the principal, resolved at most once per request.
+    groups: Arc<OnceLock<Vec<RoleId>>>,
+    // Authorization decisions keyed by (user, relation, object), only valid for this request.
+    authz_decisions: Arc<Mutex<HashMap<(String, String, String), bool>>>,
 }
 
 impl RequestMetadata {
@@ -34,9 +60,11 @@ impl RequestMetadata {
         &mut self,
         actor: Actor,
         authentication: Authentication,
+        method: AuthenticationMethod,
     ) -> &mut Self {
         self.actor = actor;
         self.authentication = Some(authentication);
+        self.authentication_method = Some(method);
         self
     }
 
@@ -52,15 +80,48 @@ impl RequestMetadata {
         }
     }
 
+    /// Metadata of actions performed by background tasks of the server itself,
+    /// outside of any client request.
+    #[must_use]
+    pub(crate) fn new_background_task() -> Self {
+        Self {
+            request_id: Uuid::now_v7().to_string(),
+            project_id: None,
+            authentication: None,
+            authentication_method: None,
+            base_url: CONFIG
+                .base_uri
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            actor: Actor::Anonymous,
+            idempotency_key: None,
+            requested_credential_ttl: None,
+            warnings: Arc::default(),
+            retry_after: Arc::default(),
+            sunset: Arc::default(),
+            groups: Arc::default(),
+            authz_decisions: Arc::default(),
+        }
+    }
+
     #[cfg(test)]
     #[must_use]
     pub fn new_unauthenticated() -> Self {
         Self {
-            request_id: Uuid::now_v7(),
+            request_id: Uuid::now_v7().to_string(),
             project_id: None,
             authentication: None,
+            authentication_method: None,
             base_url: "http://localhost:8181".to_string(),
             actor: Actor::Anonymous,
+            idempotency_key: None,
+            requested_credential_ttl: None,
+            warnings: Arc::default(),
+            retry_after: Arc::default(),
+            sunset: Arc::default(),
+            groups: Arc::default(),
+            authz_decisions: Arc::default(),
         }
     }
 
@@ -73,7 +134,7 @@ impl RequestMetadata {
     #[must_use]
     pub fn random_human(user_id: crate::service::UserId) -> Self {
         Self {
-            request_id: Uuid::now_v7(),
+            request_id: Uuid::now_v7().to_string(),
             authentication: Some(
                 Authentication::builder()
                     .token_header(None)
@@ -84,12 +145,48 @@ impl RequestMetadata {
                     .principal_type(None)
                     .build(),
             ),
+            authentication_method: Some(AuthenticationMethod::Oidc),
             base_url: "http://localhost:8181".to_string(),
             actor: Actor::Principal(user_id),
             project_id: None,
+            idempotency_key: None,
+            requested_credential_ttl: None,
+            warnings: Arc::default(),
+            retry_after: Arc::default(),
+            sunset: Arc::default(),
+            groups: Arc::default(),
+            authz_decisions: Arc::default(),
         }
     }
 
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_authentication_method(mut self, method: AuthenticationMethod) -> Self {
+        self.authentication_method = Some(method);
+        self
+    }
+
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_idempotency_key(mut self, idempotency_key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(idempotency_key.into());
+        self
+    }
+
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_requested_credential_ttl(mut self, ttl: chrono::Duration) -> Self {
+        self.requested_credential_ttl = Some(ttl);
+        self
+    }
+
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_project_id(mut self, project_id: ProjectId) -> Self {
+        self.project_id = Some(project_id);
+        self
+    }
+
     #[must_use]
     pub fn actor(&self) -> &Actor {
         &self.actor
@@ -100,9 +197,115 @@ impl RequestMetadata {
         self.authentication.as_ref()
     }
 
+    /// Method the request was authenticated with. `None` for anonymous requests.
+    #[must_use]
+    pub fn authentication_method(&self) -> Option<AuthenticationMethod> {
+        self.authentication_method
+    }
+
+    /// Correlation ID of the request, taken from the configured request ID header
+    /// or generated if the client did not send one.
+    #[must_use]
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    /// Value of the `Idempotency-Key` header, if provided.
+    #[must_use]
+    pub fn idempotency_key(&self) -> Option<&str> {
+        self.idempotency_key.as_deref()
+    }
+
+    /// Lifetime of vended credentials requested via the `x-lakekeeper-credential-ttl` header.
+    /// Requests can only shorten the lifetime, never extend it.
+    #[must_use]
+    pub fn requested_credential_ttl(&self) -> Option<chrono::Duration> {
+        self.requested_credential_ttl
+    }
+
+    /// Attach a warning to the response, e.g. because a limit is approached.
+    /// Warnings do not change the outcome of the request.
+    pub fn add_warning(&self, warning: impl Into<String>) {
+        self.warnings
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(warning.into());
+    }
+
+    /// Warnings added so far while handling the request.
+    #[must_use]
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Ask the client to wait at least `seconds` before retrying via the
+    /// `Retry-After` header, e.g. because the request was throttled.
+    pub fn set_retry_after(&self, seconds: u64) {
+        *self
+            .retry_after
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(seconds);
+    }
+
+    /// Seconds the client is asked to wait before retrying, if set.
+    #[must_use]
+    pub fn retry_after(&self) -> Option<u64> {
+        *self
+            .retry_after
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Announce via the `Sunset` header that the requested resource is retired at `sunset`.
+    pub fn set_sunset(&self, sunset: chrono::DateTime<chrono::Utc>) {
+        *self.sunset.lock().unwrap_or_else(PoisonError::into_inner) = Some(sunset);
+    }
+
+    /// Point in time from which on the requested resource is retired, if announced.
     #[must_use]
-    pub fn request_id(&self) -> Uuid {
-        self.request_id
+    pub fn sunset(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        *self.sunset.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Record the groups resolved for the principal of the request.
+    /// Only the first call has an effect.
+    pub fn set_groups(&self, groups: Vec<RoleId>) {
+        let _ = self.groups.set(groups);
+    }
+
+    /// Groups resolved for the principal of the request, if any.
+    #[must_use]
+    pub fn groups(&self) -> Option<&[RoleId]> {
+        self.groups.get().map(Vec::as_slice)
+    }
+
+    /// Authorization decision recorded earlier in this request for `user`,
+    /// `relation` and `object`, if any.
+    #[must_use]
+    pub(crate) fn authz_decision(&self, user: &str, relation: &str, object: &str) -> Option<bool> {
+        self.authz_decisions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&(user.to_string(), relation.to_string(), object.to_string()))
+            .copied()
+    }
+
+    /// Record an authorization decision for the rest of this request.
+    /// Decisions are never shared with other requests.
+    pub(crate) fn record_authz_decision(
+        &self,
+        user: String,
+        relation: String,
+        object: String,
+        allowed: bool,
+    ) {
+        self.authz_decisions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert((user, relation, object), allowed);
     }
 
     #[must_use]
@@ -169,17 +372,12 @@ pub(crate) async fn create_request_metadata_with_trace_and_project_fn(
     mut request: axum::extract::Request,
     next: Next,
 ) -> Response {
-    let request_id: Uuid = headers
-        .get(X_REQUEST_ID_HEADER)
-        .and_then(|hv| {
-            hv.to_str()
-                .map(Uuid::from_str)
-                .ok()
-                .transpose()
-                .ok()
-                .flatten()
-        })
-        .unwrap_or(Uuid::now_v7());
+    // The header is set by the request ID layer if the client did not send one
+    let request_id = headers
+        .get(&CONFIG.request_id_header)
+        .and_then(|hv| hv.to_str().ok())
+        .filter(|id| !id.is_empty())
+        .map_or_else(|| Uuid::now_v7().to_string(), ToString::to_string);
 
     let Some(host) = determine_base_uri(&headers) else {
         return IcebergErrorResponse::from(ErrorModel::bad_request(
@@ -199,14 +397,97 @@ pub(crate) async fn create_request_metadata_with_trace_and_project_fn(
         Ok(ident) => ident,
         Err(err) => return err.into_response(),
     };
+    let idempotency_key = match parse_idempotency_key(&headers) {
+        Ok(key) => key,
+        Err(err) => return IcebergErrorResponse::from(err).into_response(),
+    };
+    let requested_credential_ttl = match parse_credential_ttl(&headers) {
+        Ok(ttl) => ttl,
+        Err(err) => return IcebergErrorResponse::from(err).into_response(),
+    };
+    let warnings = Arc::<Mutex<Vec<String>>>::default();
+    let retry_after = Arc::<Mutex<Option<u64>>>::default();
+    let sunset = Arc::<Mutex<Option<chrono::DateTime<chrono::Utc>>>>::default();
     request.extensions_mut().insert(RequestMetadata {
         request_id,
         authentication: None,
+        authentication_method: None,
         base_url: host,
         actor: Actor::Anonymous,
         project_id,
+        idempotency_key,
+        requested_credential_ttl,
+        warnings: warnings.clone(),
+        retry_after: retry_after.clone(),
+        sunset: sunset.clone(),
+        groups: Arc::default(),
+        authz_decisions: Arc::default(),
     });
-    next.run(request).await
+    let mut response = next.run(request).await;
+
+    let warnings = std::mem::take(&mut *warnings.lock().unwrap_or_else(PoisonError::into_inner));
+    for warning in warnings {
+        if let Some(value) = warning_header_value(&warning) {
+            response.headers_mut().append(header::WARNING, value);
+        }
+    }
+    if let Some(seconds) = *retry_after.lock().unwrap_or_else(PoisonError::into_inner) {
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+    }
+    if let Some(sunset) = *sunset.lock().unwrap_or_else(PoisonError::into_inner) {
+        // HTTP-date, e.g. `Sat, 01 Nov 2025 00:00:00 GMT`
+        let value = sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            response.headers_mut().insert(SUNSET_HEADER, value);
+        }
+    }
+    response
+}
+
+#[cfg(feature = "router")]
+/// Format `text` as a miscellaneous persistent warning (RFC 7234, code 299).
+fn warning_header_value(text: &str) -> Option<HeaderValue> {
+    let text = text.replace('\\', "\\\\").replace('"', "\\\"");
+    HeaderValue::from_str(&format!("299 - \"{text}\"")).ok()
+}
+
+fn parse_idempotency_key(headers: &HeaderMap) -> Result<Option<String>, ErrorModel> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    let invalid = || {
+        ErrorModel::bad_request(
+            "Idempotency-Key header must contain between 1 and 255 visible ASCII characters",
+            "InvalidIdempotencyKey",
+            None,
+        )
+    };
+    let key = value.to_str().map_err(|_| invalid())?;
+    if key.is_empty() || key.len() > 255 || !key.chars().all(|c| c.is_ascii_graphic()) {
+        return Err(invalid());
+    }
+    Ok(Some(key.to_string()))
+}
+
+fn parse_credential_ttl(headers: &HeaderMap) -> Result<Option<chrono::Duration>, ErrorModel> {
+    let Some(value) = headers.get(CREDENTIAL_TTL_HEADER) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .filter(|seconds| *seconds > 0)
+        .map(|seconds| Some(chrono::Duration::seconds(seconds.into())))
+        .ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!("{CREDENTIAL_TTL_HEADER} header must be a positive number of seconds"),
+                "InvalidCredentialTtl",
+                None,
+            )
+        })
 }
 
 fn determine_base_uri(headers: &HeaderMap) -> Option<String> {
@@ -265,10 +546,217 @@ mod test {
     use http::{header::HeaderValue, HeaderMap};
 
     use crate::request_metadata::{
-        determine_base_uri, X_FORWARDED_FOR_HEADER, X_FORWARDED_PORT_HEADER,
-        X_FORWARDED_PROTO_HEADER,
+        determine_base_uri, parse_idempotency_key, IDEMPOTENCY_KEY_HEADER, X_FORWARDED_FOR_HEADER,
+        X_FORWARDED_PORT_HEADER, X_FORWARDED_PROTO_HEADER,
     };
 
+    #[cfg(feature = "router")]
+    #[tokio::test]
+    async fn test_request_id_is_propagated_to_events_and_response() {
+        use std::sync::{Arc, Mutex};
+
+        use axum::{body::Body, middleware::from_fn, routing::post, Extension, Router};
+        use tower::{ServiceBuilder, ServiceExt};
+        use tower_http::ServiceBuilderExt;
+        use uuid::Uuid;
+
+        use crate::{
+            request_metadata::{
+                create_request_metadata_with_trace_and_project_fn, RequestMetadata,
+            },
+            service::event_publisher::{
+                CloudEventBackend, CloudEventsPublisher, CloudEventsPublisherBackgroundTask,
+                WarehouseEventMetadata,
+            },
+            tracing::MakeRequestUuid7,
+            WarehouseIdent, CONFIG,
+        };
+
+        #[derive(Debug, Default)]
+        struct CapturingBackend(Mutex<Vec<cloudevents::Event>>);
+
+        #[async_trait::async_trait]
+        impl CloudEventBackend for CapturingBackend {
+            async fn publish(&self, event: cloudevents::Event) -> anyhow::Result<()> {
+                self.0.lock().unwrap().push(event);
+                Ok(())
+            }
+
+            fn name(&self) -> &'static str {
+                "capturing-publisher"
+            }
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::channel(10);
+        let backend = Arc::new(CapturingBackend::default());
+        let publisher_task = tokio::spawn(
+            CloudEventsPublisherBackgroundTask {
+                source: rx,
+                sinks: vec![backend.clone()],
+            }
+            .publish(),
+        );
+
+        let publisher = CloudEventsPublisher::new(tx);
+        let router = Router::new()
+            .route(
+                "/event",
+                post(
+                    move |Extension(metadata): Extension<RequestMetadata>| async move {
+                        publisher
+                            .publish_warehouse_event(
+                                Uuid::now_v7(),
+                                "test",
+                                serde_json::Value::Null,
+                                WarehouseEventMetadata {
+                                    warehouse_id: WarehouseIdent::from(Uuid::nil()),
+                                    actor: metadata.actor().to_string(),
+                                    trace_id: metadata.request_id().to_string(),
+                                },
+                            )
+                            .await
+                            .unwrap();
+                    },
+                ),
+            )
+            .layer(from_fn(create_request_metadata_with_trace_and_project_fn))
+            .layer(
+                ServiceBuilder::new()
+                    .set_request_id(CONFIG.request_id_header.clone(), MakeRequestUuid7)
+                    .propagate_request_id(CONFIG.request_id_header.clone()),
+            );
+
+        let mut response_ids = vec![];
+        for inbound in [Some("checkout-42"), None] {
+            let mut request = http::Request::post("/event").header(http::header::HOST, "localhost");
+            if let Some(inbound) = inbound {
+                request = request.header(&CONFIG.request_id_header, inbound);
+            }
+            let response = router
+                .clone()
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+            response_ids.push(
+                response
+                    .headers()
+                    .get(&CONFIG.request_id_header)
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+        drop(router);
+        publisher_task.await.unwrap().unwrap();
+
+        assert_eq!(response_ids[0], "checkout-42");
+        // An ID is generated if the client did not send one
+        assert!(Uuid::parse_str(&response_ids[1]).is_ok());
+
+        let trace_ids = backend
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| event.extension("trace-id").map(ToString::to_string))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            trace_ids,
+            response_ids.into_iter().map(Some).collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "router")]
+    #[tokio::test]
+    async fn test_warnings_are_attached_to_response() {
+        use axum::{body::Body, middleware::from_fn, routing::get, Extension, Router};
+        use tower::ServiceExt;
+
+        use crate::request_metadata::{
+            create_request_metadata_with_trace_and_project_fn, RequestMetadata,
+        };
+
+        let router = Router::new()
+            .route(
+                "/warn",
+                get(
+                    |Extension(metadata): Extension<RequestMetadata>| async move {
+                        metadata.add_warning("Namespace contains 8 of at most 10 tables");
+                        metadata.add_warning(r#"Description of "t" is long"#);
+                    },
+                ),
+            )
+            .layer(from_fn(create_request_metadata_with_trace_and_project_fn));
+        let response = router
+            .oneshot(
+                http::Request::get("/warn")
+                    .header(http::header::HOST, "localhost")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+        let warnings = response
+            .headers()
+            .get_all(http::header::WARNING)
+            .iter()
+            .map(|v| v.to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![
+                r#"299 - "Namespace contains 8 of at most 10 tables""#,
+                r#"299 - "Description of \"t\" is long""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_idempotency_key() {
+        assert_eq!(parse_idempotency_key(&HeaderMap::new()).unwrap(), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("my-key-1"));
+        assert_eq!(
+            parse_idempotency_key(&headers).unwrap(),
+            Some("my-key-1".to_string())
+        );
+
+        for invalid in ["", "with space", "a".repeat(256).as_str()] {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                IDEMPOTENCY_KEY_HEADER,
+                HeaderValue::from_str(invalid).unwrap(),
+            );
+            assert!(parse_idempotency_key(&headers).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_parse_credential_ttl() {
+        assert_eq!(parse_credential_ttl(&HeaderMap::new()).unwrap(), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CREDENTIAL_TTL_HEADER, HeaderValue::from_static("1800"));
+        assert_eq!(
+            parse_credential_ttl(&headers).unwrap(),
+            Some(chrono::Duration::minutes(30))
+        );
+
+        for invalid in ["", "0", "-5", "1h"] {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                CREDENTIAL_TTL_HEADER,
+                HeaderValue::from_str(invalid).unwrap(),
+            );
+            assert!(parse_credential_ttl(&headers).is_err(), "{invalid}");
+        }
+    }
+
     #[test]
     fn test_determine_host_without_host_header_with_config_provided_base_uri() {
         figment::Jail::expect_with(|jail| {
@@ -0,0 +1,233 @@
+use std::{sync::OnceLock, time::Duration};
+
+use axum_prometheus::metrics;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::{
+    request_metadata::RequestMetadata,
+    service::{
+        task_queue::{seconds_to_std_duration, std_duration_to_seconds},
+        Catalog, Transaction,
+    },
+};
+
+/// Maximum number of records written in a single transaction.
+const BATCH_SIZE: usize = 500;
+const DROPPED_RECORDS_METRIC: &str = "lakekeeper_audit_log_dropped_records_total";
+
+/// Sender of the running audit log. Unset if the audit log is disabled.
+static AUDIT_LOG: OnceLock<mpsc::Sender<AuditRecord>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditLogConfig {
+    /// Persist authorization decisions in the catalog database.
+    pub enabled: bool,
+    /// Records older than this are pruned.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub retention: Duration,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub prune_interval: Duration,
+    /// Number of records buffered before further records are dropped.
+    pub buffer_size: usize,
+}
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention: Duration::from_secs(90 * 24 * 3600),
+            prune_interval: Duration::from_secs(3600),
+            buffer_size: 10_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditOutcome {
+    Allowed,
+    Denied,
+}
+
+/// A single authorization decision.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuditRecord {
+    pub id: uuid::Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Principal that requested the action. `None` for anonymous requests.
+    pub principal: Option<String>,
+    /// Groups of the principal resolved while authorizing the request.
+    pub groups: Vec<String>,
+    /// Requested action, for example `can_drop`.
+    pub action: String,
+    /// Object the action was requested on, for example `table:<table-id>`.
+    pub object: String,
+    pub outcome: AuditOutcome,
+    pub request_id: String,
+}
+
+impl AuditRecord {
+    #[must_use]
+    pub fn new(
+        metadata: &RequestMetadata,
+        action: impl std::fmt::Display,
+        object: impl Into<String>,
+        outcome: AuditOutcome,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::now_v7(),
+            created_at: chrono::Utc::now(),
+            principal: metadata.user_id().map(ToString::to_string),
+            groups: metadata
+                .groups()
+                .unwrap_or_default()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            action: action.to_string(),
+            object: object.into(),
+            outcome,
+            request_id: metadata.request_id().to_string(),
+        }
+    }
+}
+
+/// Filter for querying the audit log. All conditions must match.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditLogFilter {
+    pub principal: Option<String>,
+    /// Prefix of the object, for example `table:` or `warehouse:<warehouse-id>`.
+    pub object_prefix: Option<String>,
+    pub outcome: Option<AuditOutcome>,
+    /// Inclusive lower bound of `created_at`.
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Exclusive upper bound of `created_at`.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Record an authorization decision if the audit log is enabled.
+/// Never blocks: If the buffer is full, the record is dropped.
+pub(crate) fn record_decision(
+    metadata: &RequestMetadata,
+    action: impl std::fmt::Display,
+    object: impl std::fmt::Display,
+    allowed: bool,
+) {
+    if AUDIT_LOG.get().is_none() {
+        return;
+    }
+    send_record(AuditRecord::new(
+        metadata,
+        action,
+        object.to_string(),
+        outcome(allowed),
+    ));
+}
+
+/// Record a decision taken by a component that does not know the request,
+/// such as a contract verifier. The record has no principal and no request ID.
+pub(crate) fn record_system_decision(
+    action: impl std::fmt::Display,
+    object: impl std::fmt::Display,
+    allowed: bool,
+) {
+    if AUDIT_LOG.get().is_none() {
+        return;
+    }
+    send_record(AuditRecord {
+        id: uuid::Uuid::now_v7(),
+        created_at: chrono::Utc::now(),
+        principal: None,
+        groups: vec![],
+        action: action.to_string(),
+        object: object.to_string(),
+        outcome: outcome(allowed),
+        request_id: String::new(),
+    });
+}
+
+fn outcome(allowed: bool) -> AuditOutcome {
+    if allowed {
+        AuditOutcome::Allowed
+    } else {
+        AuditOutcome::Denied
+    }
+}
+
+/// Never blocks: If the buffer is full, the record is dropped.
+fn send_record(record: AuditRecord) {
+    let Some(tx) = AUDIT_LOG.get() else {
+        return;
+    };
+    if tx.try_send(record).is_err() {
+        metrics::counter!(DROPPED_RECORDS_METRIC).increment(1);
+        tracing::warn!("Audit log buffer is full, dropping record");
+    }
+}
+
+/// Persists recorded authorization decisions and periodically prunes records older
+/// than the configured retention. Never returns; spawn it as a background task.
+pub async fn audit_log_task<C: Catalog>(catalog_state: C::State, config: AuditLogConfig) {
+    let (tx, mut rx) = mpsc::channel(config.buffer_size.max(1));
+    if AUDIT_LOG.set(tx).is_err() {
+        tracing::error!("Audit log task is already running");
+        return;
+    }
+
+    let mut prune_interval = tokio::time::interval(config.prune_interval);
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+    loop {
+        tokio::select! {
+            received = rx.recv_many(&mut batch, BATCH_SIZE) => {
+                if received == 0 {
+                    return;
+                }
+                if let Err(e) = write_records::<C>(catalog_state.clone(), &batch).await {
+                    tracing::error!(
+                        records = batch.len(),
+                        "Failed to write audit records: {:?}",
+                        e.error
+                    );
+                }
+                batch.clear();
+            }
+            _ = prune_interval.tick() => {
+                match prune_records::<C>(catalog_state.clone(), config.retention).await {
+                    Ok(pruned) => tracing::debug!(pruned, "Pruned audit log"),
+                    Err(e) => tracing::error!("Failed to prune audit log: {:?}", e.error),
+                }
+            }
+        }
+    }
+}
+
+async fn write_records<C: Catalog>(
+    catalog_state: C::State,
+    records: &[AuditRecord],
+) -> crate::api::Result<()> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    C::insert_audit_records(records, t.transaction()).await?;
+    t.commit().await
+}
+
+async fn prune_records<C: Catalog>(
+    catalog_state: C::State,
+    retention: Duration,
+) -> crate::api::Result<u64> {
+    let before = chrono::Duration::from_std(retention)
+        .ok()
+        .and_then(|retention| chrono::Utc::now().checked_sub_signed(retention))
+        .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let pruned = C::prune_audit_records(before, t.transaction()).await?;
+    t.commit().await?;
+    Ok(pruned)
+}
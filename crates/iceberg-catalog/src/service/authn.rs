@@ -1,7 +1,13 @@
-use std::{fmt::Debug, str::FromStr};
+use std::{
+    fmt::Debug,
+    net::SocketAddr,
+    str::FromStr,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
 
 use axum::{
-    extract::{Request, State},
+    extract::{ConnectInfo, Request, State},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -11,13 +17,21 @@ use axum_extra::{
 };
 use http::{HeaderMap, StatusCode};
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
-use limes::{format_subject, parse_subject, Authenticator, Subject};
+use itertools::Itertools;
+use limes::{format_subject, parse_subject, Authentication, Authenticator, Subject};
 use serde::{Deserialize, Serialize};
 
-use super::{authz::Authorizer, RoleId};
+use super::{
+    authz::Authorizer,
+    client_certificate::ClientCertificateVerifier,
+    token_verification::{is_opaque_token, IntrospectionVerifier},
+    Catalog, RoleId,
+};
 use crate::{
-    api::{self},
+    api::{self, load_shedding::warehouse_id_from_path},
+    logging::hash_principal,
     request_metadata::RequestMetadata,
+    WarehouseIdent,
 };
 
 pub const IDP_SEPARATOR: char = '~';
@@ -35,9 +49,37 @@ pub enum Actor {
     },
 }
 
+/// Method a request was authenticated with.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum AuthenticationMethod {
+    /// JWT validated by the OpenID provider or Kubernetes.
+    Oidc,
+    /// Opaque token validated via token introspection.
+    TokenIntrospection,
+    /// Client certificate forwarded by a TLS-terminating proxy.
+    Mtls,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct AuthMiddlewareState<T: Authenticator, A: Authorizer> {
-    pub authenticator: T,
+    pub authenticator: Option<T>,
+    /// Validates opaque tokens. JWTs are always validated by `authenticator`.
+    pub token_introspection: Option<IntrospectionVerifier>,
+    /// Validates client certificates forwarded by a proxy. Takes precedence over bearer tokens.
+    pub client_certificate: Option<ClientCertificateVerifier>,
     pub authorizer: A,
 }
 
@@ -46,6 +88,11 @@ pub struct UserId(Subject);
 
 /// Use a limes [`Authenticator`] to Authenticate a request.
 ///
+/// If client certificate authentication is configured and the request carries
+/// a forwarded client certificate, the certificate is used instead of the bearer token.
+/// Certificates are only accepted from trusted proxies. The header is removed from
+/// requests of other peers, which are authenticated via their bearer token.
+///
 /// This middleware needs to run after [`create_request_metadata_with_trace_and_project_fn`](crate::request_metadata::create_request_metadata_with_trace_and_project_fn).
 pub(crate) async fn auth_middleware_fn<T: Authenticator, A: Authorizer>(
     State(state): State<AuthMiddlewareState<T, A>>,
@@ -54,18 +101,39 @@ pub(crate) async fn auth_middleware_fn<T: Authenticator, A: Authorizer>(
     mut request: Request,
     next: Next,
 ) -> Response {
-    let authenticator = &state.authenticator;
     let authorizer = &state.authorizer;
-    let Some(authorization) = authorization else {
-        tracing::debug!("Missing authorization header");
-        return (StatusCode::UNAUTHORIZED, "Missing authorization header").into_response();
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let client_certificate = match &state.client_certificate {
+        Some(verifier) if headers.contains_key(verifier.header()) => {
+            if peer.is_some_and(|peer| verifier.is_trusted_proxy(peer)) {
+                headers
+                    .get(verifier.header())
+                    .map(|certificate| (verifier, certificate))
+            } else {
+                tracing::debug!(
+                    "Removing client certificate header of request from untrusted peer {peer:?}"
+                );
+                request.headers_mut().remove(verifier.header());
+                None
+            }
+        }
+        _ => None,
     };
-
-    let authentication = match authenticator.authenticate(authorization.token()).await {
-        Ok(principal) => principal,
-        Err(e) => {
-            tracing::debug!("Failed to authenticate: {}", e);
-            return (StatusCode::UNAUTHORIZED, "Failed to authenticate").into_response();
+    let (authentication, method) = if let Some((verifier, certificate)) = client_certificate {
+        match verifier.authenticate(certificate) {
+            Ok(principal) => (principal, AuthenticationMethod::Mtls),
+            Err(e) => {
+                tracing::debug!("Failed to authenticate via client certificate: {}", e);
+                return IcebergErrorResponse::from(e).into_response();
+            }
+        }
+    } else {
+        match authenticate_bearer(&state, authorization).await {
+            Ok(authenticated) => authenticated,
+            Err(response) => return response,
         }
     };
     let user_id = match UserId::try_new(authentication.subject().clone()) {
@@ -86,6 +154,7 @@ pub(crate) async fn auth_middleware_fn<T: Authenticator, A: Authorizer>(
         Ok(role_id) => role_id,
         Err(e) => return e.into_response(),
     };
+    tracing::Span::current().record("principal_hash", hash_principal(&user_id.to_string()));
     let actor = match role_id {
         Some(role_id) => Actor::Role {
             principal: user_id,
@@ -100,12 +169,142 @@ pub(crate) async fn auth_middleware_fn<T: Authenticator, A: Authorizer>(
     }
 
     if let Some(request_metadata) = request.extensions_mut().get_mut::<RequestMetadata>() {
-        request_metadata.set_authentication(actor.clone(), authentication);
+        request_metadata.set_authentication(actor.clone(), authentication, method);
     }
 
     next.run(request).await
 }
 
+/// Maximum number of warehouses whose allowed authentication methods are cached.
+const MAX_CACHED_WAREHOUSES: u64 = 10_000;
+/// Changes of the allowed authentication methods made on other instances take
+/// effect after this delay.
+const ALLOWED_AUTHENTICATION_METHODS_TTL: Duration = Duration::from_secs(30);
+
+/// Allowed authentication methods of warehouses, `None` if all methods are allowed.
+static ALLOWED_AUTHENTICATION_METHODS: LazyLock<
+    moka::sync::Cache<WarehouseIdent, Option<Arc<[AuthenticationMethod]>>>,
+> = LazyLock::new(|| {
+    moka::sync::Cache::builder()
+        .max_capacity(MAX_CACHED_WAREHOUSES)
+        .time_to_live(ALLOWED_AUTHENTICATION_METHODS_TTL)
+        .build()
+});
+
+/// Drop the cached allowed authentication methods of a warehouse after they changed.
+pub(crate) fn invalidate_allowed_authentication_methods(warehouse_id: WarehouseIdent) {
+    ALLOWED_AUTHENTICATION_METHODS.invalidate(&warehouse_id);
+}
+
+/// Reject requests to a warehouse that were authenticated with a method the
+/// warehouse does not allow with `403 Forbidden`.
+///
+/// Requests without authentication method are only possible with authentication
+/// disabled and are not restricted.
+///
+/// # Errors
+/// If the method is not allowed or the allowed methods cannot be loaded.
+pub(crate) async fn require_allowed_authentication_method<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    request_metadata: &RequestMetadata,
+    catalog_state: C::State,
+) -> api::Result<()> {
+    let Some(method) = request_metadata.authentication_method() else {
+        return Ok(());
+    };
+    let allowed = if let Some(allowed) = ALLOWED_AUTHENTICATION_METHODS.get(&warehouse_id) {
+        allowed
+    } else {
+        let allowed = C::get_warehouse_allowed_authentication_methods(warehouse_id, catalog_state)
+            .await?
+            .map(Arc::from);
+        ALLOWED_AUTHENTICATION_METHODS.insert(warehouse_id, allowed.clone());
+        allowed
+    };
+    match allowed {
+        Some(allowed) if !allowed.contains(&method) => {
+            tracing::debug!(
+                "Denying request to warehouse {warehouse_id} authenticated via {method}"
+            );
+            Err(ErrorModel::forbidden(
+                format!(
+                    "Warehouse does not allow authentication via {method}. Allowed methods: {}",
+                    allowed.iter().join(", ")
+                ),
+                "AuthenticationMethodNotAllowed",
+                None,
+            )
+            .into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Apply [`require_allowed_authentication_method`] to requests whose path addresses
+/// a warehouse. Handlers resolving the warehouse otherwise, such as `GET /config`,
+/// check the method themselves.
+///
+/// This middleware needs to run after [`auth_middleware_fn`] and before any authorization.
+pub(crate) async fn warehouse_authentication_method_fn<C: Catalog>(
+    State(catalog_state): State<C::State>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let warehouse_id = warehouse_id_from_path(request.uri().path());
+    let request_metadata = request.extensions().get::<RequestMetadata>();
+    if let (Some(warehouse_id), Some(request_metadata)) = (warehouse_id, request_metadata) {
+        if let Err(e) = require_allowed_authentication_method::<C>(
+            warehouse_id,
+            request_metadata,
+            catalog_state,
+        )
+        .await
+        {
+            return e.into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+async fn authenticate_bearer<T: Authenticator, A: Authorizer>(
+    state: &AuthMiddlewareState<T, A>,
+    authorization: Option<TypedHeader<Authorization<Bearer>>>,
+) -> Result<(Authentication, AuthenticationMethod), Response> {
+    let Some(authorization) = authorization else {
+        tracing::debug!("Missing authorization header");
+        return Err((StatusCode::UNAUTHORIZED, "Missing authorization header").into_response());
+    };
+    let token = authorization.token();
+
+    let introspection = state
+        .token_introspection
+        .as_ref()
+        .filter(|_| is_opaque_token(token));
+    if let Some(introspection) = introspection {
+        introspection
+            .authenticate(token)
+            .await
+            .map(|authentication| (authentication, AuthenticationMethod::TokenIntrospection))
+            .map_err(|e| {
+                tracing::debug!("Failed to authenticate via token introspection: {}", e);
+                IcebergErrorResponse::from(e).into_response()
+            })
+    } else if let Some(authenticator) = &state.authenticator {
+        authenticator
+            .authenticate(token)
+            .await
+            .map(|authentication| (authentication, AuthenticationMethod::Oidc))
+            .map_err(|e| {
+                tracing::debug!("Failed to authenticate: {}", e);
+                (StatusCode::UNAUTHORIZED, "Failed to authenticate").into_response()
+            })
+    } else {
+        tracing::debug!("No authenticator configured for JWT");
+        Err((StatusCode::UNAUTHORIZED, "Failed to authenticate").into_response())
+    }
+}
+
 fn extract_role_id(headers: &HeaderMap) -> Result<Option<RoleId>, IcebergErrorResponse> {
     if let Some(role_id) = headers.get(ASSUME_ROLE_HEADER) {
         let role_id = role_id.to_str().map_err(|e| {
@@ -231,9 +430,186 @@ impl Serialize for UserId {
 
 #[cfg(test)]
 mod tests {
+    use axum::{body::Body, middleware::from_fn, routing::get, Extension, Router};
+    use http::HeaderValue;
+    use http_body_util::BodyExt;
+    use limes::AuthenticatorEnum;
+    use tower::ServiceExt;
     use uuid::Uuid;
 
     use super::*;
+    use crate::{
+        api::management::v1::{
+            warehouse::{
+                Service as _, TabularDeleteProfile,
+                UpdateWarehouseAllowedAuthenticationMethodsRequest,
+            },
+            ApiServer,
+        },
+        implementations::postgres::PostgresCatalog,
+        service::{
+            authz::AllowAllAuthorizer,
+            client_certificate::test::{escaped_pem, verifier, CLIENT, TRUSTED_PROXY},
+        },
+    };
+
+    async fn whoami(router: &Router, headers: &[(&str, HeaderValue)]) -> (StatusCode, String) {
+        let mut request = http::Request::builder().uri("/whoami");
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+        let response = router
+            .clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[sqlx::test]
+    async fn test_warehouse_rejects_disallowed_authentication_method(pool: sqlx::PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool,
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        ApiServer::update_warehouse_allowed_authentication_methods(
+            warehouse.warehouse_id,
+            UpdateWarehouseAllowedAuthenticationMethodsRequest {
+                allowed_authentication_methods: Some(vec![AuthenticationMethod::Mtls]),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let router = Router::new()
+            .route("/catalog/v1/{prefix}/namespaces", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                ctx.v1_state.catalog.clone(),
+                warehouse_authentication_method_fn::<PostgresCatalog>,
+            ))
+            .layer(from_fn(|mut request: Request, next: Next| async move {
+                let method = if request.headers().contains_key("x-ssl-client-cert") {
+                    AuthenticationMethod::Mtls
+                } else {
+                    AuthenticationMethod::Oidc
+                };
+                request.extensions_mut().insert(
+                    RequestMetadata::random_human(UserId::new_unchecked("oidc", "alice"))
+                        .with_authentication_method(method),
+                );
+                next.run(request).await
+            }));
+        let list_namespaces = |headers: &'static [(&'static str, &'static str)]| {
+            let mut request = http::Request::builder()
+                .uri(format!("/catalog/v1/{}/namespaces", warehouse.warehouse_id));
+            for (name, value) in headers {
+                request = request.header(*name, *value);
+            }
+            router.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        let response = list_namespaces(&[]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let error: IcebergErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error.error.r#type, "AuthenticationMethodNotAllowed");
+
+        let response = list_namespaces(&[("x-ssl-client-cert", "present")])
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Updates take effect immediately on this instance
+        ApiServer::update_warehouse_allowed_authentication_methods(
+            warehouse.warehouse_id,
+            UpdateWarehouseAllowedAuthenticationMethodsRequest {
+                allowed_authentication_methods: None,
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let response = list_namespaces(&[]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    fn client_certificate_router(peer: SocketAddr) -> Router {
+        Router::new()
+            .route(
+                "/whoami",
+                get(
+                    |Extension(metadata): Extension<RequestMetadata>| async move {
+                        metadata.actor().to_string()
+                    },
+                ),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                AuthMiddlewareState::<AuthenticatorEnum, _> {
+                    authenticator: None,
+                    token_introspection: None,
+                    client_certificate: Some(verifier("svc-{subject.cn}")),
+                    authorizer: AllowAllAuthorizer,
+                },
+                auth_middleware_fn,
+            ))
+            .layer(from_fn(
+                move |mut request: Request, next: Next| async move {
+                    request
+                        .extensions_mut()
+                        .insert(RequestMetadata::new_unauthenticated());
+                    request.extensions_mut().insert(ConnectInfo(peer));
+                    next.run(request).await
+                },
+            ))
+    }
+
+    #[tokio::test]
+    async fn test_client_certificate_authenticates_as_mapped_principal() {
+        let router = client_certificate_router(SocketAddr::new(TRUSTED_PROXY, 40000));
+        let bearer = HeaderValue::from_static("Bearer not-a-valid-token");
+
+        // The certificate takes precedence over the bearer token
+        let (status, actor) = whoami(
+            &router,
+            &[
+                ("x-ssl-client-cert", escaped_pem(CLIENT)),
+                ("authorization", bearer.clone()),
+            ],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(actor, "Principal(mtls~svc-billing-service)");
+
+        let (status, _) = whoami(&router, &[("authorization", bearer)]).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = whoami(
+            &router,
+            &[("x-ssl-client-cert", HeaderValue::from_static("garbage"))],
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_client_certificate_of_untrusted_peer_is_ignored() {
+        let router = client_certificate_router("10.0.0.1:40000".parse().unwrap());
+
+        // The header is removed, so the request needs a bearer token
+        let (status, body) = whoami(&router, &[("x-ssl-client-cert", escaped_pem(CLIENT))]).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, "Missing authorization header");
+    }
 
     #[test]
     fn test_user_id() {
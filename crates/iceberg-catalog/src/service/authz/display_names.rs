@@ -0,0 +1,107 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    api::iceberg::v1::Result,
+    service::{authn::UserId, RoleId},
+};
+
+/// A user or role listed in an admin-facing response, such as the assignments
+/// of an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrincipalId {
+    User(UserId),
+    Role(RoleId),
+}
+
+impl std::fmt::Display for PrincipalId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrincipalId::User(user_id) => write!(f, "{user_id}"),
+            PrincipalId::Role(role_id) => write!(f, "{role_id}"),
+        }
+    }
+}
+
+/// Resolves principals to human-readable names from an external directory,
+/// for example LDAP or SCIM.
+///
+/// Display names are for presentation only. They are never used to
+/// authorize requests.
+#[async_trait::async_trait]
+pub trait DisplayNameResolver: Send + Sync + Debug + 'static {
+    /// Display name of the principal, `None` if the directory does not know it.
+    ///
+    /// # Errors
+    /// If the directory cannot be reached. The raw identifier is shown instead.
+    async fn resolve_display_name(&self, principal: &PrincipalId) -> Result<Option<String>>;
+}
+
+/// Caches the names returned by a [`DisplayNameResolver`] per principal for `ttl`.
+/// Failed resolutions are not cached.
+#[derive(Debug)]
+pub(crate) struct DisplayNameCache {
+    resolver: Arc<dyn DisplayNameResolver>,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl DisplayNameCache {
+    pub(crate) fn new(resolver: Arc<dyn DisplayNameResolver>, ttl: Duration) -> Self {
+        Self {
+            resolver,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Display names of `principals`, keyed by their identifier. Principals
+    /// that cannot be resolved are listed with their identifier as name.
+    pub(crate) async fn display_names(
+        &self,
+        principals: impl IntoIterator<Item = PrincipalId>,
+    ) -> HashMap<String, String> {
+        let principals = principals
+            .into_iter()
+            .map(|principal| (principal.to_string(), principal))
+            .collect::<HashMap<_, _>>();
+
+        let names = principals.into_iter().map(|(key, principal)| async move {
+            let name = self.display_name(key.clone(), &principal).await;
+            (key, name)
+        });
+        futures::future::join_all(names).await.into_iter().collect()
+    }
+
+    async fn display_name(&self, key: String, principal: &PrincipalId) -> String {
+        if let Some((resolved_at, name)) = self
+            .entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+        {
+            if resolved_at.elapsed() < self.ttl {
+                return name.clone();
+            }
+        }
+
+        let name = match self.resolver.resolve_display_name(principal).await {
+            Ok(name) => name.unwrap_or_else(|| key.clone()),
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to resolve display name of {principal}: {:?}",
+                    e.error
+                );
+                return key;
+            }
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.retain(|_, (resolved_at, _)| resolved_at.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), name.clone()));
+        name
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::iceberg::v1::{ErrorModel, Result},
+    service::{authn::UserId, RoleId},
+    CONFIG,
+};
+
+/// Most principals whose groups are cached at the same time.
+const MAX_CACHED_PRINCIPALS: u64 = 10_000;
+
+/// Longest time to establish a connection to the directory.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+static GROUP_RESOLVER_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(CONFIG.backend_timeouts.authz_check)
+        .build()
+        // Only fails if the TLS backend cannot be initialized, as `reqwest::Client::new`
+        .expect("Failed to build HTTP client for group resolution")
+});
+
+/// Resolves the groups of a principal from an external directory, for example
+/// LDAP or SCIM, while a request is authorized.
+///
+/// Groups are identified by the Lakekeeper role that represents them. A principal
+/// is treated as an assignee of each returned role for the duration of the request,
+/// without the assignment being stored.
+#[async_trait::async_trait]
+pub trait GroupResolver: Send + Sync + Debug + 'static {
+    /// Groups the principal is currently a member of.
+    ///
+    /// # Errors
+    /// If the directory cannot be reached. Requests fail instead of being
+    /// authorized without the groups.
+    async fn resolve_groups(&self, user_id: &UserId) -> Result<Vec<RoleId>>;
+}
+
+/// Resolves groups by sending a `POST` request with the `user-id` of the principal
+/// to a directory service, which responds with the ids of the roles representing
+/// the `groups` of the principal.
+#[derive(Debug)]
+pub(crate) struct HttpGroupResolver {
+    pub(crate) url: url::Url,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct HttpGroupRequest<'a> {
+    user_id: &'a str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct HttpGroupResponse {
+    groups: Vec<RoleId>,
+}
+
+#[async_trait::async_trait]
+impl GroupResolver for HttpGroupResolver {
+    async fn resolve_groups(&self, user_id: &UserId) -> Result<Vec<RoleId>> {
+        let response = GROUP_RESOLVER_CLIENT
+            .post(self.url.clone())
+            .json(&HttpGroupRequest {
+                user_id: &user_id.to_string(),
+            })
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| {
+                ErrorModel::internal(
+                    format!("Failed to request groups from {}: {e}", self.url),
+                    "GroupResolverError",
+                    Some(Box::new(e)),
+                )
+            })?
+            .json::<HttpGroupResponse>()
+            .await
+            .map_err(|e| {
+                ErrorModel::internal(
+                    format!("Failed to parse groups returned by {}: {e}", self.url),
+                    "GroupResolverError",
+                    Some(Box::new(e)),
+                )
+            })?;
+        Ok(response.groups)
+    }
+}
+
+/// Caches the groups returned by a [`GroupResolver`] per principal for `ttl`.
+/// Failed resolutions are not cached. At most [`MAX_CACHED_PRINCIPALS`] principals
+/// are cached, the least recently used are evicted first.
+#[derive(Debug)]
+pub(crate) struct GroupCache {
+    resolver: Arc<dyn GroupResolver>,
+    entries: moka::sync::Cache<String, Vec<RoleId>>,
+}
+
+impl GroupCache {
+    pub(crate) fn new(resolver: Arc<dyn GroupResolver>, ttl: Duration) -> Self {
+        Self {
+            resolver,
+            entries: moka::sync::Cache::builder()
+                .max_capacity(MAX_CACHED_PRINCIPALS)
+                .time_to_live(ttl)
+                .build(),
+        }
+    }
+
+    pub(crate) async fn groups(&self, user_id: &UserId) -> Result<Vec<RoleId>> {
+        let key = user_id.to_string();
+        if let Some(groups) = self.entries.get(&key) {
+            return Ok(groups);
+        }
+
+        let groups = self.resolver.resolve_groups(user_id).await?;
+        self.entries.insert(key, groups.clone());
+        Ok(groups)
+    }
+}
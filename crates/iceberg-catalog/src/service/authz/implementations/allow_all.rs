@@ -8,9 +8,10 @@ use crate::{
     service::{
         authn::UserId,
         authz::{
-            Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogRoleAction,
-            CatalogServerAction, CatalogTableAction, CatalogUserAction, CatalogViewAction,
-            CatalogWarehouseAction, ListProjectsResponse, NamespaceParent,
+            Authorizer, BaselineRelations, CatalogNamespaceAction, CatalogObject,
+            CatalogProjectAction, CatalogRoleAction, CatalogServerAction, CatalogTableAction,
+            CatalogUserAction, CatalogViewAction, CatalogWarehouseAction, ListProjectsResponse,
+            NamespaceParent,
         },
         health::{Health, HealthExt},
         Actor, Catalog, NamespaceIdentUuid, ProjectId, RoleId, SecretStore, State, TableIdentUuid,
@@ -203,6 +204,16 @@ impl Authorizer for AllowAllAuthorizer {
         Ok(())
     }
 
+    async fn move_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+        _previous_parent: NamespaceParent,
+        _new_parent: NamespaceParent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     async fn create_table(
         &self,
         _metadata: &RequestMetadata,
@@ -228,4 +239,12 @@ impl Authorizer for AllowAllAuthorizer {
     async fn delete_view(&self, _view_id: ViewIdentUuid) -> Result<()> {
         Ok(())
     }
+
+    async fn check_baseline_relations(
+        &self,
+        _object: CatalogObject,
+        _repair: bool,
+    ) -> Result<BaselineRelations> {
+        Ok(BaselineRelations::Consistent)
+    }
 }
@@ -1,11 +1,16 @@
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    str::FromStr,
+};
 
 use axum::{
     extract::{Path, Query, State as AxumState},
     routing::{get, post},
-    Extension, Json, Router,
+    Extension, Router,
 };
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
 use http::StatusCode;
+use iceberg_ext::catalog::rest::{encoding::Json, ErrorModel};
 use openfga_rs::{
     CheckRequestTupleKey, ConsistencyPreference, ReadRequestTupleKey, TupleKey,
     TupleKeyWithoutCondition,
@@ -38,9 +43,12 @@ use crate::{
     api::ApiContext,
     request_metadata::RequestMetadata,
     service::{
-        authz::implementations::openfga::{
-            entities::OpenFgaEntity, service_ext::MAX_TUPLES_PER_WRITE, OpenFGAAuthorizer,
-            OpenFGAError, OpenFGAResult,
+        authz::{
+            display_names::PrincipalId,
+            implementations::openfga::{
+                entities::OpenFgaEntity, service_ext::MAX_TUPLES_PER_WRITE, OpenFGAAuthorizer,
+                OpenFGAError, OpenFGAResult, ProjectScopedObject,
+            },
         },
         Actor, Catalog, NamespaceIdentUuid, Result, RoleId, SecretStore, State, TableIdentUuid,
         ViewIdentUuid,
@@ -49,6 +57,10 @@ use crate::{
 };
 
 const _MAX_ASSIGNMENTS_PER_RELATION: i32 = 200;
+/// Privileges of namespaces, tables and views are inherited from their `parent`
+/// namespaces by the authorization model, so grants on a namespace apply to its
+/// whole subtree without tuples for each level.
+const NAMESPACE_INHERITANCE: bool = true;
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 #[serde(rename_all = "camelCase")]
@@ -109,12 +121,24 @@ struct GetRoleAssignmentsQuery {
     #[serde(default)]
     #[param(nullable = false, required = false)]
     relations: Option<Vec<RoleRelation>>,
+    /// Token of a previous response to continue reading assignments that
+    /// were not returned because the page limit was reached.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct GetRoleAssignmentsResponse {
     assignments: Vec<RoleAssignment>,
+    /// Display names of the listed users and roles, keyed by their id.
+    /// Only set if a display name resolver is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_names: Option<HashMap<String, String>>,
+    /// Set if the result is partial because the page limit was reached.
+    /// Pass it as `pageToken` to read the remaining assignments.
+    next_page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -124,12 +148,24 @@ struct GetServerAssignmentsQuery {
     #[serde(default)]
     #[param(nullable = false, required = false)]
     relations: Option<Vec<ServerRelation>>,
+    /// Token of a previous response to continue reading assignments that
+    /// were not returned because the page limit was reached.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct GetServerAssignmentsResponse {
     assignments: Vec<ServerAssignment>,
+    /// Display names of the listed users and roles, keyed by their id.
+    /// Only set if a display name resolver is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_names: Option<HashMap<String, String>>,
+    /// Set if the result is partial because the page limit was reached.
+    /// Pass it as `pageToken` to read the remaining assignments.
+    next_page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -139,14 +175,26 @@ pub(super) struct GetProjectAssignmentsQuery {
     #[serde(default)]
     #[param(nullable = false, required = false)]
     relations: Option<Vec<ProjectRelation>>,
+    /// Token of a previous response to continue reading assignments that
+    /// were not returned because the page limit was reached.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct GetProjectAssignmentsResponse {
     assignments: Vec<ProjectAssignment>,
+    /// Display names of the listed users and roles, keyed by their id.
+    /// Only set if a display name resolver is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_names: Option<HashMap<String, String>>,
     #[schema(value_type = Uuid)]
     project_id: ProjectId,
+    /// Set if the result is partial because the page limit was reached.
+    /// Pass it as `pageToken` to read the remaining assignments.
+    next_page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -156,12 +204,24 @@ pub(super) struct GetWarehouseAssignmentsQuery {
     #[serde(default)]
     #[param(nullable = false, required = false)]
     relations: Option<Vec<WarehouseRelation>>,
+    /// Token of a previous response to continue reading assignments that
+    /// were not returned because the page limit was reached.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct GetWarehouseAssignmentsResponse {
     assignments: Vec<WarehouseAssignment>,
+    /// Display names of the listed users and roles, keyed by their id.
+    /// Only set if a display name resolver is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_names: Option<HashMap<String, String>>,
+    /// Set if the result is partial because the page limit was reached.
+    /// Pass it as `pageToken` to read the remaining assignments.
+    next_page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -171,12 +231,24 @@ pub(super) struct GetNamespaceAssignmentsQuery {
     #[serde(default)]
     #[param(nullable = false, required = false)]
     relations: Option<Vec<NamespaceRelation>>,
+    /// Token of a previous response to continue reading assignments that
+    /// were not returned because the page limit was reached.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct GetNamespaceAssignmentsResponse {
     assignments: Vec<NamespaceAssignment>,
+    /// Display names of the listed users and roles, keyed by their id.
+    /// Only set if a display name resolver is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_names: Option<HashMap<String, String>>,
+    /// Set if the result is partial because the page limit was reached.
+    /// Pass it as `pageToken` to read the remaining assignments.
+    next_page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -186,12 +258,24 @@ pub(super) struct GetTableAssignmentsQuery {
     #[serde(default)]
     #[param(nullable = false, required = false)]
     relations: Option<Vec<TableRelation>>,
+    /// Token of a previous response to continue reading assignments that
+    /// were not returned because the page limit was reached.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct GetTableAssignmentsResponse {
     assignments: Vec<TableAssignment>,
+    /// Display names of the listed users and roles, keyed by their id.
+    /// Only set if a display name resolver is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_names: Option<HashMap<String, String>>,
+    /// Set if the result is partial because the page limit was reached.
+    /// Pass it as `pageToken` to read the remaining assignments.
+    next_page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -201,12 +285,24 @@ pub(super) struct GetViewAssignmentsQuery {
     #[serde(default)]
     #[param(nullable = false, required = false)]
     relations: Option<Vec<ViewRelation>>,
+    /// Token of a previous response to continue reading assignments that
+    /// were not returned because the page limit was reached.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct GetViewAssignmentsResponse {
     assignments: Vec<ViewAssignment>,
+    /// Display names of the listed users and roles, keyed by their id.
+    /// Only set if a display name resolver is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_names: Option<HashMap<String, String>>,
+    /// Set if the result is partial because the page limit was reached.
+    /// Pass it as `pageToken` to read the remaining assignments.
+    next_page_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -276,6 +372,9 @@ struct UpdateRoleAssignmentsRequest {
 #[serde(rename_all = "kebab-case")]
 struct GetWarehouseAuthPropertiesResponse {
     managed_access: bool,
+    /// Whether privileges granted on a namespace apply to all namespaces, tables
+    /// and views nested in it. Always enabled by the current authorization model.
+    namespace_inheritance: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -309,7 +408,12 @@ async fn get_role_access_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetAccessQuery>,
 ) -> Result<(StatusCode, Json<GetRoleAccessResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Role(role_id))
+        .await?
+        .into_owned();
     let relations = get_allowed_actions(
         authorizer,
         metadata.actor(),
@@ -373,10 +477,15 @@ async fn get_project_access<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetAccessQuery>,
 ) -> Result<(StatusCode, Json<GetProjectAccessResponse>)> {
-    let authorizer = api_context.v1_state.authz;
     let project_id = metadata
         .preferred_project_id()
         .ok_or(OpenFGAError::NoProjectId)?;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_project(project_id)
+        .await?
+        .into_owned();
     let relations = get_allowed_actions(
         authorizer,
         metadata.actor(),
@@ -412,7 +521,12 @@ async fn get_project_access_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetAccessQuery>,
 ) -> Result<(StatusCode, Json<GetProjectAccessResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_project(project_id)
+        .await?
+        .into_owned();
     let relations = get_allowed_actions(
         authorizer,
         metadata.actor(),
@@ -448,7 +562,12 @@ async fn get_warehouse_access_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetAccessQuery>,
 ) -> Result<(StatusCode, Json<GetWarehouseAccessResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Warehouse(warehouse_id))
+        .await?
+        .into_owned();
     let relations = get_allowed_actions(
         authorizer,
         metadata.actor(),
@@ -482,7 +601,12 @@ async fn get_warehouse_by_id<C: Catalog, S: SecretStore>(
     AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
     Extension(metadata): Extension<RequestMetadata>,
 ) -> Result<(StatusCode, Json<GetWarehouseAuthPropertiesResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Warehouse(warehouse_id))
+        .await?
+        .into_owned();
     authorizer
         .require_action(
             &metadata,
@@ -495,7 +619,10 @@ async fn get_warehouse_by_id<C: Catalog, S: SecretStore>(
 
     Ok((
         StatusCode::OK,
-        Json(GetWarehouseAuthPropertiesResponse { managed_access }),
+        Json(GetWarehouseAuthPropertiesResponse {
+            managed_access,
+            namespace_inheritance: NAMESPACE_INHERITANCE,
+        }),
     ))
 }
 
@@ -517,7 +644,12 @@ async fn set_warehouse_managed_access<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Json(request): Json<SetManagedAccessRequest>,
 ) -> Result<StatusCode> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Warehouse(warehouse_id))
+        .await?
+        .into_owned();
     authorizer
         .require_action(
             &metadata,
@@ -550,7 +682,12 @@ async fn set_namespace_managed_access<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Json(request): Json<SetManagedAccessRequest>,
 ) -> Result<StatusCode> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Namespace(namespace_id))
+        .await?
+        .into_owned();
     authorizer
         .require_action(
             &metadata,
@@ -581,7 +718,12 @@ async fn get_namespace_by_id<C: Catalog, S: SecretStore>(
     AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
     Extension(metadata): Extension<RequestMetadata>,
 ) -> Result<(StatusCode, Json<GetNamespaceAuthPropertiesResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Namespace(namespace_id))
+        .await?
+        .into_owned();
     authorizer
         .require_action(
             &metadata,
@@ -627,7 +769,12 @@ async fn get_namespace_access_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetAccessQuery>,
 ) -> Result<(StatusCode, Json<GetNamespaceAccessResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Namespace(namespace_id))
+        .await?
+        .into_owned();
     let relations = get_allowed_actions(
         authorizer,
         metadata.actor(),
@@ -663,7 +810,12 @@ async fn get_table_access_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetAccessQuery>,
 ) -> Result<(StatusCode, Json<GetTableAccessResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Table(table_id))
+        .await?
+        .into_owned();
     let relations = get_allowed_actions(
         authorizer,
         metadata.actor(),
@@ -699,7 +851,12 @@ async fn get_view_access_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetAccessQuery>,
 ) -> Result<(StatusCode, Json<GetViewAccessResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::View(view_id))
+        .await?
+        .into_owned();
     let relations = get_allowed_actions(
         authorizer,
         metadata.actor(),
@@ -735,7 +892,12 @@ async fn get_role_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetRoleAssignmentsQuery>,
 ) -> Result<(StatusCode, Json<GetRoleAssignmentsResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Role(role_id))
+        .await?
+        .into_owned();
     authorizer
         .require_action(
             &metadata,
@@ -743,11 +905,22 @@ async fn get_role_assignments_by_id<C: Catalog, S: SecretStore>(
             &role_id.to_openfga(),
         )
         .await?;
-    let assignments = get_relations(authorizer, query.relations, &role_id.to_openfga()).await?;
+    let (assignments, next_page_token) = get_relations(
+        authorizer.clone(),
+        query.relations,
+        query.page_token.as_deref(),
+        &role_id.to_openfga(),
+    )
+    .await?;
+    let display_names = resolve_display_names(&authorizer, &assignments).await;
 
     Ok((
         StatusCode::OK,
-        Json(GetRoleAssignmentsResponse { assignments }),
+        Json(GetRoleAssignmentsResponse {
+            assignments,
+            display_names,
+            next_page_token,
+        }),
     ))
 }
 
@@ -774,11 +947,22 @@ async fn get_server_assignments<C: Catalog, S: SecretStore>(
             &OPENFGA_SERVER,
         )
         .await?;
-    let assignments = get_relations(authorizer, query.relations, &OPENFGA_SERVER).await?;
+    let (assignments, next_page_token) = get_relations(
+        authorizer.clone(),
+        query.relations,
+        query.page_token.as_deref(),
+        &OPENFGA_SERVER,
+    )
+    .await?;
+    let display_names = resolve_display_names(&authorizer, &assignments).await;
 
     Ok((
         StatusCode::OK,
-        Json(GetServerAssignmentsResponse { assignments }),
+        Json(GetServerAssignmentsResponse {
+            assignments,
+            display_names,
+            next_page_token,
+        }),
     ))
 }
 
@@ -797,10 +981,15 @@ async fn get_project_assignments<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetProjectAssignmentsQuery>,
 ) -> Result<(StatusCode, Json<GetProjectAssignmentsResponse>)> {
-    let authorizer = api_context.v1_state.authz;
     let project_id = metadata
         .preferred_project_id()
         .ok_or(OpenFGAError::NoProjectId)?;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_project(project_id)
+        .await?
+        .into_owned();
     authorizer
         .require_action(
             &metadata,
@@ -808,13 +997,22 @@ async fn get_project_assignments<C: Catalog, S: SecretStore>(
             &project_id.to_openfga(),
         )
         .await?;
-    let assignments = get_relations(authorizer, query.relations, &project_id.to_openfga()).await?;
+    let (assignments, next_page_token) = get_relations(
+        authorizer.clone(),
+        query.relations,
+        query.page_token.as_deref(),
+        &project_id.to_openfga(),
+    )
+    .await?;
+    let display_names = resolve_display_names(&authorizer, &assignments).await;
 
     Ok((
         StatusCode::OK,
         Json(GetProjectAssignmentsResponse {
             assignments,
+            display_names,
             project_id,
+            next_page_token,
         }),
     ))
 }
@@ -838,7 +1036,12 @@ async fn get_project_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetProjectAssignmentsQuery>,
 ) -> Result<(StatusCode, Json<GetProjectAssignmentsResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_project(project_id)
+        .await?
+        .into_owned();
     authorizer
         .require_action(
             &metadata,
@@ -846,13 +1049,22 @@ async fn get_project_assignments_by_id<C: Catalog, S: SecretStore>(
             &project_id.to_openfga(),
         )
         .await?;
-    let assignments = get_relations(authorizer, query.relations, &project_id.to_openfga()).await?;
+    let (assignments, next_page_token) = get_relations(
+        authorizer.clone(),
+        query.relations,
+        query.page_token.as_deref(),
+        &project_id.to_openfga(),
+    )
+    .await?;
+    let display_names = resolve_display_names(&authorizer, &assignments).await;
 
     Ok((
         StatusCode::OK,
         Json(GetProjectAssignmentsResponse {
             assignments,
+            display_names,
             project_id,
+            next_page_token,
         }),
     ))
 }
@@ -876,16 +1088,32 @@ async fn get_warehouse_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetWarehouseAssignmentsQuery>,
 ) -> Result<(StatusCode, Json<GetWarehouseAssignmentsResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Warehouse(warehouse_id))
+        .await?
+        .into_owned();
     let object = warehouse_id.to_openfga();
     authorizer
         .require_action(&metadata, AllWarehouseRelation::CanReadAssignments, &object)
         .await?;
-    let assignments = get_relations(authorizer, query.relations, &object).await?;
+    let (assignments, next_page_token) = get_relations(
+        authorizer.clone(),
+        query.relations,
+        query.page_token.as_deref(),
+        &object,
+    )
+    .await?;
+    let display_names = resolve_display_names(&authorizer, &assignments).await;
 
     Ok((
         StatusCode::OK,
-        Json(GetWarehouseAssignmentsResponse { assignments }),
+        Json(GetWarehouseAssignmentsResponse {
+            assignments,
+            display_names,
+            next_page_token,
+        }),
     ))
 }
 
@@ -908,7 +1136,12 @@ async fn get_namespace_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetNamespaceAssignmentsQuery>,
 ) -> Result<(StatusCode, Json<GetNamespaceAssignmentsResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Namespace(namespace_id))
+        .await?
+        .into_owned();
     let object = namespace_id.to_openfga();
     authorizer
         .require_action(
@@ -917,11 +1150,22 @@ async fn get_namespace_assignments_by_id<C: Catalog, S: SecretStore>(
             &object,
         )
         .await?;
-    let assignments = get_relations(authorizer, query.relations, &object).await?;
+    let (assignments, next_page_token) = get_relations(
+        authorizer.clone(),
+        query.relations,
+        query.page_token.as_deref(),
+        &object,
+    )
+    .await?;
+    let display_names = resolve_display_names(&authorizer, &assignments).await;
 
     Ok((
         StatusCode::OK,
-        Json(GetNamespaceAssignmentsResponse { assignments }),
+        Json(GetNamespaceAssignmentsResponse {
+            assignments,
+            display_names,
+            next_page_token,
+        }),
     ))
 }
 
@@ -944,16 +1188,32 @@ async fn get_table_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetTableAssignmentsQuery>,
 ) -> Result<(StatusCode, Json<GetTableAssignmentsResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Table(table_id))
+        .await?
+        .into_owned();
     let object = table_id.to_openfga();
     authorizer
         .require_action(&metadata, AllTableRelations::CanReadAssignments, &object)
         .await?;
-    let assignments = get_relations(authorizer, query.relations, &object).await?;
+    let (assignments, next_page_token) = get_relations(
+        authorizer.clone(),
+        query.relations,
+        query.page_token.as_deref(),
+        &object,
+    )
+    .await?;
+    let display_names = resolve_display_names(&authorizer, &assignments).await;
 
     Ok((
         StatusCode::OK,
-        Json(GetTableAssignmentsResponse { assignments }),
+        Json(GetTableAssignmentsResponse {
+            assignments,
+            display_names,
+            next_page_token,
+        }),
     ))
 }
 
@@ -976,16 +1236,32 @@ async fn get_view_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Query(query): Query<GetViewAssignmentsQuery>,
 ) -> Result<(StatusCode, Json<GetViewAssignmentsResponse>)> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::View(view_id))
+        .await?
+        .into_owned();
     let object = view_id.to_openfga();
     authorizer
         .require_action(&metadata, AllViewRelations::CanReadAssignments, &object)
         .await?;
-    let assignments = get_relations(authorizer, query.relations, &object).await?;
+    let (assignments, next_page_token) = get_relations(
+        authorizer.clone(),
+        query.relations,
+        query.page_token.as_deref(),
+        &object,
+    )
+    .await?;
+    let display_names = resolve_display_names(&authorizer, &assignments).await;
 
     Ok((
         StatusCode::OK,
-        Json(GetViewAssignmentsResponse { assignments }),
+        Json(GetViewAssignmentsResponse {
+            assignments,
+            display_names,
+            next_page_token,
+        }),
     ))
 }
 
@@ -1032,10 +1308,15 @@ async fn update_project_assignments<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Json(request): Json<UpdateProjectAssignmentsRequest>,
 ) -> Result<StatusCode> {
-    let authorizer = api_context.v1_state.authz;
     let project_id = metadata
         .preferred_project_id()
         .ok_or(OpenFGAError::NoProjectId)?;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_project(project_id)
+        .await?
+        .into_owned();
     checked_write(
         authorizer,
         metadata.actor(),
@@ -1067,7 +1348,12 @@ async fn update_project_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Json(request): Json<UpdateProjectAssignmentsRequest>,
 ) -> Result<StatusCode> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_project(project_id)
+        .await?
+        .into_owned();
     checked_write(
         authorizer,
         metadata.actor(),
@@ -1099,7 +1385,12 @@ async fn update_warehouse_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Json(request): Json<UpdateWarehouseAssignmentsRequest>,
 ) -> Result<StatusCode> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Warehouse(warehouse_id))
+        .await?
+        .into_owned();
     checked_write(
         authorizer,
         metadata.actor(),
@@ -1131,7 +1422,12 @@ async fn update_namespace_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Json(request): Json<UpdateNamespaceAssignmentsRequest>,
 ) -> Result<StatusCode> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Namespace(namespace_id))
+        .await?
+        .into_owned();
     checked_write(
         authorizer,
         metadata.actor(),
@@ -1163,7 +1459,12 @@ async fn update_table_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Json(request): Json<UpdateTableAssignmentsRequest>,
 ) -> Result<StatusCode> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Table(table_id))
+        .await?
+        .into_owned();
     checked_write(
         authorizer,
         metadata.actor(),
@@ -1195,7 +1496,12 @@ async fn update_view_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Json(request): Json<UpdateViewAssignmentsRequest>,
 ) -> Result<StatusCode> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::View(view_id))
+        .await?
+        .into_owned();
     checked_write(
         authorizer,
         metadata.actor(),
@@ -1227,7 +1533,12 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
     Extension(metadata): Extension<RequestMetadata>,
     Json(request): Json<UpdateRoleAssignmentsRequest>,
 ) -> Result<StatusCode> {
-    let authorizer = api_context.v1_state.authz;
+    let authorizer = api_context
+        .v1_state
+        .authz
+        .for_object(ProjectScopedObject::Role(role_id))
+        .await?
+        .into_owned();
     // Improve error message of role beeing assigned to itself
     for assignment in &request.writes {
         let assignee = match assignment {
@@ -1387,35 +1698,119 @@ pub(super) fn new_v1_router<C: Catalog, S: SecretStore>(
         .route("/permissions/check", post(check))
 }
 
+/// Display names of the users and roles of `assignments`, if a display name
+/// resolver is configured.
+async fn resolve_display_names<RA: Assignment>(
+    authorizer: &OpenFGAAuthorizer,
+    assignments: &[RA],
+) -> Option<HashMap<String, String>> {
+    let cache = authorizer.display_name_cache.as_ref()?;
+    Some(
+        cache
+            .display_names(
+                assignments
+                    .iter()
+                    .map(|assignment| PrincipalId::from(assignment.user_or_role())),
+            )
+            .await,
+    )
+}
+
+/// Read the assignments of `object`. Relations with more tuples than the
+/// configured page limit are returned partially, together with a token to
+/// continue reading them. When continuing, only relations of the token are read.
 async fn get_relations<RA: Assignment>(
     authorizer: OpenFGAAuthorizer,
     query_relations: Option<Vec<RA::Relation>>,
+    page_token: Option<&str>,
     object: &str,
-) -> Result<Vec<RA>> {
+) -> Result<(Vec<RA>, Option<String>)> {
     let relations = query_relations.unwrap_or_else(|| RA::Relation::iter().collect());
+    let mut continuation_tokens = page_token.map(AssignmentsPageToken::from_str).transpose()?;
+    let relations = relations
+        .into_iter()
+        .filter_map(|relation| {
+            let openfga_relation = relation.to_openfga().to_string();
+            match continuation_tokens.as_mut() {
+                None => Some((relation, openfga_relation, None)),
+                Some(tokens) => tokens
+                    .0
+                    .remove(&openfga_relation)
+                    .map(|token| (relation, openfga_relation, Some(token))),
+            }
+        })
+        .collect::<Vec<_>>();
 
-    let relations = relations.iter().map(|relation| async {
-        authorizer
-            .clone()
-            .read_all(ReadRequestTupleKey {
-                user: String::new(),
-                relation: relation.to_openfga().to_string(),
-                object: object.to_string(),
-            })
-            .await?
-            .into_iter()
-            .filter_map(|t| t.key)
-            .map(|t| RA::try_from_user(&t.user, relation))
-            .collect::<OpenFGAResult<Vec<RA>>>()
-    });
+    let pages = relations
+        .iter()
+        .map(|(relation, openfga_relation, continuation_token)| {
+            let authorizer = &authorizer;
+            async move {
+                let page = authorizer
+                    .read_pages(
+                        ReadRequestTupleKey {
+                            user: String::new(),
+                            relation: openfga_relation.clone(),
+                            object: object.to_string(),
+                        },
+                        continuation_token.clone(),
+                    )
+                    .await?;
+                let assignments = page
+                    .tuples
+                    .into_iter()
+                    .filter_map(|t| t.key)
+                    .map(|t| RA::try_from_user(&t.user, relation))
+                    .collect::<OpenFGAResult<Vec<RA>>>()?;
+                OpenFGAResult::Ok((
+                    assignments,
+                    page.continuation_token
+                        .map(|token| (openfga_relation.clone(), token)),
+                ))
+            }
+        });
 
-    let relations = futures::future::try_join_all(relations)
-        .await?
-        .into_iter()
-        .flatten()
-        .collect();
+    let mut assignments = Vec::new();
+    let mut next_page_token = AssignmentsPageToken::default();
+    for (page, continuation_token) in futures::future::try_join_all(pages).await? {
+        assignments.extend(page);
+        next_page_token.0.extend(continuation_token);
+    }
+
+    Ok((
+        assignments,
+        (!next_page_token.0.is_empty()).then(|| next_page_token.to_string()),
+    ))
+}
 
-    Ok(relations)
+/// Continuation tokens of relations that were not read completely, keyed by relation.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct AssignmentsPageToken(BTreeMap<String, String>);
+
+impl std::fmt::Display for AssignmentsPageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_vec(&self.0).map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", BASE64_URL_SAFE_NO_PAD.encode(json))
+    }
+}
+
+impl FromStr for AssignmentsPageToken {
+    type Err = ErrorModel;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        BASE64_URL_SAFE_NO_PAD
+            .decode(s)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .map(Self)
+            .ok_or_else(|| {
+                ErrorModel::bad_request(
+                    "Invalid page token for assignments",
+                    "PaginateTokenParseError",
+                    None,
+                )
+            })
+    }
 }
 
 async fn get_allowed_actions<A: ReducedRelation + IntoEnumIterator>(
@@ -1620,6 +2015,223 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_relations_beyond_max_pages_returns_partial_result() {
+        use std::sync::Arc;
+
+        use openfga_rs::Tuple;
+
+        use super::super::{service_ext::TuplePage, MockClient};
+        use crate::service::authn::UserId;
+
+        let alice = UserId::new_unchecked("oidc", "alice");
+        let bob = UserId::new_unchecked("oidc", "bob");
+        let admin = ServerRelation::Admin.to_openfga().to_string();
+        let tuple = |user: &UserId| Tuple {
+            key: Some(TupleKey {
+                user: user.to_openfga(),
+                relation: admin.clone(),
+                object: OPENFGA_SERVER.to_string(),
+                condition: None,
+            }),
+            timestamp: None,
+        };
+        let (first, second) = (tuple(&alice), tuple(&bob));
+
+        // Admins span more than `max_pages`, operators are empty
+        let mut mock = MockClient::default();
+        mock.expect_read_pages()
+            .returning(move |_, key, max_pages, continuation_token| {
+                assert_eq!(max_pages, 1);
+                let page = match (key.relation == admin, continuation_token.as_deref()) {
+                    (true, None) => TuplePage {
+                        tuples: vec![first.clone()],
+                        continuation_token: Some("admin-page-2".to_string()),
+                    },
+                    (true, Some("admin-page-2")) => TuplePage {
+                        tuples: vec![second.clone()],
+                        continuation_token: None,
+                    },
+                    (false, None) => TuplePage::default(),
+                    (_, token) => panic!("Unexpected read of {key:?} from {token:?}"),
+                };
+                Ok(page)
+            });
+        let authorizer = OpenFGAAuthorizer {
+            client: Arc::new(mock),
+            store_id: "test_store".to_string(),
+            authorization_model_id: "test_model".to_string(),
+            health: Arc::default(),
+            check_timeout: crate::CONFIG.backend_timeouts.authz_check,
+            max_pages: 1,
+            relation_mapping: Arc::default(),
+            group_cache: None,
+            display_name_cache: None,
+            request_check_cache: true,
+            emit_metrics: true,
+            project_stores: None,
+            project_resolver: None,
+        };
+
+        let (assignments, next_page_token): (Vec<ServerAssignment>, _) =
+            get_relations(authorizer.clone(), None, None, &OPENFGA_SERVER)
+                .await
+                .unwrap();
+        assert_eq!(assignments, vec![ServerAssignment::Admin(alice.into())]);
+        let next_page_token = next_page_token.expect("Partial result must have a page token");
+
+        // Only the relation with remaining pages is read again
+        let (assignments, next_page_token): (Vec<ServerAssignment>, _) = get_relations(
+            authorizer.clone(),
+            None,
+            Some(&next_page_token),
+            &OPENFGA_SERVER,
+        )
+        .await
+        .unwrap();
+        assert_eq!(assignments, vec![ServerAssignment::Admin(bob.into())]);
+        assert!(next_page_token.is_none());
+
+        let err = get_relations::<ServerAssignment>(authorizer, None, Some("foo"), &OPENFGA_SERVER)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::BAD_REQUEST.as_u16());
+    }
+
+    #[tokio::test]
+    async fn test_assignments_include_display_names_of_resolver() {
+        use std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            time::Duration,
+        };
+
+        use openfga_rs::Tuple;
+
+        use super::super::{service_ext::TuplePage, MockClient};
+        use crate::service::{authn::UserId, authz::display_names::DisplayNameResolver};
+
+        #[derive(Debug, Default)]
+        struct Directory {
+            calls: AtomicUsize,
+        }
+
+        #[async_trait::async_trait]
+        impl DisplayNameResolver for Directory {
+            async fn resolve_display_name(
+                &self,
+                principal: &PrincipalId,
+            ) -> Result<Option<String>> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                match principal {
+                    PrincipalId::User(user) if user.to_string() == "oidc~alice" => {
+                        Ok(Some("Alice Liddell".to_string()))
+                    }
+                    PrincipalId::User(_) => Ok(None),
+                    PrincipalId::Role(_) => Err(ErrorModel::internal(
+                        "Directory unavailable",
+                        "DirectoryUnavailable",
+                        None,
+                    )
+                    .into()),
+                }
+            }
+        }
+
+        let alice = UserId::new_unchecked("oidc", "alice");
+        let bob = UserId::new_unchecked("oidc", "bob");
+        let role = RoleId::new(uuid::Uuid::now_v7());
+        let admin = ServerRelation::Admin.to_openfga().to_string();
+        let tuples = [
+            alice.to_openfga(),
+            bob.to_openfga(),
+            role.into_assignees().to_openfga(),
+        ]
+        .map(|user| Tuple {
+            key: Some(TupleKey {
+                user,
+                relation: admin.clone(),
+                object: OPENFGA_SERVER.to_string(),
+                condition: None,
+            }),
+            timestamp: None,
+        });
+
+        let mut mock = MockClient::default();
+        mock.expect_read_pages().returning(move |_, key, _, _| {
+            Ok(TuplePage {
+                tuples: if key.relation == admin {
+                    tuples.to_vec()
+                } else {
+                    vec![]
+                },
+                continuation_token: None,
+            })
+        });
+        let authorizer = OpenFGAAuthorizer {
+            client: Arc::new(mock),
+            store_id: "test_store".to_string(),
+            authorization_model_id: "test_model".to_string(),
+            health: Arc::default(),
+            check_timeout: crate::CONFIG.backend_timeouts.authz_check,
+            max_pages: 1,
+            relation_mapping: Arc::default(),
+            group_cache: None,
+            display_name_cache: None,
+            request_check_cache: true,
+            emit_metrics: true,
+            project_stores: None,
+            project_resolver: None,
+        };
+        let (assignments, _): (Vec<ServerAssignment>, _) =
+            get_relations(authorizer.clone(), None, None, &OPENFGA_SERVER)
+                .await
+                .unwrap();
+        assert_eq!(assignments.len(), 3);
+
+        // Without a resolver, only the raw ids are listed
+        let response = GetServerAssignmentsResponse {
+            display_names: resolve_display_names(&authorizer, &assignments).await,
+            assignments: assignments.clone(),
+            next_page_token: None,
+        };
+        assert!(response.display_names.is_none());
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("display-names").is_none());
+
+        let directory = Arc::new(Directory::default());
+        let authorizer =
+            authorizer.with_display_name_resolver(directory.clone(), Duration::from_secs(60));
+        let display_names = resolve_display_names(&authorizer, &assignments)
+            .await
+            .unwrap();
+        assert_eq!(
+            display_names,
+            HashMap::from([
+                ("oidc~alice".to_string(), "Alice Liddell".to_string()),
+                // Unknown principals and failed resolutions fall back to the id
+                ("oidc~bob".to_string(), "oidc~bob".to_string()),
+                (role.to_string(), role.to_string()),
+            ])
+        );
+        let json = serde_json::to_value(GetServerAssignmentsResponse {
+            assignments: assignments.clone(),
+            display_names: Some(display_names),
+            next_page_token: None,
+        })
+        .unwrap();
+        assert_eq!(json["display-names"]["oidc~alice"], "Alice Liddell");
+
+        // Resolved names are cached, failed resolutions are retried
+        assert_eq!(directory.calls.load(Ordering::SeqCst), 3);
+        resolve_display_names(&authorizer, &assignments)
+            .await
+            .unwrap();
+        assert_eq!(directory.calls.load(Ordering::SeqCst), 4);
+    }
+
     #[needs_env_var(TEST_OPENFGA = 1)]
     mod openfga {
         use openfga_rs::TupleKey;
@@ -1666,8 +2278,8 @@ mod tests {
         async fn test_get_relations() {
             let (_, authorizer) = authorizer_for_empty_store().await;
 
-            let relations: Vec<ServerAssignment> =
-                get_relations(authorizer.clone(), None, &OPENFGA_SERVER)
+            let (relations, _): (Vec<ServerAssignment>, _) =
+                get_relations(authorizer.clone(), None, None, &OPENFGA_SERVER)
                     .await
                     .unwrap();
             assert!(relations.is_empty());
@@ -1686,8 +2298,8 @@ mod tests {
                 .await
                 .unwrap();
 
-            let relations: Vec<ServerAssignment> =
-                get_relations(authorizer.clone(), None, &OPENFGA_SERVER)
+            let (relations, _): (Vec<ServerAssignment>, _) =
+                get_relations(authorizer.clone(), None, None, &OPENFGA_SERVER)
                     .await
                     .unwrap();
             assert_eq!(relations.len(), 1);
@@ -1879,8 +2491,8 @@ mod tests {
             .await
             .unwrap();
 
-            let relations: Vec<ServerAssignment> =
-                get_relations(authorizer.clone(), None, &OPENFGA_SERVER)
+            let (relations, _): (Vec<ServerAssignment>, _) =
+                get_relations(authorizer.clone(), None, None, &OPENFGA_SERVER)
                     .await
                     .unwrap();
             assert_eq!(relations.len(), 2);
@@ -1920,8 +2532,8 @@ mod tests {
             .await
             .unwrap();
 
-            let relations: Vec<RoleAssignment> =
-                get_relations(authorizer.clone(), None, &role_id_1.to_openfga())
+            let (relations, _): (Vec<RoleAssignment>, _) =
+                get_relations(authorizer.clone(), None, None, &role_id_1.to_openfga())
                     .await
                     .unwrap();
             assert_eq!(relations.len(), 3);
@@ -1963,8 +2575,8 @@ mod tests {
             .await
             .unwrap();
 
-            let relations: Vec<ProjectAssignment> =
-                get_relations(authorizer.clone(), None, &project_id.to_openfga())
+            let (relations, _): (Vec<ProjectAssignment>, _) =
+                get_relations(authorizer.clone(), None, None, &project_id.to_openfga())
                     .await
                     .unwrap();
             assert_eq!(relations.len(), 4);
@@ -1,6 +1,7 @@
-use axum::{extract::State as AxumState, Extension, Json};
+use axum::{extract::State as AxumState, Extension};
 use http::StatusCode;
 use iceberg::{NamespaceIdent, TableIdent};
+use iceberg_ext::catalog::rest::encoding::Json;
 use openfga_rs::CheckRequestTupleKey;
 use serde::{Deserialize, Serialize};
 
@@ -13,7 +14,7 @@ use super::{
         ReducedRelation, ServerRelation as AllServerAction, TableRelation as AllTableRelations,
         UserOrRole, ViewRelation as AllViewRelations, WarehouseRelation as AllWarehouseRelation,
     },
-    OpenFGAAuthorizer, OpenFGAError, OPENFGA_SERVER,
+    OpenFGAAuthorizer, OpenFGAError, ProjectScopedObject, OPENFGA_SERVER,
 };
 use crate::{
     api::ApiContext,
@@ -54,13 +55,57 @@ async fn check_internal<C: Catalog, S: SecretStore>(
     metadata: &RequestMetadata,
     request: CheckRequest,
 ) -> Result<bool> {
-    let authorizer = api_context.v1_state.authz.clone();
     let CheckRequest {
         // If for_principal is specified, the user needs to have the
         // CanReadAssignments relation
         identity: mut for_principal,
         operation: action_request,
     } = request;
+    // Select the store holding the relations of the checked object
+    let object = match &action_request {
+        CheckOperation::Server { .. } => None,
+        CheckOperation::Project { project_id, .. } => Some(ProjectScopedObject::Project(
+            project_id
+                .or(metadata.preferred_project_id())
+                .ok_or(OpenFGAError::NoProjectId)?,
+        )),
+        CheckOperation::Warehouse { warehouse_id, .. } => {
+            Some(ProjectScopedObject::Warehouse(*warehouse_id))
+        }
+        CheckOperation::Namespace { namespace, .. } => Some(match namespace {
+            NamespaceIdentOrUuid::Id { namespace_id } => {
+                ProjectScopedObject::Namespace(*namespace_id)
+            }
+            NamespaceIdentOrUuid::Name { warehouse_id, .. } => {
+                ProjectScopedObject::Warehouse(*warehouse_id)
+            }
+        }),
+        CheckOperation::Table { table, .. } => Some(match table {
+            TabularIdentOrUuid::Id { table_id } => {
+                ProjectScopedObject::Table(TableIdentUuid::from(*table_id))
+            }
+            TabularIdentOrUuid::Name { warehouse_id, .. } => {
+                ProjectScopedObject::Warehouse(*warehouse_id)
+            }
+        }),
+        CheckOperation::View { view, .. } => Some(match view {
+            TabularIdentOrUuid::Id { table_id } => {
+                ProjectScopedObject::View(ViewIdentUuid::from(*table_id))
+            }
+            TabularIdentOrUuid::Name { warehouse_id, .. } => {
+                ProjectScopedObject::Warehouse(*warehouse_id)
+            }
+        }),
+    };
+    let authorizer = match object {
+        Some(object) => api_context
+            .v1_state
+            .authz
+            .for_object(object)
+            .await?
+            .into_owned(),
+        None => api_context.v1_state.authz.clone(),
+    };
     // Set for_principal to None if the user is checking their own access
     let user_or_role = metadata.actor().to_user_or_role();
     if let Some(user_or_role) = &user_or_role {
@@ -3,6 +3,7 @@
 use std::{
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use http::Request;
@@ -13,16 +14,24 @@ use openfga_rs::{
         body::BoxBody,
         transport::{Channel, Endpoint},
     },
+    ReadAuthorizationModelRequest,
 };
 use tokio::sync::RwLock;
 use tower::ServiceBuilder;
 
 use super::{
-    ClientHelper as _, ModelVersion, OpenFGAAuthorizer, OpenFGAError, OpenFGAResult, AUTH_CONFIG,
+    ClientHelper as _, ModelVersion, OpenFGAAuthorizer, OpenFGAError, OpenFGAResult, ProjectStores,
+    RelationMapping, StoreProvisioner, AUTH_CONFIG,
 };
 use crate::{
-    service::authz::implementations::{openfga::migration::get_auth_model_id, Authorizers},
-    OpenFGAAuth,
+    service::authz::{
+        groups::HttpGroupResolver,
+        implementations::{
+            openfga::migration::{get_auth_model_id, migrate},
+            Authorizers,
+        },
+    },
+    OpenFGAAuth, CONFIG,
 };
 
 pub type UnauthenticatedOpenFGAAuthorizer = OpenFGAAuthorizer;
@@ -66,14 +75,30 @@ pub(crate) async fn new_client_from_config(
 }
 
 /// Create a new `OpenFGA` authorizer from the configuration.
+/// If bootstrapping is enabled, the store is created and the active
+/// authorization model is published first if they are missing.
 ///
 /// # Errors
 /// - Server connection fails
+/// - Bootstrapping fails
 /// - Store (name) not found (from crate Config)
 /// - Active Authorization model not found
+/// - Relation mapping refers to relations not in the active Authorization model
 pub async fn new_authorizer_from_config() -> OpenFGAResult<Authorizers> {
-    let client = new_client_from_config().await?;
-    Ok(Authorizers::OpenFGA(new_authorizer(client, None).await?))
+    let mut client = new_client_from_config().await?;
+    if AUTH_CONFIG.bootstrap {
+        tracing::info!("Bootstrapping OpenFGA store {}", AUTH_CONFIG.store_name);
+        migrate(&mut client, None).await?;
+    }
+    let authorizer = new_authorizer(client, None).await?;
+    let authorizer = match &AUTH_CONFIG.group_resolver_url {
+        Some(url) => authorizer.with_group_resolver(
+            Arc::new(HttpGroupResolver { url: url.clone() }),
+            Duration::from_secs(AUTH_CONFIG.group_cache_ttl_seconds),
+        ),
+        None => authorizer,
+    };
+    Ok(Authorizers::OpenFGA(authorizer))
 }
 
 /// Create a new `OpenFGA` authorizer with the given client.
@@ -88,16 +113,50 @@ pub(crate) async fn new_authorizer(
     let store_id = client
         .get_store_by_name(&store_name)
         .await?
-        .ok_or(OpenFGAError::StoreNotFound { store: store_name })?
+        .ok_or_else(|| OpenFGAError::StoreNotFound {
+            store: store_name.clone(),
+        })?
         .id;
     let authorization_model_id =
         get_auth_model_id(&mut client, store_id.clone(), active_model_version).await?;
+    let relation_mapping = if AUTH_CONFIG.relation_mapping.is_empty() {
+        RelationMapping::default()
+    } else {
+        let type_definitions = client
+            .read_authorization_model(ReadAuthorizationModelRequest {
+                store_id: store_id.clone(),
+                id: authorization_model_id.clone(),
+            })
+            .await
+            .map_err(OpenFGAError::read_authorization_model)?
+            .into_inner()
+            .authorization_model
+            .map(|model| model.type_definitions)
+            .unwrap_or_default();
+        RelationMapping::try_new(&AUTH_CONFIG.relation_mapping, &type_definitions)?
+    };
+
+    let project_stores = AUTH_CONFIG.store_per_project.then(|| {
+        Arc::new(ProjectStores::new(
+            Arc::new(client.clone()) as Arc<dyn StoreProvisioner>,
+            store_name.clone(),
+        ))
+    });
 
     Ok(OpenFGAAuthorizer {
         client: Arc::new(client),
         store_id,
         authorization_model_id,
         health: Arc::new(RwLock::new(vec![])),
+        check_timeout: CONFIG.backend_timeouts.authz_check,
+        max_pages: AUTH_CONFIG.max_pages,
+        relation_mapping: Arc::new(relation_mapping),
+        group_cache: None,
+        display_name_cache: None,
+        request_check_cache: AUTH_CONFIG.request_check_cache,
+        emit_metrics: AUTH_CONFIG.metrics,
+        project_stores,
+        project_resolver: None,
     })
 }
 
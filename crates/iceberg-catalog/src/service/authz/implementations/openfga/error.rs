@@ -2,10 +2,10 @@ use http::StatusCode;
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
 use openfga_rs::{
     tonic::{self, metadata::errors::InvalidMetadataValue, Code},
-    CheckRequest, ReadRequest, ReadRequestTupleKey, WriteRequest,
+    CheckRequest, ReadRequest, WriteRequest,
 };
 
-use crate::service::authz::implementations::FgaType;
+use crate::service::{authz::implementations::FgaType, backend_timeout::BackendTimeoutError};
 
 pub type OpenFGAResult<T> = Result<T, OpenFGAError>;
 
@@ -37,15 +37,12 @@ pub enum OpenFGAError {
     },
     #[error("Store creation failed: {0}")]
     StoreCreationFailed(tonic::Status),
-    #[error("Store {store} not found. Please ensure to run migration first.")]
+    #[error(
+        "Store {store} not found. Please ensure to run migration first or enable bootstrapping."
+    )]
     StoreNotFound { store: String },
     #[error("Too many authorization models in database. Max allowed pages: {0}")]
     TooManyAuthorizationModels(u32),
-    #[error("Too many pages")]
-    TooManyPages {
-        max_pages: u32,
-        tuple: ReadRequestTupleKey,
-    },
     #[error("Authentication to Authorization system failed")]
     Unauthenticated(tonic::Status),
     #[error("Unexpected entity for type {type:?}: {value}")]
@@ -54,7 +51,11 @@ pub enum OpenFGAError {
     UnknownType(String),
     #[error("Invalid entity string: `{0}`")]
     InvalidEntity(String),
-    #[error("Unknown model version currently applied")]
+    #[error("Invalid relation mapping: {0}")]
+    InvalidRelationMapping(String),
+    #[error("Reading authorization model failed")]
+    ReadAuthorizationModelFailed(tonic::Status),
+    #[error("Unknown model version {0} currently applied")]
     UnknownModelVersionApplied(u64),
     #[error("Failed to write Authorization model: {0}")]
     WriteAuthorizationModelFailed(tonic::Status),
@@ -67,6 +68,10 @@ pub enum OpenFGAError {
     TooManyWrites { actual: i32, max: i32 },
     #[error("Project ID could not be inferred from request. Please specify it explicitly.")]
     NoProjectId,
+    #[error("Project of `{object}` not found")]
+    ObjectProjectNotFound { object: String },
+    #[error("OpenFGA stores per project require a project resolver")]
+    ProjectResolverMissing,
     #[error("Authentication required")]
     AuthenticationRequired,
     #[error("Unauthorized for action `{relation}` on `{object}` for `{user}`")]
@@ -77,6 +82,10 @@ pub enum OpenFGAError {
     },
     #[error("Cannot assign {0} to itself")]
     SelfAssignment(String),
+    #[error("Resolving the groups of `{user}` failed: {reason}")]
+    GroupResolutionFailed { user: String, reason: String },
+    #[error(transparent)]
+    Timeout(#[from] BackendTimeoutError),
 }
 
 impl OpenFGAError {
@@ -102,6 +111,10 @@ impl OpenFGAError {
         Self::known_status(&status).unwrap_or(OpenFGAError::ListAuthenticationModelsFailed(status))
     }
 
+    pub(crate) fn read_authorization_model(status: tonic::Status) -> Self {
+        Self::known_status(&status).unwrap_or(OpenFGAError::ReadAuthorizationModelFailed(status))
+    }
+
     pub(crate) fn write_authorization_model(status: tonic::Status) -> Self {
         Self::known_status(&status).unwrap_or(OpenFGAError::WriteAuthorizationModelFailed(status))
     }
@@ -110,6 +123,31 @@ impl OpenFGAError {
         OpenFGAError::UnexpectedEntity { r#type, value }
     }
 
+    /// Class of the error in the `result` label of `OpenFGA` call metrics. Requests
+    /// rejected by `OpenFGA`, such as writing an existing tuple, are distinguished
+    /// from timeouts and failures of the backend.
+    pub(crate) fn metric_class(&self) -> &'static str {
+        match self {
+            OpenFGAError::Timeout(_) => "timeout",
+            OpenFGAError::Unauthenticated(_)
+            | OpenFGAError::ClientCredentialFailed(_)
+            | OpenFGAError::InvalidBearerToken(_) => "unauthenticated",
+            _ if self.as_status().is_some_and(|status| {
+                matches!(
+                    status.code(),
+                    Code::InvalidArgument
+                        | Code::NotFound
+                        | Code::AlreadyExists
+                        | Code::FailedPrecondition
+                )
+            }) =>
+            {
+                "rejected"
+            }
+            _ => "error",
+        }
+    }
+
     fn as_status(&self) -> Option<&tonic::Status> {
         match self {
             OpenFGAError::CheckFailed { source, .. }
@@ -118,6 +156,7 @@ impl OpenFGAError {
             OpenFGAError::Unauthenticated(status)
             | OpenFGAError::Internal(status)
             | OpenFGAError::WriteAuthorizationModelFailed(status)
+            | OpenFGAError::ReadAuthorizationModelFailed(status)
             | OpenFGAError::ListStoresFailed(status)
             | OpenFGAError::StoreCreationFailed(status) => Some(status),
             _ => None,
@@ -133,12 +172,34 @@ impl From<OpenFGAError> for ErrorModel {
             e @ OpenFGAError::NoProjectId => {
                 ErrorModel::bad_request(err_msg, "NoProjectId", Some(Box::new(e)))
             }
+            e @ OpenFGAError::ObjectProjectNotFound { .. } => {
+                ErrorModel::not_found(err_msg, "ObjectProjectNotFound", Some(Box::new(e)))
+            }
             e @ OpenFGAError::AuthenticationRequired => {
                 ErrorModel::unauthorized(err_msg, "AuthenticationRequired", Some(Box::new(e)))
             }
             e @ OpenFGAError::Unauthorized { .. } => {
                 ErrorModel::unauthorized(err_msg, "Unauthorized", Some(Box::new(e)))
             }
+            OpenFGAError::Timeout(e) => e.into(),
+            e @ OpenFGAError::StoreNotFound { .. } => ErrorModel::new(
+                err_msg,
+                "StoreNotFound",
+                StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+                Some(Box::new(e)),
+            ),
+            e @ OpenFGAError::UnknownModelVersionApplied(_) => ErrorModel::new(
+                err_msg,
+                "UnknownModelVersionApplied",
+                StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                Some(Box::new(e)),
+            ),
+            e @ OpenFGAError::GroupResolutionFailed { .. } => ErrorModel::new(
+                err_msg,
+                "GroupResolutionFailed",
+                StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+                Some(Box::new(e)),
+            ),
             e @ OpenFGAError::WriteFailed { .. } => {
                 if status_msg
                     .as_deref()
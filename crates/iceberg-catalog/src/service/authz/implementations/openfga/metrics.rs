@@ -0,0 +1,71 @@
+use std::{future::Future, time::Instant};
+
+use axum_prometheus::metrics;
+
+use super::OpenFGAResult;
+
+const CALLS_METRIC: &str = "lakekeeper_openfga_calls_total";
+const CALL_DURATION_METRIC: &str = "lakekeeper_openfga_call_duration_seconds";
+
+/// Type of a call to `OpenFGA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum OpenFgaCall {
+    Check,
+    Read,
+    Write,
+}
+
+impl OpenFgaCall {
+    fn as_str(self) -> &'static str {
+        match self {
+            OpenFgaCall::Check => "check",
+            OpenFgaCall::Read => "read",
+            OpenFgaCall::Write => "write",
+        }
+    }
+}
+
+/// Outcome of a successful call to `OpenFGA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CallOutcome {
+    Allowed,
+    Denied,
+    Ok,
+}
+
+impl CallOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            CallOutcome::Allowed => "allowed",
+            CallOutcome::Denied => "denied",
+            CallOutcome::Ok => "ok",
+        }
+    }
+}
+
+/// Run `call_fut` and record its latency and outcome if `enabled`.
+///
+/// Metrics are labeled by the type of the call and the class of its result only.
+/// Users, relations and objects are never used as labels, as they would create a
+/// series per principal and object.
+pub(super) async fn observe_call<T>(
+    enabled: bool,
+    call: OpenFgaCall,
+    call_fut: impl Future<Output = OpenFGAResult<T>>,
+    outcome: impl FnOnce(&T) -> CallOutcome,
+) -> OpenFGAResult<T> {
+    if !enabled {
+        return call_fut.await;
+    }
+
+    let started = Instant::now();
+    let result = call_fut.await;
+    let result_class = match &result {
+        Ok(value) => outcome(value).as_str(),
+        Err(e) => e.metric_class(),
+    };
+    metrics::histogram!(CALL_DURATION_METRIC, "call" => call.as_str())
+        .record(started.elapsed().as_secs_f64());
+    metrics::counter!(CALLS_METRIC, "call" => call.as_str(), "result" => result_class).increment(1);
+    result
+}
@@ -333,7 +333,9 @@ pub(crate) mod tests {
     #[needs_env_var(TEST_OPENFGA = 1)]
     mod openfga {
         use super::super::*;
-        use crate::service::authz::implementations::openfga::new_client_from_config;
+        use crate::service::authz::implementations::openfga::{
+            client::new_authorizer, new_client_from_config,
+        };
 
         #[tokio::test]
         async fn test_migrate() {
@@ -370,5 +372,50 @@ pub(crate) mod tests {
                     .unwrap();
             assert_eq!(auth_model_id, auth_model_id_search);
         }
+
+        #[tokio::test]
+        async fn test_bootstrap_empty_store_is_idempotent() {
+            let mut client = new_client_from_config().await.unwrap();
+            let store_name = format!("test_store_{}", uuid::Uuid::now_v7());
+            assert!(client
+                .get_store_by_name(&store_name)
+                .await
+                .unwrap()
+                .is_none());
+            let err = new_authorizer(client.clone(), Some(store_name.clone()))
+                .await
+                .unwrap_err();
+            assert!(matches!(err, OpenFGAError::StoreNotFound { .. }));
+
+            migrate(&mut client, Some(store_name.clone()))
+                .await
+                .unwrap();
+            let store = client
+                .get_store_by_name(&store_name)
+                .await
+                .unwrap()
+                .unwrap();
+            let authorizer = new_authorizer(client.clone(), Some(store_name.clone()))
+                .await
+                .unwrap();
+
+            // A second bootstrap neither creates a store nor publishes a model
+            migrate(&mut client, Some(store_name.clone()))
+                .await
+                .unwrap();
+            let store_after = client
+                .get_store_by_name(&store_name)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(store_after.id, store.id);
+            let authz_models = client.get_all_auth_models(store.id.clone()).await.unwrap();
+            assert_eq!(authz_models.len(), 1);
+            let auth_model_id =
+                get_auth_model_id(&mut client, store.id.clone(), ModelVersion::active())
+                    .await
+                    .unwrap();
+            assert_eq!(auth_model_id, authorizer.authorization_model_id);
+        }
     }
 }
@@ -1,8 +1,10 @@
 use std::{
+    borrow::Cow,
     collections::HashSet,
     fmt,
     fmt::{Debug, Formatter},
     sync::Arc,
+    time::Duration,
 };
 
 use async_stream::{__private::AsyncStream, stream};
@@ -14,9 +16,10 @@ use openfga_rs::{
     tonic::{
         Response, Status, {self},
     },
-    CheckRequest, CheckRequestTupleKey, CheckResponse, ConsistencyPreference, ListObjectsRequest,
-    ListObjectsResponse, ReadRequest, ReadRequestTupleKey, ReadResponse, Tuple, TupleKey,
-    TupleKeyWithoutCondition, WriteRequest, WriteRequestDeletes, WriteRequestWrites, WriteResponse,
+    CheckRequest, CheckRequestTupleKey, CheckResponse, ConsistencyPreference, ContextualTupleKeys,
+    ListObjectsRequest, ListObjectsResponse, ReadRequest, ReadRequestTupleKey, ReadResponse,
+    TupleKey, TupleKeyWithoutCondition, WriteRequest, WriteRequestDeletes, WriteRequestWrites,
+    WriteResponse,
 };
 
 use crate::{
@@ -24,10 +27,13 @@ use crate::{
     service::{
         authn::Actor,
         authz::{
-            Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogServerAction,
-            CatalogTableAction, CatalogViewAction, CatalogWarehouseAction, ErrorModel,
-            ListProjectsResponse, Result,
+            display_names::{DisplayNameCache, DisplayNameResolver},
+            groups::{GroupCache, GroupResolver},
+            Authorizer, BaselineRelations, CatalogNamespaceAction, CatalogObject,
+            CatalogProjectAction, CatalogServerAction, CatalogTableAction, CatalogViewAction,
+            CatalogWarehouseAction, ErrorModel, ListProjectsResponse, Result,
         },
+        backend_timeout::{with_timeout, Backend},
         NamespaceIdentUuid, TableIdentUuid,
     },
     ProjectId, WarehouseIdent, CONFIG,
@@ -39,8 +45,11 @@ mod client;
 mod entities;
 mod error;
 mod health;
+mod metrics;
 mod migration;
 mod models;
+mod project_stores;
+mod relation_mapping;
 mod relations;
 
 mod service_ext;
@@ -53,14 +62,18 @@ pub use client::{
 use entities::{OpenFgaEntity, ParseOpenFgaEntity as _};
 pub use error::{OpenFGAError, OpenFGAResult};
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
+use metrics::{observe_call, CallOutcome, OpenFgaCall};
 pub(crate) use migration::migrate;
 pub(crate) use models::{ModelVersion, OpenFgaType, RoleAssignee};
+pub use project_stores::{ProjectResolver, ProjectScopedObject};
+pub(crate) use project_stores::{ProjectStore, ProjectStores, StoreProvisioner};
+use relation_mapping::RelationMapping;
 use relations::{
     NamespaceRelation, ProjectRelation, RoleRelation, ServerRelation, TableRelation, ViewRelation,
     WarehouseRelation,
 };
 pub(crate) use service_ext::ClientHelper;
-use service_ext::MAX_TUPLES_PER_WRITE;
+use service_ext::{TuplePage, MAX_TUPLES_PER_WRITE};
 use tokio::sync::RwLock;
 use utoipa::OpenApi;
 
@@ -95,6 +108,22 @@ pub struct OpenFGAAuthorizer {
     pub(crate) store_id: String,
     pub(crate) authorization_model_id: String,
     pub(crate) health: Arc<RwLock<Vec<Health>>>,
+    pub(crate) check_timeout: Duration,
+    /// Maximum number of pages read by listings such as role assignments.
+    pub(crate) max_pages: u32,
+    pub(crate) relation_mapping: Arc<RelationMapping>,
+    pub(crate) group_cache: Option<Arc<GroupCache>>,
+    /// Resolves display names of principals in assignment listings.
+    pub(crate) display_name_cache: Option<Arc<DisplayNameCache>>,
+    /// Remember check decisions for the duration of a request.
+    pub(crate) request_check_cache: bool,
+    /// Record the latency and outcome of calls to `OpenFGA`.
+    pub(crate) emit_metrics: bool,
+    /// Stores of projects if each project uses its own store.
+    /// `store_id` and `authorization_model_id` then refer to the store of server-level relations.
+    pub(crate) project_stores: Option<Arc<ProjectStores>>,
+    /// Looks up the project of objects to select their store.
+    pub(crate) project_resolver: Option<Arc<dyn ProjectResolver>>,
 }
 
 impl Debug for OpenFGAAuthorizer {
@@ -103,6 +132,14 @@ impl Debug for OpenFGAAuthorizer {
             .field("store_id", &self.store_id)
             .field("authorization_model_id", &self.authorization_model_id)
             .field("health", &self.health)
+            .field("check_timeout", &self.check_timeout)
+            .field("relation_mapping", &self.relation_mapping)
+            .field("group_cache", &self.group_cache)
+            .field("display_name_cache", &self.display_name_cache)
+            .field("request_check_cache", &self.request_check_cache)
+            .field("emit_metrics", &self.emit_metrics)
+            .field("project_stores", &self.project_stores)
+            .field("project_resolver", &self.project_resolver)
             .field("client", &"...")
             .finish()
     }
@@ -128,13 +165,20 @@ impl Authorizer for OpenFGAAuthorizer {
                 principal,
                 assumed_role,
             } => {
-                let assume_role_allowed = self
-                    .check(CheckRequestTupleKey {
-                        user: Actor::Principal(principal.clone()).to_openfga(),
-                        relation: relations::RoleRelation::CanAssume.to_string(),
-                        object: assumed_role.to_openfga(),
-                    })
-                    .await?;
+                // The project of the role is not known here, so all stores are checked.
+                let mut assume_role_allowed = false;
+                for authorizer in self.all_stores().await? {
+                    assume_role_allowed = authorizer
+                        .check(CheckRequestTupleKey {
+                            user: Actor::Principal(principal.clone()).to_openfga(),
+                            relation: relations::RoleRelation::CanAssume.to_string(),
+                            object: assumed_role.to_openfga(),
+                        })
+                        .await?;
+                    if assume_role_allowed {
+                        break;
+                    }
+                }
 
                 if assume_role_allowed {
                     Ok(())
@@ -204,8 +248,9 @@ impl Authorizer for OpenFGAAuthorizer {
     }
 
     async fn list_projects(&self, metadata: &RequestMetadata) -> Result<ListProjectsResponse> {
-        let actor = metadata.actor();
-        self.list_projects_internal(actor).await
+        let contextual_tuples = self.group_tuples(metadata).await?;
+        self.list_projects_internal(metadata.actor(), contextual_tuples)
+            .await
     }
 
     async fn can_search_users(&self, metadata: &RequestMetadata) -> Result<bool> {
@@ -219,13 +264,11 @@ impl Authorizer for OpenFGAAuthorizer {
         role_id: RoleId,
         action: &CatalogRoleAction,
     ) -> Result<bool> {
-        self.check(CheckRequestTupleKey {
-            user: metadata.actor().to_openfga(),
-            relation: action.to_string(),
-            object: role_id.to_openfga(),
-        })
-        .await
-        .map_err(Into::into)
+        self.for_object(ProjectScopedObject::Role(role_id))
+            .await?
+            .check_action(metadata, action.to_string(), role_id.to_openfga())
+            .await
+            .map_err(Into::into)
     }
 
     async fn is_allowed_user_action(
@@ -259,19 +302,19 @@ impl Authorizer for OpenFGAAuthorizer {
             // For multi-tenant setups, we need to restrict this to a tenant.
             CatalogUserAction::CanRead => Ok(true),
             CatalogUserAction::CanUpdate => {
-                self.check(CheckRequestTupleKey {
-                    user: actor.to_openfga(),
-                    relation: CatalogServerAction::CanUpdateUsers.to_string(),
-                    object: server_id,
-                })
+                self.check_action(
+                    metadata,
+                    CatalogServerAction::CanUpdateUsers.to_string(),
+                    server_id,
+                )
                 .await
             }
             CatalogUserAction::CanDelete => {
-                self.check(CheckRequestTupleKey {
-                    user: actor.to_openfga(),
-                    relation: CatalogServerAction::CanDeleteUsers.to_string(),
-                    object: server_id,
-                })
+                self.check_action(
+                    metadata,
+                    CatalogServerAction::CanDeleteUsers.to_string(),
+                    server_id,
+                )
                 .await
             }
         }
@@ -283,13 +326,9 @@ impl Authorizer for OpenFGAAuthorizer {
         metadata: &RequestMetadata,
         action: &CatalogServerAction,
     ) -> Result<bool> {
-        self.check(CheckRequestTupleKey {
-            user: metadata.actor().to_openfga(),
-            relation: action.to_string(),
-            object: OPENFGA_SERVER.clone(),
-        })
-        .await
-        .map_err(Into::into)
+        self.check_action(metadata, action.to_string(), OPENFGA_SERVER.clone())
+            .await
+            .map_err(Into::into)
     }
 
     async fn is_allowed_project_action(
@@ -298,13 +337,11 @@ impl Authorizer for OpenFGAAuthorizer {
         project_id: ProjectId,
         action: &CatalogProjectAction,
     ) -> Result<bool> {
-        self.check(CheckRequestTupleKey {
-            user: metadata.actor().to_openfga(),
-            relation: action.to_string(),
-            object: project_id.to_openfga(),
-        })
-        .await
-        .map_err(Into::into)
+        self.for_project(project_id)
+            .await?
+            .check_action(metadata, action.to_string(), project_id.to_openfga())
+            .await
+            .map_err(Into::into)
     }
 
     async fn is_allowed_warehouse_action(
@@ -313,13 +350,11 @@ impl Authorizer for OpenFGAAuthorizer {
         warehouse_id: WarehouseIdent,
         action: &CatalogWarehouseAction,
     ) -> Result<bool> {
-        self.check(CheckRequestTupleKey {
-            user: metadata.actor().to_openfga(),
-            relation: action.to_string(),
-            object: warehouse_id.to_openfga(),
-        })
-        .await
-        .map_err(Into::into)
+        self.for_object(ProjectScopedObject::Warehouse(warehouse_id))
+            .await?
+            .check_action(metadata, action.to_string(), warehouse_id.to_openfga())
+            .await
+            .map_err(Into::into)
     }
 
     async fn is_allowed_namespace_action(
@@ -328,13 +363,11 @@ impl Authorizer for OpenFGAAuthorizer {
         namespace_id: NamespaceIdentUuid,
         action: impl From<&CatalogNamespaceAction> + std::fmt::Display + Send,
     ) -> Result<bool> {
-        self.check(CheckRequestTupleKey {
-            user: metadata.actor().to_openfga(),
-            relation: action.to_string(),
-            object: namespace_id.to_openfga(),
-        })
-        .await
-        .map_err(Into::into)
+        self.for_object(ProjectScopedObject::Namespace(namespace_id))
+            .await?
+            .check_action(metadata, action.to_string(), namespace_id.to_openfga())
+            .await
+            .map_err(Into::into)
     }
 
     async fn is_allowed_table_action(
@@ -343,13 +376,11 @@ impl Authorizer for OpenFGAAuthorizer {
         table_id: TableIdentUuid,
         action: impl From<&CatalogTableAction> + std::fmt::Display + Send,
     ) -> Result<bool> {
-        self.check(CheckRequestTupleKey {
-            user: metadata.actor().to_openfga(),
-            relation: action.to_string(),
-            object: table_id.to_openfga(),
-        })
-        .await
-        .map_err(Into::into)
+        self.for_object(ProjectScopedObject::Table(table_id))
+            .await?
+            .check_action(metadata, action.to_string(), table_id.to_openfga())
+            .await
+            .map_err(Into::into)
     }
 
     async fn is_allowed_view_action(
@@ -358,13 +389,11 @@ impl Authorizer for OpenFGAAuthorizer {
         view_id: ViewIdentUuid,
         action: impl From<&CatalogViewAction> + std::fmt::Display + Send,
     ) -> Result<bool> {
-        self.check(CheckRequestTupleKey {
-            user: metadata.actor().to_openfga(),
-            relation: action.to_string(),
-            object: view_id.to_openfga(),
-        })
-        .await
-        .map_err(Into::into)
+        self.for_object(ProjectScopedObject::View(view_id))
+            .await?
+            .check_action(metadata, action.to_string(), view_id.to_openfga())
+            .await
+            .map_err(Into::into)
     }
 
     async fn delete_user(&self, _metadata: &RequestMetadata, user_id: UserId) -> Result<()> {
@@ -377,31 +406,34 @@ impl Authorizer for OpenFGAAuthorizer {
         role_id: RoleId,
         parent_project_id: ProjectId,
     ) -> Result<()> {
+        let authorizer = self.for_project(parent_project_id).await?;
         let actor = metadata.actor();
 
-        self.require_no_relations(&role_id, ConsistencyPreference::MinimizeLatency)
+        authorizer
+            .require_no_relations(&role_id, ConsistencyPreference::MinimizeLatency)
             .await?;
         let parent_id = parent_project_id.to_openfga();
         let this_id = role_id.to_openfga();
-        self.write(
-            Some(vec![
-                TupleKey {
-                    user: actor.to_openfga(),
-                    relation: RoleRelation::Ownership.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: parent_id.clone(),
-                    relation: RoleRelation::Project.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-            ]),
-            None,
-        )
-        .await
-        .map_err(Into::into)
+        authorizer
+            .write(
+                Some(vec![
+                    TupleKey {
+                        user: actor.to_openfga(),
+                        relation: RoleRelation::Ownership.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: parent_id.clone(),
+                        relation: RoleRelation::Project.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                ]),
+                None,
+            )
+            .await
+            .map_err(Into::into)
     }
 
     async fn delete_role(&self, _metadata: &RequestMetadata, role_id: RoleId) -> Result<()> {
@@ -413,37 +445,40 @@ impl Authorizer for OpenFGAAuthorizer {
         metadata: &RequestMetadata,
         project_id: ProjectId,
     ) -> Result<()> {
+        let authorizer = self.for_project(project_id).await?;
         let actor = metadata.actor();
 
-        self.require_no_relations(&project_id, ConsistencyPreference::MinimizeLatency)
+        authorizer
+            .require_no_relations(&project_id, ConsistencyPreference::MinimizeLatency)
             .await?;
         let server = OPENFGA_SERVER.clone();
         let this_id = project_id.to_openfga();
-        self.write(
-            Some(vec![
-                TupleKey {
-                    user: actor.to_openfga(),
-                    relation: ProjectRelation::ProjectAdmin.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: server.clone(),
-                    relation: ProjectRelation::Server.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: this_id,
-                    relation: ServerRelation::Project.to_string(),
-                    object: server,
-                    condition: None,
-                },
-            ]),
-            None,
-        )
-        .await
-        .map_err(Into::into)
+        authorizer
+            .write(
+                Some(vec![
+                    TupleKey {
+                        user: actor.to_openfga(),
+                        relation: ProjectRelation::ProjectAdmin.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: server.clone(),
+                        relation: ProjectRelation::Server.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: this_id,
+                        relation: ServerRelation::Project.to_string(),
+                        object: server,
+                        condition: None,
+                    },
+                ]),
+                None,
+            )
+            .await
+            .map_err(Into::into)
     }
 
     async fn delete_project(
@@ -460,37 +495,40 @@ impl Authorizer for OpenFGAAuthorizer {
         warehouse_id: WarehouseIdent,
         parent_project_id: ProjectId,
     ) -> Result<()> {
+        let authorizer = self.for_project(parent_project_id).await?;
         let actor = metadata.actor();
 
-        self.require_no_relations(&warehouse_id, ConsistencyPreference::MinimizeLatency)
+        authorizer
+            .require_no_relations(&warehouse_id, ConsistencyPreference::MinimizeLatency)
             .await?;
         let project_id = parent_project_id.to_openfga();
         let this_id = warehouse_id.to_openfga();
-        self.write(
-            Some(vec![
-                TupleKey {
-                    user: actor.to_openfga(),
-                    relation: WarehouseRelation::Ownership.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: project_id.clone(),
-                    relation: WarehouseRelation::Project.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: this_id.clone(),
-                    relation: ProjectRelation::Warehouse.to_string(),
-                    object: project_id.clone(),
-                    condition: None,
-                },
-            ]),
-            None,
-        )
-        .await
-        .map_err(Into::into)
+        authorizer
+            .write(
+                Some(vec![
+                    TupleKey {
+                        user: actor.to_openfga(),
+                        relation: WarehouseRelation::Ownership.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: project_id.clone(),
+                        relation: WarehouseRelation::Project.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: this_id.clone(),
+                        relation: ProjectRelation::Warehouse.to_string(),
+                        object: project_id.clone(),
+                        condition: None,
+                    },
+                ]),
+                None,
+            )
+            .await
+            .map_err(Into::into)
     }
 
     async fn delete_warehouse(
@@ -507,9 +545,18 @@ impl Authorizer for OpenFGAAuthorizer {
         namespace_id: NamespaceIdentUuid,
         parent: NamespaceParent,
     ) -> Result<()> {
+        let authorizer = self
+            .for_object(match parent {
+                NamespaceParent::Warehouse(warehouse_id) => {
+                    ProjectScopedObject::Warehouse(warehouse_id)
+                }
+                NamespaceParent::Namespace(parent_id) => ProjectScopedObject::Namespace(parent_id),
+            })
+            .await?;
         let actor = metadata.actor();
 
-        self.require_no_relations(&namespace_id, ConsistencyPreference::MinimizeLatency)
+        authorizer
+            .require_no_relations(&namespace_id, ConsistencyPreference::MinimizeLatency)
             .await?;
 
         let (parent_id, parent_child_relation) = match parent {
@@ -524,31 +571,32 @@ impl Authorizer for OpenFGAAuthorizer {
         };
         let this_id = namespace_id.to_openfga();
 
-        self.write(
-            Some(vec![
-                TupleKey {
-                    user: actor.to_openfga(),
-                    relation: NamespaceRelation::Ownership.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: parent_id.clone(),
-                    relation: NamespaceRelation::Parent.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: this_id.clone(),
-                    relation: parent_child_relation,
-                    object: parent_id.clone(),
-                    condition: None,
-                },
-            ]),
-            None,
-        )
-        .await
-        .map_err(Into::into)
+        authorizer
+            .write(
+                Some(vec![
+                    TupleKey {
+                        user: actor.to_openfga(),
+                        relation: NamespaceRelation::Ownership.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: parent_id.clone(),
+                        relation: NamespaceRelation::Parent.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: this_id.clone(),
+                        relation: parent_child_relation,
+                        object: parent_id.clone(),
+                        condition: None,
+                    },
+                ]),
+                None,
+            )
+            .await
+            .map_err(Into::into)
     }
 
     async fn delete_namespace(
@@ -559,46 +607,83 @@ impl Authorizer for OpenFGAAuthorizer {
         self.delete_all_relations(&namespace_id).await
     }
 
+    async fn move_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        namespace_id: NamespaceIdentUuid,
+        previous_parent: NamespaceParent,
+        new_parent: NamespaceParent,
+    ) -> Result<()> {
+        let authorizer = self
+            .for_object(ProjectScopedObject::Namespace(namespace_id))
+            .await?;
+        let deletes = baseline_tuples(CatalogObject::Namespace {
+            namespace_id,
+            parent: previous_parent,
+        })
+        .into_iter()
+        .map(|t| TupleKeyWithoutCondition {
+            user: t.user,
+            relation: t.relation,
+            object: t.object,
+        })
+        .collect();
+        let writes = baseline_tuples(CatalogObject::Namespace {
+            namespace_id,
+            parent: new_parent,
+        });
+
+        authorizer
+            .write(Some(writes), Some(deletes))
+            .await
+            .map_err(Into::into)
+    }
+
     async fn create_table(
         &self,
         metadata: &RequestMetadata,
         table_id: TableIdentUuid,
         parent: NamespaceIdentUuid,
     ) -> Result<()> {
+        let authorizer = self
+            .for_object(ProjectScopedObject::Namespace(parent))
+            .await?;
         let actor = metadata.actor();
         let parent_id = parent.to_openfga();
         let this_id = table_id.to_openfga();
 
         // Higher consistency as for stage create overwrites old relations are deleted
         // immediately before
-        self.require_no_relations(&table_id, ConsistencyPreference::HigherConsistency)
+        authorizer
+            .require_no_relations(&table_id, ConsistencyPreference::HigherConsistency)
             .await?;
 
-        self.write(
-            Some(vec![
-                TupleKey {
-                    user: actor.to_openfga(),
-                    relation: TableRelation::Ownership.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: parent_id.clone(),
-                    relation: TableRelation::Parent.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: this_id.clone(),
-                    relation: NamespaceRelation::Child.to_string(),
-                    object: parent_id.clone(),
-                    condition: None,
-                },
-            ]),
-            None,
-        )
-        .await
-        .map_err(Into::into)
+        authorizer
+            .write(
+                Some(vec![
+                    TupleKey {
+                        user: actor.to_openfga(),
+                        relation: TableRelation::Ownership.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: parent_id.clone(),
+                        relation: TableRelation::Parent.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: this_id.clone(),
+                        relation: NamespaceRelation::Child.to_string(),
+                        object: parent_id.clone(),
+                        condition: None,
+                    },
+                ]),
+                None,
+            )
+            .await
+            .map_err(Into::into)
     }
 
     async fn delete_table(&self, table_id: TableIdentUuid) -> Result<()> {
@@ -611,69 +696,289 @@ impl Authorizer for OpenFGAAuthorizer {
         view_id: ViewIdentUuid,
         parent: NamespaceIdentUuid,
     ) -> Result<()> {
+        let authorizer = self
+            .for_object(ProjectScopedObject::Namespace(parent))
+            .await?;
         let actor = metadata.actor();
         let parent_id = parent.to_openfga();
         let this_id = view_id.to_openfga();
 
-        self.require_no_relations(&view_id, ConsistencyPreference::MinimizeLatency)
+        authorizer
+            .require_no_relations(&view_id, ConsistencyPreference::MinimizeLatency)
             .await?;
 
-        self.write(
-            Some(vec![
-                TupleKey {
-                    user: actor.to_openfga(),
-                    relation: ViewRelation::Ownership.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: parent_id.clone(),
-                    relation: ViewRelation::Parent.to_string(),
-                    object: this_id.clone(),
-                    condition: None,
-                },
-                TupleKey {
-                    user: this_id.clone(),
-                    relation: NamespaceRelation::Child.to_string(),
-                    object: parent_id.clone(),
-                    condition: None,
-                },
-            ]),
-            None,
-        )
-        .await
-        .map_err(Into::into)
+        authorizer
+            .write(
+                Some(vec![
+                    TupleKey {
+                        user: actor.to_openfga(),
+                        relation: ViewRelation::Ownership.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: parent_id.clone(),
+                        relation: ViewRelation::Parent.to_string(),
+                        object: this_id.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: this_id.clone(),
+                        relation: NamespaceRelation::Child.to_string(),
+                        object: parent_id.clone(),
+                        condition: None,
+                    },
+                ]),
+                None,
+            )
+            .await
+            .map_err(Into::into)
     }
 
     async fn delete_view(&self, view_id: ViewIdentUuid) -> Result<()> {
         self.delete_all_relations(&view_id).await
     }
+
+    async fn check_baseline_relations(
+        &self,
+        object: CatalogObject,
+        repair: bool,
+    ) -> Result<BaselineRelations> {
+        // Only the project of warehouses is known, other objects are looked up in all stores.
+        let authorizers = match object {
+            CatalogObject::Warehouse { project_id, .. } => {
+                vec![self.for_project(project_id).await?]
+            }
+            _ => self.all_stores().await?,
+        };
+        let single_store = authorizers.len() == 1;
+
+        let mut missing_in_all = Vec::new();
+        for authorizer in authorizers {
+            let mut missing = Vec::new();
+            for tuple in baseline_tuples(object) {
+                if !authorizer.tuple_exists(&tuple).await? {
+                    missing.push(tuple);
+                }
+            }
+            if missing.is_empty() {
+                return Ok(BaselineRelations::Consistent);
+            }
+            missing_in_all.push((authorizer, missing));
+        }
+
+        // Relations can only be repaired if the store of the object is known.
+        if !repair || !single_store {
+            return Ok(BaselineRelations::Missing);
+        }
+        let Some((authorizer, missing)) = missing_in_all.pop() else {
+            return Ok(BaselineRelations::Missing);
+        };
+        authorizer.write(Some(missing), None).await?;
+        Ok(BaselineRelations::Repaired)
+    }
+}
+
+/// Relations connecting `object` to its parent, as written by the `create_*` hooks.
+fn baseline_tuples(object: CatalogObject) -> Vec<TupleKey> {
+    let (this_id, parent_id, parent_relation, child_relation) = match object {
+        CatalogObject::Warehouse {
+            warehouse_id,
+            project_id,
+        } => (
+            warehouse_id.to_openfga(),
+            project_id.to_openfga(),
+            WarehouseRelation::Project.to_string(),
+            ProjectRelation::Warehouse.to_string(),
+        ),
+        CatalogObject::Namespace {
+            namespace_id,
+            parent: NamespaceParent::Warehouse(warehouse_id),
+        } => (
+            namespace_id.to_openfga(),
+            warehouse_id.to_openfga(),
+            NamespaceRelation::Parent.to_string(),
+            WarehouseRelation::Namespace.to_string(),
+        ),
+        CatalogObject::Namespace {
+            namespace_id,
+            parent: NamespaceParent::Namespace(parent_id),
+        } => (
+            namespace_id.to_openfga(),
+            parent_id.to_openfga(),
+            NamespaceRelation::Parent.to_string(),
+            NamespaceRelation::Child.to_string(),
+        ),
+        CatalogObject::Table {
+            table_id,
+            namespace_id,
+        } => (
+            table_id.to_openfga(),
+            namespace_id.to_openfga(),
+            TableRelation::Parent.to_string(),
+            NamespaceRelation::Child.to_string(),
+        ),
+        CatalogObject::View {
+            view_id,
+            namespace_id,
+        } => (
+            view_id.to_openfga(),
+            namespace_id.to_openfga(),
+            ViewRelation::Parent.to_string(),
+            NamespaceRelation::Child.to_string(),
+        ),
+    };
+
+    vec![
+        TupleKey {
+            user: parent_id.clone(),
+            relation: parent_relation,
+            object: this_id.clone(),
+            condition: None,
+        },
+        TupleKey {
+            user: this_id,
+            relation: child_relation,
+            object: parent_id,
+            condition: None,
+        },
+    ]
 }
 
 impl OpenFGAAuthorizer {
-    async fn list_projects_internal(&self, actor: &Actor) -> Result<ListProjectsResponse> {
+    /// Resolve the groups of principals via `resolver` when checking actions.
+    /// Principals are treated as assignees of the roles of their groups,
+    /// resolved groups are cached for `ttl`.
+    #[must_use]
+    pub fn with_group_resolver(mut self, resolver: Arc<dyn GroupResolver>, ttl: Duration) -> Self {
+        self.group_cache = Some(Arc::new(GroupCache::new(resolver, ttl)));
+        self
+    }
+
+    /// Resolve the display names of users and roles listed in assignment responses
+    /// via `resolver`. Resolved names are cached for `ttl`. Names are only presented,
+    /// they are never used to authorize requests.
+    #[must_use]
+    pub fn with_display_name_resolver(
+        mut self,
+        resolver: Arc<dyn DisplayNameResolver>,
+        ttl: Duration,
+    ) -> Self {
+        self.display_name_cache = Some(Arc::new(DisplayNameCache::new(resolver, ttl)));
+        self
+    }
+
+    /// Look up the project of objects via `resolver` to select the store holding their
+    /// relations. Required if projects have their own stores.
+    #[must_use]
+    pub fn with_project_resolver(mut self, resolver: Arc<dyn ProjectResolver>) -> Self {
+        self.project_resolver = Some(resolver);
+        self
+    }
+
+    /// Authorizer using the store of the project `object` belongs to if projects have
+    /// their own stores. The project is looked up in the catalog, it is never taken
+    /// from headers of the request.
+    async fn for_object(&self, object: ProjectScopedObject) -> Result<Cow<'_, Self>> {
+        if self.project_stores.is_none() {
+            return Ok(Cow::Borrowed(self));
+        }
+        let project_id = match object {
+            ProjectScopedObject::Project(project_id) => project_id,
+            object => self
+                .project_resolver
+                .as_ref()
+                .ok_or(OpenFGAError::ProjectResolverMissing)?
+                .resolve_project(object)
+                .await?
+                .ok_or_else(|| OpenFGAError::ObjectProjectNotFound {
+                    object: object.to_openfga(),
+                })?,
+        };
+        Ok(self.for_project(project_id).await?)
+    }
+
+    /// Authorizer using the store of `project_id` if projects have their own stores.
+    /// The store is created on first use.
+    async fn for_project(&self, project_id: ProjectId) -> OpenFGAResult<Cow<'_, Self>> {
+        let Some(project_stores) = &self.project_stores else {
+            return Ok(Cow::Borrowed(self));
+        };
+        let store = project_stores.resolve(project_id).await?;
+        Ok(Cow::Owned(self.with_store(store)))
+    }
+
+    /// Authorizers of the store of server-level relations and of the stores of all projects.
+    async fn all_stores(&self) -> OpenFGAResult<Vec<Cow<'_, Self>>> {
+        let Some(project_stores) = &self.project_stores else {
+            return Ok(vec![Cow::Borrowed(self)]);
+        };
+        let mut authorizers = vec![Cow::Borrowed(self)];
+        authorizers.extend(
+            project_stores
+                .all()
+                .await?
+                .into_iter()
+                .map(|store| Cow::Owned(self.with_store(store))),
+        );
+        Ok(authorizers)
+    }
+
+    fn with_store(&self, store: ProjectStore) -> Self {
+        Self {
+            store_id: store.store_id,
+            authorization_model_id: store.authorization_model_id,
+            // Calls of the returned authorizer stay in the selected store.
+            project_stores: None,
+            ..self.clone()
+        }
+    }
+
+    /// Projects `actor` may list. `contextual_tuples` hold the assignments of the
+    /// principal to the roles of its groups.
+    async fn list_projects_internal(
+        &self,
+        actor: &Actor,
+        contextual_tuples: Vec<TupleKey>,
+    ) -> Result<ListProjectsResponse> {
         let list_all = self
-            .check(CheckRequestTupleKey {
-                user: actor.to_openfga(),
-                relation: ServerRelation::CanListAllProjects.to_string(),
-                object: OPENFGA_SERVER.clone(),
-            })
+            .check_with_context(
+                CheckRequestTupleKey {
+                    user: actor.to_openfga(),
+                    relation: ServerRelation::CanListAllProjects.to_string(),
+                    object: OPENFGA_SERVER.clone(),
+                },
+                contextual_tuples.clone(),
+            )
             .await?;
 
         if list_all {
             return Ok(ListProjectsResponse::All);
         }
 
-        let projects = self
-            .list_objects(
-                FgaType::Project.to_string(),
-                CatalogProjectAction::CanIncludeInList.to_string(),
-                actor.to_openfga(),
-            )
-            .await?
-            .iter()
-            .map(|p| ProjectId::parse_from_openfga(p))
-            .collect::<std::result::Result<HashSet<ProjectId>, _>>()?;
+        let (relation, object_type) = self.relation_mapping.resolve_type(
+            CatalogProjectAction::CanIncludeInList.to_string(),
+            &FgaType::Project.to_string(),
+        );
+        let mut projects = HashSet::new();
+        for authorizer in self.all_stores().await? {
+            let listed = authorizer
+                .list_objects(
+                    object_type.clone(),
+                    relation.clone(),
+                    actor.to_openfga(),
+                    contextual_tuples.clone(),
+                )
+                .await?
+                .iter()
+                .map(|p| {
+                    p.strip_prefix(&format!("{object_type}:"))
+                        .ok_or_else(|| OpenFGAError::InvalidEntity(p.clone()))
+                        .and_then(|id| ProjectId::try_from_openfga_id(FgaType::Project, id))
+                })
+                .collect::<std::result::Result<HashSet<ProjectId>, _>>()?;
+            projects.extend(listed);
+        }
 
         Ok(ListProjectsResponse::Projects(projects))
     }
@@ -712,14 +1017,20 @@ impl OpenFGAAuthorizer {
             }),
             authorization_model_id: self.authorization_model_id.clone(),
         };
-        self.client
-            .write(write_request.clone())
-            .await
-            .map_err(|e| OpenFGAError::WriteFailed {
-                write_request,
-                source: e,
-            })
-            .map(|_| ())
+        let write = async {
+            self.client
+                .write(write_request.clone())
+                .await
+                .map_err(|e| OpenFGAError::WriteFailed {
+                    write_request,
+                    source: e,
+                })
+                .map(|_| ())
+        };
+        observe_call(self.emit_metrics, OpenFgaCall::Write, write, |_| {
+            CallOutcome::Ok
+        })
+        .await
     }
 
     /// A convenience wrapper around read that handles error conversion
@@ -737,41 +1048,184 @@ impl OpenFGAAuthorizer {
             tuple_key: Some(tuple_key),
             consistency: consistency.into(),
         };
-        self.client
-            .read(read_request.clone())
-            .await
-            .map_err(|e| OpenFGAError::ReadFailed {
-                read_request: Box::new(read_request),
-                source: e,
-            })
-            .map(tonic::Response::into_inner)
+        let read = async {
+            self.client
+                .read(read_request.clone())
+                .await
+                .map_err(|e| OpenFGAError::ReadFailed {
+                    read_request: Box::new(read_request),
+                    source: e,
+                })
+                .map(tonic::Response::into_inner)
+        };
+        observe_call(self.emit_metrics, OpenFgaCall::Read, read, |_| {
+            CallOutcome::Ok
+        })
+        .await
+    }
+
+    /// Check if exactly this tuple exists
+    async fn tuple_exists(&self, tuple: &TupleKey) -> OpenFGAResult<bool> {
+        let response = self
+            .read(
+                1,
+                ReadRequestTupleKey {
+                    user: tuple.user.clone(),
+                    relation: tuple.relation.clone(),
+                    object: tuple.object.clone(),
+                },
+                None,
+                ConsistencyPreference::HigherConsistency,
+            )
+            .await?;
+        Ok(!response.tuples.is_empty())
     }
 
-    /// Read all tuples for a given request
-    async fn read_all(&self, tuple_key: ReadRequestTupleKey) -> OpenFGAResult<Vec<Tuple>> {
-        self.client.read_all_pages(&self.store_id, tuple_key).await
+    /// Read the tuples for a given request, up to `max_pages` pages.
+    /// Partial results are signaled by a continuation token and must not
+    /// be used for authorization decisions.
+    async fn read_pages(
+        &self,
+        tuple_key: ReadRequestTupleKey,
+        continuation_token: Option<String>,
+    ) -> OpenFGAResult<TuplePage> {
+        let read = self.client.read_pages(
+            &self.store_id,
+            tuple_key,
+            self.max_pages,
+            continuation_token,
+        );
+        observe_call(self.emit_metrics, OpenFgaCall::Read, read, |_| {
+            CallOutcome::Ok
+        })
+        .await
     }
 
     /// A convenience wrapper around check
     async fn check(&self, tuple_key: CheckRequestTupleKey) -> OpenFGAResult<bool> {
+        self.check_with_context(tuple_key, vec![]).await
+    }
+
+    async fn check_with_context(
+        &self,
+        tuple_key: CheckRequestTupleKey,
+        contextual_tuples: Vec<TupleKey>,
+    ) -> OpenFGAResult<bool> {
         let check_request = CheckRequest {
             tuple_key: Some(tuple_key),
             store_id: self.store_id.clone(),
             authorization_model_id: self.authorization_model_id.clone(),
-            contextual_tuples: None,
+            contextual_tuples: (!contextual_tuples.is_empty()).then_some(ContextualTupleKeys {
+                tuple_keys: contextual_tuples,
+            }),
             trace: false,
             context: None,
             consistency: ConsistencyPreference::MinimizeLatency.into(),
         };
 
-        self.client
-            .check(check_request.clone())
-            .await
+        let check = async {
+            with_timeout(
+                Backend::AuthzCheck,
+                self.check_timeout,
+                self.client.check(check_request.clone()),
+            )
+            .await?
             .map_err(|source| OpenFGAError::CheckFailed {
                 check_request: Box::new(check_request),
                 source,
             })
             .map(|response| response.get_ref().allowed)
+        };
+        observe_call(self.emit_metrics, OpenFgaCall::Check, check, |allowed| {
+            if *allowed {
+                CallOutcome::Allowed
+            } else {
+                CallOutcome::Denied
+            }
+        })
+        .await
+    }
+
+    /// Check `action` on `object`, respecting the configured relation mapping.
+    async fn check_action(
+        &self,
+        metadata: &RequestMetadata,
+        action: String,
+        object: String,
+    ) -> OpenFGAResult<bool> {
+        let (relation, object) = self.relation_mapping.resolve(action, object);
+        self.check_for_request(
+            metadata,
+            CheckRequestTupleKey {
+                user: metadata.actor().to_openfga(),
+                relation,
+                object,
+            },
+        )
+        .await
+    }
+
+    /// Check `tuple_key` including the groups of the principal of the request.
+    /// If the request check cache is enabled, each decision is remembered until the
+    /// request completes, so that identical checks within a request query `OpenFGA` once.
+    async fn check_for_request(
+        &self,
+        metadata: &RequestMetadata,
+        tuple_key: CheckRequestTupleKey,
+    ) -> OpenFGAResult<bool> {
+        if self.request_check_cache {
+            if let Some(allowed) =
+                metadata.authz_decision(&tuple_key.user, &tuple_key.relation, &tuple_key.object)
+            {
+                return Ok(allowed);
+            }
+        }
+
+        let contextual_tuples = self.group_tuples(metadata).await?;
+        let CheckRequestTupleKey {
+            user,
+            relation,
+            object,
+        } = tuple_key.clone();
+        let allowed = self
+            .check_with_context(tuple_key, contextual_tuples)
+            .await?;
+        if self.request_check_cache {
+            metadata.record_authz_decision(user, relation, object, allowed);
+        }
+        Ok(allowed)
+    }
+
+    /// Assignments of the principal to the roles of its groups, if a group resolver
+    /// is configured. Fails if the groups cannot be resolved, so that no request is
+    /// authorized based on incomplete groups.
+    async fn group_tuples(&self, metadata: &RequestMetadata) -> OpenFGAResult<Vec<TupleKey>> {
+        let (Some(group_cache), Actor::Principal(user_id)) = (&self.group_cache, metadata.actor())
+        else {
+            return Ok(vec![]);
+        };
+        let groups = match metadata.groups() {
+            Some(groups) => groups.to_vec(),
+            None => {
+                let groups = group_cache.groups(user_id).await.map_err(|e| {
+                    OpenFGAError::GroupResolutionFailed {
+                        user: user_id.to_string(),
+                        reason: e.error.message,
+                    }
+                })?;
+                metadata.set_groups(groups.clone());
+                groups
+            }
+        };
+        Ok(groups
+            .iter()
+            .map(|role_id| TupleKey {
+                user: metadata.actor().to_openfga(),
+                relation: RoleRelation::Assignee.to_string(),
+                object: role_id.to_openfga(),
+                condition: None,
+            })
+            .collect())
     }
 
     async fn require_action(
@@ -781,11 +1235,14 @@ impl OpenFGAAuthorizer {
         object: &str,
     ) -> Result<()> {
         let allowed = self
-            .check(CheckRequestTupleKey {
-                user: metadata.actor().to_openfga(),
-                relation: action.to_string(),
-                object: object.to_string(),
-            })
+            .check_for_request(
+                metadata,
+                CheckRequestTupleKey {
+                    user: metadata.actor().to_openfga(),
+                    relation: action.to_string(),
+                    object: object.to_string(),
+                },
+            )
             .await?;
 
         if !allowed {
@@ -880,13 +1337,19 @@ impl OpenFGAAuthorizer {
         Ok(())
     }
 
+    /// Delete all relations of `object` and all relations `object` is a user of.
+    /// If projects have their own stores, relations are deleted from all stores,
+    /// as the project of `object` is not known to all callers.
     async fn delete_all_relations(&self, object: &impl OpenFgaEntity) -> Result<()> {
-        let (own_relations, user_relations) = futures::join!(
-            self.delete_own_relations(object),
-            self.delete_user_relations(object)
-        );
-        own_relations?;
-        user_relations
+        for authorizer in self.all_stores().await? {
+            let (own_relations, user_relations) = futures::join!(
+                authorizer.delete_own_relations(object),
+                authorizer.delete_user_relations(object)
+            );
+            own_relations?;
+            user_relations?;
+        }
+        Ok(())
     }
 
     async fn delete_user_relations(&self, user: &impl OpenFgaEntity) -> Result<()> {
@@ -1023,6 +1486,7 @@ impl OpenFGAAuthorizer {
         r#type: impl Into<String>,
         relation: impl Into<String>,
         user: impl Into<String>,
+        contextual_tuples: Vec<TupleKey>,
     ) -> Result<Vec<String>> {
         let user = user.into();
         self.client
@@ -1032,7 +1496,9 @@ impl OpenFGAAuthorizer {
                 user: user.clone(),
                 store_id: self.store_id.clone(),
                 authorization_model_id: self.authorization_model_id.clone(),
-                contextual_tuples: None,
+                contextual_tuples: (!contextual_tuples.is_empty()).then_some(ContextualTupleKeys {
+                    tuple_keys: contextual_tuples,
+                }),
                 context: None,
                 consistency: ConsistencyPreference::MinimizeLatency.into(),
             })
@@ -1065,11 +1531,13 @@ pub trait Client {
         &self,
         request: ReadRequest,
     ) -> std::result::Result<Response<ReadResponse>, tonic::Status>;
-    async fn read_all_pages(
+    async fn read_pages(
         &self,
         store_id: &str,
         tuple: ReadRequestTupleKey,
-    ) -> OpenFGAResult<Vec<Tuple>>;
+        max_pages: u32,
+        continuation_token: Option<String>,
+    ) -> OpenFGAResult<TuplePage>;
 
     async fn check(
         &self,
@@ -1105,12 +1573,21 @@ impl Client for OpenFgaServiceClient<ClientConnection> {
         Self::read(&mut self.clone(), request).await
     }
 
-    async fn read_all_pages(
+    async fn read_pages(
         &self,
         store_id: &str,
         tuple: ReadRequestTupleKey,
-    ) -> OpenFGAResult<Vec<Tuple>> {
-        ClientHelper::read_all_pages(&mut self.clone(), store_id, tuple).await
+        max_pages: u32,
+        continuation_token: Option<String>,
+    ) -> OpenFGAResult<TuplePage> {
+        ClientHelper::read_pages(
+            &mut self.clone(),
+            store_id,
+            tuple,
+            max_pages,
+            continuation_token,
+        )
+        .await
     }
 
     async fn check(
@@ -1124,14 +1601,47 @@ impl Client for OpenFgaServiceClient<ClientConnection> {
 #[allow(dead_code)]
 pub(crate) mod tests {
     use std::{
-        collections::HashSet,
-        sync::{Arc, RwLock},
+        collections::{BTreeMap, HashMap, HashSet},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, RwLock,
+        },
+        time::Duration,
     };
 
+    use async_trait::async_trait;
+    use http::StatusCode;
     use needs_env_var::needs_env_var;
-    use openfga_rs::{CheckResponse, ReadResponse, WriteResponse};
+    use openfga_rs::{
+        tonic::{self, Response},
+        CheckRequest, CheckRequestTupleKey, CheckResponse, ListObjectsRequest, ListObjectsResponse,
+        ReadRequest, ReadRequestTupleKey, ReadResponse, WriteRequest, WriteResponse,
+    };
+
+    use super::{
+        project_stores::{MockProjectResolver, MockStoreProvisioner},
+        service_ext::DEFAULT_MAX_PAGES,
+        ProjectScopedObject, ProjectStore, ProjectStores, TuplePage,
+    };
 
-    use crate::service::authz::implementations::openfga::{MockClient, OpenFGAAuthorizer};
+    use crate::{
+        config::OpenFGARelationMapping,
+        request_metadata::RequestMetadata,
+        service::{
+            audit::{AuditOutcome, AuditRecord},
+            authn::UserId,
+            authz::{
+                groups::GroupResolver,
+                implementations::openfga::{
+                    Client, MockClient, ModelVersion, OpenFGAAuthorizer, OpenFGAError,
+                    OpenFGAResult, OpenFgaEntity, RelationMapping,
+                },
+                Authorizer, CatalogTableAction, CatalogWarehouseAction,
+            },
+            ErrorModel, RoleId, TableIdentUuid,
+        },
+        ProjectId, WarehouseIdent, CONFIG,
+    };
 
     /// A mock for the `OpenFGA` client that allows to hide objects.
     /// This is useful to test the behavior of the authorizer when objects are hidden.
@@ -1192,10 +1702,559 @@ pub(crate) mod tests {
                 store_id: "test_store".to_string(),
                 authorization_model_id: "test_model".to_string(),
                 health: Arc::default(),
+                check_timeout: CONFIG.backend_timeouts.authz_check,
+                max_pages: DEFAULT_MAX_PAGES,
+                relation_mapping: Arc::default(),
+                group_cache: None,
+                display_name_cache: None,
+                request_check_cache: true,
+                emit_metrics: true,
+                project_stores: None,
+                project_resolver: None,
+            }
+        }
+    }
+
+    /// Delays check requests to `mock` by `delay`, as mocks respond immediately.
+    struct DelayedCheckClient {
+        mock: MockClient,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl Client for DelayedCheckClient {
+        async fn write(
+            &self,
+            request: WriteRequest,
+        ) -> Result<Response<WriteResponse>, tonic::Status> {
+            self.mock.write(request).await
+        }
+
+        async fn list_objects(
+            &self,
+            request: ListObjectsRequest,
+        ) -> Result<Response<ListObjectsResponse>, tonic::Status> {
+            self.mock.list_objects(request).await
+        }
+
+        async fn read(
+            &self,
+            request: ReadRequest,
+        ) -> Result<Response<ReadResponse>, tonic::Status> {
+            self.mock.read(request).await
+        }
+
+        async fn read_pages(
+            &self,
+            store_id: &str,
+            tuple: ReadRequestTupleKey,
+            max_pages: u32,
+            continuation_token: Option<String>,
+        ) -> OpenFGAResult<TuplePage> {
+            self.mock
+                .read_pages(store_id, tuple, max_pages, continuation_token)
+                .await
+        }
+
+        async fn check(
+            &self,
+            request: CheckRequest,
+        ) -> Result<Response<CheckResponse>, tonic::Status> {
+            tokio::time::sleep(self.delay).await;
+            self.mock.check(request).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delayed_check_times_out() {
+        let mut mock = MockClient::default();
+        mock.expect_check().returning(|_| {
+            Ok(Response::new(CheckResponse {
+                allowed: true,
+                resolution: String::new(),
+            }))
+        });
+        let authorizer = OpenFGAAuthorizer {
+            client: Arc::new(DelayedCheckClient {
+                mock,
+                delay: Duration::from_secs(60),
+            }),
+            store_id: "test_store".to_string(),
+            authorization_model_id: "test_model".to_string(),
+            health: Arc::default(),
+            check_timeout: Duration::from_millis(10),
+            max_pages: DEFAULT_MAX_PAGES,
+            relation_mapping: Arc::default(),
+            group_cache: None,
+            display_name_cache: None,
+            request_check_cache: true,
+            emit_metrics: true,
+            project_stores: None,
+            project_resolver: None,
+        };
+
+        let err = authorizer
+            .is_allowed_warehouse_action(
+                &RequestMetadata::new_unauthenticated(),
+                WarehouseIdent::from(uuid::Uuid::now_v7()),
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.error.code, StatusCode::GATEWAY_TIMEOUT.as_u16());
+        assert_eq!(err.error.r#type, "AuthzCheckTimeout");
+    }
+
+    #[tokio::test]
+    async fn test_remapped_commit_table_enforces_relation() {
+        // Only grants `modify` on tables
+        let mut mock = MockClient::default();
+        mock.expect_check().returning(|r| {
+            let tuple_key = r.tuple_key.unwrap();
+            Ok(Response::new(CheckResponse {
+                allowed: tuple_key.relation == "modify" && tuple_key.object.starts_with("table:"),
+                resolution: String::new(),
+            }))
+        });
+        let relation_mapping = RelationMapping::try_new(
+            &BTreeMap::from_iter([(
+                "table".to_string(),
+                BTreeMap::from_iter([(
+                    "can_commit".to_string(),
+                    OpenFGARelationMapping {
+                        relation: "modify".to_string(),
+                        object_type: None,
+                    },
+                )]),
+            )]),
+            &ModelVersion::active().get_model_ref().type_definitions,
+        )
+        .unwrap();
+        let mock = Arc::new(mock);
+        let authorizer = |relation_mapping: RelationMapping| OpenFGAAuthorizer {
+            client: mock.clone(),
+            store_id: "test_store".to_string(),
+            authorization_model_id: "test_model".to_string(),
+            health: Arc::default(),
+            check_timeout: CONFIG.backend_timeouts.authz_check,
+            max_pages: DEFAULT_MAX_PAGES,
+            relation_mapping: Arc::new(relation_mapping),
+            group_cache: None,
+            display_name_cache: None,
+            request_check_cache: true,
+            emit_metrics: true,
+            project_stores: None,
+            project_resolver: None,
+        };
+        let metadata = RequestMetadata::new_unauthenticated();
+        let table_id = TableIdentUuid::from(uuid::Uuid::now_v7());
+
+        let remapped = authorizer(relation_mapping);
+        assert!(remapped
+            .is_allowed_table_action(&metadata, table_id, &CatalogTableAction::CanCommit)
+            .await
+            .unwrap());
+        // Unmapped actions keep checking the relation of the same name
+        assert!(!remapped
+            .is_allowed_table_action(&metadata, table_id, &CatalogTableAction::CanDrop)
+            .await
+            .unwrap());
+
+        assert!(!authorizer(RelationMapping::default())
+            .is_allowed_table_action(&metadata, table_id, &CatalogTableAction::CanCommit)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_identical_checks_within_request_query_openfga_once() {
+        let mut mock = MockClient::default();
+        // One call for the first request, one for the second request
+        mock.expect_check().times(2).returning(|_| {
+            Ok(Response::new(CheckResponse {
+                allowed: true,
+                resolution: String::new(),
+            }))
+        });
+        let authorizer = OpenFGAAuthorizer {
+            client: Arc::new(mock),
+            store_id: "test_store".to_string(),
+            authorization_model_id: "test_model".to_string(),
+            health: Arc::default(),
+            check_timeout: CONFIG.backend_timeouts.authz_check,
+            max_pages: DEFAULT_MAX_PAGES,
+            relation_mapping: Arc::default(),
+            group_cache: None,
+            display_name_cache: None,
+            request_check_cache: true,
+            emit_metrics: true,
+            project_stores: None,
+            project_resolver: None,
+        };
+        let user = UserId::new_unchecked("oidc", "alice");
+        let table_id = TableIdentUuid::from(uuid::Uuid::now_v7());
+
+        let metadata = RequestMetadata::random_human(user.clone());
+        for _ in 0..2 {
+            authorizer
+                .require_table_action(
+                    &metadata,
+                    Ok(Some(table_id)),
+                    &CatalogTableAction::CanGetMetadata,
+                )
+                .await
+                .unwrap();
+        }
+
+        // Decisions are not shared with other requests
+        authorizer
+            .require_table_action(
+                &RequestMetadata::random_human(user),
+                Ok(Some(table_id)),
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_outcomes_are_counted() {
+        let mut mock = MockClient::default();
+        mock.expect_check().returning(|r| {
+            let tuple_key = r.tuple_key.unwrap();
+            if tuple_key.relation == "can_drop" {
+                return Err(tonic::Status::internal("OpenFGA unavailable"));
+            }
+            Ok(Response::new(CheckResponse {
+                allowed: false,
+                resolution: String::new(),
+            }))
+        });
+        let authorizer = OpenFGAAuthorizer {
+            client: Arc::new(mock),
+            store_id: "test_store".to_string(),
+            authorization_model_id: "test_model".to_string(),
+            health: Arc::default(),
+            check_timeout: CONFIG.backend_timeouts.authz_check,
+            max_pages: DEFAULT_MAX_PAGES,
+            relation_mapping: Arc::default(),
+            group_cache: None,
+            display_name_cache: None,
+            request_check_cache: true,
+            emit_metrics: true,
+            project_stores: None,
+            project_resolver: None,
+        };
+        let recorder =
+            axum_prometheus::metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        // The test runtime is single-threaded, so all calls use the local recorder
+        let _guard = axum_prometheus::metrics::set_default_local_recorder(&recorder);
+
+        let tuple_key = |relation: &str| CheckRequestTupleKey {
+            user: "user:alice".to_string(),
+            relation: relation.to_string(),
+            object: "table:t1".to_string(),
+        };
+        assert!(!authorizer.check(tuple_key("can_commit")).await.unwrap());
+        let err = authorizer.check(tuple_key("can_drop")).await.unwrap_err();
+        assert!(matches!(err, OpenFGAError::CheckFailed { .. }));
+
+        let rendered = handle.render();
+        assert!(
+            rendered.contains(r#"lakekeeper_openfga_calls_total{call="check",result="denied"} 1"#)
+        );
+        assert!(
+            rendered.contains(r#"lakekeeper_openfga_calls_total{call="check",result="error"} 1"#)
+        );
+        assert!(!rendered.contains("alice"));
+    }
+
+    #[tokio::test]
+    async fn test_projects_use_their_own_stores() {
+        let checked_stores: Arc<RwLock<Vec<String>>> = Arc::default();
+        let checked_stores_clone = checked_stores.clone();
+        let mut mock = MockClient::default();
+        mock.expect_check().returning(move |r| {
+            checked_stores_clone.write().unwrap().push(r.store_id);
+            Ok(Response::new(CheckResponse {
+                allowed: true,
+                resolution: String::new(),
+            }))
+        });
+        let mut provisioner = MockStoreProvisioner::new();
+        provisioner
+            .expect_provision_store()
+            .times(2)
+            .returning(|store_name| {
+                Ok(ProjectStore {
+                    store_id: store_name.to_string(),
+                    authorization_model_id: "test_model".to_string(),
+                })
+            });
+        let authorizer = OpenFGAAuthorizer {
+            client: Arc::new(mock),
+            store_id: "test_store".to_string(),
+            authorization_model_id: "test_model".to_string(),
+            health: Arc::default(),
+            check_timeout: CONFIG.backend_timeouts.authz_check,
+            max_pages: DEFAULT_MAX_PAGES,
+            relation_mapping: Arc::default(),
+            group_cache: None,
+            display_name_cache: None,
+            request_check_cache: true,
+            emit_metrics: true,
+            project_stores: Some(Arc::new(ProjectStores::new(
+                Arc::new(provisioner),
+                "test_store".to_string(),
+            ))),
+            project_resolver: None,
+        };
+
+        let project_1 = ProjectId::default();
+        let project_2 = ProjectId::default();
+        let warehouse_1 = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let warehouse_2 = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let mut resolver = MockProjectResolver::new();
+        resolver.expect_resolve_project().returning(move |object| {
+            Ok(match object {
+                ProjectScopedObject::Warehouse(w) if w == warehouse_1 => Some(project_1),
+                ProjectScopedObject::Warehouse(w) if w == warehouse_2 => Some(project_2),
+                _ => None,
+            })
+        });
+        let authorizer = authorizer.with_project_resolver(Arc::new(resolver));
+
+        // The store follows the project of the warehouse, not the project header
+        for warehouse_id in [warehouse_1, warehouse_2, warehouse_1] {
+            let metadata = RequestMetadata::random_human(UserId::new_unchecked("oidc", "alice"))
+                .with_project_id(project_2);
+            assert!(authorizer
+                .is_allowed_warehouse_action(
+                    &metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanUse
+                )
+                .await
+                .unwrap());
+        }
+
+        // Objects without a project are not checked in any store
+        let err = authorizer
+            .is_allowed_warehouse_action(
+                &RequestMetadata::random_human(UserId::new_unchecked("oidc", "alice")),
+                WarehouseIdent::from(uuid::Uuid::now_v7()),
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "ObjectProjectNotFound");
+
+        // Each project is provisioned once and checked in its own store
+        assert_eq!(
+            *checked_stores.read().unwrap(),
+            vec![
+                format!("test_store-{project_1}"),
+                format!("test_store-{project_2}"),
+                format!("test_store-{project_1}"),
+            ]
+        );
+    }
+
+    #[derive(Debug, Default)]
+    struct MockGroupResolver {
+        groups: HashMap<String, Vec<RoleId>>,
+        fail: bool,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl GroupResolver for MockGroupResolver {
+        async fn resolve_groups(&self, user_id: &UserId) -> crate::api::Result<Vec<RoleId>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                return Err(
+                    ErrorModel::internal("Directory unavailable", "LdapError", None).into(),
+                );
             }
+            Ok(self
+                .groups
+                .get(&user_id.to_string())
+                .cloned()
+                .unwrap_or_default())
         }
     }
 
+    #[tokio::test]
+    async fn test_group_membership_grants_access_via_contextual_tuple() {
+        let group = RoleId::new(uuid::Uuid::now_v7());
+        let warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+        // Emulates OpenFGA: Only assignees of the group may use the warehouse,
+        // and nobody is stored as an assignee.
+        let group_user = format!("{}#assignee", group.to_openfga());
+        let warehouse = warehouse_id.to_openfga();
+        let mut mock = MockClient::default();
+        mock.expect_check().returning(move |r| {
+            let tuple_key = r.tuple_key.unwrap();
+            let is_assignee = r.contextual_tuples.is_some_and(|tuples| {
+                tuples.tuple_keys.iter().any(|t| {
+                    t.user == tuple_key.user && format!("{}#{}", t.object, t.relation) == group_user
+                })
+            });
+            Ok(Response::new(CheckResponse {
+                allowed: is_assignee && tuple_key.object == warehouse,
+                resolution: String::new(),
+            }))
+        });
+        let alice = UserId::new_unchecked("oidc", "alice");
+        let bob = UserId::new_unchecked("oidc", "bob");
+        let resolver = Arc::new(MockGroupResolver {
+            groups: HashMap::from([(alice.to_string(), vec![group])]),
+            ..Default::default()
+        });
+        let authorizer = OpenFGAAuthorizer {
+            client: Arc::new(mock),
+            store_id: "test_store".to_string(),
+            authorization_model_id: "test_model".to_string(),
+            health: Arc::default(),
+            check_timeout: CONFIG.backend_timeouts.authz_check,
+            max_pages: DEFAULT_MAX_PAGES,
+            relation_mapping: Arc::default(),
+            group_cache: None,
+            display_name_cache: None,
+            request_check_cache: true,
+            emit_metrics: true,
+            project_stores: None,
+            project_resolver: None,
+        }
+        .with_group_resolver(resolver.clone(), Duration::from_secs(60));
+
+        let metadata = RequestMetadata::random_human(alice.clone());
+        authorizer
+            .require_warehouse_action(&metadata, warehouse_id, &CatalogWarehouseAction::CanUse)
+            .await
+            .unwrap();
+        assert_eq!(metadata.groups(), Some([group].as_slice()));
+        let record = AuditRecord::new(
+            &metadata,
+            CatalogWarehouseAction::CanUse,
+            "warehouse",
+            AuditOutcome::Allowed,
+        );
+        assert_eq!(record.groups, vec![group.to_string()]);
+
+        // Groups are cached across requests
+        authorizer
+            .require_warehouse_action(
+                &RequestMetadata::random_human(alice),
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await
+            .unwrap();
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 1);
+
+        assert!(!authorizer
+            .is_allowed_warehouse_action(
+                &RequestMetadata::random_human(bob.clone()),
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await
+            .unwrap());
+
+        // Resolver failures fail closed
+        let failing = authorizer.with_group_resolver(
+            Arc::new(MockGroupResolver {
+                fail: true,
+                ..Default::default()
+            }),
+            Duration::from_secs(60),
+        );
+        let err = failing
+            .require_warehouse_action(
+                &RequestMetadata::random_human(bob),
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::SERVICE_UNAVAILABLE.as_u16());
+        assert_eq!(err.error.r#type, "GroupResolutionFailed");
+    }
+
+    #[tokio::test]
+    async fn test_group_membership_is_applied_when_listing_projects() {
+        let group = RoleId::new(uuid::Uuid::now_v7());
+        let project = ProjectId::from(uuid::Uuid::now_v7());
+        let group_user = format!("{}#assignee", group.to_openfga());
+        let mut mock = MockClient::default();
+        mock.expect_check().returning(|_| {
+            Ok(Response::new(CheckResponse {
+                allowed: false,
+                resolution: String::new(),
+            }))
+        });
+        let listed = project.to_openfga();
+        mock.expect_list_objects().returning(move |r| {
+            let is_assignee = r.contextual_tuples.is_some_and(|tuples| {
+                tuples.tuple_keys.iter().any(|t| {
+                    t.user == r.user && format!("{}#{}", t.object, t.relation) == group_user
+                })
+            });
+            Ok(Response::new(ListObjectsResponse {
+                objects: if is_assignee {
+                    vec![listed.clone()]
+                } else {
+                    vec![]
+                },
+            }))
+        });
+        let alice = UserId::new_unchecked("oidc", "alice");
+        let authorizer = OpenFGAAuthorizer {
+            client: Arc::new(mock),
+            store_id: "test_store".to_string(),
+            authorization_model_id: "test_model".to_string(),
+            health: Arc::default(),
+            check_timeout: CONFIG.backend_timeouts.authz_check,
+            max_pages: DEFAULT_MAX_PAGES,
+            relation_mapping: Arc::default(),
+            group_cache: None,
+            display_name_cache: None,
+            request_check_cache: true,
+            emit_metrics: true,
+            project_stores: None,
+            project_resolver: None,
+        }
+        .with_group_resolver(
+            Arc::new(MockGroupResolver {
+                groups: HashMap::from([(alice.to_string(), vec![group])]),
+                ..Default::default()
+            }),
+            Duration::from_secs(60),
+        );
+
+        let projects = authorizer
+            .list_projects(&RequestMetadata::random_human(alice))
+            .await
+            .unwrap();
+        assert_eq!(
+            projects,
+            crate::service::authz::ListProjectsResponse::Projects(HashSet::from([project]))
+        );
+        let projects = authorizer
+            .list_projects(&RequestMetadata::random_human(UserId::new_unchecked(
+                "oidc", "bob",
+            )))
+            .await
+            .unwrap();
+        assert_eq!(
+            projects,
+            crate::service::authz::ListProjectsResponse::Projects(HashSet::new())
+        );
+    }
+
     #[needs_env_var(TEST_OPENFGA = 1)]
     mod openfga {
         use http::StatusCode;
@@ -1226,7 +2285,7 @@ pub(crate) mod tests {
             let project = ProjectId::from(uuid::Uuid::now_v7());
 
             let projects = authorizer
-                .list_projects_internal(&actor)
+                .list_projects_internal(&actor, vec![])
                 .await
                 .expect("Failed to list projects");
             assert_eq!(projects, ListProjectsResponse::Projects(HashSet::new()));
@@ -1245,7 +2304,7 @@ pub(crate) mod tests {
                 .unwrap();
 
             let projects = authorizer
-                .list_projects_internal(&actor)
+                .list_projects_internal(&actor, vec![])
                 .await
                 .expect("Failed to list projects");
             assert_eq!(
@@ -1254,6 +2313,134 @@ pub(crate) mod tests {
             );
         }
 
+        #[tokio::test]
+        async fn test_check_baseline_relations_of_table() {
+            let authorizer = new_authorizer_in_empty_store().await;
+            let metadata =
+                RequestMetadata::random_human(UserId::new_unchecked("oidc", "this_user"));
+            let namespace_id = NamespaceIdentUuid::from(uuid::Uuid::now_v7());
+            let table_id = TableIdentUuid::from(uuid::Uuid::now_v7());
+            let object = CatalogObject::Table {
+                table_id,
+                namespace_id,
+            };
+
+            authorizer
+                .create_table(&metadata, table_id, namespace_id)
+                .await
+                .unwrap();
+            assert_eq!(
+                authorizer
+                    .check_baseline_relations(object, false)
+                    .await
+                    .unwrap(),
+                BaselineRelations::Consistent
+            );
+
+            // Namespace owners can no longer reach the table
+            authorizer
+                .write(
+                    None,
+                    Some(vec![TupleKeyWithoutCondition {
+                        user: namespace_id.to_openfga(),
+                        relation: TableRelation::Parent.to_string(),
+                        object: table_id.to_openfga(),
+                    }]),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                authorizer
+                    .check_baseline_relations(object, false)
+                    .await
+                    .unwrap(),
+                BaselineRelations::Missing
+            );
+            assert_eq!(
+                authorizer
+                    .check_baseline_relations(object, true)
+                    .await
+                    .unwrap(),
+                BaselineRelations::Repaired
+            );
+            assert_eq!(
+                authorizer
+                    .check_baseline_relations(object, false)
+                    .await
+                    .unwrap(),
+                BaselineRelations::Consistent
+            );
+        }
+
+        #[tokio::test]
+        async fn test_namespace_grant_is_inherited_by_nested_tables() {
+            let authorizer = new_authorizer_in_empty_store().await;
+            let owner = RequestMetadata::random_human(UserId::new_unchecked("oidc", "owner"));
+            let viewer_id = UserId::new_unchecked("oidc", "viewer");
+            let viewer = RequestMetadata::random_human(viewer_id.clone());
+            let warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+            let ns_1 = NamespaceIdentUuid::from(uuid::Uuid::now_v7());
+            let ns_2 = NamespaceIdentUuid::from(uuid::Uuid::now_v7());
+            let ns_3 = NamespaceIdentUuid::from(uuid::Uuid::now_v7());
+            let table_id = TableIdentUuid::from(uuid::Uuid::now_v7());
+
+            authorizer
+                .create_warehouse(&owner, warehouse_id, ProjectId::from(uuid::Uuid::now_v7()))
+                .await
+                .unwrap();
+            authorizer
+                .create_namespace(&owner, ns_1, NamespaceParent::Warehouse(warehouse_id))
+                .await
+                .unwrap();
+            authorizer
+                .create_namespace(&owner, ns_2, NamespaceParent::Namespace(ns_1))
+                .await
+                .unwrap();
+            authorizer
+                .create_namespace(&owner, ns_3, NamespaceParent::Namespace(ns_2))
+                .await
+                .unwrap();
+            authorizer
+                .create_table(&owner, table_id, ns_3)
+                .await
+                .unwrap();
+
+            let can_load = || async {
+                authorizer
+                    .is_allowed_table_action(&viewer, table_id, &CatalogTableAction::CanGetMetadata)
+                    .await
+                    .unwrap()
+                    && authorizer
+                        .is_allowed_table_action(
+                            &viewer,
+                            table_id,
+                            &CatalogTableAction::CanReadData,
+                        )
+                        .await
+                        .unwrap()
+            };
+            assert!(!can_load().await);
+
+            // Only the top-level namespace is granted, no tuples for the levels below
+            authorizer
+                .write(
+                    Some(vec![TupleKey {
+                        user: viewer_id.to_openfga(),
+                        relation: NamespaceRelation::Select.to_string(),
+                        object: ns_1.to_openfga(),
+                        condition: None,
+                    }]),
+                    None,
+                )
+                .await
+                .unwrap();
+            assert!(can_load().await);
+            assert!(!authorizer
+                .is_allowed_table_action(&viewer, table_id, &CatalogTableAction::CanWriteData)
+                .await
+                .unwrap());
+        }
+
         #[tokio::test]
         async fn test_require_no_relations_own_relations() {
             let authorizer = new_authorizer_in_empty_store().await;
@@ -0,0 +1,227 @@
+//! `OpenFGA` stores of individual projects.
+//!
+//! If `LAKEKEEPER__OPENFGA__STORE_PER_PROJECT` is enabled, the relations of each
+//! project and of all objects in it are stored in a separate store named
+//! `{store_name}-{project_id}`. Server-level relations remain in the store `store_name`.
+//! The store of an object is selected by the project the object belongs to in the
+//! catalog, never by headers of the request.
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use openfga_rs::{open_fga_service_client::OpenFgaServiceClient, ListStoresRequest};
+use tokio::sync::RwLock;
+
+use super::{
+    client::ClientConnection,
+    entities::OpenFgaEntity as _,
+    migration::{get_auth_model_id, migrate},
+    service_ext::DEFAULT_MAX_PAGES,
+    ClientHelper as _, ModelVersion, OpenFGAError, OpenFGAResult,
+};
+use crate::{
+    api::iceberg::v1::Result,
+    service::{NamespaceIdentUuid, RoleId, TableIdentUuid, ViewIdentUuid},
+    ProjectId, WarehouseIdent,
+};
+
+/// Object whose relations are stored in the store of its project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectScopedObject {
+    Project(ProjectId),
+    Role(RoleId),
+    Warehouse(WarehouseIdent),
+    Namespace(NamespaceIdentUuid),
+    Table(TableIdentUuid),
+    View(ViewIdentUuid),
+}
+
+impl ProjectScopedObject {
+    pub(crate) fn to_openfga(self) -> String {
+        match self {
+            ProjectScopedObject::Project(project_id) => project_id.to_openfga(),
+            ProjectScopedObject::Role(role_id) => role_id.to_openfga(),
+            ProjectScopedObject::Warehouse(warehouse_id) => warehouse_id.to_openfga(),
+            ProjectScopedObject::Namespace(namespace_id) => namespace_id.to_openfga(),
+            ProjectScopedObject::Table(table_id) => table_id.to_openfga(),
+            ProjectScopedObject::View(view_id) => view_id.to_openfga(),
+        }
+    }
+}
+
+/// Looks up the project of objects in the catalog, so that their relations are
+/// read from and written to the store of that project.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ProjectResolver: Send + Sync + std::fmt::Debug + 'static {
+    /// Project `object` belongs to. `None` if the object does not exist.
+    ///
+    /// # Errors
+    /// If the catalog cannot be reached.
+    async fn resolve_project(&self, object: ProjectScopedObject) -> Result<Option<ProjectId>>;
+}
+
+/// Store and authorization model used for the relations of a project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProjectStore {
+    pub(crate) store_id: String,
+    pub(crate) authorization_model_id: String,
+}
+
+/// Creates and looks up the stores of projects.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub(crate) trait StoreProvisioner: Send + Sync {
+    /// Create the store `store_name` if it is missing, migrate it to the active
+    /// authorization model and return its IDs.
+    async fn provision_store(&self, store_name: &str) -> OpenFGAResult<ProjectStore>;
+
+    /// Names of all existing stores starting with `prefix`.
+    async fn list_store_names(&self, prefix: &str) -> OpenFGAResult<Vec<String>>;
+}
+
+#[async_trait]
+impl StoreProvisioner for OpenFgaServiceClient<ClientConnection> {
+    async fn provision_store(&self, store_name: &str) -> OpenFGAResult<ProjectStore> {
+        let mut client = self.clone();
+        migrate(&mut client, Some(store_name.to_string())).await?;
+        let store_id = client
+            .get_store_by_name(store_name)
+            .await?
+            .ok_or_else(|| OpenFGAError::StoreNotFound {
+                store: store_name.to_string(),
+            })?
+            .id;
+        let authorization_model_id =
+            get_auth_model_id(&mut client, store_id.clone(), ModelVersion::active()).await?;
+        Ok(ProjectStore {
+            store_id,
+            authorization_model_id,
+        })
+    }
+
+    async fn list_store_names(&self, prefix: &str) -> OpenFGAResult<Vec<String>> {
+        let mut client = self.clone();
+        let mut names = vec![];
+        let mut continuation_token = String::new();
+        for _ in 0..DEFAULT_MAX_PAGES {
+            let stores = client
+                .list_stores(ListStoresRequest {
+                    page_size: Some(100),
+                    continuation_token: continuation_token.clone(),
+                })
+                .await
+                .map_err(OpenFGAError::list_stores)?
+                .into_inner();
+            names.extend(
+                stores
+                    .stores
+                    .into_iter()
+                    .map(|s| s.name)
+                    .filter(|name| name.starts_with(prefix)),
+            );
+            if stores.continuation_token.is_empty() {
+                break;
+            }
+            continuation_token = stores.continuation_token;
+        }
+        Ok(names)
+    }
+}
+
+/// Stores of projects, provisioned on first use and cached for the lifetime of the server.
+pub(crate) struct ProjectStores {
+    provisioner: Arc<dyn StoreProvisioner>,
+    store_name: String,
+    stores: RwLock<HashMap<ProjectId, ProjectStore>>,
+    /// Set once the existing stores have been loaded by [`ProjectStores::all`].
+    all_loaded: RwLock<bool>,
+}
+
+impl std::fmt::Debug for ProjectStores {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProjectStores")
+            .field("store_name", &self.store_name)
+            .field("stores", &self.stores)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ProjectStores {
+    pub(crate) fn new(provisioner: Arc<dyn StoreProvisioner>, store_name: String) -> Self {
+        Self {
+            provisioner,
+            store_name,
+            stores: RwLock::default(),
+            all_loaded: RwLock::new(false),
+        }
+    }
+
+    fn store_name_prefix(&self) -> String {
+        format!("{}-", self.store_name)
+    }
+
+    /// Store of `project_id`. The store is created on first use.
+    pub(crate) async fn resolve(&self, project_id: ProjectId) -> OpenFGAResult<ProjectStore> {
+        if let Some(store) = self.stores.read().await.get(&project_id) {
+            return Ok(store.clone());
+        }
+
+        let mut stores = self.stores.write().await;
+        // Another request might have provisioned the store while we waited for the lock.
+        if let Some(store) = stores.get(&project_id) {
+            return Ok(store.clone());
+        }
+        let store_name = format!("{}{project_id}", self.store_name_prefix());
+        tracing::info!("Provisioning OpenFGA store {store_name} for project {project_id}");
+        let store = self.provisioner.provision_store(&store_name).await?;
+        stores.insert(project_id, store.clone());
+        Ok(store)
+    }
+
+    /// Stores of all projects, including projects not used since the server started.
+    pub(crate) async fn all(&self) -> OpenFGAResult<Vec<ProjectStore>> {
+        if !*self.all_loaded.read().await {
+            let mut all_loaded = self.all_loaded.write().await;
+            if !*all_loaded {
+                let prefix = self.store_name_prefix();
+                for name in self.provisioner.list_store_names(&prefix).await? {
+                    // Stores of other deployments sharing the prefix are skipped.
+                    if let Ok(project_id) = name[prefix.len()..].parse::<ProjectId>() {
+                        self.resolve(project_id).await?;
+                    }
+                }
+                *all_loaded = true;
+            }
+        }
+        Ok(self.stores.read().await.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_is_provisioned_once_per_project() {
+        let mut provisioner = MockStoreProvisioner::new();
+        provisioner
+            .expect_provision_store()
+            .times(2)
+            .returning(|store_name| {
+                Ok(ProjectStore {
+                    store_id: format!("id-{store_name}"),
+                    authorization_model_id: "model".to_string(),
+                })
+            });
+        let stores = ProjectStores::new(Arc::new(provisioner), "lakekeeper".to_string());
+
+        let project_1 = ProjectId::default();
+        let project_2 = ProjectId::default();
+        let store_1 = stores.resolve(project_1).await.unwrap();
+        assert_eq!(store_1, stores.resolve(project_1).await.unwrap());
+        assert_eq!(store_1.store_id, format!("id-lakekeeper-{project_1}"));
+        let store_2 = stores.resolve(project_2).await.unwrap();
+        assert_ne!(store_1, store_2);
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::{BTreeMap, HashMap};
+
+use openfga_rs::TypeDefinition;
+use strum::IntoEnumIterator;
+
+use super::{OpenFGAError, OpenFGAResult};
+use crate::{
+    config::OpenFGARelationMapping,
+    service::authz::{
+        implementations::FgaType, CatalogNamespaceAction, CatalogProjectAction, CatalogRoleAction,
+        CatalogServerAction, CatalogTableAction, CatalogViewAction, CatalogWarehouseAction,
+    },
+};
+
+/// Relations checked for catalog actions, validated against the
+/// authorization model of the store.
+///
+/// Actions without a mapping check the relation of the same name on the
+/// object the action is performed on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RelationMapping {
+    // object type -> action -> mapping
+    mapping: HashMap<String, HashMap<String, OpenFGARelationMapping>>,
+}
+
+impl RelationMapping {
+    /// Validate `config` against the type definitions of the authorization model.
+    ///
+    /// # Errors
+    /// Fails if an action is unknown, or if a mapped object type or relation
+    /// is not part of the authorization model.
+    pub(crate) fn try_new(
+        config: &BTreeMap<String, BTreeMap<String, OpenFGARelationMapping>>,
+        type_definitions: &[TypeDefinition],
+    ) -> OpenFGAResult<Self> {
+        let mut mapping: HashMap<String, HashMap<String, _>> = HashMap::new();
+        for (object_type, actions) in config {
+            let known_actions = catalog_actions(object_type).ok_or_else(|| {
+                OpenFGAError::InvalidRelationMapping(format!("Unknown object type `{object_type}`"))
+            })?;
+
+            for (action, target) in actions {
+                if !known_actions.contains(action) {
+                    return Err(OpenFGAError::InvalidRelationMapping(format!(
+                        "Unknown action `{action}` for object type `{object_type}`. Expected one of: {}",
+                        known_actions.join(", ")
+                    )));
+                }
+
+                let target_type = target.object_type.as_ref().unwrap_or(object_type);
+                let type_definition = type_definitions
+                    .iter()
+                    .find(|t| &t.r#type == target_type)
+                    .ok_or_else(|| {
+                        OpenFGAError::InvalidRelationMapping(format!(
+                            "Object type `{target_type}` of action `{object_type}.{action}` is not part of the authorization model"
+                        ))
+                    })?;
+                if !type_definition.relations.contains_key(&target.relation) {
+                    return Err(OpenFGAError::InvalidRelationMapping(format!(
+                        "Relation `{}` of action `{object_type}.{action}` is not defined for type `{target_type}`",
+                        target.relation
+                    )));
+                }
+
+                mapping
+                    .entry(object_type.clone())
+                    .or_default()
+                    .insert(action.clone(), target.clone());
+            }
+        }
+
+        Ok(Self { mapping })
+    }
+
+    /// Relation and object to check for `action` on `object`,
+    /// where `object` is an `OpenFGA` object such as `table:<id>`.
+    pub(crate) fn resolve(&self, action: String, object: String) -> (String, String) {
+        let Some((object_type, id)) = object.split_once(':') else {
+            return (action, object);
+        };
+        let (relation, target_type) = self.resolve_type(action, object_type);
+        if target_type == object_type {
+            (relation, object)
+        } else {
+            (relation, format!("{target_type}:{id}"))
+        }
+    }
+
+    /// Relation and object type to list objects by for `action` on objects
+    /// of `object_type`. Listed objects keep the id of the original object.
+    pub(crate) fn resolve_type(&self, action: String, object_type: &str) -> (String, String) {
+        match self
+            .mapping
+            .get(object_type)
+            .and_then(|actions| actions.get(&action))
+        {
+            Some(OpenFGARelationMapping {
+                relation,
+                object_type: Some(target_type),
+            }) => (relation.clone(), target_type.clone()),
+            Some(OpenFGARelationMapping {
+                relation,
+                object_type: None,
+            }) => (relation.clone(), object_type.to_string()),
+            None => (action, object_type.to_string()),
+        }
+    }
+}
+
+fn catalog_actions(object_type: &str) -> Option<Vec<String>> {
+    fn names<T: IntoEnumIterator + std::fmt::Display>() -> Vec<String> {
+        T::iter().map(|a| a.to_string()).collect()
+    }
+
+    let actions = match object_type.parse::<FgaType>().ok()? {
+        FgaType::Role => names::<CatalogRoleAction>(),
+        FgaType::Server => names::<CatalogServerAction>(),
+        FgaType::Project => names::<CatalogProjectAction>(),
+        FgaType::Warehouse => names::<CatalogWarehouseAction>(),
+        FgaType::Namespace => names::<CatalogNamespaceAction>(),
+        FgaType::Table => names::<CatalogTableAction>(),
+        FgaType::View => names::<CatalogViewAction>(),
+        // User actions are checked on the server
+        FgaType::User | FgaType::ModelVersion | FgaType::AuthModelId => return None,
+    };
+    Some(actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::authz::implementations::openfga::ModelVersion;
+
+    fn config(
+        object_type: &str,
+        action: &str,
+        relation: &str,
+    ) -> BTreeMap<String, BTreeMap<String, OpenFGARelationMapping>> {
+        BTreeMap::from_iter([(
+            object_type.to_string(),
+            BTreeMap::from_iter([(
+                action.to_string(),
+                OpenFGARelationMapping {
+                    relation: relation.to_string(),
+                    object_type: None,
+                },
+            )]),
+        )])
+    }
+
+    fn try_new(
+        config: &BTreeMap<String, BTreeMap<String, OpenFGARelationMapping>>,
+    ) -> OpenFGAResult<RelationMapping> {
+        RelationMapping::try_new(
+            config,
+            &ModelVersion::active().get_model_ref().type_definitions,
+        )
+    }
+
+    #[test]
+    fn test_resolve_mapped_and_unmapped_actions() {
+        let mapping = try_new(&config("table", "can_commit", "modify")).unwrap();
+        assert_eq!(
+            mapping.resolve("can_commit".to_string(), "table:t1".to_string()),
+            ("modify".to_string(), "table:t1".to_string())
+        );
+        assert_eq!(
+            mapping.resolve("can_drop".to_string(), "table:t1".to_string()),
+            ("can_drop".to_string(), "table:t1".to_string())
+        );
+        assert_eq!(
+            mapping.resolve("can_commit".to_string(), "view:v1".to_string()),
+            ("can_commit".to_string(), "view:v1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_type() {
+        let mapping = try_new(&config("project", "can_include_in_list", "describe")).unwrap();
+        assert_eq!(
+            mapping.resolve_type("can_include_in_list".to_string(), "project"),
+            ("describe".to_string(), "project".to_string())
+        );
+        assert_eq!(
+            mapping.resolve_type("can_include_in_list".to_string(), "warehouse"),
+            ("can_include_in_list".to_string(), "warehouse".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_mappings_are_rejected() {
+        for config in [
+            config("table", "can_commit", "writer"),
+            config("table", "can_teleport", "modify"),
+            config("dataset", "can_commit", "modify"),
+            config("user", "can_update", "can_update_users"),
+        ] {
+            let err = try_new(&config).unwrap_err();
+            assert!(
+                matches!(err, OpenFGAError::InvalidRelationMapping(_)),
+                "{err:?}"
+            );
+        }
+
+        let mut config = config("table", "can_commit", "modify");
+        config
+            .get_mut("table")
+            .unwrap()
+            .get_mut("can_commit")
+            .unwrap()
+            .object_type = Some("dataset".to_string());
+        let err = try_new(&config).unwrap_err();
+        assert!(matches!(err, OpenFGAError::InvalidRelationMapping(_)));
+    }
+}
@@ -10,8 +10,9 @@ use super::{
 use crate::service::{
     authn::UserId,
     authz::{
-        implementations::FgaType, CatalogNamespaceAction, CatalogProjectAction, CatalogRoleAction,
-        CatalogServerAction, CatalogTableAction, CatalogViewAction, CatalogWarehouseAction,
+        display_names::PrincipalId, implementations::FgaType, CatalogNamespaceAction,
+        CatalogProjectAction, CatalogRoleAction, CatalogServerAction, CatalogTableAction,
+        CatalogViewAction, CatalogWarehouseAction,
     },
     Actor, RoleId,
 };
@@ -20,7 +21,11 @@ pub(super) trait Assignment: Sized {
     type Relation: ReducedRelation + GrantableRelation;
     fn try_from_user(user: &str, relation: &Self::Relation) -> OpenFGAResult<Self>;
 
-    fn openfga_user(&self) -> String;
+    fn user_or_role(&self) -> &UserOrRole;
+
+    fn openfga_user(&self) -> String {
+        self.user_or_role().to_openfga()
+    }
 
     fn relation(&self) -> Self::Relation;
 }
@@ -84,6 +89,15 @@ impl From<RoleId> for UserOrRole {
     }
 }
 
+impl From<&UserOrRole> for PrincipalId {
+    fn from(user_or_role: &UserOrRole) -> Self {
+        match user_or_role {
+            UserOrRole::User(user) => PrincipalId::User(user.clone()),
+            UserOrRole::Role(role) => PrincipalId::Role(*role.role()),
+        }
+    }
+}
+
 impl ParseOpenFgaEntity for UserOrRole {
     fn try_from_openfga_id(r#type: FgaType, id: &str) -> OpenFGAResult<Self> {
         match r#type {
@@ -176,9 +190,9 @@ impl Assignment for RoleAssignment {
         }
     }
 
-    fn openfga_user(&self) -> String {
+    fn user_or_role(&self) -> &UserOrRole {
         match self {
-            RoleAssignment::Ownership(user) | RoleAssignment::Assignee(user) => user.to_openfga(),
+            RoleAssignment::Ownership(user) | RoleAssignment::Assignee(user) => user,
         }
     }
 
@@ -305,9 +319,9 @@ impl Assignment for ServerAssignment {
         }
     }
 
-    fn openfga_user(&self) -> String {
+    fn user_or_role(&self) -> &UserOrRole {
         match self {
-            ServerAssignment::Admin(user) | ServerAssignment::Operator(user) => user.to_openfga(),
+            ServerAssignment::Admin(user) | ServerAssignment::Operator(user) => user,
         }
     }
 
@@ -360,6 +374,11 @@ impl ReducedRelation for CatalogServerAction {
             CatalogServerAction::CanDeleteUsers => ServerRelation::CanDeleteUsers,
             CatalogServerAction::CanListUsers => ServerRelation::CanListAllProjects,
             CatalogServerAction::CanProvisionUsers => ServerRelation::CanProvisionUsers,
+            // The audit log is restricted to server admins, operators are not included.
+            CatalogServerAction::CanReadAuditLog => ServerRelation::Admin,
+            CatalogServerAction::CanRemoveTableImmutability => ServerRelation::Admin,
+            CatalogServerAction::CanForceDeleteWarehouse => ServerRelation::Admin,
+            CatalogServerAction::CanListSecretReferences => ServerRelation::Admin,
         }
     }
 }
@@ -500,7 +519,7 @@ impl Assignment for ProjectAssignment {
         }
     }
 
-    fn openfga_user(&self) -> String {
+    fn user_or_role(&self) -> &UserOrRole {
         match self {
             ProjectAssignment::ProjectAdmin(user)
             | ProjectAssignment::SecurityAdmin(user)
@@ -509,7 +528,7 @@ impl Assignment for ProjectAssignment {
             | ProjectAssignment::Describe(user)
             | ProjectAssignment::Select(user)
             | ProjectAssignment::Create(user)
-            | ProjectAssignment::Modify(user) => user.to_openfga(),
+            | ProjectAssignment::Modify(user) => user,
         }
     }
 
@@ -728,7 +747,7 @@ impl Assignment for WarehouseAssignment {
         }
     }
 
-    fn openfga_user(&self) -> String {
+    fn user_or_role(&self) -> &UserOrRole {
         match self {
             WarehouseAssignment::Ownership(user)
             | WarehouseAssignment::PassGrants(user)
@@ -736,7 +755,7 @@ impl Assignment for WarehouseAssignment {
             | WarehouseAssignment::Select(user)
             | WarehouseAssignment::Create(user)
             | WarehouseAssignment::Modify(user)
-            | WarehouseAssignment::ManageGrants(user) => user.to_openfga(),
+            | WarehouseAssignment::ManageGrants(user) => user,
         }
     }
 
@@ -843,6 +862,14 @@ impl ReducedRelation for CatalogWarehouseAction {
             CatalogWarehouseAction::CanModifySoftDeletion => {
                 WarehouseRelation::CanModifySoftDeletion
             }
+            CatalogWarehouseAction::CanModifyFeatureFlags
+            | CatalogWarehouseAction::CanManageTableLocks
+            | CatalogWarehouseAction::CanUpdateSnapshotLimit
+            | CatalogWarehouseAction::CanUpdateCredentialRefresh
+            | CatalogWarehouseAction::CanUpdateAuthenticationMethods
+            | CatalogWarehouseAction::CanManageTableTemplates
+            | CatalogWarehouseAction::CanUpdateDefaultNamespace => WarehouseRelation::Modify,
+            CatalogWarehouseAction::CanListSecretReferences => WarehouseRelation::Ownership,
             CatalogWarehouseAction::CanUse => WarehouseRelation::CanUse,
             CatalogWarehouseAction::CanIncludeInList => WarehouseRelation::CanIncludeInList,
             CatalogWarehouseAction::CanDeactivate => WarehouseRelation::CanDeactivate,
@@ -983,7 +1010,7 @@ impl Assignment for NamespaceAssignment {
         }
     }
 
-    fn openfga_user(&self) -> String {
+    fn user_or_role(&self) -> &UserOrRole {
         match self {
             NamespaceAssignment::Ownership(user)
             | NamespaceAssignment::PassGrants(user)
@@ -991,7 +1018,7 @@ impl Assignment for NamespaceAssignment {
             | NamespaceAssignment::Describe(user)
             | NamespaceAssignment::Select(user)
             | NamespaceAssignment::Create(user)
-            | NamespaceAssignment::Modify(user) => user.to_openfga(),
+            | NamespaceAssignment::Modify(user) => user,
         }
     }
 
@@ -1195,14 +1222,14 @@ impl Assignment for TableAssignment {
         }
     }
 
-    fn openfga_user(&self) -> String {
+    fn user_or_role(&self) -> &UserOrRole {
         match self {
             TableAssignment::Ownership(user)
             | TableAssignment::PassGrants(user)
             | TableAssignment::ManageGrants(user)
             | TableAssignment::Describe(user)
             | TableAssignment::Select(user)
-            | TableAssignment::Modify(user) => user.to_openfga(),
+            | TableAssignment::Modify(user) => user,
         }
     }
 
@@ -1392,13 +1419,13 @@ impl Assignment for ViewAssignment {
         }
     }
 
-    fn openfga_user(&self) -> String {
+    fn user_or_role(&self) -> &UserOrRole {
         match self {
             ViewAssignment::Ownership(user)
             | ViewAssignment::PassGrants(user)
             | ViewAssignment::ManageGrants(user)
             | ViewAssignment::Describe(user)
-            | ViewAssignment::Modify(user) => user.to_openfga(),
+            | ViewAssignment::Modify(user) => user,
         }
     }
 
@@ -9,12 +9,23 @@ use openfga_rs::{
 
 use super::{OpenFGAError, OpenFGAResult};
 
-const DEFAULT_MAX_PAGES: u32 = 500;
+pub(crate) const DEFAULT_MAX_PAGES: u32 = 500;
 const PAGE_SIZE: i16 = 100;
 /// Maximum number of tuples to write in a single request.
 /// Limit set by `OpenFGA`
 pub(crate) const MAX_TUPLES_PER_WRITE: i32 = 100;
 
+/// Tuples returned by [`ClientHelper::read_pages`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TuplePage {
+    pub tuples: Vec<Tuple>,
+    /// Set if `max_pages` were read before all tuples were returned.
+    /// Pass it to the next call to continue reading.
+    /// Results with a continuation token are partial and must not be used
+    /// for authorization decisions.
+    pub continuation_token: Option<String>,
+}
+
 #[async_trait::async_trait]
 pub(crate) trait ClientHelper {
     async fn get_store_by_name(&mut self, store_name: &str) -> OpenFGAResult<Option<Store>>;
@@ -36,12 +47,14 @@ pub(crate) trait ClientHelper {
         store_id: String,
     ) -> OpenFGAResult<Vec<super::models::AuthorizationModel>>;
 
-    /// Read all pages of a read request
-    async fn read_all_pages(
+    /// Read up to `max_pages` pages of a read request, starting at `continuation_token`.
+    async fn read_pages(
         &mut self,
         store_id: &str,
         tuple: ReadRequestTupleKey,
-    ) -> OpenFGAResult<Vec<Tuple>>;
+        max_pages: u32,
+        continuation_token: Option<String>,
+    ) -> OpenFGAResult<TuplePage>;
 }
 
 #[async_trait::async_trait]
@@ -182,16 +195,17 @@ where
         Ok(models)
     }
 
-    async fn read_all_pages(
+    async fn read_pages(
         &mut self,
         store_id: &str,
         tuple: ReadRequestTupleKey,
-    ) -> OpenFGAResult<Vec<Tuple>> {
-        let mut continuation_token = String::new();
+        max_pages: u32,
+        continuation_token: Option<String>,
+    ) -> OpenFGAResult<TuplePage> {
+        let mut continuation_token = continuation_token.unwrap_or_default();
         let mut tuples = Vec::new();
-        let mut count = 0;
 
-        loop {
+        for _ in 0..max_pages.max(1) {
             let read_request = openfga_rs::ReadRequest {
                 store_id: store_id.to_owned(),
                 tuple_key: Some(tuple.clone()),
@@ -208,20 +222,22 @@ where
                 })?
                 .into_inner();
             tuples.extend(response.tuples);
-            continuation_token.clone_from(&response.continuation_token);
-            if continuation_token.is_empty() || count > DEFAULT_MAX_PAGES {
-                if count > DEFAULT_MAX_PAGES {
-                    return Err(OpenFGAError::TooManyPages {
-                        max_pages: DEFAULT_MAX_PAGES,
-                        tuple,
-                    });
-                }
-                break;
+            continuation_token = response.continuation_token;
+            if continuation_token.is_empty() {
+                return Ok(TuplePage {
+                    tuples,
+                    continuation_token: None,
+                });
             }
-            count += 1;
         }
 
-        Ok(tuples)
+        tracing::debug!(
+            "Stopped reading tuples for {tuple:?} after {max_pages} pages. Returning partial result."
+        );
+        Ok(TuplePage {
+            tuples,
+            continuation_token: Some(continuation_token),
+        })
     }
 }
 
@@ -386,12 +402,13 @@ mod test {
                 object: "model_version:".to_string(),
             };
 
-            let tuples = client
-                .read_all_pages(&store.id, read_request.clone())
+            let page = client
+                .read_pages(&store.id, read_request.clone(), DEFAULT_MAX_PAGES, None)
                 .await
                 .unwrap();
 
-            assert!(tuples.is_empty());
+            assert!(page.tuples.is_empty());
+            assert!(page.continuation_token.is_none());
 
             let authorization_model_id = create_model(&mut client, &store.id).await;
 
@@ -414,12 +431,33 @@ mod test {
                     .unwrap();
             }
 
-            let tuples = client
-                .read_all_pages(&store.id, read_request.clone())
+            let page = client
+                .read_pages(&store.id, read_request.clone(), DEFAULT_MAX_PAGES, None)
                 .await
                 .unwrap();
 
-            assert_eq!(tuples.len(), 501);
+            assert_eq!(page.tuples.len(), 501);
+            assert!(page.continuation_token.is_none());
+
+            // Reading beyond `max_pages` returns a partial result with a continuation token
+            let partial = client
+                .read_pages(&store.id, read_request.clone(), 2, None)
+                .await
+                .unwrap();
+            assert_eq!(partial.tuples.len(), 2 * PAGE_SIZE as usize);
+            assert!(partial.continuation_token.is_some());
+
+            let rest = client
+                .read_pages(
+                    &store.id,
+                    read_request.clone(),
+                    DEFAULT_MAX_PAGES,
+                    partial.continuation_token,
+                )
+                .await
+                .unwrap();
+            assert_eq!(rest.tuples.len(), 501 - 2 * PAGE_SIZE as usize);
+            assert!(rest.continuation_token.is_none());
         }
 
         #[tokio::test]
@@ -456,19 +494,24 @@ mod test {
                 .await
                 .unwrap();
 
-            let tuples = client
-                .read_all_pages(
+            let page = client
+                .read_pages(
                     &store.id,
                     ReadRequestTupleKey {
                         user: "auth_model_id:*".to_string(),
                         relation: "exists".to_string(),
                         object: "model_version:".to_string(),
                     },
+                    DEFAULT_MAX_PAGES,
+                    None,
                 )
                 .await
                 .unwrap();
 
-            assert_eq!(i32::try_from(tuples.len()).unwrap(), MAX_TUPLES_PER_WRITE);
+            assert_eq!(
+                i32::try_from(page.tuples.len()).unwrap(),
+                MAX_TUPLES_PER_WRITE
+            );
         }
     }
 }
@@ -9,12 +9,18 @@ use super::{
 };
 use crate::{api::iceberg::v1::Result, request_metadata::RequestMetadata};
 
+pub mod display_names;
+pub mod groups;
 pub mod implementations;
+pub mod reconciler;
 
 use iceberg_ext::catalog::rest::ErrorModel;
 pub use implementations::allow_all::AllowAllAuthorizer;
 
-use crate::{api::ApiContext, service::authn::UserId};
+use crate::{
+    api::ApiContext,
+    service::{audit::record_decision, authn::UserId},
+};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
 #[strum(serialize_all = "snake_case")]
@@ -40,6 +46,14 @@ pub enum CatalogServerAction {
     CanListUsers,
     /// Can provision user
     CanProvisionUsers,
+    /// Can read the audit log of authorization decisions.
+    CanReadAuditLog,
+    /// Can remove the write-once flag of immutable tables.
+    CanRemoveTableImmutability,
+    /// Can delete warehouses together with all their contents.
+    CanForceDeleteWarehouse,
+    /// Can list the secrets referenced by any warehouse.
+    CanListSecretReferences,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
@@ -81,6 +95,14 @@ pub enum CatalogWarehouseAction {
     CanRename,
     CanListDeletedTabulars,
     CanModifySoftDeletion,
+    CanModifyFeatureFlags,
+    CanManageTableLocks,
+    CanListSecretReferences,
+    CanUpdateSnapshotLimit,
+    CanUpdateCredentialRefresh,
+    CanUpdateAuthenticationMethods,
+    CanManageTableTemplates,
+    CanUpdateDefaultNamespace,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
@@ -145,12 +167,56 @@ pub enum ListProjectsResponse {
     All,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NamespaceParent {
     Warehouse(WarehouseIdent),
     Namespace(NamespaceIdentUuid),
 }
 
+/// An object of the catalog hierarchy together with its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogObject {
+    Warehouse {
+        warehouse_id: WarehouseIdent,
+        project_id: ProjectId,
+    },
+    Namespace {
+        namespace_id: NamespaceIdentUuid,
+        parent: NamespaceParent,
+    },
+    Table {
+        table_id: TableIdentUuid,
+        namespace_id: NamespaceIdentUuid,
+    },
+    View {
+        view_id: ViewIdentUuid,
+        namespace_id: NamespaceIdentUuid,
+    },
+}
+
+impl CatalogObject {
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CatalogObject::Warehouse { .. } => "warehouse",
+            CatalogObject::Namespace { .. } => "namespace",
+            CatalogObject::Table { .. } => "table",
+            CatalogObject::View { .. } => "view",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum BaselineRelations {
+    /// All baseline relations exist.
+    Consistent,
+    /// Some baseline relations are missing and have not been repaired.
+    Missing,
+    /// Missing baseline relations have been re-written.
+    Repaired,
+}
+
 #[async_trait::async_trait]
 /// Interface to provide AuthZ functions to the catalog.
 /// The provided `Actor` argument of all methods except `check_actor`
@@ -315,6 +381,16 @@ where
         namespace_id: NamespaceIdentUuid,
     ) -> Result<()>;
 
+    /// Hook that is called when a namespace is moved to a different parent.
+    /// This is used to replace the relations connecting the namespace to its parent.
+    async fn move_namespace(
+        &self,
+        metadata: &RequestMetadata,
+        namespace_id: NamespaceIdentUuid,
+        previous_parent: NamespaceParent,
+        new_parent: NamespaceParent,
+    ) -> Result<()>;
+
     /// Hook that is called when a new table is created.
     /// This is used to set up the initial permissions for the table.
     async fn create_table(
@@ -341,8 +417,20 @@ where
     /// This is used to clean up permissions for the view.
     async fn delete_view(&self, view_id: ViewIdentUuid) -> Result<()>;
 
+    /// Verify that the baseline relations of `object` exist, i.e. the relations written
+    /// by the corresponding `create_*` hook that connect the object to its parent.
+    /// Owners of parent objects reach the object only through these relations.
+    /// If `repair` is true, missing relations are re-written.
+    async fn check_baseline_relations(
+        &self,
+        object: CatalogObject,
+        repair: bool,
+    ) -> Result<BaselineRelations>;
+
     async fn require_search_users(&self, metadata: &RequestMetadata) -> Result<()> {
-        if self.can_search_users(metadata).await? {
+        let allowed = self.can_search_users(metadata).await?;
+        record_decision(metadata, "can_search_users", "server", allowed);
+        if allowed {
             Ok(())
         } else {
             Err(ErrorModel::forbidden(
@@ -360,10 +448,11 @@ where
         user_id: &UserId,
         action: &CatalogUserAction,
     ) -> Result<()> {
-        if self
+        let allowed = self
             .is_allowed_user_action(metadata, user_id, action)
-            .await?
-        {
+            .await?;
+        record_decision(metadata, action, format!("user:{user_id}"), allowed);
+        if allowed {
             Ok(())
         } else {
             Err(ErrorModel::forbidden(
@@ -381,10 +470,11 @@ where
         role_id: RoleId,
         action: &CatalogRoleAction,
     ) -> Result<()> {
-        if self
+        let allowed = self
             .is_allowed_role_action(metadata, role_id, action)
-            .await?
-        {
+            .await?;
+        record_decision(metadata, action, format!("role:{role_id}"), allowed);
+        if allowed {
             Ok(())
         } else {
             Err(ErrorModel::forbidden(
@@ -401,7 +491,9 @@ where
         metadata: &RequestMetadata,
         action: &CatalogServerAction,
     ) -> Result<()> {
-        if self.is_allowed_server_action(metadata, action).await? {
+        let allowed = self.is_allowed_server_action(metadata, action).await?;
+        record_decision(metadata, action, "server", allowed);
+        if allowed {
             Ok(())
         } else {
             let actor = metadata.actor();
@@ -420,10 +512,11 @@ where
         project_id: ProjectId,
         action: &CatalogProjectAction,
     ) -> Result<()> {
-        if self
+        let allowed = self
             .is_allowed_project_action(metadata, project_id, action)
-            .await?
-        {
+            .await?;
+        record_decision(metadata, action, format!("project:{project_id}"), allowed);
+        if allowed {
             Ok(())
         } else {
             let actor = metadata.actor();
@@ -442,10 +535,16 @@ where
         warehouse_id: WarehouseIdent,
         action: &CatalogWarehouseAction,
     ) -> Result<()> {
-        if self
+        let allowed = self
             .is_allowed_warehouse_action(metadata, warehouse_id, action)
-            .await?
-        {
+            .await?;
+        record_decision(
+            metadata,
+            action,
+            format!("warehouse:{warehouse_id}"),
+            allowed,
+        );
+        if allowed {
             Ok(())
         } else {
             let actor = metadata.actor();
@@ -476,10 +575,13 @@ where
         match namespace_id {
             Ok(None) => Err(ErrorModel::forbidden(msg, typ, None).into()),
             Ok(Some(namespace_id)) => {
-                if self
+                let object = format!("namespace:{namespace_id}");
+                let action_name = action.to_string();
+                let allowed = self
                     .is_allowed_namespace_action(metadata, namespace_id, action)
-                    .await?
-                {
+                    .await?;
+                record_decision(metadata, action_name, object, allowed);
+                if allowed {
                     Ok(namespace_id)
                 } else {
                     Err(ErrorModel::forbidden(msg, typ, None).into())
@@ -506,10 +608,13 @@ where
         match table_id {
             Ok(None) => Err(ErrorModel::forbidden(msg, typ, None).into()),
             Ok(Some(table_id)) => {
-                if self
+                let object = format!("table:{}", table_id.table_uuid());
+                let action_name = action.to_string();
+                let allowed = self
                     .is_allowed_table_action(metadata, table_id.table_uuid(), action)
-                    .await?
-                {
+                    .await?;
+                record_decision(metadata, action_name, object, allowed);
+                if allowed {
                     Ok(table_id)
                 } else {
                     Err(ErrorModel::forbidden(msg, typ, None).into())
@@ -536,10 +641,13 @@ where
         match view_id {
             Ok(None) => Err(ErrorModel::forbidden(msg, typ, None).into()),
             Ok(Some(view_id)) => {
-                if self
+                let object = format!("view:{view_id}");
+                let action_name = action.to_string();
+                let allowed = self
                     .is_allowed_view_action(metadata, view_id, action)
-                    .await?
-                {
+                    .await?;
+                record_decision(metadata, action_name, object, allowed);
+                if allowed {
                     Ok(view_id)
                 } else {
                     Err(ErrorModel::forbidden(msg, typ, None).into())
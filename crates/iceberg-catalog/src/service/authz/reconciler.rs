@@ -0,0 +1,373 @@
+use std::{collections::VecDeque, time::Duration};
+
+use axum_prometheus::metrics;
+use iceberg::NamespaceIdent;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{
+        iceberg::{types::PageToken, v1::PaginationQuery},
+        Result,
+    },
+    service::{
+        authz::{Authorizer, BaselineRelations, CatalogObject, NamespaceParent},
+        task_queue::{seconds_to_std_duration, std_duration_to_seconds},
+        Catalog, ListFlags, ListNamespacesQuery, NamespaceIdentUuid, TableIdentUuid,
+        TabularIdentUuid, Transaction, ViewIdentUuid, WarehouseStatus,
+    },
+    WarehouseIdent,
+};
+
+const PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuthzReconcilerConfig {
+    /// Periodically verify the baseline authorization relations of all catalog objects.
+    pub enabled: bool,
+    /// Re-write missing baseline relations. If false, discrepancies are only reported.
+    pub repair: bool,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub interval: Duration,
+    /// Maximum number of pages listed per warehouse or namespace in a single run.
+    pub max_pages: u32,
+}
+
+impl Default for AuthzReconcilerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            repair: false,
+            interval: Duration::from_secs(24 * 3600),
+            max_pages: 100,
+        }
+    }
+}
+
+/// Result of a single reconciliation run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReconciliationReport {
+    /// Number of objects checked.
+    pub checked: usize,
+    /// Objects with missing baseline relations that have not been repaired.
+    pub missing: Vec<CatalogObject>,
+    /// Objects whose missing baseline relations have been re-written.
+    pub repaired: Vec<CatalogObject>,
+    /// True if a listing hit `max_pages`, so that some objects were not checked.
+    pub truncated: bool,
+}
+
+impl ReconciliationReport {
+    async fn check<A: Authorizer>(&mut self, authorizer: &A, object: CatalogObject, repair: bool) {
+        let status = match authorizer.check_baseline_relations(object, repair).await {
+            Ok(status) => status,
+            Err(e) => {
+                tracing::error!(?object, "Failed to check baseline relations: {:?}", e.error);
+                return;
+            }
+        };
+        self.checked += 1;
+        metrics::counter!(
+            "lakekeeper_authz_reconciler_objects_total",
+            "kind" => object.kind(),
+            "status" => status.to_string()
+        )
+        .increment(1);
+        match status {
+            BaselineRelations::Consistent => {}
+            BaselineRelations::Missing => {
+                tracing::warn!(
+                    ?object,
+                    "Catalog object is missing baseline authorization relations"
+                );
+                self.missing.push(object);
+            }
+            BaselineRelations::Repaired => {
+                tracing::info!(?object, "Re-wrote missing baseline authorization relations");
+                self.repaired.push(object);
+            }
+        }
+    }
+
+    fn truncate(&mut self, what: &str, max_pages: u32) {
+        tracing::warn!(
+            "Stopped listing {what} after {max_pages} pages. Remaining objects are not checked in this run."
+        );
+        self.truncated = true;
+    }
+}
+
+/// Periodically runs [`reconcile`]. Never returns; spawn it as a background task.
+pub async fn authz_reconciler_task<C: Catalog, A: Authorizer>(
+    catalog_state: C::State,
+    authorizer: A,
+    config: AuthzReconcilerConfig,
+) {
+    loop {
+        tokio::time::sleep(config.interval).await;
+
+        match reconcile::<C, A>(catalog_state.clone(), &authorizer, &config).await {
+            Ok(report) => tracing::info!(
+                checked = report.checked,
+                missing = report.missing.len(),
+                repaired = report.repaired.len(),
+                truncated = report.truncated,
+                "Finished authorization consistency check"
+            ),
+            Err(e) => tracing::error!("Authorization consistency check failed: {:?}", e.error),
+        }
+    }
+}
+
+/// Verify that every warehouse, namespace, table and view in the catalog has its
+/// baseline authorization relations. Every page is listed in a separate short
+/// read transaction, so that request serving is never blocked.
+pub async fn reconcile<C: Catalog, A: Authorizer>(
+    catalog_state: C::State,
+    authorizer: &A,
+    config: &AuthzReconcilerConfig,
+) -> Result<ReconciliationReport> {
+    let mut report = ReconciliationReport::default();
+
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let projects = C::list_projects(None, t.transaction()).await?;
+    t.commit().await?;
+
+    for project in projects {
+        let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+        let warehouses = C::list_warehouses(
+            project.project_id,
+            Some(vec![WarehouseStatus::Active, WarehouseStatus::Inactive]),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        for warehouse in warehouses {
+            report
+                .check(
+                    authorizer,
+                    CatalogObject::Warehouse {
+                        warehouse_id: warehouse.id,
+                        project_id: project.project_id,
+                    },
+                    config.repair,
+                )
+                .await;
+            reconcile_warehouse::<C, A>(
+                catalog_state.clone(),
+                authorizer,
+                config,
+                warehouse.id,
+                &mut report,
+            )
+            .await?;
+        }
+    }
+
+    Ok(report)
+}
+
+async fn reconcile_warehouse<C: Catalog, A: Authorizer>(
+    catalog_state: C::State,
+    authorizer: &A,
+    config: &AuthzReconcilerConfig,
+    warehouse_id: WarehouseIdent,
+    report: &mut ReconciliationReport,
+) -> Result<()> {
+    let mut parents: VecDeque<(Option<NamespaceIdent>, NamespaceParent)> =
+        VecDeque::from([(None, NamespaceParent::Warehouse(warehouse_id))]);
+
+    while let Some((parent_ident, parent)) = parents.pop_front() {
+        let mut page_token = PageToken::NotSpecified;
+        for page in 0.. {
+            if page >= config.max_pages {
+                report.truncate(
+                    &format!("namespaces of warehouse {warehouse_id}"),
+                    config.max_pages,
+                );
+                break;
+            }
+            let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+            let namespaces = C::list_namespaces(
+                warehouse_id,
+                &ListNamespacesQuery {
+                    page_token: page_token.clone(),
+                    page_size: Some(PAGE_SIZE),
+                    parent: parent_ident.clone(),
+                    return_uuids: true,
+                },
+                t.transaction(),
+            )
+            .await?;
+            t.commit().await?;
+
+            let is_last_page = i64::try_from(namespaces.len()).unwrap_or(i64::MAX) < PAGE_SIZE;
+            page_token = namespaces.next_token().map(ToString::to_string).into();
+            for (namespace_id, namespace_ident) in namespaces {
+                report
+                    .check(
+                        authorizer,
+                        CatalogObject::Namespace {
+                            namespace_id,
+                            parent,
+                        },
+                        config.repair,
+                    )
+                    .await;
+                reconcile_namespace_tabulars::<C, A>(
+                    catalog_state.clone(),
+                    authorizer,
+                    config,
+                    warehouse_id,
+                    namespace_id,
+                    report,
+                )
+                .await?;
+                parents.push_back((
+                    Some(namespace_ident),
+                    NamespaceParent::Namespace(namespace_id),
+                ));
+            }
+            if is_last_page {
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn reconcile_namespace_tabulars<C: Catalog, A: Authorizer>(
+    catalog_state: C::State,
+    authorizer: &A,
+    config: &AuthzReconcilerConfig,
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    report: &mut ReconciliationReport,
+) -> Result<()> {
+    let mut page_token = PageToken::NotSpecified;
+    for page in 0.. {
+        if page >= config.max_pages {
+            report.truncate(
+                &format!("tabulars of namespace {namespace_id}"),
+                config.max_pages,
+            );
+            break;
+        }
+        let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+        let tabulars = C::list_tabulars(
+            warehouse_id,
+            Some(namespace_id),
+            ListFlags::all(),
+            t.transaction(),
+            PaginationQuery::new(page_token.clone(), Some(PAGE_SIZE)),
+        )
+        .await?;
+        t.commit().await?;
+
+        let is_last_page = i64::try_from(tabulars.len()).unwrap_or(i64::MAX) < PAGE_SIZE;
+        page_token = tabulars.next_token().map(ToString::to_string).into();
+        for (tabular_id, _) in tabulars {
+            let object = match tabular_id {
+                TabularIdentUuid::Table(id) => CatalogObject::Table {
+                    table_id: TableIdentUuid::from(id),
+                    namespace_id,
+                },
+                TabularIdentUuid::View(id) => CatalogObject::View {
+                    view_id: ViewIdentUuid::from(id),
+                    namespace_id,
+                },
+            };
+            report.check(authorizer, object, config.repair).await;
+        }
+        if is_last_page {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::{
+        api::{
+            iceberg::{
+                types::Prefix,
+                v1::{tables::TablesService as _, DataAccess, NamespaceParameters},
+            },
+            management::v1::warehouse::TabularDeleteProfile,
+        },
+        catalog::{tables::test::create_request, CatalogServer},
+        implementations::postgres::PostgresCatalog,
+        request_metadata::RequestMetadata,
+        service::authz::implementations::openfga::tests::ObjectHidingMock,
+    };
+
+    #[sqlx::test]
+    async fn test_table_without_owner_relations_is_detected(pool: PgPool) {
+        // The mock returns no tuples on read, so all baseline relations appear missing
+        let authz = ObjectHidingMock::new().to_authorizer();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            None,
+            authz.clone(),
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let table = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+                namespace: ns.namespace.clone(),
+            },
+            create_request(Some("tab-1".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let table_id = TableIdentUuid::from(table.metadata.uuid());
+
+        let config = AuthzReconcilerConfig {
+            max_pages: 10,
+            ..AuthzReconcilerConfig::default()
+        };
+        let report = reconcile::<PostgresCatalog, _>(ctx.v1_state.catalog.clone(), &authz, &config)
+            .await
+            .unwrap();
+
+        assert_eq!(report.checked, 3);
+        assert!(!report.truncated);
+        assert!(report.repaired.is_empty());
+        assert!(report.missing.iter().any(|o| matches!(
+            o,
+            CatalogObject::Table { table_id: id, .. } if *id == table_id
+        )));
+
+        // Repairs are opt-in
+        let config = AuthzReconcilerConfig {
+            repair: true,
+            ..config
+        };
+        let report = reconcile::<PostgresCatalog, _>(ctx.v1_state.catalog.clone(), &authz, &config)
+            .await
+            .unwrap();
+        assert!(report.missing.is_empty());
+        assert_eq!(report.repaired.len(), 3);
+    }
+}
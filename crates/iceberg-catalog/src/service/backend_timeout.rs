@@ -0,0 +1,140 @@
+use std::{future::Future, time::Duration};
+
+use http::StatusCode;
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::service::task_queue::{seconds_to_std_duration, std_duration_to_seconds};
+
+/// Backends whose calls are bounded by a timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+pub enum Backend {
+    CatalogQuery,
+    AuthzCheck,
+    StorageRead,
+    StorageWrite,
+    StoragePurge,
+    CredentialVending,
+}
+
+/// Independent timeouts for calls to each backend.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackendTimeouts {
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub catalog_query: Duration,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub authz_check: Duration,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub storage_read: Duration,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub storage_write: Duration,
+    /// Timeout for removing all files below a location when purging a table or view.
+    /// Separate from `storage_write`, as the duration grows with the number of files.
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub storage_purge: Duration,
+    #[serde(
+        deserialize_with = "seconds_to_std_duration",
+        serialize_with = "std_duration_to_seconds"
+    )]
+    pub credential_vending: Duration,
+}
+
+impl Default for BackendTimeouts {
+    fn default() -> Self {
+        Self {
+            catalog_query: Duration::from_secs(30),
+            authz_check: Duration::from_secs(10),
+            storage_read: Duration::from_secs(60),
+            storage_write: Duration::from_secs(60),
+            storage_purge: Duration::from_secs(60 * 60),
+            credential_vending: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{backend} call timed out after {}ms", timeout.as_millis())]
+pub struct BackendTimeoutError {
+    pub backend: Backend,
+    pub timeout: Duration,
+}
+
+impl BackendTimeoutError {
+    /// Error type reported in the response, e.g. `AuthzCheckTimeout`.
+    #[must_use]
+    pub fn to_type(&self) -> String {
+        format!("{}Timeout", self.backend)
+    }
+}
+
+impl From<BackendTimeoutError> for ErrorModel {
+    fn from(err: BackendTimeoutError) -> Self {
+        ErrorModel::new(
+            err.to_string(),
+            err.to_type(),
+            StatusCode::GATEWAY_TIMEOUT.as_u16(),
+            Some(Box::new(err)),
+        )
+    }
+}
+
+impl From<BackendTimeoutError> for IcebergErrorResponse {
+    fn from(err: BackendTimeoutError) -> Self {
+        ErrorModel::from(err).into()
+    }
+}
+
+/// Await `future`, failing with a [`BackendTimeoutError`] for `backend` if it
+/// does not complete within `timeout`.
+///
+/// # Errors
+/// Fails if `future` does not complete within `timeout`.
+pub async fn with_timeout<F: Future>(
+    backend: Backend,
+    timeout: Duration,
+    future: F,
+) -> Result<F::Output, BackendTimeoutError> {
+    tokio::time::timeout(timeout, future).await.map_err(|_| {
+        tracing::warn!("{backend} call timed out after {}ms", timeout.as_millis());
+        BackendTimeoutError { backend, timeout }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_with_timeout() {
+        let result = with_timeout(Backend::StorageRead, Duration::from_millis(10), async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(result.backend, Backend::StorageRead);
+
+        let error = ErrorModel::from(result);
+        assert_eq!(error.code, 504);
+        assert_eq!(error.r#type, "StorageReadTimeout");
+
+        let result = with_timeout(Backend::StorageRead, Duration::from_secs(10), async { 1 })
+            .await
+            .unwrap();
+        assert_eq!(result, 1);
+    }
+}
@@ -22,7 +22,10 @@ use crate::{
     api::{
         iceberg::v1::{PaginatedMapping, PaginationQuery},
         management::v1::{
+            audit::ListAuditLogResponse,
             role::{ListRolesResponse, Role, SearchRoleResponse},
+            table::TableHistoryResponse,
+            table_template::{TableTemplate, TableTemplateResponse},
             user::{ListUsersResponse, SearchUserResponse, User, UserLastUpdatedWith, UserType},
             warehouse::{TabularDeleteProfile, WarehouseStatisticsResponse},
         },
@@ -30,10 +33,17 @@ use crate::{
     catalog::tables::TableMetadataDiffs,
     request_metadata::RequestMetadata,
     service::{
-        authn::UserId,
+        audit::{AuditLogFilter, AuditRecord},
+        authn::{AuthenticationMethod, UserId},
+        commit_throttle::CommitRateLimit,
+        feature_flags::WarehouseFeatureFlags,
         health::HealthExt,
+        namespace_property_schema::NamespacePropertySchema,
+        schema_evolution::SchemaEvolutionPolicy,
+        secret_encryption::WrappedDataKey,
+        snapshot_limit::SnapshotLimit,
         tabular_idents::{TabularIdentOwned, TabularIdentUuid},
-        task_queue::TaskId,
+        task_queue::{tabular_purge_queue::PendingPurge, TaskId},
     },
     SecretIdent,
 };
@@ -65,6 +75,55 @@ pub struct GetNamespaceResponse {
     pub namespace_id: NamespaceIdentUuid,
     pub warehouse_id: WarehouseIdent,
     pub properties: Option<std::collections::HashMap<String, String>>,
+    /// Storage prefix set on the namespace itself, overriding the base location
+    /// of the warehouse. Not inherited from parent namespaces.
+    pub storage_prefix: Option<String>,
+    /// Region of the bucket of `storage_prefix`, overriding the region of the
+    /// storage profile of the warehouse.
+    pub storage_region: Option<String>,
+    /// Default lifetime of credentials vended for tables of the namespace,
+    /// set on the namespace itself. Not inherited from parent namespaces.
+    pub credential_ttl: Option<chrono::Duration>,
+}
+
+/// Number of active tables and views directly contained in a namespace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NamespaceTabularCounts {
+    pub tables: i64,
+    pub views: i64,
+}
+
+/// Active tables and views of a namespace and the total size of the data files
+/// referenced by the current snapshots of its tables.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NamespaceUsage {
+    pub tables: i64,
+    pub views: i64,
+    /// Sum of `total-files-size` of the current snapshot summaries.
+    /// Snapshots without this summary field are not counted.
+    pub data_size_bytes: i64,
+}
+
+/// Child namespaces of a namespace and the tables and views of the namespace
+/// and its children.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NamespaceDescendants {
+    /// Child namespaces, ordered deepest first.
+    pub namespaces: Vec<(NamespaceIdentUuid, NamespaceIdent)>,
+    /// Tables and views that are not soft-deleted, including staged tables.
+    pub tabulars: Vec<(TabularIdentUuid, TableIdent)>,
+}
+
+impl NamespaceDescendants {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.namespaces.len() + self.tabulars.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty() && self.tabulars.is_empty()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -79,7 +138,7 @@ pub struct CreateTableResponse {
     pub staged_table_id: Option<TableIdentUuid>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LoadTableResponse {
     pub table_id: TableIdentUuid,
     pub namespace_id: NamespaceIdentUuid,
@@ -87,6 +146,110 @@ pub struct LoadTableResponse {
     pub metadata_location: Option<Location>,
     pub storage_secret_ident: Option<SecretIdent>,
     pub storage_profile: StorageProfile,
+    /// Time before credential expiry at which clients should refresh vended credentials.
+    /// `None` uses the server default.
+    pub credential_refresh_before: Option<chrono::Duration>,
+    /// Whether the table was registered from an import prefix and rejects all commits.
+    pub read_only: bool,
+}
+
+/// A table whose metadata changed after a point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedTable {
+    pub table_id: TableIdentUuid,
+    pub table: TableIdent,
+    /// Metadata location after the latest change.
+    pub metadata_location: Location,
+    /// Time of the latest change.
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Database session holding a lock on a table, usually an open commit transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableLock {
+    /// Process id of the session holding the lock.
+    pub holder_pid: i32,
+    pub application_name: Option<String>,
+    pub client_address: Option<String>,
+    /// State of the session, e.g. `idle in transaction`.
+    pub state: Option<String>,
+    /// Start of the transaction holding the lock.
+    pub transaction_started_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Move of a table to a new name or namespace, as part of [`Catalog::rename_tables`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRename {
+    pub table_id: TableIdentUuid,
+    pub source: TableIdent,
+    pub destination: TableIdent,
+}
+
+/// Table whose location equals or is nested in the location of another table
+/// of the same warehouse. Writing to either table may corrupt the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableLocationCollision {
+    pub table_id: TableIdentUuid,
+    pub location: String,
+    /// Table whose location equals or contains `location`.
+    pub parent_table_id: TableIdentUuid,
+    pub parent_location: String,
+}
+
+/// Contents removed by a forced deletion of a warehouse.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeletedWarehouseContents {
+    pub namespace_ids: Vec<NamespaceIdentUuid>,
+    pub tabulars: Vec<DeletedTabular>,
+    /// Locations of tabulars dropped earlier whose purge was still pending.
+    pub pending_purge_locations: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedTabular {
+    pub tabular_id: TabularIdentUuid,
+    pub location: String,
+    /// Whether the tabular was dropped with purge before the warehouse was deleted.
+    /// `None` if the tabular was not soft-deleted.
+    pub dropped_with_purge: Option<bool>,
+}
+
+/// Storage secrets of a warehouse affected by a credential rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotatedStorageSecrets {
+    /// Secret used before the rotation, retained until the end of the overlap window.
+    pub previous_secret_id: Option<SecretIdent>,
+    /// Secret retained by an earlier rotation. It is no longer referenced by the
+    /// warehouse and can be deleted.
+    pub superseded_secret_id: Option<SecretIdent>,
+}
+
+/// Purpose for which a warehouse references a secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum WarehouseSecretUsage {
+    /// Credential currently used to access the storage of the warehouse.
+    StorageCredential,
+    /// Credential used before the last rotation, retained until `expires-at`.
+    PreviousStorageCredential,
+}
+
+/// Reference from a warehouse to a secret in the secret store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarehouseSecretReference {
+    pub secret_id: SecretIdent,
+    pub usage: WarehouseSecretUsage,
+    /// Time until which the secret is retained. `None` if it is retained indefinitely.
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Point in a table's history to load the table at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotIdOrTimestamp {
+    SnapshotId(i64),
+    /// Milliseconds since the epoch. Resolves to the snapshot of the `main`
+    /// branch that was current at this time.
+    TimestampMs(i64),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -99,6 +262,8 @@ pub struct GetTableMetadataResponse {
     pub metadata_location: Option<String>,
     pub storage_secret_ident: Option<SecretIdent>,
     pub storage_profile: StorageProfile,
+    /// Description of the table, mirrored from the `comment` table property.
+    pub description: Option<String>,
 }
 
 impl TableUuid for GetTableMetadataResponse {
@@ -107,10 +272,23 @@ impl TableUuid for GetTableMetadataResponse {
     }
 }
 
+/// Stored result of a request that carried an `Idempotency-Key` header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyRecord {
+    /// Operation the key was first used for, e.g. `createTable`.
+    pub operation: String,
+    pub response: serde_json::Value,
+}
+
 #[derive(Debug)]
 pub struct GetStorageConfigResponse {
     pub storage_profile: StorageProfile,
     pub storage_secret_ident: Option<SecretIdent>,
+    /// Time before credential expiry at which clients should refresh vended credentials.
+    /// `None` uses the server default.
+    pub refresh_before: Option<chrono::Duration>,
+    /// Whether the table was registered from an import prefix and rejects all commits.
+    pub read_only: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -129,6 +307,143 @@ pub struct GetWarehouseResponse {
     pub status: WarehouseStatus,
     /// Tabular delete profile used for the warehouse.
     pub tabular_delete_profile: TabularDeleteProfile,
+    /// Feature flags explicitly set for the warehouse.
+    pub feature_flags: WarehouseFeatureFlags,
+    /// Location schemes explicitly allowed for the warehouse.
+    /// `None` allows all schemes supported by the storage profile.
+    pub allowed_schemes: Option<Vec<String>>,
+    /// Time to wait before deleting the storage of dropped and purged tabulars.
+    /// `None` deletes the storage immediately.
+    pub purge_grace_period: Option<chrono::Duration>,
+    /// Number of manifests of a committed snapshot above which small manifests are merged.
+    /// `None` disables merging.
+    pub manifest_merge_threshold: Option<i32>,
+    /// Maximum number of tables in a single namespace.
+    /// `None` allows an unlimited number of tables.
+    pub max_tables_per_namespace: Option<i32>,
+    /// Whether views count towards `max_tables_per_namespace`.
+    pub max_tables_per_namespace_includes_views: bool,
+    /// Locations that namespaces may use as storage prefix instead of the
+    /// base location of the storage profile.
+    pub storage_prefix_overrides: Vec<String>,
+    /// Schema changes allowed on commit.
+    pub schema_evolution_policy: SchemaEvolutionPolicy,
+    /// Lowercase file formats allowed for data and delete files, for example `parquet`.
+    /// `None` allows all formats.
+    pub allowed_file_formats: Option<Vec<String>>,
+    /// Constraints on namespace property values.
+    pub namespace_property_schema: NamespacePropertySchema,
+    /// Free-form operational metadata set by operators.
+    pub annotations: HashMap<String, String>,
+    /// Properties applied to new views unless set on the namespace or the view itself.
+    pub default_view_properties: HashMap<String, String>,
+    /// Maximum rate of commits to tables and views.
+    /// `None` disables throttling.
+    pub commit_rate_limit: Option<CommitRateLimit>,
+    /// Time before the expiry of vended credentials at which clients should refresh them.
+    /// `None` uses the server default.
+    pub credential_refresh_before: Option<chrono::Duration>,
+    /// Authentication methods requests to the warehouse may use.
+    /// `None` allows all methods.
+    pub allowed_authentication_methods: Option<Vec<AuthenticationMethod>>,
+    /// Time after which namespaces without tables, views and child namespaces are removed.
+    /// `None` disables the cleanup.
+    pub empty_namespace_cleanup_after: Option<chrono::Duration>,
+    /// Locations outside of the base location of the storage profile from which
+    /// tables may be registered. Such tables are read-only.
+    pub import_prefixes: Vec<String>,
+    /// Namespace that table operations target if they address the default
+    /// namespace alias. `None` disables the alias.
+    pub default_namespace: Option<NamespaceIdent>,
+    /// Maximum number of snapshots per table, enforced on commit.
+    /// `None` allows an unlimited number of snapshots.
+    pub snapshot_limit: Option<SnapshotLimit>,
+}
+
+impl GetWarehouseResponse {
+    /// Effective location schemes of the warehouse. Explicitly allowed schemes
+    /// that are not supported by the storage profile are ignored.
+    #[must_use]
+    pub fn allowed_schemes(&self) -> Vec<String> {
+        let supported = self.storage_profile.supported_schemes();
+        match &self.allowed_schemes {
+            Some(allowed) => allowed
+                .iter()
+                .filter(|scheme| supported.contains(&scheme.as_str()))
+                .cloned()
+                .collect(),
+            None => supported.into_iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Require that the scheme of `location` is allowed for the warehouse.
+    ///
+    /// # Errors
+    /// Fails if the scheme is not one of the effective allowed schemes.
+    pub fn require_allowed_scheme(&self, location: &Location) -> Result<(), ErrorModel> {
+        let allowed = self.allowed_schemes();
+        let scheme = location.scheme();
+        if allowed.iter().any(|s| s == scheme) {
+            return Ok(());
+        }
+
+        Err(ErrorModel::bad_request(
+            format!(
+                "Scheme '{scheme}' of location {location} is not allowed for warehouse '{}'. Allowed schemes: {}.",
+                self.name,
+                allowed.join(", ")
+            ),
+            "LocationSchemeNotAllowed",
+            None,
+        ))
+    }
+
+    /// Whether `location` is below one of the import prefixes of the warehouse.
+    #[must_use]
+    pub fn is_import_location(&self, location: &Location) -> bool {
+        self.import_prefixes
+            .iter()
+            .filter_map(|p| p.parse::<Location>().ok())
+            .any(|prefix| location.is_sublocation_of(&prefix))
+    }
+
+    /// Whether data and delete files of the warehouse may use `file_format`.
+    #[must_use]
+    pub fn allows_file_format(&self, file_format: &str) -> bool {
+        self.allowed_file_formats.as_ref().map_or(true, |allowed| {
+            allowed.iter().any(|f| f.eq_ignore_ascii_case(file_format))
+        })
+    }
+
+    /// Require that `storage_prefix` is one of the storage prefix overrides of the
+    /// warehouse or located below one of them.
+    ///
+    /// # Errors
+    /// Fails if the prefix is not covered by any storage prefix override.
+    pub fn require_allowed_storage_prefix(
+        &self,
+        storage_prefix: &Location,
+    ) -> Result<(), ErrorModel> {
+        self.require_allowed_scheme(storage_prefix)?;
+        let allowed = self
+            .storage_prefix_overrides
+            .iter()
+            .filter_map(|o| o.parse::<Location>().ok())
+            .any(|o| storage_prefix.is_sublocation_of(&o));
+        if allowed {
+            return Ok(());
+        }
+
+        Err(ErrorModel::bad_request(
+            format!(
+                "Storage prefix {storage_prefix} is not allowed for warehouse '{}'. Allowed prefixes: {}.",
+                self.name,
+                self.storage_prefix_overrides.join(", ")
+            ),
+            "StoragePrefixNotAllowed",
+            None,
+        ))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -153,6 +468,10 @@ pub struct TableCreation<'c> {
     pub(crate) table_ident: &'c TableIdent,
     pub(crate) metadata_location: Option<&'c Location>,
     pub(crate) table_metadata: TableMetadata,
+    /// Team whose storage prefix the table is placed under.
+    pub(crate) storage_team: Option<&'c str>,
+    /// Whether the table was registered from an import prefix and rejects all commits.
+    pub(crate) read_only: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -212,6 +531,13 @@ where
         catalog_state: Self::State,
     ) -> Result<Option<WarehouseIdent>>;
 
+    /// Authentication methods allowed for requests to an active warehouse.
+    /// `None` if all methods are allowed or the warehouse does not exist.
+    async fn get_warehouse_allowed_authentication_methods(
+        warehouse_id: WarehouseIdent,
+        catalog_state: Self::State,
+    ) -> Result<Option<Vec<AuthenticationMethod>>>;
+
     /// Wrapper around get_warehouse_by_name that returns
     /// not found error if the warehouse does not exist.
     async fn require_warehouse_by_name(
@@ -306,19 +632,172 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Replace the properties of several namespaces at once.
+    ///
+    /// Fails without updating any namespace if one of them does not exist.
+    /// Updates only become visible together when the transaction is committed.
+    async fn update_many_namespace_properties<'a>(
+        warehouse_id: WarehouseIdent,
+        properties: Vec<(NamespaceIdentUuid, HashMap<String, String>)>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the storage prefix of a namespace and the region of its bucket.
+    /// `None` removes the override.
+    async fn set_namespace_storage_prefix<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        storage_prefix: Option<&str>,
+        storage_region: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the default lifetime of credentials vended for tables of a namespace.
+    /// `None` removes the override.
+    async fn set_namespace_credential_ttl<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        credential_ttl: Option<chrono::Duration>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Rename a namespace and all of its child namespaces.
+    /// Tables and views keep their ids and storage locations.
+    ///
+    /// Fails with 404 if `source` does not exist and with 409 if
+    /// `destination` or one of the renamed child namespaces already exists.
+    async fn rename_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        source: &NamespaceIdent,
+        destination: &NamespaceIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Count the tables and views of a namespace that are not soft-deleted.
+    /// Locks the namespace until the end of the transaction, so that concurrent
+    /// creations in the same namespace are serialized.
+    async fn count_tabulars_in_namespace<'a>(
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<NamespaceTabularCounts>;
+
+    /// Usage of a namespace, optionally including all of its child namespaces.
+    /// Fails with a not-found error if the namespace does not exist in the warehouse.
+    async fn namespace_usage<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        include_descendants: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<NamespaceUsage>;
+
+    /// Child namespaces, tables and views below a namespace. Returns at most
+    /// `limit` namespaces and at most `limit` tabulars.
+    async fn list_namespace_descendants<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        limit: usize,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<NamespaceDescendants>;
+
+    /// Active tables of a namespace, optionally including the tables of all
+    /// child namespaces, ordered by namespace and name.
+    async fn list_namespace_tables<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        include_descendants: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<(TableIdentUuid, TableIdent)>>;
+
+    /// Delete all namespaces of a warehouse that contain no tables, views or child
+    /// namespaces and were not created, updated or emptied after `inactive_since`.
+    /// Parents that become empty are deleted as well if they are inactive.
+    /// Namespaces below a first level in `exempt_namespaces` are never deleted.
+    /// Namespaces in which tables or views are created concurrently are skipped.
+    async fn delete_empty_namespaces<'a>(
+        warehouse_id: WarehouseIdent,
+        inactive_since: chrono::DateTime<chrono::Utc>,
+        exempt_namespaces: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceIdentUuid>>;
+
     async fn create_table<'a>(
         table_creation: TableCreation<'_>,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<CreateTableResponse>;
 
+    /// List tables of a namespace. If `name_prefix` is set, only tables whose
+    /// name starts with the prefix are listed.
     async fn list_tables<'a>(
         warehouse_id: WarehouseIdent,
         namespace: &NamespaceIdent,
+        name_prefix: Option<&str>,
         list_flags: ListFlags,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
         pagination_query: PaginationQuery,
     ) -> Result<PaginatedMapping<TableIdentUuid, TableIdent>>;
 
+    /// List active tables of a warehouse whose metadata changed at or after `since`,
+    /// ordered by the time of their latest change and the table id.
+    /// Consumers resume from the page token of the last table they processed,
+    /// not from its change time, which is shared by all tables of a commit.
+    async fn list_changed_tables<'a>(
+        warehouse_id: WarehouseIdent,
+        since: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+        pagination_query: PaginationQuery,
+    ) -> Result<PaginatedMapping<TableIdentUuid, ChangedTable>>;
+
+    /// Sessions holding a lock on a table, oldest transaction first.
+    async fn list_table_locks(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TableLock>>;
+
+    /// Terminate all sessions holding a lock on a table, rolling back their
+    /// transactions. Returns the terminated sessions.
+    async fn release_table_locks(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Vec<TableLock>>;
+
+    /// Snapshots of a table, newest first, together with the principal that
+    /// committed them if it was recorded.
+    async fn table_history(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        pagination: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<TableHistoryResponse>;
+
+    /// Store `template` as the next version of the table template `name`,
+    /// starting at version 1.
+    async fn create_table_template_version<'a>(
+        warehouse_id: WarehouseIdent,
+        name: &str,
+        template: &TableTemplate,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<TableTemplateResponse>;
+
+    /// Get the given version of a table template, or its latest version if
+    /// `version` is not set. Returns `None` if it does not exist.
+    async fn get_table_template<'a>(
+        warehouse_id: WarehouseIdent,
+        name: &str,
+        version: Option<i32>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<TableTemplateResponse>>;
+
+    /// Record the principal that committed a snapshot of a table.
+    /// Existing records are kept, records for dropped tables are ignored.
+    async fn insert_commit_attribution<'a>(
+        table_id: TableIdentUuid,
+        snapshot_id: i64,
+        principal: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
     /// Return Err only on unexpected errors, not if the table does not exist.
     /// If include_staged is true, also return staged tables.
     /// If the table does not exist, return Ok(None).
@@ -350,6 +829,17 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<HashMap<TableIdentUuid, LoadTableResponse>>;
 
+    /// Load a table as of a snapshot or point in time.
+    /// The returned metadata uses the schema and partition spec that were effective
+    /// for the snapshot. Snapshots after it are removed and `main` points to it.
+    /// Return a `SnapshotNotFound` error if the snapshot does not exist or was expired.
+    async fn load_table_at<'a>(
+        warehouse_id: WarehouseIdent,
+        table: TableIdentUuid,
+        at: SnapshotIdOrTimestamp,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<LoadTableResponse>;
+
     /// Get table metadata by table id.
     /// If include_staged is true, also return staged tables,
     /// i.e. tables with no metadata file yet.
@@ -361,6 +851,22 @@ where
         catalog_state: Self::State,
     ) -> Result<Option<GetTableMetadataResponse>>;
 
+    /// Resolve the current name of a table by its id. Ids are stable across renames.
+    /// Return a `TableNotFound` error if the table does not exist, is staged or is soft-deleted.
+    async fn resolve_table_uuid<'a>(
+        warehouse_id: WarehouseIdent,
+        table: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<TableIdent>;
+
+    /// Current metadata location of an active table.
+    /// Return Ok(None) if the table does not exist or is staged.
+    async fn get_table_metadata_location<'a>(
+        warehouse_id: WarehouseIdent,
+        table: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<String>>;
+
     /// Get table metadata by location.
     /// Return Ok(None) if the table does not exist.
     async fn get_table_metadata_by_s3_location(
@@ -379,6 +885,15 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Rename multiple tables in one transaction. Tables may be moved across namespaces.
+    /// A table may take over the name of another table of the batch, for example to
+    /// swap two tables.
+    async fn rename_tables<'a>(
+        warehouse_id: WarehouseIdent,
+        renames: &[TableRename],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
     /// Drop a table.
     /// Should drop staged and non-staged tables.
     ///
@@ -405,6 +920,32 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<()>;
 
+    /// Check whether `location` overlaps with the location of an existing tabular.
+    async fn is_tabular_location_in_use<'a>(
+        location: &Location,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool>;
+
+    /// Check whether a table or view with `tabular_id` exists in any warehouse,
+    /// including staged and soft-deleted ones. Ids are unique across warehouses.
+    async fn is_tabular_id_in_use<'a>(
+        tabular_id: TabularIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool>;
+
+    /// List purges of a warehouse whose storage has not been deleted yet.
+    async fn list_pending_purges<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<PendingPurge>>;
+
+    /// List tables of a warehouse whose locations overlap with the location of
+    /// another table of the warehouse. Tables sharing a location are reported once.
+    async fn list_table_location_collisions<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<TableLocationCollision>>;
+
     /// Commit changes to a table.
     /// The table might be staged or not.
     async fn commit_table_transaction<'a>(
@@ -556,10 +1097,16 @@ where
     ) -> Result<WarehouseStatisticsResponse>;
 
     /// Delete a warehouse.
+    ///
+    /// Unless `force` is set, the warehouse must not contain any namespaces,
+    /// tables or views. With `force`, all namespaces, tables and views of the
+    /// warehouse as well as its queued tasks are deleted, and the removed contents
+    /// are returned.
     async fn delete_warehouse<'a>(
         warehouse_id: WarehouseIdent,
+        force: bool,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
-    ) -> Result<()>;
+    ) -> Result<DeletedWarehouseContents>;
 
     /// Rename a warehouse.
     async fn rename_warehouse<'a>(
@@ -575,6 +1122,189 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Set the feature flags of a warehouse, replacing all existing overrides.
+    async fn set_warehouse_feature_flags<'a>(
+        warehouse_id: WarehouseIdent,
+        feature_flags: &WarehouseFeatureFlags,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the location schemes allowed for a warehouse.
+    /// `None` resets to the schemes supported by the storage profile.
+    async fn set_warehouse_allowed_schemes<'a>(
+        warehouse_id: WarehouseIdent,
+        allowed_schemes: Option<&[String]>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the time to wait before deleting the storage of dropped and purged tabulars.
+    async fn set_warehouse_purge_grace_period<'a>(
+        warehouse_id: WarehouseIdent,
+        purge_grace_period: Option<chrono::Duration>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the schema changes a warehouse allows on commit.
+    async fn set_warehouse_schema_evolution_policy<'a>(
+        warehouse_id: WarehouseIdent,
+        schema_evolution_policy: SchemaEvolutionPolicy,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the file formats allowed for data and delete files of a warehouse.
+    /// `None` allows all formats.
+    async fn set_warehouse_allowed_file_formats<'a>(
+        warehouse_id: WarehouseIdent,
+        allowed_file_formats: Option<&[String]>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the constraints on namespace property values of a warehouse.
+    async fn set_warehouse_namespace_property_schema<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_property_schema: &NamespacePropertySchema,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Replace the annotations of a warehouse.
+    async fn set_warehouse_annotations<'a>(
+        warehouse_id: WarehouseIdent,
+        annotations: &HashMap<String, String>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the maximum rate of commits to tables and views of a warehouse.
+    /// `None` disables throttling.
+    async fn set_warehouse_commit_rate_limit<'a>(
+        warehouse_id: WarehouseIdent,
+        commit_rate_limit: Option<CommitRateLimit>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the time before the expiry of vended credentials at which clients
+    /// should refresh them. `None` resets to the server default.
+    async fn set_warehouse_credential_refresh_before<'a>(
+        warehouse_id: WarehouseIdent,
+        credential_refresh_before: Option<chrono::Duration>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the time after which empty namespaces of a warehouse are removed.
+    /// `None` disables the cleanup.
+    async fn set_warehouse_empty_namespace_cleanup_after<'a>(
+        warehouse_id: WarehouseIdent,
+        cleanup_after: Option<chrono::Duration>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the authentication methods requests to a warehouse may use.
+    /// `None` allows all methods.
+    async fn set_warehouse_allowed_authentication_methods<'a>(
+        warehouse_id: WarehouseIdent,
+        allowed_authentication_methods: Option<&[AuthenticationMethod]>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Replace the properties applied to new views of a warehouse.
+    async fn set_warehouse_default_view_properties<'a>(
+        warehouse_id: WarehouseIdent,
+        default_view_properties: &HashMap<String, String>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the number of manifests of a committed snapshot above which small
+    /// manifests are merged. `None` disables merging.
+    async fn set_warehouse_manifest_merge_threshold<'a>(
+        warehouse_id: WarehouseIdent,
+        manifest_merge_threshold: Option<i32>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the locations outside of the storage profile's base location from which
+    /// tables may be registered as read-only tables.
+    async fn set_warehouse_import_prefixes<'a>(
+        warehouse_id: WarehouseIdent,
+        import_prefixes: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the namespace that table operations addressing the default namespace
+    /// alias target. `None` disables the alias.
+    async fn set_warehouse_default_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        default_namespace: Option<&NamespaceIdent>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the maximum number of snapshots per table of a warehouse.
+    /// `None` allows an unlimited number of snapshots.
+    async fn set_warehouse_snapshot_limit<'a>(
+        warehouse_id: WarehouseIdent,
+        snapshot_limit: Option<SnapshotLimit>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the locations that namespaces may use as storage prefix.
+    async fn set_warehouse_storage_prefix_overrides<'a>(
+        warehouse_id: WarehouseIdent,
+        storage_prefix_overrides: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the maximum number of tables in a single namespace.
+    /// `None` allows an unlimited number of tables.
+    async fn set_warehouse_max_tables_per_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        max_tables_per_namespace: Option<i32>,
+        includes_views: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Load the non-expired result stored for `idempotency_key` of `principal`.
+    async fn load_idempotency_record<'a>(
+        warehouse_id: WarehouseIdent,
+        principal: &str,
+        idempotency_key: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<IdempotencyRecord>>;
+
+    /// Store the result of a request for `idempotency_key` of `principal`.
+    /// Fails with 409 if a non-expired record for the key exists.
+    async fn store_idempotency_record<'a>(
+        warehouse_id: WarehouseIdent,
+        principal: &str,
+        idempotency_key: &str,
+        record: IdempotencyRecord,
+        expires_at: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Delete expired idempotency records of all warehouses.
+    /// Returns the number of deleted records.
+    async fn prune_idempotency_records<'a>(
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<u64>;
+
+    /// Persist records of the audit log.
+    async fn insert_audit_records<'a>(
+        records: &[AuditRecord],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// List records of the audit log matching `filter`, oldest first.
+    async fn list_audit_records(
+        filter: AuditLogFilter,
+        pagination: PaginationQuery,
+        catalog_state: Self::State,
+    ) -> Result<ListAuditLogResponse>;
+
+    /// Delete records of the audit log created before `before`.
+    /// Returns the number of deleted records.
+    async fn prune_audit_records<'a>(
+        before: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<u64>;
+
     /// Rename a project.
     async fn rename_project<'a>(
         project_id: ProjectId,
@@ -596,6 +1326,58 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Switch the storage secret of a warehouse to `storage_secret_id`. The previous
+    /// secret is retained until `previous_secret_expires_at`, replacing any secret
+    /// retained by an earlier rotation.
+    async fn rotate_storage_secret<'a>(
+        warehouse_id: WarehouseIdent,
+        storage_secret_id: SecretIdent,
+        previous_secret_expires_at: chrono::DateTime<chrono::Utc>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<RotatedStorageSecrets>;
+
+    /// Stop retaining `secret_id` as previous storage secret of a warehouse.
+    /// Returns whether the secret is no longer referenced by the warehouse and can be deleted.
+    async fn release_previous_storage_secret<'a>(
+        warehouse_id: WarehouseIdent,
+        secret_id: SecretIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool>;
+
+    /// Secrets referenced by a warehouse. The secrets themselves are not loaded.
+    async fn list_warehouse_secret_references<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<WarehouseSecretReference>>;
+
+    /// Data key used to encrypt the storage credentials of a warehouse.
+    /// Return Ok(None) if the warehouse has no data key yet.
+    async fn get_warehouse_data_key<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<WrappedDataKey>>;
+
+    /// Store the data key of a warehouse. Returns false if the warehouse
+    /// already has a data key, which is kept.
+    async fn create_warehouse_data_key<'a>(
+        warehouse_id: WarehouseIdent,
+        data_key: &WrappedDataKey,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool>;
+
+    /// Replace the data key of a warehouse after it was re-wrapped with another master key.
+    async fn update_warehouse_data_key<'a>(
+        warehouse_id: WarehouseIdent,
+        data_key: &WrappedDataKey,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Lock and list data keys of all warehouses that are not wrapped with `master_key_id`.
+    async fn list_warehouse_data_keys_to_rewrap<'a>(
+        master_key_id: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<(WarehouseIdent, WrappedDataKey)>>;
+
     /// Return Err only on unexpected errors, not if the table does not exist.
     /// If include_staged is true, also return staged tables.
     /// If the table does not exist, return Ok(None).
@@ -623,9 +1405,13 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<ViewMetadataWithLocation>;
 
+    /// List views of a namespace. Tables are not included, they are listed
+    /// via `list_tables`. If `name_prefix` is set, only views whose name starts
+    /// with the prefix are listed.
     async fn list_views<'a>(
         warehouse_id: WarehouseIdent,
         namespace: &NamespaceIdent,
+        name_prefix: Option<&str>,
         include_deleted: bool,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
         pagination_query: PaginationQuery,
@@ -668,7 +1454,7 @@ where
         warehouse_id: WarehouseIdent,
         tabular_id: TableIdentUuid,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
-    ) -> Result<(Option<SecretIdent>, StorageProfile)>;
+    ) -> Result<GetStorageConfigResponse>;
 
     async fn resolve_table_ident(
         warehouse_id: WarehouseIdent,
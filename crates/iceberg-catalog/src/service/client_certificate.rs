@@ -0,0 +1,487 @@
+use std::{net::IpAddr, sync::Arc};
+
+use anyhow::Context;
+use base64::Engine;
+use http::{HeaderName, HeaderValue};
+use iceberg_ext::catalog::rest::ErrorModel;
+use limes::{Authentication, Subject};
+use x509_parser::{
+    certificate::X509Certificate, extensions::GeneralName, parse_x509_certificate, pem::Pem,
+    x509::AttributeTypeAndValue,
+};
+
+use super::UserId;
+use crate::{config::ClientCertificateAuthConfig, CONFIG};
+
+/// IdP id of principals authenticated via client certificates.
+pub const CLIENT_CERTIFICATE_IDP_ID: &str = "mtls";
+
+/// Placeholders supported in principal templates.
+const PLACEHOLDERS: [&str; 6] = [
+    "subject.cn",
+    "subject.o",
+    "subject.ou",
+    "san.dns",
+    "san.uri",
+    "san.email",
+];
+
+/// Maximum number of intermediate CAs between a client certificate and a trust anchor.
+const MAX_INTERMEDIATES: usize = 4;
+
+/// Authenticates client certificates forwarded by a TLS-terminating proxy.
+///
+/// Certificates must chain up to one of the trust anchors, be valid at the time
+/// of the request and, if they restrict their extended key usage, allow client
+/// authentication. Intermediate CAs are taken from the CA bundle or from the
+/// forwarded header, following the client certificate.
+#[derive(Debug, Clone)]
+pub struct ClientCertificateVerifier {
+    /// DER encoded trust anchor certificates
+    trust_anchors: Arc<Vec<Vec<u8>>>,
+    header: HeaderName,
+    principal_template: String,
+    /// Peers allowed to forward client certificates.
+    trusted_proxies: Arc<Vec<IpAddr>>,
+}
+
+impl ClientCertificateVerifier {
+    /// Create a verifier from `client_certificate_authentication`.
+    /// Returns `None` if client certificate authentication is not configured.
+    ///
+    /// # Errors
+    /// Fails if the trust anchor cannot be read or the configuration is invalid.
+    pub fn from_config() -> anyhow::Result<Option<Self>> {
+        CONFIG
+            .client_certificate_authentication
+            .as_ref()
+            .map(|config| {
+                let trust_anchor = std::fs::read(&config.trust_anchor).with_context(|| {
+                    format!(
+                        "Failed to read client certificate trust anchor {}",
+                        config.trust_anchor.display()
+                    )
+                })?;
+                Self::new(&trust_anchor, config)
+            })
+            .transpose()
+    }
+
+    /// Create a verifier trusting the CA certificates in the PEM encoded bundle `trust_anchor`.
+    ///
+    /// # Errors
+    /// Fails if `trust_anchor` contains no CA certificate, the header name is
+    /// invalid, the principal template contains unsupported placeholders or
+    /// no trusted proxy is configured.
+    pub fn new(trust_anchor: &[u8], config: &ClientCertificateAuthConfig) -> anyhow::Result<Self> {
+        let trust_anchors = Pem::iter_from_buffer(trust_anchor)
+            .map(|pem| {
+                let pem = pem.context("Failed to parse client certificate trust anchor")?;
+                let (_, certificate) = parse_x509_certificate(&pem.contents)
+                    .context("Failed to parse client certificate trust anchor")?;
+                anyhow::ensure!(
+                    certificate.is_ca(),
+                    "Client certificate trust anchor {} is not a CA certificate",
+                    certificate.subject()
+                );
+                Ok(pem.contents)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        anyhow::ensure!(
+            !trust_anchors.is_empty(),
+            "Client certificate trust anchor contains no certificate"
+        );
+        validate_principal_template(&config.principal_template)?;
+        anyhow::ensure!(
+            !config.trusted_proxies.is_empty(),
+            "Client certificate authentication requires at least one trusted proxy"
+        );
+
+        Ok(Self {
+            trust_anchors: Arc::new(trust_anchors),
+            header: HeaderName::try_from(config.header.as_str())
+                .context("Invalid client certificate header")?,
+            principal_template: config.principal_template.clone(),
+            trusted_proxies: Arc::new(
+                config
+                    .trusted_proxies
+                    .iter()
+                    .map(IpAddr::to_canonical)
+                    .collect(),
+            ),
+        })
+    }
+
+    /// Header holding the forwarded client certificate.
+    #[must_use]
+    pub fn header(&self) -> &HeaderName {
+        &self.header
+    }
+
+    /// Whether `peer` may forward client certificates. The header of all other
+    /// peers is removed, as clients could set it themselves.
+    #[must_use]
+    pub fn is_trusted_proxy(&self, peer: IpAddr) -> bool {
+        self.trusted_proxies.contains(&peer.to_canonical())
+    }
+
+    /// Authenticate the forwarded client certificate in `value`.
+    ///
+    /// # Errors
+    /// Fails with `AuthenticationRequired` if the certificate cannot be parsed,
+    /// is not trusted or does not map to a valid principal.
+    pub fn authenticate(&self, value: &HeaderValue) -> Result<Authentication, ErrorModel> {
+        let ders = decode_certificates(value)?;
+        let certificates = ders
+            .iter()
+            .map(|der| {
+                parse_x509_certificate(der)
+                    .map(|(_, certificate)| certificate)
+                    .map_err(|e| {
+                        authentication_required(
+                            "Failed to parse client certificate",
+                            Some(Box::new(e)),
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some((certificate, intermediates)) = certificates.split_first() else {
+            return Err(authentication_required(
+                "Client certificate header contains no certificate",
+                None,
+            ));
+        };
+        self.verify(certificate, intermediates)?;
+
+        let principal = render_principal(&self.principal_template, certificate)?;
+        let subject = Subject::new(Some(CLIENT_CERTIFICATE_IDP_ID.to_string()), principal);
+        UserId::try_from(subject.clone()).map_err(|e| {
+            authentication_required(
+                format!(
+                    "Client certificate maps to an invalid principal: {}",
+                    e.message
+                ),
+                None,
+            )
+        })?;
+
+        Ok(Authentication::builder()
+            .token_header(None)
+            .claims(serde_json::json!({
+                "subject": certificate.subject().to_string(),
+                "issuer": certificate.issuer().to_string(),
+                "serial": certificate.raw_serial_as_string(),
+            }))
+            .subject(subject)
+            .name(attribute(certificate, "subject.cn"))
+            .email(attribute(certificate, "san.email"))
+            .principal_type(None)
+            .build())
+    }
+
+    fn verify<'a>(
+        &self,
+        certificate: &X509Certificate<'a>,
+        intermediates: &[X509Certificate<'a>],
+    ) -> Result<(), ErrorModel> {
+        if !certificate.validity().is_valid() {
+            return Err(authentication_required(
+                "Client certificate is expired or not yet valid",
+                None,
+            ));
+        }
+        let extended_key_usage = certificate.extended_key_usage().map_err(|e| {
+            authentication_required("Failed to parse client certificate", Some(Box::new(e)))
+        })?;
+        if extended_key_usage.is_some_and(|usage| !(usage.value.client_auth || usage.value.any)) {
+            return Err(authentication_required(
+                "Client certificate is not valid for client authentication",
+                None,
+            ));
+        }
+
+        let anchors = self
+            .trust_anchors
+            .iter()
+            .filter_map(|der| parse_x509_certificate(der).ok())
+            .map(|(_, anchor)| anchor)
+            .collect::<Vec<_>>();
+        // Walk up the chain until a certificate is issued by a trust anchor.
+        // Certificates of the bundle may be intermediates themselves.
+        let mut current = certificate;
+        for _ in 0..=MAX_INTERMEDIATES {
+            if anchors.iter().any(|anchor| is_issued_by(current, anchor)) {
+                return Ok(());
+            }
+            let Some(issuer) = intermediates.iter().find(|intermediate| {
+                intermediate.is_ca()
+                    && intermediate.validity().is_valid()
+                    && is_issued_by(current, intermediate)
+            }) else {
+                break;
+            };
+            current = issuer;
+        }
+        Err(authentication_required(
+            "Client certificate is not issued by a trusted CA",
+            None,
+        ))
+    }
+}
+
+fn is_issued_by(certificate: &X509Certificate<'_>, issuer: &X509Certificate<'_>) -> bool {
+    issuer.subject().as_raw() == certificate.issuer().as_raw()
+        && certificate
+            .verify_signature(Some(issuer.public_key()))
+            .is_ok()
+}
+
+/// Proxies forward certificates either as URL-encoded PEM, as nginx does,
+/// or as base64-encoded DER. PEM values may be followed by intermediate CAs.
+fn decode_certificates(value: &HeaderValue) -> Result<Vec<Vec<u8>>, ErrorModel> {
+    let invalid = |e: Box<dyn std::error::Error + Send + Sync + 'static>| {
+        authentication_required("Failed to decode client certificate", Some(e))
+    };
+    let value = value.to_str().map_err(|e| invalid(Box::new(e)))?;
+    let value = urlencoding::decode(value).map_err(|e| invalid(Box::new(e)))?;
+
+    if value.contains("-----BEGIN") {
+        Pem::iter_from_buffer(value.as_bytes())
+            .map(|pem| {
+                pem.map(|pem| pem.contents)
+                    .map_err(|e| invalid(Box::new(e)))
+            })
+            .collect()
+    } else {
+        base64::engine::general_purpose::STANDARD
+            .decode(value.trim())
+            .map(|der| vec![der])
+            .map_err(|e| invalid(Box::new(e)))
+    }
+}
+
+fn validate_principal_template(template: &str) -> anyhow::Result<()> {
+    let remainder = PLACEHOLDERS.iter().fold(template.to_string(), |rest, p| {
+        rest.replace(&format!("{{{p}}}"), "")
+    });
+    anyhow::ensure!(
+        remainder.len() < template.len(),
+        "Client certificate principal template '{template}' contains no placeholder"
+    );
+    anyhow::ensure!(
+        !remainder.contains(['{', '}']),
+        "Client certificate principal template '{template}' contains an unsupported placeholder. Supported placeholders: {}",
+        PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+    );
+    Ok(())
+}
+
+fn render_principal(
+    template: &str,
+    certificate: &X509Certificate<'_>,
+) -> Result<String, ErrorModel> {
+    let mut principal = template.to_string();
+    for placeholder in PLACEHOLDERS {
+        let pattern = format!("{{{placeholder}}}");
+        if !principal.contains(&pattern) {
+            continue;
+        }
+        let value = attribute(certificate, placeholder).ok_or_else(|| {
+            authentication_required(
+                format!("Client certificate has no {placeholder} to derive the principal from"),
+                None,
+            )
+        })?;
+        principal = principal.replace(&pattern, &value);
+    }
+    Ok(principal)
+}
+
+/// First value of the certificate attribute named by `placeholder`.
+fn attribute(certificate: &X509Certificate<'_>, placeholder: &str) -> Option<String> {
+    let subject = certificate.subject();
+    match placeholder {
+        "subject.cn" => first_value(subject.iter_common_name()),
+        "subject.o" => first_value(subject.iter_organization()),
+        "subject.ou" => first_value(subject.iter_organizational_unit()),
+        "san.dns" | "san.uri" | "san.email" => certificate
+            .subject_alternative_name()
+            .ok()
+            .flatten()?
+            .value
+            .general_names
+            .iter()
+            .find_map(|name| match (placeholder, name) {
+                ("san.dns", GeneralName::DNSName(v))
+                | ("san.uri", GeneralName::URI(v))
+                | ("san.email", GeneralName::RFC822Name(v)) => Some((*v).to_string()),
+                _ => None,
+            }),
+        _ => None,
+    }
+}
+
+fn first_value<'a>(
+    mut values: impl Iterator<Item = &'a AttributeTypeAndValue<'a>>,
+) -> Option<String> {
+    values.find_map(|v| v.as_str().ok().map(ToString::to_string))
+}
+
+fn authentication_required(
+    message: impl Into<String>,
+    source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+) -> ErrorModel {
+    ErrorModel::unauthorized(message, "AuthenticationRequired", source)
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use x509_parser::pem::parse_x509_pem;
+
+    use super::*;
+
+    pub(crate) const TRUST_ANCHOR: &str =
+        include_str!("../../tests/client_certificates/trust-anchor.pem");
+    pub(crate) const CLIENT: &str = include_str!("../../tests/client_certificates/client.pem");
+    const UNTRUSTED_CLIENT: &str =
+        include_str!("../../tests/client_certificates/untrusted-client.pem");
+    const CHAIN_ROOT: &str = include_str!("../../tests/client_certificates/chain-root.pem");
+    const CHAIN_INTERMEDIATE: &str =
+        include_str!("../../tests/client_certificates/chain-intermediate.pem");
+    const CHAIN_CLIENT: &str = include_str!("../../tests/client_certificates/chain-client.pem");
+
+    pub(crate) const TRUSTED_PROXY: IpAddr = IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+
+    fn verifier_with_bundle(bundle: &str, principal_template: &str) -> ClientCertificateVerifier {
+        ClientCertificateVerifier::new(
+            bundle.as_bytes(),
+            &ClientCertificateAuthConfig {
+                trust_anchor: "unused".into(),
+                header: "x-ssl-client-cert".to_string(),
+                principal_template: principal_template.to_string(),
+                trusted_proxies: vec![TRUSTED_PROXY],
+            },
+        )
+        .unwrap()
+    }
+
+    pub(crate) fn verifier(principal_template: &str) -> ClientCertificateVerifier {
+        verifier_with_bundle(TRUST_ANCHOR, principal_template)
+    }
+
+    pub(crate) fn escaped_pem(pem: &str) -> HeaderValue {
+        HeaderValue::from_str(&urlencoding::encode(pem)).unwrap()
+    }
+
+    #[test]
+    fn test_trusted_certificate_maps_to_principal() {
+        let authentication = verifier("{subject.ou}-{subject.cn}")
+            .authenticate(&escaped_pem(CLIENT))
+            .unwrap();
+        assert_eq!(
+            authentication.subject(),
+            &Subject::new(
+                Some(CLIENT_CERTIFICATE_IDP_ID.to_string()),
+                "Finance-billing-service".to_string()
+            )
+        );
+        assert_eq!(
+            authentication.name().map(ToString::to_string),
+            Some("billing-service".to_string())
+        );
+    }
+
+    #[test]
+    fn test_base64_der_certificate_is_accepted() {
+        let (_, pem) = parse_x509_pem(CLIENT.as_bytes()).unwrap();
+        let der = base64::engine::general_purpose::STANDARD.encode(pem.contents);
+        let authentication = verifier("{subject.cn}")
+            .authenticate(&HeaderValue::from_str(&der).unwrap())
+            .unwrap();
+        assert_eq!(authentication.subject().subject_in_idp(), "billing-service");
+    }
+
+    #[test]
+    fn test_untrusted_certificate_is_rejected() {
+        // Issued by a CA with the same name as the trust anchor, but a different key
+        let err = verifier("{subject.cn}")
+            .authenticate(&escaped_pem(UNTRUSTED_CLIENT))
+            .unwrap_err();
+        assert_eq!(err.code, 401);
+        assert_eq!(
+            err.message,
+            "Client certificate is not issued by a trusted CA"
+        );
+    }
+
+    #[test]
+    fn test_certificate_chain_is_verified_against_bundle() {
+        // Intermediates forwarded after the client certificate complete the chain
+        let forwarded_chain = format!("{CHAIN_CLIENT}{CHAIN_INTERMEDIATE}");
+        let authentication = verifier_with_bundle(CHAIN_ROOT, "{subject.cn}")
+            .authenticate(&escaped_pem(&forwarded_chain))
+            .unwrap();
+        assert_eq!(
+            authentication.subject().subject_in_idp(),
+            "reporting-service"
+        );
+
+        // Without the intermediate, the client certificate does not chain up to the root
+        let err = verifier_with_bundle(CHAIN_ROOT, "{subject.cn}")
+            .authenticate(&escaped_pem(CHAIN_CLIENT))
+            .unwrap_err();
+        assert_eq!(err.code, 401);
+
+        // Intermediates of the bundle are trusted as well
+        let bundle = format!("{CHAIN_ROOT}{CHAIN_INTERMEDIATE}");
+        verifier_with_bundle(&bundle, "{subject.cn}")
+            .authenticate(&escaped_pem(CHAIN_CLIENT))
+            .unwrap();
+
+        // Forwarded intermediates must chain up to the bundle
+        let err = verifier("{subject.cn}")
+            .authenticate(&escaped_pem(&forwarded_chain))
+            .unwrap_err();
+        assert_eq!(err.code, 401);
+    }
+
+    #[test]
+    fn test_only_trusted_proxies_may_forward_certificates() {
+        let verifier = verifier("{subject.cn}");
+        assert!(verifier.is_trusted_proxy(TRUSTED_PROXY));
+        assert!(verifier.is_trusted_proxy("::ffff:127.0.0.1".parse().unwrap()));
+        assert!(!verifier.is_trusted_proxy("10.0.0.1".parse().unwrap()));
+
+        let err = ClientCertificateVerifier::new(
+            TRUST_ANCHOR.as_bytes(),
+            &ClientCertificateAuthConfig {
+                trust_anchor: "unused".into(),
+                header: "x-ssl-client-cert".to_string(),
+                principal_template: "{subject.cn}".to_string(),
+                trusted_proxies: vec![],
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("trusted proxy"), "{err}");
+    }
+
+    #[test]
+    fn test_principal_must_be_a_valid_user_id() {
+        // URIs contain characters that are not allowed in user ids
+        let err = verifier("{san.uri}")
+            .authenticate(&escaped_pem(CLIENT))
+            .unwrap_err();
+        assert_eq!(err.code, 401);
+        let err = verifier("{san.email}")
+            .authenticate(&escaped_pem(CLIENT))
+            .unwrap_err();
+        assert!(err.message.contains("has no san.email"), "{}", err.message);
+    }
+
+    #[test]
+    fn test_principal_template_is_validated() {
+        validate_principal_template("svc-{san.dns}").unwrap();
+        validate_principal_template("static").unwrap_err();
+        validate_principal_template("{subject.cn}-{subject.serial}").unwrap_err();
+    }
+}
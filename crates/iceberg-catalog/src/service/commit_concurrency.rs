@@ -0,0 +1,228 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex, PoisonError},
+    time::Duration,
+};
+
+use axum_prometheus::metrics;
+use http::StatusCode;
+use iceberg_ext::catalog::rest::ErrorModel;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::{
+    config::CommitConcurrencyConfig,
+    request_metadata::RequestMetadata,
+    service::{Result, TableIdentUuid},
+};
+
+const REJECTED_COMMITS_METRIC: &str = "lakekeeper_commits_rejected_by_concurrency_limit_total";
+
+/// Slots of all tables with commits in flight on this server.
+static TABLE_COMMIT_SLOTS: LazyLock<TableCommitSlots> = LazyLock::new(TableCommitSlots::default);
+
+type Semaphores = Arc<Mutex<HashMap<TableIdentUuid, Arc<Semaphore>>>>;
+
+/// Semaphores bounding the number of commits in flight per table. A semaphore
+/// only exists while a commit to the table holds or waits for a slot.
+#[derive(Debug, Default)]
+struct TableCommitSlots {
+    semaphores: Semaphores,
+}
+
+/// Slot of a commit to one table. Released when dropped.
+#[derive(Debug)]
+pub(crate) struct TableCommitSlot {
+    table_id: TableIdentUuid,
+    permit: Option<OwnedSemaphorePermit>,
+    semaphores: Semaphores,
+}
+
+impl Drop for TableCommitSlot {
+    fn drop(&mut self) {
+        drop(self.permit.take());
+        let mut semaphores = self
+            .semaphores
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Only the map references the semaphore: No other commit holds or waits for a slot.
+        if semaphores
+            .get(&self.table_id)
+            .is_some_and(|semaphore| Arc::strong_count(semaphore) == 1)
+        {
+            semaphores.remove(&self.table_id);
+        }
+    }
+}
+
+impl TableCommitSlots {
+    /// Wait up to `max_wait` for a slot of each table. Slots are acquired in
+    /// the order of the table ids, so that commits to overlapping sets of tables
+    /// cannot deadlock.
+    async fn acquire(
+        &self,
+        table_ids: impl IntoIterator<Item = TableIdentUuid>,
+        limit: usize,
+        max_wait: Duration,
+    ) -> std::result::Result<Vec<TableCommitSlot>, TableIdentUuid> {
+        let mut table_ids = table_ids.into_iter().collect::<Vec<_>>();
+        table_ids.sort_unstable();
+        table_ids.dedup();
+
+        let deadline = tokio::time::Instant::now() + max_wait;
+        let mut slots = Vec::with_capacity(table_ids.len());
+        for table_id in table_ids {
+            let semaphore = self
+                .semaphores
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(table_id)
+                .or_insert_with(|| Arc::new(Semaphore::new(limit)))
+                .clone();
+            // Registered before waiting, so that the semaphore is removed if the wait times out.
+            let mut slot = TableCommitSlot {
+                table_id,
+                permit: None,
+                semaphores: self.semaphores.clone(),
+            };
+            let permit = tokio::time::timeout_at(deadline, semaphore.acquire_owned()).await;
+            drop(semaphore);
+            // Semaphores are never closed, so only the timeout can fail
+            let Ok(Ok(permit)) = permit else {
+                return Err(table_id);
+            };
+            slot.permit = Some(permit);
+            slots.push(slot);
+        }
+        Ok(slots)
+    }
+}
+
+/// Acquire a commit slot for each of the tables, waiting up to the configured
+/// time for commits in flight to finish. Commits are not limited if no maximum
+/// is configured. Slots are released when the returned value is dropped.
+///
+/// # Errors
+/// Fails with `429 Too Many Requests` and a `Retry-After` header if no slot
+/// became available in time.
+pub(crate) async fn acquire_table_commit_slots(
+    table_ids: impl IntoIterator<Item = TableIdentUuid>,
+    config: &CommitConcurrencyConfig,
+    request_metadata: &RequestMetadata,
+) -> Result<Vec<TableCommitSlot>> {
+    let Some(limit) = config.max_in_flight_commits_per_table else {
+        return Ok(vec![]);
+    };
+
+    TABLE_COMMIT_SLOTS
+        .acquire(table_ids, limit, config.max_wait)
+        .await
+        .map_err(|table_id| {
+            metrics::counter!(REJECTED_COMMITS_METRIC).increment(1);
+            request_metadata.set_retry_after(config.retry_after_seconds);
+            ErrorModel::new(
+                format!(
+                    "Too many concurrent commits to table {table_id} (maximum {limit}). Retry later."
+                ),
+                "TooManyConcurrentCommits",
+                StatusCode::TOO_MANY_REQUESTS.as_u16(),
+                None,
+            )
+            .into()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_commits_to_same_table_are_serialized() {
+        let slots = Arc::new(TableCommitSlots::default());
+        let table_id = TableIdentUuid::from(uuid::Uuid::now_v7());
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let commits = (0..8).map(|_| {
+            let (slots, in_flight, max_in_flight) =
+                (slots.clone(), in_flight.clone(), max_in_flight.clone());
+            tokio::spawn(async move {
+                let _slots = slots
+                    .acquire([table_id], 1, Duration::from_secs(10))
+                    .await
+                    .unwrap();
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            })
+        });
+        for commit in futures::future::join_all(commits).await {
+            commit.unwrap();
+        }
+
+        // All commits succeeded, one after another
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 1);
+        // Semaphores of tables without commits in flight are removed
+        assert!(slots.semaphores.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_commit_slot_wait_times_out() {
+        let slots = TableCommitSlots::default();
+        let busy = TableIdentUuid::from(uuid::Uuid::now_v7());
+        let other = TableIdentUuid::from(uuid::Uuid::now_v7());
+
+        let held = slots
+            .acquire([busy], 1, Duration::from_millis(10))
+            .await
+            .unwrap();
+        let err = slots
+            .acquire([other, busy], 1, Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert_eq!(err, busy);
+        // Other tables are not affected
+        let _other = slots
+            .acquire([other], 1, Duration::from_millis(10))
+            .await
+            .unwrap();
+
+        drop(held);
+        slots
+            .acquire([busy], 1, Duration::from_millis(10))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejected_commit_returns_429_with_retry_after() {
+        let config = CommitConcurrencyConfig {
+            max_in_flight_commits_per_table: Some(1),
+            max_wait: Duration::from_millis(10),
+            retry_after_seconds: 2,
+        };
+        let table_id = TableIdentUuid::from(uuid::Uuid::now_v7());
+        let metadata = RequestMetadata::new_unauthenticated();
+
+        let _held = acquire_table_commit_slots([table_id], &config, &metadata)
+            .await
+            .unwrap();
+        let err = acquire_table_commit_slots([table_id], &config, &metadata)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::TOO_MANY_REQUESTS.as_u16());
+        assert_eq!(err.error.r#type, "TooManyConcurrentCommits");
+        assert_eq!(metadata.retry_after(), Some(2));
+
+        // Commits are not limited by default
+        let config = CommitConcurrencyConfig::default();
+        for _ in 0..3 {
+            assert!(acquire_table_commit_slots([table_id], &config, &metadata)
+                .await
+                .unwrap()
+                .is_empty());
+        }
+    }
+}
@@ -0,0 +1,152 @@
+#![allow(clippy::module_name_repetitions)]
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use axum_prometheus::metrics;
+use iceberg::{spec::Summary, TableIdent};
+use iceberg_ext::catalog::rest::ErrorModel;
+
+use crate::{
+    service::{Catalog, TableIdentUuid, Transaction},
+    WarehouseIdent,
+};
+
+/// Information about a table commit that has been persisted successfully.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableCommitEvent {
+    pub warehouse_id: WarehouseIdent,
+    pub table_id: TableIdentUuid,
+    pub table_ident: TableIdent,
+    /// Current snapshot of the table before the commit.
+    pub parent_snapshot_id: Option<i64>,
+    /// Current snapshot of the table after the commit.
+    pub snapshot_id: Option<i64>,
+    /// Summary of the current snapshot after the commit.
+    pub summary: Option<Summary>,
+    /// Principal that made the commit. `None` for anonymous requests and
+    /// commits of background tasks.
+    pub principal: Option<String>,
+}
+
+/// A trait for observing successful table commits.
+///
+/// In contrast to [`ContractVerification`](crate::service::contract_verification::ContractVerification),
+/// observers cannot veto a change: they are called after the commit has been persisted, and any
+/// error they return is logged and counted, but never surfaced to the client. One possible
+/// application is emitting data-lineage information without blocking writes.
+///
+/// # Example
+///
+/// ```rust
+///     use async_trait::async_trait;
+///     use iceberg_catalog::service::commit_observer::{CommitObserver, TableCommitEvent};
+///     use iceberg_ext::catalog::rest::ErrorModel;
+///
+///     #[derive(Debug)]
+///     pub struct LoggingObserver;
+///
+///     #[async_trait]
+///     impl CommitObserver for LoggingObserver {
+///         fn name(&self) -> &'static str {
+///             "LoggingObserver"
+///         }
+///
+///         async fn on_table_commit(&self, commit: &TableCommitEvent) -> Result<(), ErrorModel> {
+///             println!(
+///                 "Table {} moved from snapshot {:?} to {:?}",
+///                 commit.table_id, commit.parent_snapshot_id, commit.snapshot_id
+///             );
+///             Ok(())
+///         }
+///     }
+/// ```
+#[async_trait]
+pub trait CommitObserver: Debug {
+    fn name(&self) -> &'static str;
+
+    async fn on_table_commit(&self, commit: &TableCommitEvent) -> Result<(), ErrorModel>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CommitObservers {
+    observers: Vec<Arc<dyn CommitObserver + Sync + Send>>,
+}
+
+impl CommitObservers {
+    #[must_use]
+    pub fn new(observers: Vec<Arc<dyn CommitObserver + Sync + Send>>) -> Self {
+        Self { observers }
+    }
+
+    /// Notify all observers about the given commits.
+    ///
+    /// Failures are logged and counted in the `lakekeeper_commit_observer_failures_total`
+    /// metric. They are never returned, as the commit has already been persisted.
+    pub async fn notify_table_commits(&self, commits: &[TableCommitEvent]) {
+        if self.observers.is_empty() {
+            return;
+        }
+
+        let futures = commits.iter().flat_map(|commit| {
+            self.observers.iter().map(move |observer| async move {
+                if let Err(error) = observer.on_table_commit(commit).await {
+                    tracing::warn!(
+                        ?error,
+                        "CommitObserver '{}' failed for table '{}'",
+                        observer.name(),
+                        commit.table_id
+                    );
+                    metrics::counter!(
+                        "lakekeeper_commit_observer_failures_total",
+                        "observer" => observer.name()
+                    )
+                    .increment(1);
+                }
+            })
+        });
+        futures::future::join_all(futures).await;
+    }
+}
+
+/// Records the principal of each commit that added a snapshot, which is shown
+/// in the history of the table.
+pub struct CommitAttributionObserver<C: Catalog> {
+    catalog_state: C::State,
+}
+
+impl<C: Catalog> CommitAttributionObserver<C> {
+    #[must_use]
+    pub fn new(catalog_state: C::State) -> Self {
+        Self { catalog_state }
+    }
+}
+
+impl<C: Catalog> Debug for CommitAttributionObserver<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommitAttributionObserver").finish()
+    }
+}
+
+#[async_trait]
+impl<C: Catalog> CommitObserver for CommitAttributionObserver<C> {
+    fn name(&self) -> &'static str {
+        "CommitAttributionObserver"
+    }
+
+    async fn on_table_commit(&self, commit: &TableCommitEvent) -> Result<(), ErrorModel> {
+        let (Some(snapshot_id), Some(principal)) = (commit.snapshot_id, &commit.principal) else {
+            return Ok(());
+        };
+        if commit.parent_snapshot_id == Some(snapshot_id) {
+            return Ok(());
+        }
+
+        let mut t = C::Transaction::begin_write(self.catalog_state.clone())
+            .await
+            .map_err(|e| e.error)?;
+        C::insert_commit_attribution(commit.table_id, snapshot_id, principal, t.transaction())
+            .await
+            .map_err(|e| e.error)?;
+        t.commit().await.map_err(|e| e.error)
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use axum_prometheus::metrics;
+use http::StatusCode;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+use crate::{request_metadata::RequestMetadata, service::Result, WarehouseIdent};
+
+const THROTTLED_COMMITS_METRIC: &str = "lakekeeper_throttled_commits_total";
+
+/// Lowest sustained commit rate of a warehouse: one commit per day.
+const MIN_COMMITS_PER_SECOND: f64 = 1.0 / 86_400.0;
+
+/// Buckets of all warehouses that received commits on this server.
+static COMMIT_THROTTLE: LazyLock<CommitThrottle> = LazyLock::new(CommitThrottle::default);
+
+/// Maximum rate of commits to tables and views of a warehouse.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitRateLimit {
+    /// Sustained number of commits per second.
+    pub commits_per_second: f64,
+    /// Number of commits accepted at once after a quiet period.
+    pub burst: u32,
+}
+
+impl CommitRateLimit {
+    /// # Errors
+    /// Fails if the rate is below one commit per day or the burst is zero.
+    pub fn validate(&self) -> Result<()> {
+        if !self.commits_per_second.is_finite() || self.commits_per_second < MIN_COMMITS_PER_SECOND
+        {
+            return Err(ErrorModel::bad_request(
+                "`commits-per-second` must allow at least one commit per day",
+                "InvalidCommitRateLimit",
+                None,
+            )
+            .into());
+        }
+        if self.burst == 0 {
+            return Err(ErrorModel::bad_request(
+                "`burst` must be at least 1",
+                "InvalidCommitRateLimit",
+                None,
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: CommitRateLimit,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: CommitRateLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst),
+            updated_at: now,
+        }
+    }
+
+    /// Take a token, or return the time until the next token is available.
+    fn try_take(&mut self, now: Instant) -> std::result::Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.commits_per_second)
+            .min(f64::from(self.limit.burst));
+        self.updated_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            // Saturate for rates that were stored before they were validated
+            Err(
+                Duration::try_from_secs_f64((1.0 - self.tokens) / self.limit.commits_per_second)
+                    .unwrap_or(Duration::MAX),
+            )
+        }
+    }
+}
+
+/// Token buckets limiting the commit rate of each warehouse independently.
+/// Limits are enforced per server instance.
+#[derive(Debug, Default)]
+struct CommitThrottle {
+    buckets: Mutex<HashMap<WarehouseIdent, TokenBucket>>,
+}
+
+impl CommitThrottle {
+    fn try_acquire(
+        &self,
+        warehouse_id: WarehouseIdent,
+        limit: CommitRateLimit,
+        now: Instant,
+    ) -> std::result::Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        let bucket = buckets
+            .entry(warehouse_id)
+            .or_insert_with(|| TokenBucket::new(limit, now));
+        // The limit was changed by an admin
+        if bucket.limit != limit {
+            let tokens = bucket.tokens.min(f64::from(limit.burst));
+            *bucket = TokenBucket::new(limit, now);
+            bucket.tokens = tokens;
+        }
+        bucket.try_take(now)
+    }
+}
+
+/// Require that a commit to the warehouse stays within its commit rate limit.
+/// Warehouses without a limit are not throttled.
+///
+/// # Errors
+/// Fails with `429 Too Many Requests` if the limit is exceeded. The time until
+/// the next commit is accepted is returned via the `Retry-After` header.
+pub(crate) fn throttle_commit(
+    warehouse_id: WarehouseIdent,
+    limit: Option<CommitRateLimit>,
+    request_metadata: &RequestMetadata,
+) -> Result<()> {
+    let Some(limit) = limit else {
+        return Ok(());
+    };
+
+    COMMIT_THROTTLE
+        .try_acquire(warehouse_id, limit, Instant::now())
+        .map_err(|wait| {
+            metrics::counter!(THROTTLED_COMMITS_METRIC).increment(1);
+            // Retry-After only supports whole seconds
+            let seconds = wait
+                .as_secs()
+                .saturating_add(u64::from(wait.subsec_nanos() > 0));
+            request_metadata.set_retry_after(seconds.max(1));
+            ErrorModel::new(
+                format!(
+                    "Commit rate limit of warehouse exceeded ({} commits per second, burst {}). Retry later.",
+                    limit.commits_per_second, limit.burst
+                ),
+                "CommitRateLimitExceeded",
+                StatusCode::TOO_MANY_REQUESTS.as_u16(),
+                None,
+            )
+            .into()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_exceeding_commit_rate_of_one_warehouse_does_not_affect_another() {
+        let throttle = CommitThrottle::default();
+        let limit = CommitRateLimit {
+            commits_per_second: 1.0,
+            burst: 2,
+        };
+        let noisy = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let quiet = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let now = Instant::now();
+
+        assert!(throttle.try_acquire(noisy, limit, now).is_ok());
+        assert!(throttle.try_acquire(noisy, limit, now).is_ok());
+        let wait = throttle.try_acquire(noisy, limit, now).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(1));
+
+        assert!(throttle.try_acquire(quiet, limit, now).is_ok());
+        assert!(throttle.try_acquire(quiet, limit, now).is_ok());
+
+        // Tokens are refilled at the configured rate
+        let later = now + Duration::from_millis(1500);
+        assert!(throttle.try_acquire(noisy, limit, later).is_ok());
+        assert!(throttle.try_acquire(noisy, limit, later).is_err());
+    }
+
+    #[test]
+    fn test_throttled_commit_returns_429_with_retry_after() {
+        let limit = CommitRateLimit {
+            commits_per_second: 0.1,
+            burst: 1,
+        };
+        let noisy = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let quiet = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let metadata = RequestMetadata::new_unauthenticated();
+
+        throttle_commit(noisy, Some(limit), &metadata).unwrap();
+        let err = throttle_commit(noisy, Some(limit), &metadata).unwrap_err();
+        assert_eq!(err.error.code, StatusCode::TOO_MANY_REQUESTS.as_u16());
+        assert_eq!(err.error.r#type, "CommitRateLimitExceeded");
+        assert!(metadata
+            .retry_after()
+            .is_some_and(|s| (9..=10).contains(&s)));
+
+        let metadata = RequestMetadata::new_unauthenticated();
+        throttle_commit(quiet, Some(limit), &metadata).unwrap();
+        assert_eq!(metadata.retry_after(), None);
+
+        // Warehouses without a limit are never throttled
+        for _ in 0..10 {
+            throttle_commit(noisy, None, &metadata).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_validate_commit_rate_limit() {
+        for (commits_per_second, burst) in [
+            (0.0, 1),
+            (-1.0, 1),
+            (f64::NAN, 1),
+            (f64::MIN_POSITIVE, 1),
+            (1.0, 0),
+        ] {
+            assert!(CommitRateLimit {
+                commits_per_second,
+                burst
+            }
+            .validate()
+            .is_err());
+        }
+        assert!(CommitRateLimit {
+            commits_per_second: 0.5,
+            burst: 1
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_tiny_commit_rate_does_not_panic() {
+        let throttle = CommitThrottle::default();
+        let limit = CommitRateLimit {
+            commits_per_second: f64::MIN_POSITIVE,
+            burst: 1,
+        };
+        let warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let now = Instant::now();
+
+        assert!(throttle.try_acquire(warehouse_id, limit, now).is_ok());
+        let wait = throttle.try_acquire(warehouse_id, limit, now).unwrap_err();
+        assert_eq!(wait, Duration::MAX);
+    }
+}
@@ -7,8 +7,30 @@ use iceberg::{
     TableIdent, TableUpdate,
 };
 use iceberg_ext::catalog::rest::{ErrorModel, ViewUpdate};
+use serde::{Deserialize, Serialize};
 
-use crate::service::TabularIdentUuid;
+use crate::{
+    service::{audit::record_system_decision, TabularIdentUuid},
+    CONFIG,
+};
+
+/// Action recorded in the audit log when a change is allowed although a verifier failed.
+const BYPASS_AUDIT_ACTION: &str = "bypass_contract_verification";
+
+/// Behavior if a contract verifier fails to check a change, for example because
+/// the verification service is unreachable. A verifier that rejects the change
+/// always blocks it, regardless of this policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContractVerifierUnavailablePolicy {
+    /// Block the change.
+    #[default]
+    #[serde(alias = "fail_closed", alias = "FailClosed")]
+    FailClosed,
+    /// Allow the change, logging a warning and writing an audit record.
+    #[serde(alias = "fail_open", alias = "FailOpen")]
+    FailOpen,
+}
 
 /// A trait for checking if a table change is allowed.
 ///
@@ -117,6 +139,10 @@ use crate::service::TabularIdentUuid;
 ///         }
 ///     }
 /// ```
+///
+/// Return `Ok(ContractVerificationOutcome::Violation)` to reject a change. Return `Err` only if the
+/// change could not be checked, for example because an external service is unavailable. How such
+/// failures are handled is governed by [`ContractVerifierUnavailablePolicy`].
 #[async_trait]
 pub trait ContractVerification: Debug {
     fn name(&self) -> &'static str;
@@ -189,12 +215,53 @@ impl ContractVerificationOutcome {
 #[derive(Debug, Clone)]
 pub struct ContractVerifiers {
     checkers: Vec<Arc<dyn ContractVerification + Sync + Send>>,
+    unavailable_policy: ContractVerifierUnavailablePolicy,
 }
 
 impl ContractVerifiers {
+    /// Verifiers that handle failing checkers according to the configured
+    /// `contract_verifier_unavailable_policy`.
     #[must_use]
     pub fn new(checkers: Vec<Arc<dyn ContractVerification + Sync + Send>>) -> Self {
-        Self { checkers }
+        Self {
+            checkers,
+            unavailable_policy: CONFIG.contract_verifier_unavailable_policy,
+        }
+    }
+
+    #[must_use]
+    pub fn with_unavailable_policy(mut self, policy: ContractVerifierUnavailablePolicy) -> Self {
+        self.unavailable_policy = policy;
+        self
+    }
+
+    /// Handle a checker that failed to check a change on `object`.
+    /// Returns `Ok` if the change may proceed without the checker.
+    fn on_checker_failure(
+        &self,
+        checker: &(dyn ContractVerification + Sync + Send),
+        object: &str,
+        error: ErrorModel,
+    ) -> Result<(), ErrorModel> {
+        match self.unavailable_policy {
+            ContractVerifierUnavailablePolicy::FailClosed => {
+                tracing::warn!("ContractVerifier '{}' failed", checker.name());
+                Err(error)
+            }
+            ContractVerifierUnavailablePolicy::FailOpen => {
+                tracing::warn!(
+                    "ContractVerifier '{}' failed, allowing change on '{object}' without it: {}",
+                    checker.name(),
+                    error
+                );
+                record_system_decision(
+                    format!("{BYPASS_AUDIT_ACTION}:{}", checker.name()),
+                    object,
+                    true,
+                );
+                Ok(())
+            }
+        }
     }
 }
 
@@ -223,10 +290,11 @@ impl ContractVerification for ContractVerifiers {
                     );
                     return Ok(block_result);
                 }
-                Err(error) => {
-                    tracing::warn!("Checker {} failed", checker.name());
-                    return Err(error);
-                }
+                Err(error) => self.on_checker_failure(
+                    checker.as_ref(),
+                    &format!("table:{}", current_metadata.uuid()),
+                    error,
+                )?,
             }
         }
 
@@ -252,10 +320,11 @@ impl ContractVerification for ContractVerifiers {
                     );
                     return Ok(block_result);
                 }
-                Err(error) => {
-                    tracing::warn!("Checker {} failed", checker.name());
-                    return Err(error);
-                }
+                Err(error) => self.on_checker_failure(
+                    checker.as_ref(),
+                    &format!("view:{}", current_metadata.view_uuid),
+                    error,
+                )?,
             }
         }
 
@@ -277,10 +346,11 @@ impl ContractVerification for ContractVerifiers {
                     );
                     return Ok(block_result);
                 }
-                Err(error) => {
-                    tracing::warn!("ContractVerifier '{}' failed", checker.name());
-                    return Err(error);
-                }
+                Err(error) => self.on_checker_failure(
+                    checker.as_ref(),
+                    &tabular_object(table_ident_uuid),
+                    error,
+                )?,
             }
         }
         Ok(ContractVerificationOutcome::Clear {})
@@ -304,11 +374,203 @@ impl ContractVerification for ContractVerifiers {
                     return Ok(block_result);
                 }
                 Err(error) => {
-                    tracing::warn!("ContractVerifier '{}' failed", checker.name());
-                    return Err(error);
+                    self.on_checker_failure(checker.as_ref(), &tabular_object(source), error)?
                 }
             }
         }
         Ok(ContractVerificationOutcome::Clear {})
     }
 }
+
+/// Object of a tabular as it appears in the audit log, for example `table:<table-id>`.
+fn tabular_object(tabular_id: TabularIdentUuid) -> String {
+    match tabular_id {
+        TabularIdentUuid::Table(id) => format!("table:{id}"),
+        TabularIdentUuid::View(id) => format!("view:{id}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use iceberg::spec::{
+        FormatVersion, Schema, SortOrder, TableMetadataBuilder, UnboundPartitionSpec,
+    };
+
+    use super::*;
+
+    /// Simulates a verifier whose service is unreachable or that rejects every change.
+    #[derive(Debug)]
+    enum TestVerifier {
+        Unavailable,
+        Rejecting,
+    }
+
+    impl TestVerifier {
+        fn outcome(&self) -> Result<ContractVerificationOutcome, ErrorModel> {
+            match self {
+                TestVerifier::Unavailable => Err(ErrorModel::builder()
+                    .code(503)
+                    .message("Connection refused")
+                    .r#type("ContractVerifierUnavailable".to_string())
+                    .build()),
+                TestVerifier::Rejecting => Ok(ContractVerificationOutcome::Violation {
+                    error_model: ErrorModel::builder()
+                        .code(409)
+                        .message("Column is part of a contract")
+                        .r#type("ContractViolation".to_string())
+                        .build(),
+                }),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ContractVerification for TestVerifier {
+        fn name(&self) -> &'static str {
+            "TestVerifier"
+        }
+
+        async fn check_table_updates(
+            &self,
+            _table_updates: &[TableUpdate],
+            _current_metadata: &TableMetadata,
+        ) -> Result<ContractVerificationOutcome, ErrorModel> {
+            self.outcome()
+        }
+
+        async fn check_view_updates(
+            &self,
+            _view_updates: &[ViewUpdate],
+            _current_metadata: &ViewMetadata,
+        ) -> Result<ContractVerificationOutcome, ErrorModel> {
+            self.outcome()
+        }
+
+        async fn check_drop(
+            &self,
+            _table_ident_uuid: TabularIdentUuid,
+        ) -> Result<ContractVerificationOutcome, ErrorModel> {
+            self.outcome()
+        }
+
+        async fn check_rename(
+            &self,
+            _source: TabularIdentUuid,
+            _destination: &TableIdent,
+        ) -> Result<ContractVerificationOutcome, ErrorModel> {
+            self.outcome()
+        }
+    }
+
+    fn verifiers(
+        verifier: TestVerifier,
+        policy: ContractVerifierUnavailablePolicy,
+    ) -> ContractVerifiers {
+        ContractVerifiers::new(vec![Arc::new(verifier)]).with_unavailable_policy(policy)
+    }
+
+    fn table_metadata() -> TableMetadata {
+        TableMetadataBuilder::new(
+            Schema::builder().build().unwrap(),
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata
+    }
+
+    #[tokio::test]
+    async fn test_fail_closed_blocks_on_unavailable_verifier() {
+        let verifiers = verifiers(
+            TestVerifier::Unavailable,
+            ContractVerifierUnavailablePolicy::FailClosed,
+        );
+        let err = verifiers
+            .check_table_updates(&[], &table_metadata())
+            .await
+            .unwrap_err();
+        assert_eq!(err.r#type, "ContractVerifierUnavailable");
+        let err = verifiers
+            .check_drop(TabularIdentUuid::Table(uuid::Uuid::now_v7()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.r#type, "ContractVerifierUnavailable");
+    }
+
+    #[tokio::test]
+    async fn test_fail_open_allows_on_unavailable_verifier() {
+        let verifiers = verifiers(
+            TestVerifier::Unavailable,
+            ContractVerifierUnavailablePolicy::FailOpen,
+        );
+        verifiers
+            .check_table_updates(&[], &table_metadata())
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap();
+        verifiers
+            .check_drop(TabularIdentUuid::Table(uuid::Uuid::now_v7()))
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap();
+        verifiers
+            .check_rename(
+                TabularIdentUuid::View(uuid::Uuid::now_v7()),
+                &TableIdent::from_strs(["ns", "renamed"]).unwrap(),
+            )
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejections_block_regardless_of_policy() {
+        for policy in [
+            ContractVerifierUnavailablePolicy::FailClosed,
+            ContractVerifierUnavailablePolicy::FailOpen,
+        ] {
+            let verifiers = verifiers(TestVerifier::Rejecting, policy);
+            let err = verifiers
+                .check_table_updates(&[], &table_metadata())
+                .await
+                .unwrap()
+                .into_result()
+                .unwrap_err();
+            assert_eq!(err.r#type, "ContractViolation", "{policy:?}");
+            let err = verifiers
+                .check_drop(TabularIdentUuid::Table(uuid::Uuid::now_v7()))
+                .await
+                .unwrap()
+                .into_result()
+                .unwrap_err();
+            assert_eq!(err.r#type, "ContractViolation", "{policy:?}");
+        }
+    }
+
+    #[test]
+    fn test_unavailable_policy_deserialization() {
+        for (value, expected) in [
+            ("fail-closed", ContractVerifierUnavailablePolicy::FailClosed),
+            ("fail-open", ContractVerifierUnavailablePolicy::FailOpen),
+            ("fail_open", ContractVerifierUnavailablePolicy::FailOpen),
+        ] {
+            let policy: ContractVerifierUnavailablePolicy =
+                serde_json::from_str(&format!("\"{value}\"")).unwrap();
+            assert_eq!(policy, expected);
+        }
+        assert_eq!(
+            ContractVerifierUnavailablePolicy::default(),
+            ContractVerifierUnavailablePolicy::FailClosed
+        );
+    }
+}
@@ -0,0 +1,190 @@
+#![allow(clippy::module_name_repetitions)]
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use iceberg::TableIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+
+use crate::{request_metadata::RequestMetadata, service::CreateTableRequest};
+
+/// Additions a [`CreateRequestMutator`] makes to a create-table request.
+///
+/// Mutations can only add to a request: properties that are already set, by the
+/// user or by a previous mutator, are kept, and a location requested by the user
+/// is never changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreateTableMutation {
+    /// Properties to add to the table. Iceberg tables have no tags, so tags are
+    /// added as properties as well.
+    pub properties: HashMap<String, String>,
+    /// Relative path, such as `org-a/finance`, inserted between the default parent
+    /// location of the table and the table's own folder. Ignored if the request
+    /// specifies a location.
+    pub location_sub_prefix: Option<String>,
+}
+
+/// A trait for augmenting requests to create tables before they are validated.
+///
+/// In contrast to [`ContractVerification`](crate::service::contract_verification::ContractVerification),
+/// mutators cannot veto a creation: they return additions, which are applied to the request in the
+/// order the mutators are configured in and are part of the created table's metadata. One possible
+/// application is stamping properties derived from the requesting principal on every new table.
+/// An error returned by a mutator fails the creation.
+///
+/// # Example
+///
+/// ```rust
+///     use async_trait::async_trait;
+///     use iceberg::TableIdent;
+///     use iceberg_catalog::{
+///         api::RequestMetadata,
+///         service::{
+///             create_request_mutator::{CreateRequestMutator, CreateTableMutation},
+///             CreateTableRequest,
+///         },
+///     };
+///     use iceberg_ext::catalog::rest::ErrorModel;
+///
+///     #[derive(Debug)]
+///     pub struct CreatedByMutator;
+///
+///     #[async_trait]
+///     impl CreateRequestMutator for CreatedByMutator {
+///         fn name(&self) -> &'static str {
+///             "CreatedByMutator"
+///         }
+///
+///         async fn mutate_create_table(
+///             &self,
+///             _request: &CreateTableRequest,
+///             _table: &TableIdent,
+///             request_metadata: &RequestMetadata,
+///         ) -> Result<CreateTableMutation, ErrorModel> {
+///             let mut mutation = CreateTableMutation::default();
+///             if let Some(user_id) = request_metadata.user_id() {
+///                 mutation
+///                     .properties
+///                     .insert("created-by".to_string(), user_id.to_string());
+///             }
+///             Ok(mutation)
+///         }
+///     }
+/// ```
+#[async_trait]
+pub trait CreateRequestMutator: Debug {
+    fn name(&self) -> &'static str;
+
+    async fn mutate_create_table(
+        &self,
+        request: &CreateTableRequest,
+        table: &TableIdent,
+        request_metadata: &RequestMetadata,
+    ) -> Result<CreateTableMutation, ErrorModel>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CreateRequestMutators {
+    mutators: Vec<Arc<dyn CreateRequestMutator + Sync + Send>>,
+}
+
+impl CreateRequestMutators {
+    #[must_use]
+    pub fn new(mutators: Vec<Arc<dyn CreateRequestMutator + Sync + Send>>) -> Self {
+        Self { mutators }
+    }
+
+    /// Apply all mutators to `request` in order. Each mutator sees the additions of
+    /// the previous ones.
+    ///
+    /// Returns the segments of the location sub-prefixes of all mutators in order.
+    /// Empty if the request specifies a location or no mutator returned a sub-prefix.
+    ///
+    /// # Errors
+    /// - If a mutator fails
+    /// - If a mutator returns an invalid location sub-prefix
+    pub async fn mutate_create_table(
+        &self,
+        request: &mut CreateTableRequest,
+        table: &TableIdent,
+        request_metadata: &RequestMetadata,
+    ) -> Result<Vec<String>, ErrorModel> {
+        let mut sub_prefix = Vec::new();
+        for mutator in &self.mutators {
+            let CreateTableMutation {
+                properties,
+                location_sub_prefix,
+            } = mutator
+                .mutate_create_table(request, table, request_metadata)
+                .await
+                .inspect_err(|error| {
+                    tracing::warn!(
+                        ?error,
+                        "CreateRequestMutator '{}' failed for table '{table}'",
+                        mutator.name()
+                    );
+                })?;
+
+            if !properties.is_empty() {
+                let request_properties = request.properties.get_or_insert_with(HashMap::new);
+                for (key, value) in properties {
+                    request_properties.entry(key).or_insert(value);
+                }
+            }
+            if let Some(location_sub_prefix) = location_sub_prefix {
+                sub_prefix.extend(
+                    location_segments(mutator.name(), &location_sub_prefix)?
+                        .into_iter()
+                        .map(str::to_string),
+                );
+            }
+        }
+
+        if request.location.is_some() {
+            return Ok(vec![]);
+        }
+        Ok(sub_prefix)
+    }
+}
+
+/// Split a location sub-prefix into its segments. Sub-prefixes must be relative
+/// and must not leave the default location.
+fn location_segments<'a>(
+    mutator: &str,
+    location_sub_prefix: &'a str,
+) -> Result<Vec<&'a str>, ErrorModel> {
+    let segments = location_sub_prefix
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    if location_sub_prefix.contains("://")
+        || segments
+            .iter()
+            .any(|segment| matches!(*segment, "." | ".."))
+    {
+        return Err(ErrorModel::internal(
+            format!(
+                "CreateRequestMutator '{mutator}' returned an invalid location sub-prefix '{location_sub_prefix}'"
+            ),
+            "InvalidLocationSubPrefix",
+            None,
+        ));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_segments() {
+        assert_eq!(
+            location_segments("m", "/org-a//finance/").unwrap(),
+            vec!["org-a", "finance"]
+        );
+        for invalid in ["../other", "a/./b", "s3://bucket/a"] {
+            let err = location_segments("m", invalid).unwrap_err();
+            assert_eq!(err.r#type, "InvalidLocationSubPrefix");
+        }
+    }
+}
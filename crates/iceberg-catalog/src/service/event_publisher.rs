@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use cloudevents::Event;
 use uuid::Uuid;
 
-use super::WarehouseIdent;
+use super::{NamespaceIdentUuid, WarehouseIdent};
 use crate::service::tabular_idents::TabularIdentUuid;
 
 #[derive(Debug, Clone)]
@@ -36,6 +36,49 @@ impl CloudEventsPublisher {
         typ: &str,
         data: serde_json::Value,
         metadata: EventMetadata,
+    ) -> anyhow::Result<()> {
+        self.send(id, typ, data, EventSubject::Tabular(metadata))
+            .await
+    }
+
+    /// Publish an event about a namespace rather than a table or view.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be sent to the channel due to capacity / timeout.
+    pub async fn publish_namespace_event(
+        &self,
+        id: Uuid,
+        typ: &str,
+        data: serde_json::Value,
+        metadata: NamespaceEventMetadata,
+    ) -> anyhow::Result<()> {
+        self.send(id, typ, data, EventSubject::Namespace(metadata))
+            .await
+    }
+
+    /// Publish an event about a warehouse, for example a change of its storage credential.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be sent to the channel due to capacity / timeout.
+    pub async fn publish_warehouse_event(
+        &self,
+        id: Uuid,
+        typ: &str,
+        data: serde_json::Value,
+        metadata: WarehouseEventMetadata,
+    ) -> anyhow::Result<()> {
+        self.send(id, typ, data, EventSubject::Warehouse(metadata))
+            .await
+    }
+
+    async fn send(
+        &self,
+        id: Uuid,
+        typ: &str,
+        data: serde_json::Value,
+        metadata: EventSubject,
     ) -> anyhow::Result<()> {
         self.tx
             .send_timeout(
@@ -65,7 +108,32 @@ pub struct EventMetadata {
     pub prefix: String,
     pub num_events: usize,
     pub sequence_number: usize,
-    pub trace_id: Uuid,
+    pub trace_id: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NamespaceEventMetadata {
+    pub namespace_id: NamespaceIdentUuid,
+    pub warehouse_id: WarehouseIdent,
+    pub namespace: String,
+    pub prefix: String,
+    pub trace_id: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct WarehouseEventMetadata {
+    pub warehouse_id: WarehouseIdent,
+    /// Actor that triggered the event.
+    pub actor: String,
+    pub trace_id: String,
+}
+
+/// The catalog object an event is about.
+#[derive(Debug, Clone)]
+pub enum EventSubject {
+    Tabular(EventMetadata),
+    Namespace(NamespaceEventMetadata),
+    Warehouse(WarehouseEventMetadata),
 }
 
 #[derive(Debug)]
@@ -73,7 +141,7 @@ pub struct Payload {
     pub id: Uuid,
     pub typ: String,
     pub data: serde_json::Value,
-    pub metadata: EventMetadata,
+    pub metadata: EventSubject,
 }
 
 #[derive(Debug)]
@@ -113,34 +181,59 @@ impl CloudEventsPublisherBackgroundTask {
                 .ty(typ)
                 .data("application/json", data);
 
-            let EventMetadata {
-                tabular_id,
-                warehouse_id,
-                name,
-                namespace,
-                prefix,
-                num_events,
-                sequence_number,
-                trace_id,
-            } = metadata;
-            // TODO: this could be more elegant with a proc macro to give us IntoIter for EventMetadata
-            let event = event_builder
-                .extension("tabular-type", tabular_id.typ_str())
-                .extension("tabular-id", tabular_id.to_string())
-                .extension("warehouse-id", warehouse_id.to_string())
-                .extension("name", name.to_string())
-                .extension("namespace", namespace.to_string())
-                .extension("prefix", prefix.to_string())
-                // TODO: decide what to do with these numbers, likely they are never anywhere close to
-                // saturating the respective int types, so probably a non-issue. Still we are converting
-                // the numbers to_string here to avoid usize -> i64 which is what EventBuilderV10
-                // uses to represent integers. The CloudEvents spec states i32 would be the correct int
-                // type.
-                .extension("num-events", num_events.to_string())
-                .extension("sequence-number", sequence_number.to_string())
-                // Implement distributed tracing: https://github.com/lakekeeper/lakekeeper/issues/63
-                .extension("trace-id", trace_id.to_string())
-                .build()?;
+            let event = match metadata {
+                EventSubject::Tabular(EventMetadata {
+                    tabular_id,
+                    warehouse_id,
+                    name,
+                    namespace,
+                    prefix,
+                    num_events,
+                    sequence_number,
+                    trace_id,
+                }) => {
+                    // TODO: this could be more elegant with a proc macro to give us IntoIter for EventMetadata
+                    event_builder
+                        .extension("tabular-type", tabular_id.typ_str())
+                        .extension("tabular-id", tabular_id.to_string())
+                        .extension("warehouse-id", warehouse_id.to_string())
+                        .extension("name", name.to_string())
+                        .extension("namespace", namespace.to_string())
+                        .extension("prefix", prefix.to_string())
+                        // TODO: decide what to do with these numbers, likely they are never anywhere close to
+                        // saturating the respective int types, so probably a non-issue. Still we are converting
+                        // the numbers to_string here to avoid usize -> i64 which is what EventBuilderV10
+                        // uses to represent integers. The CloudEvents spec states i32 would be the correct int
+                        // type.
+                        .extension("num-events", num_events.to_string())
+                        .extension("sequence-number", sequence_number.to_string())
+                        // Implement distributed tracing: https://github.com/lakekeeper/lakekeeper/issues/63
+                        .extension("trace-id", trace_id)
+                        .build()?
+                }
+                EventSubject::Namespace(NamespaceEventMetadata {
+                    namespace_id,
+                    warehouse_id,
+                    namespace,
+                    prefix,
+                    trace_id,
+                }) => event_builder
+                    .extension("namespace-id", namespace_id.to_string())
+                    .extension("warehouse-id", warehouse_id.to_string())
+                    .extension("namespace", namespace.to_string())
+                    .extension("prefix", prefix.to_string())
+                    .extension("trace-id", trace_id)
+                    .build()?,
+                EventSubject::Warehouse(WarehouseEventMetadata {
+                    warehouse_id,
+                    actor,
+                    trace_id,
+                }) => event_builder
+                    .extension("warehouse-id", warehouse_id.to_string())
+                    .extension("actor", actor)
+                    .extension("trace-id", trace_id)
+                    .build()?,
+            };
 
             for sink in &self.sinks {
                 if let Err(e) = sink.publish(event.clone()).await {
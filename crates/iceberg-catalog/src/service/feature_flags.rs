@@ -0,0 +1,172 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::{WarehouseIdent, CONFIG};
+
+/// Features that can be enabled or disabled per warehouse.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumIter,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum WarehouseFeatureFlag {
+    /// Listing and undropping of soft-deleted tabulars.
+    UndropTabulars,
+    /// Access to the warehouse statistics endpoint.
+    WarehouseStatistics,
+    /// Reject commits that add data or delete files outside of the table location.
+    ValidateDataFilePaths,
+}
+
+impl WarehouseFeatureFlag {
+    /// Value of this flag if the warehouse does not override it.
+    ///
+    /// Can be configured via `LAKEKEEPER__WAREHOUSE_FEATURE_FLAGS__<FLAG>`.
+    #[must_use]
+    pub fn server_default(self) -> bool {
+        CONFIG
+            .warehouse_feature_flags
+            .iter()
+            .find(|(k, _)| Self::parse(k) == Some(self))
+            .map_or_else(|| self.builtin_default(), |(_, v)| *v)
+    }
+
+    fn builtin_default(self) -> bool {
+        match self {
+            WarehouseFeatureFlag::UndropTabulars | WarehouseFeatureFlag::WarehouseStatistics => {
+                true
+            }
+            // Some setups intentionally reference files outside of the table location
+            WarehouseFeatureFlag::ValidateDataFilePaths => false,
+        }
+    }
+
+    /// Parse a flag name. Both kebab-case and `snake_case` are accepted,
+    /// as environment variables cannot contain dashes.
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        Self::from_str(&name.trim().to_lowercase().replace('_', "-")).ok()
+    }
+}
+
+/// Feature flags explicitly set on a warehouse.
+/// Flags that are not set fall back to the server default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarehouseFeatureFlags(BTreeMap<WarehouseFeatureFlag, bool>);
+
+impl WarehouseFeatureFlags {
+    /// Build flags from their stored representation. Unknown flags are ignored.
+    pub(crate) fn from_raw(warehouse_id: WarehouseIdent, raw: HashMap<String, bool>) -> Self {
+        Self(
+            raw.into_iter()
+                .filter_map(|(name, enabled)| {
+                    let flag = WarehouseFeatureFlag::parse(&name);
+                    if flag.is_none() {
+                        tracing::warn!(
+                            "Ignoring unknown feature flag '{name}' of warehouse '{warehouse_id}'"
+                        );
+                    }
+                    flag.map(|f| (f, enabled))
+                })
+                .collect(),
+        )
+    }
+
+    /// Representation used for storage.
+    pub(crate) fn to_raw(&self) -> HashMap<String, bool> {
+        self.0.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    /// Set or - if `enabled` is `None` - remove an override for `flag`.
+    pub fn set(&mut self, flag: WarehouseFeatureFlag, enabled: Option<bool>) {
+        match enabled {
+            Some(enabled) => self.0.insert(flag, enabled),
+            None => self.0.remove(&flag),
+        };
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self, flag: WarehouseFeatureFlag) -> bool {
+        self.0
+            .get(&flag)
+            .copied()
+            .unwrap_or_else(|| flag.server_default())
+    }
+
+    /// Effective value of every known flag, taking server defaults into account.
+    #[must_use]
+    pub fn effective(&self) -> BTreeMap<WarehouseFeatureFlag, bool> {
+        WarehouseFeatureFlag::iter()
+            .map(|f| (f, self.is_enabled(f)))
+            .collect()
+    }
+
+    /// Returns an error if `flag` is disabled.
+    ///
+    /// # Errors
+    /// - 403 if the feature is disabled for this warehouse
+    pub fn require(&self, flag: WarehouseFeatureFlag) -> Result<(), ErrorModel> {
+        if self.is_enabled(flag) {
+            Ok(())
+        } else {
+            Err(ErrorModel::forbidden(
+                format!("Feature '{flag}' is not enabled for this warehouse"),
+                "WarehouseFeatureDisabled",
+                None,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_feature_flag() {
+        assert_eq!(
+            WarehouseFeatureFlag::parse("undrop-tabulars"),
+            Some(WarehouseFeatureFlag::UndropTabulars)
+        );
+        assert_eq!(
+            WarehouseFeatureFlag::parse("WAREHOUSE_STATISTICS"),
+            Some(WarehouseFeatureFlag::WarehouseStatistics)
+        );
+        assert_eq!(WarehouseFeatureFlag::parse("scan-planning"), None);
+    }
+
+    #[test]
+    fn test_unknown_flags_are_ignored() {
+        let flags = WarehouseFeatureFlags::from_raw(
+            uuid::Uuid::nil().into(),
+            HashMap::from_iter([
+                ("undrop-tabulars".to_string(), false),
+                ("does-not-exist".to_string(), true),
+            ]),
+        );
+        assert!(!flags.is_enabled(WarehouseFeatureFlag::UndropTabulars));
+        assert!(flags.is_enabled(WarehouseFeatureFlag::WarehouseStatistics));
+        assert_eq!(
+            flags.to_raw(),
+            HashMap::from_iter([("undrop-tabulars".to_string(), false)])
+        );
+    }
+}
@@ -1,25 +1,42 @@
+pub mod audit;
 pub mod authn;
 pub mod authz;
+pub mod backend_timeout;
 mod catalog;
+pub mod client_certificate;
+pub mod commit_concurrency;
+pub mod commit_observer;
+pub mod commit_throttle;
 pub mod contract_verification;
+pub mod create_request_mutator;
 pub mod event_publisher;
+pub mod feature_flags;
 pub mod health;
+pub mod namespace_property_schema;
+pub mod schema_evolution;
+pub mod schema_validation;
+pub mod secret_encryption;
 pub mod secrets;
+pub mod snapshot_limit;
 pub mod storage;
 mod tabular_idents;
 pub mod task_queue;
+pub mod token_verification;
 
 use std::{ops::Deref, str::FromStr};
 
 pub use authn::{Actor, UserId};
 pub use catalog::{
-    Catalog, CommitTableResponse, CreateNamespaceRequest, CreateNamespaceResponse,
-    CreateOrUpdateUserResponse, CreateTableRequest, CreateTableResponse, DeletionDetails,
-    DropFlags, GetNamespaceResponse, GetProjectResponse, GetStorageConfigResponse,
-    GetTableMetadataResponse, GetWarehouseResponse, ListFlags, ListNamespacesQuery,
-    ListNamespacesResponse, LoadTableResponse, NamespaceIdent, Result, StartupValidationData,
-    TableCommit, TableCreation, TableIdent, Transaction, UndropTabularResponse,
-    UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse, ViewMetadataWithLocation,
+    Catalog, ChangedTable, CommitTableResponse, CreateNamespaceRequest, CreateNamespaceResponse,
+    CreateOrUpdateUserResponse, CreateTableRequest, CreateTableResponse, DeletedTabular,
+    DeletedWarehouseContents, DeletionDetails, DropFlags, GetNamespaceResponse, GetProjectResponse,
+    GetStorageConfigResponse, GetTableMetadataResponse, GetWarehouseResponse, IdempotencyRecord,
+    ListFlags, ListNamespacesQuery, ListNamespacesResponse, LoadTableResponse,
+    NamespaceDescendants, NamespaceIdent, NamespaceTabularCounts, NamespaceUsage, Result,
+    RotatedStorageSecrets, SnapshotIdOrTimestamp, StartupValidationData, TableCommit,
+    TableCreation, TableIdent, TableLocationCollision, TableLock, TableRename, Transaction,
+    UndropTabularResponse, UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
+    ViewMetadataWithLocation, WarehouseSecretReference, WarehouseSecretUsage,
 };
 use http::StatusCode;
 pub use secrets::{SecretIdent, SecretStore};
@@ -32,7 +49,8 @@ pub use crate::api::{ErrorModel, IcebergErrorResponse};
 use crate::{
     api::{iceberg::v1::Prefix, ThreadSafe as ServiceState},
     service::{
-        contract_verification::ContractVerifiers, event_publisher::CloudEventsPublisher,
+        commit_observer::CommitObservers, contract_verification::ContractVerifiers,
+        create_request_mutator::CreateRequestMutators, event_publisher::CloudEventsPublisher,
         task_queue::TaskQueues,
     },
 };
@@ -45,6 +63,8 @@ pub struct State<A: Authorizer + Clone, C: Catalog, S: SecretStore> {
     pub secrets: S,
     pub publisher: CloudEventsPublisher,
     pub contract_verifiers: ContractVerifiers,
+    pub commit_observers: CommitObservers,
+    pub create_request_mutators: CreateRequestMutators,
     pub queues: TaskQueues,
 }
 
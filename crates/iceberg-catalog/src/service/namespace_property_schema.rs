@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, HashMap};
+
+use iceberg_ext::catalog::rest::ErrorModel;
+use lazy_regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Constraints on the values of namespace properties of a warehouse, keyed by
+/// property name. Properties without a constraint accept any value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(transparent)]
+pub struct NamespacePropertySchema(pub BTreeMap<String, NamespacePropertyConstraint>);
+
+/// Format a namespace property value must conform to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum NamespacePropertyConstraint {
+    /// Any string, or a string fully matching `pattern` if specified.
+    String {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pattern: Option<String>,
+    },
+    /// A 64 bit integer within the inclusive bounds, if specified.
+    Integer {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<i64>,
+    },
+    /// `true` or `false`, case-insensitive.
+    Boolean,
+}
+
+impl NamespacePropertyConstraint {
+    fn accepts(&self, value: &str) -> Result<bool, lazy_regex::regex::Error> {
+        Ok(match self {
+            Self::String { pattern: None } => true,
+            Self::String {
+                pattern: Some(pattern),
+            } => anchored(pattern)?.is_match(value),
+            Self::Integer { min, max } => value.parse::<i64>().is_ok_and(|v| {
+                min.map_or(true, |min| v >= min) && max.map_or(true, |max| v <= max)
+            }),
+            Self::Boolean => {
+                value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")
+            }
+        })
+    }
+
+    fn expected_format(&self) -> String {
+        match self {
+            Self::String { pattern: None } => "a string".to_string(),
+            Self::String {
+                pattern: Some(pattern),
+            } => format!("a string matching `{pattern}`"),
+            Self::Integer { min, max } => match (min, max) {
+                (None, None) => "an integer".to_string(),
+                (Some(min), None) => format!("an integer >= {min}"),
+                (None, Some(max)) => format!("an integer <= {max}"),
+                (Some(min), Some(max)) => format!("an integer between {min} and {max}"),
+            },
+            Self::Boolean => "`true` or `false`".to_string(),
+        }
+    }
+}
+
+/// Patterns must match the whole value, not only a part of it.
+fn anchored(pattern: &str) -> Result<Regex, lazy_regex::regex::Error> {
+    Regex::new(&format!("^(?:{pattern})$"))
+}
+
+impl NamespacePropertySchema {
+    /// Require that the schema itself is valid: Property names must be lowercase,
+    /// patterns must be valid regular expressions and integer bounds must not be empty.
+    ///
+    /// # Errors
+    /// Fails with a 400 naming the first invalid property.
+    pub fn validate(&self) -> Result<(), ErrorModel> {
+        let invalid = |key: &str, reason: String| {
+            Err(ErrorModel::bad_request(
+                format!("Invalid constraint for namespace property '{key}': {reason}"),
+                "InvalidNamespacePropertySchema",
+                None,
+            ))
+        };
+
+        for (key, constraint) in &self.0 {
+            if key != &key.to_lowercase() {
+                return invalid(key, "Property names must be lowercase".to_string());
+            }
+            match constraint {
+                NamespacePropertyConstraint::String {
+                    pattern: Some(pattern),
+                } => {
+                    if let Err(e) = anchored(pattern) {
+                        return invalid(key, format!("Invalid pattern `{pattern}`: {e}"));
+                    }
+                }
+                NamespacePropertyConstraint::Integer {
+                    min: Some(min),
+                    max: Some(max),
+                } if min > max => {
+                    return invalid(key, format!("min {min} is greater than max {max}"));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Require that all constrained keys in `properties` have conforming values.
+    ///
+    /// # Errors
+    /// Fails with a 400 naming every nonconforming key and its expected format.
+    pub fn check_properties(&self, properties: &HashMap<String, String>) -> Result<(), ErrorModel> {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+
+        let mut violations = properties
+            .iter()
+            .filter_map(|(key, value)| {
+                let constraint = self.0.get(key)?;
+                // Patterns are validated when the schema is set
+                (!constraint.accepts(value).unwrap_or(true)).then(|| {
+                    format!(
+                        "Property '{key}' must be {}, got '{value}'",
+                        constraint.expected_format()
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        if violations.is_empty() {
+            return Ok(());
+        }
+        violations.sort();
+
+        Err(ErrorModel::bad_request(
+            format!(
+                "Namespace properties do not conform to the property schema of the warehouse: {}",
+                violations.join("; ")
+            ),
+            "NamespacePropertySchemaViolation",
+            None,
+        )
+        .append_details(violations))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn schema() -> NamespacePropertySchema {
+        serde_json::from_value(serde_json::json!({
+            "cost_center": {"type": "string", "pattern": "CC-[0-9]{4}"},
+            "ttl_days": {"type": "integer", "min": 1, "max": 365},
+            "pii": {"type": "boolean"},
+        }))
+        .unwrap()
+    }
+
+    fn properties(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_conforming_and_unconstrained_properties_are_accepted() {
+        let schema = schema();
+        schema.validate().unwrap();
+        schema
+            .check_properties(&properties(&[
+                ("cost_center", "CC-1234"),
+                ("ttl_days", "30"),
+                ("pii", "TRUE"),
+                ("owner", "anything goes"),
+            ]))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_nonconforming_properties_are_listed() {
+        let err = schema()
+            .check_properties(&properties(&[
+                ("cost_center", "CC-1234-extra"),
+                ("ttl_days", "0"),
+                ("pii", "yes"),
+            ]))
+            .unwrap_err();
+        assert_eq!(err.r#type, "NamespacePropertySchemaViolation");
+        assert_eq!(err.stack.len(), 3, "{:?}", err.stack);
+        assert!(err
+            .message
+            .contains("'ttl_days' must be an integer between 1 and 365"));
+        assert!(err
+            .message
+            .contains("'cost_center' must be a string matching `CC-[0-9]{4}`"));
+    }
+
+    #[test]
+    fn test_invalid_schemas_are_rejected() {
+        for schema in [
+            serde_json::json!({"Cost_Center": {"type": "string"}}),
+            serde_json::json!({"cost_center": {"type": "string", "pattern": "("}}),
+            serde_json::json!({"ttl_days": {"type": "integer", "min": 10, "max": 1}}),
+        ] {
+            let schema: NamespacePropertySchema = serde_json::from_value(schema).unwrap();
+            let err = schema.validate().unwrap_err();
+            assert_eq!(err.r#type, "InvalidNamespacePropertySchema");
+        }
+    }
+}
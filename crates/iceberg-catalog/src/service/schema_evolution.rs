@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+
+use iceberg::{
+    spec::{NestedFieldRef, Schema, TableMetadata, Type},
+    TableIdent,
+};
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+/// Schema changes a warehouse allows on commit.
+///
+/// The policy restricts changes that are legal according to the Iceberg spec
+/// but may break readers of a table. It is evaluated independently of
+/// [`ContractVerifiers`](super::contract_verification::ContractVerifiers).
+/// The default policy allows all changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case", default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct SchemaEvolutionPolicy {
+    /// Allow removing columns, including nested fields.
+    pub allow_column_drops: bool,
+    /// Allow changing the type of existing columns, for example promoting `int` to `long`.
+    pub allow_type_changes: bool,
+    /// Allow adding required columns to existing structs.
+    pub allow_required_field_additions: bool,
+}
+
+impl Default for SchemaEvolutionPolicy {
+    fn default() -> Self {
+        Self {
+            allow_column_drops: true,
+            allow_type_changes: true,
+            allow_required_field_additions: true,
+        }
+    }
+}
+
+struct IndexedField<'a> {
+    parent_id: Option<i32>,
+    field: &'a NestedFieldRef,
+    /// Whether the field is a column of a struct rather than a list element or map key / value.
+    is_struct_field: bool,
+}
+
+impl SchemaEvolutionPolicy {
+    /// Whether the policy allows all schema changes.
+    #[must_use]
+    pub fn allows_all(&self) -> bool {
+        self.allow_column_drops && self.allow_type_changes && self.allow_required_field_additions
+    }
+
+    /// Require that changing the current schema of a table from the one of
+    /// `previous` to the one of `new` complies with the policy.
+    ///
+    /// # Errors
+    /// Fails with a 400 listing every violating change.
+    pub fn check_table_commit(
+        &self,
+        table: &TableIdent,
+        previous: &TableMetadata,
+        new: &TableMetadata,
+    ) -> Result<(), ErrorModel> {
+        if self.allows_all() || previous.current_schema_id() == new.current_schema_id() {
+            return Ok(());
+        }
+
+        let violations = self.violations(previous.current_schema(), new.current_schema());
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        Err(ErrorModel::bad_request(
+            format!(
+                "Schema change of table '{}' violates the schema evolution policy of the warehouse: {}",
+                table,
+                violations.join("; ")
+            ),
+            "SchemaEvolutionPolicyViolation",
+            None,
+        )
+        .append_details(violations))
+    }
+
+    pub(crate) fn violations(&self, previous: &Schema, new: &Schema) -> Vec<String> {
+        let previous_fields = index_schema(previous);
+        let new_fields = index_schema(new);
+        let name = |schema: &Schema, field: &IndexedField<'_>| {
+            schema
+                .name_by_field_id(field.field.id)
+                .unwrap_or(&field.field.name)
+                .to_string()
+        };
+
+        let mut violations = vec![];
+        for (id, previous_field) in &previous_fields {
+            match new_fields.get(id) {
+                None => {
+                    // Only report the outermost dropped field
+                    let parent_dropped = previous_field
+                        .parent_id
+                        .is_some_and(|parent_id| !new_fields.contains_key(&parent_id));
+                    if !self.allow_column_drops && !parent_dropped {
+                        violations.push(format!(
+                            "dropped column '{}'",
+                            name(previous, previous_field)
+                        ));
+                    }
+                }
+                Some(new_field) => {
+                    if let (Type::Primitive(previous_type), Type::Primitive(new_type)) = (
+                        previous_field.field.field_type.as_ref(),
+                        new_field.field.field_type.as_ref(),
+                    ) {
+                        if !self.allow_type_changes && previous_type != new_type {
+                            violations.push(format!(
+                                "changed type of column '{}' from {previous_type} to {new_type}",
+                                name(new, new_field)
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if !self.allow_required_field_additions {
+            for (id, new_field) in &new_fields {
+                // Required fields of newly added structs, lists and maps are allowed
+                // as long as the added column itself is optional.
+                let parent_existed = new_field
+                    .parent_id
+                    .map_or(true, |parent_id| previous_fields.contains_key(&parent_id));
+                if !previous_fields.contains_key(id)
+                    && new_field.is_struct_field
+                    && new_field.field.required
+                    && parent_existed
+                {
+                    violations.push(format!(
+                        "added required column '{}'",
+                        name(new, new_field)
+                    ));
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+fn index_schema(schema: &Schema) -> BTreeMap<i32, IndexedField<'_>> {
+    let mut index = BTreeMap::new();
+    for field in schema.as_struct().fields() {
+        index_field(field, None, true, &mut index);
+    }
+    index
+}
+
+fn index_field<'a>(
+    field: &'a NestedFieldRef,
+    parent_id: Option<i32>,
+    is_struct_field: bool,
+    index: &mut BTreeMap<i32, IndexedField<'a>>,
+) {
+    index.insert(
+        field.id,
+        IndexedField {
+            parent_id,
+            field,
+            is_struct_field,
+        },
+    );
+    match field.field_type.as_ref() {
+        Type::Struct(struct_type) => {
+            for child in struct_type.fields() {
+                index_field(child, Some(field.id), true, index);
+            }
+        }
+        Type::List(list_type) => {
+            index_field(&list_type.element_field, Some(field.id), false, index);
+        }
+        Type::Map(map_type) => {
+            index_field(&map_type.key_field, Some(field.id), false, index);
+            index_field(&map_type.value_field, Some(field.id), false, index);
+        }
+        Type::Primitive(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iceberg::spec::{NestedField, PrimitiveType, StructType};
+
+    use super::*;
+
+    fn schema(fields: Vec<NestedField>) -> Schema {
+        Schema::builder()
+            .with_fields(fields.into_iter().map(Into::into))
+            .build()
+            .unwrap()
+    }
+
+    fn restrictive() -> SchemaEvolutionPolicy {
+        SchemaEvolutionPolicy {
+            allow_column_drops: false,
+            allow_type_changes: false,
+            allow_required_field_additions: false,
+        }
+    }
+
+    #[test]
+    fn test_default_policy_allows_all() {
+        let policy: SchemaEvolutionPolicy = serde_json::from_str("{}").unwrap();
+        assert_eq!(policy, SchemaEvolutionPolicy::default());
+        assert!(policy.allows_all());
+    }
+
+    #[test]
+    fn test_violations_are_listed() {
+        let previous = schema(vec![
+            NestedField::required(1, "id", Type::Primitive(PrimitiveType::Int)),
+            NestedField::optional(2, "name", Type::Primitive(PrimitiveType::String)),
+            NestedField::optional(
+                3,
+                "address",
+                Type::Struct(StructType::new(vec![NestedField::optional(
+                    4,
+                    "city",
+                    Type::Primitive(PrimitiveType::String),
+                )
+                .into()])),
+            ),
+        ]);
+        let new = schema(vec![
+            NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)),
+            NestedField::required(5, "age", Type::Primitive(PrimitiveType::Int)),
+            // Required fields of a new optional struct are fine
+            NestedField::optional(
+                6,
+                "contact",
+                Type::Struct(StructType::new(vec![NestedField::required(
+                    7,
+                    "email",
+                    Type::Primitive(PrimitiveType::String),
+                )
+                .into()])),
+            ),
+        ]);
+
+        let violations = restrictive().violations(&previous, &new);
+        assert_eq!(
+            violations,
+            vec![
+                "changed type of column 'id' from int to long",
+                "dropped column 'name'",
+                "dropped column 'address'",
+                "added required column 'age'",
+            ]
+        );
+        assert!(SchemaEvolutionPolicy::default()
+            .violations(&previous, &new)
+            .is_empty());
+    }
+}
@@ -0,0 +1,427 @@
+use std::collections::{BTreeMap, HashSet};
+
+use iceberg::spec::{PrimitiveType, Schema};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Kind of a rule of the Iceberg specification a schema violates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub enum SchemaViolationType {
+    /// A field, list element or map key / value lacks a required attribute,
+    /// or an attribute has the wrong JSON type.
+    MalformedField,
+    /// A field ID is used more than once.
+    DuplicateFieldId,
+    /// Two fields of the same struct have the same name.
+    DuplicateFieldName,
+    /// A type is not a known primitive, struct, list or map type.
+    InvalidType,
+    /// An identifier field ID references no field or a field that cannot be an identifier.
+    InvalidIdentifierField,
+    /// The schema is rejected by the Iceberg schema builder.
+    InvalidSchema,
+}
+
+/// A violation of the Iceberg rules by a proposed schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SchemaViolation {
+    pub r#type: SchemaViolationType,
+    /// Dot-separated path of the offending field. Absent for violations of the schema itself.
+    pub field_path: Option<String>,
+    pub message: String,
+}
+
+/// Field, list element or map key / value visited while validating a schema.
+struct VisitedField {
+    path: String,
+    primitive: Option<PrimitiveType>,
+    required: bool,
+    /// Whether the field is nested in a list, a map or an optional struct.
+    in_optional_or_collection: bool,
+}
+
+#[derive(Default)]
+struct SchemaValidator {
+    fields: BTreeMap<i64, Vec<VisitedField>>,
+    violations: Vec<SchemaViolation>,
+}
+
+/// Validate a proposed schema in its JSON representation against the structural
+/// rules of the Iceberg specification, without creating anything.
+///
+/// All violations found are reported. A schema without violations is accepted by
+/// table creation. An empty result means the schema is valid.
+#[must_use]
+pub fn validate_schema(schema: &Value) -> Vec<SchemaViolation> {
+    let mut validator = SchemaValidator::default();
+    let Some(object) = schema.as_object() else {
+        validator.violation(
+            SchemaViolationType::MalformedField,
+            None,
+            "Schema must be a JSON object",
+        );
+        return validator.violations;
+    };
+    if object.get("type").and_then(Value::as_str) != Some("struct") {
+        validator.violation(
+            SchemaViolationType::InvalidType,
+            None,
+            "Type of a schema must be `struct`",
+        );
+    }
+    validator.visit_struct(object, None, false);
+    validator.check_duplicate_ids();
+    validator.check_identifier_fields(object);
+
+    // Rules not covered above are enforced by the schema builder used by table creation.
+    if validator.violations.is_empty() {
+        if let Err(e) = serde_json::from_value::<Schema>(schema.clone()) {
+            validator.violation(SchemaViolationType::InvalidSchema, None, e.to_string());
+        }
+    }
+    validator.violations
+}
+
+impl SchemaValidator {
+    fn violation(
+        &mut self,
+        r#type: SchemaViolationType,
+        field_path: Option<&str>,
+        message: impl Into<String>,
+    ) {
+        self.violations.push(SchemaViolation {
+            r#type,
+            field_path: field_path.map(ToString::to_string),
+            message: message.into(),
+        });
+    }
+
+    fn visit_struct(
+        &mut self,
+        object: &Map<String, Value>,
+        path: Option<&str>,
+        in_optional_or_collection: bool,
+    ) {
+        let Some(fields) = object.get("fields").and_then(Value::as_array) else {
+            self.violation(
+                SchemaViolationType::MalformedField,
+                path,
+                "Struct must have a `fields` array",
+            );
+            return;
+        };
+
+        let mut names = HashSet::new();
+        for field in fields {
+            let Some(field) = field.as_object() else {
+                self.violation(
+                    SchemaViolationType::MalformedField,
+                    path,
+                    "Struct fields must be JSON objects",
+                );
+                continue;
+            };
+            let Some(name) = field.get("name").and_then(Value::as_str) else {
+                self.violation(
+                    SchemaViolationType::MalformedField,
+                    path,
+                    "Struct field must have a `name` string",
+                );
+                continue;
+            };
+            let field_path = path.map_or_else(|| name.to_string(), |p| format!("{p}.{name}"));
+            if !names.insert(name) {
+                self.violation(
+                    SchemaViolationType::DuplicateFieldName,
+                    Some(&field_path),
+                    format!("Field name `{name}` is used more than once in the same struct"),
+                );
+            }
+            let Some(required) = field.get("required").and_then(Value::as_bool) else {
+                self.violation(
+                    SchemaViolationType::MalformedField,
+                    Some(&field_path),
+                    "Field must have a boolean `required` attribute",
+                );
+                continue;
+            };
+            self.visit_field(
+                field,
+                "id",
+                "type",
+                required,
+                field_path,
+                in_optional_or_collection,
+            );
+        }
+    }
+
+    /// Visit a field, list element or map key / value, whose ID and type are
+    /// stored in the attributes `id_key` and `type_key` of `object`.
+    fn visit_field(
+        &mut self,
+        object: &Map<String, Value>,
+        id_key: &str,
+        type_key: &str,
+        required: bool,
+        path: String,
+        in_optional_or_collection: bool,
+    ) {
+        let Some(id) = object.get(id_key).and_then(Value::as_i64) else {
+            self.violation(
+                SchemaViolationType::MalformedField,
+                Some(&path),
+                format!("Field must have an integer `{id_key}`"),
+            );
+            return;
+        };
+        let Some(field_type) = object.get(type_key) else {
+            self.violation(
+                SchemaViolationType::MalformedField,
+                Some(&path),
+                format!("Field must have a `{type_key}`"),
+            );
+            return;
+        };
+
+        let primitive = self.visit_type(field_type, &path, in_optional_or_collection || !required);
+        self.fields.entry(id).or_default().push(VisitedField {
+            path,
+            primitive,
+            required,
+            in_optional_or_collection,
+        });
+    }
+
+    /// Visit a type. Returns the type if it is a valid primitive type.
+    fn visit_type(
+        &mut self,
+        field_type: &Value,
+        path: &str,
+        in_optional_or_collection: bool,
+    ) -> Option<PrimitiveType> {
+        let object = match field_type {
+            Value::String(primitive) => {
+                return match serde_json::from_value::<PrimitiveType>(field_type.clone()) {
+                    Ok(primitive) => Some(primitive),
+                    Err(_) => {
+                        self.violation(
+                            SchemaViolationType::InvalidType,
+                            Some(path),
+                            format!("Unknown primitive type `{primitive}`"),
+                        );
+                        None
+                    }
+                };
+            }
+            Value::Object(object) => object,
+            _ => {
+                self.violation(
+                    SchemaViolationType::InvalidType,
+                    Some(path),
+                    "Type must be a primitive type name or a JSON object",
+                );
+                return None;
+            }
+        };
+
+        match object.get("type").and_then(Value::as_str) {
+            Some("struct") => self.visit_struct(object, Some(path), in_optional_or_collection),
+            Some("list") => {
+                let required = self.required_attribute(object, "element-required", path);
+                self.visit_field(
+                    object,
+                    "element-id",
+                    "element",
+                    required,
+                    format!("{path}.element"),
+                    true,
+                );
+            }
+            Some("map") => {
+                self.visit_field(object, "key-id", "key", true, format!("{path}.key"), true);
+                let required = self.required_attribute(object, "value-required", path);
+                self.visit_field(
+                    object,
+                    "value-id",
+                    "value",
+                    required,
+                    format!("{path}.value"),
+                    true,
+                );
+            }
+            other => self.violation(
+                SchemaViolationType::InvalidType,
+                Some(path),
+                format!(
+                    "Nested type must be `struct`, `list` or `map`, found {}",
+                    other.map_or_else(|| "none".to_string(), |t| format!("`{t}`"))
+                ),
+            ),
+        }
+        None
+    }
+
+    fn required_attribute(&mut self, object: &Map<String, Value>, key: &str, path: &str) -> bool {
+        object.get(key).and_then(Value::as_bool).unwrap_or_else(|| {
+            self.violation(
+                SchemaViolationType::MalformedField,
+                Some(path),
+                format!("Type must have a boolean `{key}` attribute"),
+            );
+            false
+        })
+    }
+
+    fn check_duplicate_ids(&mut self) {
+        let duplicates = self
+            .fields
+            .iter()
+            .filter(|(_, fields)| fields.len() > 1)
+            .map(|(id, fields)| {
+                let paths = fields
+                    .iter()
+                    .map(|f| format!("`{}`", f.path))
+                    .collect::<Vec<_>>();
+                (
+                    fields[1].path.clone(),
+                    format!("Field ID {id} is used by fields {}", paths.join(", ")),
+                )
+            })
+            .collect::<Vec<_>>();
+        for (path, message) in duplicates {
+            self.violation(SchemaViolationType::DuplicateFieldId, Some(&path), message);
+        }
+    }
+
+    fn check_identifier_fields(&mut self, schema: &Map<String, Value>) {
+        let Some(identifier_field_ids) = schema.get("identifier-field-ids") else {
+            return;
+        };
+        let Some(identifier_field_ids) = identifier_field_ids.as_array() else {
+            self.violation(
+                SchemaViolationType::InvalidIdentifierField,
+                None,
+                "`identifier-field-ids` must be an array of field IDs",
+            );
+            return;
+        };
+
+        for id in identifier_field_ids {
+            let field = id
+                .as_i64()
+                .and_then(|id| self.fields.get(&id))
+                .and_then(|fields| fields.first());
+            let Some(field) = field else {
+                let message = format!("Identifier field ID {id} does not reference a field");
+                self.violation(SchemaViolationType::InvalidIdentifierField, None, message);
+                continue;
+            };
+
+            let reason = match &field.primitive {
+                None => Some("is not a primitive type"),
+                Some(PrimitiveType::Float | PrimitiveType::Double) => {
+                    Some("is a floating point type")
+                }
+                Some(_) if !field.required => Some("is optional"),
+                Some(_) if field.in_optional_or_collection => {
+                    Some("is nested in a list, a map or an optional struct")
+                }
+                Some(_) => None,
+            };
+            if let Some(reason) = reason {
+                let (path, message) = (
+                    field.path.clone(),
+                    format!("Identifier field `{}` {reason}", field.path),
+                );
+                self.violation(
+                    SchemaViolationType::InvalidIdentifierField,
+                    Some(&path),
+                    message,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_valid_schema_has_no_violations() {
+        let schema = json!({
+            "type": "struct",
+            "schema-id": 0,
+            "identifier-field-ids": [1],
+            "fields": [
+                {"id": 1, "name": "id", "required": true, "type": "long"},
+                {"id": 2, "name": "tags", "required": false, "type": {
+                    "type": "map", "key-id": 3, "key": "string",
+                    "value-id": 4, "value": "decimal(10,2)", "value-required": false
+                }}
+            ]
+        });
+        assert_eq!(validate_schema(&schema), vec![]);
+    }
+
+    #[test]
+    fn test_duplicate_field_ids_are_reported() {
+        let schema = json!({
+            "type": "struct",
+            "schema-id": 0,
+            "fields": [
+                {"id": 1, "name": "id", "required": true, "type": "long"},
+                {"id": 2, "name": "address", "required": false, "type": {
+                    "type": "struct",
+                    "fields": [{"id": 1, "name": "street", "required": false, "type": "string"}]
+                }}
+            ]
+        });
+        assert_eq!(
+            validate_schema(&schema),
+            vec![SchemaViolation {
+                r#type: SchemaViolationType::DuplicateFieldId,
+                field_path: Some("address.street".to_string()),
+                message: "Field ID 1 is used by fields `id`, `address.street`".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_all_violations_are_reported() {
+        let schema = json!({
+            "type": "struct",
+            "schema-id": 0,
+            "identifier-field-ids": [2, 9],
+            "fields": [
+                {"id": 1, "name": "id", "required": true, "type": "bigint"},
+                {"id": 2, "name": "id", "required": true, "type": "double"},
+                {"id": 3, "name": "items", "required": true, "type": {"type": "array"}}
+            ]
+        });
+        let violations = validate_schema(&schema)
+            .into_iter()
+            .map(|v| (v.r#type, v.field_path))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            violations,
+            vec![
+                (SchemaViolationType::InvalidType, Some("id".to_string())),
+                (
+                    SchemaViolationType::DuplicateFieldName,
+                    Some("id".to_string())
+                ),
+                (SchemaViolationType::InvalidType, Some("items".to_string())),
+                (
+                    SchemaViolationType::InvalidIdentifierField,
+                    Some("id".to_string())
+                ),
+                (SchemaViolationType::InvalidIdentifierField, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,597 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{ErrorModel, Result},
+    config::SecretEnvelopeEncryptionConfig,
+    service::{
+        secrets::{Secret, SecretInStorage, SecretStore},
+        storage::StorageCredential,
+        Catalog, Transaction,
+    },
+    SecretIdent, WarehouseIdent, CONFIG,
+};
+
+/// Length of master and data keys in bytes.
+const KEY_LEN: usize = 32;
+
+lazy_static::lazy_static! {
+    /// Master keys are validated when the configuration is loaded. `Err` holds the
+    /// message of an invalid configuration nevertheless.
+    static ref ENVELOPE_ENCRYPTION: std::result::Result<Option<EnvelopeEncryption>, String> =
+        CONFIG
+            .secret_envelope_encryption
+            .as_ref()
+            .map(EnvelopeEncryption::try_from_config)
+            .transpose()
+            .map_err(|e| e.error.message);
+}
+
+/// Envelope encryption as configured, `None` if it is disabled.
+fn envelope_encryption() -> Result<Option<&'static EnvelopeEncryption>> {
+    match &*ENVELOPE_ENCRYPTION {
+        Ok(envelope) => Ok(envelope.as_ref()),
+        Err(message) => Err(ErrorModel::internal(message.clone(), "InvalidMasterKey", None).into()),
+    }
+}
+
+/// Data key of a warehouse, encrypted with a master key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrappedDataKey {
+    /// Identifies the master key the data key is wrapped with.
+    pub master_key_id: String,
+    /// Nonce followed by the encrypted data key.
+    pub wrapped_key: Vec<u8>,
+}
+
+/// Storage credential encrypted with the data key of `warehouse-id`, as it is
+/// handed to the secret store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct EnvelopeEncryptedCredential {
+    pub(crate) warehouse_id: uuid::Uuid,
+    /// Base64-encoded nonce followed by the encrypted credential.
+    pub(crate) ciphertext: String,
+    /// Type of the encrypted credential, e.g. `s3/access-key`. Kept in cleartext
+    /// so that secrets can be listed without decrypting them.
+    /// Not set for credentials encrypted before the type was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) credential_type: Option<String>,
+}
+
+impl SecretInStorage for EnvelopeEncryptedCredential {}
+
+/// Credentials stored before envelope encryption was enabled remain readable.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StoredStorageCredential {
+    Envelope(EnvelopeEncryptedCredential),
+    Plain(StorageCredential),
+}
+
+impl SecretInStorage for StoredStorageCredential {}
+
+/// Non-secret fields of a stored storage credential. All other fields, including
+/// the ciphertext of envelope-encrypted credentials, are skipped.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct StoredStorageCredentialType {
+    /// Storage type of plain credentials, e.g. `s3`.
+    #[serde(default, rename = "type")]
+    storage_type: Option<String>,
+    /// `access-key` for plain credentials, `s3/access-key` for envelope-encrypted ones.
+    #[serde(default)]
+    credential_type: Option<String>,
+}
+
+impl SecretInStorage for StoredStorageCredentialType {}
+
+impl StoredStorageCredentialType {
+    fn into_credential_type(self) -> Option<String> {
+        match (self.storage_type, self.credential_type) {
+            (Some(storage_type), Some(credential_type)) => {
+                Some(format!("{storage_type}/{credential_type}"))
+            }
+            (None, credential_type) => credential_type,
+            (Some(_), None) => None,
+        }
+    }
+}
+
+struct MasterKey {
+    id: String,
+    key: LessSafeKey,
+}
+
+impl std::fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MasterKey").field("id", &self.id).finish()
+    }
+}
+
+impl MasterKey {
+    fn from_base64(encoded: &str) -> Result<Self> {
+        let key = BASE64_STANDARD.decode(encoded.trim()).map_err(|e| {
+            ErrorModel::internal(
+                "Secret envelope encryption master key is not valid base64",
+                "InvalidMasterKey",
+                Some(Box::new(e)),
+            )
+        })?;
+        if key.len() != KEY_LEN {
+            return Err(ErrorModel::internal(
+                format!("Secret envelope encryption master key must be {KEY_LEN} bytes long"),
+                "InvalidMasterKey",
+                None,
+            )
+            .into());
+        }
+        // The id is derived from the key, so that keys do not have to be named
+        let id = digest(&SHA256, &key).as_ref()[..8]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        Ok(Self {
+            id,
+            key: aead_key(&key)?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct EnvelopeEncryption {
+    master_key: MasterKey,
+    previous_master_keys: Vec<MasterKey>,
+}
+
+impl EnvelopeEncryption {
+    pub(crate) fn try_from_config(config: &SecretEnvelopeEncryptionConfig) -> Result<Self> {
+        Ok(Self {
+            master_key: MasterKey::from_base64(&config.master_key)?,
+            previous_master_keys: config
+                .previous_master_keys
+                .iter()
+                .flatten()
+                .map(|key| MasterKey::from_base64(key))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    fn master_key(&self, id: &str) -> Result<&MasterKey> {
+        std::iter::once(&self.master_key)
+            .chain(&self.previous_master_keys)
+            .find(|key| key.id == id)
+            .ok_or_else(|| {
+                ErrorModel::internal(
+                    format!("Data key is wrapped with unknown master key {id}"),
+                    "UnknownMasterKey",
+                    None,
+                )
+                .into()
+            })
+    }
+
+    fn generate_data_key(
+        &self,
+        warehouse_id: WarehouseIdent,
+    ) -> Result<(LessSafeKey, WrappedDataKey)> {
+        let mut key = [0u8; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut key)
+            .map_err(|_| crypto_error("Error generating data key"))?;
+        let wrapped_key = seal(&self.master_key.key, warehouse_id.as_bytes(), &key)?;
+        Ok((
+            aead_key(&key)?,
+            WrappedDataKey {
+                master_key_id: self.master_key.id.clone(),
+                wrapped_key,
+            },
+        ))
+    }
+
+    fn unwrap_data_key(
+        &self,
+        warehouse_id: WarehouseIdent,
+        wrapped: &WrappedDataKey,
+    ) -> Result<LessSafeKey> {
+        let master_key = self.master_key(&wrapped.master_key_id)?;
+        aead_key(&open(
+            &master_key.key,
+            warehouse_id.as_bytes(),
+            &wrapped.wrapped_key,
+        )?)
+    }
+
+    fn rewrap_data_key(
+        &self,
+        warehouse_id: WarehouseIdent,
+        wrapped: &WrappedDataKey,
+    ) -> Result<WrappedDataKey> {
+        let master_key = self.master_key(&wrapped.master_key_id)?;
+        let key = open(
+            &master_key.key,
+            warehouse_id.as_bytes(),
+            &wrapped.wrapped_key,
+        )?;
+        Ok(WrappedDataKey {
+            master_key_id: self.master_key.id.clone(),
+            wrapped_key: seal(&self.master_key.key, warehouse_id.as_bytes(), &key)?,
+        })
+    }
+}
+
+/// Store the storage credential of a warehouse in the secret store. If envelope
+/// encryption is enabled, the credential is encrypted with the data key of the
+/// warehouse, which is created on first use.
+pub(crate) async fn create_storage_secret<C: Catalog, S: SecretStore>(
+    warehouse_id: WarehouseIdent,
+    credential: StorageCredential,
+    secrets: &S,
+    transaction: &mut C::Transaction,
+) -> Result<SecretIdent> {
+    store_storage_secret::<C, S>(
+        envelope_encryption()?,
+        warehouse_id,
+        credential,
+        secrets,
+        transaction,
+    )
+    .await
+}
+
+/// Get a storage credential from the secret store, decrypting it if it is
+/// envelope-encrypted.
+pub(crate) async fn get_storage_secret<C: Catalog, S: SecretStore>(
+    secret_id: &SecretIdent,
+    catalog_state: C::State,
+    secrets: &S,
+) -> Result<StorageCredential> {
+    load_storage_secret::<C, S>(envelope_encryption()?, secret_id, catalog_state, secrets)
+        .await
+        .map(|secret| secret.secret)
+}
+
+/// Get the type of a storage credential together with the timestamps of its secret.
+/// The credential is not decrypted. The type is `None` for envelope-encrypted
+/// credentials stored before the type was recorded.
+pub(crate) async fn get_storage_secret_type<S: SecretStore>(
+    secret_id: &SecretIdent,
+    secrets: &S,
+) -> Result<Secret<Option<String>>> {
+    let Secret {
+        secret_id: _,
+        secret,
+        created_at,
+        updated_at,
+    } = secrets
+        .get_secret_by_id::<StoredStorageCredentialType>(secret_id)
+        .await?;
+    Ok(Secret {
+        secret_id: *secret_id,
+        secret: secret.into_credential_type(),
+        created_at,
+        updated_at,
+    })
+}
+
+/// Re-wrap data keys that are wrapped with a previous master key with the current
+/// master key. Credentials encrypted with the data keys are not modified.
+/// Returns the number of re-wrapped data keys.
+///
+/// # Errors
+/// If the master keys are invalid or a data key is wrapped with a master key
+/// that is not configured.
+pub async fn rewrap_warehouse_data_keys<C: Catalog>(catalog_state: C::State) -> Result<usize> {
+    match envelope_encryption()? {
+        Some(envelope) => rewrap_data_keys::<C>(envelope, catalog_state).await,
+        None => Ok(0),
+    }
+}
+
+async fn store_storage_secret<C: Catalog, S: SecretStore>(
+    envelope: Option<&EnvelopeEncryption>,
+    warehouse_id: WarehouseIdent,
+    credential: StorageCredential,
+    secrets: &S,
+    transaction: &mut C::Transaction,
+) -> Result<SecretIdent> {
+    let Some(envelope) = envelope else {
+        return secrets.create_secret(credential).await;
+    };
+
+    let credential_type = credential.credential_type().to_string();
+    let data_key = warehouse_data_key::<C>(envelope, warehouse_id, transaction).await?;
+    let plaintext = serde_json::to_vec(&credential).map_err(|_e| {
+        ErrorModel::internal(
+            "Error serializing secret",
+            "SecretSerializeError",
+            // We do not add the error here as it might contain sensitive information
+            None,
+        )
+    })?;
+    let ciphertext = seal(&data_key, warehouse_id.as_bytes(), &plaintext)?;
+    secrets
+        .create_secret(EnvelopeEncryptedCredential {
+            warehouse_id: *warehouse_id,
+            ciphertext: BASE64_STANDARD.encode(ciphertext),
+            credential_type: Some(credential_type),
+        })
+        .await
+}
+
+async fn load_storage_secret<C: Catalog, S: SecretStore>(
+    envelope: Option<&EnvelopeEncryption>,
+    secret_id: &SecretIdent,
+    catalog_state: C::State,
+    secrets: &S,
+) -> Result<Secret<StorageCredential>> {
+    let Secret {
+        secret_id: _,
+        secret,
+        created_at,
+        updated_at,
+    } = secrets
+        .get_secret_by_id::<StoredStorageCredential>(secret_id)
+        .await?;
+    let with_metadata = |secret| Secret {
+        secret_id: *secret_id,
+        secret,
+        created_at,
+        updated_at,
+    };
+    let encrypted = match secret {
+        StoredStorageCredential::Plain(credential) => return Ok(with_metadata(credential)),
+        StoredStorageCredential::Envelope(encrypted) => encrypted,
+    };
+    let envelope = envelope.ok_or_else(|| {
+        ErrorModel::internal(
+            "Storage credential is envelope-encrypted, but envelope encryption is not configured",
+            "SecretEnvelopeEncryptionNotConfigured",
+            None,
+        )
+    })?;
+
+    let warehouse_id = WarehouseIdent::from(encrypted.warehouse_id);
+    let mut t = C::Transaction::begin_read(catalog_state).await?;
+    let wrapped = C::get_warehouse_data_key(warehouse_id, t.transaction()).await?;
+    t.commit().await?;
+    let wrapped = wrapped.ok_or_else(|| data_key_not_found(warehouse_id))?;
+
+    let data_key = envelope.unwrap_data_key(warehouse_id, &wrapped)?;
+    let ciphertext = BASE64_STANDARD
+        .decode(&encrypted.ciphertext)
+        .map_err(|_| crypto_error("Error decoding envelope-encrypted secret"))?;
+    let plaintext = open(&data_key, warehouse_id.as_bytes(), &ciphertext)?;
+    serde_json::from_slice(&plaintext)
+        .map(with_metadata)
+        .map_err(|_e| {
+            ErrorModel::internal(
+                "Error parsing secret",
+                "SecretParseError",
+                // We do not add the error here as it might contain sensitive information
+                None,
+            )
+            .append_detail(format!("Secret ID: {secret_id}"))
+            .into()
+        })
+}
+
+async fn rewrap_data_keys<C: Catalog>(
+    envelope: &EnvelopeEncryption,
+    catalog_state: C::State,
+) -> Result<usize> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let data_keys =
+        C::list_warehouse_data_keys_to_rewrap(&envelope.master_key.id, t.transaction()).await?;
+    for (warehouse_id, wrapped) in &data_keys {
+        let rewrapped = envelope.rewrap_data_key(*warehouse_id, wrapped)?;
+        C::update_warehouse_data_key(*warehouse_id, &rewrapped, t.transaction()).await?;
+    }
+    t.commit().await?;
+    Ok(data_keys.len())
+}
+
+async fn warehouse_data_key<C: Catalog>(
+    envelope: &EnvelopeEncryption,
+    warehouse_id: WarehouseIdent,
+    transaction: &mut C::Transaction,
+) -> Result<LessSafeKey> {
+    if let Some(wrapped) =
+        C::get_warehouse_data_key(warehouse_id, transaction.transaction()).await?
+    {
+        return envelope.unwrap_data_key(warehouse_id, &wrapped);
+    }
+
+    let (data_key, wrapped) = envelope.generate_data_key(warehouse_id)?;
+    if C::create_warehouse_data_key(warehouse_id, &wrapped, transaction.transaction()).await? {
+        return Ok(data_key);
+    }
+    // Created by a concurrent request
+    let wrapped = C::get_warehouse_data_key(warehouse_id, transaction.transaction())
+        .await?
+        .ok_or_else(|| data_key_not_found(warehouse_id))?;
+    envelope.unwrap_data_key(warehouse_id, &wrapped)
+}
+
+fn aead_key(key: &[u8]) -> Result<LessSafeKey> {
+    UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| crypto_error("Invalid encryption key").into())
+}
+
+/// Encrypt `plaintext` with a random nonce, which is prepended to the result.
+fn seal(key: &LessSafeKey, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| crypto_error("Error generating nonce"))?;
+    let mut in_out = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(aad),
+        &mut in_out,
+    )
+    .map_err(|_| crypto_error("Error encrypting secret"))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend(in_out);
+    Ok(sealed)
+}
+
+fn open(key: &LessSafeKey, aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < NONCE_LEN {
+        return Err(crypto_error("Error decrypting secret").into());
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| crypto_error("Error decrypting secret"))?;
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::from(aad), &mut in_out)
+        .map_err(|_| crypto_error("Error decrypting secret"))?;
+    Ok(plaintext.to_vec())
+}
+
+fn crypto_error(message: &str) -> ErrorModel {
+    ErrorModel::internal(message, "SecretEncryptionError", None)
+}
+
+fn data_key_not_found(warehouse_id: WarehouseIdent) -> ErrorModel {
+    ErrorModel::internal(
+        format!("Data key of warehouse {warehouse_id} not found"),
+        "DataKeyNotFound",
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        implementations::postgres::{
+            warehouse::test::initialize_warehouse, CatalogState, PostgresCatalog,
+            PostgresTransaction, SecretsState,
+        },
+        service::storage::S3Credential,
+    };
+
+    fn envelope(master_key: u8, previous_master_keys: &[u8]) -> EnvelopeEncryption {
+        EnvelopeEncryption::try_from_config(&SecretEnvelopeEncryptionConfig {
+            master_key: BASE64_STANDARD.encode([master_key; KEY_LEN]),
+            previous_master_keys: Some(
+                previous_master_keys
+                    .iter()
+                    .map(|key| BASE64_STANDARD.encode([*key; KEY_LEN]))
+                    .collect(),
+            ),
+        })
+        .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_envelope_encryption_round_trip(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let secrets = SecretsState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let credential: StorageCredential = S3Credential::AccessKey {
+            aws_access_key_id: "my access key".to_string(),
+            aws_secret_access_key: "my secret key".to_string(),
+        }
+        .into();
+
+        let envelope_v1 = envelope(1, &[]);
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let secret_id = store_storage_secret::<PostgresCatalog, _>(
+            Some(&envelope_v1),
+            warehouse_id,
+            credential.clone(),
+            &secrets,
+            &mut t,
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+
+        // The secret store only receives ciphertext
+        let stored = secrets
+            .get_secret_by_id::<EnvelopeEncryptedCredential>(&secret_id)
+            .await
+            .unwrap()
+            .secret;
+        assert_eq!(stored.warehouse_id, *warehouse_id);
+        let ciphertext = BASE64_STANDARD.decode(&stored.ciphertext).unwrap();
+        assert!(!ciphertext
+            .windows(b"my secret key".len())
+            .any(|w| w == b"my secret key"));
+        assert!(secrets
+            .get_secret_by_id::<StorageCredential>(&secret_id)
+            .await
+            .is_err());
+        assert_eq!(
+            get_storage_secret_type(&secret_id, &secrets)
+                .await
+                .unwrap()
+                .secret
+                .as_deref(),
+            Some("s3/access-key")
+        );
+
+        let loaded = load_storage_secret::<PostgresCatalog, _>(
+            Some(&envelope_v1),
+            &secret_id,
+            state.clone(),
+            &secrets,
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded.secret, credential);
+
+        // Rotating the master key re-wraps the data key, the secret is not modified
+        let envelope_v2 = envelope(2, &[1]);
+        assert_eq!(
+            rewrap_data_keys::<PostgresCatalog>(&envelope_v2, state.clone())
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            rewrap_data_keys::<PostgresCatalog>(&envelope_v2, state.clone())
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            secrets
+                .get_secret_by_id::<EnvelopeEncryptedCredential>(&secret_id)
+                .await
+                .unwrap()
+                .secret,
+            stored
+        );
+        let loaded = load_storage_secret::<PostgresCatalog, _>(
+            Some(&envelope(2, &[])),
+            &secret_id,
+            state.clone(),
+            &secrets,
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded.secret, credential);
+        let err = load_storage_secret::<PostgresCatalog, _>(
+            Some(&envelope_v1),
+            &secret_id,
+            state.clone(),
+            &secrets,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "UnknownMasterKey");
+    }
+}
@@ -0,0 +1,64 @@
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+use crate::service::Result;
+
+/// Maximum number of snapshots of tables of a warehouse.
+///
+/// Commits that would leave a table with more than `max-snapshots` snapshots
+/// expire the oldest snapshots until `retain-last` snapshots are left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotLimit {
+    /// Number of snapshots above which a commit expires snapshots.
+    pub max_snapshots: u32,
+    /// Number of snapshots left after expiration.
+    pub retain_last: u32,
+}
+
+impl SnapshotLimit {
+    /// # Errors
+    /// Fails if `retain-last` is zero or larger than `max-snapshots`.
+    pub fn validate(&self) -> Result<()> {
+        if self.retain_last == 0 {
+            return Err(ErrorModel::bad_request(
+                "`retain-last` must be at least 1",
+                "InvalidSnapshotLimit",
+                None,
+            )
+            .into());
+        }
+        if self.retain_last > self.max_snapshots {
+            return Err(ErrorModel::bad_request(
+                "`retain-last` must not be larger than `max-snapshots`",
+                "InvalidSnapshotLimit",
+                None,
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_snapshot_limit() {
+        for (max_snapshots, retain_last) in [(0, 0), (10, 0), (5, 6)] {
+            assert!(SnapshotLimit {
+                max_snapshots,
+                retain_last
+            }
+            .validate()
+            .is_err());
+        }
+        assert!(SnapshotLimit {
+            max_snapshots: 10,
+            retain_last: 10
+        }
+        .validate()
+        .is_ok());
+    }
+}
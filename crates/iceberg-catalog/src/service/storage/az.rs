@@ -161,6 +161,7 @@ impl AdlsProfile {
         table_location: &Location,
         creds: &AzCredential,
         permissions: StoragePermissions,
+        credential_ttl: Option<chrono::Duration>,
     ) -> Result<TableConfig, TableConfigError> {
         let AzCredential::ClientCredentials {
             client_id,
@@ -181,7 +182,7 @@ impl AdlsProfile {
         let mut creds = TableProperties::default();
 
         let sas = self
-            .get_sas_token(table_location, cred, permissions)
+            .get_sas_token(table_location, cred, permissions, credential_ttl)
             .await?;
 
         creds.insert(&custom::CustomConfig {
@@ -249,14 +250,18 @@ impl AdlsProfile {
         path: &Location,
         cred: StorageCredentials,
         permissions: StoragePermissions,
+        ttl: Option<chrono::Duration>,
     ) -> Result<String, CredentialsError> {
         let client = blob_service_client(self.account_name.as_str(), cred);
 
         let start = time::OffsetDateTime::now_utc();
         let max_validity_seconds = i64::MAX;
         let sas_token_validity_seconds = self.sas_token_validity_seconds.unwrap_or(3600);
-        let clamped_validity_seconds = i64::try_from(sas_token_validity_seconds)
-            .unwrap_or(max_validity_seconds)
+        let clamped_validity_seconds = ttl
+            .map_or_else(
+                || i64::try_from(sas_token_validity_seconds).unwrap_or(max_validity_seconds),
+                |ttl| ttl.num_seconds(),
+            )
             .clamp(0, max_validity_seconds);
 
         let delegation_key = client
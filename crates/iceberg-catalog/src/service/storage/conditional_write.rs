@@ -0,0 +1,284 @@
+//! Conditional writes of new files.
+//!
+//! New metadata files are written only if no file exists at their location yet,
+//! so that two writers of the same location fail at the storage layer instead of
+//! overwriting each other. S3 uses `If-None-Match: *`, GCS the precondition
+//! `ifGenerationMatch=0`. Conditional writes to other storages are not implemented.
+
+use std::{sync::LazyLock, time::Duration};
+
+use async_trait::async_trait;
+use http::StatusCode;
+use iceberg_ext::configs::Location;
+
+use super::GcsServiceKey;
+#[cfg(feature = "s3-signer")]
+use super::S3Profile;
+use crate::CONFIG;
+
+/// Longest time to establish a connection to the storage.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(CONFIG.backend_timeouts.storage_write)
+        .build()
+        // Only fails if the TLS backend cannot be initialized, as `reqwest::Client::new`
+        .expect("Failed to build HTTP client for conditional writes")
+});
+
+#[derive(thiserror::Error, Debug)]
+pub enum ConditionalWriteError {
+    #[error("A file already exists at {0}.")]
+    PreconditionFailed(String),
+    #[error("Storage does not support conditional writes: {0}")]
+    Unsupported(String),
+    #[error("Conditional write failed: {0}")]
+    Request(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+/// Writes files only if no file exists at their location.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ConditionalWriter: Send + Sync + std::fmt::Debug {
+    /// Write `content` to `location` unless a file exists there.
+    ///
+    /// # Errors
+    /// - [`ConditionalWriteError::PreconditionFailed`] if a file exists at `location`
+    /// - [`ConditionalWriteError::Unsupported`] if the storage does not support
+    ///   conditional writes. Nothing is written in this case.
+    async fn write_if_absent(
+        &self,
+        location: &Location,
+        content: Vec<u8>,
+    ) -> Result<(), ConditionalWriteError>;
+}
+
+/// Writer of storages without conditional writes. The reason names the storage
+/// profile, so that it is reported once per profile.
+#[derive(Debug)]
+pub(crate) struct Unsupported(pub(crate) String);
+
+#[async_trait]
+impl ConditionalWriter for Unsupported {
+    async fn write_if_absent(&self, _: &Location, _: Vec<u8>) -> Result<(), ConditionalWriteError> {
+        Err(ConditionalWriteError::Unsupported(self.0.clone()))
+    }
+}
+
+/// Map the response of a conditional write to its outcome.
+async fn into_result(
+    location: &Location,
+    response: reqwest::Result<reqwest::Response>,
+) -> Result<(), ConditionalWriteError> {
+    let response = response.map_err(|e| ConditionalWriteError::Request(Box::new(e)))?;
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        // S3 answers `409 Conflict` if a conditional write to the same key is in progress
+        StatusCode::PRECONDITION_FAILED | StatusCode::CONFLICT => Err(
+            ConditionalWriteError::PreconditionFailed(location.to_string()),
+        ),
+        StatusCode::NOT_IMPLEMENTED => {
+            tracing::debug!(
+                "Storage rejected the precondition: {}",
+                response.text().await.unwrap_or_default()
+            );
+            Err(ConditionalWriteError::Unsupported(format!(
+                "Storage of {} rejected the precondition",
+                location.url().host_str().unwrap_or_default()
+            )))
+        }
+        status => Err(ConditionalWriteError::Request(
+            format!(
+                "Unexpected status {status}: {}",
+                response.text().await.unwrap_or_default()
+            )
+            .into(),
+        )),
+    }
+}
+
+/// Writes to S3 with `If-None-Match: *`, signed with the access key of the profile
+/// or with credentials of its credential provider.
+#[cfg(feature = "s3-signer")]
+#[derive(Debug)]
+pub(crate) struct S3ConditionalWriter {
+    pub(crate) profile: S3Profile,
+    pub(crate) credentials: S3WriteCredentials,
+}
+
+/// Credentials used to sign conditional writes to S3.
+#[cfg(feature = "s3-signer")]
+#[derive(Debug)]
+pub(crate) enum S3WriteCredentials {
+    /// Access key of the storage profile.
+    AccessKey(aws_credential_types::Credentials),
+    /// Credentials requested from the credential provider of the storage profile
+    /// for the folder of each written file.
+    Provider(url::Url),
+}
+
+#[cfg(feature = "s3-signer")]
+impl S3ConditionalWriter {
+    async fn credentials(
+        &self,
+        location: &Location,
+    ) -> Result<aws_credential_types::Credentials, ConditionalWriteError> {
+        use super::credential_provider::{
+            vend_scoped_credentials, CredentialRequest, HttpCredentialProvider,
+        };
+
+        let url = match &self.credentials {
+            S3WriteCredentials::AccessKey(credentials) => return Ok(credentials.clone()),
+            S3WriteCredentials::Provider(url) => url,
+        };
+        let mut prefix = location.clone();
+        prefix.pop();
+        let credentials = vend_scoped_credentials(
+            &HttpCredentialProvider { url },
+            &CredentialRequest {
+                prefix,
+                access_level: super::StoragePermissions::ReadWrite,
+                ttl: None,
+            },
+        )
+        .await
+        .map_err(|e| ConditionalWriteError::Request(Box::new(e)))?;
+        Ok(aws_credential_types::Credentials::new(
+            credentials.access_key_id,
+            credentials.secret_access_key,
+            credentials.session_token,
+            None,
+            "iceberg-rest-credential-provider",
+        ))
+    }
+
+    /// URL of the object at `location`, following the endpoint and addressing style of the profile.
+    fn object_url(&self, location: &Location) -> Result<url::Url, ConditionalWriteError> {
+        let s3_location = super::S3Location::try_from_location(location, true)
+            .map_err(|e| ConditionalWriteError::Request(Box::new(e)))?;
+        let bucket = s3_location.bucket_name();
+        let path = location.url().path();
+        let url = match &self.profile.endpoint {
+            Some(endpoint) if self.profile.path_style_access.unwrap_or(true) => {
+                format!("{}/{bucket}{path}", endpoint.as_str().trim_end_matches('/'))
+            }
+            Some(endpoint) => format!(
+                "{}://{bucket}.{}{path}",
+                endpoint.scheme(),
+                endpoint.authority()
+            ),
+            None => format!(
+                "https://{bucket}.s3.{}.amazonaws.com{path}",
+                self.profile.region
+            ),
+        };
+        url.parse()
+            .map_err(|e| ConditionalWriteError::Request(Box::new(e)))
+    }
+}
+
+#[cfg(feature = "s3-signer")]
+#[async_trait]
+impl ConditionalWriter for S3ConditionalWriter {
+    async fn write_if_absent(
+        &self,
+        location: &Location,
+        content: Vec<u8>,
+    ) -> Result<(), ConditionalWriteError> {
+        use aws_sigv4::{
+            http_request::{
+                sign, PayloadChecksumKind, PercentEncodingMode, SignableBody, SignableRequest,
+                SigningSettings,
+            },
+            sign::v4,
+        };
+
+        let url = self.object_url(location)?;
+        let credentials = self.credentials(location).await?;
+        let headers = [("if-none-match", "*")];
+
+        let mut settings = SigningSettings::default();
+        settings.percent_encoding_mode = PercentEncodingMode::Single;
+        settings.payload_checksum_kind = PayloadChecksumKind::XAmzSha256;
+        let identity = credentials.into();
+        let signing_params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(&self.profile.region)
+            .name("s3")
+            .time(std::time::SystemTime::now())
+            .settings(settings)
+            .build()
+            .map_err(|e| ConditionalWriteError::Request(Box::new(e)))?
+            .into();
+        let signable = SignableRequest::new(
+            "PUT",
+            url.as_str(),
+            headers.into_iter(),
+            SignableBody::Bytes(&content),
+        )
+        .map_err(|e| ConditionalWriteError::Request(Box::new(e)))?;
+        let (instructions, _) = sign(signable, &signing_params)
+            .map_err(|e| ConditionalWriteError::Request(Box::new(e)))?
+            .into_parts();
+
+        let mut request = CLIENT.put(url);
+        for (key, value) in headers.into_iter().chain(instructions.headers()) {
+            request = request.header(key, value);
+        }
+        into_result(location, request.body(content).send().await).await
+    }
+}
+
+/// Writes to GCS with the precondition `ifGenerationMatch=0`, authenticated
+/// with the service account key of the profile.
+#[derive(Debug)]
+pub(crate) struct GcsConditionalWriter {
+    pub(crate) key: GcsServiceKey,
+}
+
+#[async_trait]
+impl ConditionalWriter for GcsConditionalWriter {
+    async fn write_if_absent(
+        &self,
+        location: &Location,
+        content: Vec<u8>,
+    ) -> Result<(), ConditionalWriteError> {
+        let bucket = location.url().host_str().ok_or_else(|| {
+            ConditionalWriteError::Request(format!("No bucket in location {location}").into())
+        })?;
+        let object = location.url().path().trim_start_matches('/');
+        let object =
+            urlencoding::decode(object).map_err(|e| ConditionalWriteError::Request(Box::new(e)))?;
+
+        let config = google_cloud_auth::project::Config::default()
+            .with_scopes(&["https://www.googleapis.com/auth/devstorage.read_write"]);
+        let token = google_cloud_auth::project::create_token_source_from_credentials(
+            &(&self.key).into(),
+            &config,
+        )
+        .await
+        .map_err(|e| {
+            ConditionalWriteError::Request(format!("Failed to create token source: {e:?}").into())
+        })?
+        .token()
+        .await
+        .map_err(|e| {
+            ConditionalWriteError::Request(format!("Failed to get token: {e:?}").into())
+        })?;
+
+        let request = CLIENT
+            .post(format!(
+                "https://storage.googleapis.com/upload/storage/v1/b/{bucket}/o"
+            ))
+            .query(&[
+                ("uploadType", "media"),
+                ("name", object.as_ref()),
+                ("ifGenerationMatch", "0"),
+            ])
+            .bearer_auth(token.access_token)
+            .body(content);
+        into_result(location, request.send().await).await
+    }
+}
@@ -0,0 +1,254 @@
+use std::{fmt::Debug, str::FromStr, sync::LazyLock, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use iceberg_ext::configs::Location;
+use serde::{Deserialize, Serialize};
+use veil::Redact;
+
+use super::{error::TableConfigError, StoragePermissions};
+use crate::CONFIG;
+
+/// Longest time to establish a connection to a credential provider.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+static CREDENTIAL_PROVIDER_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(CONFIG.backend_timeouts.credential_vending)
+        .build()
+        // Only fails if the TLS backend cannot be initialized, as `reqwest::Client::new`
+        .expect("Failed to build HTTP client for credential providers")
+});
+
+/// Provider used to vend storage credentials for tables of a warehouse
+/// instead of the built-in STS integration.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum CredentialProviderConfig {
+    /// Request credentials from an external credential broker via HTTP.
+    #[schema(title = "CredentialProviderHttp")]
+    Http {
+        /// Endpoint that receives a `POST` request with the `prefix`,
+        /// `access-level` and optional `ttl-seconds` of the requested credentials.
+        url: url::Url,
+    },
+}
+
+/// Scope of the credentials requested from a [`CredentialProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredentialRequest {
+    /// Location the credentials must be restricted to.
+    pub prefix: Location,
+    /// Highest permission the credentials may grant.
+    pub access_level: StoragePermissions,
+    /// Requested lifetime of the credentials. `None` uses the default of the provider.
+    pub ttl: Option<chrono::Duration>,
+}
+
+/// Temporary S3 credentials returned by a [`CredentialProvider`].
+#[derive(Redact, Clone, PartialEq, Eq)]
+pub struct VendedCredentials {
+    pub access_key_id: String,
+    #[redact(partial)]
+    pub secret_access_key: String,
+    #[redact]
+    pub session_token: Option<String>,
+    /// Point in time after which the credentials are no longer valid.
+    pub expiration: Option<DateTime<Utc>>,
+    /// Location the credentials grant access to.
+    pub prefix: Location,
+    /// Permission the credentials grant.
+    pub access_level: StoragePermissions,
+}
+
+/// A source of temporary storage credentials scoped to a single table.
+///
+/// The built-in implementation uses the STS endpoint of the storage. Custom
+/// implementations can delegate to an external credential broker. Providers
+/// must report the scope of the credentials they return. Credentials that are
+/// broader than requested are rejected.
+#[async_trait]
+pub trait CredentialProvider: Debug + Send + Sync {
+    /// Name of the provider, used in error messages.
+    fn name(&self) -> &'static str;
+
+    /// Vend credentials restricted to the scope of `request`.
+    async fn vend_credentials(
+        &self,
+        request: &CredentialRequest,
+    ) -> Result<VendedCredentials, TableConfigError>;
+}
+
+/// Vend credentials via `provider` and ensure they are not broader than requested.
+pub(crate) async fn vend_scoped_credentials(
+    provider: &dyn CredentialProvider,
+    request: &CredentialRequest,
+) -> Result<VendedCredentials, TableConfigError> {
+    let credentials = provider.vend_credentials(request).await?;
+    if !credentials.prefix.is_sublocation_of(&request.prefix)
+        || credentials.access_level > request.access_level
+    {
+        return Err(TableConfigError::FailedDependency(format!(
+            "Credential provider '{}' returned {:?} credentials for '{}', but only {:?} credentials for '{}' were requested",
+            provider.name(),
+            credentials.access_level,
+            credentials.prefix,
+            request.access_level,
+            request.prefix
+        )));
+    }
+    Ok(credentials)
+}
+
+/// Credential provider that calls an external credential broker.
+#[derive(Debug)]
+pub(crate) struct HttpCredentialProvider<'a> {
+    pub(crate) url: &'a url::Url,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct HttpCredentialRequest<'a> {
+    prefix: &'a str,
+    access_level: StoragePermissions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl_seconds: Option<i64>,
+}
+
+#[derive(Redact, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct HttpCredentialResponse {
+    access_key_id: String,
+    #[redact(partial)]
+    secret_access_key: String,
+    #[redact]
+    session_token: Option<String>,
+    expiration: Option<DateTime<Utc>>,
+    prefix: String,
+    access_level: StoragePermissions,
+}
+
+#[async_trait]
+impl CredentialProvider for HttpCredentialProvider<'_> {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn vend_credentials(
+        &self,
+        request: &CredentialRequest,
+    ) -> Result<VendedCredentials, TableConfigError> {
+        let response = CREDENTIAL_PROVIDER_CLIENT
+            .post(self.url.clone())
+            .json(&HttpCredentialRequest {
+                prefix: request.prefix.as_str(),
+                access_level: request.access_level,
+                ttl_seconds: request.ttl.map(|ttl| ttl.num_seconds()),
+            })
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| {
+                tracing::error!("Failed to request credentials from {}: {:?}", self.url, e);
+                TableConfigError::FailedDependency(format!(
+                    "Failed to request credentials from credential provider: {e}"
+                ))
+            })?
+            .json::<HttpCredentialResponse>()
+            .await
+            .map_err(|e| {
+                TableConfigError::FailedDependency(format!(
+                    "Failed to parse response of credential provider: {e}"
+                ))
+            })?;
+
+        let prefix = Location::from_str(&response.prefix).map_err(|e| {
+            TableConfigError::FailedDependency(format!(
+                "Credential provider returned an invalid prefix '{}': {e}",
+                response.prefix
+            ))
+        })?;
+
+        Ok(VendedCredentials {
+            access_key_id: response.access_key_id,
+            secret_access_key: response.secret_access_key,
+            session_token: response.session_token,
+            expiration: response.expiration,
+            prefix,
+            access_level: response.access_level,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Records the requests it receives and returns credentials for the requested
+    /// scope, unless `granted_prefix` or `granted_access_level` are set.
+    #[derive(Debug, Default)]
+    pub(crate) struct MockCredentialProvider {
+        pub(crate) requests: Mutex<Vec<CredentialRequest>>,
+        pub(crate) granted_prefix: Option<Location>,
+        pub(crate) granted_access_level: Option<StoragePermissions>,
+    }
+
+    #[async_trait]
+    impl CredentialProvider for MockCredentialProvider {
+        fn name(&self) -> &'static str {
+            "mock"
+        }
+
+        async fn vend_credentials(
+            &self,
+            request: &CredentialRequest,
+        ) -> Result<VendedCredentials, TableConfigError> {
+            self.requests.lock().unwrap().push(request.clone());
+            Ok(VendedCredentials {
+                access_key_id: "mock-key".to_string(),
+                secret_access_key: "mock-secret".to_string(),
+                session_token: Some("mock-token".to_string()),
+                expiration: DateTime::from_timestamp(1_700_000_000, 0),
+                prefix: self
+                    .granted_prefix
+                    .clone()
+                    .unwrap_or_else(|| request.prefix.clone()),
+                access_level: self.granted_access_level.unwrap_or(request.access_level),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broader_credentials_are_rejected() {
+        let request = CredentialRequest {
+            prefix: "s3://bucket/ns/table".parse().unwrap(),
+            access_level: StoragePermissions::Read,
+            ttl: None,
+        };
+
+        let provider = MockCredentialProvider::default();
+        let credentials = vend_scoped_credentials(&provider, &request).await.unwrap();
+        assert_eq!(credentials.prefix, request.prefix);
+
+        for granted_prefix in ["s3://bucket/ns", "s3://bucket/ns/table-2"] {
+            let provider = MockCredentialProvider {
+                granted_prefix: Some(granted_prefix.parse().unwrap()),
+                ..Default::default()
+            };
+            vend_scoped_credentials(&provider, &request)
+                .await
+                .unwrap_err();
+        }
+
+        let provider = MockCredentialProvider {
+            granted_access_level: Some(StoragePermissions::ReadWrite),
+            ..Default::default()
+        };
+        vend_scoped_credentials(&provider, &request)
+            .await
+            .unwrap_err();
+    }
+}
@@ -2,7 +2,10 @@ use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
 
 use crate::{
     catalog::{compression_codec::UnsupportedCompressionCodec, io::IoError},
-    service::storage::{StorageProfile, StorageType},
+    service::{
+        backend_timeout::BackendTimeoutError,
+        storage::{StorageProfile, StorageType},
+    },
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -148,6 +151,8 @@ pub enum TableConfigError {
         String,
         #[source] Option<Box<dyn std::error::Error + 'static + Send + Sync>>,
     ),
+    #[error(transparent)]
+    Timeout(#[from] BackendTimeoutError),
 }
 
 impl From<TableConfigError> for IcebergErrorResponse {
@@ -164,6 +169,7 @@ impl From<TableConfigError> for IcebergErrorResponse {
             e @ TableConfigError::Internal(_, _) => {
                 ErrorModel::internal(e.to_string(), "StsError", Some(Box::new(e))).into()
             }
+            TableConfigError::Timeout(e) => e.into(),
         }
     }
 }
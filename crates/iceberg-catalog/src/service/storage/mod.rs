@@ -1,13 +1,19 @@
 #![allow(clippy::match_wildcard_for_single_variants)]
 
 pub(crate) mod az;
+mod conditional_write;
+pub mod credential_provider;
 mod error;
 mod gcs;
 pub(crate) mod s3;
 
 pub use az::{AdlsLocation, AdlsProfile, AzCredential};
+#[cfg(test)]
+pub(crate) use conditional_write::MockConditionalWriter;
+pub use conditional_write::{ConditionalWriteError, ConditionalWriter};
+pub use error::TableConfigError;
 pub(crate) use error::ValidationError;
-use error::{ConversionError, CredentialsError, FileIoError, TableConfigError, UpdateError};
+use error::{ConversionError, CredentialsError, FileIoError, UpdateError};
 use futures::StreamExt;
 pub use gcs::{GcsCredential, GcsProfile, GcsServiceKey};
 use iceberg::io::FileIO;
@@ -25,10 +31,16 @@ use crate::{
     catalog::{compression_codec::CompressionCodec, io::list_location},
     request_metadata::RequestMetadata,
     retry::retry_fn,
-    service::tabular_idents::TabularIdentUuid,
-    WarehouseIdent,
+    service::{
+        backend_timeout::{with_timeout, Backend},
+        tabular_idents::TabularIdentUuid,
+    },
+    WarehouseIdent, CONFIG,
 };
 
+/// Path segment below the warehouse base location that holds the team prefixes.
+pub const TEAM_LOCATION_SEGMENT: &str = "teams";
+
 /// Storage profile for a warehouse.
 #[derive(
     Debug, Clone, Eq, PartialEq, Serialize, Deserialize, derive_more::From, utoipa::ToSchema,
@@ -64,19 +76,60 @@ pub enum StorageType {
     Gcs,
 }
 
-#[derive(Debug, Clone, PartialEq, Copy)]
+/// Access levels of vended credentials, ordered from narrowest to broadest.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum StoragePermissions {
     Read,
     ReadWrite,
     ReadWriteDelete,
 }
 
+/// Credential property telling clients how many milliseconds before the
+/// expiry of vended credentials they should request new credentials.
+pub(crate) const CREDENTIALS_REFRESH_BEFORE_MS: &str = "lakekeeper.credentials.refresh-before-ms";
+
 #[derive(Debug)]
 pub struct TableConfig {
     pub(crate) creds: TableProperties,
     pub(crate) config: TableProperties,
 }
 
+impl TableConfig {
+    /// Expiry of the vended credentials in milliseconds since the epoch,
+    /// if reported by the storage.
+    pub(crate) fn credentials_expire_at_ms(&self) -> Option<i64> {
+        use iceberg_ext::configs::table::{gcs, s3};
+
+        self.creds
+            .get_prop_opt::<s3::SessionTokenExpiresAtMs>()
+            .or_else(|| self.creds.get_prop_opt::<gcs::TokenExpiresAt>())
+            .and_then(|expires_at| expires_at.parse().ok())
+    }
+
+    /// Add a refresh hint to expiring credentials. `refresh_before` is the
+    /// setting of the warehouse, `None` uses the server default.
+    #[must_use]
+    pub(crate) fn with_refresh_hint(mut self, refresh_before: Option<chrono::Duration>) -> Self {
+        use iceberg_ext::configs::table::custom;
+
+        if self.credentials_expire_at_ms().is_none() {
+            return self;
+        }
+        let refresh_before_ms = refresh_before.map_or_else(
+            || i64::try_from(CONFIG.credential_refresh_before.as_millis()).unwrap_or(i64::MAX),
+            |d| d.num_milliseconds(),
+        );
+        let hint = custom::CustomConfig {
+            key: CREDENTIALS_REFRESH_BEFORE_MS.to_string(),
+            value: refresh_before_ms.to_string(),
+        };
+        self.creds.insert(&hint);
+        self.config.insert(&hint);
+        self
+    }
+}
+
 impl StorageProfile {
     #[must_use]
     pub fn generate_catalog_config(
@@ -153,6 +206,71 @@ impl StorageProfile {
         }
     }
 
+    /// Create a writer for conditional writes of new files to the storage profile.
+    ///
+    /// Storages without support for conditional writes, or credentials the writer
+    /// cannot authenticate with, yield a writer that reports them as unsupported.
+    #[must_use]
+    pub fn conditional_writer(
+        &self,
+        secret: Option<&StorageCredential>,
+    ) -> Box<dyn ConditionalWriter> {
+        let unsupported = |reason: &str| -> Box<dyn ConditionalWriter> {
+            let profile = self
+                .base_location()
+                .map(|location| location.to_string())
+                .unwrap_or_default();
+            Box::new(conditional_write::Unsupported(format!(
+                "{reason} (storage profile at '{profile}')"
+            )))
+        };
+        match self {
+            #[cfg(feature = "s3-signer")]
+            StorageProfile::S3(profile) => {
+                if profile.assume_role_arn.is_some() {
+                    return unsupported("Conditional writes to S3 do not support assume role ARNs");
+                }
+                let credentials = match (
+                    secret
+                        .and_then(|s| s.try_to_s3().ok())
+                        .map(|c| profile.get_aws_sdk_credentials(Some(c))),
+                    &profile.credential_provider,
+                ) {
+                    (Some(Ok(credentials)), _) => {
+                        conditional_write::S3WriteCredentials::AccessKey(credentials)
+                    }
+                    (_, Some(credential_provider::CredentialProviderConfig::Http { url })) => {
+                        conditional_write::S3WriteCredentials::Provider(url.clone())
+                    }
+                    _ => return unsupported(
+                        "Conditional writes to S3 require an access key or a credential provider",
+                    ),
+                };
+                Box::new(conditional_write::S3ConditionalWriter {
+                    profile: profile.clone(),
+                    credentials,
+                })
+            }
+            #[cfg(not(feature = "s3-signer"))]
+            StorageProfile::S3(_) => {
+                unsupported("Conditional writes to S3 require the s3-signer feature")
+            }
+            StorageProfile::Gcs(_) => match secret.and_then(|s| s.try_into_gcs().ok()) {
+                Some(GcsCredential::ServiceAccountKey { key }) => {
+                    Box::new(conditional_write::GcsConditionalWriter { key: key.clone() })
+                }
+                None => unsupported("Conditional writes to GCS require a service account key"),
+            },
+            StorageProfile::Adls(_) => {
+                unsupported("Conditional writes to ADLS are not implemented yet")
+            }
+            #[cfg(test)]
+            StorageProfile::Test(_) => {
+                unsupported("Test storage does not support conditional writes")
+            }
+        }
+    }
+
     /// Get the base location of this Storage Profiles
     ///
     /// # Errors
@@ -192,6 +310,19 @@ impl StorageProfile {
         Ok(base_location)
     }
 
+    /// Get the location below which all tabulars of a team are stored.
+    ///
+    /// # Errors
+    /// Fails if the `key_prefix` is not valid for S3 URLs.
+    pub fn default_team_location(&self, team: &str) -> Result<Location, ValidationError> {
+        let mut base_location: Location = self.base_location()?;
+        base_location
+            .without_trailing_slash()
+            .push(TEAM_LOCATION_SEGMENT)
+            .push(team);
+        Ok(base_location)
+    }
+
     #[must_use]
     pub fn storage_type(&self) -> StorageType {
         match self {
@@ -203,7 +334,56 @@ impl StorageProfile {
         }
     }
 
+    /// Profile to use for locations in a bucket of `region`. Only S3 profiles
+    /// are region specific, other profiles are returned unchanged.
+    #[must_use]
+    pub fn with_region(&self, region: Option<&str>) -> std::borrow::Cow<'_, Self> {
+        match (self, region) {
+            (StorageProfile::S3(profile), Some(region)) if profile.region != region => {
+                let mut profile = profile.clone();
+                profile.region = region.to_string();
+                std::borrow::Cow::Owned(StorageProfile::S3(profile))
+            }
+            _ => std::borrow::Cow::Borrowed(self),
+        }
+    }
+
+    /// Location schemes that can be used with this storage profile.
+    #[must_use]
+    pub fn supported_schemes(&self) -> Vec<&'static str> {
+        match self {
+            StorageProfile::S3(profile) => {
+                if profile.allow_alternate_schemes() {
+                    vec!["s3", "s3a", "s3n"]
+                } else {
+                    vec!["s3"]
+                }
+            }
+            #[cfg(test)]
+            StorageProfile::Test(_) => vec!["file"],
+            StorageProfile::Adls(_) => vec!["abfss"],
+            StorageProfile::Gcs(_) => vec!["gs"],
+        }
+    }
+
+    /// Lifetime of vended credentials if no other lifetime is requested.
+    #[must_use]
+    pub fn default_credential_ttl(&self) -> chrono::Duration {
+        match self {
+            StorageProfile::Adls(profile) => profile
+                .sas_token_validity_seconds
+                .and_then(|seconds| i64::try_from(seconds).ok())
+                .map_or(chrono::Duration::hours(1), chrono::Duration::seconds),
+            // Default duration of STS sessions and lifetime of downscoped GCS tokens
+            StorageProfile::S3(_) | StorageProfile::Gcs(_) => chrono::Duration::hours(1),
+            #[cfg(test)]
+            StorageProfile::Test(_) => chrono::Duration::hours(1),
+        }
+    }
+
     /// Generate the table config for the storage profile.
+    /// `credential_ttl` overrides the lifetime of vended credentials for S3 and ADLS.
+    /// The lifetime of GCS credentials cannot be changed.
     ///
     /// # Errors
     /// Fails if the underlying storage profile's generation fails.
@@ -213,6 +393,29 @@ impl StorageProfile {
         secret: Option<&StorageCredential>,
         table_location: &Location,
         storage_permissions: StoragePermissions,
+        credential_ttl: Option<chrono::Duration>,
+    ) -> Result<TableConfig, TableConfigError> {
+        with_timeout(
+            Backend::CredentialVending,
+            CONFIG.backend_timeouts.credential_vending,
+            self.generate_table_config_inner(
+                data_access,
+                secret,
+                table_location,
+                storage_permissions,
+                credential_ttl,
+            ),
+        )
+        .await?
+    }
+
+    async fn generate_table_config_inner(
+        &self,
+        data_access: &DataAccess,
+        secret: Option<&StorageCredential>,
+        table_location: &Location,
+        storage_permissions: StoragePermissions,
+        credential_ttl: Option<chrono::Duration>,
     ) -> Result<TableConfig, TableConfigError> {
         match self {
             StorageProfile::S3(profile) => {
@@ -222,6 +425,7 @@ impl StorageProfile {
                         secret.map(|s| s.try_to_s3()).transpose()?,
                         table_location,
                         storage_permissions,
+                        credential_ttl,
                     )
                     .await
             }
@@ -236,14 +440,29 @@ impl StorageProfile {
                             })?
                             .try_to_az()?,
                         storage_permissions,
+                        credential_ttl,
                     )
                     .await
             }
             #[cfg(test)]
-            StorageProfile::Test(_) => Ok(TableConfig {
-                creds: TableProperties::default(),
-                config: TableProperties::default(),
-            }),
+            StorageProfile::Test(_) => {
+                use iceberg_ext::configs::table::s3;
+
+                // Vend short-lived fake credentials, so that expiry handling can be tested.
+                let mut creds = TableProperties::default();
+                if data_access.vended_credentials {
+                    let expires_at = chrono::Utc::now()
+                        + credential_ttl.unwrap_or_else(|| self.default_credential_ttl());
+                    creds.insert(&s3::SessionToken(Uuid::now_v7().to_string()));
+                    creds.insert(&s3::SessionTokenExpiresAtMs(
+                        expires_at.timestamp_millis().to_string(),
+                    ));
+                }
+                Ok(TableConfig {
+                    creds,
+                    config: TableProperties::default(),
+                })
+            }
             StorageProfile::Gcs(profile) => {
                 profile
                     .generate_table_config(
@@ -304,7 +523,9 @@ impl StorageProfile {
 
         // Test vended-credentials access
         let test_vended_credentials = match self {
-            StorageProfile::S3(profile) => profile.sts_enabled,
+            StorageProfile::S3(profile) => {
+                profile.sts_enabled || profile.credential_provider.is_some()
+            }
             StorageProfile::Adls(_) => true,
             StorageProfile::Gcs(_) => true,
             #[cfg(test)]
@@ -321,6 +542,7 @@ impl StorageProfile {
                     credential,
                     &test_location,
                     StoragePermissions::ReadWriteDelete,
+                    None,
                 )
                 .await?;
             match &self {
@@ -647,6 +869,20 @@ impl StorageCredential {
         }
     }
 
+    /// Type of the credential, e.g. `s3/access-key`. Does not reveal any secret value.
+    #[must_use]
+    pub fn credential_type(&self) -> &'static str {
+        match self {
+            StorageCredential::S3(S3Credential::AccessKey { .. }) => "s3/access-key",
+            StorageCredential::Az(AzCredential::ClientCredentials { .. }) => {
+                "az/client-credentials"
+            }
+            StorageCredential::Gcs(GcsCredential::ServiceAccountKey { .. }) => {
+                "gcs/service-account-key"
+            }
+        }
+    }
+
     /// Try to convert the credential into an S3 credential.
     ///
     /// # Errors
@@ -898,6 +1134,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_with_region() {
+        let profile = StorageProfile::S3(
+            S3Profile::builder()
+                .bucket("my-bucket".to_string())
+                .region("us-east-1".to_string())
+                .sts_enabled(false)
+                .flavor(S3Flavor::Aws)
+                .build(),
+        );
+
+        assert_eq!(*profile.with_region(None), profile);
+        assert_eq!(*profile.with_region(Some("us-east-1")), profile);
+        let StorageProfile::S3(regional) = profile.with_region(Some("eu-central-1")).into_owned()
+        else {
+            panic!("Expected an S3 profile");
+        };
+        assert_eq!(regional.region, "eu-central-1");
+        assert_eq!(regional.bucket, "my-bucket");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "s3-signer")]
+    async fn test_conditional_writer_of_s3_profiles() {
+        let profile = S3Profile::builder()
+            .bucket("my-bucket".to_string())
+            .region("us-east-1".to_string())
+            .sts_enabled(false)
+            .flavor(S3Flavor::Aws)
+            .build();
+
+        let location = Location::from_str("s3://my-bucket/table/metadata/v1.json").unwrap();
+        let err = StorageProfile::S3(profile.clone())
+            .conditional_writer(None)
+            .write_if_absent(&location, vec![])
+            .await
+            .unwrap_err();
+        let ConditionalWriteError::Unsupported(reason) = err else {
+            panic!("Expected unsupported conditional write, got {err:?}");
+        };
+        assert!(reason.contains("s3://my-bucket"), "{reason}");
+
+        let mut with_provider = profile;
+        with_provider.credential_provider =
+            Some(credential_provider::CredentialProviderConfig::Http {
+                url: "http://localhost:8080/credentials".parse().unwrap(),
+            });
+        let writer = StorageProfile::S3(with_provider).conditional_writer(None);
+        assert!(format!("{writer:?}").contains("Provider"), "{writer:?}");
+    }
+
     // TODO: add vended azure test here once opendal supports sas
 
     #[tokio::test]
@@ -993,6 +1280,7 @@ mod tests {
                 Some(cred),
                 &table_location1,
                 StoragePermissions::ReadWriteDelete,
+                None,
             )
             .await
             .unwrap();
@@ -1006,6 +1294,7 @@ mod tests {
                 Some(cred),
                 &table_location2,
                 StoragePermissions::ReadWriteDelete,
+                None,
             )
             .await
             .unwrap();
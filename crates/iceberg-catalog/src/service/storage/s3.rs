@@ -19,6 +19,10 @@ use crate::{
     },
     request_metadata::RequestMetadata,
     service::storage::{
+        credential_provider::{
+            vend_scoped_credentials, CredentialProvider, CredentialProviderConfig,
+            CredentialRequest, HttpCredentialProvider, VendedCredentials,
+        },
         error::{CredentialsError, FileIoError, TableConfigError, UpdateError, ValidationError},
         StoragePermissions, TableConfig,
     },
@@ -78,6 +82,11 @@ pub struct S3Profile {
     #[serde(default)]
     #[builder(default, setter(strip_option))]
     pub allow_alternative_protocols: Option<bool>,
+    /// Custom provider used to vend credentials instead of STS.
+    /// If not set, credentials are vended via STS if `sts-enabled` is true.
+    #[serde(default)]
+    #[builder(default, setter(strip_option))]
+    pub credential_provider: Option<CredentialProviderConfig>,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -255,6 +264,7 @@ impl S3Profile {
             sts_enabled: _,
             flavor: _,
             allow_alternative_protocols: _,
+            credential_provider: _,
         } = self;
 
         // assume_role_arn is not supported currently
@@ -325,14 +335,43 @@ impl S3Profile {
     /// # Errors
     /// Fails if vended credentials are used - currently not supported.
     pub async fn generate_table_config(
+        &self,
+        data_access: &DataAccess,
+        cred: Option<&S3Credential>,
+        table_location: &Location,
+        storage_permissions: StoragePermissions,
+        credential_ttl: Option<chrono::Duration>,
+    ) -> Result<TableConfig, TableConfigError> {
+        let provider: Option<Box<dyn CredentialProvider + '_>> = match &self.credential_provider {
+            Some(CredentialProviderConfig::Http { url }) => {
+                Some(Box::new(HttpCredentialProvider { url }))
+            }
+            None if self.sts_enabled => Some(Box::new(StsCredentialProvider {
+                profile: self,
+                credential: cred,
+            })),
+            None => None,
+        };
+        self.generate_table_config_with_provider(
+            data_access,
+            table_location,
+            storage_permissions,
+            credential_ttl,
+            provider.as_deref(),
+        )
+        .await
+    }
+
+    async fn generate_table_config_with_provider(
         &self,
         DataAccess {
             vended_credentials,
             remote_signing,
         }: &DataAccess,
-        cred: Option<&S3Credential>,
         table_location: &Location,
         storage_permissions: StoragePermissions,
+        credential_ttl: Option<chrono::Duration>,
+        provider: Option<&dyn CredentialProvider>,
     ) -> Result<TableConfig, TableConfigError> {
         // If vended_credentials is False and remote_signing is False,
         // use remote_signing.
@@ -358,32 +397,35 @@ impl S3Profile {
         }
 
         if *vended_credentials {
-            if self.sts_enabled {
-                let aws_sdk_sts::types::Credentials {
+            if let Some(provider) = provider {
+                let VendedCredentials {
                     access_key_id,
                     secret_access_key,
                     session_token,
-                    expiration: _,
+                    expiration,
                     ..
-                } = if let (S3Flavor::S3Compat, Some(cred)) = (self.flavor, cred) {
-                    self.get_minio_sts_token(table_location, cred, storage_permissions)
-                        .await?
-                } else if let (Some(cred), Some(arn)) = (cred, self.sts_role_arn.as_ref()) {
-                    self.get_aws_sts_token(table_location, cred, arn, storage_permissions)
-                        .await?
-                } else {
-                    // This error should never be returned since we validate this when creating the profile.
-                    // We should consider using an enum instead of 3 independent fields.
-                    return Err(TableConfigError::Misconfiguration(
-                        "STS either needs Flavor Minio and credentials OR Flavor aws, credentials and a sts role arn.".to_string(),
-                    ));
-                };
+                } = vend_scoped_credentials(
+                    provider,
+                    &CredentialRequest {
+                        prefix: table_location.clone(),
+                        access_level: storage_permissions,
+                        ttl: credential_ttl,
+                    },
+                )
+                .await?;
                 config.insert(&s3::AccessKeyId(access_key_id.clone()));
                 config.insert(&s3::SecretAccessKey(secret_access_key.clone()));
-                config.insert(&s3::SessionToken(session_token.clone()));
                 creds.insert(&s3::AccessKeyId(access_key_id));
                 creds.insert(&s3::SecretAccessKey(secret_access_key));
-                creds.insert(&s3::SessionToken(session_token));
+                if let Some(session_token) = session_token {
+                    config.insert(&s3::SessionToken(session_token.clone()));
+                    creds.insert(&s3::SessionToken(session_token));
+                }
+                if let Some(expiration) = expiration {
+                    let expires_at_ms = expiration.timestamp_millis().to_string();
+                    config.insert(&s3::SessionTokenExpiresAtMs(expires_at_ms.clone()));
+                    creds.insert(&s3::SessionTokenExpiresAtMs(expires_at_ms));
+                }
             } else {
                 insert_pyiceberg_hack(&mut config);
                 remote_signing = true;
@@ -401,14 +443,37 @@ impl S3Profile {
         Ok(TableConfig { creds, config })
     }
 
+    async fn get_sts_credentials(
+        &self,
+        cred: Option<&S3Credential>,
+        table_location: &Location,
+        storage_permissions: StoragePermissions,
+        ttl: Option<chrono::Duration>,
+    ) -> Result<aws_sdk_sts::types::Credentials, TableConfigError> {
+        if let (S3Flavor::S3Compat, Some(cred)) = (self.flavor, cred) {
+            self.get_minio_sts_token(table_location, cred, storage_permissions, ttl)
+                .await
+        } else if let (Some(cred), Some(arn)) = (cred, self.sts_role_arn.as_ref()) {
+            self.get_aws_sts_token(table_location, cred, arn, storage_permissions, ttl)
+                .await
+        } else {
+            // This error should never be returned since we validate this when creating the profile.
+            // We should consider using an enum instead of 3 independent fields.
+            Err(TableConfigError::Misconfiguration(
+                "STS either needs Flavor Minio and credentials OR Flavor aws, credentials and a sts role arn.".to_string(),
+            ))
+        }
+    }
+
     async fn get_aws_sts_token(
         &self,
         table_location: &Location,
         cred: &S3Credential,
         arn: &str,
         storage_permissions: StoragePermissions,
+        ttl: Option<chrono::Duration>,
     ) -> Result<aws_sdk_sts::types::Credentials, TableConfigError> {
-        self.get_sts_token(table_location, cred, Some(arn), storage_permissions)
+        self.get_sts_token(table_location, cred, Some(arn), storage_permissions, ttl)
             .await
     }
 
@@ -417,8 +482,9 @@ impl S3Profile {
         table_location: &Location,
         cred: &S3Credential,
         storage_permissions: StoragePermissions,
+        ttl: Option<chrono::Duration>,
     ) -> Result<aws_sdk_sts::types::Credentials, TableConfigError> {
-        self.get_sts_token(table_location, cred, None, storage_permissions)
+        self.get_sts_token(table_location, cred, None, storage_permissions, ttl)
             .await
     }
 
@@ -428,6 +494,7 @@ impl S3Profile {
         cred: &S3Credential,
         arn: Option<&str>,
         storage_permissions: StoragePermissions,
+        ttl: Option<chrono::Duration>,
     ) -> Result<aws_sdk_sts::types::Credentials, TableConfigError> {
         let cred = self
             .get_aws_sdk_config(self.get_aws_sdk_credentials(Some(cred))?)
@@ -439,7 +506,10 @@ impl S3Profile {
             .policy(Self::get_aws_policy_string(
                 table_location,
                 storage_permissions,
-            )?);
+            )?)
+            .set_duration_seconds(
+                ttl.map(|ttl| i32::try_from(ttl.num_seconds()).unwrap_or(i32::MAX)),
+            );
         let assume_role_builder = if let Some(arn) = arn {
             assume_role_builder.role_arn(arn)
         } else {
@@ -682,6 +752,53 @@ fn validate_bucket_name(bucket: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Default credential provider which assumes a role restricted to the table location via STS.
+#[derive(Debug)]
+struct StsCredentialProvider<'a> {
+    profile: &'a S3Profile,
+    credential: Option<&'a S3Credential>,
+}
+
+#[async_trait::async_trait]
+impl CredentialProvider for StsCredentialProvider<'_> {
+    fn name(&self) -> &'static str {
+        "sts"
+    }
+
+    async fn vend_credentials(
+        &self,
+        request: &CredentialRequest,
+    ) -> Result<VendedCredentials, TableConfigError> {
+        let aws_sdk_sts::types::Credentials {
+            access_key_id,
+            secret_access_key,
+            session_token,
+            expiration,
+            ..
+        } = self
+            .profile
+            .get_sts_credentials(
+                self.credential,
+                &request.prefix,
+                request.access_level,
+                request.ttl,
+            )
+            .await?;
+
+        Ok(VendedCredentials {
+            access_key_id,
+            secret_access_key,
+            session_token: Some(session_token),
+            expiration: chrono::DateTime::from_timestamp(
+                expiration.secs(),
+                expiration.subsec_nanos(),
+            ),
+            prefix: request.prefix.clone(),
+            access_level: request.access_level,
+        })
+    }
+}
+
 fn insert_pyiceberg_hack(config: &mut TableProperties) {
     config.insert(&s3::Signer("S3V4RestSigner".to_string()));
     config.insert(&custom::CustomConfig {
@@ -854,7 +971,10 @@ pub(crate) mod test {
 
     use super::*;
     use crate::service::{
-        storage::{StorageLocations as _, StorageProfile},
+        storage::{
+            credential_provider::test::MockCredentialProvider, StorageLocations as _,
+            StorageProfile,
+        },
         tabular_idents::TabularIdentUuid,
         NamespaceIdentUuid,
     };
@@ -904,6 +1024,7 @@ pub(crate) mod test {
             sts_enabled: false,
             flavor: S3Flavor::Aws,
             allow_alternative_protocols: Some(false),
+            credential_provider: None,
         };
         let sp: StorageProfile = profile.clone().into();
 
@@ -944,6 +1065,7 @@ pub(crate) mod test {
             sts_enabled: false,
             flavor: S3Flavor::Aws,
             allow_alternative_protocols: Some(false),
+            credential_provider: None,
         };
 
         let namespace_location = Location::from_str("s3://test-bucket/foo/").unwrap();
@@ -987,6 +1109,7 @@ pub(crate) mod test {
                 flavor: S3Flavor::S3Compat,
                 sts_enabled: true,
                 allow_alternative_protocols: Some(false),
+                credential_provider: None,
             };
             let cred = S3Credential::AccessKey {
                 aws_access_key_id: TEST_ACCESS_KEY.clone(),
@@ -1050,6 +1173,7 @@ pub(crate) mod test {
                         flavor: S3Flavor::Aws,
                         sts_enabled: true,
                         allow_alternative_protocols: Some(false),
+                        credential_provider: None,
                     }
                     .into();
 
@@ -1126,6 +1250,77 @@ pub(crate) mod test {
         let _ = serde_json::from_str::<serde_json::Value>(&policy).unwrap();
     }
 
+    #[test]
+    fn policy_is_scoped_to_table_bucket() {
+        // Tables of namespaces with a storage prefix override live in a different bucket
+        // than the base location of the profile.
+        let table_location = "s3://eu-bucket/finance/ns/table";
+        let policy = S3Profile::get_aws_policy_string(
+            &table_location.parse().unwrap(),
+            StoragePermissions::Read,
+        )
+        .unwrap();
+        let policy = serde_json::from_str::<serde_json::Value>(&policy).unwrap();
+        assert_eq!(
+            policy["Statement"][0]["Resource"],
+            serde_json::json!([
+                "arn:aws:s3:::eu-bucket/finance/ns/table/",
+                "arn:aws:s3:::eu-bucket/finance/ns/table/*"
+            ])
+        );
+        assert_eq!(policy["Statement"][1]["Resource"], "arn:aws:s3:::eu-bucket");
+    }
+
+    #[tokio::test]
+    async fn custom_credential_provider_receives_requested_scope() {
+        let profile = S3Profile::builder()
+            .bucket("test-bucket".to_string())
+            .region("eu-central-1".to_string())
+            .sts_enabled(false)
+            .build();
+        let provider = MockCredentialProvider::default();
+        let table_location: Location = "s3://test-bucket/ns/table".parse().unwrap();
+
+        let table_config = profile
+            .generate_table_config_with_provider(
+                &DataAccess {
+                    vended_credentials: true,
+                    remote_signing: false,
+                },
+                &table_location,
+                StoragePermissions::Read,
+                Some(chrono::Duration::hours(2)),
+                Some(&provider),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            provider.requests.lock().unwrap().as_slice(),
+            &[CredentialRequest {
+                prefix: table_location,
+                access_level: StoragePermissions::Read,
+                ttl: Some(chrono::Duration::hours(2)),
+            }]
+        );
+        assert_eq!(
+            table_config.creds.get_prop_opt::<s3::AccessKeyId>(),
+            Some("mock-key".to_string())
+        );
+        assert_eq!(
+            table_config
+                .creds
+                .get_prop_opt::<s3::SessionTokenExpiresAtMs>(),
+            Some("1700000000000".to_string())
+        );
+        assert_eq!(
+            table_config
+                .config
+                .get_prop_opt::<s3::RemoteSigningEnabled>(),
+            None
+        );
+    }
+
     #[test]
     fn test_parse_s3_location_invalid_proto() {
         S3Location::try_from_str("adls://test-bucket/foo/", false).unwrap_err();
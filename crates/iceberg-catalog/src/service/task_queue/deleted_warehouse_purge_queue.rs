@@ -0,0 +1,164 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::StreamExt;
+use iceberg_ext::{catalog::rest::ErrorModel, configs::Location};
+use tracing::Instrument;
+
+use crate::{
+    api::Result,
+    catalog::maybe_get_secret,
+    service::{
+        storage::StorageProfile,
+        task_queue::{tabular_purge_queue::remove_location, Task, TaskQueue},
+        Catalog, SecretStore,
+    },
+    SecretIdent, WarehouseIdent,
+};
+
+/// Number of locations of a deleted warehouse purged concurrently.
+const DELETED_WAREHOUSE_PURGE_CONCURRENCY: usize = 16;
+
+pub type DeletedWarehousePurgeQueue = Arc<
+    dyn TaskQueue<Task = DeletedWarehousePurgeTask, Input = DeletedWarehousePurgeInput>
+        + Send
+        + Sync
+        + 'static,
+>;
+
+// TODO: concurrent workers
+pub async fn deleted_warehouse_purge_task<C: Catalog, S: SecretStore>(
+    fetcher: DeletedWarehousePurgeQueue,
+    catalog_state: C::State,
+    secret_state: S,
+) {
+    loop {
+        // add some jitter to avoid syncing with other queues
+        tokio::time::sleep(fetcher.config().poll_interval + Duration::from_millis(29)).await;
+
+        let purge_task = match fetcher.pick_new_task().await {
+            Ok(purge_task) => purge_task,
+            Err(err) => {
+                tracing::error!("Failed to fetch deleted warehouse purge: {:?}", err);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(purge_task) = purge_task else {
+            continue;
+        };
+
+        let span = tracing::debug_span!(
+            "deleted_warehouse_purge",
+            warehouse_id = %purge_task.warehouse_ident,
+            locations = purge_task.locations.len(),
+            queue_name = %purge_task.task.queue_name,
+            task = ?purge_task.task,
+        );
+
+        instrumented_purge::<C, S>(
+            fetcher.clone(),
+            catalog_state.clone(),
+            &secret_state,
+            &purge_task,
+        )
+        .instrument(span.or_current())
+        .await;
+    }
+}
+
+async fn instrumented_purge<C: Catalog, S: SecretStore>(
+    fetcher: DeletedWarehousePurgeQueue,
+    catalog_state: C::State,
+    secret_state: &S,
+    purge_task: &DeletedWarehousePurgeTask,
+) {
+    match purge::<C, S>(purge_task, catalog_state, secret_state).await {
+        Ok(()) => {
+            fetcher.retrying_record_success(&purge_task.task).await;
+            tracing::info!(
+                "Purged data of deleted warehouse {}",
+                purge_task.warehouse_ident
+            );
+        }
+        Err(err) => {
+            tracing::error!(
+                "Failed to purge data of deleted warehouse {}: {}",
+                purge_task.warehouse_ident,
+                err.error
+            );
+            fetcher
+                .retrying_record_failure(&purge_task.task, &err.error.to_string())
+                .await;
+        }
+    };
+}
+
+/// Remove all locations of the task. Locations that were removed already are
+/// empty, so a retry only removes what is left.
+async fn purge<C: Catalog, S: SecretStore>(
+    DeletedWarehousePurgeTask {
+        warehouse_ident,
+        storage_profile,
+        storage_secret_id,
+        locations,
+        task: _,
+    }: &DeletedWarehousePurgeTask,
+    catalog_state: C::State,
+    secret_state: &S,
+) -> Result<()> {
+    let secret = maybe_get_secret::<C, S>(*storage_secret_id, catalog_state, secret_state).await?;
+    let file_io = storage_profile.file_io(secret.as_ref())?;
+
+    let failed = futures::stream::iter(locations)
+        .map(|location| {
+            let file_io = &file_io;
+            async move {
+                remove_location(file_io, location).await.map_err(|e| {
+                    tracing::error!(
+                        "Failed to purge '{location}' of deleted warehouse {warehouse_ident}: {:?}",
+                        e.error
+                    );
+                    location
+                })
+            }
+        })
+        .buffer_unordered(DELETED_WAREHOUSE_PURGE_CONCURRENCY)
+        .filter_map(|result| async move { result.err() })
+        .collect::<Vec<_>>()
+        .await;
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(ErrorModel::internal(
+            format!(
+                "Failed to purge {} of {} locations of deleted warehouse {warehouse_ident}",
+                failed.len(),
+                locations.len()
+            ),
+            "FileIOError",
+            None,
+        )
+        .into())
+    }
+}
+
+#[derive(Debug)]
+pub struct DeletedWarehousePurgeTask {
+    pub warehouse_ident: WarehouseIdent,
+    pub storage_profile: StorageProfile,
+    pub storage_secret_id: Option<SecretIdent>,
+    pub locations: Vec<Location>,
+    pub task: Task,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeletedWarehousePurgeInput {
+    pub warehouse_ident: WarehouseIdent,
+    /// Storage profile of the deleted warehouse, used to access its data.
+    pub storage_profile: StorageProfile,
+    pub storage_secret_id: Option<SecretIdent>,
+    /// Locations of the tables and views of the warehouse to purge.
+    pub locations: Vec<Location>,
+}
@@ -9,11 +9,17 @@ use uuid::Uuid;
 use super::{authz::Authorizer, WarehouseIdent};
 use crate::service::{
     task_queue::{
+        deleted_warehouse_purge_queue::DeletedWarehousePurgeInput,
+        snapshot_cleanup_queue::SnapshotCleanupInput,
+        storage_secret_cleanup_queue::StorageSecretCleanupInput,
         tabular_expiration_queue::TabularExpirationInput, tabular_purge_queue::TabularPurgeInput,
     },
     Catalog, SecretStore,
 };
 
+pub mod deleted_warehouse_purge_queue;
+pub mod snapshot_cleanup_queue;
+pub mod storage_secret_cleanup_queue;
 pub mod tabular_expiration_queue;
 pub mod tabular_purge_queue;
 
@@ -21,6 +27,9 @@ pub mod tabular_purge_queue;
 pub struct TaskQueues {
     tabular_expiration: tabular_expiration_queue::ExpirationQueue,
     tabular_purge: tabular_purge_queue::TabularPurgeQueue,
+    snapshot_cleanup: snapshot_cleanup_queue::SnapshotCleanupQueue,
+    storage_secret_cleanup: storage_secret_cleanup_queue::StorageSecretCleanupQueue,
+    deleted_warehouse_purge: deleted_warehouse_purge_queue::DeletedWarehousePurgeQueue,
 }
 
 impl TaskQueues {
@@ -28,10 +37,16 @@ impl TaskQueues {
     pub fn new(
         expiration: tabular_expiration_queue::ExpirationQueue,
         purge: tabular_purge_queue::TabularPurgeQueue,
+        snapshot_cleanup: snapshot_cleanup_queue::SnapshotCleanupQueue,
+        storage_secret_cleanup: storage_secret_cleanup_queue::StorageSecretCleanupQueue,
+        deleted_warehouse_purge: deleted_warehouse_purge_queue::DeletedWarehousePurgeQueue,
     ) -> Self {
         Self {
             tabular_expiration: expiration,
             tabular_purge: purge,
+            snapshot_cleanup,
+            storage_secret_cleanup,
+            deleted_warehouse_purge,
         }
     }
 
@@ -59,6 +74,30 @@ impl TaskQueues {
         self.tabular_purge.enqueue(task).await
     }
 
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn queue_snapshot_cleanup(
+        &self,
+        task: SnapshotCleanupInput,
+    ) -> crate::api::Result<()> {
+        self.snapshot_cleanup.enqueue(task).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn queue_storage_secret_cleanup(
+        &self,
+        task: StorageSecretCleanupInput,
+    ) -> crate::api::Result<()> {
+        self.storage_secret_cleanup.enqueue(task).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn queue_deleted_warehouse_purge(
+        &self,
+        task: DeletedWarehousePurgeInput,
+    ) -> crate::api::Result<()> {
+        self.deleted_warehouse_purge.enqueue(task).await
+    }
+
     pub async fn spawn_queues<C, S, A>(
         &self,
         catalog_state: C::State,
@@ -81,9 +120,34 @@ impl TaskQueues {
         let purge_queue_handler = tokio::task::spawn(tabular_purge_queue::purge_task::<C, S>(
             self.tabular_purge.clone(),
             catalog_state.clone(),
-            secret_store,
+            secret_store.clone(),
         ));
 
+        let snapshot_cleanup_queue_handler =
+            tokio::task::spawn(snapshot_cleanup_queue::snapshot_cleanup_task::<C, S>(
+                self.snapshot_cleanup.clone(),
+                catalog_state.clone(),
+                secret_store.clone(),
+            ));
+
+        let storage_secret_cleanup_queue_handler =
+            tokio::task::spawn(storage_secret_cleanup_queue::storage_secret_cleanup_task::<
+                C,
+                S,
+            >(
+                self.storage_secret_cleanup.clone(),
+                catalog_state.clone(),
+                secret_store.clone(),
+            ));
+
+        let deleted_warehouse_purge_queue_handler = tokio::task::spawn(
+            deleted_warehouse_purge_queue::deleted_warehouse_purge_task::<C, S>(
+                self.deleted_warehouse_purge.clone(),
+                catalog_state.clone(),
+                secret_store,
+            ),
+        );
+
         tokio::select!(
             _ = expiration_queue_handler => {
                 tracing::error!("Tabular expiration queue handler exited unexpectedly");
@@ -93,6 +157,18 @@ impl TaskQueues {
                 tracing::error!("Tabular purge queue handler exited unexpectedly");
                 Err(anyhow::anyhow!("Tabular purge queue handler exited unexpectedly"))
             },
+            _ = snapshot_cleanup_queue_handler => {
+                tracing::error!("Snapshot cleanup queue handler exited unexpectedly");
+                Err(anyhow::anyhow!("Snapshot cleanup queue handler exited unexpectedly"))
+            },
+            _ = storage_secret_cleanup_queue_handler => {
+                tracing::error!("Storage secret cleanup queue handler exited unexpectedly");
+                Err(anyhow::anyhow!("Storage secret cleanup queue handler exited unexpectedly"))
+            },
+            _ = deleted_warehouse_purge_queue_handler => {
+                tracing::error!("Deleted warehouse purge queue handler exited unexpectedly");
+                Err(anyhow::anyhow!("Deleted warehouse purge queue handler exited unexpectedly"))
+            },
         )?;
         Ok(())
     }
@@ -309,6 +385,27 @@ mod test {
         );
         let purge_queue = Arc::new(
             crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(
+                rw.clone(),
+                config.clone(),
+            )
+            .unwrap(),
+        );
+        let snapshot_cleanup_queue = Arc::new(
+            crate::implementations::postgres::task_queues::SnapshotCleanupQueue::from_config(
+                rw.clone(),
+                config.clone(),
+            )
+            .unwrap(),
+        );
+        let storage_secret_cleanup_queue = Arc::new(
+            crate::implementations::postgres::task_queues::StorageSecretCleanupQueue::from_config(
+                rw.clone(),
+                config.clone(),
+            )
+            .unwrap(),
+        );
+        let deleted_warehouse_purge_queue = Arc::new(
+            crate::implementations::postgres::task_queues::DeletedWarehousePurgeQueue::from_config(
                 rw.clone(),
                 config,
             )
@@ -317,8 +414,13 @@ mod test {
 
         let catalog_state = CatalogState::from_pools(pool.clone(), pool.clone());
 
-        let queues =
-            crate::service::task_queue::TaskQueues::new(expiration_queue.clone(), purge_queue);
+        let queues = crate::service::task_queue::TaskQueues::new(
+            expiration_queue.clone(),
+            purge_queue,
+            snapshot_cleanup_queue,
+            storage_secret_cleanup_queue,
+            deleted_warehouse_purge_queue,
+        );
         let secrets =
             crate::implementations::postgres::SecretsState::from_pools(pool.clone(), pool);
         let cloned = queues.clone();
@@ -0,0 +1,261 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use iceberg::{
+    io::FileIO,
+    spec::{Snapshot, TableMetadata},
+};
+use iceberg_ext::{
+    catalog::rest::ErrorModel,
+    configs::{Location, ParseFromStr},
+};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::{
+    api::Result,
+    catalog::{io::delete_file, maybe_get_secret},
+    service::{
+        task_queue::{Task, TaskQueue},
+        Catalog, SecretStore, TableIdentUuid, Transaction,
+    },
+    WarehouseIdent,
+};
+
+pub type SnapshotCleanupQueue = Arc<
+    dyn TaskQueue<Task = SnapshotCleanupTask, Input = SnapshotCleanupInput> + Send + Sync + 'static,
+>;
+
+// TODO: concurrent workers
+pub async fn snapshot_cleanup_task<C: Catalog, S: SecretStore>(
+    fetcher: SnapshotCleanupQueue,
+    catalog_state: C::State,
+    secret_state: S,
+) {
+    loop {
+        // add some jitter to avoid syncing with other queues
+        tokio::time::sleep(fetcher.config().poll_interval + Duration::from_millis(17)).await;
+
+        let cleanup_task = match fetcher.pick_new_task().await {
+            Ok(cleanup_task) => cleanup_task,
+            Err(err) => {
+                tracing::error!("Failed to fetch snapshot cleanup: {:?}", err);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(cleanup_task) = cleanup_task else {
+            continue;
+        };
+
+        let span = tracing::debug_span!(
+            "snapshot_cleanup",
+            table_id = %cleanup_task.table_id,
+            warehouse_id = %cleanup_task.warehouse_ident,
+            queue_name = %cleanup_task.task.queue_name,
+            task = ?cleanup_task.task,
+        );
+
+        instrumented_cleanup::<C, S>(
+            fetcher.clone(),
+            catalog_state.clone(),
+            &secret_state,
+            &cleanup_task,
+        )
+        .instrument(span.or_current())
+        .await;
+    }
+}
+
+pub(crate) async fn instrumented_cleanup<C: Catalog, S: SecretStore>(
+    fetcher: SnapshotCleanupQueue,
+    catalog_state: C::State,
+    secret_state: &S,
+    cleanup_task: &SnapshotCleanupTask,
+) {
+    match cleanup::<C, S>(cleanup_task, secret_state, catalog_state).await {
+        Ok(()) => {
+            fetcher.retrying_record_success(&cleanup_task.task).await;
+            tracing::info!(
+                "Successfully cleaned up files of {} expired snapshots of table {}",
+                cleanup_task.snapshots.len(),
+                cleanup_task.table_id
+            );
+        }
+        Err(err) => {
+            tracing::error!(
+                "Failed to clean up expired snapshots of table {}: {}",
+                cleanup_task.table_id,
+                err.error
+            );
+            fetcher
+                .retrying_record_failure(&cleanup_task.task, &err.error.to_string())
+                .await;
+        }
+    };
+}
+
+/// Delete the files referenced by the expired snapshots of a table that are no
+/// longer referenced by any snapshot of the table.
+///
+/// Only files below the location of the table are deleted. Files below an import
+/// prefix of the warehouse are owned by the exporting side and are kept. Nothing
+/// is deleted if the table no longer exists, as its storage is then handled by
+/// the drop of the table.
+async fn cleanup<C, S>(
+    SnapshotCleanupTask {
+        table_id,
+        warehouse_ident,
+        snapshots,
+        task: _,
+    }: &SnapshotCleanupTask,
+    secret_state: &S,
+    catalog_state: C::State,
+) -> Result<()>
+where
+    C: Catalog,
+    S: SecretStore,
+{
+    let mut trx = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let warehouse = C::require_warehouse(*warehouse_ident, trx.transaction()).await?;
+    let table = C::load_tables(*warehouse_ident, [*table_id], false, trx.transaction())
+        .await?
+        .remove(table_id);
+    trx.commit().await?;
+
+    let Some(table) = table else {
+        tracing::info!("Skipping cleanup of expired snapshots of '{table_id}', table not found.");
+        return Ok(());
+    };
+    let metadata = &table.table_metadata;
+    let table_location = Location::parse_value(metadata.location()).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to parse table location.",
+            "ParseError",
+            Some(Box::new(e)),
+        )
+    })?;
+
+    let secret =
+        maybe_get_secret::<C, S>(warehouse.storage_secret_id, catalog_state, secret_state).await?;
+    let file_io = warehouse.storage_profile.file_io(secret.as_ref())?;
+
+    let retained =
+        referenced_files(metadata.snapshots().map(AsRef::as_ref), metadata, &file_io).await?;
+    let expired = referenced_files(
+        snapshots
+            .iter()
+            .filter(|s| metadata.snapshot_by_id(s.snapshot_id()).is_none()),
+        metadata,
+        &file_io,
+    )
+    .await?;
+    let orphaned = expired
+        .difference(&retained)
+        .filter_map(|file| Location::parse_value(file).ok())
+        .filter(|location| {
+            location.is_sublocation_of(&table_location) && !warehouse.is_import_location(location)
+        })
+        .collect::<Vec<_>>();
+
+    // Delete files in parallel - if one delete fails, we still want to delete the rest
+    let n_failed = futures::future::join_all(
+        orphaned
+            .iter()
+            .map(|location| delete_file(&file_io, location)),
+    )
+    .await
+    .into_iter()
+    .filter_map(|r| {
+        r.map_err(|e| tracing::warn!("Failed to delete file of expired snapshot: {:?}", e))
+            .err()
+    })
+    .count();
+    tracing::debug!(
+        "Deleted {} of {} files of expired snapshots of table '{table_id}'",
+        orphaned.len() - n_failed,
+        orphaned.len()
+    );
+
+    Ok(())
+}
+
+/// Paths of the manifest lists, manifests, data and delete files referenced by `snapshots`.
+async fn referenced_files<'a>(
+    snapshots: impl IntoIterator<Item = &'a Snapshot>,
+    metadata: &TableMetadata,
+    file_io: &FileIO,
+) -> Result<HashSet<String>> {
+    let mut files = HashSet::new();
+    let mut manifests = HashSet::new();
+    for snapshot in snapshots {
+        files.insert(snapshot.manifest_list().to_string());
+        let manifest_list = snapshot
+            .load_manifest_list(file_io, metadata)
+            .await
+            .map_err(|e| io_error("Failed to read manifest list", e))?;
+        for manifest_file in manifest_list.entries() {
+            if !manifests.insert(manifest_file.manifest_path.clone()) {
+                continue;
+            }
+            let manifest = manifest_file
+                .load_manifest(file_io)
+                .await
+                .map_err(|e| io_error("Failed to read manifest", e))?;
+            files.extend(
+                manifest
+                    .entries()
+                    .iter()
+                    .map(|entry| entry.file_path().to_string()),
+            );
+        }
+    }
+    files.extend(manifests);
+    Ok(files)
+}
+
+fn io_error(message: &str, e: iceberg::Error) -> ErrorModel {
+    ErrorModel::internal(
+        format!("{message} while cleaning up expired snapshots: {e}"),
+        "SnapshotCleanupError",
+        Some(Box::new(e)),
+    )
+}
+
+#[derive(Debug)]
+pub struct SnapshotCleanupTask {
+    pub table_id: TableIdentUuid,
+    pub warehouse_ident: WarehouseIdent,
+    pub snapshots: Vec<Snapshot>,
+    pub task: Task,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotCleanupInput {
+    pub table_id: TableIdentUuid,
+    pub warehouse_ident: WarehouseIdent,
+    /// Snapshots removed from the metadata of the table.
+    pub snapshots: Vec<Snapshot>,
+    /// Earliest time at which files are deleted, `None` deletes them immediately.
+    pub cleanup_after: Option<DateTime<Utc>>,
+}
+
+impl SnapshotCleanupInput {
+    /// Idempotency key of the cleanup, so that expiring the same snapshots
+    /// again does not queue a second cleanup.
+    #[must_use]
+    pub fn idempotency_key(&self) -> Uuid {
+        let mut snapshot_ids = self
+            .snapshots
+            .iter()
+            .map(Snapshot::snapshot_id)
+            .collect::<Vec<_>>();
+        snapshot_ids.sort_unstable();
+        let bytes = snapshot_ids
+            .iter()
+            .flat_map(|id| id.to_be_bytes())
+            .collect::<Vec<_>>();
+        Uuid::new_v5(&*self.table_id, &bytes)
+    }
+}
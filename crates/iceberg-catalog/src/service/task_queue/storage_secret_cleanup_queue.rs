@@ -0,0 +1,136 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use tracing::Instrument;
+
+use crate::{
+    api::Result,
+    service::{
+        task_queue::{Task, TaskQueue},
+        Catalog, SecretIdent, SecretStore, Transaction,
+    },
+    WarehouseIdent,
+};
+
+pub type StorageSecretCleanupQueue = Arc<
+    dyn TaskQueue<Task = StorageSecretCleanupTask, Input = StorageSecretCleanupInput>
+        + Send
+        + Sync
+        + 'static,
+>;
+
+// TODO: concurrent workers
+pub async fn storage_secret_cleanup_task<C: Catalog, S: SecretStore>(
+    fetcher: StorageSecretCleanupQueue,
+    catalog_state: C::State,
+    secret_state: S,
+) {
+    loop {
+        // add some jitter to avoid syncing with other queues
+        tokio::time::sleep(fetcher.config().poll_interval + Duration::from_millis(23)).await;
+
+        let cleanup_task = match fetcher.pick_new_task().await {
+            Ok(cleanup_task) => cleanup_task,
+            Err(err) => {
+                tracing::error!("Failed to fetch storage secret cleanup: {:?}", err);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(cleanup_task) = cleanup_task else {
+            continue;
+        };
+
+        let span = tracing::debug_span!(
+            "storage_secret_cleanup",
+            secret_id = %cleanup_task.secret_id,
+            warehouse_id = %cleanup_task.warehouse_ident,
+            queue_name = %cleanup_task.task.queue_name,
+            task = ?cleanup_task.task,
+        );
+
+        instrumented_cleanup::<C, S>(
+            fetcher.clone(),
+            catalog_state.clone(),
+            &secret_state,
+            &cleanup_task,
+        )
+        .instrument(span.or_current())
+        .await;
+    }
+}
+
+pub(crate) async fn instrumented_cleanup<C: Catalog, S: SecretStore>(
+    fetcher: StorageSecretCleanupQueue,
+    catalog_state: C::State,
+    secret_state: &S,
+    cleanup_task: &StorageSecretCleanupTask,
+) {
+    match release_previous_storage_secret::<C, S>(
+        cleanup_task.warehouse_ident,
+        cleanup_task.secret_id,
+        catalog_state,
+        secret_state,
+    )
+    .await
+    {
+        Ok(()) => {
+            fetcher.retrying_record_success(&cleanup_task.task).await;
+            tracing::info!(
+                "Released previous storage secret {} of warehouse {}",
+                cleanup_task.secret_id,
+                cleanup_task.warehouse_ident
+            );
+        }
+        Err(err) => {
+            tracing::error!(
+                "Failed to release previous storage secret {} of warehouse {}: {}",
+                cleanup_task.secret_id,
+                cleanup_task.warehouse_ident,
+                err.error
+            );
+            fetcher
+                .retrying_record_failure(&cleanup_task.task, &err.error.to_string())
+                .await;
+        }
+    };
+}
+
+/// Stop retaining `secret_id` as previous storage secret of a warehouse, and
+/// delete it from the secret store unless the warehouse still references it.
+///
+/// # Errors
+/// If the warehouse cannot be updated or the secret cannot be deleted.
+pub(crate) async fn release_previous_storage_secret<C: Catalog, S: SecretStore>(
+    warehouse_id: WarehouseIdent,
+    secret_id: SecretIdent,
+    catalog_state: C::State,
+    secrets: &S,
+) -> Result<()> {
+    let mut transaction = C::Transaction::begin_write(catalog_state).await?;
+    let releasable =
+        C::release_previous_storage_secret(warehouse_id, secret_id, transaction.transaction())
+            .await?;
+    transaction.commit().await?;
+    if releasable {
+        secrets.delete_secret(&secret_id).await?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct StorageSecretCleanupTask {
+    pub warehouse_ident: WarehouseIdent,
+    pub secret_id: SecretIdent,
+    pub task: Task,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageSecretCleanupInput {
+    pub warehouse_ident: WarehouseIdent,
+    /// Secret replaced by a rotation of the storage credential of the warehouse.
+    pub secret_id: SecretIdent,
+    /// End of the overlap of the rotation, `None` releases the secret immediately.
+    pub cleanup_after: Option<DateTime<Utc>>,
+}
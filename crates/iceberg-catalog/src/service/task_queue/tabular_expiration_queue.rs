@@ -137,6 +137,12 @@ where
     };
 
     if matches!(expiration.deletion_kind, DeleteKind::Purge) {
+        let warehouse = C::require_warehouse(expiration.warehouse_ident, trx.transaction())
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get warehouse: {:?}", e);
+                e
+            })?;
         delete_queue
             .enqueue(TabularPurgeInput {
                 tabular_id: expiration.tabular_id,
@@ -144,6 +150,7 @@ where
                 tabular_type: expiration.tabular_type,
                 parent_id: Some(expiration.task.task_id),
                 tabular_location,
+                purge_after: TabularPurgeInput::purge_after(warehouse.purge_grace_period),
             })
             .await?;
     }
@@ -1,5 +1,8 @@
 use std::{sync::Arc, time::Duration};
 
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use iceberg::io::FileIO;
 use iceberg_ext::{
     catalog::rest::ErrorModel,
     configs::{Location, ParseFromStr},
@@ -9,14 +12,20 @@ use uuid::Uuid;
 
 use crate::{
     api::{management::v1::TabularType, Result},
-    catalog::{io::remove_all, maybe_get_secret},
+    catalog::{
+        io::{list_location, remove_all, IoError},
+        maybe_get_secret,
+    },
     service::{
-        task_queue::{Task, TaskQueue},
+        task_queue::{Task, TaskQueue, TaskStatus},
         Catalog, SecretStore, Transaction,
     },
     WarehouseIdent,
 };
 
+/// Maximum number of remaining objects listed in the error of a failed purge.
+const MAX_REPORTED_REMAINING_OBJECTS: usize = 10;
+
 pub type TabularPurgeQueue =
     Arc<dyn TaskQueue<Task = TabularPurgeTask, Input = TabularPurgeInput> + Send + Sync + 'static>;
 
@@ -109,7 +118,7 @@ where
     C: Catalog,
     S: SecretStore,
 {
-    let mut trx = C::Transaction::begin_write(catalog_state)
+    let mut trx = C::Transaction::begin_write(catalog_state.clone())
         .await
         .map_err(|e| {
             tracing::error!("Failed to start transaction: {:?}", e);
@@ -123,12 +132,47 @@ where
             e
         })?;
 
+    let tabular_location = Location::parse_value(tabular_location).map_err(|e| {
+        tracing::error!(
+            "Failed delete tabular - to parse location {}: {:?}",
+            tabular_location,
+            e
+        );
+        ErrorModel::internal(
+            "Failed to parse table location of deleted tabular.",
+            "ParseError",
+            Some(Box::new(e)),
+        )
+    })?;
+
+    // The data may have been re-registered during the grace period.
+    let location_in_use = C::is_tabular_location_in_use(&tabular_location, trx.transaction())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check if location is in use: {:?}", e);
+            e
+        })?;
+
     trx.commit().await.map_err(|e| {
         tracing::error!("Failed to commit transaction: {:?}", e);
         e
     })?;
 
-    let secret = maybe_get_secret(warehouse.storage_secret_id, secret_state)
+    if location_in_use {
+        tracing::info!(
+            "Skipping purge of '{tabular_id}', location '{tabular_location}' is used by another tabular."
+        );
+        return Ok(());
+    }
+    // Data below import prefixes is owned by the exporting side.
+    if warehouse.is_import_location(&tabular_location) {
+        tracing::info!(
+            "Skipping purge of '{tabular_id}', location '{tabular_location}' is below an import prefix."
+        );
+        return Ok(());
+    }
+
+    let secret = maybe_get_secret::<C, S>(warehouse.storage_secret_id, catalog_state, secret_state)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get secret: {:?}", e);
@@ -143,31 +187,63 @@ where
             e
         })?;
 
-    let tabular_location = Location::parse_value(tabular_location).map_err(|e| {
-        tracing::error!(
-            "Failed delete tabular - to parse location {}: {:?}",
-            tabular_location,
+    remove_location(&file_io, &tabular_location)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                ?e,
+                "Failed to purge '{tabular_id}' at location: '{tabular_location}'",
+            );
             e
-        );
-        ErrorModel::internal(
-            "Failed to parse table location of deleted tabular.",
-            "ParseError",
-            Some(Box::new(e)),
-        )
-    })?;
-    remove_all(&file_io, &tabular_location).await.map_err(|e| {
-        tracing::error!(
-            ?e,
-            "Failed to purge '{tabular_id}' at location: '{tabular_location}'",
-        );
-        ErrorModel::internal(
-            "Failed to remove location.",
-            "FileIOError",
-            Some(Box::new(e)),
-        )
-    })?;
+        })
+}
 
-    Ok(())
+/// Remove all objects at `location`.
+///
+/// # Errors
+/// Fails if objects could not be removed. The error lists up to
+/// [`MAX_REPORTED_REMAINING_OBJECTS`] of the objects left behind.
+pub(crate) async fn remove_location(file_io: &FileIO, location: &Location) -> Result<()> {
+    let Err(e) = remove_all(file_io, location).await else {
+        return Ok(());
+    };
+    // Report what is left so that partial failures can be inspected until the retry succeeds.
+    let message = match remaining_objects(file_io, location).await {
+        Ok((0, _)) => format!("Failed to remove location '{location}'."),
+        Ok((count, sample)) => format!(
+            "Failed to remove location '{location}'. {count} objects remain: {}{}",
+            sample.join(", "),
+            if count > sample.len() { ", ..." } else { "" }
+        ),
+        Err(list_err) => {
+            tracing::warn!(
+                ?list_err,
+                "Failed to list remaining objects of failed purge"
+            );
+            format!(
+                "Failed to remove location '{location}'. Remaining objects could not be listed."
+            )
+        }
+    };
+    Err(ErrorModel::internal(message, "FileIOError", Some(Box::new(e))).into())
+}
+
+/// Count the objects left at `location` and return up to
+/// [`MAX_REPORTED_REMAINING_OBJECTS`] of their paths.
+async fn remaining_objects(
+    file_io: &FileIO,
+    location: &Location,
+) -> std::result::Result<(usize, Vec<String>), IoError> {
+    let mut pages = list_location(file_io, location, None).await?;
+    let mut count = 0;
+    let mut sample = Vec::new();
+    while let Some(page) = pages.next().await {
+        let page = page?;
+        count += page.len();
+        let missing = MAX_REPORTED_REMAINING_OBJECTS.saturating_sub(sample.len());
+        sample.extend(page.into_iter().take(missing));
+    }
+    Ok((count, sample))
 }
 
 #[derive(Debug)]
@@ -186,4 +262,160 @@ pub struct TabularPurgeInput {
     pub tabular_type: TabularType,
     pub parent_id: Option<Uuid>,
     pub tabular_location: String,
+    /// Earliest time at which the storage is deleted, `None` deletes it immediately.
+    pub purge_after: Option<DateTime<Utc>>,
+}
+
+impl TabularPurgeInput {
+    /// Earliest time at which a purge queued now may delete storage,
+    /// given the `purge_grace_period` of the warehouse.
+    #[must_use]
+    pub fn purge_after(purge_grace_period: Option<chrono::Duration>) -> Option<DateTime<Utc>> {
+        purge_grace_period.map(|grace_period| Utc::now() + grace_period)
+    }
+}
+
+/// A purge that has been queued but has not completed yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingPurge {
+    pub task_id: Uuid,
+    pub tabular_id: Uuid,
+    pub tabular_type: TabularType,
+    pub tabular_location: String,
+    pub status: TaskStatus,
+    pub attempt: i32,
+    /// Earliest time at which the storage is deleted.
+    pub purge_after: Option<DateTime<Utc>>,
+    /// Details of the last failed attempt, including the objects that remained.
+    pub last_error: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use iceberg::TableIdent;
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::{
+        api::{
+            iceberg::v1::{
+                tables::TablesService as _, DataAccess, DropParams, NamespaceParameters, Prefix,
+                TableParameters,
+            },
+            management::v1::{
+                warehouse::{
+                    Service as _, TabularDeleteProfile, UpdateWarehousePurgeGracePeriodRequest,
+                },
+                ApiServer,
+            },
+        },
+        catalog::{tables::test::create_request, CatalogServer},
+        implementations::postgres::{PostgresCatalog, ReadWrite},
+        request_metadata::RequestMetadata,
+        service::{authz::AllowAllAuthorizer, task_queue::TaskQueueConfig},
+    };
+
+    #[sqlx::test]
+    async fn test_purge_waits_for_grace_period(pool: PgPool) {
+        let storage_profile = crate::catalog::test::test_io_profile();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            storage_profile.clone(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        ApiServer::update_warehouse_purge_grace_period(
+            warehouse.warehouse_id,
+            UpdateWarehousePurgeGracePeriodRequest {
+                purge_grace_period_seconds: Some(2),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        let table = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: ns.namespace.clone(),
+            },
+            create_request(Some("tab1".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+        let metadata_location = table.metadata_location.unwrap();
+        let file_io = storage_profile.file_io(None).unwrap();
+        assert!(file_io.exists(&metadata_location).await.unwrap());
+
+        CatalogServer::drop_table(
+            TableParameters {
+                prefix,
+                table: TableIdent {
+                    namespace: ns.namespace,
+                    name: "tab1".to_string(),
+                },
+            },
+            DropParams {
+                purge_requested: Some(true),
+            },
+            ctx.clone(),
+            RequestMetadata::new_unauthenticated(),
+        )
+        .await
+        .unwrap();
+
+        let list_pending = || {
+            ApiServer::list_pending_purges(
+                warehouse.warehouse_id,
+                ctx.clone(),
+                RequestMetadata::new_unauthenticated(),
+            )
+        };
+        let pending = list_pending().await.unwrap().pending_purges;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].tabular_id, table.metadata.uuid());
+        assert!(pending[0].purge_after.is_some());
+
+        let queue: TabularPurgeQueue = Arc::new(
+            crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(
+                ReadWrite::from_pools(pool.clone(), pool),
+                TaskQueueConfig::default(),
+            )
+            .unwrap(),
+        );
+
+        // The data survives until the grace period elapsed
+        assert!(queue.pick_new_task().await.unwrap().is_none());
+        assert!(file_io.exists(&metadata_location).await.unwrap());
+
+        tokio::time::sleep(Duration::from_millis(2100)).await;
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("Purge should be picked up after the grace period");
+        instrumented_purge::<_, PostgresCatalog>(
+            queue.clone(),
+            ctx.v1_state.catalog.clone(),
+            &ctx.v1_state.secrets,
+            &task,
+        )
+        .await;
+
+        assert!(!file_io.exists(&metadata_location).await.unwrap());
+        assert!(list_pending().await.unwrap().pending_purges.is_empty());
+    }
 }
@@ -0,0 +1,446 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, RwLock},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use iceberg_ext::catalog::rest::ErrorModel;
+use limes::{Authentication, Subject};
+use serde::{Deserialize, Serialize};
+
+use crate::{config::TokenIntrospectionConfig, CONFIG};
+
+static INTROSPECTION_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
+/// Default tolerance for clock skew applied to the `nbf` and `exp` of tokens.
+pub const DEFAULT_TOKEN_CLOCK_SKEW_LEEWAY: Duration = Duration::from_secs(60);
+/// Largest configurable tolerance for clock skew. Larger values are capped,
+/// so that expired tokens cannot be accepted for long.
+pub const MAX_TOKEN_CLOCK_SKEW_LEEWAY: Duration = Duration::from_secs(300);
+
+/// Whether `token` is opaque, i.e. not a JWT that can be validated locally.
+#[must_use]
+pub fn is_opaque_token(token: &str) -> bool {
+    token.split('.').count() != 3
+}
+
+/// Validates opaque tokens via OAuth 2.0 Token Introspection (RFC 7662).
+///
+/// Providers are queried in order of their IdP id until one reports the token
+/// as active. Active tokens are cached until their `exp`, plus the leeway
+/// for clock skew.
+#[derive(Debug, Clone)]
+pub struct IntrospectionVerifier {
+    providers: Arc<Vec<(String, TokenIntrospectionConfig)>>,
+    required_scope: Option<String>,
+    leeway: chrono::Duration,
+    cache: Arc<RwLock<HashMap<String, CachedAuthentication>>>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedAuthentication {
+    authentication: Authentication,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct IntrospectionRequest<'a> {
+    token: &'a str,
+    token_type_hint: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct IntrospectionResponse {
+    active: bool,
+    sub: Option<String>,
+    scope: Option<String>,
+    exp: Option<i64>,
+    nbf: Option<i64>,
+    iss: Option<String>,
+    username: Option<String>,
+    email: Option<String>,
+}
+
+impl IntrospectionVerifier {
+    /// Create a verifier for the providers configured in `token_introspection`.
+    /// Returns `None` if no provider is configured.
+    #[must_use]
+    pub fn from_config() -> Option<Self> {
+        (!CONFIG.token_introspection.is_empty()).then(|| {
+            Self::new(
+                CONFIG
+                    .token_introspection
+                    .iter()
+                    .map(|(idp_id, config)| (idp_id.clone(), config.clone()))
+                    .collect(),
+                CONFIG.openid_scope.clone(),
+            )
+            .with_leeway(CONFIG.token_clock_skew_leeway)
+        })
+    }
+
+    #[must_use]
+    pub fn new(
+        providers: Vec<(String, TokenIntrospectionConfig)>,
+        required_scope: Option<String>,
+    ) -> Self {
+        Self {
+            providers: Arc::new(providers),
+            required_scope,
+            leeway: leeway_to_chrono(DEFAULT_TOKEN_CLOCK_SKEW_LEEWAY),
+            cache: Arc::default(),
+        }
+    }
+
+    /// Accept tokens up to `leeway` before their `nbf` and after their `exp`.
+    /// The leeway is capped at [`MAX_TOKEN_CLOCK_SKEW_LEEWAY`].
+    #[must_use]
+    pub fn with_leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway_to_chrono(leeway);
+        self
+    }
+
+    /// Authenticate an opaque token.
+    ///
+    /// # Errors
+    /// Fails with `AuthenticationRequired` if no provider reports the token as
+    /// active or if introspection fails.
+    pub async fn authenticate(&self, token: &str) -> Result<Authentication, ErrorModel> {
+        let now = Utc::now();
+        if let Some(cached) = self
+            .cache
+            .read()
+            .map_err(|_| lock_poisoned())?
+            .get(token)
+            .filter(|cached| cached.expires_at > now)
+        {
+            return Ok(cached.authentication.clone());
+        }
+
+        for (idp_id, provider) in &*self.providers {
+            let Some((authentication, expires_at)) =
+                self.introspect(idp_id, provider, token).await?
+            else {
+                continue;
+            };
+
+            if let Some(expires_at) = expires_at.map(|expires_at| expires_at + self.leeway) {
+                let mut cache = self.cache.write().map_err(|_| lock_poisoned())?;
+                cache.retain(|_, cached| cached.expires_at > now);
+                cache.insert(
+                    token.to_string(),
+                    CachedAuthentication {
+                        authentication: authentication.clone(),
+                        expires_at,
+                    },
+                );
+            }
+            return Ok(authentication);
+        }
+
+        Err(authentication_required("Token is not active", None))
+    }
+
+    /// Returns `None` if the provider does not consider the token active and valid.
+    async fn introspect(
+        &self,
+        idp_id: &str,
+        provider: &TokenIntrospectionConfig,
+        token: &str,
+    ) -> Result<Option<(Authentication, Option<DateTime<Utc>>)>, ErrorModel> {
+        let claims = INTROSPECTION_CLIENT
+            .post(provider.introspection_endpoint.clone())
+            .basic_auth(&provider.client_id, Some(&provider.client_secret))
+            .form(&IntrospectionRequest {
+                token,
+                token_type_hint: "access_token",
+            })
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| {
+                tracing::warn!(
+                    "Failed to introspect token at {}: {e}",
+                    provider.introspection_endpoint
+                );
+                authentication_required("Token introspection failed", Some(Box::new(e)))
+            })?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| {
+                authentication_required(
+                    "Failed to parse token introspection response",
+                    Some(Box::new(e)),
+                )
+            })?;
+        let response =
+            serde_json::from_value::<IntrospectionResponse>(claims.clone()).map_err(|e| {
+                authentication_required(
+                    "Failed to parse token introspection response",
+                    Some(Box::new(e)),
+                )
+            })?;
+
+        if !response.active {
+            return Ok(None);
+        }
+        if provider
+            .issuer
+            .as_ref()
+            .is_some_and(|issuer| response.iss.as_ref() != Some(issuer))
+        {
+            tracing::debug!("Ignoring introspected token of IdP {idp_id} with unexpected issuer");
+            return Ok(None);
+        }
+        let expires_at = response
+            .exp
+            .map(|exp| {
+                DateTime::from_timestamp(exp, 0)
+                    .ok_or_else(|| authentication_required("Invalid token expiration", None))
+            })
+            .transpose()?;
+        let not_before = response
+            .nbf
+            .map(|nbf| {
+                DateTime::from_timestamp(nbf, 0)
+                    .ok_or_else(|| authentication_required("Invalid token start of validity", None))
+            })
+            .transpose()?;
+        let now = Utc::now();
+        if expires_at.is_some_and(|expires_at| expires_at + self.leeway <= now) {
+            tracing::debug!("Ignoring introspected token of IdP {idp_id} that has expired");
+            return Ok(None);
+        }
+        if not_before.is_some_and(|not_before| not_before - self.leeway > now) {
+            tracing::debug!("Ignoring introspected token of IdP {idp_id} that is not yet valid");
+            return Ok(None);
+        }
+        if let Some(required_scope) = &self.required_scope {
+            if !response
+                .scope
+                .as_deref()
+                .unwrap_or_default()
+                .split_whitespace()
+                .any(|scope| scope == required_scope)
+            {
+                return Err(authentication_required(
+                    format!("Token is missing required scope '{required_scope}'"),
+                    None,
+                ));
+            }
+        }
+        let Some(sub) = response.sub else {
+            return Err(authentication_required(
+                "Token introspection response contains no subject",
+                None,
+            ));
+        };
+
+        let authentication = Authentication::builder()
+            .token_header(None)
+            .claims(claims)
+            .subject(Subject::new(Some(idp_id.to_string()), sub))
+            .name(response.username)
+            .email(response.email)
+            .principal_type(None)
+            .build();
+
+        Ok(Some((authentication, expires_at)))
+    }
+}
+
+fn leeway_to_chrono(leeway: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(leeway.min(MAX_TOKEN_CLOCK_SKEW_LEEWAY)).unwrap_or_default()
+}
+
+fn authentication_required(
+    message: impl Into<String>,
+    source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+) -> ErrorModel {
+    ErrorModel::unauthorized(message, "AuthenticationRequired", source)
+}
+
+fn lock_poisoned() -> ErrorModel {
+    ErrorModel::internal(
+        "Token introspection cache lock poisoned",
+        "LockPoisoned",
+        None,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{extract::State, routing::post, Json, Router};
+    use http::{header, HeaderMap, StatusCode};
+
+    use super::*;
+
+    async fn mock_introspection_endpoint() -> (url::Url, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router =
+            Router::new()
+                .route(
+                    "/introspect",
+                    post(
+                        |State(calls): State<Arc<AtomicUsize>>,
+                         headers: HeaderMap,
+                         body: String| async move {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            // base64("lakekeeper:secret")
+                            assert_eq!(
+                                headers.get(header::AUTHORIZATION).unwrap(),
+                                "Basic bGFrZWtlZXBlcjpzZWNyZXQ="
+                            );
+                            if body.contains("token=opaque-token") {
+                                Json(serde_json::json!({
+                                    "active": true,
+                                    "sub": "service-account-1",
+                                    "scope": "openid lakekeeper",
+                                    "iss": "https://idp.example.com",
+                                    "exp": Utc::now().timestamp() + 3600,
+                                    "username": "Service Account",
+                                }))
+                            } else if let Some((exp, nbf)) = [
+                                ("token=expired-token", -30, None),
+                                ("token=long-expired-token", -120, None),
+                                ("token=future-token", 3600, Some(30)),
+                            ]
+                            .into_iter()
+                            .find(|(token, _, _)| body.contains(token))
+                            .map(|(_, exp, nbf)| (exp, nbf))
+                            {
+                                // Clock of the IdP is behind, so that it still considers the token active
+                                let now = Utc::now().timestamp();
+                                Json(serde_json::json!({
+                                    "active": true,
+                                    "sub": "service-account-1",
+                                    "iss": "https://idp.example.com",
+                                    "exp": now + exp,
+                                    "nbf": nbf.map(|nbf| now + nbf),
+                                }))
+                            } else {
+                                Json(serde_json::json!({ "active": false }))
+                            }
+                        },
+                    ),
+                )
+                .with_state(calls.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        (
+            format!("http://{address}/introspect").parse().unwrap(),
+            calls,
+        )
+    }
+
+    fn verifier(
+        endpoint: url::Url,
+        issuer: &str,
+        required_scope: Option<&str>,
+    ) -> IntrospectionVerifier {
+        IntrospectionVerifier::new(
+            vec![(
+                "corp".to_string(),
+                TokenIntrospectionConfig {
+                    introspection_endpoint: endpoint,
+                    issuer: Some(issuer.to_string()),
+                    client_id: "lakekeeper".to_string(),
+                    client_secret: "secret".to_string(),
+                },
+            )],
+            required_scope.map(ToString::to_string),
+        )
+    }
+
+    #[test]
+    fn test_is_opaque_token() {
+        assert!(is_opaque_token("opaque-token"));
+        assert!(!is_opaque_token("header.payload.signature"));
+    }
+
+    #[tokio::test]
+    async fn test_opaque_token_is_introspected_and_cached() {
+        let (endpoint, calls) = mock_introspection_endpoint().await;
+        let verifier = verifier(endpoint, "https://idp.example.com", Some("lakekeeper"));
+
+        for _ in 0..2 {
+            let authentication = verifier.authenticate("opaque-token").await.unwrap();
+            assert_eq!(
+                authentication.subject(),
+                &Subject::new(Some("corp".to_string()), "service-account-1".to_string())
+            );
+            assert_eq!(authentication.claims()["scope"], "openid lakekeeper");
+            assert_eq!(authentication.full_name(), Some("Service Account"));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let err = verifier.authenticate("revoked-token").await.unwrap_err();
+        assert_eq!(err.code, StatusCode::UNAUTHORIZED);
+        assert_eq!(err.r#type, "AuthenticationRequired");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_introspection_validates_issuer_and_scope() {
+        let (endpoint, _) = mock_introspection_endpoint().await;
+
+        let err = verifier(endpoint.clone(), "https://other.example.com", None)
+            .authenticate("opaque-token")
+            .await
+            .unwrap_err();
+        assert_eq!(err.r#type, "AuthenticationRequired");
+
+        let err = verifier(endpoint, "https://idp.example.com", Some("admin"))
+            .authenticate("opaque-token")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, StatusCode::UNAUTHORIZED);
+        assert_eq!(err.r#type, "AuthenticationRequired");
+    }
+
+    #[tokio::test]
+    async fn test_token_validity_includes_clock_skew_leeway() {
+        let (endpoint, _) = mock_introspection_endpoint().await;
+
+        // Expired 30 seconds ago, within the default leeway
+        let default_leeway = verifier(endpoint.clone(), "https://idp.example.com", None);
+        default_leeway.authenticate("expired-token").await.unwrap();
+        // Valid in 30 seconds
+        default_leeway.authenticate("future-token").await.unwrap();
+        // Expired 120 seconds ago, beyond the leeway
+        let err = default_leeway
+            .authenticate("long-expired-token")
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, StatusCode::UNAUTHORIZED);
+        assert_eq!(err.r#type, "AuthenticationRequired");
+
+        let no_leeway =
+            verifier(endpoint, "https://idp.example.com", None).with_leeway(Duration::ZERO);
+        for token in ["expired-token", "future-token", "long-expired-token"] {
+            let err = no_leeway.authenticate(token).await.unwrap_err();
+            assert_eq!(err.r#type, "AuthenticationRequired");
+        }
+
+        let capped = no_leeway.with_leeway(Duration::from_secs(3600));
+        assert_eq!(capped.leeway, chrono::Duration::seconds(300));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_requires_authentication() {
+        let verifier = verifier(
+            "http://127.0.0.1:1/introspect".parse().unwrap(),
+            "https://idp.example.com",
+            None,
+        );
+        let err = verifier.authenticate("opaque-token").await.unwrap_err();
+        assert_eq!(err.code, StatusCode::UNAUTHORIZED);
+        assert_eq!(err.r#type, "AuthenticationRequired");
+    }
+}
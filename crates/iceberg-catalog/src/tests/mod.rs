@@ -1,6 +1,6 @@
 mod stats;
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use iceberg::{NamespaceIdent, TableIdent};
 use iceberg_ext::catalog::rest::{
@@ -29,13 +29,18 @@ use crate::{
     },
     catalog::CatalogServer,
     implementations::postgres::{
-        task_queues::{TabularExpirationQueue, TabularPurgeQueue},
+        task_queues::{
+            DeletedWarehousePurgeQueue, SnapshotCleanupQueue, StorageSecretCleanupQueue,
+            TabularExpirationQueue, TabularPurgeQueue,
+        },
         CatalogState, PostgresCatalog, ReadWrite, SecretsState,
     },
     request_metadata::RequestMetadata,
     service::{
         authz::Authorizer,
+        commit_observer::CommitObservers,
         contract_verification::ContractVerifiers,
+        create_request_mutator::CreateRequestMutators,
         event_publisher::CloudEventsPublisher,
         storage::{
             S3Credential, S3Flavor, S3Profile, StorageCredential, StorageProfile, TestProfile,
@@ -79,6 +84,7 @@ pub(crate) fn minio_profile() -> (StorageProfile, StorageCredential) {
         flavor: S3Flavor::S3Compat,
         sts_enabled: true,
         allow_alternative_protocols: None,
+        credential_provider: None,
     }
     .into();
 
@@ -203,6 +209,7 @@ pub(crate) async fn setup<T: Authorizer>(
             storage_profile,
             storage_credential,
             delete_profile,
+            annotations: HashMap::new(),
         },
         api_context.clone(),
         metadata,
@@ -227,6 +234,8 @@ pub(crate) fn get_api_context<T: Authorizer>(
             secrets: SecretsState::from_pools(pool.clone(), pool.clone()),
             publisher: CloudEventsPublisher::new(tx.clone()),
             contract_verifiers: ContractVerifiers::new(vec![]),
+            commit_observers: CommitObservers::new(vec![]),
+            create_request_mutators: CreateRequestMutators::new(vec![]),
             queues: TaskQueues::new(
                 Arc::new(
                     TabularExpirationQueue::from_config(
@@ -242,6 +251,27 @@ pub(crate) fn get_api_context<T: Authorizer>(
                     )
                     .unwrap(),
                 ),
+                Arc::new(
+                    SnapshotCleanupQueue::from_config(
+                        ReadWrite::from_pools(pool.clone(), pool.clone()),
+                        q_config.clone(),
+                    )
+                    .unwrap(),
+                ),
+                Arc::new(
+                    StorageSecretCleanupQueue::from_config(
+                        ReadWrite::from_pools(pool.clone(), pool.clone()),
+                        q_config.clone(),
+                    )
+                    .unwrap(),
+                ),
+                Arc::new(
+                    DeletedWarehousePurgeQueue::from_config(
+                        ReadWrite::from_pools(pool.clone(), pool.clone()),
+                        q_config.clone(),
+                    )
+                    .unwrap(),
+                ),
             ),
         },
     }
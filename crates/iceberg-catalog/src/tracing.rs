@@ -1,3 +1,4 @@
+use axum::extract::MatchedPath;
 use http::Request;
 use tower_http::{
     request_id::{MakeRequestId, RequestId},
@@ -6,9 +7,11 @@ use tower_http::{
 use tracing::{Level, Span};
 use uuid::Uuid;
 
-use crate::api::X_REQUEST_ID_HEADER;
+use crate::{api::load_shedding::warehouse_id_from_path, CONFIG};
 
-/// A `MakeSpan` implementation that attaches the `request_id` to the span.
+/// A `MakeSpan` implementation that attaches the `request_id`, the `operation` and
+/// the `warehouse_id` to the span. `principal_hash` is recorded once the request
+/// is authenticated.
 #[derive(Debug, Clone)]
 pub(crate) struct RestMakeSpan {
     level: Level,
@@ -27,6 +30,12 @@ impl RestMakeSpan {
 /// does.
 impl<B> MakeSpan<B> for RestMakeSpan {
     fn make_span(&mut self, request: &Request<B>) -> Span {
+        let path = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map_or_else(|| request.uri().path(), MatchedPath::as_str);
+        let operation = format!("{} {path}", request.method());
+        let warehouse_id = warehouse_id_from_path(request.uri().path());
         // This ugly macro is needed, unfortunately, because `tracing::span!`
         // required the level argument to be static. Meaning we can't just pass
         // `self.level`.
@@ -38,11 +47,14 @@ impl<B> MakeSpan<B> for RestMakeSpan {
                         method = %request.method(),
                         uri = %request.uri(),
                         version = ?request.version(),
+                        operation = %operation,
+                        warehouse_id = warehouse_id.map(tracing::field::display),
                         request_id = %request
                                     .headers()
-                                    .get(X_REQUEST_ID_HEADER)
+                                    .get(&CONFIG.request_id_header)
                                     .and_then(|v| v.to_str().ok())
-                                    .unwrap_or("MISSING-REQUEST-ID")
+                                    .unwrap_or("MISSING-REQUEST-ID"),
+                        principal_hash = tracing::field::Empty,
                     )
             }
         }
@@ -19,16 +19,21 @@ pub mod rest {
         OAuthTokenExchangeRequest, OAuthTokenRequest, OAuthTokenResponse, OAuthTokenType,
     };
 
+    #[cfg(feature = "axum")]
+    pub mod encoding;
+
     mod error;
     #[cfg(feature = "axum")]
     pub(crate) use error::impl_into_response;
-    pub use error::{Error, ErrorModel, IcebergErrorResponse};
+    pub use error::{
+        Error, ErrorModel, IcebergErrorResponse, ProblemDetails, PROBLEM_JSON_CONTENT_TYPE,
+    };
 
     mod table;
     pub use table::{
         CommitTableRequest, CommitTableResponse, CommitTransactionRequest, CreateTableRequest,
         ListTablesResponse, LoadCredentialsResponse, LoadTableResult, RegisterTableRequest,
-        RenameTableRequest, StorageCredential,
+        RenameTableRequest, StorageCredential, TableDeprecation,
     };
 
     mod view;
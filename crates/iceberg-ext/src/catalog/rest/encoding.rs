@@ -0,0 +1,133 @@
+use std::future::Future;
+
+use axum::{
+    extract::{FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{ErrorModel, IcebergErrorResponse};
+
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Encoding of response bodies negotiated with the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseEncoding {
+    #[default]
+    Json,
+    MsgPack,
+}
+
+tokio::task_local! {
+    static RESPONSE_ENCODING: ResponseEncoding;
+}
+
+/// Run `future` with response bodies encoded as `encoding`.
+pub async fn with_response_encoding<F: Future>(encoding: ResponseEncoding, future: F) -> F::Output {
+    RESPONSE_ENCODING.scope(encoding, future).await
+}
+
+/// Serialize `body` in the encoding negotiated for the current request.
+/// Outside of [`with_response_encoding`], bodies are encoded as JSON.
+#[must_use]
+pub fn encode_response<T: Serialize>(body: T) -> Response {
+    let encoding = RESPONSE_ENCODING
+        .try_with(|encoding| *encoding)
+        .unwrap_or_default();
+    match encoding {
+        ResponseEncoding::Json => axum::Json(body).into_response(),
+        ResponseEncoding::MsgPack => match rmp_serde::to_vec_named(&body) {
+            Ok(bytes) => (
+                [(
+                    http::header::CONTENT_TYPE,
+                    http::HeaderValue::from_static(MSGPACK_CONTENT_TYPE),
+                )],
+                bytes,
+            )
+                .into_response(),
+            Err(e) => {
+                // Keep the response rather than failing the request.
+                tracing::warn!("Failed to encode response as MessagePack, returning JSON: {e}");
+                axum::Json(body).into_response()
+            }
+        },
+    }
+}
+
+/// Drop-in replacement for [`axum::Json`] that also accepts request bodies sent
+/// with `Content-Type: application/msgpack` and encodes responses as negotiated
+/// via [`with_response_encoding`]. Both encodings use the same serde representation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !has_content_type(request.headers(), MSGPACK_CONTENT_TYPE) {
+            return axum::Json::<T>::from_request(request, state)
+                .await
+                .map(|axum::Json(value)| Json(value))
+                .map_err(IntoResponse::into_response);
+        }
+
+        // Bounded by the same body limit as JSON bodies
+        let bytes = axum::body::Bytes::from_request(request, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        rmp_serde::from_slice(&bytes).map(Json).map_err(|e| {
+            IcebergErrorResponse::from(ErrorModel::bad_request(
+                format!("Failed to decode MessagePack request body: {e}"),
+                "InvalidMsgPackBody",
+                Some(Box::new(e)),
+            ))
+            .into_response()
+        })
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        encode_response(self.0)
+    }
+}
+
+fn has_content_type(headers: &http::HeaderMap, content_type: &str) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|hv| hv.to_str().ok())
+        .and_then(|hv| hv.split(';').next())
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(content_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content_type(response: &axum::response::Response) -> &str {
+        response
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_encode_response() {
+        let body = serde_json::json!({"a": 1});
+        assert_eq!(content_type(&encode_response(&body)), JSON_CONTENT_TYPE);
+        let response =
+            with_response_encoding(ResponseEncoding::MsgPack, async { encode_response(&body) })
+                .await;
+        assert_eq!(content_type(&response), MSGPACK_CONTENT_TYPE);
+        let response =
+            with_response_encoding(ResponseEncoding::Json, async { encode_response(&body) }).await;
+        assert_eq!(content_type(&response), JSON_CONTENT_TYPE);
+    }
+}
@@ -13,7 +13,7 @@ macro_rules! impl_into_response {
     ($type:ty) => {
         impl axum::response::IntoResponse for $type {
             fn into_response(self) -> axum::http::Response<axum::body::Body> {
-                axum::Json(self).into_response()
+                $crate::catalog::rest::encoding::encode_response(self)
             }
         }
     };
@@ -63,6 +63,49 @@ pub struct IcebergErrorResponse {
     pub error: ErrorModel,
 }
 
+/// Media type of problem details as defined in RFC 9457.
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+const PROBLEM_TYPE_URI_PREFIX: &str = "urn:iceberg:error:";
+
+/// Problem details (RFC 9457) representation of an [`ErrorModel`],
+/// for clients that prefer `application/problem+json` over the Iceberg error format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    /// URI of the error type: `urn:iceberg:error:<ErrorModel.type>`
+    pub r#type: String,
+    /// Type of the originating [`ErrorModel`]
+    pub title: String,
+    /// HTTP response code
+    pub status: u16,
+    /// Human-readable error message
+    pub detail: String,
+    /// Identifies this occurrence of the error, i.e. `urn:uuid:<error-id>`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub instance: Option<String>,
+}
+
+impl ProblemDetails {
+    /// Type of the originating [`ErrorModel`].
+    #[must_use]
+    pub fn error_type(&self) -> &str {
+        self.r#type
+            .strip_prefix(PROBLEM_TYPE_URI_PREFIX)
+            .unwrap_or(&self.title)
+    }
+}
+
+impl From<&ErrorModel> for ProblemDetails {
+    fn from(value: &ErrorModel) -> Self {
+        Self {
+            r#type: format!("{PROBLEM_TYPE_URI_PREFIX}{}", value.r#type),
+            title: value.r#type.clone(),
+            status: value.code,
+            detail: value.message.clone(),
+            instance: None,
+        }
+    }
+}
+
 /// JSON error payload returned in a response with further details on the error
 #[derive(Default, Debug, TypedBuilder, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ErrorModel {
@@ -251,19 +294,38 @@ impl axum::response::IntoResponse for IcebergErrorResponse {
         let error_id = uuid::Uuid::now_v7();
         tracing::info!(%error_id, %stack_s, ?details, %message, %r#type, %code, "Error response");
 
-        let mut response = axum::Json(IcebergErrorResponse {
-            error: ErrorModel {
-                message,
-                r#type,
-                code,
-                source: None,
-                stack: vec![error_id.to_string()],
-            },
-        })
-        .into_response();
+        let error = ErrorModel {
+            message,
+            r#type,
+            code,
+            source: None,
+            stack: vec![error_id.to_string()],
+        };
+        // Allows middlewares to render the error differently, e.g. as problem+json
+        let problem_details = ProblemDetails {
+            instance: Some(format!("urn:uuid:{error_id}")),
+            ..ProblemDetails::from(&error)
+        };
+        let mut response = super::encoding::encode_response(IcebergErrorResponse { error });
 
         *response.status_mut() = axum::http::StatusCode::from_u16(code)
             .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+        response.extensions_mut().insert(problem_details);
+        response
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for ProblemDetails {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        let status = axum::http::StatusCode::from_u16(self.status)
+            .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = axum::Json(self).into_response();
+        *response.status_mut() = status;
+        response.headers_mut().insert(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static(PROBLEM_JSON_CONTENT_TYPE),
+        );
         response
     }
 }
@@ -312,4 +374,20 @@ mod tests {
         let resp: IcebergErrorResponse = serde_json::from_value(json.clone()).unwrap();
         assert_eq!(serde_json::to_value(resp).unwrap(), json);
     }
+
+    #[test]
+    fn test_problem_details_from_error_model() {
+        let error = ErrorModel::not_found("Table does not exist", "NoSuchTableException", None);
+        let problem = ProblemDetails::from(&error);
+        assert_eq!(problem.error_type(), "NoSuchTableException");
+        assert_eq!(
+            serde_json::to_value(problem).unwrap(),
+            serde_json::json!({
+                "type": "urn:iceberg:error:NoSuchTableException",
+                "title": "NoSuchTableException",
+                "status": 404,
+                "detail": "Table does not exist"
+            })
+        );
+    }
 }
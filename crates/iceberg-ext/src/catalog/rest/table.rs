@@ -25,6 +25,23 @@ pub struct LoadTableResult {
     pub metadata: TableMetadata,
     pub config: Option<std::collections::HashMap<String, String>>,
     pub storage_credentials: Option<Vec<StorageCredential>>,
+    /// Lakekeeper extension: Set if the table is deprecated and planned to be retired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<TableDeprecation>,
+}
+
+/// Lakekeeper extension: Notice to consumers of a table that is planned to be retired.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableDeprecation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Point in time from which on the table is retired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether commits to the table are rejected once the sunset has passed.
+    #[serde(default)]
+    pub block_writes_after_sunset: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -44,6 +61,10 @@ pub struct CreateTableRequest {
 pub struct RegisterTableRequest {
     pub name: String,
     pub metadata_location: String,
+    /// Lakekeeper extension: Register the table with a new UUID if the UUID of the
+    /// metadata is already used by another table or view, instead of rejecting it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub assign_new_uuid: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
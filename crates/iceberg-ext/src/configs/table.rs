@@ -62,6 +62,7 @@ pub mod s3 {
             AccessKeyId, String, "s3.access-key-id", "s3_access_key_id";
             SecretAccessKey, String, "s3.secret-access-key", "s3_secret_access_key";
             SessionToken, String, "s3.session-token", "s3_session_token";
+            SessionTokenExpiresAtMs, String, "s3.session-token-expires-at-ms", "s3_session_token_expires_at_ms";
             RemoteSigningEnabled, bool, "s3.remote-signing-enabled", "s3_remote_signing_enabled";
             Signer, String, "s3.signer", "s3_signer";
             SignerUri, String, "s3.signer.uri", "s3_signer_uri";